@@ -14,7 +14,9 @@ use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 
+use pallet_clock_rpc::{Clock, ClockApiServer};
 use pallet_portal_rpc::{Portal, PortalApiServer};
+use pallet_rewards_rpc::{Rewards, RewardsApiServer};
 use pallet_xdns_rpc::{Xdns, XdnsApiServer};
 
 use sc_consensus_grandpa::FinalityProofProvider;
@@ -57,6 +59,8 @@ where
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: pallet_xdns_rpc::XdnsRuntimeApi<Block, AccountId>,
+    C::Api: pallet_clock_rpc::ClockRuntimeApi<Block, BlockNumber>,
+    C::Api: pallet_rewards_rpc::RewardsRuntimeApi<Block, AccountId, Balance>,
     C::Api: pallet_portal_rpc::PortalRuntimeApi<Block, AccountId, Balance, Hash>,
     C::Api: sp_consensus_grandpa::GrandpaApi<Block>,
     C::Api: BlockBuilder<Block>,
@@ -79,6 +83,8 @@ where
     module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
     module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
     module.merge(Xdns::new(client.clone()).into_rpc())?;
+    module.merge(Clock::new(client.clone()).into_rpc())?;
+    module.merge(Rewards::new(client.clone()).into_rpc())?;
     module.merge(Portal::new(client).into_rpc())?;
 
     let GrandpaDeps {
@@ -317,6 +317,7 @@ pub fn run() -> Result<()> {
                     collator_options,
                     id,
                     hwbench,
+                    cli.eth_api,
                 )
                 .await
                 .map(|r| r.0)
@@ -1,6 +1,7 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
 // std
+use crate::cli::EthApiOptions;
 use cumulus_client_cli::CollatorOptions;
 use std::{collections::BTreeMap, sync::Arc, time::Duration};
 // Local Runtime Types
@@ -177,6 +178,7 @@ async fn start_node_impl(
     collator_options: CollatorOptions,
     para_id: ParaId,
     hwbench: Option<sc_sysinfo::HwBench>,
+    eth_api: EthApiOptions,
 ) -> sc_service::error::Result<(TaskManager, Arc<ParachainClient>)> {
     let parachain_config = prepare_node_config(parachain_config);
 
@@ -312,6 +314,7 @@ async fn start_node_impl(
         let transaction_pool = transaction_pool.clone();
         let sync = sync_service.clone();
         let pubsub_notification_sinks = pubsub_notification_sinks.clone();
+        let debug_permits = fc_rpc::DebugPermits::new(eth_api.ethapi_max_permits as usize);
 
         Box::new(move |deny_unsafe, subscription| {
             let deps = crate::rpc::FullDeps {
@@ -330,6 +333,8 @@ async fn start_node_impl(
                 overrides: overrides.clone(),
                 forced_parent_hashes: None,
                 enable_evm_rpc: true, //additional_config.enable_evm_rpc,
+                eth_api: eth_api.clone(),
+                debug_permits: debug_permits.clone(),
             };
 
             crate::rpc::create_full(deps, subscription, pubsub_notification_sinks.clone())
@@ -560,6 +565,7 @@ pub async fn start_parachain_node(
     collator_options: CollatorOptions,
     para_id: ParaId,
     hwbench: Option<sc_sysinfo::HwBench>,
+    eth_api: EthApiOptions,
 ) -> sc_service::error::Result<(TaskManager, Arc<ParachainClient>)> {
     start_node_impl(
         parachain_config,
@@ -567,6 +573,7 @@ pub async fn start_parachain_node(
         collator_options,
         para_id,
         hwbench,
+        eth_api,
     )
     .await
 }
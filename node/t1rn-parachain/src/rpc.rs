@@ -13,10 +13,12 @@ use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 
-use parachain_runtime::{opaque::Block, AccountId, Balance, Hash, Nonce};
+use parachain_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Nonce};
 
 use pallet_portal_rpc::{Portal, PortalApiServer};
 
+use pallet_clock_rpc::{Clock, ClockApiServer};
+use pallet_rewards_rpc::{Rewards, RewardsApiServer};
 use pallet_xdns_rpc::{Xdns, XdnsApiServer};
 
 pub use sc_rpc_api::DenyUnsafe;
@@ -42,6 +44,8 @@ where
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: pallet_xdns_rpc::XdnsRuntimeApi<Block, AccountId>,
+    C::Api: pallet_clock_rpc::ClockRuntimeApi<Block, BlockNumber>,
+    C::Api: pallet_rewards_rpc::RewardsRuntimeApi<Block, AccountId, Balance>,
     C::Api: pallet_portal_rpc::PortalRuntimeApi<Block, AccountId, Balance, Hash>,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + 'static,
@@ -60,6 +64,8 @@ where
     module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 
     module.merge(Xdns::new(client.clone()).into_rpc())?;
+    module.merge(Clock::new(client.clone()).into_rpc())?;
+    module.merge(Rewards::new(client.clone()).into_rpc())?;
     module.merge(Portal::new(client).into_rpc())?;
 
     Ok(module)
@@ -7,6 +7,32 @@ pub struct Cli {
 
     #[clap(flatten)]
     pub run: RunCmd,
+
+    #[command(flatten)]
+    pub eth_api: EthApiOptions,
+}
+
+/// Opt-in RPC API sets, see `--ethapi`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum EthApi {
+    /// `debug_traceTransaction`, `debug_traceBlockByNumber` and `debug_traceCall`.
+    Debug,
+}
+
+/// RPC options of the EVM.
+#[derive(Debug, Clone, clap::Args)]
+pub struct EthApiOptions {
+    /// Enable the given RPC API sets, comma separated, e.g. `--ethapi=debug`.
+    #[arg(long, value_delimiter = ',')]
+    pub ethapi: Vec<EthApi>,
+
+    /// Number of `debug_*` transaction replays run at once, the others wait for their turn.
+    #[arg(long, default_value = "10")]
+    pub ethapi_max_permits: u32,
+
+    /// Number of steps a `structLogger` trace keeps, the steps past it are left out.
+    #[arg(long, default_value = "10000")]
+    pub ethapi_trace_max_steps: u32,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -6,7 +6,9 @@
 #![warn(missing_docs)]
 
 use jsonrpsee::RpcModule;
-use sc_client_api::{AuxStore, Backend, BlockchainEvents, StateBackend, StorageProvider};
+use sc_client_api::{
+    AuxStore, Backend, BlockBackend, BlockchainEvents, ProofProvider, StateBackend, StorageProvider,
+};
 use sc_network::NetworkService;
 use sc_network_sync::SyncingService;
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
@@ -22,18 +24,22 @@ use sp_runtime::{traits::BlakeTwo256, OpaqueExtrinsic};
 use std::{collections::BTreeMap, sync::Arc};
 use t2rn_parachain_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Nonce};
 
+use pallet_clock_rpc::{Clock, ClockApiServer};
 use pallet_portal_rpc::{Portal, PortalApiServer};
+use pallet_rewards_rpc::{Rewards, RewardsApiServer};
 use pallet_xdns_rpc::{Xdns, XdnsApiServer};
 
 use sp_api::CallApiAt;
 
 use fc_rpc::{
-    pending::ConsensusDataProvider, Eth, EthApiServer, EthBlockDataCacheTask, EthFilter,
-    EthFilterApiServer, EthPubSub, EthPubSubApiServer, Net, NetApiServer, OverrideHandle, Web3,
-    Web3ApiServer,
+    pending::ConsensusDataProvider, Debug, DebugApiServer, DebugPermits, Eth, EthApiServer,
+    EthBlockDataCacheTask, EthFilter, EthFilterApiServer, EthProof, EthProofApiServer, EthPubSub,
+    EthPubSubApiServer, Net, NetApiServer, OverrideHandle, Web3, Web3ApiServer,
 };
 use fc_rpc_core::types::{FeeHistoryCache, FilterPool};
 
+use crate::cli::{EthApi, EthApiOptions};
+
 use cumulus_primitives_core::PersistedValidationData;
 use cumulus_primitives_parachain_inherent::ParachainInherentData;
 use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
@@ -105,6 +111,10 @@ pub struct FullDeps<C, P, A: ChainApi, BE> {
     pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
     /// Enable EVM RPC servers
     pub enable_evm_rpc: bool,
+    /// Opt-in EVM RPC API sets and their limits.
+    pub eth_api: EthApiOptions,
+    /// Replays the `debug_*` methods may run at once, shared by all RPC modules.
+    pub debug_permits: DebugPermits,
     /// Grandpa block import setup.
     pub grandpa: GrandpaDeps<BE>,
     /// Mandated parent hashes for a given block hash.
@@ -123,15 +133,20 @@ pub fn create_full<C, P, BE, A>(
 where
     C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
     C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+    C: BlockBackend<Block> + ProofProvider<Block>,
     C: BlockchainEvents<Block>,
     C: CallApiAt<Block>,
     C: Send + Sync + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: pallet_xdns_rpc::XdnsRuntimeApi<Block, AccountId>,
+    C::Api: pallet_clock_rpc::ClockRuntimeApi<Block, BlockNumber>,
+    C::Api: pallet_rewards_rpc::RewardsRuntimeApi<Block, AccountId, Balance>,
     C::Api: pallet_portal_rpc::PortalRuntimeApi<Block, AccountId, Balance, Hash>,
     C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
     C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+    C::Api: fp_rpc::EvmTracingRuntimeApi<Block>,
+    C::Api: fp_rpc::EvmProofRuntimeApi<Block>,
     C::Api: sp_consensus_grandpa::GrandpaApi<Block>,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool<Block = Block> + Sync + Send + 'static,
@@ -160,6 +175,8 @@ where
         overrides,
         block_data_cache,
         enable_evm_rpc,
+        eth_api,
+        debug_permits,
         grandpa,
         forced_parent_hashes,
     } = deps;
@@ -167,6 +184,8 @@ where
     module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
     module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
     module.merge(Xdns::new(client.clone()).into_rpc())?;
+    module.merge(Clock::new(client.clone()).into_rpc())?;
+    module.merge(Rewards::new(client.clone()).into_rpc())?;
     module.merge(Portal::new(client.clone()).into_rpc())?;
 
     let GrandpaDeps {
@@ -241,6 +260,19 @@ where
     let max_stored_filters: usize = 500;
     //let tx_pool = TxPool::new(client.clone(), graph);
 
+    module.merge(EthProof::new(client.clone(), frontier_backend.clone()).into_rpc())?;
+    if eth_api.ethapi.contains(&EthApi::Debug) {
+        module.merge(
+            Debug::new(
+                client.clone(),
+                frontier_backend.clone(),
+                debug_permits,
+                eth_api.ethapi_trace_max_steps,
+            )
+            .into_rpc(),
+        )?;
+    }
+
     module.merge(
         EthFilter::new(
             client.clone(),
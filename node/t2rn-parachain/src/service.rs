@@ -1,5 +1,6 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
+use crate::cli::EthApiOptions;
 use fc_consensus::FrontierBlockImport;
 use fc_rpc_core::types::{FeeHistoryCache, FilterPool};
 use futures::{FutureExt, StreamExt};
@@ -154,7 +155,10 @@ pub fn new_partial(
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+    config: Configuration,
+    eth_api: EthApiOptions,
+) -> Result<TaskManager, ServiceError> {
     let sc_service::PartialComponents {
         client,
         backend,
@@ -301,6 +305,7 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
         let sync = sync_service.clone();
 
         let pubsub_notification_sinks = pubsub_notification_sinks.clone();
+        let debug_permits = fc_rpc::DebugPermits::new(eth_api.ethapi_max_permits as usize);
 
         Box::new(
             move |deny_unsafe, subscription: sc_rpc::SubscriptionTaskExecutor| {
@@ -320,6 +325,8 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
                     overrides: overrides.clone(),
                     forced_parent_hashes: None,
                     enable_evm_rpc: true,
+                    eth_api: eth_api.clone(),
+                    debug_permits: debug_permits.clone(),
                     grandpa: crate::rpc::GrandpaDeps {
                         shared_voter_state: SharedVoterState::empty(),
                         shared_authority_set: shared_authority_set.clone(),
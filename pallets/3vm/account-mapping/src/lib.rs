@@ -18,19 +18,26 @@
 //!
 //! Evm Accounts module provide a two way mapping between Substrate accounts and
 //! EVM accounts so user only have deal with one account / private key.
+//!
+//! Ownership of an EVM address can be proven either with a `personal_sign` signature or
+//! with EIP-712 typed data, which standard wallets display as a structured prompt.
+//! Accounts can also be mapped automatically on their first EVM interaction and moved
+//! to a new Substrate key with `link_accounts`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
 use circuit_runtime_types::{AccountIndex, EvmAddress};
 use frame_support::{
+    dispatch::DispatchClass,
     ensure, log,
     pallet_prelude::*,
-    traits::{Currency, ExistenceRequirement, IsType, OnKilledAccount},
+    storage::with_storage_layer,
+    traits::{Currency, ExistenceRequirement, IsType, OnKilledAccount, OnRuntimeUpgrade},
     transactional,
 };
-use frame_system::{ensure_signed, pallet_prelude::*};
-use pallet_3vm_evm::AddressMapping as BaseAddressMapping;
+use frame_system::{ensure_signed, pallet_prelude::*, RawOrigin};
+use pallet_3vm_evm::{AddressMapping as BaseAddressMapping, EnsureAddressOrigin};
 use scale_codec::Encode;
 use sp_core::crypto::AccountId32;
 use sp_io::{
@@ -83,6 +90,84 @@ pub fn ethereum_signable_message(what: &[u8], extra: &[u8]) -> Vec<u8> {
     [&ETH_SIGNED_MESSAGE_PREFIX[..], &message_digest[..]].concat()
 }
 
+pub const EIP712_DOMAIN_TYPE: &[u8] = b"EIP712Domain(string name,string version,uint256 chainId)";
+pub const EIP712_DOMAIN_NAME: &[u8] = b"t3rn Account Mapping";
+pub const EIP712_DOMAIN_VERSION: &[u8] = b"1";
+/// Typed data signed by an EVM address to be mapped to `substrateAccount` (SCALE-encoded).
+pub const EIP712_CLAIM_TYPE: &[u8] = b"Claim(bytes substrateAccount,address evmAddress)";
+/// Typed data signed by an EVM address to move its mapping from `fromAccount` to `toAccount`.
+/// The `nonce` is the number of links the address has signed so far, so none can be replayed.
+pub const EIP712_LINK_TYPE: &[u8] =
+    b"Link(bytes fromAccount,bytes toAccount,address evmAddress,uint256 nonce)";
+
+fn eip712_address_word(address: &EvmAddress) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address.as_bytes());
+    word
+}
+
+/// EIP-712 domain separator of the account mapping on the EVM chain of `chain_id`.
+pub fn eip712_domain_separator(chain_id: u64) -> [u8; 32] {
+    let mut chain_id_word = [0u8; 32];
+    chain_id_word[24..].copy_from_slice(&chain_id.to_be_bytes());
+    keccak_256(
+        &[
+            &keccak_256(EIP712_DOMAIN_TYPE)[..],
+            &keccak_256(EIP712_DOMAIN_NAME)[..],
+            &keccak_256(EIP712_DOMAIN_VERSION)[..],
+            &chain_id_word[..],
+        ]
+        .concat(),
+    )
+}
+
+fn eip712_digest(chain_id: u64, struct_hash: [u8; 32]) -> [u8; 32] {
+    keccak_256(
+        &[
+            &b"\x19\x01"[..],
+            &eip712_domain_separator(chain_id)[..],
+            &struct_hash[..],
+        ]
+        .concat(),
+    )
+}
+
+/// Digest an EVM address signs with `eth_signTypedData_v4` to claim `account`.
+pub fn eip712_claim_digest(chain_id: u64, account: &[u8], evm_address: &EvmAddress) -> [u8; 32] {
+    let struct_hash = keccak_256(
+        &[
+            &keccak_256(EIP712_CLAIM_TYPE)[..],
+            &keccak_256(account)[..],
+            &eip712_address_word(evm_address)[..],
+        ]
+        .concat(),
+    );
+    eip712_digest(chain_id, struct_hash)
+}
+
+/// Digest an EVM address signs with `eth_signTypedData_v4` to move its mapping to another account.
+pub fn eip712_link_digest(
+    chain_id: u64,
+    from_account: &[u8],
+    to_account: &[u8],
+    evm_address: &EvmAddress,
+    nonce: u64,
+) -> [u8; 32] {
+    let mut nonce_word = [0u8; 32];
+    nonce_word[24..].copy_from_slice(&nonce.to_be_bytes());
+    let struct_hash = keccak_256(
+        &[
+            &keccak_256(EIP712_LINK_TYPE)[..],
+            &keccak_256(from_account)[..],
+            &keccak_256(to_account)[..],
+            &eip712_address_word(evm_address)[..],
+            &nonce_word[..],
+        ]
+        .concat(),
+    );
+    eip712_digest(chain_id, struct_hash)
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -111,6 +196,15 @@ pub mod pallet {
         /// Storage deposit free charged when saving data into the blockchain.
         #[pallet::constant]
         type StorageDepositFee: Get<BalanceOf<Self>>;
+
+        /// Whether accounts without a mapping get their default EVM address mapped
+        /// on their first EVM interaction, free of the storage deposit fee.
+        #[pallet::constant]
+        type AutoMapOnFirstInteraction: Get<bool>;
+
+        /// Accounts visited per block by the `MapTruncatedEvmAddresses` migration.
+        #[pallet::constant]
+        type MaxMigratedAccountsPerBlock: Get<u32>;
     }
 
     #[pallet::event]
@@ -122,6 +216,17 @@ pub mod pallet {
             account_id: T::AccountId,
             evm_address: EvmAddress,
         },
+        /// Default EVM address mapped on the first EVM interaction of the account.
+        AutoMapAccount {
+            account_id: T::AccountId,
+            evm_address: EvmAddress,
+        },
+        /// The EVM address mapping and balance moved over to a new Substrate account.
+        LinkAccounts {
+            from: T::AccountId,
+            to: T::AccountId,
+            evm_address: EvmAddress,
+        },
     }
 
     /// Error for evm accounts module.
@@ -139,6 +244,12 @@ pub mod pallet {
         PreImageAddressNotMatchingRecovered,
         /// Account ref count is not zero
         NonZeroRefCount,
+        /// AccountId has no EVM address mapped
+        AccountIdNotMapped,
+        /// Accounts can't be linked to themselves
+        CannotLinkToSelf,
+        /// Account has reserved or locked balance, or other consumers, which can't be moved
+        AccountHasConsumers,
     }
 
     /// The Substrate Account for EvmAddresses
@@ -157,11 +268,38 @@ pub mod pallet {
     pub type EvmAddresses<T: Config> =
         StorageMap<_, Twox64Concat, T::AccountId, EvmAddress, OptionQuery>;
 
+    /// Number of `link_accounts` signed by the EvmAddresses, part of the signed payload
+    ///
+    /// LinkNonces: map EvmAddress => u64
+    #[pallet::storage]
+    #[pallet::getter(fn link_nonces)]
+    pub type LinkNonces<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, u64, ValueQuery>;
+
+    /// Version of the pallet storage, see `migrations`
+    #[pallet::storage]
+    pub type StorageMigrations<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Raw `frame_system::Account` key the `MapTruncatedEvmAddresses` migration continues
+    /// after, empty before the first account. Removed once all accounts are visited.
+    #[pallet::storage]
+    #[pallet::getter(fn truncated_addresses_cursor)]
+    pub type TruncatedAddressesCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
     #[pallet::pallet]
     pub struct Pallet<T>(_);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            match TruncatedAddressesCursor::<T>::get() {
+                Some(cursor) => migrations::map_truncated_evm_addresses::<T>(
+                    cursor,
+                    T::MaxMigratedAccountsPerBlock::get(),
+                ),
+                None => T::DbWeight::get().reads(1),
+            }
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -200,34 +338,7 @@ pub mod pallet {
                 Error::<T>::PreImageAddressNotMatchingRecovered
             );
 
-            // check if the evm padded address already exists
-            let account_id = T::AddressMapping::into_account_id(&eth_address);
-            if frame_system::Pallet::<T>::account_exists(&account_id) {
-                // merge balance from `evm padded address` to `origin`
-                <T as Config>::Currency::transfer(
-                    &account_id,
-                    &who,
-                    <T as Config>::Currency::free_balance(&account_id),
-                    ExistenceRequirement::AllowDeath,
-                )?;
-                //T::TransferAll::transfer_all(&account_id, &who)?;
-            }
-
-            // Transfer storage deposit fee
-            <T as Config>::Currency::transfer(
-                &who,
-                &T::NetworkTreasuryAccount::get(),
-                T::StorageDepositFee::get(),
-                ExistenceRequirement::KeepAlive,
-            )?;
-
-            Accounts::<T>::insert(eth_address, &who);
-            EvmAddresses::<T>::insert(&who, eth_address);
-
-            Self::deposit_event(Event::ClaimAccount {
-                account_id: who,
-                evm_address: eth_address,
-            });
+            Self::do_claim_eth_account(who, eth_address)?;
 
             Ok(Pays::No.into())
         }
@@ -283,6 +394,112 @@ pub mod pallet {
 
             Ok(Pays::Yes.into())
         }
+
+        /// Claim account mapping between Substrate accounts and EVM accounts with an
+        /// EIP-712 typed data signature, as produced by `eth_signTypedData_v4`.
+        /// Ensure eth_address has not been mapped.
+        ///
+        /// - `eth_address`: The address to bind to the caller's account
+        /// - `eth_signature`: A signature of the `Claim` typed data over the caller's account
+        #[pallet::call_index(3)]
+        #[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+        #[transactional]
+        pub fn claim_eth_account_typed(
+            origin: OriginFor<T>,
+            eth_address: EvmAddress,
+            eth_signature: EcdsaSignature,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                !EvmAddresses::<T>::contains_key(&who),
+                Error::<T>::AccountIdHasMapped
+            );
+            ensure!(
+                !Accounts::<T>::contains_key(eth_address),
+                Error::<T>::EthAddressHasMapped
+            );
+
+            let digest = eip712_claim_digest(T::ChainId::get(), &who.encode(), &eth_address);
+            let address = Self::eth_recover_digest(&eth_signature, &digest)
+                .ok_or(Error::<T>::BadSignature)?;
+
+            ensure!(
+                eth_address == address,
+                Error::<T>::PreImageAddressNotMatchingRecovered
+            );
+
+            Self::do_claim_eth_account(who, eth_address)?;
+
+            Ok(Pays::No.into())
+        }
+
+        /// Move the caller's EVM address mapping and free balance over to `new_account`,
+        /// e.g. when switching Substrate keys. Either everything migrates or nothing does.
+        /// Accounts with reserved or locked balance have to release it first.
+        ///
+        /// - `new_account`: The account to take over the mapping, must not be mapped yet
+        /// - `eth_signature`: A signature of the `Link` typed data by the mapped EVM address,
+        ///   over its current `LinkNonces` entry
+        #[pallet::call_index(4)]
+        #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 6).ref_time())]
+        #[transactional]
+        pub fn link_accounts(
+            origin: OriginFor<T>,
+            new_account: T::AccountId,
+            eth_signature: EcdsaSignature,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(who != new_account, Error::<T>::CannotLinkToSelf);
+            // Reserves and locks hold a consumer reference and would stay behind with `who`
+            ensure!(
+                frame_system::Pallet::<T>::consumers(&who) == 0,
+                Error::<T>::AccountHasConsumers
+            );
+            let eth_address = EvmAddresses::<T>::get(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+            ensure!(
+                !EvmAddresses::<T>::contains_key(&new_account),
+                Error::<T>::AccountIdHasMapped
+            );
+
+            let nonce = LinkNonces::<T>::get(eth_address);
+            let digest = eip712_link_digest(
+                T::ChainId::get(),
+                &who.encode(),
+                &new_account.encode(),
+                &eth_address,
+                nonce,
+            );
+            let address = Self::eth_recover_digest(&eth_signature, &digest)
+                .ok_or(Error::<T>::BadSignature)?;
+
+            ensure!(
+                eth_address == address,
+                Error::<T>::PreImageAddressNotMatchingRecovered
+            );
+
+            // Reaping `who` removes its mapping, so the new one is written after the transfer
+            <T as Config>::Currency::transfer(
+                &who,
+                &new_account,
+                <T as Config>::Currency::free_balance(&who),
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            EvmAddresses::<T>::remove(&who);
+            Accounts::<T>::insert(eth_address, &new_account);
+            EvmAddresses::<T>::insert(&new_account, eth_address);
+            LinkNonces::<T>::insert(eth_address, nonce.saturating_add(1));
+
+            Self::deposit_event(Event::LinkAccounts {
+                from: who,
+                to: new_account,
+                evm_address: eth_address,
+            });
+
+            Ok(Pays::Yes.into())
+        }
     }
 }
 
@@ -316,6 +533,104 @@ impl<T: Config> Pallet<T> {
         signature
     }
 
+    #[cfg(any(feature = "runtime-benchmarks", feature = "std"))]
+    // Signs the given EIP-712 digest.
+    // Only for tests
+    pub fn eth_sign_digest(secret: &libsecp256k1::SecretKey, digest: &[u8; 32]) -> EcdsaSignature {
+        let (sig, recovery_id) = libsecp256k1::sign(&libsecp256k1::Message::parse(digest), secret);
+        let mut r = [0u8; 65];
+        r[0..64].copy_from_slice(&sig.serialize()[..]);
+        r[64] = recovery_id.serialize();
+        EcdsaSignature(r)
+    }
+
+    // Maps `eth_address` to `who` after its ownership has been proven, merging the balance
+    // of the evm padded address into `who` and charging the storage deposit fee.
+    fn do_claim_eth_account(who: T::AccountId, eth_address: EvmAddress) -> DispatchResult {
+        // check if the evm padded address already exists
+        let account_id = T::AddressMapping::into_account_id(&eth_address);
+        if frame_system::Pallet::<T>::account_exists(&account_id) {
+            // merge balance from `evm padded address` to `origin`
+            <T as Config>::Currency::transfer(
+                &account_id,
+                &who,
+                <T as Config>::Currency::free_balance(&account_id),
+                ExistenceRequirement::AllowDeath,
+            )?;
+        }
+
+        // Transfer storage deposit fee
+        <T as Config>::Currency::transfer(
+            &who,
+            &T::NetworkTreasuryAccount::get(),
+            T::StorageDepositFee::get(),
+            ExistenceRequirement::KeepAlive,
+        )?;
+
+        Accounts::<T>::insert(eth_address, &who);
+        EvmAddresses::<T>::insert(&who, eth_address);
+
+        Self::deposit_event(Event::ClaimAccount {
+            account_id: who,
+            evm_address: eth_address,
+        });
+
+        Ok(())
+    }
+
+    /// Returns whether `who` may act as `address` in the EVM. If enabled, accounts without
+    /// a mapping acting as their default EVM address get it mapped on this first interaction,
+    /// merging the balance of the evm padded address into `who` as claiming it would.
+    ///
+    /// Origin checks aren't part of the EVM call weights, so the mapping registers its own.
+    pub fn map_on_first_interaction(who: &T::AccountId, address: &EvmAddress) -> bool {
+        if !T::AddressMapping::is_linked(who, address) {
+            return false
+        }
+
+        if T::AutoMapOnFirstInteraction::get()
+            && !EvmAddresses::<T>::contains_key(who)
+            && !Accounts::<T>::contains_key(address)
+        {
+            // Once mapped, the evm padded address can't be reached anymore
+            let account_id = T::AddressMapping::into_account_id(address);
+            let merged = with_storage_layer(|| {
+                if frame_system::Pallet::<T>::account_exists(&account_id) {
+                    <T as Config>::Currency::transfer(
+                        &account_id,
+                        who,
+                        <T as Config>::Currency::free_balance(&account_id),
+                        ExistenceRequirement::AllowDeath,
+                    )?;
+                }
+                Ok::<(), DispatchError>(())
+            });
+            frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                T::DbWeight::get().reads_writes(5, 4),
+                DispatchClass::Normal,
+            );
+
+            match merged {
+                Ok(()) => {
+                    Accounts::<T>::insert(address, who);
+                    EvmAddresses::<T>::insert(who, address);
+
+                    Self::deposit_event(Event::AutoMapAccount {
+                        account_id: who.clone(),
+                        evm_address: *address,
+                    });
+                },
+                Err(err) => log::warn!(
+                    "AccountMapping::map_on_first_interaction - evm padded balance of {:?} not merged: {:?}",
+                    address,
+                    err
+                ),
+            }
+        }
+
+        true
+    }
+
     // Constructs the message that Ethereum RPC's `personal_sign` and `eth_sign` would sign.
     fn ethereum_signable_message(what: &[u8], extra: &[u8]) -> Vec<u8> {
         let message_digest = keccak_256([what, extra].concat().as_slice());
@@ -331,6 +646,14 @@ impl<T: Config> Pallet<T> {
             .copy_from_slice(&keccak_256(&secp256k1_ecdsa_recover(&s.0, &msg).ok()?[..])[12..]);
         Some(res)
     }
+
+    // Attempts to recover the Ethereum address from a signature of an EIP-712 digest.
+    fn eth_recover_digest(s: &EcdsaSignature, digest: &[u8; 32]) -> Option<EvmAddress> {
+        let mut res = EvmAddress::default();
+        res.0
+            .copy_from_slice(&keccak_256(&secp256k1_ecdsa_recover(&s.0, digest).ok()?[..])[12..]);
+        Some(res)
+    }
 }
 
 // Creates a an EvmAddress from an AccountId by appending the bytes "evm:" to
@@ -423,6 +746,120 @@ where
         }
     }
 }
+/// EVM call and withdraw origin accepting the addresses linked to the signer, which maps
+/// the signer's default EVM address on its first interaction if enabled.
+pub struct EnsureAddressMapped<T>(PhantomData<T>);
+
+impl<T: Config, OuterOrigin> EnsureAddressOrigin<OuterOrigin> for EnsureAddressMapped<T>
+where
+    OuterOrigin: Into<Result<RawOrigin<T::AccountId>, OuterOrigin>> + From<RawOrigin<T::AccountId>>,
+{
+    type Success = T::AccountId;
+
+    fn try_address_origin(
+        address: &EvmAddress,
+        origin: OuterOrigin,
+    ) -> Result<T::AccountId, OuterOrigin> {
+        origin.into().and_then(|o| match o {
+            RawOrigin::Signed(who) if Pallet::<T>::map_on_first_interaction(&who, address) =>
+                Ok(who),
+            r => Err(OuterOrigin::from(r)),
+        })
+    }
+}
+
+pub mod migrations {
+    use super::*;
+
+    /// Maps the addresses accounts acted as under `EnsureAddressTruncated`, i.e. the first
+    /// 20 bytes of the account, to keep their EVM balance and nonce reachable once the EVM
+    /// call and withdraw origins switch to `EnsureAddressMapped`. Only addresses with an evm
+    /// padded account, so with EVM activity, are mapped and that account's balance is merged.
+    ///
+    /// The upgrade only starts the migration, `on_initialize` then visits at most
+    /// `MaxMigratedAccountsPerBlock` accounts a block, following `TruncatedAddressesCursor`.
+    pub struct MapTruncatedEvmAddresses<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MapTruncatedEvmAddresses<T> {
+        fn on_runtime_upgrade() -> Weight {
+            const CURRENT_STORAGE_VERSION: u32 = 1;
+            if StorageMigrations::<T>::get() >= CURRENT_STORAGE_VERSION {
+                return T::DbWeight::get().reads(1)
+            }
+
+            TruncatedAddressesCursor::<T>::put(Vec::<u8>::new());
+            StorageMigrations::<T>::put(CURRENT_STORAGE_VERSION);
+            T::DbWeight::get().reads_writes(1, 2)
+        }
+    }
+
+    /// Visits at most `limit` accounts after the raw key `cursor`, mapping their truncated
+    /// addresses, and moves the cursor on or removes it once all accounts are visited.
+    pub fn map_truncated_evm_addresses<T: Config>(cursor: Vec<u8>, limit: u32) -> Weight {
+        let mut accounts = if cursor.is_empty() {
+            frame_system::Account::<T>::iter_keys()
+        } else {
+            frame_system::Account::<T>::iter_keys_from(cursor)
+        };
+        let mut reads = 1u64;
+        let mut writes = 1u64;
+        let mut visited = 0u32;
+        while visited < limit {
+            let who = match accounts.next() {
+                Some(who) => who,
+                None => break,
+            };
+            visited += 1;
+            reads += 1;
+            let raw = who.encode();
+            if raw.len() < 20 || raw.starts_with(b"evm:") {
+                continue
+            }
+            let truncated = EvmAddress::from_slice(&raw[0..20]);
+            reads += 3;
+            if EvmAddresses::<T>::contains_key(&who) || Accounts::<T>::contains_key(truncated) {
+                continue
+            }
+            let padded = match T::AccountId::decode(
+                &mut &create_default_substrate_address(&truncated).encode()[..],
+            ) {
+                Ok(padded) => padded,
+                Err(_) => continue,
+            };
+            if !frame_system::Pallet::<T>::account_exists(&padded) {
+                continue
+            }
+
+            if let Err(err) = with_storage_layer(|| {
+                <T as Config>::Currency::transfer(
+                    &padded,
+                    &who,
+                    <T as Config>::Currency::free_balance(&padded),
+                    ExistenceRequirement::AllowDeath,
+                )
+            }) {
+                log::warn!(
+                    "MapTruncatedEvmAddresses - evm padded balance of {:?} not merged: {:?}",
+                    truncated,
+                    err
+                );
+                continue
+            }
+            Accounts::<T>::insert(truncated, &who);
+            EvmAddresses::<T>::insert(&who, truncated);
+            reads += 2;
+            writes += 4;
+        }
+
+        if visited < limit {
+            TruncatedAddressesCursor::<T>::kill();
+        } else {
+            TruncatedAddressesCursor::<T>::put(accounts.last_raw_key().to_vec());
+        }
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
+
 pub struct CallKillAccount<T>(PhantomData<T>);
 
 impl<T: Config> OnKilledAccount<T::AccountId> for CallKillAccount<T> {
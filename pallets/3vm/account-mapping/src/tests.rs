@@ -17,7 +17,7 @@
 #![cfg(test)]
 use super::*;
 
-use frame_support::assert_ok;
+use frame_support::{assert_noop, assert_ok, traits::ReservableCurrency};
 
 use crate::Event;
 use circuit_mock_runtime::{
@@ -92,3 +92,307 @@ fn default_evm_account_dereviation_is_compatible_with_eth_standards() {
         assert_eq!(AccountMapping::evm_addresses(ALICE), Some(hex!("0101010101010101010101010101010101010101").into()));
     });
 }
+
+#[test]
+fn claim_account_with_eip712_signature_works() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            sp_runtime::MultiAddress::Id(ALICE),
+            100000
+        ));
+
+        let chain_id = <Runtime as Config>::ChainId::get();
+        let digest = eip712_claim_digest(chain_id, &ALICE.encode(), &eth(&alice()));
+        let signature = AccountMapping::eth_sign_digest(&alice(), &digest);
+
+        assert_ok!(AccountMapping::claim_eth_account_typed(
+            RuntimeOrigin::signed(ALICE),
+            eth(&alice()),
+            signature
+        ));
+
+        assert_eq!(AccountMapping::accounts(eth(&alice())), Some(ALICE));
+        assert_eq!(AccountMapping::evm_addresses(ALICE), Some(eth(&alice())));
+    });
+}
+
+#[test]
+fn claim_account_with_eip712_signature_over_other_account_fails() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            sp_runtime::MultiAddress::Id(BOB),
+            100000
+        ));
+
+        let chain_id = <Runtime as Config>::ChainId::get();
+        let digest = eip712_claim_digest(chain_id, &ALICE.encode(), &eth(&alice()));
+        let signature = AccountMapping::eth_sign_digest(&alice(), &digest);
+
+        assert_noop!(
+            AccountMapping::claim_eth_account_typed(
+                RuntimeOrigin::signed(BOB),
+                eth(&alice()),
+                signature
+            ),
+            Error::<Runtime>::PreImageAddressNotMatchingRecovered
+        );
+    });
+}
+
+#[test]
+fn link_accounts_moves_mapping_and_balance_to_new_account() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            sp_runtime::MultiAddress::Id(ALICE),
+            100000
+        ));
+
+        let chain_id = <Runtime as Config>::ChainId::get();
+        let digest = eip712_claim_digest(chain_id, &ALICE.encode(), &eth(&alice()));
+        assert_ok!(AccountMapping::claim_eth_account_typed(
+            RuntimeOrigin::signed(ALICE),
+            eth(&alice()),
+            AccountMapping::eth_sign_digest(&alice(), &digest)
+        ));
+        let alice_balance = Balances::free_balance(ALICE);
+        let bob_balance = Balances::free_balance(BOB);
+
+        let digest =
+            eip712_link_digest(chain_id, &ALICE.encode(), &BOB.encode(), &eth(&alice()), 0);
+        assert_ok!(AccountMapping::link_accounts(
+            RuntimeOrigin::signed(ALICE),
+            BOB,
+            AccountMapping::eth_sign_digest(&alice(), &digest)
+        ));
+
+        assert_eq!(AccountMapping::accounts(eth(&alice())), Some(BOB));
+        assert_eq!(AccountMapping::evm_addresses(BOB), Some(eth(&alice())));
+        assert_eq!(AccountMapping::evm_addresses(ALICE), None);
+        assert_eq!(Balances::free_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(BOB), bob_balance + alice_balance);
+    });
+}
+
+#[test]
+fn link_accounts_requires_signature_of_mapped_address() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            sp_runtime::MultiAddress::Id(ALICE),
+            100000
+        ));
+
+        let chain_id = <Runtime as Config>::ChainId::get();
+        let digest = eip712_claim_digest(chain_id, &ALICE.encode(), &eth(&alice()));
+        assert_ok!(AccountMapping::claim_eth_account_typed(
+            RuntimeOrigin::signed(ALICE),
+            eth(&alice()),
+            AccountMapping::eth_sign_digest(&alice(), &digest)
+        ));
+
+        let digest =
+            eip712_link_digest(chain_id, &ALICE.encode(), &BOB.encode(), &eth(&alice()), 0);
+        assert_noop!(
+            AccountMapping::link_accounts(
+                RuntimeOrigin::signed(ALICE),
+                BOB,
+                AccountMapping::eth_sign_digest(&bob(), &digest)
+            ),
+            Error::<Runtime>::PreImageAddressNotMatchingRecovered
+        );
+        assert_noop!(
+            AccountMapping::link_accounts(
+                RuntimeOrigin::signed(BOB),
+                ALICE,
+                AccountMapping::eth_sign_digest(&alice(), &digest)
+            ),
+            Error::<Runtime>::AccountIdNotMapped
+        );
+    });
+}
+
+#[test]
+fn default_evm_address_is_mapped_on_first_interaction() {
+    ExtBuilder::default().build().execute_with(|| {
+        let default_address = EvmAddressMapping::<Runtime>::get_default_evm_address(&ALICE);
+
+        assert!(<EnsureAddressMapped<Runtime> as EnsureAddressOrigin<RuntimeOrigin>>::try_address_origin(
+            &eth(&bob()),
+            RuntimeOrigin::signed(ALICE)
+        )
+        .is_err());
+        assert_eq!(AccountMapping::evm_addresses(ALICE), None);
+
+        assert_ok!(<EnsureAddressMapped<Runtime> as EnsureAddressOrigin<RuntimeOrigin>>::try_address_origin(
+            &default_address,
+            RuntimeOrigin::signed(ALICE)
+        ));
+
+        assert_eq!(AccountMapping::evm_addresses(ALICE), Some(default_address));
+        assert_eq!(AccountMapping::accounts(default_address), Some(ALICE));
+    });
+}
+
+#[test]
+fn link_accounts_signature_cannot_be_replayed() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            sp_runtime::MultiAddress::Id(ALICE),
+            100000
+        ));
+
+        let chain_id = <Runtime as Config>::ChainId::get();
+        let digest = eip712_claim_digest(chain_id, &ALICE.encode(), &eth(&alice()));
+        assert_ok!(AccountMapping::claim_eth_account_typed(
+            RuntimeOrigin::signed(ALICE),
+            eth(&alice()),
+            AccountMapping::eth_sign_digest(&alice(), &digest)
+        ));
+
+        let to_bob = AccountMapping::eth_sign_digest(
+            &alice(),
+            &eip712_link_digest(chain_id, &ALICE.encode(), &BOB.encode(), &eth(&alice()), 0),
+        );
+        assert_ok!(AccountMapping::link_accounts(
+            RuntimeOrigin::signed(ALICE),
+            BOB,
+            to_bob.clone()
+        ));
+        assert_eq!(AccountMapping::link_nonces(eth(&alice())), 1);
+
+        let to_alice = AccountMapping::eth_sign_digest(
+            &alice(),
+            &eip712_link_digest(chain_id, &BOB.encode(), &ALICE.encode(), &eth(&alice()), 1),
+        );
+        assert_ok!(AccountMapping::link_accounts(
+            RuntimeOrigin::signed(BOB),
+            ALICE,
+            to_alice
+        ));
+        assert_eq!(AccountMapping::accounts(eth(&alice())), Some(ALICE));
+
+        // The first link was signed over a nonce that has been used up
+        assert_noop!(
+            AccountMapping::link_accounts(RuntimeOrigin::signed(ALICE), BOB, to_bob),
+            Error::<Runtime>::PreImageAddressNotMatchingRecovered
+        );
+    });
+}
+
+#[test]
+fn first_interaction_merges_evm_padded_balance_and_registers_its_weight() {
+    ExtBuilder::default().build().execute_with(|| {
+        let default_address = EvmAddressMapping::<Runtime>::get_default_evm_address(&ALICE);
+        let padded_account = create_default_substrate_address(&default_address);
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            sp_runtime::MultiAddress::Id(padded_account.clone()),
+            5000
+        ));
+        let alice_balance = Balances::free_balance(ALICE);
+        let block_weight = System::block_weight().total();
+
+        assert_ok!(<EnsureAddressMapped<Runtime> as EnsureAddressOrigin<
+            RuntimeOrigin,
+        >>::try_address_origin(
+            &default_address, RuntimeOrigin::signed(ALICE)
+        ));
+
+        assert_eq!(AccountMapping::accounts(default_address), Some(ALICE));
+        assert_eq!(Balances::free_balance(&padded_account), 0);
+        assert_eq!(Balances::free_balance(ALICE), alice_balance + 5000);
+        assert!(System::block_weight().total().ref_time() > block_weight.ref_time());
+    });
+}
+
+#[test]
+fn migration_maps_truncated_addresses_with_evm_activity() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            sp_runtime::MultiAddress::Id(ALICE),
+            100000
+        ));
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            sp_runtime::MultiAddress::Id(BOB),
+            100000
+        ));
+        // Alice acted in the EVM as the first 20 bytes of her account, Bob never did
+        let alice_truncated = EvmAddress::from_slice(&AsRef::<[u8; 32]>::as_ref(&ALICE)[0..20]);
+        let bob_truncated = EvmAddress::from_slice(&AsRef::<[u8; 32]>::as_ref(&BOB)[0..20]);
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            sp_runtime::MultiAddress::Id(create_default_substrate_address(&alice_truncated)),
+            5000
+        ));
+
+        migrations::MapTruncatedEvmAddresses::<Runtime>::on_runtime_upgrade();
+        assert_eq!(AccountMapping::truncated_addresses_cursor(), Some(vec![]));
+
+        // Accounts are visited a few per block until the cursor runs out of them
+        let mut blocks = 0;
+        while AccountMapping::truncated_addresses_cursor().is_some() {
+            blocks += 1;
+            AccountMapping::on_initialize(blocks);
+        }
+        assert!(blocks > 1);
+
+        assert_eq!(AccountMapping::accounts(alice_truncated), Some(ALICE));
+        assert_eq!(AccountMapping::evm_addresses(ALICE), Some(alice_truncated));
+        assert_eq!(Balances::free_balance(ALICE), 105000);
+        assert_eq!(AccountMapping::accounts(bob_truncated), None);
+        assert_eq!(AccountMapping::evm_addresses(BOB), None);
+        assert_eq!(StorageMigrations::<Runtime>::get(), 1);
+    });
+}
+
+#[test]
+fn migration_starts_only_once() {
+    ExtBuilder::default().build().execute_with(|| {
+        migrations::MapTruncatedEvmAddresses::<Runtime>::on_runtime_upgrade();
+        AccountMapping::on_initialize(1);
+        TruncatedAddressesCursor::<Runtime>::kill();
+
+        migrations::MapTruncatedEvmAddresses::<Runtime>::on_runtime_upgrade();
+
+        assert_eq!(AccountMapping::truncated_addresses_cursor(), None);
+    });
+}
+
+#[test]
+fn link_accounts_rejects_accounts_with_reserved_balance() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            sp_runtime::MultiAddress::Id(ALICE),
+            100000
+        ));
+
+        let chain_id = <Runtime as Config>::ChainId::get();
+        let digest = eip712_claim_digest(chain_id, &ALICE.encode(), &eth(&alice()));
+        assert_ok!(AccountMapping::claim_eth_account_typed(
+            RuntimeOrigin::signed(ALICE),
+            eth(&alice()),
+            AccountMapping::eth_sign_digest(&alice(), &digest)
+        ));
+        assert_ok!(<Balances as ReservableCurrency<_>>::reserve(&ALICE, 1000));
+
+        let digest =
+            eip712_link_digest(chain_id, &ALICE.encode(), &BOB.encode(), &eth(&alice()), 0);
+        assert_noop!(
+            AccountMapping::link_accounts(
+                RuntimeOrigin::signed(ALICE),
+                BOB,
+                AccountMapping::eth_sign_digest(&alice(), &digest)
+            ),
+            Error::<Runtime>::AccountHasConsumers
+        );
+        assert_eq!(AccountMapping::accounts(eth(&alice())), Some(ALICE));
+        assert_eq!(Balances::reserved_balance(ALICE), 1000);
+    });
+}
@@ -5,19 +5,26 @@
 pub use pallet::*;
 
 use frame_support::{
-    dispatch::DispatchResult, ensure, sp_runtime::DispatchError, traits::Currency,
+    dispatch::DispatchResult,
+    ensure,
+    sp_runtime::DispatchError,
+    traits::{Currency, Get},
+    weights::Weight,
 };
 use frame_system::pallet_prelude::BlockNumberFor;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{StaticLookup, Zero};
 use sp_std::vec::Vec;
 use t3rn_primitives::{
     account_manager::Outcome,
     circuit::{
-        LocalStateExecutionView, OnLocalTrigger, VacuumEVM3DOrder, VacuumEVMOrder, VacuumEVMProof,
-        VacuumEVMTeleportOrder,
+        LocalStateExecutionView, OnLocalTrigger, VacuumEVM3DOrder, VacuumEVMConfirmation,
+        VacuumEVMOrder, VacuumEVMProof, VacuumEVMTeleportOrder,
     },
     contract_metadata::ContractType,
-    contracts_registry::{AuthorInfo, ContractsRegistry, KindValidator, RegistryContract},
+    contracts_registry::{
+        AuthorInfo, ContractsRegistry, KindValidator, RegistryContract, Royalties,
+    },
+    gmp::{GmpAcknowledgement, GmpCallback, OnGmpAcknowledged},
     threevm::{
         LocalStateAccess, ModuleOperations, Precompile, PrecompileArgs, PrecompileInvocation,
         Remunerated, Remuneration, SignalOpcode, ThreeVm, VacuumAccess,
@@ -35,6 +42,7 @@ mod tests;
 
 pub mod precompile;
 pub mod remuneration;
+pub mod royalties;
 pub mod signal;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -49,14 +57,20 @@ pub mod pallet {
 
     use crate::BalanceOf;
     use frame_support::{pallet_prelude::*, traits::Currency};
-    use frame_system::pallet_prelude::BlockNumberFor;
+    use frame_system::{
+        ensure_signed,
+        pallet_prelude::{BlockNumberFor, OriginFor},
+    };
+    use sp_core::H256;
     use sp_std::vec::Vec;
     use t3rn_primitives::{
         account_manager::AccountManager,
         circuit::OnLocalTrigger,
         contract_metadata::ContractType,
         contracts_registry::ContractsRegistry,
+        gmp::GmpAcknowledgement,
         portal::Portal,
+        rewards::RewardsWriteApi,
         threevm::{AddressMapping, VacuumAccess},
         ChainId,
     };
@@ -84,7 +98,7 @@ pub mod pallet {
         type EscrowAccount: Get<Self::AccountId>;
 
         /// Asset Id for the account manager
-        type AssetId;
+        type AssetId: From<u32>;
 
         /// The account manager that handles the escrow pool
         type AccountManager: AccountManager<
@@ -103,6 +117,13 @@ pub mod pallet {
 
         /// Inject access to portal so contracts can use light clients
         type Portal: Portal<Self>;
+
+        /// Accrues the royalties of registry contracts as pending claims of their payees
+        type Rewards: RewardsWriteApi<Self::AccountId, BalanceOf<Self>, BlockNumberFor<Self>>;
+
+        /// Most GMP acknowledgements kept in the inbox of a contract until it prunes them
+        #[pallet::constant]
+        type MaxGmpAcknowledgements: Get<u32>;
     }
 
     #[pallet::pallet]
@@ -122,6 +143,23 @@ pub mod pallet {
     #[pallet::getter(fn author_of)]
     pub(crate) type AuthorOf<T: Config> = StorageMap<_, Identity, T::AccountId, T::AccountId>;
 
+    /// A mapping of a contract's address to the registry contract it was instantiated from.
+    #[pallet::storage]
+    #[pallet::getter(fn registry_contract_of)]
+    pub(crate) type RegistryContractOf<T: Config> = StorageMap<_, Identity, T::AccountId, T::Hash>;
+
+    /// Acknowledgements of the GMP messages sent by contracts, delivered to their inbox. [contract, message_id]
+    #[pallet::storage]
+    #[pallet::getter(fn gmp_acknowledgement_of)]
+    pub(crate) type GmpAcknowledgements<T: Config> =
+        StorageDoubleMap<_, Identity, T::AccountId, Identity, H256, GmpAcknowledgement>;
+
+    /// Number of acknowledgements in the GMP inbox of a contract, up to `MaxGmpAcknowledgements`
+    #[pallet::storage]
+    #[pallet::getter(fn gmp_acknowledgement_count_of)]
+    pub(crate) type GmpAcknowledgementCount<T: Config> =
+        StorageMap<_, Identity, T::AccountId, u32, ValueQuery>;
+
     #[pallet::genesis_config]
     #[derive(frame_support::DefaultNoBound)]
     pub struct GenesisConfig<T: Config> {
@@ -152,6 +190,18 @@ pub mod pallet {
         AuthorStored((T::AccountId, T::AccountId)),
         /// An author of a module was removed [contract]
         AuthorRemoved(T::AccountId),
+        /// A contract was linked to the registry contract it was instantiated from [contract, registry_id]
+        RegistryContractStored((T::AccountId, T::Hash)),
+        /// A royalty was taken out of the reward of a side effect requested by a contract, for its payees [contract, registry_id, royalty]
+        RoyaltyCharged((T::AccountId, T::Hash, BalanceOf<T>)),
+        /// A royalty could not be taken out of the reward of a side effect requested by a contract [contract, charge_id, error]
+        RoyaltyPayoutFailed((T::AccountId, T::Hash, DispatchError)),
+        /// An acknowledgement of a GMP message was delivered to the inbox of its sender contract [contract, message_id, success]
+        GmpAcknowledgementDelivered((T::AccountId, H256, bool)),
+        /// An acknowledgement of a GMP message wasn't kept as the inbox of its sender contract is full [contract, message_id, success]
+        GmpAcknowledgementDropped((T::AccountId, H256, bool)),
+        /// A contract pruned an acknowledgement of a GMP message from its inbox [contract, message_id]
+        GmpAcknowledgementPruned((T::AccountId, H256)),
     }
 
     #[derive(PartialEq)]
@@ -163,6 +213,8 @@ pub mod pallet {
         CannotTriggerWithoutSideEffects,
         /// The contract could not be found in the registry
         ContractNotFound,
+        /// The contract release has been deprecated by its package owner
+        ContractDeprecated,
         /// An origin could not be extracted from the buffer
         InvalidOrigin,
         /// The contract cannot be instantiated due to its type
@@ -181,10 +233,31 @@ pub mod pallet {
         /// Invalid arithmetic computation causes overflow
         InvalidArithmeticOverflow,
         DownstreamCircuit,
+        /// The GMP acknowledgement isn't in the inbox of the contract
+        GmpAcknowledgementNotFound,
     }
 
     #[pallet::call]
-    impl<T: Config> Pallet<T> {}
+    impl<T: Config> Pallet<T> {
+        /// Remove the acknowledgement of a GMP message from the inbox of the calling contract, making room for
+        ///     the acknowledgements of its later messages.
+        #[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+        pub fn prune_gmp_acknowledgement(origin: OriginFor<T>, message_id: H256) -> DispatchResult {
+            let contract = ensure_signed(origin)?;
+            ensure!(
+                GmpAcknowledgements::<T>::contains_key(&contract, message_id),
+                Error::<T>::GmpAcknowledgementNotFound
+            );
+
+            GmpAcknowledgements::<T>::remove(&contract, message_id);
+            GmpAcknowledgementCount::<T>::mutate(&contract, |count| {
+                *count = count.saturating_sub(1)
+            });
+            Self::deposit_event(Event::<T>::GmpAcknowledgementPruned((contract, message_id)));
+
+            Ok(())
+        }
+    }
 }
 
 impl<T: Config> Pallet<T> {
@@ -244,9 +317,16 @@ impl<T: Config> VacuumAccess<T> for Pallet<T> {
 
     fn evm_confirm(
         origin: &T::RuntimeOrigin,
-        vacuum_evm_order: VacuumEVMOrder,
+        vacuum_evm_confirmation: VacuumEVMConfirmation,
+    ) -> Result<bool, DispatchError> {
+        <T as Config>::VacuumEVMApi::evm_confirm(origin, vacuum_evm_confirmation)
+    }
+
+    fn evm_submit_correctness_proof(
+        origin: &T::RuntimeOrigin,
+        vacuum_evm_proof: VacuumEVMProof,
     ) -> Result<bool, DispatchError> {
-        <T as Config>::VacuumEVMApi::evm_confirm(origin, vacuum_evm_order)
+        <T as Config>::VacuumEVMApi::evm_submit_correctness_proof(origin, vacuum_evm_proof)
     }
 
     fn evm_3d_order(
@@ -318,6 +398,10 @@ impl<T: Config> ThreeVm<T, BalanceOf<T>> for Pallet<T> {
     {
         let registry_contract = T::ContractsRegistry::fetch_contract_by_id(*id)
             .map_err(|_| Error::<T>::ContractNotFound)?;
+        ensure!(
+            !T::ContractsRegistry::is_deprecated(*id),
+            Error::<T>::ContractDeprecated
+        );
         let contract_len = registry_contract.bytes.len();
         let mut module = module_generator(registry_contract.bytes);
         module.set_author(registry_contract.author.clone());
@@ -376,9 +460,82 @@ impl<T: Config> ThreeVm<T, BalanceOf<T>> for Pallet<T> {
     fn try_remove_author(contract: &T::AccountId) -> Result<(), DispatchError> {
         if AuthorOf::<T>::contains_key(contract) {
             AuthorOf::<T>::remove(contract);
+            RegistryContractOf::<T>::remove(contract);
             Self::deposit_event(Event::<T>::AuthorRemoved(contract.clone()))
         }
 
         Ok(())
     }
+
+    fn try_persist_registry_contract(
+        contract: &T::AccountId,
+        registry_id: &T::Hash,
+    ) -> Result<(), DispatchError> {
+        if !RegistryContractOf::<T>::contains_key(contract) {
+            RegistryContractOf::<T>::insert(contract, registry_id);
+            Self::deposit_event(Event::<T>::RegistryContractStored((
+                contract.clone(),
+                *registry_id,
+            )))
+        }
+        Ok(())
+    }
+}
+
+impl<T: Config> Royalties<T::AccountId, BalanceOf<T>, T::Hash> for Pallet<T> {
+    fn accrue_royalty(
+        requester: &T::AccountId,
+        charge_id: T::Hash,
+        sfx_reward: BalanceOf<T>,
+        asset_id: Option<u32>,
+    ) -> BalanceOf<T> {
+        royalties::accrue_royalty::<T>(requester, charge_id, sfx_reward, asset_id).unwrap_or_else(
+            |err| {
+                Self::deposit_event(Event::<T>::RoyaltyPayoutFailed((
+                    requester.clone(),
+                    charge_id,
+                    err,
+                )));
+                Zero::zero()
+            },
+        )
+    }
+}
+
+impl<T: Config> OnGmpAcknowledged<T::AccountId> for Pallet<T> {
+    fn on_gmp_acknowledged(
+        callback: &GmpCallback<T::AccountId>,
+        _sender: &T::AccountId,
+        acknowledgement: &GmpAcknowledgement,
+    ) -> Weight {
+        let contract = match callback {
+            GmpCallback::Contract(contract) => contract,
+            GmpCallback::Pallet(_) => return Weight::zero(),
+        };
+        let inbox_len = GmpAcknowledgementCount::<T>::get(contract);
+        if inbox_len >= T::MaxGmpAcknowledgements::get() {
+            Self::deposit_event(Event::<T>::GmpAcknowledgementDropped((
+                contract.clone(),
+                acknowledgement.message_id,
+                acknowledgement.success,
+            )));
+            return T::DbWeight::get().reads_writes(1, 1)
+        }
+
+        GmpAcknowledgements::<T>::insert(contract, acknowledgement.message_id, acknowledgement);
+        GmpAcknowledgementCount::<T>::insert(contract, inbox_len.saturating_add(1));
+        Self::deposit_event(Event::<T>::GmpAcknowledgementDelivered((
+            contract.clone(),
+            acknowledgement.message_id,
+            acknowledgement.success,
+        )));
+        Self::on_gmp_acknowledged_max_weight(acknowledgement.return_data.len() as u32)
+    }
+
+    fn on_gmp_acknowledged_max_weight(return_data_len: u32) -> Weight {
+        // Writing the return data into the inbox
+        T::DbWeight::get()
+            .reads_writes(1, 3)
+            .saturating_add(Weight::from_parts(0, return_data_len.into()))
+    }
 }
@@ -239,8 +239,10 @@ impl pallet_3vm::Config for Test {
     type ContractsRegistry = ContractsRegistry;
     type Currency = Balances;
     type EscrowAccount = EscrowAccount;
+    type MaxGmpAcknowledgements = ConstU32<64>;
     type OnLocalTrigger = Circuit;
     type Portal = Portal;
+    type Rewards = RewardsMockApi;
     type RuntimeEvent = RuntimeEvent;
     type SignalBounceThreshold = ConstU32<2>;
     type VacuumEVMApi = Vacuum;
@@ -257,9 +259,12 @@ impl t3rn_primitives::rewards::RewardsWriteApi<AccountId32, u64, u32> for Reward
     ) -> bool {
         true
     }
+
+    fn accrue_royalty(_payee: &AccountId32, _amount: u64, _asset_id: Option<u32>) {}
 }
 
 impl pallet_circuit_vacuum::Config for Test {
+    type AccountManager = AccountManager;
     type AddressMapping = EvmAddressMapping<Test>;
     type CircuitSubmitAPI = Circuit;
     type Currency = Balances;
@@ -270,9 +275,24 @@ impl pallet_circuit_vacuum::Config for Test {
     type Xdns = Xdns;
 }
 
+parameter_types! {
+    pub const ContractDepositBase: Balance = 10;
+    pub const ContractDepositPerByte: Balance = 1;
+    pub const MaxCodeLength: u32 = 512 * 1024;
+    pub const MaxSourceLength: u32 = 512 * 1024;
+    pub const MaxPackageNameLength: u32 = 128;
+    pub const MaxReleasesPerPackage: u32 = 256;
+}
+
 impl pallet_contracts_registry::Config for Test {
     type Balances = Balances;
+    type ContractDepositBase = ContractDepositBase;
+    type ContractDepositPerByte = ContractDepositPerByte;
     type Currency = Balances;
+    type MaxCodeLength = MaxCodeLength;
+    type MaxPackageNameLength = MaxPackageNameLength;
+    type MaxReleasesPerPackage = MaxReleasesPerPackage;
+    type MaxSourceLength = MaxSourceLength;
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
 }
@@ -292,6 +312,8 @@ impl pallet_account_manager::Config for Test {
 
 parameter_types! {
     pub const CircuitAccountId: AccountId = AccountId32::new([33u8; 32]);
+    pub const GmpDepositPerByte: Balance = 1;
+    pub const GmpMessageTimeout: BlockNumber = 600;
 }
 
 impl pallet_circuit::Config for Test {
@@ -302,7 +324,12 @@ impl pallet_circuit::Config for Test {
     type Currency = Balances;
     type DeletionQueueLimit = ConstU32<1024>;
     type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
+    type GmpCallbacks = ThreeVm;
+    type GmpDepositPerByte = GmpDepositPerByte;
+    type GmpMessageTimeout = GmpMessageTimeout;
+    type PauseScopes = t3rn_primitives::maintenance::PauseScopesMock;
     type Portal = Portal;
+    type Royalties = ThreeVm;
     type RuntimeEvent = RuntimeEvent;
     type SFXBiddingPeriod = ConstU32<3>;
     type SelfAccountId = CircuitAccountId;
@@ -361,6 +388,13 @@ impl PalletAssetsOverlay<Test, Balance> for Test {
     }
 }
 
+parameter_types! {
+    pub const GatewayRegistrationDeposit: Balance = 100;
+    pub const GatewayChangeTimelock: BlockNumber = 10;
+    pub const MaxScheduledGatewayChanges: u32 = 100;
+    pub const MaxPendingGatewayChangesPerRegistrant: u32 = 10;
+}
+
 impl pallet_xdns::Config for Test {
     type AssetsOverlay = Test;
     type AttestersRead =
@@ -368,6 +402,10 @@ impl pallet_xdns::Config for Test {
     type Balances = Balances;
     type CircuitDLQ = Circuit;
     type Currency = Balances;
+    type GatewayChangeTimelock = GatewayChangeTimelock;
+    type GatewayRegistrationDeposit = GatewayRegistrationDeposit;
+    type MaxPendingGatewayChangesPerRegistrant = MaxPendingGatewayChangesPerRegistrant;
+    type MaxScheduledGatewayChanges = MaxScheduledGatewayChanges;
     type Portal = Portal;
     type RuntimeEvent = RuntimeEvent;
     type SelfGatewayId = CircuitTargetId;
@@ -468,6 +506,7 @@ impl pallet_portal::SelectLightClient<Test> for SelectLightClientRegistry {
 
 impl pallet_portal::Config for Test {
     type Currency = Balances;
+    type GatewayRegistrationOrigin = EnsureRoot<AccountId>;
     type RuntimeEvent = RuntimeEvent;
     type SelectLightClient = SelectLightClientRegistry;
     type WeightInfo = pallet_portal::weights::SubstrateWeight<Test>;
@@ -6,14 +6,16 @@ use sp_core::H160;
 use sp_std::prelude::*;
 use t3rn_primitives::{
     circuit::{
-        LocalTrigger, OnLocalTrigger, VacuumEVM3DOrder, VacuumEVMOrder, VacuumEVMProof,
-        VacuumEVMTeleportOrder,
+        LocalTrigger, OnLocalTrigger, VacuumEVM3DOrder, VacuumEVMConfirmation, VacuumEVMOrder,
+        VacuumEVMProof, VacuumEVMTeleportOrder,
     },
     execution_source_to_option,
     portal::{Portal, PrecompileArgs as PortalPrecompileArgs},
     threevm::{
         AddressMapping, GetState, LocalStateAccess, PrecompileArgs, PrecompileInvocation,
-        VacuumAccess, GET_STATE, PORTAL, POST_SIGNAL, SUBMIT,
+        VacuumAccess, GET_STATE, PORTAL, POST_SIGNAL, SUBMIT, VACUUM_3D_ORDER, VACUUM_CONFIRM,
+        VACUUM_ORDER, VACUUM_SUBMIT_CORRECTNESS_PROOF, VACUUM_SUBMIT_FAULT_PROOF,
+        VACUUM_TELEPORT_ORDER,
     },
     SpeedMode, T3rnCodec,
 };
@@ -176,11 +178,11 @@ pub(crate) fn invoke_raw<T: Config>(precompile: &u8, args: &mut &[u8], output: &
                 }
             },
             VACUUM_CONFIRM => {
-                let args: CodecResult<VacuumEVMOrder> = match codec {
+                let args: CodecResult<VacuumEVMConfirmation> = match codec {
                     T3rnCodec::Scale => Decode::decode(&mut &args[..]),
-                    T3rnCodec::Rlp => VacuumEVMOrder::from_rlp_encoded_packed(&args[..]).map_err(|e| {
-                        log::debug!(target: LOG_TARGET, "Failed to decode vacuum order: {:?}", e);
-                        codec::Error::from("Failed to decode vacuum order")
+                    T3rnCodec::Rlp => VacuumEVMConfirmation::from_rlp(&args[..]).map_err(|e| {
+                        log::debug!(target: LOG_TARGET, "Failed to decode vacuum confirmation: {:?}", e);
+                        codec::Error::from("Failed to decode vacuum confirmation")
                     }),
                 };
 
@@ -443,8 +445,9 @@ pub(crate) fn invoke<T: Config>(
             // let success = true;
             Ok(PrecompileInvocation::VacuumOrder(success))
         },
-        PrecompileArgs::VacuumConfirm(origin, vacuum_order) => {
-            let success = true;
+        PrecompileArgs::VacuumConfirm(origin, vacuum_confirmation) => {
+            let success =
+                <Pallet<T> as VacuumAccess<T>>::evm_confirm(&origin, vacuum_confirmation)?;
             Ok(PrecompileInvocation::VacuumConfirm(success))
         },
         PrecompileArgs::Vacuum3DOrder(origin, vacuum_order) => {
@@ -452,7 +455,10 @@ pub(crate) fn invoke<T: Config>(
             Ok(PrecompileInvocation::VacuumOrder(success))
         },
         PrecompileArgs::VacuumSubmitCorrectnessProof(origin, vacuum_proof) => {
-            let success = true;
+            let success = <Pallet<T> as VacuumAccess<T>>::evm_submit_correctness_proof(
+                &origin,
+                vacuum_proof,
+            )?;
             Ok(PrecompileInvocation::VacuumSubmitCorrectnessProof(success))
         },
         PrecompileArgs::VacuumSubmitFaultProof(origin, vacuum_proof) => {
@@ -516,7 +522,7 @@ mod tests {
     use crate::mock::{new_test_ext, AccountId, Test, ALICE};
     use sp_core::{H160, H256, U256};
     use sp_runtime::traits::Hash;
-    use t3rn_primitives::{circuit::LocalStateExecutionView, threevm::VACUUM_ORDER};
+    use t3rn_primitives::circuit::LocalStateExecutionView;
     use t3rn_sdk_primitives::{
         storage::BoundedVec,
         xc::{Chain, Operation},
@@ -568,6 +574,26 @@ mod tests {
             let mut out = Vec::<u8>::new();
 
             invoke_raw::<Test>(&244_u8, args, &mut out);
+            let res =
+                <core::result::Result<(), crate::Error<Test>> as Decode>::decode(&mut &out[..])
+                    .unwrap();
+            assert_eq!(res, Err(Error::<Test>::InvalidPrecompilePointer));
+        });
+    }
+
+    #[test]
+    fn invoke_raw_vacuum_confirm_rejects_short_rlp_args() {
+        new_test_ext().execute_with(|| {
+            let account = H160::from_low_u64_be(4);
+            let args = &mut &[
+                vec![T3rnCodec::Rlp.into()],
+                account.0.to_vec(),
+                vec![7u8; 31], // less than the 32 bytes of the SFX id
+            ]
+            .concat()[..];
+            let mut out = Vec::<u8>::new();
+
+            invoke_raw::<Test>(&VACUUM_CONFIRM, args, &mut out);
             let res =
                 <core::result::Result<(), crate::Error<Test>> as Decode>::decode(&mut &out[..])
                     .unwrap();
@@ -0,0 +1,60 @@
+use crate::{BalanceOf, Config, Event, Pallet, RegistryContractOf};
+use frame_support::sp_runtime::{
+    traits::{Saturating, Zero},
+    DispatchError,
+};
+use t3rn_primitives::{
+    account_manager::AccountManager, contracts_registry::ContractsRegistry,
+    rewards::RewardsWriteApi,
+};
+
+const LOG_TARGET: &str = "3vm::royalties";
+
+/// Takes the royalty of the registry contract of the contract instance `requester` out of the SFX
+/// reward held under `charge_id`, in the reward asset, and accrues the parts as pending claims of
+/// the payees.
+pub(crate) fn accrue_royalty<T: Config>(
+    requester: &T::AccountId,
+    charge_id: T::Hash,
+    sfx_reward: BalanceOf<T>,
+    asset_id: Option<u32>,
+) -> Result<BalanceOf<T>, DispatchError> {
+    let registry_id = match RegistryContractOf::<T>::get(requester) {
+        Some(registry_id) => registry_id,
+        None => return Ok(Zero::zero()),
+    };
+    let royalty = match T::ContractsRegistry::royalty_of(registry_id) {
+        Some(royalty) => royalty,
+        None => {
+            T::ContractsRegistry::record_usage(registry_id, Zero::zero(), asset_id);
+            return Ok(Zero::zero())
+        },
+    };
+
+    let parts = royalty.split(sfx_reward);
+    let total = parts.iter().fold(BalanceOf::<T>::zero(), |acc, (_, part)| {
+        acc.saturating_add(*part)
+    });
+
+    if !total.is_zero() {
+        T::AccountManager::withhold_from_deposit(charge_id, total)?;
+        for (payee, part) in parts {
+            T::Rewards::accrue_royalty(&payee, part, asset_id);
+        }
+        log::debug!(
+            target: LOG_TARGET,
+            "Took {:?} royalty of {:?} out of the reward of {:?}",
+            total,
+            registry_id,
+            charge_id
+        );
+        Pallet::<T>::deposit_event(Event::<T>::RoyaltyCharged((
+            requester.clone(),
+            registry_id,
+            total,
+        )));
+    }
+    T::ContractsRegistry::record_usage(registry_id, total, asset_id);
+
+    Ok(total)
+}
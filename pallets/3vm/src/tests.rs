@@ -42,3 +42,229 @@ fn test_substrate_boundec_vec_compatibility() {
         <t3rn_sdk_primitives::storage::BoundedVec::<Chain::<AccountId32, u128, [u8; 32]>, 3> as MaxEncodedLen>::max_encoded_len()
     );
 }
+
+#[test]
+fn royalty_is_taken_out_of_the_sfx_reward_of_registry_contract_instances_only() {
+    use frame_support::traits::Currency;
+    use sp_core::H256;
+    use sp_runtime::Percent;
+    use t3rn_primitives::{
+        account_manager::{AccountManager as AccountManagerT, RequestCharge},
+        claimable::{BenefitSource, CircuitRole},
+        contracts_registry::{AuthorInfo, RegistryContract, Royalties, RoyaltyConfig},
+        threevm::ThreeVm as ThreeVmT,
+    };
+
+    new_test_ext().execute_with(|| {
+        let contract = RegistryContract {
+            code_txt: b"royalty code".to_vec(),
+            author: AuthorInfo::new(ALICE, None),
+            ..Default::default()
+        };
+        let registry_id = contract.generate_id::<Test>();
+        pallet_contracts_registry::ContractsRegistry::<Test>::insert(registry_id, contract);
+        pallet_contracts_registry::Royalties::<Test>::insert(
+            registry_id,
+            RoyaltyConfig {
+                rate: Percent::from_percent(10),
+                payees: vec![(ALICE, Percent::from_percent(100))],
+            },
+        );
+        Balances::make_free_balance_be(&BOB, 10_000);
+        Balances::make_free_balance_be(&CHARLIE, 10_000);
+        let sfx_reward = |requester: AccountId32| RequestCharge {
+            payee: requester,
+            offered_reward: 1_000,
+            charge_fee: 0,
+            source: BenefitSource::TrafficFees,
+            role: CircuitRole::Executor,
+            recipient: Some(DJANGO),
+            maybe_asset_id: None,
+        };
+        let (bob_charge, charlie_charge) = (H256::repeat_byte(1), H256::repeat_byte(2));
+        assert!(<AccountManager as AccountManagerT<_, _, _, _, _>>::deposit(
+            bob_charge,
+            sfx_reward(BOB)
+        )
+        .is_ok());
+        assert!(<AccountManager as AccountManagerT<_, _, _, _, _>>::deposit(
+            charlie_charge,
+            sfx_reward(CHARLIE)
+        )
+        .is_ok());
+
+        assert_eq!(ThreeVm::accrue_royalty(&BOB, bob_charge, 1_000, None), 0);
+
+        assert!(ThreeVm::try_persist_registry_contract(&BOB, &registry_id).is_ok());
+        assert_eq!(ThreeVm::accrue_royalty(&BOB, bob_charge, 1_000, None), 100);
+        assert_eq!(
+            ThreeVm::accrue_royalty(&CHARLIE, charlie_charge, 1_000, None),
+            0
+        );
+
+        // The requesters pay no more than the SFX rewards, the executor is paid less the royalty
+        assert_eq!(Balances::free_balance(&BOB), 9_000);
+        assert_eq!(Balances::free_balance(&CHARLIE), 9_000);
+        assert_eq!(
+            <AccountManager as AccountManagerT<_, _, _, _, _>>::get_charge_or_fail(bob_charge)
+                .unwrap()
+                .offered_reward,
+            900
+        );
+        assert_eq!(
+            <AccountManager as AccountManagerT<_, _, _, _, _>>::get_charge_or_fail(charlie_charge)
+                .unwrap()
+                .offered_reward,
+            1_000
+        );
+
+        let usage = pallet_contracts_registry::ContractUsages::<Test>::get(registry_id);
+        assert_eq!(usage.uses, 1);
+        assert_eq!(usage.earnings, vec![(None, 100)]);
+    });
+}
+
+#[test]
+fn royalty_payout_failure_is_surfaced_as_an_event() {
+    use sp_core::H256;
+    use sp_runtime::Percent;
+    use t3rn_primitives::{
+        contracts_registry::{AuthorInfo, RegistryContract, Royalties, RoyaltyConfig},
+        threevm::ThreeVm as ThreeVmT,
+    };
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let contract = RegistryContract {
+            code_txt: b"royalty code".to_vec(),
+            author: AuthorInfo::new(ALICE, None),
+            ..Default::default()
+        };
+        let registry_id = contract.generate_id::<Test>();
+        pallet_contracts_registry::ContractsRegistry::<Test>::insert(registry_id, contract);
+        pallet_contracts_registry::Royalties::<Test>::insert(
+            registry_id,
+            RoyaltyConfig {
+                rate: Percent::from_percent(10),
+                payees: vec![(ALICE, Percent::from_percent(100))],
+            },
+        );
+        assert!(ThreeVm::try_persist_registry_contract(&BOB, &registry_id).is_ok());
+
+        // No SFX reward is held under the charge to take the royalty out of
+        let charge_id = H256::repeat_byte(1);
+        assert_eq!(ThreeVm::accrue_royalty(&BOB, charge_id, 1_000, None), 0);
+        System::assert_last_event(RuntimeEvent::ThreeVm(
+            crate::Event::<Test>::RoyaltyPayoutFailed((
+                BOB,
+                charge_id,
+                pallet_account_manager::Error::<Test>::NoChargeOfGivenIdRegistered.into(),
+            )),
+        ));
+        assert_eq!(
+            pallet_contracts_registry::ContractUsages::<Test>::get(registry_id).uses,
+            0
+        );
+    });
+}
+
+#[test]
+fn gmp_acknowledgement_is_delivered_to_the_inbox_of_contract_callbacks_only() {
+    use sp_core::H256;
+    use t3rn_primitives::gmp::{GmpAcknowledgement, GmpCallback, OnGmpAcknowledged};
+
+    new_test_ext().execute_with(|| {
+        let acknowledgement = GmpAcknowledgement {
+            message_id: H256::repeat_byte(1),
+            nonce: 0,
+            success: true,
+            return_data: b"return data".to_vec(),
+        };
+
+        ThreeVm::on_gmp_acknowledged(&GmpCallback::Pallet(*b"hook"), &BOB, &acknowledgement);
+        assert_eq!(
+            ThreeVm::gmp_acknowledgement_of(&BOB, H256::repeat_byte(1)),
+            None
+        );
+
+        ThreeVm::on_gmp_acknowledged(&GmpCallback::Contract(BOB), &BOB, &acknowledgement);
+        assert_eq!(
+            ThreeVm::gmp_acknowledgement_of(&BOB, H256::repeat_byte(1)),
+            Some(acknowledgement)
+        );
+    });
+}
+
+#[test]
+fn gmp_acknowledgements_are_kept_up_to_the_inbox_bound_until_pruned() {
+    use frame_support::{assert_noop, assert_ok, traits::Get};
+    use sp_core::H256;
+    use t3rn_primitives::gmp::{GmpAcknowledgement, GmpCallback, OnGmpAcknowledged};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let acknowledgement = |id: u64| GmpAcknowledgement {
+            message_id: H256::from_low_u64_be(id),
+            nonce: id,
+            success: true,
+            return_data: vec![],
+        };
+        let max_acknowledgements: u32 = <Test as crate::Config>::MaxGmpAcknowledgements::get();
+
+        for id in 0..max_acknowledgements as u64 {
+            ThreeVm::on_gmp_acknowledged(&GmpCallback::Contract(BOB), &BOB, &acknowledgement(id));
+        }
+        assert_eq!(
+            ThreeVm::gmp_acknowledgement_count_of(&BOB),
+            max_acknowledgements
+        );
+
+        let overflowing_id = max_acknowledgements as u64;
+        ThreeVm::on_gmp_acknowledged(
+            &GmpCallback::Contract(BOB),
+            &BOB,
+            &acknowledgement(overflowing_id),
+        );
+        assert_eq!(
+            ThreeVm::gmp_acknowledgement_of(&BOB, H256::from_low_u64_be(overflowing_id)),
+            None
+        );
+        System::assert_last_event(RuntimeEvent::ThreeVm(
+            crate::Event::<Test>::GmpAcknowledgementDropped((
+                BOB,
+                H256::from_low_u64_be(overflowing_id),
+                true,
+            )),
+        ));
+
+        assert_noop!(
+            ThreeVm::prune_gmp_acknowledgement(
+                RuntimeOrigin::signed(ALICE),
+                H256::from_low_u64_be(0)
+            ),
+            crate::Error::<Test>::GmpAcknowledgementNotFound
+        );
+        assert_ok!(ThreeVm::prune_gmp_acknowledgement(
+            RuntimeOrigin::signed(BOB),
+            H256::from_low_u64_be(0)
+        ));
+        assert_eq!(
+            ThreeVm::gmp_acknowledgement_of(&BOB, H256::from_low_u64_be(0)),
+            None
+        );
+        assert_eq!(
+            ThreeVm::gmp_acknowledgement_count_of(&BOB),
+            max_acknowledgements - 1
+        );
+
+        ThreeVm::on_gmp_acknowledged(
+            &GmpCallback::Contract(BOB),
+            &BOB,
+            &acknowledgement(overflowing_id),
+        );
+        assert_eq!(
+            ThreeVm::gmp_acknowledgement_of(&BOB, H256::from_low_u64_be(overflowing_id)),
+            Some(acknowledgement(overflowing_id))
+        );
+    });
+}
@@ -0,0 +1,619 @@
+use crate::{
+    monetary::Monetary, AssetIdOf, BalanceOf, Config, Error, Event, InsuranceAssetCaps,
+    InsuranceGatewayCaps, InsurancePools, InsuranceShares, Pallet, PendingInsuranceWithdrawals,
+    TargetId,
+};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{Currency, ExistenceRequirement, Get, Imbalance},
+};
+use sp_runtime::{
+    traits::{AccountIdConversion, CheckedAdd, CheckedDiv, CheckedMul, One, Saturating, Zero},
+    ArithmeticError,
+};
+use t3rn_primitives::account_manager::PendingInsuranceWithdrawal;
+
+// Insurance pools hold the liquidity of their providers on a sub-account of the insurance pallet id per asset,
+//  so the liquidity of each pool is backed by the balance of its account alone.
+//  Providers own the pool pro rata to their shares, so the slice of SFX rewards paid to the pool raises the value of each share,
+//  while the top-ups of requester compensations lower it. Withdrawals wait out a notice period, so the providers
+//  can't collect the yield of large commits and leave ahead of the top-ups.
+impl<T: Config> Pallet<T> {
+    pub fn do_deposit_insurance(
+        provider: &T::AccountId,
+        asset_id: Option<AssetIdOf<T>>,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        ensure!(!amount.is_zero(), Error::<T>::InsuranceAmountZero);
+
+        let mut pool = InsurancePools::<T>::get(&asset_id);
+        let shares = if pool.liquidity.is_zero() {
+            // Shares of a drained pool are worthless, so they are diluted away by the first new deposit
+            amount.checked_mul(&pool.total_shares.max(One::one()))
+        } else {
+            amount
+                .checked_mul(&pool.total_shares)
+                .and_then(|value| value.checked_div(&pool.liquidity))
+        }
+        .ok_or(ArithmeticError::Overflow)?;
+        ensure!(!shares.is_zero(), Error::<T>::InsuranceAmountZero);
+
+        pool.liquidity = pool
+            .liquidity
+            .checked_add(&amount)
+            .ok_or(ArithmeticError::Overflow)?;
+        pool.total_shares = pool
+            .total_shares
+            .checked_add(&shares)
+            .ok_or(ArithmeticError::Overflow)?;
+
+        Self::transfer_into_insurance_pool(provider, &asset_id, amount)?;
+
+        InsuranceShares::<T>::mutate(&asset_id, provider, |provider_shares| {
+            *provider_shares = provider_shares.saturating_add(shares)
+        });
+        InsurancePools::<T>::insert(&asset_id, pool);
+
+        Self::deposit_event(Event::InsuranceDeposited {
+            provider: provider.clone(),
+            asset_id,
+            amount,
+            shares,
+        });
+
+        Ok(())
+    }
+
+    /// Put the provider's shares on the withdrawal notice, valued at the current price of the pool's shares.
+    /// Further requests add up and restart the notice.
+    pub fn do_withdraw_insurance(
+        provider: &T::AccountId,
+        asset_id: Option<AssetIdOf<T>>,
+        shares: BalanceOf<T>,
+    ) -> DispatchResult {
+        ensure!(!shares.is_zero(), Error::<T>::InsuranceAmountZero);
+        let mut pending = PendingInsuranceWithdrawals::<T>::get(&asset_id, provider).unwrap_or(
+            PendingInsuranceWithdrawal {
+                shares: Zero::zero(),
+                amount: Zero::zero(),
+                unlock_at: Zero::zero(),
+            },
+        );
+        let unnoticed_shares =
+            InsuranceShares::<T>::get(&asset_id, provider).saturating_sub(pending.shares);
+        ensure!(
+            shares <= unnoticed_shares,
+            Error::<T>::InsufficientInsuranceShares
+        );
+
+        let pool = InsurancePools::<T>::get(&asset_id);
+        let amount = shares
+            .checked_mul(&pool.liquidity)
+            .and_then(|value| value.checked_div(&pool.total_shares))
+            .ok_or(ArithmeticError::Overflow)?;
+
+        pending.shares = pending.shares.saturating_add(shares);
+        pending.amount = pending.amount.saturating_add(amount);
+        pending.unlock_at = frame_system::Pallet::<T>::block_number()
+            .saturating_add(T::InsuranceWithdrawalNoticePeriod::get());
+        PendingInsuranceWithdrawals::<T>::insert(&asset_id, provider, pending.clone());
+
+        Self::deposit_event(Event::InsuranceWithdrawalRequested {
+            provider: provider.clone(),
+            asset_id,
+            amount: pending.amount,
+            shares: pending.shares,
+            unlock_at: pending.unlock_at,
+        });
+
+        Ok(())
+    }
+
+    /// Pay out the shares past their withdrawal notice, at the lower of their values when requested and now.
+    pub fn do_claim_insurance_withdrawal(
+        provider: &T::AccountId,
+        asset_id: Option<AssetIdOf<T>>,
+    ) -> DispatchResult {
+        let pending = PendingInsuranceWithdrawals::<T>::get(&asset_id, provider)
+            .ok_or(Error::<T>::NoPendingInsuranceWithdrawal)?;
+        ensure!(
+            frame_system::Pallet::<T>::block_number() >= pending.unlock_at,
+            Error::<T>::InsuranceWithdrawalNotDue
+        );
+        let shares = pending.shares;
+        let provider_shares = InsuranceShares::<T>::get(&asset_id, provider);
+
+        let mut pool = InsurancePools::<T>::get(&asset_id);
+        let value = shares
+            .checked_mul(&pool.liquidity)
+            .and_then(|value| value.checked_div(&pool.total_shares))
+            .ok_or(ArithmeticError::Overflow)?;
+        // The last provider leaving takes whatever is left in the pool, as there are no shares left to hold it
+        let amount = if shares == pool.total_shares {
+            value
+        } else {
+            value.min(pending.amount)
+        };
+
+        if !amount.is_zero() {
+            Self::transfer_out_of_insurance_pool(provider, &asset_id, amount)
+                .map_err(|_| Error::<T>::InsufficientInsurancePoolLiquidity)?;
+        }
+
+        pool.liquidity = pool.liquidity.saturating_sub(amount);
+        pool.total_shares = pool.total_shares.saturating_sub(shares);
+        if pool.total_shares.is_zero() {
+            InsurancePools::<T>::remove(&asset_id);
+        } else {
+            InsurancePools::<T>::insert(&asset_id, pool);
+        }
+        if shares >= provider_shares {
+            InsuranceShares::<T>::remove(&asset_id, provider);
+        } else {
+            InsuranceShares::<T>::insert(&asset_id, provider, provider_shares - shares);
+        }
+        PendingInsuranceWithdrawals::<T>::remove(&asset_id, provider);
+
+        Self::deposit_event(Event::InsuranceWithdrawn {
+            provider: provider.clone(),
+            asset_id,
+            amount,
+            shares,
+        });
+
+        Ok(())
+    }
+
+    /// Pay the insurance pool of the asset its share of the committed SFX reward, as long as the pool has providers.
+    /// Returns the amount paid to the pool.
+    pub fn reward_insurance_pool(
+        asset_id: Option<AssetIdOf<T>>,
+        reward: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let mut pool = InsurancePools::<T>::get(&asset_id);
+        if pool.total_shares.is_zero() {
+            return Zero::zero()
+        }
+        let pool_account = Self::insurance_pool_account(&asset_id);
+        let pool_reward = T::InsurancePoolRewardShare::get().mul_floor(reward);
+        // Native rewards below the existential deposit of the pool's account aren't credited
+        let pool_reward = match asset_id {
+            None => T::Currency::deposit_creating(&pool_account, pool_reward).peek(),
+            Some(_) => {
+                Monetary::<T::AccountId, T::Assets, T::Currency, T::AssetBalanceOf>::deposit(
+                    &pool_account,
+                    asset_id.clone(),
+                    pool_reward,
+                );
+                pool_reward
+            },
+        };
+        if pool_reward.is_zero() {
+            return Zero::zero()
+        }
+
+        pool.liquidity = pool.liquidity.saturating_add(pool_reward);
+        InsurancePools::<T>::insert(&asset_id, pool);
+
+        Self::deposit_event(Event::InsurancePoolRewarded {
+            asset_id,
+            amount: pool_reward,
+        });
+
+        pool_reward
+    }
+
+    /// Top up the beneficiary out of the insurance pool of the asset by the shortfall, capped by the caps of both the gateway and the asset.
+    /// Gateways or assets without a cap set aren't covered by the pools.
+    pub fn do_compensate_from_insurance_pool(
+        beneficiary: &T::AccountId,
+        target: TargetId,
+        shortfall: BalanceOf<T>,
+        asset_id: Option<AssetIdOf<T>>,
+    ) -> BalanceOf<T> {
+        let cap = match (
+            InsuranceGatewayCaps::<T>::get(target),
+            InsuranceAssetCaps::<T>::get(&asset_id),
+        ) {
+            (Some(gateway_cap), Some(asset_cap)) => gateway_cap.min(asset_cap),
+            _ => return Zero::zero(),
+        };
+
+        let mut pool = InsurancePools::<T>::get(&asset_id);
+        let compensation = shortfall.min(cap).min(pool.liquidity);
+        if compensation.is_zero() {
+            return Zero::zero()
+        }
+
+        if let Err(err) = Self::transfer_out_of_insurance_pool(beneficiary, &asset_id, compensation)
+        {
+            log::error!(
+                "Insurance pool failed to compensate {:?} for target {:?} with {:?}: {:?}",
+                beneficiary,
+                target,
+                compensation,
+                err
+            );
+            return Zero::zero()
+        }
+        pool.liquidity = pool.liquidity.saturating_sub(compensation);
+        InsurancePools::<T>::insert(&asset_id, pool);
+
+        Self::deposit_event(Event::InsuranceCompensationPaid {
+            beneficiary: beneficiary.clone(),
+            target,
+            asset_id,
+            amount: compensation,
+        });
+
+        compensation
+    }
+
+    pub fn insurance_pool_account(asset_id: &Option<AssetIdOf<T>>) -> T::AccountId {
+        T::InsurancePalletId::get().into_sub_account_truncating(asset_id)
+    }
+
+    fn transfer_into_insurance_pool(
+        provider: &T::AccountId,
+        asset_id: &Option<AssetIdOf<T>>,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        let pool_account = Self::insurance_pool_account(asset_id);
+        match asset_id {
+            None => T::Currency::transfer(
+                provider,
+                &pool_account,
+                amount,
+                ExistenceRequirement::KeepAlive,
+            ),
+            Some(_) => {
+                Monetary::<T::AccountId, T::Assets, T::Currency, T::AssetBalanceOf>::withdraw(
+                    provider,
+                    amount,
+                    asset_id.clone(),
+                )?;
+                Monetary::<T::AccountId, T::Assets, T::Currency, T::AssetBalanceOf>::deposit(
+                    &pool_account,
+                    asset_id.clone(),
+                    amount,
+                );
+                Ok(())
+            },
+        }
+    }
+
+    fn transfer_out_of_insurance_pool(
+        beneficiary: &T::AccountId,
+        asset_id: &Option<AssetIdOf<T>>,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        let pool_account = Self::insurance_pool_account(asset_id);
+        match asset_id {
+            // The pool's account is reaped once drained, as nothing else is held on it
+            None => T::Currency::transfer(
+                &pool_account,
+                beneficiary,
+                amount,
+                ExistenceRequirement::AllowDeath,
+            ),
+            Some(_) => {
+                Monetary::<T::AccountId, T::Assets, T::Currency, T::AssetBalanceOf>::withdraw(
+                    &pool_account,
+                    amount,
+                    asset_id.clone(),
+                )?;
+                Monetary::<T::AccountId, T::Assets, T::Currency, T::AssetBalanceOf>::deposit(
+                    beneficiary,
+                    asset_id.clone(),
+                    amount,
+                );
+                Ok(())
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use circuit_mock_runtime::*;
+    use frame_support::{
+        assert_err, assert_ok,
+        traits::{Currency, Get},
+    };
+    use sp_core::H256;
+    use t3rn_primitives::{
+        account_manager::{
+            AccountManager as AccountManagerExt, InsurancePool, Outcome,
+            PendingInsuranceWithdrawal, RequestCharge,
+        },
+        claimable::{BenefitSource, CircuitRole},
+        common::RoundInfo,
+        Balance,
+    };
+
+    const DEFAULT_BALANCE: Balance = 1_000_000;
+    const TARGET: [u8; 4] = [1, 1, 1, 1];
+    const NATIVE: Option<AssetId> = None;
+    const NOTICE_PERIOD: BlockNumber = 10;
+
+    fn setup_providers() {
+        let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+        let _ = Balances::deposit_creating(&BOB, DEFAULT_BALANCE);
+    }
+
+    fn compensate(beneficiary: &AccountId, shortfall: Balance) -> Balance {
+        <AccountManager as AccountManagerExt<AccountId, Balance, Hash, BlockNumber, AssetId>>::compensate_from_insurance_pool(
+            beneficiary,
+            TARGET,
+            shortfall,
+            None,
+        )
+    }
+
+    #[test]
+    fn insurance_providers_own_the_pool_pro_rata_to_their_shares() {
+        ExtBuilder::default().build().execute_with(|| {
+            setup_providers();
+
+            assert_ok!(AccountManager::deposit_insurance(
+                RuntimeOrigin::signed(ALICE),
+                None,
+                1000
+            ));
+            // Yield raises the value of shares
+            assert_eq!(AccountManager::reward_insurance_pool(NATIVE, 10_000), 1000);
+            assert_ok!(AccountManager::deposit_insurance(
+                RuntimeOrigin::signed(BOB),
+                None,
+                1000
+            ));
+
+            assert_eq!(
+                AccountManager::insurance_pools(NATIVE),
+                InsurancePool {
+                    liquidity: 3000,
+                    total_shares: 1500,
+                }
+            );
+            assert_eq!(AccountManager::insurance_shares(NATIVE, &BOB), 500);
+            assert_eq!(
+                Balances::free_balance(&AccountManager::insurance_pool_account(&NATIVE)),
+                3000
+            );
+
+            assert_err!(
+                AccountManager::withdraw_insurance(RuntimeOrigin::signed(BOB), None, 501),
+                pallet_account_manager::Error::<Runtime>::InsufficientInsuranceShares
+            );
+            assert_ok!(AccountManager::withdraw_insurance(
+                RuntimeOrigin::signed(ALICE),
+                None,
+                1000
+            ));
+            System::set_block_number(System::block_number() + NOTICE_PERIOD);
+            assert_ok!(AccountManager::claim_insurance_withdrawal(
+                RuntimeOrigin::signed(ALICE),
+                None
+            ));
+            assert_eq!(Balances::free_balance(&ALICE), DEFAULT_BALANCE + 1000);
+            assert_eq!(AccountManager::insurance_shares(NATIVE, &ALICE), 0);
+            assert_eq!(
+                AccountManager::insurance_pools(NATIVE),
+                InsurancePool {
+                    liquidity: 1000,
+                    total_shares: 500,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn insurance_withdrawals_stay_exposed_to_top_ups_without_yield_until_the_notice_ends() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            setup_providers();
+            assert_ok!(AccountManager::set_gateway_insurance_cap(
+                RuntimeOrigin::root(),
+                TARGET,
+                Some(1000)
+            ));
+            assert_ok!(AccountManager::set_asset_insurance_cap(
+                RuntimeOrigin::root(),
+                None,
+                Some(1000)
+            ));
+            for provider in [ALICE, BOB] {
+                assert_ok!(AccountManager::deposit_insurance(
+                    RuntimeOrigin::signed(provider),
+                    None,
+                    1000
+                ));
+            }
+
+            assert_err!(
+                AccountManager::claim_insurance_withdrawal(RuntimeOrigin::signed(ALICE), None),
+                pallet_account_manager::Error::<Runtime>::NoPendingInsuranceWithdrawal
+            );
+            assert_ok!(AccountManager::withdraw_insurance(
+                RuntimeOrigin::signed(ALICE),
+                None,
+                600
+            ));
+            System::set_block_number(5);
+            assert_ok!(AccountManager::withdraw_insurance(
+                RuntimeOrigin::signed(ALICE),
+                None,
+                400
+            ));
+            // Shares already on notice can't be requested again
+            assert_err!(
+                AccountManager::withdraw_insurance(RuntimeOrigin::signed(ALICE), None, 1),
+                pallet_account_manager::Error::<Runtime>::InsufficientInsuranceShares
+            );
+            assert_eq!(
+                AccountManager::pending_insurance_withdrawals(NATIVE, &ALICE),
+                Some(PendingInsuranceWithdrawal {
+                    shares: 1000,
+                    amount: 1000,
+                    unlock_at: 5 + NOTICE_PERIOD,
+                })
+            );
+
+            // Top-ups paid out on notice lower the value of the shares on their way out
+            assert_eq!(compensate(&CHARLIE, 500), 500);
+            System::set_block_number(5 + NOTICE_PERIOD - 1);
+            assert_err!(
+                AccountManager::claim_insurance_withdrawal(RuntimeOrigin::signed(ALICE), None),
+                pallet_account_manager::Error::<Runtime>::InsuranceWithdrawalNotDue
+            );
+            System::set_block_number(5 + NOTICE_PERIOD);
+            assert_ok!(AccountManager::claim_insurance_withdrawal(
+                RuntimeOrigin::signed(ALICE),
+                None
+            ));
+            assert_eq!(Balances::free_balance(&ALICE), DEFAULT_BALANCE - 250);
+            assert_eq!(
+                AccountManager::pending_insurance_withdrawals(NATIVE, &ALICE),
+                None
+            );
+
+            // Yield earned on notice stays with the providers remaining in the pool
+            assert_ok!(AccountManager::withdraw_insurance(
+                RuntimeOrigin::signed(BOB),
+                None,
+                500
+            ));
+            assert_eq!(AccountManager::reward_insurance_pool(NATIVE, 10_000), 1000);
+            System::set_block_number(5 + 2 * NOTICE_PERIOD);
+            assert_ok!(AccountManager::claim_insurance_withdrawal(
+                RuntimeOrigin::signed(BOB),
+                None
+            ));
+            assert_eq!(Balances::free_balance(&BOB), DEFAULT_BALANCE - 1000 + 375);
+            assert_eq!(
+                AccountManager::insurance_pools(NATIVE),
+                InsurancePool {
+                    liquidity: 1375,
+                    total_shares: 500,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn insurance_pool_tops_up_compensation_within_gateway_and_asset_caps() {
+        ExtBuilder::default().build().execute_with(|| {
+            setup_providers();
+            assert_ok!(AccountManager::deposit_insurance(
+                RuntimeOrigin::signed(ALICE),
+                None,
+                1000
+            ));
+
+            // Gateways and assets aren't covered until both of their caps are set
+            assert_eq!(compensate(&CHARLIE, 100), 0);
+            assert_ok!(AccountManager::set_gateway_insurance_cap(
+                RuntimeOrigin::root(),
+                TARGET,
+                Some(300)
+            ));
+            assert_eq!(compensate(&CHARLIE, 100), 0);
+            assert_ok!(AccountManager::set_asset_insurance_cap(
+                RuntimeOrigin::root(),
+                None,
+                Some(200)
+            ));
+
+            assert_eq!(compensate(&CHARLIE, 100), 100);
+            assert_eq!(compensate(&CHARLIE, 1000), 200);
+            assert_eq!(Balances::free_balance(&CHARLIE), 300);
+            assert_eq!(AccountManager::insurance_pools(NATIVE).liquidity, 700);
+            System::assert_last_event(RuntimeEvent::AccountManager(
+                pallet_account_manager::Event::InsuranceCompensationPaid {
+                    beneficiary: CHARLIE,
+                    target: TARGET,
+                    asset_id: None,
+                    amount: 200,
+                },
+            ));
+
+            // Compensations never exceed the pool's liquidity
+            assert_ok!(AccountManager::set_asset_insurance_cap(
+                RuntimeOrigin::root(),
+                None,
+                Some(5000)
+            ));
+            assert_ok!(AccountManager::set_gateway_insurance_cap(
+                RuntimeOrigin::root(),
+                TARGET,
+                Some(5000)
+            ));
+            assert_eq!(compensate(&CHARLIE, 1000), 700);
+            assert_eq!(AccountManager::insurance_pools(NATIVE).liquidity, 0);
+            assert_eq!(
+                Balances::free_balance(&AccountManager::insurance_pool_account(&NATIVE)),
+                0
+            );
+        });
+    }
+
+    #[test]
+    fn committed_sfx_rewards_pay_the_insurance_pool_only_with_providers() {
+        ExtBuilder::default().build().execute_with(|| {
+            setup_providers();
+            let reward_share =
+                <Runtime as pallet_account_manager::Config>::InsurancePoolRewardShare::get();
+
+            let commit_sfx_reward = |charge_id: H256| {
+                assert_ok!(<AccountManager as AccountManagerExt<
+                    AccountId,
+                    Balance,
+                    Hash,
+                    BlockNumber,
+                    AssetId,
+                >>::deposit(
+                    charge_id,
+                    RequestCharge {
+                        payee: ALICE,
+                        offered_reward: 1000,
+                        charge_fee: 0,
+                        source: BenefitSource::TrafficFees,
+                        role: CircuitRole::Executor,
+                        recipient: Some(CHARLIE),
+                        maybe_asset_id: None
+                    }
+                ));
+                assert!(<AccountManager as AccountManagerExt<
+                    AccountId,
+                    Balance,
+                    Hash,
+                    BlockNumber,
+                    AssetId,
+                >>::finalize_infallible(
+                    charge_id, Outcome::Commit
+                ));
+                AccountManager::settlements_per_round::<RoundInfo<BlockNumber>, H256>(
+                    Default::default(),
+                    charge_id,
+                )
+                .unwrap()
+                .settlement_amount
+            };
+
+            assert_eq!(commit_sfx_reward(H256::repeat_byte(1)), 1000);
+
+            assert_ok!(AccountManager::deposit_insurance(
+                RuntimeOrigin::signed(BOB),
+                None,
+                1000
+            ));
+            let pool_reward = reward_share.mul_floor(1000 as Balance);
+            assert_eq!(commit_sfx_reward(H256::repeat_byte(2)), 1000 - pool_reward);
+            assert_eq!(
+                AccountManager::insurance_pools(NATIVE).liquidity,
+                1000 + pool_reward
+            );
+        });
+    }
+}
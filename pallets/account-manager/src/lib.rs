@@ -12,7 +12,7 @@ use frame_support::{
     pallet_prelude::Weight,
     traits::{fungibles::Inspect, Currency, Get},
 };
-use sp_runtime::traits::Convert;
+use sp_runtime::{traits::Convert, Percent};
 
 use t3rn_primitives::{
     account_manager::{AccountManager, Outcome},
@@ -29,6 +29,7 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod insurance;
 pub mod manager;
 pub mod monetary;
 pub mod transaction;
@@ -39,6 +40,11 @@ reexport_currency_types!();
 pub type AssetsBalanceOf<T> =
     <<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
+pub type AssetIdOf<T> =
+    <<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
+
+pub type TargetId = [u8; 4];
+
 // Definition of the pallet logic, to be aggregated at runtime definition through
 // `construct_runtime`.
 #[frame_support::pallet]
@@ -50,10 +56,14 @@ pub mod pallet {
     use frame_support::{
         pallet_prelude::*,
         traits::{tokens::fungibles::Unbalanced, Currency, ReservableCurrency},
+        PalletId,
     };
     use frame_system::pallet_prelude::*;
 
-    use t3rn_primitives::account_manager::{ExecutionId, RequestCharge, Settlement};
+    use t3rn_primitives::account_manager::{
+        ExecutionId, ExecutorFeeSponsor, InsurancePool, PendingInsuranceWithdrawal, RequestCharge,
+        Settlement,
+    };
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -80,6 +90,26 @@ pub mod pallet {
         type AssetBalanceOf: Convert<BalanceOf<Self>, AssetsBalanceOf<Self>>;
 
         type AssetId: FullCodec + Copy + MaybeSerializeDeserialize + Debug + Default + Eq + TypeInfo;
+
+        /// Id of the pallet whose sub-accounts, one per asset, hold the liquidity of the insurance pools
+        #[pallet::constant]
+        type InsurancePalletId: Get<PalletId>;
+
+        /// Slice of the committed SFX rewards paid to the insurance pool of the reward asset as the providers' yield
+        #[pallet::constant]
+        type InsurancePoolRewardShare: Get<Percent>;
+
+        /// Blocks insurance providers wait for their withdrawals, staying exposed to the top-ups paid out of the pool meanwhile
+        #[pallet::constant]
+        type InsuranceWithdrawalNoticePeriod: Get<BlockNumberFor<Self>>;
+
+        /// Sponsor of the transaction fees of executors' calls, charged instead of the executor by the currency adapter
+        type ExecutorFeeSponsor: ExecutorFeeSponsor<
+            Self::AccountId,
+            BalanceOf<Self>,
+            Self::Hash,
+            <Self as frame_system::Config>::RuntimeCall,
+        >;
     }
 
     // Simple declaration of the `Pallet` type. It is placeholder we use to implement traits and
@@ -119,6 +149,52 @@ pub mod pallet {
         >,
     >;
 
+    #[pallet::storage]
+    #[pallet::getter(fn insurance_pools)]
+    pub type InsurancePools<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Option<AssetIdOf<T>>,
+        InsurancePool<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn insurance_shares)]
+    pub type InsuranceShares<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        Option<AssetIdOf<T>>,
+        Blake2_128Concat,
+        T::AccountId, // Liquidity provider
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pending_insurance_withdrawals)]
+    pub type PendingInsuranceWithdrawals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        Option<AssetIdOf<T>>,
+        Blake2_128Concat,
+        T::AccountId, // Liquidity provider
+        PendingInsuranceWithdrawal<BalanceOf<T>, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Highest top-up paid out of the insurance pools for a single SFX on the gateway
+    #[pallet::storage]
+    #[pallet::getter(fn insurance_gateway_caps)]
+    pub type InsuranceGatewayCaps<T: Config> =
+        StorageMap<_, Identity, TargetId, BalanceOf<T>, OptionQuery>;
+
+    /// Highest top-up paid out of the insurance pool of the asset for a single SFX
+    #[pallet::storage]
+    #[pallet::getter(fn insurance_asset_caps)]
+    pub type InsuranceAssetCaps<T: Config> =
+        StorageMap<_, Blake2_128Concat, Option<AssetIdOf<T>>, BalanceOf<T>, OptionQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::weight(T::DbWeight::get().reads(2) + T::DbWeight::get().writes(1))]
@@ -174,6 +250,62 @@ pub mod pallet {
                 <T::Assets as Inspect<T::AccountId>>::AssetId,
             >>::finalize(charge_id, outcome, maybe_recipient, maybe_actual_fees)
         }
+
+        #[pallet::weight(T::DbWeight::get().reads(3) + T::DbWeight::get().writes(4))]
+        pub fn deposit_insurance(
+            origin: OriginFor<T>,
+            asset_id: Option<AssetIdOf<T>>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let provider = ensure_signed(origin)?;
+            Self::do_deposit_insurance(&provider, asset_id, amount)
+        }
+
+        #[pallet::weight(T::DbWeight::get().reads(3) + T::DbWeight::get().writes(1))]
+        pub fn withdraw_insurance(
+            origin: OriginFor<T>,
+            asset_id: Option<AssetIdOf<T>>,
+            shares: BalanceOf<T>,
+        ) -> DispatchResult {
+            let provider = ensure_signed(origin)?;
+            Self::do_withdraw_insurance(&provider, asset_id, shares)
+        }
+
+        #[pallet::weight(T::DbWeight::get().reads(4) + T::DbWeight::get().writes(5))]
+        pub fn claim_insurance_withdrawal(
+            origin: OriginFor<T>,
+            asset_id: Option<AssetIdOf<T>>,
+        ) -> DispatchResult {
+            let provider = ensure_signed(origin)?;
+            Self::do_claim_insurance_withdrawal(&provider, asset_id)
+        }
+
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_gateway_insurance_cap(
+            origin: OriginFor<T>,
+            target: TargetId,
+            maybe_cap: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            InsuranceGatewayCaps::<T>::set(target, maybe_cap);
+            Self::deposit_event(Event::InsuranceGatewayCapSet { target, maybe_cap });
+            Ok(())
+        }
+
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_asset_insurance_cap(
+            origin: OriginFor<T>,
+            asset_id: Option<AssetIdOf<T>>,
+            maybe_cap: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            InsuranceAssetCaps::<T>::set(asset_id, maybe_cap);
+            Self::deposit_event(Event::InsuranceAssetCapSet {
+                asset_id,
+                maybe_cap,
+            });
+            Ok(())
+        }
     }
 
     // Pallet implements [`Hooks`] trait to define some logic to execute in some context.
@@ -222,6 +354,43 @@ pub mod pallet {
             recipient: Option<T::AccountId>,
             amount: BalanceOf<T>,
         },
+        InsuranceDeposited {
+            provider: T::AccountId,
+            asset_id: Option<AssetIdOf<T>>,
+            amount: BalanceOf<T>,
+            shares: BalanceOf<T>,
+        },
+        InsuranceWithdrawalRequested {
+            provider: T::AccountId,
+            asset_id: Option<AssetIdOf<T>>,
+            amount: BalanceOf<T>,
+            shares: BalanceOf<T>,
+            unlock_at: BlockNumberFor<T>,
+        },
+        InsuranceWithdrawn {
+            provider: T::AccountId,
+            asset_id: Option<AssetIdOf<T>>,
+            amount: BalanceOf<T>,
+            shares: BalanceOf<T>,
+        },
+        InsurancePoolRewarded {
+            asset_id: Option<AssetIdOf<T>>,
+            amount: BalanceOf<T>,
+        },
+        InsuranceCompensationPaid {
+            beneficiary: T::AccountId,
+            target: TargetId,
+            asset_id: Option<AssetIdOf<T>>,
+            amount: BalanceOf<T>,
+        },
+        InsuranceGatewayCapSet {
+            target: TargetId,
+            maybe_cap: Option<BalanceOf<T>>,
+        },
+        InsuranceAssetCapSet {
+            asset_id: Option<AssetIdOf<T>>,
+            maybe_cap: Option<BalanceOf<T>>,
+        },
     }
 
     #[pallet::error]
@@ -238,6 +407,12 @@ pub mod pallet {
         DecodingExecutionIDFailed,
         TransferDepositFailedOldChargeNotFound,
         TransferDepositFailedToReleasePreviousCharge,
+        InsuranceAmountZero,
+        InsufficientInsuranceShares,
+        InsufficientInsurancePoolLiquidity,
+        NoPendingInsuranceWithdrawal,
+        InsuranceWithdrawalNotDue,
+        WithheldAmountExceedsReward,
     }
 
     #[pallet::genesis_config]
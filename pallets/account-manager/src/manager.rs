@@ -1,6 +1,6 @@
 use crate::{
     AccountManager as AccountManagerExt, BalanceOf, Config, ContractsRegistryExecutionNonce, Error,
-    Outcome, Pallet, PendingCharges, SettlementsPerRound,
+    Outcome, Pallet, PendingCharges, SettlementsPerRound, TargetId,
 };
 
 use codec::{Decode, Encode};
@@ -9,7 +9,7 @@ use frame_support::{
     traits::{fungibles::Inspect, Get},
 };
 use sp_runtime::{
-    traits::{CheckedAdd, CheckedDiv, CheckedMul, Convert, Zero},
+    traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Convert, Saturating, Zero},
     ArithmeticError, DispatchError,
 };
 use sp_std::prelude::*;
@@ -217,32 +217,45 @@ impl<T: Config>
                         }
 
                         // Deposit funds out of Escrow executions to recipient account immediately
+                        //  and fall through so the charge is cleared out of PendingCharges below
                         if charge.source == BenefitSource::EscrowUnlock {
                             Monetary::<T::AccountId, T::Assets, T::Currency, T::AssetBalanceOf>::deposit(
                                 &recipient,
                                 maybe_asset_id,
                                 charge.offered_reward,
                             );
-                            return true
+                        } else {
+                            // SFX rewards pay their slice to the insurance pool of the reward asset
+                            let insurance_pool_reward = if charge.role == CircuitRole::Executor
+                                && charge.source == BenefitSource::TrafficFees
+                            {
+                                Self::reward_insurance_pool(
+                                    maybe_asset_id.clone(),
+                                    charge.offered_reward,
+                                )
+                            } else {
+                                Zero::zero()
+                            };
+                            SettlementsPerRound::<T>::insert(
+                                T::Clock::current_round(),
+                                charge_id,
+                                Settlement::<
+                                    T::AccountId,
+                                    BalanceOf<T>,
+                                    <T::Assets as Inspect<T::AccountId>>::AssetId,
+                                > {
+                                    requester: charge.payee,
+                                    recipient,
+                                    settlement_amount: charge
+                                        .offered_reward
+                                        .saturating_sub(insurance_pool_reward),
+                                    outcome,
+                                    source: charge.source,
+                                    role: charge.role,
+                                    maybe_asset_id,
+                                },
+                            );
                         }
-
-                        SettlementsPerRound::<T>::insert(
-                            T::Clock::current_round(),
-                            charge_id,
-                            Settlement::<
-                                T::AccountId,
-                                BalanceOf<T>,
-                                <T::Assets as Inspect<T::AccountId>>::AssetId,
-                            > {
-                                requester: charge.payee,
-                                recipient,
-                                settlement_amount: charge.offered_reward,
-                                outcome,
-                                source: charge.source,
-                                role: charge.role,
-                                maybe_asset_id,
-                            },
-                        );
                     },
                     Outcome::Slash => {
                         Monetary::<T::AccountId, T::Assets, T::Currency, T::AssetBalanceOf>::deposit(
@@ -276,6 +289,41 @@ impl<T: Config>
         }
     }
 
+    fn slash_deposit_to(
+        charge_id: T::Hash,
+        beneficiary: &T::AccountId,
+        claimed: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let charge = Self::get_charge_or_fail(charge_id)?;
+
+        // Pay the claim out of the deposit and slash the rest of it to the Escrow account
+        let paid = claimed.min(charge.offered_reward);
+        Monetary::<T::AccountId, T::Assets, T::Currency, T::AssetBalanceOf>::deposit(
+            beneficiary,
+            charge.maybe_asset_id.clone(),
+            paid,
+        );
+        let slashed = charge.offered_reward.saturating_sub(paid);
+        if slashed > Zero::zero() {
+            Monetary::<T::AccountId, T::Assets, T::Currency, T::AssetBalanceOf>::deposit(
+                &T::EscrowAccount::get(),
+                charge.maybe_asset_id.clone(),
+                slashed,
+            );
+        }
+
+        // Take charge fee to treasury
+        if charge.charge_fee > Zero::zero() {
+            Monetary::<T::AccountId, T::Assets, T::Currency, T::AssetBalanceOf>::deposit(
+                &T::EscrowAccount::get(),
+                charge.maybe_asset_id,
+                charge.charge_fee,
+            );
+        }
+        PendingCharges::<T>::remove(charge_id);
+        Ok(paid)
+    }
+
     fn cancel_deposit(charge_id: T::Hash) -> bool {
         match PendingCharges::<T>::get(charge_id) {
             Some(charge) => {
@@ -301,6 +349,19 @@ impl<T: Config>
         })
     }
 
+    fn withhold_from_deposit(charge_id: T::Hash, amount: BalanceOf<T>) -> DispatchResult {
+        PendingCharges::<T>::try_mutate(charge_id, |maybe_charge| match maybe_charge {
+            Some(charge) => {
+                charge.offered_reward = charge
+                    .offered_reward
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T>::WithheldAmountExceedsReward)?;
+                Ok(())
+            },
+            None => Err(Error::<T>::NoChargeOfGivenIdRegistered.into()),
+        })
+    }
+
     fn transfer_deposit(
         charge_id: T::Hash,
         new_charge_id: T::Hash,
@@ -396,6 +457,15 @@ impl<T: Config>
             payee, amount, asset_id,
         )
     }
+
+    fn compensate_from_insurance_pool(
+        beneficiary: &T::AccountId,
+        target: TargetId,
+        shortfall: BalanceOf<T>,
+        asset_id: Option<<T::Assets as Inspect<T::AccountId>>::AssetId>,
+    ) -> BalanceOf<T> {
+        Self::do_compensate_from_insurance_pool(beneficiary, target, shortfall, asset_id)
+    }
 }
 
 #[cfg(test)]
@@ -799,6 +869,142 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_finalize_commit_of_escrow_unlock_pays_recipient_and_clears_pending_charge() {
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+            let escrow_account = <Runtime as pallet_account_manager::Config>::EscrowAccount::get();
+            let _ = Balances::deposit_creating(&escrow_account, DEFAULT_BALANCE);
+            let charge_amt = 100;
+            let charge_fee = 10;
+            let execution_id: H256 = H256::repeat_byte(0);
+
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+                AssetId,
+            >>::deposit(
+                execution_id,
+                RequestCharge {
+                    payee: ALICE,
+                    offered_reward: charge_amt,
+                    charge_fee,
+                    source: BenefitSource::EscrowUnlock,
+                    role: CircuitRole::Executor,
+                    recipient: Some(BOB),
+                    maybe_asset_id: None
+                }
+            ));
+
+            let bob_balance = Balances::free_balance(&BOB);
+            let escrow_balance = Balances::free_balance(&escrow_account);
+
+            assert!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+                AssetId,
+            >>::finalize_infallible(
+                execution_id, Outcome::Commit
+            ));
+
+            // Escrow unlocks pay out immediately instead of settling at the end of the round
+            assert_eq!(Balances::free_balance(&BOB), bob_balance + charge_amt);
+            assert_eq!(
+                AccountManager::settlements_per_round::<RoundInfo<BlockNumber>, H256>(
+                    Default::default(),
+                    execution_id,
+                ),
+                None
+            );
+            // The charge is cleared and its fee is taken like for any other charge
+            assert_eq!(
+                AccountManager::pending_charges_per_round::<H256>(execution_id),
+                None
+            );
+            assert_eq!(
+                Balances::free_balance(&escrow_account),
+                escrow_balance + charge_fee
+            );
+        });
+    }
+
+    #[test]
+    fn test_withhold_from_deposit_reduces_the_reward_paid_at_commit() {
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+            let escrow_account = <Runtime as pallet_account_manager::Config>::EscrowAccount::get();
+            let _ = Balances::deposit_creating(&escrow_account, DEFAULT_BALANCE);
+            let charge_amt = 100;
+            let execution_id: H256 = H256::repeat_byte(0);
+
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+                AssetId,
+            >>::deposit(
+                execution_id,
+                RequestCharge {
+                    payee: ALICE,
+                    offered_reward: charge_amt,
+                    charge_fee: 0,
+                    source: BenefitSource::EscrowUnlock,
+                    role: CircuitRole::Executor,
+                    recipient: Some(BOB),
+                    maybe_asset_id: None
+                }
+            ));
+
+            assert_err!(
+                <AccountManager as AccountManagerExt<
+                    AccountId,
+                    Balance,
+                    Hash,
+                    BlockNumber,
+                    AssetId,
+                >>::withhold_from_deposit(execution_id, charge_amt + 1),
+                pallet_account_manager::Error::<Runtime>::WithheldAmountExceedsReward
+            );
+            assert_err!(
+                <AccountManager as AccountManagerExt<
+                    AccountId,
+                    Balance,
+                    Hash,
+                    BlockNumber,
+                    AssetId,
+                >>::withhold_from_deposit(H256::repeat_byte(1), 10),
+                pallet_account_manager::Error::<Runtime>::NoChargeOfGivenIdRegistered
+            );
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+                AssetId,
+            >>::withhold_from_deposit(execution_id, 10));
+
+            let bob_balance = Balances::free_balance(&BOB);
+            assert!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+                AssetId,
+            >>::finalize_infallible(
+                execution_id, Outcome::Commit
+            ));
+
+            // The requester paid the full reward, the recipient is paid what is left of it
+            assert_eq!(Balances::free_balance(&ALICE), DEFAULT_BALANCE - charge_amt);
+            assert_eq!(Balances::free_balance(&BOB), bob_balance + charge_amt - 10);
+        });
+    }
+
     #[test]
     fn test_finalize_unexpected_failure_works() {
         ExtBuilder::default().build().execute_with(|| {
@@ -862,4 +1068,70 @@ mod tests {
             assert_eq!(settlement, None);
         });
     }
+
+    #[test]
+    fn test_slash_deposit_to_pays_beneficiary_out_of_the_deposit() {
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+            let _ = Balances::deposit_creating(&BOB, DEFAULT_BALANCE);
+            let charge_amt = 100;
+            let claimed = 60;
+            let execution_id: H256 = H256::repeat_byte(0);
+            let escrow_account = <Runtime as pallet_account_manager::Config>::EscrowAccount::get();
+            let escrow_balance_before = Balances::free_balance(&escrow_account);
+
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+                AssetId,
+            >>::deposit(
+                execution_id,
+                RequestCharge {
+                    payee: ALICE,
+                    offered_reward: charge_amt,
+                    charge_fee: 0,
+                    source: BenefitSource::TrafficRewards,
+                    role: CircuitRole::Executor,
+                    recipient: None,
+                    maybe_asset_id: None
+                }
+            ));
+
+            assert_eq!(
+                <AccountManager as AccountManagerExt<
+                    AccountId,
+                    Balance,
+                    Hash,
+                    BlockNumber,
+                    AssetId,
+                >>::slash_deposit_to(execution_id, &BOB, claimed),
+                Ok(claimed)
+            );
+
+            assert_eq!(Balances::free_balance(&ALICE), DEFAULT_BALANCE - charge_amt);
+            assert_eq!(Balances::free_balance(&BOB), DEFAULT_BALANCE + claimed);
+            assert_eq!(
+                Balances::free_balance(&escrow_account),
+                escrow_balance_before + charge_amt - claimed
+            );
+            assert_eq!(
+                AccountManager::pending_charges_per_round::<H256>(execution_id,),
+                None
+            );
+
+            // Deposit can only be slashed once
+            assert_err!(
+                <AccountManager as AccountManagerExt<
+                    AccountId,
+                    Balance,
+                    Hash,
+                    BlockNumber,
+                    AssetId,
+                >>::slash_deposit_to(execution_id, &BOB, claimed),
+                pallet_account_manager::Error::<Runtime>::NoChargeOfGivenIdRegistered
+            );
+        });
+    }
 }
@@ -6,10 +6,14 @@ use codec::{self, Encode};
 use frame_support::{
     assert_ok,
     dispatch::{DispatchInfo, PostDispatchInfo},
-    traits::fungibles::Mutate,
+    traits::{fungibles::Mutate, Get},
     weights::Weight,
 };
 use sp_runtime::{traits::SignedExtension, AccountId32};
+use t3rn_primitives::{
+    xdns::{Xdns, ASSET_FEE_PRICE_UNIT},
+    SubstrateToken, TokenInfo,
+};
 
 pub const ALICE: AccountId32 = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId32 = AccountId32::new([1u8; 32]);
@@ -51,6 +55,22 @@ fn setup_asset() -> u32 {
         decimals,
     ));
 
+    // Fees are payable in the assets registered in XDNS, here at the price of 1 TRN
+    assert_ok!(XDNS::override_token(
+        asset_id,
+        <Runtime as pallet_xdns::Config>::SelfGatewayId::get(),
+        TokenInfo::Substrate(SubstrateToken {
+            id: asset_id,
+            symbol: symbol.encode(),
+            decimals,
+        }),
+    ));
+    assert_ok!(XDNS::set_asset_fee_price(
+        Origin::root(),
+        asset_id,
+        Some(ASSET_FEE_PRICE_UNIT)
+    ));
+
     asset_id
 }
 
@@ -108,3 +128,50 @@ fn transaction_payment_in_asset_fails_if_insufficient_balance() {
             .is_err());
     });
 }
+
+#[test]
+fn transaction_payment_in_asset_is_converted_by_xdns_fee_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        let asset_id = setup_asset();
+        let caller = BOB;
+        let info = info_from_weight(5_u64.into());
+        let len = 10;
+        let initial_balance = 10_000_000_000_000;
+        assert_ok!(Assets::mint_into(asset_id, &caller, initial_balance));
+
+        // Asset worth half of TRN pays twice the native fee
+        assert_ok!(XDNS::set_asset_fee_price(
+            Origin::root(),
+            asset_id,
+            Some(2 * ASSET_FEE_PRICE_UNIT)
+        ));
+        let native_fee = TransactionPayment::compute_fee(len, &info, 0);
+        ChargeAssetTxPayment::<Runtime>::from(0, Some(asset_id))
+            .pre_dispatch(&caller, CALL, &info, len as usize)
+            .expect("asset transaction payment");
+        assert_eq!(
+            Assets::balance(asset_id, caller.clone()),
+            initial_balance - 2 * native_fee
+        );
+
+        // Assets not registered in XDNS can't pay the fees
+        let unregistered_asset_id = 2_u32;
+        assert_ok!(Assets::force_create(
+            Origin::root(),
+            unregistered_asset_id,
+            sp_runtime::MultiAddress::Id(ALICE),
+            true,
+            1
+        ));
+        assert_ok!(Assets::mint_into(
+            unregistered_asset_id,
+            &caller,
+            initial_balance
+        ));
+        assert!(
+            ChargeAssetTxPayment::<Runtime>::from(0, Some(unregistered_asset_id))
+                .pre_dispatch(&caller, CALL, &info, len as usize)
+                .is_err()
+        );
+    });
+}
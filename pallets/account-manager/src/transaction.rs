@@ -1,5 +1,6 @@
 use crate::Config;
 use frame_support::traits::{Currency, Imbalance, OnUnbalanced, SameOrOther, TryDrop};
+pub use t3rn_primitives::account_manager::ExecutorFeeSponsor;
 
 pub type NegativeImbalanceOf<T, C> =
     <C as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
@@ -111,12 +112,15 @@ macro_rules! setup_currency_adapter {
     () => {
         use sp_runtime::traits::Saturating;
         use frame_support::traits::IsSubType;
+        use pallet_account_manager::transaction::ExecutorFeeSponsor;
         use pallet_transaction_payment::CurrencyAdapter;
         use codec::Decode;
 
         pub struct AccountManagerCurrencyAdapter<C, OU>(sp_std::marker::PhantomData<(C, OU)>);
+        // Beneficiary of the fees, sponsor that paid them in place of the signer along with its sponsorship, and the fees paid
         type AccountImbalanceLiquidityInfo<T, C> = (
             Option<<T as frame_system::Config>::AccountId>,
+            Option<(<T as frame_system::Config>::AccountId, <T as frame_system::Config>::Hash)>,
             Option<pallet_account_manager::transaction::NegativeImbalanceOf<T, C>>,
         );
 
@@ -149,6 +153,22 @@ macro_rules! setup_currency_adapter {
             ) -> Result<Self::LiquidityInfo, frame_support::pallet_prelude::TransactionValidityError> {
                 let call: &<T as frame_system::Config>::RuntimeCall = call;
 
+                // Executors' fees may be paid by their sponsor, falling back to the executor if the sponsor can't pay.
+                //  Tips are always paid by the signer.
+                let sponsored = <T as pallet_account_manager::Config>::ExecutorFeeSponsor::sponsor_of(who, call, fee)
+                    .filter(|_| sp_runtime::traits::Zero::is_zero(&tip))
+                    .and_then(|(sponsor, sponsorship)| {
+                        <CurrencyAdapter<C, OU> as pallet_transaction_payment::OnChargeTransaction<T>>::withdraw_fee(
+                            &sponsor, call, info, fee, tip,
+                        )
+                        .ok()
+                        .map(|info| (sponsor, sponsorship, info))
+                    });
+                if let Some((sponsor, sponsorship, info)) = sponsored {
+                    <T as pallet_account_manager::Config>::ExecutorFeeSponsor::charge_sponsor(who, call, sponsorship, fee);
+                    return Ok((None, Some((sponsor, sponsorship)), info))
+                }
+
                 let result = <CurrencyAdapter<C, OU> as pallet_transaction_payment::OnChargeTransaction<T>>::withdraw_fee(
                     who, call, info, fee, tip,
                 );
@@ -157,12 +177,12 @@ macro_rules! setup_currency_adapter {
                     if let Some(author) = ThreeVm::get_author(dest) {
                         return result.map(|info| {
                             let opaque_author = T::AccountId::decode(&mut author.as_ref()).ok();
-                            (opaque_author, info)
+                            (opaque_author, None, info)
                         });
                     }
                 }
 
-                result.map(|info| (None, info))
+                result.map(|info| (None, None, info))
             }
 
             // Largely, this is a copy of pallet-tx-payment except the part at the end where we check
@@ -175,14 +195,25 @@ macro_rules! setup_currency_adapter {
                 tip: Self::Balance,
                 already_withdrawn: Self::LiquidityInfo,
             ) -> Result<(), frame_support::pallet_prelude::TransactionValidityError> {
-                if let (beneficiary, Some(paid)) = already_withdrawn {
+                if let (beneficiary, sponsor, Some(paid)) = already_withdrawn {
                     // Calculate how much refund we should return
                     let refund_amount = paid.peek().saturating_sub(corrected_fee);
                     // refund to the the account that paid the fees. If this fails, the
                     // account might have dropped below the existential balance. In
                     // that case we don't refund anything.
-                    let refund_imbalance = C::deposit_into_existing(who, refund_amount)
+                    let payer = sponsor.as_ref().map(|(sponsor, _)| sponsor).unwrap_or(who);
+                    let refund_imbalance = C::deposit_into_existing(payer, refund_amount)
                         .unwrap_or_else(|_| C::PositiveImbalance::zero());
+                    // The sponsorship's budget is only spent on the fee the sponsor ends up paying
+                    if let Some((sponsor, sponsorship)) = sponsor {
+                        <T as pallet_account_manager::Config>::ExecutorFeeSponsor::refund_sponsor(
+                            who,
+                            &sponsor,
+                            sponsorship,
+                            refund_imbalance.peek(),
+                            paid.peek().saturating_sub(refund_imbalance.peek()),
+                        );
+                    }
                     // merge the imbalance caused by paying the fees and refunding parts of it again.
                     let adjusted_paid = paid.offset(refund_imbalance).same().map_err(|_| {
                         frame_support::pallet_prelude::TransactionValidityError::Invalid(
@@ -27,9 +27,19 @@ contract AttestationsVerifier {
     uint256 public totalAttesters; // added a counter to track total attestors.
 
     function batchEncodePacked(Batch memory batch) public pure returns (bytes memory) {
+        // Committees vary in size, so a committee transition is prefixed with the length of each committee
+        // Matches BatchMessageEncoding::EthAbiV2 - the target has to be switched to it with set_batch_message_encoding before deploying
+        bytes memory committeeTransition;
+        if (batch.nextCommittee.length > 0 || batch.bannedCommittee.length > 0) {
+            committeeTransition = abi.encodePacked(
+                batch.nextCommittee.length,
+                batch.nextCommittee,
+                batch.bannedCommittee.length,
+                batch.bannedCommittee
+            );
+        }
         return abi.encodePacked(
-            batch.nextCommittee,
-            batch.bannedCommittee,
+            committeeTransition,
             batch.committedSfx,
             batch.revertedSfx,
             batch.index
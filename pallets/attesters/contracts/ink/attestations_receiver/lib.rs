@@ -0,0 +1,356 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Reference receiver of the attestation batches on Substrate targets.
+/// Batches are SCALE-encoded, hashed with blake2_256 and signed by the committee with sr25519 keys,
+///     see BatchMessage::scale_message in pallet-attesters.
+#[ink::contract]
+mod attestations_receiver {
+    use ink::{
+        env::hash::{Blake2x256, HashOutput},
+        prelude::vec::Vec,
+        storage::Mapping,
+    };
+
+    pub type Sr25519Public = [u8; 32];
+    pub type Sr25519Signature = [u8; 64];
+
+    /// Batch in the order of fields SCALE-encoded by the attesters pallet
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct Batch {
+        pub next_committee: Vec<Vec<u8>>,
+        pub banned_committee: Vec<Vec<u8>>,
+        pub committed_sfx: Vec<[u8; 64]>,
+        pub reverted_sfx: Vec<[u8; 32]>,
+        pub gmp_messages: Vec<[u8; 32]>,
+        pub index: u32,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        BatchHashMismatch,
+        BatchIndexMismatch,
+        SignaturesVerificationFailed,
+        EmptyCommitteeTransition,
+    }
+
+    /// Emitted as BatchApplied:Event(MessageHash:H256, BeneficiaryOnTarget:Account32, AttestingCommittee:H256)
+    ///     and verified by commit_batch of the attesters pallet
+    #[ink(event)]
+    pub struct BatchApplied {
+        #[ink(topic)]
+        message_hash: [u8; 32],
+        #[ink(topic)]
+        executor: AccountId,
+        attesting_committee: [u8; 32],
+    }
+
+    #[ink(storage)]
+    pub struct AttestationsReceiver {
+        committee: Vec<Sr25519Public>,
+        banned: Mapping<Sr25519Public, ()>,
+        committed_sfx: Mapping<[u8; 64], ()>,
+        reverted_sfx: Mapping<[u8; 32], ()>,
+        committed_gmp: Mapping<[u8; 32], ()>,
+        current_batch_index: u32,
+    }
+
+    impl AttestationsReceiver {
+        #[ink(constructor)]
+        pub fn new(initial_committee: Vec<Sr25519Public>, starting_index: u32) -> Self {
+            Self {
+                committee: initial_committee,
+                banned: Mapping::default(),
+                committed_sfx: Mapping::default(),
+                reverted_sfx: Mapping::default(),
+                committed_gmp: Mapping::default(),
+                current_batch_index: starting_index,
+            }
+        }
+
+        #[ink(message)]
+        pub fn message_hash(&self, batch: Batch) -> [u8; 32] {
+            let mut hash = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_encoded::<Blake2x256, _>(&batch, &mut hash);
+            hash
+        }
+
+        #[ink(message)]
+        pub fn receive_attestation_batch(
+            &mut self,
+            batch: Batch,
+            expected_batch_hash: [u8; 32],
+            signatures: Vec<(Sr25519Public, Sr25519Signature)>,
+        ) -> Result<(), Error> {
+            if self.message_hash(batch.clone()) != expected_batch_hash {
+                return Err(Error::BatchHashMismatch)
+            }
+            if batch.index != self.current_batch_index.saturating_add(1) {
+                return Err(Error::BatchIndexMismatch)
+            }
+            if !self.verify_signed_by_active_committee(expected_batch_hash, signatures) {
+                return Err(Error::SignaturesVerificationFailed)
+            }
+
+            let mut attesting_committee = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_encoded::<Blake2x256, _>(&self.committee, &mut attesting_committee);
+
+            let banned: Vec<Sr25519Public> = Self::sr25519_keys(&batch.banned_committee).collect();
+            let mut committee = if batch.next_committee.is_empty() {
+                self.committee.clone()
+            } else {
+                Self::sr25519_keys(&batch.next_committee)
+                    .filter(|key| !self.banned.contains(key))
+                    .collect()
+            };
+            committee.retain(|member| !banned.contains(member));
+            // A committee left without members would accept batches without signatures
+            if committee.is_empty() {
+                return Err(Error::EmptyCommitteeTransition)
+            }
+
+            self.committee = committee;
+            for key in banned {
+                self.banned.insert(key, &());
+            }
+            for sfx in batch.committed_sfx.iter() {
+                self.committed_sfx.insert(sfx, &());
+            }
+            for sfx in batch.reverted_sfx.iter() {
+                self.reverted_sfx.insert(sfx, &());
+            }
+            for message_id in batch.gmp_messages.iter() {
+                self.committed_gmp.insert(message_id, &());
+            }
+            self.current_batch_index = batch.index;
+
+            self.env().emit_event(BatchApplied {
+                message_hash: expected_batch_hash,
+                executor: self.env().caller(),
+                attesting_committee,
+            });
+
+            Ok(())
+        }
+
+        /// Count the distinct members of the committee whose sr25519 signatures of the hash verify,
+        ///     requiring 2/3 of the committee rounded up, and at least one signature.
+        #[ink(message)]
+        pub fn verify_signed_by_active_committee(
+            &self,
+            message_hash: [u8; 32],
+            signatures: Vec<(Sr25519Public, Sr25519Signature)>,
+        ) -> bool {
+            let quorum = ((self.committee.len() * 2 + 2) / 3).max(1);
+            let mut signers: Vec<Sr25519Public> = Vec::new();
+            for (signer, signature) in signatures.iter() {
+                if !self.committee.contains(signer) || signers.contains(signer) {
+                    continue
+                }
+                if self
+                    .env()
+                    .sr25519_verify(signature, &message_hash, signer)
+                    .is_ok()
+                {
+                    signers.push(*signer);
+                }
+            }
+            signers.len() >= quorum
+        }
+
+        #[ink(message)]
+        pub fn committee(&self) -> Vec<Sr25519Public> {
+            self.committee.clone()
+        }
+
+        #[ink(message)]
+        pub fn current_batch_index(&self) -> u32 {
+            self.current_batch_index
+        }
+
+        #[ink(message)]
+        pub fn is_sfx_committed(&self, sfx: [u8; 64]) -> bool {
+            self.committed_sfx.contains(sfx)
+        }
+
+        #[ink(message)]
+        pub fn is_sfx_reverted(&self, sfx: [u8; 32]) -> bool {
+            self.reverted_sfx.contains(sfx)
+        }
+
+        #[ink(message)]
+        pub fn is_gmp_committed(&self, message_id: [u8; 32]) -> bool {
+            self.committed_gmp.contains(message_id)
+        }
+
+        // Committees are transmitted as the recoverables attesters agreed to, which are sr25519 keys for Substrate targets
+        fn sr25519_keys(committee: &[Vec<u8>]) -> impl Iterator<Item = Sr25519Public> + '_ {
+            committee
+                .iter()
+                .filter_map(|recoverable| Sr25519Public::try_from(recoverable.as_slice()).ok())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use schnorrkel::{signing_context, ExpansionMode, Keypair, MiniSecretKey};
+
+        fn keypair(seed: u8) -> Keypair {
+            MiniSecretKey::from_bytes(&[seed; 32])
+                .unwrap()
+                .expand_to_keypair(ExpansionMode::Ed25519)
+        }
+
+        fn public(seed: u8) -> Sr25519Public {
+            keypair(seed).public.to_bytes()
+        }
+
+        fn sign(seed: u8, message_hash: [u8; 32]) -> (Sr25519Public, Sr25519Signature) {
+            let signature = keypair(seed).sign(signing_context(b"substrate").bytes(&message_hash));
+            (public(seed), signature.to_bytes())
+        }
+
+        fn batch() -> Batch {
+            Batch {
+                next_committee: vec![vec![4u8; 32]],
+                banned_committee: vec![],
+                committed_sfx: vec![[1u8; 64]],
+                reverted_sfx: vec![[2u8; 32]],
+                gmp_messages: vec![[3u8; 32]],
+                index: 7,
+            }
+        }
+
+        #[ink::test]
+        fn message_hash_matches_scale_batch_hash_of_attesters_pallet() {
+            let receiver = AttestationsReceiver::new(vec![], 6);
+            assert_eq!(
+                receiver.message_hash(batch()),
+                [
+                    0xb3, 0x58, 0x70, 0x88, 0x92, 0x5f, 0xb5, 0xaa, 0x8f, 0x1d, 0xe7, 0x8b, 0x4a,
+                    0xe8, 0x7e, 0x02, 0x92, 0x14, 0x1b, 0xb7, 0x9c, 0xc7, 0xfe, 0x01, 0x8d, 0x19,
+                    0xff, 0x78, 0x4f, 0x00, 0x6f, 0xad,
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn batch_signed_by_committee_quorum_is_applied() {
+            let mut receiver = AttestationsReceiver::new(vec![public(1), public(2), public(3)], 6);
+            let batch = Batch {
+                next_committee: vec![public(2).to_vec(), public(4).to_vec()],
+                banned_committee: vec![public(3).to_vec()],
+                ..batch()
+            };
+            let message_hash = receiver.message_hash(batch.clone());
+
+            assert_eq!(
+                receiver.receive_attestation_batch(
+                    batch.clone(),
+                    message_hash,
+                    vec![sign(1, message_hash), sign(2, message_hash)],
+                ),
+                Ok(())
+            );
+
+            assert_eq!(receiver.committee(), vec![public(2), public(4)]);
+            assert_eq!(receiver.current_batch_index(), 7);
+            assert!(receiver.is_sfx_committed([1u8; 64]));
+            assert!(receiver.is_sfx_reverted([2u8; 32]));
+            assert!(receiver.is_gmp_committed([3u8; 32]));
+            assert_eq!(ink::env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn batch_without_quorum_of_valid_committee_signatures_is_rejected() {
+            let mut receiver = AttestationsReceiver::new(vec![public(1), public(2), public(3)], 6);
+            let message_hash = receiver.message_hash(batch());
+
+            // Repeated signer, outsider of the committee and signature of other message don't count
+            let signatures = vec![
+                sign(1, message_hash),
+                sign(1, message_hash),
+                sign(4, message_hash),
+                sign(2, [0u8; 32]),
+            ];
+            assert_eq!(
+                receiver.receive_attestation_batch(batch(), message_hash, signatures),
+                Err(Error::SignaturesVerificationFailed)
+            );
+            assert_eq!(
+                receiver.receive_attestation_batch(batch(), [0u8; 32], vec![]),
+                Err(Error::BatchHashMismatch)
+            );
+
+            let mut late_receiver = AttestationsReceiver::new(vec![public(1)], 7);
+            assert_eq!(
+                late_receiver.receive_attestation_batch(
+                    batch(),
+                    message_hash,
+                    vec![sign(1, message_hash)]
+                ),
+                Err(Error::BatchIndexMismatch)
+            );
+        }
+
+        #[ink::test]
+        fn quorum_rounds_up_and_requires_a_signature() {
+            let single_member = AttestationsReceiver::new(vec![public(1)], 6);
+            let message_hash = single_member.message_hash(batch());
+            assert!(!single_member.verify_signed_by_active_committee(message_hash, vec![]));
+            assert!(single_member
+                .verify_signed_by_active_committee(message_hash, vec![sign(1, message_hash)]));
+
+            let empty_committee = AttestationsReceiver::new(vec![], 6);
+            assert!(!empty_committee.verify_signed_by_active_committee(message_hash, vec![]));
+
+            // 2/3 of 4 members rounds up to 3 signatures
+            let receiver =
+                AttestationsReceiver::new(vec![public(1), public(2), public(3), public(4)], 6);
+            assert!(!receiver.verify_signed_by_active_committee(
+                message_hash,
+                vec![sign(1, message_hash), sign(2, message_hash)]
+            ));
+            assert!(receiver.verify_signed_by_active_committee(
+                message_hash,
+                vec![
+                    sign(1, message_hash),
+                    sign(2, message_hash),
+                    sign(3, message_hash)
+                ]
+            ));
+        }
+
+        #[ink::test]
+        fn transition_leaving_the_committee_empty_is_rejected() {
+            let mut receiver = AttestationsReceiver::new(vec![public(1)], 6);
+
+            // Entries of the next committee other than sr25519 keys are skipped
+            let malformed_next_committee = Batch {
+                next_committee: vec![vec![4u8; 20]],
+                ..batch()
+            };
+            // Banning the whole committee
+            let banned_committee = Batch {
+                next_committee: vec![],
+                banned_committee: vec![public(1).to_vec()],
+                ..batch()
+            };
+            for batch in [malformed_next_committee, banned_committee] {
+                let message_hash = receiver.message_hash(batch.clone());
+                assert_eq!(
+                    receiver.receive_attestation_batch(
+                        batch,
+                        message_hash,
+                        vec![sign(1, message_hash)]
+                    ),
+                    Err(Error::EmptyCommitteeTransition)
+                );
+            }
+            assert_eq!(receiver.committee(), vec![public(1)]);
+            assert_eq!(receiver.current_batch_index(), 6);
+        }
+    }
+}
@@ -1,40 +1,105 @@
-// #![cfg(feature = "runtime-benchmarks")]
-//
-// use super::*;
-// use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-// use frame_system::RawOrigin;
-// // Import zero sp
-//
-// benchmarks! {
-//     submit_attestation {
-//         let s in 0 .. 4096;
-//         let signature_len = 65;
-//
-//         let caller: T::AccountId = whitelisted_caller();
-//         let ecdsa_key = ecdsa::Public::default();
-//         let ed25519_key = ed25519::Public::default();
-//         let sr25519_key = sr25519::Public::default();
-//         let target_as_self: [u8; 4] = [3u8; 4];
-//
-//         // Register the caller as an attester
-//         Attesters::<T>::insert(&caller, AttesterInfo {
-//             key_ed: ed25519_key,
-//             key_sr: sr25519_key,
-//             key_ec: ecdsa_key,
-//             commission: sp_runtime::traits::Zero::zero(),
-//             index: 0,
-//         });
-//
-//         let message = vec![1; s as usize];
-//         let signature = vec![0; signature_len as usize];
-//
-//     }: _(RawOrigin::Signed(caller.clone()), message, signature.into(), target_as_self)
-//     verify {
-//         // Ensure the event was emitted
-//         // System::assert_last_event::<T>(Event::AttestationSubmitted(caller).into());
-//
-//         Ok(())
-//     }
-// }
-//
-// impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+//! Benchmarking setup for pallet-attesters
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_application_crypto::{ecdsa, ed25519, sr25519, RuntimePublic};
+use sp_runtime::{traits::Saturating, Perbill, Percent};
+
+const BENCHMARKED_TARGET: TargetId = [9u8; 4];
+
+benchmarks! {
+    set_committee_size {
+        let committee_size = T::ActiveSetSize::get();
+    }: _(RawOrigin::Root, committee_size)
+    verify {
+        assert_eq!(CommitteeSizeOverride::<T>::get(), Some(committee_size));
+    }
+
+    set_quorum_config {
+        let quorum_config = QuorumConfig {
+            kind: QuorumKind::StakeWeighted,
+            threshold: Perbill::from_percent(50),
+        };
+    }: _(RawOrigin::Root, BENCHMARKED_TARGET, quorum_config)
+    verify {
+        assert_eq!(QuorumConfigs::<T>::get(BENCHMARKED_TARGET), quorum_config);
+    }
+
+    set_batch_message_encoding {
+    }: _(RawOrigin::Root, BENCHMARKED_TARGET, BatchMessageEncoding::EthAbiV2)
+    verify {
+        assert_eq!(
+            BatchMessageEncodings::<T>::get(BENCHMARKED_TARGET),
+            Some(BatchMessageEncoding::EthAbiV2)
+        );
+    }
+
+    rotate_keys {
+        let caller: T::AccountId = whitelisted_caller();
+        let bond = T::MinAttesterBond::get();
+        T::Currency::make_free_balance_be(&caller, bond.saturating_mul(2u32.into()));
+        Pallet::<T>::register_attester(
+            RawOrigin::Signed(caller.clone()).into(),
+            bond,
+            [2u8; 33],
+            [2u8; 32],
+            [3u8; 32],
+            None,
+        )?;
+
+        // Proofs of possession are signed by keys generated in the benchmarking keystore
+        let payload = Pallet::<T>::key_rotation_proof_payload(&caller);
+        let ecdsa_key = ecdsa::Public::generate_pair(ECDSA_ATTESTER_KEY_TYPE_ID, None);
+        let ed25519_key = ed25519::Public::generate_pair(ED25519_ATTESTER_KEY_TYPE_ID, None);
+        let sr25519_key = sr25519::Public::generate_pair(SR25519_ATTESTER_KEY_TYPE_ID, None);
+        let ecdsa_proof = ecdsa_key
+            .sign(ECDSA_ATTESTER_KEY_TYPE_ID, &payload)
+            .ok_or("ECDSA key not in keystore")?;
+        let ed25519_proof = ed25519_key
+            .sign(ED25519_ATTESTER_KEY_TYPE_ID, &payload)
+            .ok_or("ed25519 key not in keystore")?;
+        let sr25519_proof = sr25519_key
+            .sign(SR25519_ATTESTER_KEY_TYPE_ID, &payload)
+            .ok_or("sr25519 key not in keystore")?;
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        ecdsa_key.0,
+        ed25519_key.0,
+        sr25519_key.0,
+        ecdsa_proof.0,
+        ed25519_proof.0,
+        sr25519_proof.0
+    )
+    verify {
+        assert!(PendingKeyRotations::<T>::contains_key(&caller));
+    }
+
+    set_commission {
+        let caller: T::AccountId = whitelisted_caller();
+        let bond = T::MinAttesterBond::get();
+        T::Currency::make_free_balance_be(&caller, bond.saturating_mul(2u32.into()));
+        Pallet::<T>::register_attester(
+            RawOrigin::Signed(caller.clone()).into(),
+            bond,
+            [2u8; 33],
+            [2u8; 32],
+            [3u8; 32],
+            Some(Percent::from_percent(10)),
+        )?;
+        // Lowering the commission applies it right away, dropping the increase scheduled before
+        PendingCommissionChanges::<T>::insert(
+            &caller,
+            (Percent::from_percent(11), frame_system::Pallet::<T>::block_number()),
+        );
+    }: _(RawOrigin::Signed(caller.clone()), Percent::from_percent(5))
+    verify {
+        assert_eq!(
+            Attesters::<T>::get(&caller).map(|info| info.commission),
+            Some(Percent::from_percent(5))
+        );
+        assert!(!PendingCommissionChanges::<T>::contains_key(&caller));
+    }
+}
@@ -2,6 +2,7 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
+pub mod weights;
 
 pub use crate::pallet::*;
 
@@ -17,6 +18,22 @@ pub mod pallet {
     const TWO_EPOCHS_IN_LOCAL_BLOCKS_U8: u8 = 2 * 32;
     const ONE_EPOCHS_IN_LOCAL_BLOCKS_U8: u8 = 32;
     pub const REWARD_ADJUSTMENT: Percent = Percent::from_percent(25);
+    // Most GMP message ids attested by a single batch, the rest wait for the next batches
+    pub const MAX_GMP_MESSAGES_PER_BATCH: usize = 128;
+    // Most key rotations taken up at a single committee transition, the rest wait for the next transitions
+    pub const MAX_KEY_ROTATIONS_PER_TRANSITION: usize = 32;
+    // Most nominators backing a single attester, which bounds the nominations snapshot of each shuffling round
+    pub const MAX_NOMINATORS_PER_ATTESTER: usize = 128;
+    // Most commission increases applying at a single shuffling round, the rest are rejected until the next round
+    pub const MAX_COMMISSION_CHANGES_PER_ROUND: u32 = 64;
+    // Most nominations snapshot entries the sweep of past distribution periods visits per block
+    pub const MAX_SNAPSHOT_ENTRIES_SWEPT_PER_BLOCK: u32 = 128;
+    // ECDSA keys rotated out by an attester still recognised as its own, oldest dropped first
+    pub const MAX_ROTATED_OUT_KEYS: usize = 4;
+    // Payload each new key signs to prove its possession, followed by the encoded attester account
+    pub const KEY_ROTATION_PROOF_PREFIX: &[u8] = b"t3rn-attester-key-rotation";
+    // Version of the stored batches and attester keys, see on_runtime_upgrade
+    const CURRENT_STORAGE_VERSION: u32 = 2;
 
     use super::*;
     t3rn_primitives::reexport_currency_types!();
@@ -30,7 +47,7 @@ pub mod pallet {
         traits::{Currency, ExistenceRequirement, GenesisBuild, Randomness, ReservableCurrency},
     };
     use frame_system::pallet_prelude::{BlockNumberFor, *};
-    use sp_core::{hexdisplay::AsBytesRef, H160, H256, H512};
+    use sp_core::{crypto::KeyTypeId, hashing::blake2_256, H160, H256, H512};
     pub use t3rn_primitives::portal::InclusionReceipt;
     use t3rn_primitives::{
         light_client::{LightClientAsyncAPI, LightClientHeartbeat},
@@ -45,14 +62,20 @@ pub mod pallet {
 
     use t3rn_abi::{Codec, FilledAbi};
 
+    use crate::weights::WeightInfo;
+
     pub use t3rn_primitives::attesters::{
-        AttesterInfo, AttestersChange, AttestersReadApi, AttestersWriteApi,
-        BatchConfirmedSfxWithGMPPayload, BatchRevertedSfxId, BatchingFactor,
-        CommitteeTransitionIndices, LatencyStatus, PublicKeyEcdsa33b, Signature65b, COMMITTEE_SIZE,
-        ECDSA_ATTESTER_KEY_TYPE_ID, ED25519_ATTESTER_KEY_TYPE_ID, SR25519_ATTESTER_KEY_TYPE_ID,
+        AttesterInfo, AttestersChange, AttestersReadApi, AttestersStakingApi, AttestersWriteApi,
+        BatchConfirmedSfxWithGMPPayload, BatchGmpMessageId, BatchMessageEncoding,
+        BatchRevertedSfxId, BatchingFactor, CommitteeTransitionIndices, LatencyStatus,
+        PublicKeyEcdsa33b, QuorumConfig, QuorumKind, Signature65b, ECDSA_ATTESTER_KEY_TYPE_ID,
+        ED25519_ATTESTER_KEY_TYPE_ID, SR25519_ATTESTER_KEY_TYPE_ID,
     };
     use t3rn_primitives::{
-        attesters::{CommitteeRecoverable, CommitteeTransition},
+        attesters::{
+            ecdsa_pubkey_to_eth_address, verify_secp256k1_ecdsa_signature, CommitteeRecoverable,
+            CommitteeTransition,
+        },
         circuit::{Cause, CircuitStatus, ReadSFX},
         portal::Portal,
         rewards::RewardsWriteApi,
@@ -76,6 +99,7 @@ pub mod pallet {
         pub available_to_commit_at: BlockNumber,
         pub committed_sfx: Option<BatchConfirmedSfxWithGMPPayload>,
         pub reverted_sfx: Option<BatchRevertedSfxId>,
+        pub gmp_messages: Option<BatchGmpMessageId>,
         pub next_committee: Option<CommitteeRecoverable>,
         pub banned_committee: Option<CommitteeRecoverable>,
         pub index: u32,
@@ -87,6 +111,41 @@ pub mod pallet {
         pub halt: bool,
     }
 
+    /// BatchMessage before GMP messages were attested, kept to migrate the stored batches
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+    pub struct BatchMessageV1<BlockNumber> {
+        pub available_to_commit_at: BlockNumber,
+        pub committed_sfx: Option<BatchConfirmedSfxWithGMPPayload>,
+        pub reverted_sfx: Option<BatchRevertedSfxId>,
+        pub next_committee: Option<CommitteeRecoverable>,
+        pub banned_committee: Option<CommitteeRecoverable>,
+        pub index: u32,
+        pub signatures: Vec<(u32, Signature65b)>,
+        pub created: BlockNumber,
+        pub status: BatchStatus,
+        pub latency: LatencyStatus,
+        pub halt: bool,
+    }
+
+    impl<BlockNumber> From<BatchMessageV1<BlockNumber>> for BatchMessage<BlockNumber> {
+        fn from(batch: BatchMessageV1<BlockNumber>) -> Self {
+            BatchMessage {
+                available_to_commit_at: batch.available_to_commit_at,
+                committed_sfx: batch.committed_sfx,
+                reverted_sfx: batch.reverted_sfx,
+                gmp_messages: None,
+                next_committee: batch.next_committee,
+                banned_committee: batch.banned_committee,
+                index: batch.index,
+                signatures: batch.signatures,
+                created: batch.created,
+                status: batch.status,
+                latency: batch.latency,
+                halt: batch.halt,
+            }
+        }
+    }
+
     #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
     pub struct InfluxMessage<BlockNumber> {
         pub message_hash: H256,
@@ -104,6 +163,7 @@ pub mod pallet {
                 available_to_commit_at: Zero::zero(),
                 committed_sfx: None,
                 reverted_sfx: None,
+                gmp_messages: None,
                 next_committee: None,
                 banned_committee: None,
                 signatures: Vec::new(),
@@ -115,39 +175,82 @@ pub mod pallet {
             }
         }
     }
+    fn keccak_256_of(message: &[u8]) -> H256 {
+        let mut keccak = Keccak::v256();
+        keccak.update(message);
+        let mut res: [u8; 32] = [0; 32];
+        keccak.finalize(&mut res);
+        H256::from(res)
+    }
+
     // Add the following method to `BatchMessage` struct
     impl<BlockNumber> BatchMessage<BlockNumber> {
         pub fn message(&self) -> Vec<u8> {
+            self.eth_abi_message(false)
+        }
+
+        /// Packed 32b words of the batch. Committee transitions are either packed as they are, as expected
+        ///     by the legacy EVM verifiers, or with each committee prefixed by the amount of its members.
+        fn eth_abi_message(&self, with_committee_lengths: bool) -> Vec<u8> {
             let mut encoded_message = Vec::new();
 
-            let mut encode_eth_committee_addresses_into_message =
-                |committee: &CommitteeRecoverable| {
-                    for recoverable in committee.iter() {
-                        // Ensure recoverable address is 20 bytes long
-                        if recoverable.as_bytes_ref().len() != 20 {
+            let mut encode_eth_committee_addresses_into_message = |committee: &[Vec<u8>]| {
+                // Ensure recoverable address is 20 bytes long
+                let eth_addresses = committee
+                    .iter()
+                    .filter(|recoverable| {
+                        let is_eth_address = recoverable.len() == 20;
+                        if !is_eth_address {
                             log::warn!(
-                                "Recoverable address in BatchMessage::message() is not 20 bytes long: {:?}", recoverable.as_bytes_ref()
+                                "Recoverable address in BatchMessage::message() is not 20 bytes long: {:?}",
+                                recoverable
                             );
-                            continue
                         }
-                        // Encoding of Ethereum address will extend the length of the encoded message by 12 bytes to fill entire 32b word
-                        // Extend the encoded message with 12 bytes of zeros to keep the length of the encoded message constant
-                        const ETH_ADDRESS_LEN: usize = 20;
-                        const ETH_ADDRESS_PADDING: usize = 12;
-                        let mut eth_address_as_32b_word =
-                            [0u8; ETH_ADDRESS_LEN + ETH_ADDRESS_PADDING];
-                        eth_address_as_32b_word
-                            [ETH_ADDRESS_PADDING..ETH_ADDRESS_LEN + ETH_ADDRESS_PADDING]
-                            .copy_from_slice(recoverable.as_bytes_ref());
-                        encoded_message.extend_from_slice(eth_address_as_32b_word.as_slice());
-                    }
-                };
+                        is_eth_address
+                    })
+                    .collect::<Vec<_>>();
+                // Committees vary in size, so each one is preceded by the amount of its members as a 32b word,
+                //  the same way as uint256(committee.length) is packed by the EVM verifier
+                if with_committee_lengths {
+                    let mut length_as_32b_word = [0u8; 32];
+                    length_as_32b_word[28..]
+                        .copy_from_slice(&(eth_addresses.len() as u32).to_be_bytes());
+                    encoded_message.extend_from_slice(length_as_32b_word.as_slice());
+                }
+                for recoverable in eth_addresses {
+                    // Encoding of Ethereum address will extend the length of the encoded message by 12 bytes to fill entire 32b word
+                    // Extend the encoded message with 12 bytes of zeros to keep the length of the encoded message constant
+                    const ETH_ADDRESS_LEN: usize = 20;
+                    const ETH_ADDRESS_PADDING: usize = 12;
+                    let mut eth_address_as_32b_word = [0u8; ETH_ADDRESS_LEN + ETH_ADDRESS_PADDING];
+                    eth_address_as_32b_word
+                        [ETH_ADDRESS_PADDING..ETH_ADDRESS_LEN + ETH_ADDRESS_PADDING]
+                        .copy_from_slice(recoverable.as_slice());
+                    encoded_message.extend_from_slice(eth_address_as_32b_word.as_slice());
+                }
+            };
 
-            if let Some(ref committee) = self.next_committee {
-                encode_eth_committee_addresses_into_message(committee);
-            }
-            if let Some(ref committee) = self.banned_committee {
-                encode_eth_committee_addresses_into_message(committee);
+            if with_committee_lengths {
+                // Batches without committee changes keep the transition section empty,
+                //  otherwise both the next and banned committees are encoded, even if one of them is empty.
+                let has_committee_transition = [&self.next_committee, &self.banned_committee]
+                    .iter()
+                    .any(|committee| committee.as_ref().map_or(false, |c| !c.is_empty()));
+                if has_committee_transition {
+                    encode_eth_committee_addresses_into_message(
+                        self.next_committee.as_deref().unwrap_or_default(),
+                    );
+                    encode_eth_committee_addresses_into_message(
+                        self.banned_committee.as_deref().unwrap_or_default(),
+                    );
+                }
+            } else {
+                if let Some(ref committee) = self.next_committee {
+                    encode_eth_committee_addresses_into_message(committee);
+                }
+                if let Some(ref committee) = self.banned_committee {
+                    encode_eth_committee_addresses_into_message(committee);
+                }
             }
             if let Some(ref sfx_vec) = self.committed_sfx {
                 for sfx in sfx_vec.iter() {
@@ -159,16 +262,47 @@ pub mod pallet {
                     encoded_message.extend_from_slice(sfx.as_bytes());
                 }
             }
+            if let Some(ref message_ids) = self.gmp_messages {
+                for message_id in message_ids.iter() {
+                    encoded_message.extend_from_slice(message_id.as_bytes());
+                }
+            }
             encoded_message.extend_from_slice(self.index.to_be_bytes().as_slice());
             encoded_message
         }
 
         pub fn message_hash(&self) -> H256 {
-            let mut keccak = Keccak::v256();
-            keccak.update(&self.message());
-            let mut res: [u8; 32] = [0; 32];
-            keccak.finalize(&mut res);
-            H256::from(res)
+            keccak_256_of(&self.message())
+        }
+
+        /// SCALE encoding of the batch as decoded by Substrate targets:
+        ///     (next_committee, banned_committee, committed_sfx, reverted_sfx, gmp_messages, index)
+        pub fn scale_message(&self) -> Vec<u8> {
+            (
+                self.next_committee.clone().unwrap_or_default(),
+                self.banned_committee.clone().unwrap_or_default(),
+                self.committed_sfx.clone().unwrap_or_default(),
+                self.reverted_sfx.clone().unwrap_or_default(),
+                self.gmp_messages.clone().unwrap_or_default(),
+                self.index,
+            )
+                .encode()
+        }
+
+        pub fn message_for(&self, encoding: &BatchMessageEncoding) -> Vec<u8> {
+            match encoding {
+                BatchMessageEncoding::EthAbi => self.message(),
+                BatchMessageEncoding::EthAbiV2 => self.eth_abi_message(true),
+                BatchMessageEncoding::Scale => self.scale_message(),
+            }
+        }
+
+        pub fn message_hash_for(&self, encoding: &BatchMessageEncoding) -> H256 {
+            match encoding {
+                BatchMessageEncoding::EthAbi => self.message_hash(),
+                BatchMessageEncoding::EthAbiV2 => keccak_256_of(&self.eth_abi_message(true)),
+                BatchMessageEncoding::Scale => H256(blake2_256(&self.scale_message())),
+            }
         }
 
         pub fn is_empty(&self) -> bool {
@@ -176,6 +310,7 @@ pub mod pallet {
                 && self.banned_committee.is_none()
                 && self.committed_sfx.is_none()
                 && self.reverted_sfx.is_none()
+                && self.gmp_messages.is_none()
         }
 
         pub fn has_no_sfx(&self) -> bool {
@@ -190,11 +325,43 @@ pub mod pallet {
             if let Some(ref sfx) = self.reverted_sfx {
                 batching_factor = batching_factor.saturating_add(sfx.len() as u16)
             }
+            if let Some(ref message_ids) = self.gmp_messages {
+                batching_factor = batching_factor.saturating_add(message_ids.len() as u16)
+            }
 
             batching_factor
         }
     }
 
+    /// Keys replacing the ones of a registered attester at the next committee transition
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+    pub struct AttesterKeys {
+        pub key_ed: [u8; 32],
+        pub key_ec: [u8; 33],
+        pub key_sr: [u8; 32],
+    }
+
+    impl AttesterKeys {
+        /// Key type and raw key of each of the keys, as indexed by AttesterKeyOwners
+        pub fn key_ids(&self) -> [(KeyTypeId, Vec<u8>); 3] {
+            [
+                (ECDSA_ATTESTER_KEY_TYPE_ID, self.key_ec.to_vec()),
+                (ED25519_ATTESTER_KEY_TYPE_ID, self.key_ed.to_vec()),
+                (SR25519_ATTESTER_KEY_TYPE_ID, self.key_sr.to_vec()),
+            ]
+        }
+    }
+
+    impl From<&AttesterInfo> for AttesterKeys {
+        fn from(attester_info: &AttesterInfo) -> Self {
+            AttesterKeys {
+                key_ed: attester_info.key_ed,
+                key_ec: attester_info.key_ec,
+                key_sr: attester_info.key_sr,
+            }
+        }
+    }
+
     #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
     pub enum Slash<BlockNumber> {
         // Slash for not submitting attestations
@@ -223,6 +390,16 @@ pub mod pallet {
         pub attesting_committee: H256,
     }
 
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+    pub struct SubstrateTargetBatchDispatchEvent {
+        // Message hash as H256 (32b)
+        pub hash: H256,
+        // Executor on target as AccountId32
+        pub executor_on_target: [u8; 32],
+        // Attesting commitee
+        pub attesting_committee: H256,
+    }
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -237,6 +414,10 @@ pub mod pallet {
         type Currency: ReservableCurrency<Self::AccountId>;
         type RandomnessSource: Randomness<Self::Hash, BlockNumberFor<Self>>;
         type DefaultCommission: Get<Percent>;
+        /// Highest commission increase an attester can schedule at once
+        type MaxCommissionIncrease: Get<Percent>;
+        /// Blocks a scheduled commission increase waits before applying at the next shuffling round
+        type CommissionChangeNoticePeriod: Get<BlockNumberFor<Self>>;
         type MinNominatorBond: Get<BalanceOf<Self>>;
         type MinAttesterBond: Get<BalanceOf<Self>>;
         type Portal: Portal<Self>;
@@ -244,6 +425,7 @@ pub mod pallet {
         type ReadSFX: ReadSFX<Self::Hash, Self::AccountId, BalanceOf<Self>, BlockNumberFor<Self>>;
         type Xdns: Xdns<Self, BalanceOf<Self>>;
         type LightClientAsyncAPI: LightClientAsyncAPI<Self>;
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
@@ -348,11 +530,97 @@ pub mod pallet {
         BalanceOf<T>,
     >;
 
+    /// Nominations backing the active set attesters, summed over the shuffling rounds of the reward distribution
+    ///     period they were accumulated in, so that rewards are split by stake over time
+    #[pallet::storage]
+    #[pallet::getter(fn nominations_snapshot)]
+    pub type NominationsSnapshot<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Attester
+        Blake2_128Concat,
+        T::AccountId,        // Nominator
+        (u32, BalanceOf<T>), // Distribution period, nomination summed over its rounds
+    >;
+
+    /// Reward distribution period the nominations snapshot is accumulated in
+    #[pallet::storage]
+    #[pallet::getter(fn nominations_snapshot_period)]
+    pub type NominationsSnapshotPeriod<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Commission increases scheduled by attesters with the shuffling round block they apply at
+    #[pallet::storage]
+    #[pallet::getter(fn pending_commission_changes)]
+    pub type PendingCommissionChanges<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (Percent, BlockNumberFor<T>)>;
+
+    /// Attesters with a commission increase due at the shuffling round of the given block
+    #[pallet::storage]
+    #[pallet::getter(fn commission_changes_due)]
+    pub type CommissionChangesDue<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<T::AccountId, ConstU32<MAX_COMMISSION_CHANGES_PER_ROUND>>,
+        ValueQuery,
+    >;
+
+    /// Raw key the sweep of the nominations snapshot entries of past distribution periods resumes from,
+    ///     present while the sweep is in progress
+    #[pallet::storage]
+    pub type NominationsSnapshotSweepCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pending_key_rotations)]
+    pub type PendingKeyRotations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, AttesterKeys>;
+
+    /// Rotations of current committee members, announced to the targets with the next committee
+    /// and applied once that committee takes over
+    #[pallet::storage]
+    #[pallet::getter(fn announced_key_rotations)]
+    pub type AnnouncedKeyRotations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, AttesterKeys>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn rotated_out_ecdsa_keys)]
+    pub type RotatedOutEcdsaKeys<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Vec<PublicKeyEcdsa33b>, ValueQuery>;
+
+    /// Attester holding each registered key, including the keys of its scheduled rotations
+    #[pallet::storage]
+    #[pallet::getter(fn attester_key_owner)]
+    pub type AttesterKeyOwners<T: Config> =
+        StorageMap<_, Blake2_128Concat, (KeyTypeId, Vec<u8>), T::AccountId>;
+
     #[pallet::storage]
     #[pallet::getter(fn paid_finality_fees)]
     pub type PaidFinalityFees<T: Config> =
         StorageMap<_, Blake2_128Concat, TargetId, Vec<BalanceOf<T>>>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn committee_size_override)]
+    pub type CommitteeSizeOverride<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn quorum_config)]
+    pub type QuorumConfigs<T: Config> = StorageMap<_, Identity, TargetId, QuorumConfig, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn batch_message_encoding_override)]
+    pub type BatchMessageEncodings<T: Config> =
+        StorageMap<_, Identity, TargetId, BatchMessageEncoding, OptionQuery>;
+
+    /// Stake backing each member of the current committee by attester index, snapshotted as the committee took over
+    #[pallet::storage]
+    #[pallet::getter(fn current_committee_stakes)]
+    pub type CurrentCommitteeStakes<T: Config> =
+        StorageValue<_, Vec<(u32, BalanceOf<T>)>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn storage_migrations_done)]
+    pub type StorageMigrations<T: Config> = StorageValue<_, u32, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -390,6 +658,14 @@ pub mod pallet {
         AttestationTargetRemoved(TargetId, Vec<TargetId>),
         // ShufflingCompleted(current committee, previous committee, next committee)
         ShufflingCompleted(Vec<T::AccountId>, Vec<T::AccountId>, Vec<T::AccountId>),
+        CommitteeSizeSet(u32),
+        QuorumConfigSet(TargetId, QuorumConfig),
+        BatchMessageEncodingSet(TargetId, BatchMessageEncoding),
+        AttesterKeyRotationScheduled(T::AccountId),
+        AttesterKeysRotated(T::AccountId),
+        // attester, new commission, shuffling round block it applies at
+        CommissionChangeScheduled(T::AccountId, Percent, BlockNumberFor<T>),
+        CommissionChanged(T::AccountId, Percent),
     }
 
     #[pallet::error]
@@ -424,14 +700,26 @@ pub mod pallet {
         XdnsTargetNotActive,
         XdnsGatewayDoesNotHaveEscrowAddressRegistered,
         SfxAlreadyRequested,
+        GmpMessageAlreadyRequested,
+        GmpMessagesPerBatchExceeded,
         AddAttesterAlreadyRequested,
         RemoveAttesterAlreadyRequested,
         NextCommitteeAlreadyRequested,
         BanAttesterAlreadyRequested,
         BatchAlreadyCommitted,
         CommitteeSizeTooLarge,
+        CommitteeSizeZero,
+        QuorumThresholdZero,
+        SignatureFromRotatedOutKey,
+        AgreedRecoverableNotAttesterKey,
+        CommissionIncreaseTooLarge,
+        TooManyNominators,
+        TooManyCommissionChangesInRound,
         InfluxSignatureAlreadySubmitted,
         InfluxMessageHashIncorrect,
+        AttesterKeyAlreadyInUse,
+        InvalidKeyPossessionProof,
+        BatchMessageEncodingNotNativeToTarget,
     }
 
     #[pallet::call]
@@ -520,6 +808,127 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Schedule the keys of the attester to be replaced at the next committee transition.
+        /// Members of the current committee keep signing with their keys until the next committee,
+        /// announced to the targets with the new keys, takes over.
+        /// Each new key signs KEY_ROTATION_PROOF_PREFIX followed by the encoded attester account.
+        #[pallet::weight(T::WeightInfo::rotate_keys())]
+        pub fn rotate_keys(
+            origin: OriginFor<T>,
+            ecdsa_key: [u8; 33],
+            ed25519_key: [u8; 32],
+            sr25519_key: [u8; 32],
+            ecdsa_proof: [u8; 65],
+            ed25519_proof: [u8; 64],
+            sr25519_proof: [u8; 64],
+        ) -> DispatchResult {
+            let attester = ensure_signed(origin)?;
+
+            let attester_info = Attesters::<T>::get(&attester).ok_or(Error::<T>::NotRegistered)?;
+            // Ensure the new ECDSA key can be recovered on the EVM targets
+            ecdsa_pubkey_to_eth_address(&ecdsa_key)?;
+
+            let keys = AttesterKeys {
+                key_ed: ed25519_key,
+                key_ec: ecdsa_key,
+                key_sr: sr25519_key,
+            };
+            for key_id in keys.key_ids() {
+                if let Some(owner) = AttesterKeyOwners::<T>::get(&key_id) {
+                    ensure!(owner == attester, Error::<T>::AttesterKeyAlreadyInUse);
+                }
+            }
+
+            let rotated_info = AttesterInfo {
+                key_ed: ed25519_key,
+                key_ec: ecdsa_key,
+                key_sr: sr25519_key,
+                ..attester_info
+            };
+            let proof_payload = Self::key_rotation_proof_payload(&attester);
+            for (key_type, proof) in [
+                (ECDSA_ATTESTER_KEY_TYPE_ID, &ecdsa_proof[..]),
+                (ED25519_ATTESTER_KEY_TYPE_ID, &ed25519_proof[..]),
+                (SR25519_ATTESTER_KEY_TYPE_ID, &sr25519_proof[..]),
+            ] {
+                // ECDSA keys sign the blake2 hash of the payload, as sp_core::ecdsa::Pair::sign does
+                let signed_message = match key_type {
+                    ECDSA_ATTESTER_KEY_TYPE_ID => blake2_256(&proof_payload).to_vec(),
+                    _ => proof_payload.clone(),
+                };
+                let is_verified = rotated_info
+                    .verify_attestation_signature(
+                        key_type,
+                        &signed_message,
+                        proof,
+                        vec![],
+                        &GatewayVendor::Attesters,
+                    )
+                    .unwrap_or(false);
+                ensure!(is_verified, Error::<T>::InvalidKeyPossessionProof);
+            }
+
+            // Release the keys of a rotation scheduled before, unless the attester still uses them
+            if let Some(replaced_keys) = PendingKeyRotations::<T>::take(&attester) {
+                Self::release_keys(&attester, &replaced_keys);
+            }
+            for key_id in keys.key_ids() {
+                AttesterKeyOwners::<T>::insert(key_id, &attester);
+            }
+            PendingKeyRotations::<T>::insert(&attester, keys);
+
+            Self::deposit_event(Event::AttesterKeyRotationScheduled(attester));
+
+            Ok(())
+        }
+
+        /// Lowering the commission applies immediately. Increases are capped by MaxCommissionIncrease
+        ///     and apply at the first shuffling round after the notice period, so that nominators can unnominate before.
+        /// At most MAX_COMMISSION_CHANGES_PER_ROUND increases apply at a single round.
+        #[pallet::weight(T::WeightInfo::set_commission())]
+        pub fn set_commission(origin: OriginFor<T>, commission: Percent) -> DispatchResult {
+            let attester = ensure_signed(origin)?;
+
+            let current_commission = Attesters::<T>::get(&attester)
+                .ok_or(Error::<T>::NotRegistered)?
+                .commission;
+
+            if commission <= current_commission {
+                PendingCommissionChanges::<T>::remove(&attester);
+                Self::apply_commission(&attester, commission);
+                return Ok(())
+            }
+
+            ensure!(
+                commission
+                    .deconstruct()
+                    .saturating_sub(current_commission.deconstruct())
+                    <= T::MaxCommissionIncrease::get().deconstruct(),
+                Error::<T>::CommissionIncreaseTooLarge
+            );
+
+            let effective_at = Self::shuffling_round_at_or_after(
+                <frame_system::Pallet<T>>::block_number()
+                    .saturating_add(T::CommissionChangeNoticePeriod::get()),
+            );
+            CommissionChangesDue::<T>::try_mutate(effective_at, |due| {
+                if due.contains(&attester) {
+                    return Ok(())
+                }
+                due.try_push(attester.clone())
+            })
+            .map_err(|_| Error::<T>::TooManyCommissionChangesInRound)?;
+            PendingCommissionChanges::<T>::insert(&attester, (commission, effective_at));
+
+            Self::deposit_event(Event::CommissionChangeScheduled(
+                attester,
+                commission,
+                effective_at,
+            ));
+
+            Ok(())
+        }
+
         #[pallet::weight(10_000)]
         pub fn remove_attestation_target(origin: OriginFor<T>, target: TargetId) -> DispatchResult {
             ensure_root(origin)?;
@@ -557,10 +966,15 @@ pub mod pallet {
             let attester = ensure_signed(origin)?;
 
             // Ensure the attester is registered
-            ensure!(
-                Attesters::<T>::contains_key(&attester),
-                Error::<T>::NotRegistered
-            );
+            let attester_info = Attesters::<T>::get(&attester).ok_or(Error::<T>::NotRegistered)?;
+
+            // Substrate targets verify the attestations with sr25519 keys only
+            if Self::batch_message_encoding(&target) == BatchMessageEncoding::Scale {
+                ensure!(
+                    recoverable.as_slice() == attester_info.key_sr.as_slice(),
+                    Error::<T>::AgreedRecoverableNotAttesterKey
+                );
+            }
 
             AttestersAgreements::<T>::insert(&attester, target, recoverable.clone());
 
@@ -630,6 +1044,72 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Change the size of the committees selected at the next shuffling.
+        /// The current committee keeps its size, which its quorums are counted against, until it's rotated out.
+        #[pallet::weight(T::WeightInfo::set_committee_size())]
+        pub fn set_committee_size(origin: OriginFor<T>, committee_size: u32) -> DispatchResult {
+            ensure_root(origin)?;
+
+            ensure!(committee_size > 0, Error::<T>::CommitteeSizeZero);
+            ensure!(
+                committee_size <= T::ActiveSetSize::get(),
+                Error::<T>::CommitteeSizeTooLarge
+            );
+
+            CommitteeSizeOverride::<T>::put(committee_size);
+
+            Self::deposit_event(Event::CommitteeSizeSet(committee_size));
+
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::set_quorum_config())]
+        pub fn set_quorum_config(
+            origin: OriginFor<T>,
+            target: TargetId,
+            quorum_config: QuorumConfig,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            ensure!(
+                !quorum_config.threshold.is_zero(),
+                Error::<T>::QuorumThresholdZero
+            );
+
+            QuorumConfigs::<T>::insert(target, quorum_config);
+
+            Self::deposit_event(Event::QuorumConfigSet(target, quorum_config));
+
+            Ok(())
+        }
+
+        /// Switch the layout of the batch messages signed for the target, e.g. to EthAbiV2 once its EVM verifier
+        ///     accepts committees of any size. Batches still awaiting attestations are signed in the new layout.
+        /// Only Substrate targets registered with the Scale codec opt into the Scale encoding, after which
+        ///     the attesters agree to the target again with their sr25519 keys.
+        #[pallet::weight(T::WeightInfo::set_batch_message_encoding())]
+        pub fn set_batch_message_encoding(
+            origin: OriginFor<T>,
+            target: TargetId,
+            encoding: BatchMessageEncoding,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            if encoding == BatchMessageEncoding::Scale {
+                ensure!(
+                    Self::native_batch_message_encoding(&target)
+                        == Some(BatchMessageEncoding::Scale),
+                    Error::<T>::BatchMessageEncodingNotNativeToTarget
+                );
+            }
+
+            BatchMessageEncodings::<T>::insert(target, encoding);
+
+            Self::deposit_event(Event::BatchMessageEncodingSet(target, encoding));
+
+            Ok(())
+        }
+
         #[pallet::weight(10_000)]
         pub fn submit_attestation(
             // Must be signed by the attester in current Committee
@@ -670,9 +1150,25 @@ pub mod pallet {
             let vendor = <T as Config>::Xdns::get_verification_vendor(&target)
                 .map_err(|_| Error::<T>::XdnsTargetNotActive)?;
 
+            let encoding = Self::batch_message_encoding(&target);
+
+            // Substrate targets verify the attestations with the sr25519 key the attester agreed to for the target
+            let key_type = match encoding {
+                BatchMessageEncoding::EthAbi | BatchMessageEncoding::EthAbiV2 =>
+                    ECDSA_ATTESTER_KEY_TYPE_ID,
+                BatchMessageEncoding::Scale => {
+                    ensure!(
+                        attested_recoverable.as_slice() == attester.key_sr.as_slice(),
+                        Error::<T>::AgreedRecoverableNotAttesterKey
+                    );
+                    ensure!(signature.len() == 64, Error::<T>::InvalidSignature);
+                    SR25519_ATTESTER_KEY_TYPE_ID
+                },
+            };
+
             let is_verified = attester
                 .verify_attestation_signature(
-                    ECDSA_ATTESTER_KEY_TYPE_ID,
+                    key_type,
                     &message.encode(),
                     &signature,
                     attested_recoverable,
@@ -680,11 +1176,38 @@ pub mod pallet {
                 )
                 .map_err(|_| Error::<T>::InvalidSignature)?;
 
-            let signature_65b: [u8; 65] = signature
-                .try_into()
-                .map_err(|_| Error::<T>::InvalidSignature)?;
+            // sr25519 signatures are 64 bytes long and are stored right-padded to 65 bytes
+            let signature_65b: [u8; 65] = match key_type {
+                ECDSA_ATTESTER_KEY_TYPE_ID => signature
+                    .try_into()
+                    .map_err(|_| Error::<T>::InvalidSignature)?,
+                _ => {
+                    let signature_64b: [u8; 64] = signature
+                        .as_slice()
+                        .try_into()
+                        .map_err(|_| Error::<T>::InvalidSignature)?;
+                    let mut signature_65b = [0u8; 65];
+                    signature_65b[..64].copy_from_slice(&signature_64b);
+                    signature_65b
+                },
+            };
 
             if !is_verified {
+                // Signatures with the keys replaced at the last rotations are rejected without a slash
+                if key_type == ECDSA_ATTESTER_KEY_TYPE_ID
+                    && RotatedOutEcdsaKeys::<T>::get(&account_id)
+                        .iter()
+                        .any(|rotated_out_key| {
+                            verify_secp256k1_ecdsa_signature(
+                                &message.encode(),
+                                &signature_65b,
+                                rotated_out_key,
+                            )
+                            .unwrap_or(false)
+                        })
+                {
+                    return Err(Error::<T>::SignatureFromRotatedOutKey.into())
+                }
                 PermanentSlashes::<T>::append(account_id);
                 return Err(Error::<T>::RejectingFromSlashedAttester.into())
             }
@@ -697,7 +1220,7 @@ pub mod pallet {
                 // Find the batch with the status PendingAttestation and the same message
                 let batch = batches
                     .iter_mut()
-                    .find(|batch| batch.message_hash() == message)
+                    .find(|batch| batch.message_hash_for(&encoding) == message)
                     .ok_or(Error::<T>::BatchNotFound)?;
 
                 ensure!(
@@ -719,25 +1242,24 @@ pub mod pallet {
                 batch.signatures.push((attester.index, signature_65b));
 
                 // Update the status of the batch
-                let quorum = (T::CommitteeSize::get() * 2 / 3) as usize;
-                let full_approval = T::CommitteeSize::get() as usize;
-                if batch.signatures.len() >= quorum {
+                let full_approval = CurrentCommittee::<T>::get().len();
+                if Self::has_quorum(&target, &batch.signatures) {
                     log::debug!(
                         "Batch {:?} is ready for submission by majority",
-                        batch.message_hash()
+                        batch.message_hash_for(&encoding)
                     );
                 }
-                if batch.signatures.len() >= full_approval {
+                if full_approval > 0 && batch.signatures.len() >= full_approval {
                     batch.status = BatchStatus::ReadyForSubmissionFullyApproved;
                     log::debug!(
                         "Batch {:?} is ready for submission by full approval",
-                        batch.message_hash()
+                        batch.message_hash_for(&encoding)
                     );
                     Self::deposit_event(Event::NewConfirmationBatch(
                         target,
                         batch.clone(),
-                        batch.message(),
-                        batch.message_hash(),
+                        batch.message_for(&encoding),
+                        batch.message_hash_for(&encoding),
                     ));
                 }
 
@@ -766,12 +1288,23 @@ pub mod pallet {
                 ExecutionSource::decode(&mut &target_escrow_address[..])
                     .map_err(|_| Error::<T>::XdnsGatewayDoesNotHaveEscrowAddressRegistered)?;
 
-            let escrow_batch_success_descriptor = b"BatchApplied:Event(\
-                MessageHash:H256,\
-                BeneficiaryOnTarget:Account20,\
-                AttestingCommittee:H256,\
-            )"
-            .to_vec();
+            let encoding = Self::batch_message_encoding(&target);
+
+            let escrow_batch_success_descriptor = match encoding {
+                BatchMessageEncoding::EthAbi | BatchMessageEncoding::EthAbiV2 =>
+                    b"BatchApplied:Event(\
+                    MessageHash:H256,\
+                    BeneficiaryOnTarget:Account20,\
+                    AttestingCommittee:H256,\
+                )"
+                    .to_vec(),
+                BatchMessageEncoding::Scale => b"BatchApplied:Event(\
+                    MessageHash:H256,\
+                    BeneficiaryOnTarget:Account32,\
+                    AttestingCommittee:H256,\
+                )"
+                .to_vec(),
+            };
 
             #[cfg(not(feature = "test-skip-verification"))]
             let escrow_inclusion_receipt = T::Portal::verify_event_inclusion(
@@ -798,18 +1331,38 @@ pub mod pallet {
             #[cfg(feature = "test-skip-verification")]
             let recoded_batch_event_bytes = escrow_inclusion_receipt.message;
 
-            let on_target_batch_event =
-                TargetBatchDispatchEvent::decode(&mut &recoded_batch_event_bytes[..])
+            let (on_target_batch_hash, executor_on_target, attesting_committee) = match encoding {
+                BatchMessageEncoding::EthAbi | BatchMessageEncoding::EthAbiV2 => {
+                    let on_target_batch_event =
+                        TargetBatchDispatchEvent::decode(&mut &recoded_batch_event_bytes[..])
+                            .map_err(|_| Error::<T>::InvalidTargetInclusionProof)?;
+                    (
+                        on_target_batch_event.hash,
+                        on_target_batch_event.executor_on_target.as_bytes().to_vec(),
+                        on_target_batch_event.attesting_committee,
+                    )
+                },
+                BatchMessageEncoding::Scale => {
+                    let on_target_batch_event = SubstrateTargetBatchDispatchEvent::decode(
+                        &mut &recoded_batch_event_bytes[..],
+                    )
                     .map_err(|_| Error::<T>::InvalidTargetInclusionProof)?;
+                    (
+                        on_target_batch_event.hash,
+                        on_target_batch_event.executor_on_target.to_vec(),
+                        on_target_batch_event.attesting_committee,
+                    )
+                },
+            };
 
             // Assume that the event is emitted by the escrow contract is H256
             let recovered_enacted_batch_hash: H256 =
-                H256::decode(&mut &on_target_batch_event.hash[..])
+                H256::decode(&mut &on_target_batch_hash[..])
                     .map_err(|_| Error::<T>::UnexpectedBatchHashRecoveredFromCommitment)?;
 
             let batches = Self::get_batches_to_commit(target);
 
-            let committee_to_penalize = match on_target_batch_event.attesting_committee.0.as_slice()
+            let committee_to_penalize = match attesting_committee.0.as_slice()
                 == <T as frame_system::Config>::Hashing::hash(
                     &mut &PreviousCommittee::<T>::get().encode()[..],
                 )
@@ -822,7 +1375,7 @@ pub mod pallet {
 
             let batch = match batches
                 .iter()
-                .find(|batch| batch.message_hash() == recovered_enacted_batch_hash)
+                .find(|batch| batch.message_hash_for(&encoding) == recovered_enacted_batch_hash)
             {
                 Some(batch) => batch,
                 None => {
@@ -862,18 +1415,13 @@ pub mod pallet {
             Self::deposit_event(Event::BatchCommitted(
                 target,
                 batch.clone(),
-                batch.message(),
-                batch.message_hash(),
+                batch.message_for(&encoding),
+                batch.message_hash_for(&encoding),
                 to_pay,
             ));
 
             if to_pay > Zero::zero() {
-                Self::reward_submitter(
-                    &submitter,
-                    &on_target_batch_event.executor_on_target,
-                    &target,
-                    to_pay,
-                )?;
+                Self::reward_submitter(&submitter, &executor_on_target, &target, to_pay)?;
                 PaidFinalityFees::<T>::append(target, to_pay);
             }
 
@@ -886,11 +1434,12 @@ pub mod pallet {
 
             for target in AttestationTargets::<T>::get() {
                 let pending_attestation_batches = Self::get_all_batches_to_sign(target);
+                let encoding = Self::batch_message_encoding(&target);
                 Self::deposit_event(Event::CurrentPendingAttestationBatches(
                     target,
                     pending_attestation_batches
                         .iter()
-                        .map(|batch| (batch.index, batch.message_hash()))
+                        .map(|batch| (batch.index, batch.message_hash_for(&encoding)))
                         .collect::<Vec<(u32, H256)>>(),
                 ));
             }
@@ -988,6 +1537,38 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config> AttestersStakingApi<T::AccountId, BalanceOf<T>, DispatchError> for Pallet<T> {
+        fn nominate_on_behalf(
+            nominator: &T::AccountId,
+            attester: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> Result<(), DispatchError> {
+            ensure!(
+                Attesters::<T>::contains_key(attester),
+                Error::<T>::AttesterNotFound
+            );
+            ensure!(
+                amount >= T::MinNominatorBond::get(),
+                Error::<T>::NominatorBondTooSmall
+            );
+
+            Self::do_nominate(nominator, attester, amount)?;
+            Self::deposit_event(Event::Nominated(
+                nominator.clone(),
+                attester.clone(),
+                amount,
+            ));
+            Ok(())
+        }
+
+        /// Entries of the period that ended are swept in the following blocks.
+        fn start_nominations_snapshot_period() -> Weight {
+            NominationsSnapshotPeriod::<T>::mutate(|period| *period = period.wrapping_add(1));
+            NominationsSnapshotSweepCursor::<T>::put(Vec::<u8>::new());
+            T::DbWeight::get().reads_writes(1, 2)
+        }
+    }
+
     impl<T: Config> AttestersWriteApi<T::AccountId, DispatchError> for Pallet<T> {
         fn request_sfx_attestation_commit(
             target: TargetId,
@@ -1045,6 +1626,35 @@ pub mod pallet {
             })
         }
 
+        fn request_gmp_message_attestation(
+            target: TargetId,
+            message_id: H256,
+        ) -> Result<(), DispatchError> {
+            NextBatch::<T>::try_mutate(target, |next_batch| {
+                let next_batch = next_batch
+                    .as_mut()
+                    .ok_or::<DispatchError>(Error::<T>::BatchNotFound.into())?;
+
+                match &mut next_batch.gmp_messages {
+                    Some(message_ids) => {
+                        ensure!(
+                            !message_ids.contains(&message_id),
+                            Error::<T>::GmpMessageAlreadyRequested
+                        );
+                        ensure!(
+                            message_ids.len() < MAX_GMP_MESSAGES_PER_BATCH,
+                            Error::<T>::GmpMessagesPerBatchExceeded
+                        );
+                        message_ids.push(message_id);
+                    },
+                    None => {
+                        next_batch.gmp_messages = Some(vec![message_id]);
+                    },
+                }
+                Ok(())
+            })
+        }
+
         fn request_ban_attesters_attestation(
             ban_attester: &T::AccountId,
         ) -> Result<(), DispatchError> {
@@ -1183,6 +1793,16 @@ pub mod pallet {
                 .collect()
         }
 
+        fn read_nominations_snapshot(
+            for_attester: &T::AccountId,
+        ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+            let period = NominationsSnapshotPeriod::<T>::get();
+            NominationsSnapshot::<T>::iter_prefix(for_attester)
+                .filter(|(_, (snapshot_period, _))| snapshot_period == &period)
+                .map(|(nominator, (_, balance))| (nominator, balance))
+                .collect()
+        }
+
         /// Getter for the attestation targets.
         fn get_activated_targets() -> Vec<TargetId> {
             AttestationTargets::<T>::get()
@@ -1290,9 +1910,14 @@ pub mod pallet {
             target: TargetId,
             message: &Vec<u8>,
         ) -> Vec<BatchMessage<BlockNumberFor<T>>> {
+            let encoding = Self::batch_message_encoding(&target);
             Batches::<T>::get(target)
                 .iter()
-                .find(|batches| batches.iter().any(|batch| &batch.message() == message))
+                .find(|batches| {
+                    batches
+                        .iter()
+                        .any(|batch| &batch.message_for(&encoding) == message)
+                })
                 .unwrap()
                 .clone()
         }
@@ -1322,6 +1947,18 @@ pub mod pallet {
                 None => T::DefaultCommission::get(),
             };
 
+            let keys = AttesterKeys {
+                key_ed: ed25519_key,
+                key_ec: ecdsa_key,
+                key_sr: sr25519_key,
+            };
+            for key_id in keys.key_ids() {
+                ensure!(
+                    !AttesterKeyOwners::<T>::contains_key(&key_id),
+                    Error::<T>::AttesterKeyAlreadyInUse
+                );
+            }
+
             let next_index = Attesters::<T>::iter().count() as u32;
 
             Attesters::<T>::insert(
@@ -1334,6 +1971,9 @@ pub mod pallet {
                     index: next_index,
                 },
             );
+            for key_id in keys.key_ids() {
+                AttesterKeyOwners::<T>::insert(key_id, &account_id);
+            }
 
             // Self nominate in order to be part of the active set selection
             Self::do_nominate(&account_id, &account_id, self_nominate_amount)?;
@@ -1379,7 +2019,63 @@ pub mod pallet {
         }
 
         pub fn committee_size() -> usize {
-            T::CommitteeSize::get() as usize
+            CommitteeSizeOverride::<T>::get().unwrap_or_else(T::CommitteeSize::get) as usize
+        }
+
+        /// Self-bond and nominations backing the given attester.
+        pub fn attester_stake(attester: &T::AccountId) -> BalanceOf<T> {
+            Nominations::<T>::iter_prefix(attester)
+                .map(|(_, balance)| balance)
+                .fold(Zero::zero(), |acc, balance| acc + balance)
+        }
+
+        /// Encoding of the batch messages set by governance for the target, otherwise the EVM encoding.
+        /// Substrate targets opt into the Scale encoding with set_batch_message_encoding, as their agreements
+        ///     and batches in flight follow the EVM encoding until then.
+        pub fn batch_message_encoding(target: &TargetId) -> BatchMessageEncoding {
+            BatchMessageEncodings::<T>::get(target).unwrap_or(BatchMessageEncoding::EthAbi)
+        }
+
+        /// Encoding native to the target's execution layer registered in XDNS
+        pub fn native_batch_message_encoding(target: &TargetId) -> Option<BatchMessageEncoding> {
+            match (
+                T::Xdns::get_execution_vendor(target),
+                T::Xdns::get_target_codec(target),
+            ) {
+                (Ok(execution_vendor), Ok(codec)) =>
+                    Some(BatchMessageEncoding::select(&execution_vendor, &codec)),
+                _ => None,
+            }
+        }
+
+        /// Check whether the signatures collected for a batch reach the quorum configured for the target.
+        /// Head count quorum is a share of the members of the current committee, rounded up to at least one,
+        ///     while stake-weighted quorum is a share of the stake snapshotted as the current committee took over.
+        pub fn has_quorum(target: &TargetId, signatures: &[(u32, Signature65b)]) -> bool {
+            let quorum_config = QuorumConfigs::<T>::get(target);
+            match quorum_config.kind {
+                QuorumKind::HeadCount => {
+                    let current_committee_size = CurrentCommittee::<T>::get().len() as u32;
+                    current_committee_size > 0
+                        && signatures.len()
+                            >= quorum_config
+                                .threshold
+                                .mul_ceil(current_committee_size)
+                                .max(1) as usize
+                },
+                QuorumKind::StakeWeighted => {
+                    let mut total_stake: BalanceOf<T> = Zero::zero();
+                    let mut signed_stake: BalanceOf<T> = Zero::zero();
+                    for (member_index, stake) in CurrentCommitteeStakes::<T>::get() {
+                        total_stake = total_stake.saturating_add(stake);
+                        if signatures.iter().any(|(index, _)| *index == member_index) {
+                            signed_stake = signed_stake.saturating_add(stake);
+                        }
+                    }
+                    !total_stake.is_zero()
+                        && signed_stake >= quorum_config.threshold.mul_ceil(total_stake)
+                },
+            }
         }
 
         /// This function applies a partial slash to the stakes of an attester and its nominators.
@@ -1461,11 +2157,12 @@ pub mod pallet {
             target: TargetId,
             message_hash: &H256,
         ) -> DispatchResult {
+            let encoding = Self::batch_message_encoding(&target);
             Batches::<T>::try_mutate(target, |batches_option| {
                 let batches = batches_option.as_mut().ok_or(Error::<T>::BatchNotFound)?;
                 let batch_by_message = batches
                     .iter_mut()
-                    .find(|batch| &batch.message_hash() == message_hash)
+                    .find(|batch| &batch.message_hash_for(&encoding) == message_hash)
                     .ok_or(Error::<T>::BatchNotFound)?;
 
                 batch_by_message.status = BatchStatus::Committed;
@@ -1475,14 +2172,14 @@ pub mod pallet {
 
         pub fn reward_submitter(
             submitter: &T::AccountId,
-            submitter_on_target: &H160,
+            submitter_on_target: &[u8],
             target: &TargetId,
             to_pay: BalanceOf<T>,
         ) -> DispatchResult {
             let attester_recoverable: Vec<u8> = AttestersAgreements::<T>::get(submitter, target)
                 .ok_or(Error::<T>::AttesterDidNotAgreeToNewTarget)?;
 
-            if attester_recoverable == submitter_on_target.encode() {
+            if attester_recoverable.as_slice() == submitter_on_target {
                 T::Currency::transfer(
                     &T::TreasuryAccounts::get_treasury_account(
                         t3rn_primitives::TreasuryAccount::Fee,
@@ -1560,8 +2257,12 @@ pub mod pallet {
             target: TargetId,
             message: Vec<u8>,
         ) -> Option<BatchMessage<BlockNumberFor<T>>> {
+            let encoding = Self::batch_message_encoding(&target);
             match Batches::<T>::get(target) {
-                Some(batches) => batches.iter().find(|&b| b.message() == message).cloned(),
+                Some(batches) => batches
+                    .iter()
+                    .find(|&b| b.message_for(&encoding) == message)
+                    .cloned(),
                 None => None,
             }
         }
@@ -1570,10 +2271,11 @@ pub mod pallet {
             target: TargetId,
             message_hash: H256,
         ) -> Option<BatchMessage<BlockNumberFor<T>>> {
+            let encoding = Self::batch_message_encoding(&target);
             match Batches::<T>::get(target) {
                 Some(batches) => batches
                     .iter()
-                    .find(|&b| b.message_hash() == message_hash)
+                    .find(|&b| b.message_hash_for(&encoding) == message_hash)
                     .cloned(),
                 None => None,
             }
@@ -1618,13 +2320,15 @@ pub mod pallet {
         pub fn get_latest_batch_to_sign_hash(target: TargetId) -> Option<H256> {
             let mut batches = Self::get_batches(target, BatchStatus::PendingAttestation);
             batches.sort_by(|a, b| b.created.cmp(&a.created));
-            batches.iter().map(|b| b.message_hash()).next()
+            let encoding = Self::batch_message_encoding(&target);
+            batches.iter().map(|b| b.message_hash_for(&encoding)).next()
         }
 
         pub fn get_latest_batch_to_sign_message(target: TargetId) -> Option<Vec<u8>> {
             let mut batches = Self::get_batches(target, BatchStatus::PendingAttestation);
             batches.sort_by(|a, b| b.created.cmp(&a.created));
-            batches.iter().map(|b| b.message()).next()
+            let encoding = Self::batch_message_encoding(&target);
+            batches.iter().map(|b| b.message_for(&encoding)).next()
         }
 
         fn update_sorted_nominated_attesters(
@@ -1664,6 +2368,12 @@ pub mod pallet {
                 T::Currency::free_balance(nominator) >= amount,
                 Error::<T>::NominatorNotEnoughBalance
             );
+            ensure!(
+                Nominations::<T>::contains_key(attester, nominator)
+                    || Nominations::<T>::iter_prefix(attester).count()
+                        < MAX_NOMINATORS_PER_ATTESTER,
+                Error::<T>::TooManyNominators
+            );
 
             let current_nomination =
                 Nominations::<T>::get(attester, nominator).unwrap_or(Zero::zero());
@@ -1700,6 +2410,296 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Set the attester's commission and announce the change.
+        fn apply_commission(attester: &T::AccountId, commission: Percent) {
+            Attesters::<T>::mutate(attester, |maybe_info| {
+                if let Some(attester_info) = maybe_info.as_mut() {
+                    attester_info.commission = commission;
+                }
+            });
+            Self::deposit_event(Event::CommissionChanged(attester.clone(), commission));
+        }
+
+        /// First shuffling round block at or after the given block
+        fn shuffling_round_at_or_after(block: BlockNumberFor<T>) -> BlockNumberFor<T> {
+            let shuffling_frequency = T::ShufflingFrequency::get();
+            let into_round = block % shuffling_frequency;
+            if into_round.is_zero() {
+                block
+            } else {
+                block.saturating_add(shuffling_frequency - into_round)
+            }
+        }
+
+        /// Apply the commission increases due at the shuffling round of block `n`, bounded by
+        ///     MAX_COMMISSION_CHANGES_PER_ROUND. Attesters that lowered their commission since are skipped.
+        pub fn process_commission_changes(
+            n: BlockNumberFor<T>,
+            aggregated_weight: Weight,
+        ) -> Weight {
+            let mut aggregated_weight = aggregated_weight;
+            let due_attesters = CommissionChangesDue::<T>::take(n);
+            aggregated_weight += T::DbWeight::get().reads_writes(1, 1);
+
+            for attester in due_attesters {
+                aggregated_weight += T::DbWeight::get().reads(1);
+                if let Some((commission, effective_at)) =
+                    PendingCommissionChanges::<T>::get(&attester)
+                {
+                    if effective_at == n {
+                        PendingCommissionChanges::<T>::remove(&attester);
+                        Self::apply_commission(&attester, commission);
+                        aggregated_weight += T::DbWeight::get().reads_writes(1, 2);
+                    }
+                }
+            }
+
+            aggregated_weight
+        }
+
+        /// Add the nominations of the active set to the ones summed over the rounds of the current distribution
+        ///     period, which split the rewards of the attesters at the end of the period.
+        /// Bounded by the ActiveSetSize attesters of at most MAX_NOMINATORS_PER_ATTESTER nominators each. Entries left
+        ///     from past periods restart from the current nomination, the rest are swept in the following blocks.
+        pub fn snapshot_active_set_nominations(aggregated_weight: Weight) -> Weight {
+            let mut aggregated_weight = aggregated_weight;
+            let period = NominationsSnapshotPeriod::<T>::get();
+            aggregated_weight += T::DbWeight::get().reads(1);
+
+            for attester in ActiveSet::<T>::get() {
+                for (nominator, balance) in Nominations::<T>::iter_prefix(&attester) {
+                    NominationsSnapshot::<T>::mutate(&attester, nominator, |snapshot| {
+                        let summed = match snapshot {
+                            Some((snapshot_period, summed)) if snapshot_period == &period =>
+                                summed.saturating_add(balance),
+                            _ => balance,
+                        };
+                        *snapshot = Some((period, summed));
+                    });
+                    aggregated_weight += T::DbWeight::get().reads_writes(2, 1);
+                }
+            }
+
+            aggregated_weight
+        }
+
+        /// Remove the nominations snapshot entries of past distribution periods, visiting at most
+        ///     MAX_SNAPSHOT_ENTRIES_SWEPT_PER_BLOCK entries from where the previous block left the sweep.
+        pub fn sweep_stale_nominations_snapshot(aggregated_weight: Weight) -> Weight {
+            let mut aggregated_weight = aggregated_weight;
+            aggregated_weight += T::DbWeight::get().reads(1);
+            let cursor = match NominationsSnapshotSweepCursor::<T>::get() {
+                Some(cursor) => cursor,
+                None => return aggregated_weight,
+            };
+            let period = NominationsSnapshotPeriod::<T>::get();
+            aggregated_weight += T::DbWeight::get().reads(1);
+
+            let mut entries = NominationsSnapshot::<T>::iter_from(cursor);
+            let mut visited: u32 = 0;
+            let mut stale_entries = Vec::new();
+            while visited < MAX_SNAPSHOT_ENTRIES_SWEPT_PER_BLOCK {
+                match entries.next() {
+                    Some((attester, nominator, (snapshot_period, _))) => {
+                        if snapshot_period != period {
+                            stale_entries.push((attester, nominator));
+                        }
+                        visited += 1;
+                    },
+                    None => break,
+                }
+            }
+            aggregated_weight += T::DbWeight::get().reads(visited as u64);
+
+            if visited < MAX_SNAPSHOT_ENTRIES_SWEPT_PER_BLOCK {
+                NominationsSnapshotSweepCursor::<T>::kill();
+            } else {
+                NominationsSnapshotSweepCursor::<T>::put(entries.last_raw_key().to_vec());
+            }
+            aggregated_weight += T::DbWeight::get().writes(1);
+
+            for (attester, nominator) in stale_entries {
+                NominationsSnapshot::<T>::remove(&attester, &nominator);
+                aggregated_weight += T::DbWeight::get().writes(1);
+            }
+
+            aggregated_weight
+        }
+
+        /// Snapshot the stake backing the members of the new current committee, which its stake-weighted quorums
+        ///     are counted against until it's rotated out.
+        pub fn snapshot_current_committee_stakes(aggregated_weight: Weight) -> Weight {
+            let mut aggregated_weight = aggregated_weight;
+            let mut committee_stakes: Vec<(u32, BalanceOf<T>)> = Vec::new();
+
+            for member in CurrentCommittee::<T>::get() {
+                aggregated_weight += T::DbWeight::get().reads(1);
+                if let Some(attester_info) = Attesters::<T>::get(&member) {
+                    let mut stake: BalanceOf<T> = Zero::zero();
+                    for (_, balance) in Nominations::<T>::iter_prefix(&member) {
+                        stake = stake.saturating_add(balance);
+                        aggregated_weight += T::DbWeight::get().reads(1);
+                    }
+                    committee_stakes.push((attester_info.index, stake));
+                }
+            }
+
+            CurrentCommitteeStakes::<T>::put(committee_stakes);
+            aggregated_weight += T::DbWeight::get().reads_writes(1, 1);
+
+            aggregated_weight
+        }
+
+        /// Index the keys of the registered attesters and their pending rotations, returning the amount of attesters
+        fn index_attester_keys() -> u64 {
+            let mut indexed: u64 = 0;
+            for (attester, attester_info) in Attesters::<T>::iter() {
+                let keys = PendingKeyRotations::<T>::get(&attester)
+                    .into_iter()
+                    .chain([AttesterKeys::from(&attester_info)]);
+                for key_id in keys.flat_map(|keys| keys.key_ids()) {
+                    AttesterKeyOwners::<T>::insert(key_id, &attester);
+                }
+                indexed += 1;
+            }
+            indexed
+        }
+
+        /// Payload each of the new keys of a rotation signs, binding the keys to the attester
+        pub fn key_rotation_proof_payload(attester: &T::AccountId) -> Vec<u8> {
+            let mut payload = KEY_ROTATION_PROOF_PREFIX.to_vec();
+            payload.extend_from_slice(&attester.encode());
+            payload
+        }
+
+        /// Release the keys no longer held by the attester, nor by its scheduled rotations
+        fn release_keys(attester: &T::AccountId, released_keys: &AttesterKeys) {
+            let keys_in_use = Attesters::<T>::get(attester)
+                .map(|attester_info| AttesterKeys::from(&attester_info))
+                .into_iter()
+                .chain(PendingKeyRotations::<T>::get(attester))
+                .chain(AnnouncedKeyRotations::<T>::get(attester))
+                .flat_map(|keys| keys.key_ids())
+                .collect::<Vec<_>>();
+
+            for key_id in released_keys.key_ids() {
+                if !keys_in_use.contains(&key_id)
+                    && AttesterKeyOwners::<T>::get(&key_id).as_ref() == Some(attester)
+                {
+                    AttesterKeyOwners::<T>::remove(&key_id);
+                }
+            }
+        }
+
+        /// Recoverable agreed with a target under the previous keys, as it reads under the new keys:
+        /// eth addresses for EVM targets, sr25519 or ed25519 keys for Substrate targets
+        fn rotated_recoverable(
+            previous_keys: &AttesterKeys,
+            keys: &AttesterKeys,
+            recoverable: &[u8],
+        ) -> Option<Vec<u8>> {
+            let mut replaced_recoverables = vec![
+                (previous_keys.key_sr.to_vec(), keys.key_sr.to_vec()),
+                (previous_keys.key_ed.to_vec(), keys.key_ed.to_vec()),
+            ];
+            if let (Ok(previous_address), Ok(new_address)) = (
+                ecdsa_pubkey_to_eth_address(&previous_keys.key_ec),
+                ecdsa_pubkey_to_eth_address(&keys.key_ec),
+            ) {
+                replaced_recoverables.push((previous_address.to_vec(), new_address.to_vec()));
+            }
+            replaced_recoverables
+                .into_iter()
+                .find(|(previous_recoverable, _)| previous_recoverable == recoverable)
+                .map(|(_, new_recoverable)| new_recoverable)
+        }
+
+        /// Apply the scheduled key rotations of attesters outside of the current committee,
+        ///     so that the committee keeps signing with the keys already known to the targets.
+        /// Agreements on targets recovering the attester's ECDSA key are moved to the new key.
+        /// Runs at each committee transition, after the committees are shuffled.
+        /// Rotations announced with the previous transition apply now that their committee took over.
+        /// Pending rotations of the current committee members are announced with the next committee,
+        /// the rest apply right away as no target expects their signatures in the meantime.
+        pub fn process_key_rotations(aggregated_weight: Weight) -> Weight {
+            let mut aggregated_weight = aggregated_weight;
+
+            // Bounded by MAX_KEY_ROTATIONS_PER_TRANSITION, as only pending rotations taken below are announced
+            for (attester, keys) in AnnouncedKeyRotations::<T>::drain().collect::<Vec<_>>() {
+                aggregated_weight += T::DbWeight::get().writes(1);
+                aggregated_weight = Self::apply_key_rotation(&attester, keys, aggregated_weight);
+            }
+
+            let current_committee = CurrentCommittee::<T>::get();
+            aggregated_weight += T::DbWeight::get().reads(1);
+
+            for (attester, keys) in PendingKeyRotations::<T>::drain()
+                .take(MAX_KEY_ROTATIONS_PER_TRANSITION)
+                .collect::<Vec<_>>()
+            {
+                aggregated_weight += T::DbWeight::get().reads_writes(1, 1);
+                if current_committee.contains(&attester) {
+                    AnnouncedKeyRotations::<T>::insert(&attester, keys);
+                    aggregated_weight += T::DbWeight::get().writes(1);
+                } else {
+                    aggregated_weight =
+                        Self::apply_key_rotation(&attester, keys, aggregated_weight);
+                }
+            }
+
+            aggregated_weight
+        }
+
+        fn apply_key_rotation(
+            attester: &T::AccountId,
+            keys: AttesterKeys,
+            aggregated_weight: Weight,
+        ) -> Weight {
+            let mut aggregated_weight = aggregated_weight;
+
+            let previous_keys = match Attesters::<T>::mutate(attester, |maybe_info| {
+                maybe_info.as_mut().map(|attester_info| {
+                    let previous_keys = AttesterKeys::from(&*attester_info);
+                    attester_info.key_ec = keys.key_ec;
+                    attester_info.key_ed = keys.key_ed;
+                    attester_info.key_sr = keys.key_sr;
+                    previous_keys
+                })
+            }) {
+                Some(previous_keys) => previous_keys,
+                // Attester deregistered in the meantime
+                None => return aggregated_weight,
+            };
+            aggregated_weight += T::DbWeight::get().reads_writes(1, 1);
+
+            for (target, recoverable) in
+                AttestersAgreements::<T>::iter_prefix(attester).collect::<Vec<_>>()
+            {
+                if let Some(new_recoverable) =
+                    Self::rotated_recoverable(&previous_keys, &keys, &recoverable)
+                {
+                    AttestersAgreements::<T>::insert(attester, target, new_recoverable);
+                    aggregated_weight += T::DbWeight::get().writes(1);
+                }
+                aggregated_weight += T::DbWeight::get().reads(1);
+            }
+
+            Self::release_keys(attester, &previous_keys);
+            aggregated_weight += T::DbWeight::get().reads_writes(6, 3);
+
+            RotatedOutEcdsaKeys::<T>::mutate(attester, |rotated_out_keys| {
+                rotated_out_keys.push(previous_keys.key_ec);
+                if rotated_out_keys.len() > MAX_ROTATED_OUT_KEYS {
+                    rotated_out_keys.remove(0);
+                }
+            });
+            aggregated_weight += T::DbWeight::get().reads_writes(1, 1);
+
+            Self::deposit_event(Event::AttesterKeysRotated(attester.clone()));
+
+            aggregated_weight
+        }
+
         pub fn get_current_committee_transition_for_target(
             target: &TargetId,
         ) -> CommitteeTransition {
@@ -1711,7 +2711,17 @@ pub mod pallet {
                     if let Some(checked_recoverable) =
                         AttestersAgreements::<T>::get(attester, target)
                     {
-                        committee_transition.push((attester_info.index, checked_recoverable));
+                        // Announce the keys the attester switches to once the next committee takes over
+                        let announced_recoverable = AnnouncedKeyRotations::<T>::get(attester)
+                            .and_then(|keys| {
+                                Self::rotated_recoverable(
+                                    &AttesterKeys::from(&attester_info),
+                                    &keys,
+                                    &checked_recoverable,
+                                )
+                            })
+                            .unwrap_or(checked_recoverable);
+                        committee_transition.push((attester_info.index, announced_recoverable));
                     }
                 }
             }
@@ -1723,13 +2733,11 @@ pub mod pallet {
 
         pub fn get_current_committee_indices() -> CommitteeTransitionIndices {
             let current_committee = CurrentCommittee::<T>::get();
-            let mut committee_indices: CommitteeTransitionIndices = [0; COMMITTEE_SIZE]; // Initialize the committee_indices array
-
-            for (i, attester) in current_committee.iter().enumerate() {
-                if let Some(attester_info) = Attesters::<T>::get(attester) {
-                    committee_indices[i] = attester_info.index;
-                }
-            }
+            let mut committee_indices: CommitteeTransitionIndices = current_committee
+                .iter()
+                .filter_map(|attester| Attesters::<T>::get(attester))
+                .map(|attester_info| attester_info.index)
+                .collect();
 
             committee_indices.sort(); // Sorting the indices in ascending order
 
@@ -1740,7 +2748,7 @@ pub mod pallet {
             let active_set = ActiveSet::<T>::get();
             let active_set_size = active_set.len();
 
-            let mut committee_size = T::CommitteeSize::get() as usize;
+            let mut committee_size = Self::committee_size();
 
             let full_shuffle = if committee_size > active_set_size {
                 committee_size = active_set_size;
@@ -1887,26 +2895,25 @@ pub mod pallet {
             n: BlockNumberFor<T>,
             aggregated_weight: Weight,
         ) -> Weight {
-            let quorum = (T::CommitteeSize::get() * 2 / 3) as usize;
-
             for target in AttestationTargets::<T>::get() {
                 let mut new_next_batch = BatchMessage {
                     created: n,
                     ..Default::default()
                 };
+                let encoding = Self::batch_message_encoding(&target);
                 // If a batch exists, update its status
                 Batches::<T>::mutate(target, |batches| {
                     if let Some(batches) = batches {
                         for batch in batches.iter_mut() {
                             if batch.status == BatchStatus::PendingAttestation
-                                && batch.signatures.len() >= quorum
+                                && Self::has_quorum(&target, &batch.signatures)
                             {
                                 batch.status = BatchStatus::ReadyForSubmissionByMajority;
                                 Self::deposit_event(Event::NewConfirmationBatch(
                                     target,
                                     batch.clone(),
-                                    batch.message(),
-                                    batch.message_hash(),
+                                    batch.message_for(&encoding),
+                                    batch.message_hash_for(&encoding),
                                 ));
                             } else {
                                 // Skip if BatchingWindow overlaps with RepatriationPeriod
@@ -1933,7 +2940,7 @@ pub mod pallet {
                         target,
                         batches_pending_attestation
                             .iter()
-                            .map(|batch| (batch.index, batch.message_hash()))
+                            .map(|batch| (batch.index, batch.message_hash_for(&encoding)))
                             .collect::<Vec<(u32, H256)>>(),
                     ));
                 }
@@ -1942,7 +2949,7 @@ pub mod pallet {
                     // Check if batch has pending messages to attest for
                     // Leave the batch empty if it has no messages to attest for
                     if !next_batch.is_empty() {
-                        let message_hash = next_batch.message_hash();
+                        let message_hash = next_batch.message_hash_for(&encoding);
                         next_batch.status = BatchStatus::PendingAttestation;
                         // Push the batch to the batches vector
                         Batches::<T>::append(target, &next_batch);
@@ -1952,18 +2959,10 @@ pub mod pallet {
 
                         Self::deposit_event(Event::NewAttestationBatch(target, next_batch));
 
-                        let execution_vendor = match T::Xdns::get_verification_vendor(&target) {
-                            Ok(gv) => match gv {
-                                GatewayVendor::Ethereum => ExecutionVendor::EVM,
-                                _ => ExecutionVendor::Substrate,
-                            },
-                            Err(_) => ExecutionVendor::EVM,
-                        };
-
                         Self::deposit_event(Event::NewAttestationMessageHash(
                             target,
                             message_hash,
-                            execution_vendor,
+                            encoding.execution_vendor(),
                         ));
                     }
                 } else {
@@ -2013,8 +3012,21 @@ pub mod pallet {
                                     T::Currency::unreserve(&nominator, amount);
                                     aggregated_weight += T::DbWeight::get().writes(2);
                                 }
-                                // Remove the attester from the list of attesters
-                                Attesters::<T>::remove(attester);
+                                // Remove the attester from the list of attesters and release its keys
+                                if let Some(attester_info) = Attesters::<T>::take(attester) {
+                                    let rotations = [
+                                        PendingKeyRotations::<T>::take(attester),
+                                        AnnouncedKeyRotations::<T>::take(attester),
+                                    ];
+                                    for keys in rotations
+                                        .into_iter()
+                                        .flatten()
+                                        .chain([AttesterKeys::from(&attester_info)])
+                                    {
+                                        Self::release_keys(attester, &keys);
+                                    }
+                                    aggregated_weight += T::DbWeight::get().reads_writes(2, 11);
+                                }
                                 aggregated_weight += T::DbWeight::get().writes(1);
                                 SortedNominatedAttesters::<T>::mutate(|attesters| {
                                     if let Some(index) =
@@ -2072,6 +3084,11 @@ pub mod pallet {
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(n: BlockNumberFor<T>) -> Weight {
             let mut aggregated_weight: Weight = Zero::zero();
+            // Apply the commission increases due at this shuffling round, even if the committee isn't shuffled
+            if (n % T::ShufflingFrequency::get()).is_zero() {
+                aggregated_weight = Self::process_commission_changes(n, aggregated_weight);
+            }
+            aggregated_weight = Self::sweep_stale_nominations_snapshot(aggregated_weight);
             // Check if a shuffling round has passed
             if (n % T::ShufflingFrequency::get()).is_zero() && Self::is_last_transition_attested() {
                 // Process pending unnominations
@@ -2081,23 +3098,30 @@ pub mod pallet {
                     SortedNominatedAttesters::<T>::get()
                         .iter()
                         .filter(|(account_id, _)| !Self::is_permanently_slashed(account_id))
-                        .take(32)
+                        .take(T::ActiveSetSize::get() as usize)
                         .cloned()
                         .map(|(account_id, _balance)| account_id)
                         .collect::<Vec<T::AccountId>>(),
                 );
                 aggregated_weight += T::DbWeight::get().reads_writes(1, 1);
 
+                aggregated_weight = Self::snapshot_active_set_nominations(aggregated_weight);
+
                 // Call shuffle_committee
                 Self::shuffle_committee();
                 aggregated_weight += T::DbWeight::get().reads_writes(2, 2);
 
+                aggregated_weight = Self::snapshot_current_committee_stakes(aggregated_weight);
+
                 Self::deposit_event(Event::ShufflingCompleted(
                     CurrentCommittee::<T>::get(),
                     PreviousCommittee::<T>::get(),
                     NextCommittee::<T>::get(),
                 ));
 
+                // Rotate keys before the next committee is announced to the targets
+                aggregated_weight = Self::process_key_rotations(aggregated_weight);
+
                 for (target, batch_index) in Self::request_next_committee_attestation() {
                     CommitteeTransitionOn::<T>::insert(target, batch_index);
                 }
@@ -2128,6 +3152,65 @@ pub mod pallet {
             }
             aggregated_weight
         }
+
+        fn on_runtime_upgrade() -> Weight {
+            // Migrate the storage entries.
+            StorageMigrations::<T>::try_mutate(|current_version| {
+                match *current_version {
+                    0 => {
+                        // Storage Migration: BatchMessage adds field "gmp_messages: Option<BatchGmpMessageId>"
+                        // Storage Migration Details: batches stored before GMP messages were attested carry none
+                        let mut translated: u64 = 0;
+                        NextBatch::<T>::translate(|_, batch: BatchMessageV1<BlockNumberFor<T>>| {
+                            translated += 1;
+                            Some(batch.into())
+                        });
+                        let mut translate_batches =
+                            |_: TargetId, batches: Vec<BatchMessageV1<BlockNumberFor<T>>>| {
+                                translated += 1;
+                                Some(
+                                    batches
+                                        .into_iter()
+                                        .map(BatchMessage::from)
+                                        .collect::<Vec<_>>(),
+                                )
+                            };
+                        BatchesToSign::<T>::translate(&mut translate_batches);
+                        Batches::<T>::translate(&mut translate_batches);
+
+                        let indexed = Self::index_attester_keys();
+
+                        // Set migrations_done to true
+                        *current_version = CURRENT_STORAGE_VERSION;
+
+                        // Return the weight consumed by the migration.
+                        Ok::<Weight, DispatchError>(
+                            T::DbWeight::get().reads_writes(
+                                translated + indexed + 1,
+                                translated + 3 * indexed + 1,
+                            ),
+                        )
+                    },
+                    1 => {
+                        // Storage Migration: AttesterKeyOwners indexes the keys of the registered attesters
+                        // Storage Migration Details: keys rotated out before are no longer held by anyone
+                        let indexed = Self::index_attester_keys();
+
+                        *current_version = CURRENT_STORAGE_VERSION;
+
+                        Ok::<Weight, DispatchError>(
+                            T::DbWeight::get().reads_writes(indexed + 1, 3 * indexed + 1),
+                        )
+                    },
+                    // Add more migration cases here, if needed in the future
+                    _ => {
+                        // No migration needed.
+                        Ok::<Weight, DispatchError>(Weight::zero())
+                    },
+                }
+            })
+            .unwrap_or(Weight::zero())
+        }
     }
 
     // The genesis config type.
@@ -2143,6 +3226,9 @@ pub mod pallet {
     #[pallet::genesis_build]
     impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
         fn build(&self) {
+            // Batches created at genesis are already in the current format
+            StorageMigrations::<T>::put(CURRENT_STORAGE_VERSION);
+
             // Extend the list of attestation targets
             for target in self.attestation_targets.iter() {
                 AttestationTargets::<T>::append(target);
@@ -2168,16 +3254,24 @@ pub mod attesters_test {
     };
     use std::ops::Index;
 
-    use crate::{TargetBatchDispatchEvent, REWARD_ADJUSTMENT};
+    use crate::{
+        AnnouncedKeyRotations, AttesterKeyOwners, AttesterKeys, BatchMessageV1, Batches,
+        CommitteeSizeOverride, NextCommittee, NominationsSnapshot, NominationsSnapshotSweepCursor,
+        PendingKeyRotations, QuorumConfigs, RotatedOutEcdsaKeys, StorageMigrations,
+        SubstrateTargetBatchDispatchEvent, TargetBatchDispatchEvent, MAX_GMP_MESSAGES_PER_BATCH,
+        MAX_NOMINATORS_PER_ATTESTER, MAX_ROTATED_OUT_KEYS, MAX_SNAPSHOT_ENTRIES_SWEPT_PER_BLOCK,
+        REWARD_ADJUSTMENT,
+    };
     use codec::Encode;
     use frame_support::{
         assert_err, assert_noop, assert_ok,
         traits::{Currency, Get, Hooks, Len},
+        weights::Weight,
         StorageValue,
     };
     use sp_application_crypto::{ecdsa, ed25519, sr25519, KeyTypeId, Pair, RuntimePublic};
     use sp_core::{H160, H256, H512};
-    use sp_runtime::traits::Keccak256;
+    use sp_runtime::{traits::Keccak256, DispatchError, Perbill, Percent};
     use sp_std::convert::TryInto;
     use t3rn_mini_mock_runtime::{
         AccountId, ActiveSet, AttestationTargets, Attesters, AttestersAgreements, AttestersError,
@@ -2185,20 +3279,21 @@ pub mod attesters_test {
         CommitteeTransitionOn, ConfigAttesters, ConfigRewards, CurrentCommittee,
         ExistentialDeposit, ExtBuilder, FullSideEffects, InfluxMessage, LatencyStatus, MiniRuntime,
         NextBatch, NextCommitteeOnTarget, Nominations, PaidFinalityFees, PendingUnnominations,
-        PermanentSlashes, PreviousCommittee, Rewards, RuntimeEvent as Event, RuntimeOrigin,
-        SFX2XTXLinksMap, SortedNominatedAttesters, System, XExecSignals, ETHEREUM_TARGET,
-        POLKADOT_TARGET,
+        PermanentSlashes, PreviousCommittee, Rewards, RewardsEvent, RuntimeEvent as Event,
+        RuntimeOrigin, SFX2XTXLinksMap, SortedNominatedAttesters, System, XExecSignals,
+        ETHEREUM_TARGET, POLKADOT_TARGET,
     };
     use t3rn_primitives::{
         attesters::{
-            ecdsa_pubkey_to_eth_address, AttesterInfo, AttestersReadApi, AttestersWriteApi,
-            BatchingFactor, CommitteeRecoverable, CommitteeTransitionIndices,
+            ecdsa_pubkey_to_eth_address, AttesterInfo, AttestersReadApi, AttestersStakingApi,
+            AttestersWriteApi, BatchMessageEncoding, BatchingFactor, CommitteeRecoverable,
+            CommitteeTransitionIndices, QuorumConfig, QuorumKind,
         },
         circuit::{
             AdaptiveTimeout, CircuitStatus, FullSideEffect, SecurityLvl, SideEffect, XExecSignal,
         },
         claimable::{BenefitSource, CircuitRole, ClaimableArtifacts},
-        TreasuryAccount, TreasuryAccountProvider,
+        ExecutionVendor, TreasuryAccount, TreasuryAccountProvider,
     };
     use tiny_keccak::{Hasher, Keccak};
 
@@ -2438,13 +3533,20 @@ pub mod attesters_test {
             let _ = Attesters::force_activate_target(RuntimeOrigin::root(), *target);
         }
         for (attester, attester_info) in AttestersStore::<MiniRuntime>::iter() {
-            // assume attester agrees to eth target: deriving eth address from ecdsa key
-            let derived_eth_address = ecdsa_pubkey_to_eth_address(&attester_info.key_ec);
-            assert_ok!(derived_eth_address);
+            let recoverable = match Attesters::batch_message_encoding(target) {
+                // Substrate targets verify attestations with the sr25519 key
+                BatchMessageEncoding::Scale => attester_info.key_sr.encode(),
+                // assume attester agrees to eth target: deriving eth address from ecdsa key
+                BatchMessageEncoding::EthAbi | BatchMessageEncoding::EthAbiV2 => {
+                    let derived_eth_address = ecdsa_pubkey_to_eth_address(&attester_info.key_ec);
+                    assert_ok!(derived_eth_address);
+                    derived_eth_address.unwrap().encode()
+                },
+            };
             assert_ok!(Attesters::agree_to_new_attestation_target(
                 RuntimeOrigin::signed(attester),
                 *target,
-                derived_eth_address.unwrap().encode(),
+                recoverable,
             ));
         }
         assert!(!Attesters::pending_attestation_targets().contains(target));
@@ -2754,6 +3856,7 @@ pub mod attesters_test {
                     available_to_commit_at: 0,
                     committed_sfx: Some(vec![sfx_gmp_a]),
                     reverted_sfx: None,
+                    gmp_messages: None,
                     next_committee: None,
                     banned_committee: None,
                     signatures: vec![],
@@ -3293,7 +4396,7 @@ pub mod attesters_test {
 
             let current_block_1 = add_target_and_transition_to_next_batch(target, 0);
 
-            let _committee_transition: CommitteeTransitionIndices = [
+            let _committee_transition: CommitteeTransitionIndices = vec![
                 1u32, 2u32, 3u32, 4u32, 5u32, 6u32, 7u32, 8u32, 9u32, 10u32, 11u32, 12u32, 13u32,
                 14u32, 15u32, 16u32, 17u32, 18u32, 19u32, 20u32, 21u32, 22u32, 23u32, 24u32, 25u32,
                 26u32, 27u32, 28u32, 29u32, 30u32, 31u32, 32u32,
@@ -3451,6 +4554,7 @@ pub mod attesters_test {
                     available_to_commit_at: 0,
                     committed_sfx: Some(vec![sfx_gmp]),
                     reverted_sfx: None,
+                    gmp_messages: None,
                     next_committee: expected_transition,
                     banned_committee: Some(vec![vec![
                         51, 37, 167, 132, 37, 241, 122, 126, 72, 126, 181, 102, 107, 43, 253, 147,
@@ -3556,7 +4660,7 @@ pub mod attesters_test {
 
             let _current_block_1 = add_target_and_transition_to_next_batch(target, 0);
 
-            let _committee_transition: CommitteeTransitionIndices = [
+            let _committee_transition: CommitteeTransitionIndices = vec![
                 1u32, 2u32, 3u32, 4u32, 5u32, 6u32, 7u32, 8u32, 9u32, 10u32, 11u32, 12u32, 13u32,
                 14u32, 15u32, 16u32, 17u32, 18u32, 19u32, 20u32, 21u32, 22u32, 23u32, 24u32, 25u32,
                 26u32, 27u32, 28u32, 29u32, 30u32, 31u32, 32u32,
@@ -3658,7 +4762,7 @@ pub mod attesters_test {
     }
 
     #[test]
-    fn test_adding_2_sfx_to_next_batch_and_transition_to_pending_attestation() {
+    fn test_adding_gmp_messages_to_next_batch_extends_the_signed_message() {
         let mut ext = ExtBuilder::default()
             .with_standard_sfx_abi()
             .with_eth_gateway_record()
@@ -3667,106 +4771,1060 @@ pub mod attesters_test {
             let target: TargetId = ETHEREUM_TARGET;
 
             AttestationTargets::<MiniRuntime>::put(vec![target]);
-            assert_eq!(NextBatch::<MiniRuntime>::get(target), None);
             NextBatch::<MiniRuntime>::insert(target, BatchMessage::default());
+            let message_without_gmp = NextBatch::<MiniRuntime>::get(target).unwrap().message();
 
-            let sfx_id_a = H256::repeat_byte(1);
-            assert_ok!(Attesters::request_sfx_attestation_commit(
-                target, sfx_id_a, None
+            let (message_id_a, message_id_b) = (H256::repeat_byte(1), H256::repeat_byte(2));
+            assert_ok!(Attesters::request_gmp_message_attestation(
+                target,
+                message_id_a
             ));
-
-            let mut sfx_gmp_a = H512::zero();
-            sfx_gmp_a[..32].copy_from_slice(&sfx_id_a[..]);
-
-            // Verify that the attestation is added to the next batch
-            let next_batch = NextBatch::<MiniRuntime>::get(target).unwrap();
-            assert_eq!(next_batch.committed_sfx, Some(vec![sfx_gmp_a]));
-
-            // Add another SFX to the next batch
-            let sfx_id_b = H256::repeat_byte(2);
-            assert_ok!(Attesters::request_sfx_attestation_commit(
-                target, sfx_id_b, None
+            assert_ok!(Attesters::request_gmp_message_attestation(
+                target,
+                message_id_b
             ));
-            let mut sfx_gmp_b = H512::zero();
-            sfx_gmp_b[..32].copy_from_slice(&sfx_id_b[..]);
-            let next_batch = NextBatch::<MiniRuntime>::get(target).unwrap();
-            assert_eq!(next_batch.committed_sfx, Some(vec![sfx_gmp_a, sfx_gmp_b]));
-
-            let mut empty_batch = BatchMessage {
-                available_to_commit_at: 0,
-                committed_sfx: None,
-                reverted_sfx: None,
-                next_committee: None,
-                banned_committee: None,
-                signatures: vec![],
-                status: BatchStatus::PendingMessage,
-                created: 0,
-                latency: LatencyStatus::OnTime,
-                index: 0,
-                halt: false,
-            };
-            let batching_window: BlockNumber =
-                <MiniRuntime as ConfigAttesters>::BatchingWindow::get();
+            assert_noop!(
+                Attesters::request_gmp_message_attestation(target, message_id_a),
+                AttestersError::<MiniRuntime>::GmpMessageAlreadyRequested,
+            );
 
-            // Transition to the next batch
-            System::set_block_number(batching_window * 2);
-            Attesters::on_initialize(batching_window * 2);
             let next_batch = NextBatch::<MiniRuntime>::get(target).unwrap();
-            assert_eq!(next_batch.committed_sfx, None);
-
-            // Verify that batches by status are correct
-            assert_eq!(
-                Attesters::get_batches(target, BatchStatus::PendingMessage),
-                vec![]
-            );
-            assert_eq!(
-                Attesters::get_batches(target, BatchStatus::PendingAttestation),
-                vec![BatchMessage {
-                    available_to_commit_at: 0,
-                    committed_sfx: Some(vec![sfx_gmp_a, sfx_gmp_b]),
-                    reverted_sfx: None,
-                    next_committee: None,
-                    banned_committee: None,
-                    signatures: vec![],
-                    status: BatchStatus::PendingAttestation,
-                    created: 0,
-                    latency: LatencyStatus::OnTime,
-                    index: 0,
-                    halt: false,
-                }]
-            );
-            assert_eq!(
-                Attesters::get_batches(target, BatchStatus::ReadyForSubmissionFullyApproved),
-                vec![]
-            );
             assert_eq!(
-                Attesters::get_batches(target, BatchStatus::ReadyForSubmissionByMajority),
-                vec![]
+                next_batch.gmp_messages,
+                Some(vec![message_id_a, message_id_b])
             );
-            empty_batch.created = batching_window * 2;
-            empty_batch.index += 1;
-            assert_eq!(NextBatch::<MiniRuntime>::get(target), Some(empty_batch));
+            assert!(!next_batch.is_empty());
+            assert_eq!(next_batch.read_batching_factor(), 2);
+
+            // Message ids are signed in order, right before the batch index
+            let mut expected_message = message_id_a.as_bytes().to_vec();
+            expected_message.extend_from_slice(message_id_b.as_bytes());
+            expected_message.extend_from_slice(&message_without_gmp);
+            assert_eq!(next_batch.message(), expected_message);
         });
     }
 
     #[test]
-    fn committee_transition_generates_next_3_batches_pending_attestations_when_late() {
+    fn test_gmp_messages_per_batch_are_bounded() {
         let mut ext = ExtBuilder::default()
             .with_standard_sfx_abi()
             .with_eth_gateway_record()
             .build();
-
         ext.execute_with(|| {
-            // On initialization, the current committee should be empty and the previous committee should be None
-            assert!(CurrentCommittee::<MiniRuntime>::get().is_empty());
-            assert_eq!(PreviousCommittee::<MiniRuntime>::get(), vec![]);
+            let target: TargetId = ETHEREUM_TARGET;
 
-            // Register multiple attesters
-            let attester_count = 100;
-            for counter in 1..=attester_count {
-                let _attester = AccountId::from([counter; 32]);
-                register_attester_with_single_private_key([counter; 32]);
-            }
+            AttestationTargets::<MiniRuntime>::put(vec![target]);
+            NextBatch::<MiniRuntime>::insert(target, BatchMessage::default());
+
+            for index in 0..MAX_GMP_MESSAGES_PER_BATCH as u64 {
+                assert_ok!(Attesters::request_gmp_message_attestation(
+                    target,
+                    H256::from_low_u64_be(index + 1)
+                ));
+            }
+            assert_noop!(
+                Attesters::request_gmp_message_attestation(target, H256::repeat_byte(255)),
+                AttestersError::<MiniRuntime>::GmpMessagesPerBatchExceeded,
+            );
+
+            assert_eq!(
+                NextBatch::<MiniRuntime>::get(target)
+                    .unwrap()
+                    .read_batching_factor(),
+                MAX_GMP_MESSAGES_PER_BATCH as u16
+            );
+        });
+    }
+
+    #[test]
+    fn test_storage_migration_adds_no_gmp_messages_to_stored_batches() {
+        let mut ext = ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_eth_gateway_record()
+            .build();
+        ext.execute_with(|| {
+            let target: TargetId = ETHEREUM_TARGET;
+            let batch_v1 = BatchMessageV1::<BlockNumber> {
+                available_to_commit_at: 0,
+                committed_sfx: None,
+                reverted_sfx: Some(vec![H256::repeat_byte(1)]),
+                next_committee: None,
+                banned_committee: None,
+                index: 3,
+                signatures: vec![],
+                created: 2,
+                status: BatchStatus::PendingAttestation,
+                latency: LatencyStatus::OnTime,
+                halt: false,
+            };
+            let batches_key = Batches::<MiniRuntime>::hashed_key_for(target);
+            frame_support::storage::unhashed::put(&batches_key, &vec![batch_v1.clone()]);
+            StorageMigrations::<MiniRuntime>::kill();
+
+            Attesters::on_runtime_upgrade();
+
+            let migrated = Batches::<MiniRuntime>::get(target).unwrap();
+            assert_eq!(migrated, vec![BatchMessage::from(batch_v1.clone())]);
+            assert_eq!(migrated[0].gmp_messages, None);
+            assert_eq!(migrated[0].reverted_sfx, batch_v1.reverted_sfx);
+            assert_eq!(StorageMigrations::<MiniRuntime>::get(), 2);
+        });
+    }
+
+    #[test]
+    fn test_storage_migration_indexes_keys_of_registered_attesters() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let attester = AccountId::from([1; 32]);
+            let attester_info = register_attester_with_single_private_key([1; 32]);
+            let _ = AttesterKeyOwners::<MiniRuntime>::clear(u32::MAX, None);
+            StorageMigrations::<MiniRuntime>::put(1);
+
+            Attesters::on_runtime_upgrade();
+
+            for key_id in AttesterKeys::from(&attester_info).key_ids() {
+                assert_eq!(
+                    AttesterKeyOwners::<MiniRuntime>::get(&key_id),
+                    Some(attester.clone())
+                );
+            }
+            assert_eq!(StorageMigrations::<MiniRuntime>::get(), 2);
+        });
+    }
+
+    #[test]
+    fn test_committee_size_set_by_governance_is_used_for_shuffling_and_quorum() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let target: TargetId = ETHEREUM_TARGET;
+            assert_noop!(
+                Attesters::set_committee_size(RuntimeOrigin::signed(AccountId::from([1; 32])), 2),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Attesters::set_committee_size(RuntimeOrigin::root(), 0),
+                AttestersError::<MiniRuntime>::CommitteeSizeZero
+            );
+            let active_set_size = <MiniRuntime as ConfigAttesters>::ActiveSetSize::get();
+            assert_noop!(
+                Attesters::set_committee_size(RuntimeOrigin::root(), active_set_size + 1),
+                AttestersError::<MiniRuntime>::CommitteeSizeTooLarge
+            );
+
+            assert_eq!(
+                Attesters::committee_size(),
+                <MiniRuntime as ConfigAttesters>::CommitteeSize::get() as usize
+            );
+            assert_ok!(Attesters::set_committee_size(RuntimeOrigin::root(), 2));
+            assert_eq!(CommitteeSizeOverride::<MiniRuntime>::get(), Some(2));
+            assert_eq!(Attesters::committee_size(), 2);
+            System::assert_last_event(Event::Attesters(AttestersEvent::CommitteeSizeSet(2)));
+
+            for counter in 1..5u8 {
+                register_attester_with_single_private_key([counter; 32]);
+            }
+            select_new_committee();
+            assert_eq!(CurrentCommittee::<MiniRuntime>::get().len(), 2);
+            assert_eq!(Attesters::get_current_committee_indices().len(), 2);
+
+            // Two thirds of the two members round up to both signatures
+            assert!(!Attesters::has_quorum(&target, &[]));
+            assert!(!Attesters::has_quorum(&target, &[(1, [0u8; 65])]));
+            assert!(Attesters::has_quorum(
+                &target,
+                &[(1, [0u8; 65]), (2, [0u8; 65])]
+            ));
+        });
+    }
+
+    #[test]
+    fn test_committee_size_change_leaves_quorum_of_current_committee_unchanged() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let target: TargetId = ETHEREUM_TARGET;
+            let signatures = |count: u32| {
+                (1..=count)
+                    .map(|index| (index, [0u8; 65]))
+                    .collect::<Vec<_>>()
+            };
+
+            // Without a committee no amount of signatures reaches the quorum
+            assert!(!Attesters::has_quorum(&target, &signatures(1)));
+
+            for counter in 1..7u8 {
+                register_attester_with_single_private_key([counter; 32]);
+            }
+            select_new_committee();
+            assert_eq!(CurrentCommittee::<MiniRuntime>::get().len(), 6);
+            assert!(!Attesters::has_quorum(&target, &signatures(3)));
+            assert!(Attesters::has_quorum(&target, &signatures(4)));
+
+            // Shrinking the committees applies to the committees selected from now on
+            assert_ok!(Attesters::set_committee_size(RuntimeOrigin::root(), 1));
+            assert!(!Attesters::has_quorum(&target, &signatures(1)));
+            assert!(Attesters::has_quorum(&target, &signatures(4)));
+
+            // The next committee was selected before the change and takes over first
+            select_new_committee();
+            assert_eq!(CurrentCommittee::<MiniRuntime>::get().len(), 6);
+            select_new_committee();
+            assert_eq!(CurrentCommittee::<MiniRuntime>::get().len(), 1);
+            assert!(Attesters::has_quorum(&target, &signatures(1)));
+        });
+    }
+
+    #[test]
+    fn test_stake_weighted_quorum_counts_self_bond_and_nominations_of_signers() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let target: TargetId = ETHEREUM_TARGET;
+            for counter in 1..5u8 {
+                register_attester_with_single_private_key([counter; 32]);
+            }
+
+            // Back the first attester with 30 on top of its self-bond of 10
+            let whale_attester = AccountId::from([1; 32]);
+            let nominator = AccountId::from([100; 32]);
+            let _ = Balances::deposit_creating(&nominator, 100u128);
+            assert_ok!(Attesters::nominate(
+                RuntimeOrigin::signed(nominator),
+                whale_attester.clone(),
+                30u128
+            ));
+            assert_eq!(Attesters::attester_stake(&whale_attester), 40u128);
+
+            select_new_committee();
+            assert_eq!(CurrentCommittee::<MiniRuntime>::get().len(), 4);
+
+            let signature_of = |secret_key: [u8; 32]| {
+                let attester_info =
+                    AttestersStore::<MiniRuntime>::get(AccountId::from(secret_key)).unwrap();
+                (attester_info.index, [0u8; 65])
+            };
+            let whale_signature = vec![signature_of([1; 32])];
+            let minnows_signatures = vec![
+                signature_of([2; 32]),
+                signature_of([3; 32]),
+                signature_of([4; 32]),
+            ];
+
+            // Head count quorum: two thirds of the configured committee size
+            assert!(!Attesters::has_quorum(&target, &whale_signature));
+
+            let quorum_config = QuorumConfig {
+                kind: QuorumKind::StakeWeighted,
+                threshold: Perbill::from_percent(50),
+            };
+            assert_noop!(
+                Attesters::set_quorum_config(
+                    RuntimeOrigin::root(),
+                    target,
+                    QuorumConfig {
+                        threshold: Perbill::from_percent(0),
+                        ..quorum_config
+                    }
+                ),
+                AttestersError::<MiniRuntime>::QuorumThresholdZero
+            );
+            assert_ok!(Attesters::set_quorum_config(
+                RuntimeOrigin::root(),
+                target,
+                quorum_config
+            ));
+            assert_eq!(QuorumConfigs::<MiniRuntime>::get(target), quorum_config);
+            assert_eq!(
+                QuorumConfigs::<MiniRuntime>::get(POLKADOT_TARGET),
+                QuorumConfig::default()
+            );
+
+            // 40 out of 70 staked passes the 50% threshold, while 30 out of 70 doesn't
+            assert!(Attesters::has_quorum(&target, &whale_signature));
+            assert!(!Attesters::has_quorum(&target, &minnows_signatures));
+
+            // Nominations made after the committee took over don't count until the next rotation
+            let late_nominator = AccountId::from([101; 32]);
+            let _ = Balances::deposit_creating(&late_nominator, 200u128);
+            assert_ok!(Attesters::nominate(
+                RuntimeOrigin::signed(late_nominator),
+                AccountId::from([2; 32]),
+                100u128
+            ));
+            assert!(Attesters::has_quorum(&target, &whale_signature));
+            assert!(!Attesters::has_quorum(&target, &minnows_signatures));
+            assert_eq!(
+                Attesters::current_committee_stakes()
+                    .iter()
+                    .map(|(_, stake)| *stake)
+                    .sum::<Balance>(),
+                70u128
+            );
+        });
+    }
+
+    #[test]
+    fn test_committee_transition_message_encodes_committee_lengths() {
+        let eth_address = |byte: u8| vec![byte; 20];
+        let batch_with =
+            |next_committee: Option<CommitteeRecoverable>,
+             banned_committee: Option<CommitteeRecoverable>| BatchMessage {
+                next_committee,
+                banned_committee,
+                index: 1,
+                ..BatchMessage::<BlockNumber>::default()
+            };
+        let length_word = |length: u8| {
+            let mut word = [0u8; 32];
+            word[31] = length;
+            word.to_vec()
+        };
+        let address_word = |byte: u8| {
+            let mut word = [0u8; 32];
+            word[12..].copy_from_slice(&[byte; 20]);
+            word.to_vec()
+        };
+
+        let v2 = BatchMessageEncoding::EthAbiV2;
+        let next_only = batch_with(Some(vec![eth_address(1), eth_address(2)]), None);
+        let expected_message = [
+            length_word(2),
+            address_word(1),
+            address_word(2),
+            length_word(0),
+            1u32.to_be_bytes().to_vec(),
+        ]
+        .concat();
+        assert_eq!(next_only.message_for(&v2), expected_message);
+        assert_eq!(
+            next_only.message_hash_for(&v2),
+            H256(sp_core::hashing::keccak_256(&expected_message))
+        );
+        // The legacy EVM layout packs the committees as they are
+        assert_eq!(
+            next_only.message(),
+            [
+                address_word(1),
+                address_word(2),
+                1u32.to_be_bytes().to_vec()
+            ]
+            .concat()
+        );
+
+        // Moving the same members between the next and banned committees changes the message
+        let banned_only = batch_with(None, Some(vec![eth_address(1), eth_address(2)]));
+        let split = batch_with(Some(vec![eth_address(1)]), Some(vec![eth_address(2)]));
+        assert_ne!(
+            next_only.message_hash_for(&v2),
+            banned_only.message_hash_for(&v2)
+        );
+        assert_ne!(next_only.message_hash_for(&v2), split.message_hash_for(&v2));
+        assert_ne!(
+            banned_only.message_hash_for(&v2),
+            split.message_hash_for(&v2)
+        );
+
+        // Batches without committee transitions don't carry the lengths
+        assert_eq!(
+            batch_with(Some(vec![]), None).message_for(&v2),
+            1u32.to_be_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn test_scale_batch_message_is_blake2_hash_of_scale_encoded_batch() {
+        let batch = BatchMessage {
+            committed_sfx: Some(vec![H512::repeat_byte(1)]),
+            reverted_sfx: Some(vec![H256::repeat_byte(2)]),
+            gmp_messages: Some(vec![H256::repeat_byte(3)]),
+            next_committee: Some(vec![vec![4u8; 32]]),
+            index: 7,
+            ..BatchMessage::<BlockNumber>::default()
+        };
+
+        let expected_message = (
+            vec![vec![4u8; 32]],
+            CommitteeRecoverable::new(),
+            vec![H512::repeat_byte(1)],
+            vec![H256::repeat_byte(2)],
+            vec![H256::repeat_byte(3)],
+            7u32,
+        )
+            .encode();
+        assert_eq!(batch.scale_message(), expected_message);
+        assert_eq!(
+            batch.message_for(&BatchMessageEncoding::Scale),
+            expected_message
+        );
+        assert_eq!(
+            batch.message_hash_for(&BatchMessageEncoding::Scale),
+            H256(sp_core::hashing::blake2_256(&expected_message))
+        );
+        // Same hash is computed by the reference ink! receiver for the same batch
+        assert_eq!(
+            batch.message_hash_for(&BatchMessageEncoding::Scale),
+            H256(hex_literal::hex!(
+                "b3587088925fb5aa8f1de78b4ae87e0292141bb79cc7fe018d19ff784f006fad"
+            ))
+        );
+        assert_eq!(
+            batch.message_hash_for(&BatchMessageEncoding::EthAbi),
+            batch.message_hash()
+        );
+    }
+
+    #[test]
+    fn test_batch_message_encoding_set_by_governance_overrides_the_one_of_target() {
+        let mut ext = ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_eth_gateway_record()
+            .build();
+
+        ext.execute_with(|| {
+            assert_noop!(
+                Attesters::set_batch_message_encoding(
+                    RuntimeOrigin::signed(AccountId::from([1; 32])),
+                    ETHEREUM_TARGET,
+                    BatchMessageEncoding::EthAbiV2
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Attesters::set_batch_message_encoding(
+                RuntimeOrigin::root(),
+                ETHEREUM_TARGET,
+                BatchMessageEncoding::EthAbiV2
+            ));
+            System::assert_last_event(Event::Attesters(AttestersEvent::BatchMessageEncodingSet(
+                ETHEREUM_TARGET,
+                BatchMessageEncoding::EthAbiV2,
+            )));
+            assert_eq!(
+                Attesters::batch_message_encoding(&ETHEREUM_TARGET),
+                BatchMessageEncoding::EthAbiV2
+            );
+
+            for counter in 1..5u8 {
+                register_attester_with_single_private_key([counter; 32]);
+            }
+            let _ = add_target_and_transition_to_next_batch(ETHEREUM_TARGET, 0);
+            Attesters::request_next_committee_attestation();
+            let _ = add_target_and_transition_to_next_batch(ETHEREUM_TARGET, 1);
+
+            // Committee transitions are signed with the committee lengths from now on
+            let batch = Attesters::get_latest_batch_to_sign(ETHEREUM_TARGET).unwrap();
+            assert!(batch.next_committee.is_some());
+            assert_ne!(
+                batch.message_for(&BatchMessageEncoding::EthAbiV2),
+                batch.message()
+            );
+            assert_eq!(
+                Attesters::get_latest_batch_to_sign_message(ETHEREUM_TARGET),
+                Some(batch.message_for(&BatchMessageEncoding::EthAbiV2))
+            );
+            assert_eq!(
+                Attesters::get_latest_batch_to_sign_hash(ETHEREUM_TARGET),
+                Some(batch.message_hash_for(&BatchMessageEncoding::EthAbiV2))
+            );
+        });
+    }
+
+    #[test]
+    fn test_scale_batch_message_encoding_is_opted_into_by_substrate_targets() {
+        let mut ext = ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_polkadot_gateway_record()
+            .with_eth_gateway_record()
+            .build();
+
+        ext.execute_with(|| {
+            // Substrate targets keep the EVM encoding their agreements were made for until they opt in
+            assert_eq!(
+                Attesters::native_batch_message_encoding(&POLKADOT_TARGET),
+                Some(BatchMessageEncoding::Scale)
+            );
+            assert_eq!(
+                Attesters::batch_message_encoding(&POLKADOT_TARGET),
+                BatchMessageEncoding::EthAbi
+            );
+            assert_eq!(
+                Attesters::batch_message_encoding(&[7u8; 4]),
+                BatchMessageEncoding::EthAbi
+            );
+
+            // Only targets with the Scale codec on the Substrate execution layer opt into the Scale encoding
+            assert_noop!(
+                Attesters::set_batch_message_encoding(
+                    RuntimeOrigin::root(),
+                    ETHEREUM_TARGET,
+                    BatchMessageEncoding::Scale
+                ),
+                AttestersError::<MiniRuntime>::BatchMessageEncodingNotNativeToTarget
+            );
+            assert_noop!(
+                Attesters::set_batch_message_encoding(
+                    RuntimeOrigin::root(),
+                    [7u8; 4],
+                    BatchMessageEncoding::Scale
+                ),
+                AttestersError::<MiniRuntime>::BatchMessageEncodingNotNativeToTarget
+            );
+            assert_ok!(Attesters::set_batch_message_encoding(
+                RuntimeOrigin::root(),
+                POLKADOT_TARGET,
+                BatchMessageEncoding::Scale
+            ));
+            assert_eq!(
+                Attesters::batch_message_encoding(&POLKADOT_TARGET),
+                BatchMessageEncoding::Scale
+            );
+
+            register_attester_with_single_private_key([1u8; 32]);
+            let _ = add_target_and_transition_to_next_batch(POLKADOT_TARGET, 0);
+            assert_ok!(Attesters::request_sfx_attestation_commit(
+                POLKADOT_TARGET,
+                H256::repeat_byte(1),
+                None
+            ));
+            let _ = add_target_and_transition_to_next_batch(POLKADOT_TARGET, 1);
+
+            let batch = Attesters::get_latest_batch_to_sign(POLKADOT_TARGET).unwrap();
+            let message_hash = batch.message_hash_for(&BatchMessageEncoding::Scale);
+            assert_eq!(
+                Attesters::get_latest_batch_to_sign_hash(POLKADOT_TARGET),
+                Some(message_hash)
+            );
+            assert!(System::events().iter().any(|record| record.event
+                == Event::Attesters(AttestersEvent::NewAttestationMessageHash(
+                    POLKADOT_TARGET,
+                    message_hash,
+                    ExecutionVendor::Substrate,
+                ))));
+
+            // ECDSA signatures don't verify attestations to Substrate targets
+            let ecdsa_signature = ecdsa::Pair::from_seed(&[1u8; 32])
+                .sign_prehashed(&message_hash.0)
+                .encode();
+            assert_noop!(
+                Attesters::submit_attestation(
+                    RuntimeOrigin::signed(AccountId::from([1u8; 32])),
+                    message_hash,
+                    ecdsa_signature,
+                    POLKADOT_TARGET,
+                ),
+                AttestersError::<MiniRuntime>::InvalidSignature
+            );
+        });
+    }
+
+    #[test]
+    fn test_attestations_to_substrate_target_verify_with_sr25519_key_only() {
+        let mut ext = ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_polkadot_gateway_record()
+            .build();
+
+        ext.execute_with(|| {
+            assert_ok!(Attesters::set_batch_message_encoding(
+                RuntimeOrigin::root(),
+                POLKADOT_TARGET,
+                BatchMessageEncoding::Scale
+            ));
+            let attester = AccountId::from([1u8; 32]);
+            let attester_info = register_attester_with_single_private_key([1u8; 32]);
+            let (message_hash, signature) = sign_and_submit_sfx_to_latest_attestation(
+                attester.clone(),
+                vec![[1u8; 32]],
+                SR25519_ATTESTER_KEY_TYPE_ID,
+                POLKADOT_TARGET,
+                [1u8; 32],
+            );
+            let batch = Attesters::get_batch_by_message_hash(POLKADOT_TARGET, message_hash)
+                .expect("Batch by Scale message hash should exist");
+            // 64b signature is stored right-padded to 65 bytes
+            let mut stored_signature = [0u8; 65];
+            stored_signature[..64].copy_from_slice(&signature);
+            assert_eq!(
+                batch.signatures,
+                vec![(attester_info.index, stored_signature)]
+            );
+
+            // The Substrate receivers can't verify ed25519 signatures, nor keys of other attesters
+            for recoverable in [attester_info.key_ed.encode(), vec![0u8; 32]] {
+                assert_noop!(
+                    Attesters::agree_to_new_attestation_target(
+                        RuntimeOrigin::signed(attester.clone()),
+                        POLKADOT_TARGET,
+                        recoverable,
+                    ),
+                    AttestersError::<MiniRuntime>::AgreedRecoverableNotAttesterKey
+                );
+            }
+
+            // Signatures are exactly 64 bytes long, trailing bytes aren't dropped
+            assert_ok!(Attesters::request_sfx_attestation_commit(
+                POLKADOT_TARGET,
+                H256::repeat_byte(2),
+                None
+            ));
+            let _ = add_target_and_transition_to_next_batch(POLKADOT_TARGET, 2);
+            let message_hash = Attesters::get_latest_batch_to_sign_hash(POLKADOT_TARGET).unwrap();
+            let mut signature = sr25519::Pair::from_seed(&[1u8; 32])
+                .sign(message_hash.as_ref())
+                .encode();
+            signature.push(0u8);
+            assert_noop!(
+                Attesters::submit_attestation(
+                    RuntimeOrigin::signed(attester.clone()),
+                    message_hash,
+                    signature,
+                    POLKADOT_TARGET,
+                ),
+                AttestersError::<MiniRuntime>::InvalidSignature
+            );
+
+            // Agreements made with ed25519 keys before can't be verified
+            AttestersAgreements::<MiniRuntime>::insert(
+                &attester,
+                POLKADOT_TARGET,
+                attester_info.key_ed.encode(),
+            );
+            let signature = ed25519::Pair::from_seed(&[1u8; 32])
+                .sign(message_hash.as_ref())
+                .encode();
+            assert_noop!(
+                Attesters::submit_attestation(
+                    RuntimeOrigin::signed(attester),
+                    message_hash,
+                    signature,
+                    POLKADOT_TARGET,
+                ),
+                AttestersError::<MiniRuntime>::AgreedRecoverableNotAttesterKey
+            );
+        });
+    }
+
+    fn attester_keys_from_seed(secret_key: [u8; 32]) -> AttesterKeys {
+        AttesterKeys {
+            key_ed: ed25519::Pair::from_seed(&secret_key)
+                .public()
+                .to_raw_vec()
+                .try_into()
+                .unwrap(),
+            key_ec: ecdsa::Pair::from_seed(&secret_key)
+                .public()
+                .to_raw_vec()
+                .try_into()
+                .unwrap(),
+            key_sr: sr25519::Pair::from_seed(&secret_key)
+                .public()
+                .to_raw_vec()
+                .try_into()
+                .unwrap(),
+        }
+    }
+
+    fn key_possession_proofs(
+        attester: &AccountId,
+        secret_key: [u8; 32],
+    ) -> ([u8; 65], [u8; 64], [u8; 64]) {
+        let payload = Attesters::key_rotation_proof_payload(attester);
+        (
+            ecdsa::Pair::from_seed(&secret_key).sign(&payload).0,
+            ed25519::Pair::from_seed(&secret_key).sign(&payload).0,
+            sr25519::Pair::from_seed(&secret_key).sign(&payload).0,
+        )
+    }
+
+    fn rotate_keys_to_seed(attester: AccountId, secret_key: [u8; 32]) {
+        let keys = attester_keys_from_seed(secret_key);
+        let (ecdsa_proof, ed25519_proof, sr25519_proof) =
+            key_possession_proofs(&attester, secret_key);
+        assert_ok!(Attesters::rotate_keys(
+            RuntimeOrigin::signed(attester),
+            keys.key_ec,
+            keys.key_ed,
+            keys.key_sr,
+            ecdsa_proof,
+            ed25519_proof,
+            sr25519_proof,
+        ));
+    }
+
+    #[test]
+    fn test_rotated_keys_are_announced_with_next_committee_and_apply_once_it_takes_over() {
+        let mut ext = ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_eth_gateway_record()
+            .build();
+        ext.execute_with(|| {
+            let target: TargetId = ETHEREUM_TARGET;
+            let attester = AccountId::from([1; 32]);
+            let other_attester = AccountId::from([2; 32]);
+            let previous_info = register_attester_with_single_private_key([1; 32]);
+            register_attester_with_single_private_key([2; 32]);
+            add_target_and_transition_to_next_batch(target, 0);
+
+            let (ecdsa_proof, ed25519_proof, sr25519_proof) =
+                key_possession_proofs(&AccountId::from([9; 32]), [1; 32]);
+            assert_noop!(
+                Attesters::rotate_keys(
+                    RuntimeOrigin::signed(AccountId::from([9; 32])),
+                    previous_info.key_ec,
+                    previous_info.key_ed,
+                    previous_info.key_sr,
+                    ecdsa_proof,
+                    ed25519_proof,
+                    sr25519_proof,
+                ),
+                AttestersError::<MiniRuntime>::NotRegistered
+            );
+
+            rotate_keys_to_seed(attester.clone(), [11; 32]);
+            let new_keys = attester_keys_from_seed([11; 32]);
+            assert_eq!(
+                PendingKeyRotations::<MiniRuntime>::get(&attester),
+                Some(new_keys.clone())
+            );
+            // Keys stay unchanged until the next committee transition
+            assert_eq!(
+                AttestersStore::<MiniRuntime>::get(&attester),
+                Some(previous_info.clone())
+            );
+
+            // Re-selected members of the current committee keep signing with their keys,
+            // while the next committee is announced with the new ones
+            CurrentCommittee::<MiniRuntime>::put(vec![attester.clone()]);
+            NextCommittee::<MiniRuntime>::put(vec![attester.clone(), other_attester.clone()]);
+            Attesters::process_key_rotations(Weight::zero());
+            assert_eq!(PendingKeyRotations::<MiniRuntime>::get(&attester), None);
+            assert_eq!(
+                AnnouncedKeyRotations::<MiniRuntime>::get(&attester),
+                Some(new_keys.clone())
+            );
+            assert_eq!(
+                AttestersStore::<MiniRuntime>::get(&attester),
+                Some(previous_info.clone())
+            );
+
+            let previous_address = ecdsa_pubkey_to_eth_address(&previous_info.key_ec)
+                .unwrap()
+                .to_vec();
+            let new_address = ecdsa_pubkey_to_eth_address(&new_keys.key_ec)
+                .unwrap()
+                .to_vec();
+            assert_eq!(
+                AttestersAgreements::<MiniRuntime>::get(&attester, target),
+                Some(previous_address.clone())
+            );
+
+            Attesters::request_next_committee_attestation();
+            let next_committee = NextBatch::<MiniRuntime>::get(target)
+                .unwrap()
+                .next_committee
+                .unwrap();
+            assert!(next_committee.contains(&new_address));
+            assert!(!next_committee.contains(&previous_address));
+
+            // The rotation applies at the transition the announced committee takes over at
+            CurrentCommittee::<MiniRuntime>::put(vec![attester.clone(), other_attester.clone()]);
+            NextCommittee::<MiniRuntime>::put(vec![attester.clone(), other_attester]);
+            Attesters::process_key_rotations(Weight::zero());
+            System::assert_last_event(Event::Attesters(AttestersEvent::AttesterKeysRotated(
+                attester.clone(),
+            )));
+
+            assert_eq!(AnnouncedKeyRotations::<MiniRuntime>::get(&attester), None);
+            assert_eq!(
+                RotatedOutEcdsaKeys::<MiniRuntime>::get(&attester),
+                vec![previous_info.key_ec]
+            );
+            let rotated_info = AttestersStore::<MiniRuntime>::get(&attester).unwrap();
+            assert_eq!(rotated_info.key_ec, new_keys.key_ec);
+            assert_eq!(rotated_info.key_ed, new_keys.key_ed);
+            assert_eq!(rotated_info.key_sr, new_keys.key_sr);
+            assert_eq!(rotated_info.index, previous_info.index);
+            assert_eq!(
+                AttestersAgreements::<MiniRuntime>::get(&attester, target),
+                Some(new_address)
+            );
+        });
+    }
+
+    #[test]
+    fn test_rotated_keys_must_be_unused_and_proven_by_their_signatures() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let attester = AccountId::from([1; 32]);
+            let other_attester = AccountId::from([2; 32]);
+            register_attester_with_single_private_key([1; 32]);
+            register_attester_with_single_private_key([2; 32]);
+
+            let rotate_keys = |attester: &AccountId, secret_key: [u8; 32], prover: &AccountId| {
+                let keys = attester_keys_from_seed(secret_key);
+                let (ecdsa_proof, ed25519_proof, sr25519_proof) =
+                    key_possession_proofs(prover, secret_key);
+                Attesters::rotate_keys(
+                    RuntimeOrigin::signed(attester.clone()),
+                    keys.key_ec,
+                    keys.key_ed,
+                    keys.key_sr,
+                    ecdsa_proof,
+                    ed25519_proof,
+                    sr25519_proof,
+                )
+            };
+
+            // Keys of another attester
+            assert_noop!(
+                rotate_keys(&attester, [2; 32], &attester),
+                AttestersError::<MiniRuntime>::AttesterKeyAlreadyInUse
+            );
+            // Proofs signed for another attester
+            assert_noop!(
+                rotate_keys(&attester, [11; 32], &other_attester),
+                AttestersError::<MiniRuntime>::InvalidKeyPossessionProof
+            );
+            // Proofs not signed by the new keys
+            let keys = attester_keys_from_seed([11; 32]);
+            let (ecdsa_proof, ed25519_proof, sr25519_proof) =
+                key_possession_proofs(&attester, [12; 32]);
+            assert_noop!(
+                Attesters::rotate_keys(
+                    RuntimeOrigin::signed(attester.clone()),
+                    keys.key_ec,
+                    keys.key_ed,
+                    keys.key_sr,
+                    ecdsa_proof,
+                    ed25519_proof,
+                    sr25519_proof,
+                ),
+                AttestersError::<MiniRuntime>::InvalidKeyPossessionProof
+            );
+
+            // Keys of a scheduled rotation are held until the attester schedules another one
+            assert_ok!(rotate_keys(&attester, [11; 32], &attester));
+            assert_noop!(
+                rotate_keys(&other_attester, [11; 32], &other_attester),
+                AttestersError::<MiniRuntime>::AttesterKeyAlreadyInUse
+            );
+            let ecdsa_key_id = (ECDSA_ATTESTER_KEY_TYPE_ID, keys.key_ec.to_vec());
+            assert_eq!(
+                AttesterKeyOwners::<MiniRuntime>::get(&ecdsa_key_id),
+                Some(attester.clone())
+            );
+            assert_ok!(rotate_keys(&attester, [12; 32], &attester));
+            assert_eq!(AttesterKeyOwners::<MiniRuntime>::get(&ecdsa_key_id), None);
+            assert_ok!(rotate_keys(&other_attester, [11; 32], &other_attester));
+
+            // Registrations can't take keys held by other attesters either
+            let taken_keys = attester_keys_from_seed([12; 32]);
+            let _ = Balances::deposit_creating(&AccountId::from([12; 32]), 100u128);
+            assert_noop!(
+                Attesters::register_attester(
+                    RuntimeOrigin::signed(AccountId::from([12; 32])),
+                    10u128,
+                    taken_keys.key_ec,
+                    taken_keys.key_ed,
+                    taken_keys.key_sr,
+                    None,
+                ),
+                AttestersError::<MiniRuntime>::AttesterKeyAlreadyInUse
+            );
+        });
+    }
+
+    #[test]
+    fn test_rotated_out_keys_are_kept_up_to_the_limit() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let attester = AccountId::from([1; 32]);
+            register_attester_with_single_private_key([1; 32]);
+            // Outside of the committee each rotation applies at the next transition
+            CurrentCommittee::<MiniRuntime>::kill();
+
+            let seeds = [[11u8; 32], [12; 32], [13; 32], [14; 32], [15; 32]];
+            for seed in seeds {
+                rotate_keys_to_seed(attester.clone(), seed);
+                Attesters::process_key_rotations(Weight::zero());
+            }
+
+            // The keys of the last rotations, without the registered key dropped first
+            assert_eq!(
+                RotatedOutEcdsaKeys::<MiniRuntime>::get(&attester),
+                seeds[..MAX_ROTATED_OUT_KEYS]
+                    .iter()
+                    .map(|seed| attester_keys_from_seed(*seed).key_ec)
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                AttestersStore::<MiniRuntime>::get(&attester)
+                    .unwrap()
+                    .key_ec,
+                attester_keys_from_seed([15; 32]).key_ec
+            );
+        });
+    }
+
+    #[test]
+    fn test_attestations_signed_with_rotated_out_key_are_rejected_without_slashing() {
+        let mut ext = ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_eth_gateway_record()
+            .build();
+        ext.execute_with(|| {
+            let target: TargetId = ETHEREUM_TARGET;
+            let attester = AccountId::from([1; 32]);
+            register_attester_with_single_private_key([1; 32]);
+            add_target_and_transition_to_next_batch(target, 0);
+            assert_ok!(Attesters::request_sfx_attestation_commit(
+                target,
+                H256::repeat_byte(1),
+                None
+            ));
+            add_target_and_transition_to_next_batch(target, 1);
+            let message_hash = Attesters::get_latest_batch_to_sign_hash(target).unwrap();
+
+            rotate_keys_to_seed(attester.clone(), [11; 32]);
+            let current_committee = CurrentCommittee::<MiniRuntime>::take();
+            Attesters::process_key_rotations(Weight::zero());
+            CurrentCommittee::<MiniRuntime>::put(current_committee);
+
+            let signature_with_old_key = ecdsa::Pair::from_seed(&[1; 32])
+                .sign_prehashed(&message_hash.0)
+                .encode();
+            assert_noop!(
+                Attesters::submit_attestation(
+                    RuntimeOrigin::signed(attester.clone()),
+                    message_hash,
+                    signature_with_old_key,
+                    target,
+                ),
+                AttestersError::<MiniRuntime>::SignatureFromRotatedOutKey
+            );
+            assert!(!PermanentSlashes::<MiniRuntime>::get().contains(&attester));
+
+            let signature_with_new_key = ecdsa::Pair::from_seed(&[11; 32])
+                .sign_prehashed(&message_hash.0)
+                .encode();
+            assert_ok!(Attesters::submit_attestation(
+                RuntimeOrigin::signed(attester),
+                message_hash,
+                signature_with_new_key,
+                target,
+            ));
+        });
+    }
+
+    #[test]
+    fn test_adding_2_sfx_to_next_batch_and_transition_to_pending_attestation() {
+        let mut ext = ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_eth_gateway_record()
+            .build();
+        ext.execute_with(|| {
+            let target: TargetId = ETHEREUM_TARGET;
+
+            AttestationTargets::<MiniRuntime>::put(vec![target]);
+            assert_eq!(NextBatch::<MiniRuntime>::get(target), None);
+            NextBatch::<MiniRuntime>::insert(target, BatchMessage::default());
+
+            let sfx_id_a = H256::repeat_byte(1);
+            assert_ok!(Attesters::request_sfx_attestation_commit(
+                target, sfx_id_a, None
+            ));
+
+            let mut sfx_gmp_a = H512::zero();
+            sfx_gmp_a[..32].copy_from_slice(&sfx_id_a[..]);
+
+            // Verify that the attestation is added to the next batch
+            let next_batch = NextBatch::<MiniRuntime>::get(target).unwrap();
+            assert_eq!(next_batch.committed_sfx, Some(vec![sfx_gmp_a]));
+
+            // Add another SFX to the next batch
+            let sfx_id_b = H256::repeat_byte(2);
+            assert_ok!(Attesters::request_sfx_attestation_commit(
+                target, sfx_id_b, None
+            ));
+            let mut sfx_gmp_b = H512::zero();
+            sfx_gmp_b[..32].copy_from_slice(&sfx_id_b[..]);
+            let next_batch = NextBatch::<MiniRuntime>::get(target).unwrap();
+            assert_eq!(next_batch.committed_sfx, Some(vec![sfx_gmp_a, sfx_gmp_b]));
+
+            let mut empty_batch = BatchMessage {
+                available_to_commit_at: 0,
+                committed_sfx: None,
+                reverted_sfx: None,
+                gmp_messages: None,
+                next_committee: None,
+                banned_committee: None,
+                signatures: vec![],
+                status: BatchStatus::PendingMessage,
+                created: 0,
+                latency: LatencyStatus::OnTime,
+                index: 0,
+                halt: false,
+            };
+            let batching_window: BlockNumber =
+                <MiniRuntime as ConfigAttesters>::BatchingWindow::get();
+
+            // Transition to the next batch
+            System::set_block_number(batching_window * 2);
+            Attesters::on_initialize(batching_window * 2);
+            let next_batch = NextBatch::<MiniRuntime>::get(target).unwrap();
+            assert_eq!(next_batch.committed_sfx, None);
+
+            // Verify that batches by status are correct
+            assert_eq!(
+                Attesters::get_batches(target, BatchStatus::PendingMessage),
+                vec![]
+            );
+            assert_eq!(
+                Attesters::get_batches(target, BatchStatus::PendingAttestation),
+                vec![BatchMessage {
+                    available_to_commit_at: 0,
+                    committed_sfx: Some(vec![sfx_gmp_a, sfx_gmp_b]),
+                    reverted_sfx: None,
+                    gmp_messages: None,
+                    next_committee: None,
+                    banned_committee: None,
+                    signatures: vec![],
+                    status: BatchStatus::PendingAttestation,
+                    created: 0,
+                    latency: LatencyStatus::OnTime,
+                    index: 0,
+                    halt: false,
+                }]
+            );
+            assert_eq!(
+                Attesters::get_batches(target, BatchStatus::ReadyForSubmissionFullyApproved),
+                vec![]
+            );
+            assert_eq!(
+                Attesters::get_batches(target, BatchStatus::ReadyForSubmissionByMajority),
+                vec![]
+            );
+            empty_batch.created = batching_window * 2;
+            empty_batch.index += 1;
+            assert_eq!(NextBatch::<MiniRuntime>::get(target), Some(empty_batch));
+        });
+    }
+
+    #[test]
+    fn committee_transition_generates_next_3_batches_pending_attestations_when_late() {
+        let mut ext = ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_eth_gateway_record()
+            .build();
+
+        ext.execute_with(|| {
+            // On initialization, the current committee should be empty and the previous committee should be None
+            assert!(CurrentCommittee::<MiniRuntime>::get().is_empty());
+            assert_eq!(PreviousCommittee::<MiniRuntime>::get(), vec![]);
+
+            // Register multiple attesters
+            let attester_count = 100;
+            for counter in 1..=attester_count {
+                let _attester = AccountId::from([counter; 32]);
+                register_attester_with_single_private_key([counter; 32]);
+            }
             // Trigger the committee first setup
             select_new_committee();
             add_target_and_transition_to_next_batch(ETHEREUM_TARGET, 0);
@@ -3946,7 +6004,8 @@ pub mod attesters_test {
             let (_message_hash, _expected_message_bytes) =
                 calculate_hash_for_sfx_message(message.encode(), 0);
 
-            for counter in 1..22u8 {
+            // 21 out of 31 members reach the quorum, yet not the full approval
+            for counter in 1..32u8 {
                 // Register an attester
                 let _attester = AccountId::from([counter; 32]);
                 register_attester_with_single_private_key([counter; 32]);
@@ -3992,7 +6051,7 @@ pub mod attesters_test {
     }
 
     #[test]
-    fn register_and_submit_32x_attestations_in_ecdsa_with_batching_plus_confirmation_to_polka_target(
+    fn register_and_submit_32x_attestations_in_sr25519_with_batching_plus_confirmation_to_polka_target(
     ) {
         let target: TargetId = POLKADOT_TARGET;
         let _mock_escrow_account: AccountId = AccountId::new([2u8; 32]);
@@ -4003,6 +6062,11 @@ pub mod attesters_test {
             .build();
 
         ext.execute_with(|| {
+            assert_ok!(Attesters::set_batch_message_encoding(
+                RuntimeOrigin::root(),
+                target,
+                BatchMessageEncoding::Scale
+            ));
             let message: [u8; 32] = *b"message_that_needs_attestation32";
             let (_message_hash, _message_bytes) =
                 calculate_hash_for_sfx_message(message.encode(), 0);
@@ -4018,11 +6082,11 @@ pub mod attesters_test {
             for counter in 1..33u8 {
                 // Register an attester
                 let attester = AccountId::from([counter; 32]);
-                // Submit an attestation signed with the Ed25519 key
+                // Submit an attestation signed with the Sr25519 key
                 let (_message_hash, _signature) = sign_and_submit_sfx_to_latest_attestation(
                     attester,
                     vec![message],
-                    ECDSA_ATTESTER_KEY_TYPE_ID,
+                    SR25519_ATTESTER_KEY_TYPE_ID,
                     target,
                     [counter; 32],
                 );
@@ -4035,8 +6099,9 @@ pub mod attesters_test {
             let first_batch = attested_batches[0].clone();
 
             // Check if the attestations have been added to the batch
-            let first_batch_hash = first_batch.message_hash();
-            let first_batch_message = first_batch.message();
+            let first_batch_hash = first_batch.message_hash_for(&BatchMessageEncoding::Scale);
+            let first_batch_message = first_batch.message_for(&BatchMessageEncoding::Scale);
+            assert_eq!(first_batch_message, first_batch.scale_message());
 
             assert_eq!(first_batch.signatures.len(), 32);
             assert_eq!(
@@ -4045,10 +6110,10 @@ pub mod attesters_test {
             );
 
             let submitter = AccountId::from([1; 32]);
-            // Recover submitter's agreed target eth address
-            let submitter_eth_address =
+            // Recover submitter's agreed target sr25519 key
+            let submitter_sr25519_key =
                 AttestersAgreements::<MiniRuntime>::get(&submitter, &target)
-                    .expect("attester eth address should exist");
+                    .expect("attester sr25519 key should exist");
 
             // Hash of the current committee
             let current_committee_hash = CurrentCommittee::<MiniRuntime>::get().encode();
@@ -4057,9 +6122,9 @@ pub mod attesters_test {
             let mut res: [u8; 32] = [0; 32];
             hasher.finalize(&mut res);
 
-            let mock_valid_batch_confirmation = TargetBatchDispatchEvent {
+            let mock_valid_batch_confirmation = SubstrateTargetBatchDispatchEvent {
                 hash: first_batch_hash,
-                executor_on_target: H160::from_slice(&submitter_eth_address),
+                executor_on_target: submitter_sr25519_key.try_into().unwrap(),
                 attesting_committee: H256::from_slice(&res),
             };
 
@@ -4289,6 +6354,8 @@ pub mod attesters_test {
                 ));
             }
 
+            // The distribution period starts with the nominations in place
+            Attesters::start_nominations_snapshot_period();
             Attesters::on_initialize(400);
 
             // Trigger inflation rewards distribution
@@ -4362,6 +6429,275 @@ pub mod attesters_test {
         });
     }
 
+    #[test]
+    fn attester_rewards_are_split_by_nominations_over_the_distribution_period() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let attester = AccountId::from([1u8; 32]);
+            // The self-bond of 10 is snapshotted at the round of the registration
+            register_attester_with_single_private_key([1u8; 32]);
+
+            let nominator = AccountId::from([100u8; 32]);
+            let late_nominator = AccountId::from([101u8; 32]);
+            for account in [&nominator, &late_nominator] {
+                let _ = Balances::deposit_creating(account, 1000 + ExistentialDeposit::get());
+            }
+            assert_ok!(Attesters::nominate(
+                RuntimeOrigin::signed(nominator.clone()),
+                attester.clone(),
+                90
+            ));
+            for round in 2..10u32 {
+                Attesters::on_initialize(round * 400);
+            }
+            assert_eq!(
+                Attesters::nominations_snapshot(&attester, &nominator),
+                Some((0, 8 * 90))
+            );
+
+            // Nominating just before the last round of the period earns the share of a single round
+            assert_ok!(Attesters::nominate(
+                RuntimeOrigin::signed(late_nominator.clone()),
+                attester.clone(),
+                90
+            ));
+            Attesters::on_initialize(10 * 400);
+            for (account, summed) in [
+                (&attester, 10 * 10),
+                (&nominator, 9 * 90),
+                (&late_nominator, 90),
+            ] {
+                assert_eq!(
+                    Attesters::nominations_snapshot(&attester, account),
+                    Some((0, summed))
+                );
+            }
+
+            assert_eq!(Rewards::distribute_attester_rewards(1000), 1000);
+
+            // 10% commission first, then 900 split pro rata between 100, 810 and 90 summed over the rounds
+            System::assert_has_event(Event::Rewards(RewardsEvent::AttesterRewardSplit(
+                attester.clone(),
+                100,
+                900,
+            )));
+            assert_eq!(
+                Rewards::get_pending_claims(&attester),
+                Some(vec![
+                    ClaimableArtifacts {
+                        beneficiary: attester.clone(),
+                        role: CircuitRole::Attester,
+                        total_round_claim: 100,
+                        benefit_source: BenefitSource::Inflation,
+                        non_native_asset_id: None,
+                    },
+                    ClaimableArtifacts {
+                        beneficiary: attester.clone(),
+                        role: CircuitRole::Staker,
+                        total_round_claim: 90,
+                        benefit_source: BenefitSource::Inflation,
+                        non_native_asset_id: None,
+                    },
+                ])
+            );
+            assert_eq!(
+                Rewards::nominator_accrued_rewards(&nominator),
+                vec![(attester.clone(), 729)]
+            );
+            assert_eq!(
+                Rewards::nominator_accrued_rewards(&late_nominator),
+                vec![(attester.clone(), 81)]
+            );
+
+            // The next distribution period sums the nominations anew
+            Attesters::start_nominations_snapshot_period();
+            assert_eq!(Attesters::read_nominations_snapshot(&attester), vec![]);
+            Attesters::on_initialize(11 * 400);
+            assert_eq!(
+                Attesters::nominations_snapshot(&attester, &late_nominator),
+                Some((1, 90))
+            );
+            assert_eq!(
+                Attesters::nominations_snapshot(&attester, &nominator),
+                Some((1, 90))
+            );
+        });
+    }
+
+    #[test]
+    fn commission_decreases_apply_immediately_and_capped_increases_after_notice_period() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let attester = AccountId::from([1u8; 32]);
+            register_attester_with_single_private_key([1u8; 32]);
+            let commission_of = |attester: &AccountId| {
+                AttestersStore::<MiniRuntime>::get(attester)
+                    .unwrap()
+                    .commission
+            };
+            assert_eq!(commission_of(&attester), Percent::from_percent(10));
+
+            assert_ok!(Attesters::set_commission(
+                RuntimeOrigin::signed(attester.clone()),
+                Percent::from_percent(8)
+            ));
+            assert_eq!(commission_of(&attester), Percent::from_percent(8));
+            System::assert_last_event(Event::Attesters(AttestersEvent::CommissionChanged(
+                attester.clone(),
+                Percent::from_percent(8),
+            )));
+
+            assert_noop!(
+                Attesters::set_commission(
+                    RuntimeOrigin::signed(attester.clone()),
+                    Percent::from_percent(14)
+                ),
+                AttestersError::<MiniRuntime>::CommissionIncreaseTooLarge
+            );
+            assert_noop!(
+                Attesters::set_commission(
+                    RuntimeOrigin::signed(AccountId::from([2u8; 32])),
+                    Percent::from_percent(5)
+                ),
+                AttestersError::<MiniRuntime>::NotRegistered
+            );
+
+            assert_ok!(Attesters::set_commission(
+                RuntimeOrigin::signed(attester.clone()),
+                Percent::from_percent(13)
+            ));
+            let notice_period: BlockNumber =
+                <MiniRuntime as ConfigAttesters>::CommissionChangeNoticePeriod::get();
+            // First shuffling round after the notice period
+            let effective_at = 1200;
+            assert!(System::block_number() + notice_period > 800);
+            assert_eq!(
+                Attesters::pending_commission_changes(&attester),
+                Some((Percent::from_percent(13), effective_at))
+            );
+            assert_eq!(
+                Attesters::commission_changes_due(effective_at).into_inner(),
+                vec![attester.clone()]
+            );
+            assert_eq!(commission_of(&attester), Percent::from_percent(8));
+
+            // Shuffling rounds within the notice period keep the current commission
+            Attesters::on_initialize(800);
+            assert!(effective_at > 800);
+            assert_eq!(commission_of(&attester), Percent::from_percent(8));
+
+            Attesters::on_initialize(1200);
+            assert_eq!(commission_of(&attester), Percent::from_percent(13));
+            assert_eq!(Attesters::pending_commission_changes(&attester), None);
+            assert!(Attesters::commission_changes_due(effective_at).is_empty());
+            System::assert_has_event(Event::Attesters(AttestersEvent::CommissionChanged(
+                attester,
+                Percent::from_percent(13),
+            )));
+        });
+    }
+
+    #[test]
+    fn commission_increase_lowered_before_its_round_is_not_applied() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let attester = AccountId::from([1u8; 32]);
+            register_attester_with_single_private_key([1u8; 32]);
+            let commission_of = |attester: &AccountId| {
+                AttestersStore::<MiniRuntime>::get(attester)
+                    .unwrap()
+                    .commission
+            };
+
+            assert_ok!(Attesters::set_commission(
+                RuntimeOrigin::signed(attester.clone()),
+                Percent::from_percent(13)
+            ));
+            assert_ok!(Attesters::set_commission(
+                RuntimeOrigin::signed(attester.clone()),
+                Percent::from_percent(7)
+            ));
+            assert_eq!(Attesters::pending_commission_changes(&attester), None);
+
+            Attesters::on_initialize(1200);
+            assert_eq!(commission_of(&attester), Percent::from_percent(7));
+            assert!(Attesters::commission_changes_due(1200).is_empty());
+        });
+    }
+
+    #[test]
+    fn nominations_are_bounded_per_attester() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let attester = AccountId::from([1u8; 32]);
+            // The self-bond counts as the first nomination
+            register_attester_with_single_private_key([1u8; 32]);
+
+            let nominator_of = |index: usize| {
+                let mut account = [200u8; 32];
+                account[..8].copy_from_slice(&(index as u64).to_be_bytes());
+                AccountId::from(account)
+            };
+            for index in 1..MAX_NOMINATORS_PER_ATTESTER {
+                let nominator = nominator_of(index);
+                let _ = Balances::deposit_creating(&nominator, 100 + ExistentialDeposit::get());
+                assert_ok!(Attesters::nominate(
+                    RuntimeOrigin::signed(nominator),
+                    attester.clone(),
+                    10
+                ));
+            }
+
+            let extra_nominator = nominator_of(MAX_NOMINATORS_PER_ATTESTER);
+            let _ = Balances::deposit_creating(&extra_nominator, 100 + ExistentialDeposit::get());
+            assert_noop!(
+                Attesters::nominate(RuntimeOrigin::signed(extra_nominator), attester.clone(), 10),
+                AttestersError::<MiniRuntime>::TooManyNominators
+            );
+
+            // Existing nominators can still top up
+            assert_ok!(Attesters::nominate(
+                RuntimeOrigin::signed(nominator_of(1)),
+                attester,
+                10
+            ));
+        });
+    }
+
+    #[test]
+    fn nominations_snapshot_of_past_periods_is_swept_over_blocks() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let attester = AccountId::from([1u8; 32]);
+            let entries = MAX_SNAPSHOT_ENTRIES_SWEPT_PER_BLOCK + 10;
+            for index in 0..entries {
+                let mut nominator = [200u8; 32];
+                nominator[..4].copy_from_slice(&index.to_be_bytes());
+                NominationsSnapshot::<MiniRuntime>::insert(
+                    &attester,
+                    AccountId::from(nominator),
+                    (0, 10),
+                );
+            }
+
+            Attesters::start_nominations_snapshot_period();
+            // Entries of the current period are kept
+            let current_nominator = AccountId::from([100u8; 32]);
+            NominationsSnapshot::<MiniRuntime>::insert(&attester, &current_nominator, (1, 10));
+
+            Attesters::sweep_stale_nominations_snapshot(Weight::zero());
+            assert!(NominationsSnapshotSweepCursor::<MiniRuntime>::get().is_some());
+            assert!(NominationsSnapshot::<MiniRuntime>::iter_prefix(&attester).count() > 1);
+
+            Attesters::sweep_stale_nominations_snapshot(Weight::zero());
+            assert_eq!(NominationsSnapshotSweepCursor::<MiniRuntime>::get(), None);
+            assert_eq!(
+                NominationsSnapshot::<MiniRuntime>::iter_prefix(&attester).collect::<Vec<_>>(),
+                vec![(current_nominator, (1, 10))]
+            );
+        });
+    }
+
     #[test]
     fn attester_unnomination() {
         let mut ext = ExtBuilder::default().build();
@@ -4473,6 +6809,7 @@ pub mod attesters_test {
                 hex!("ff17743a6b48933b94f38f423b15b2fc9ebcd34aab19bd81c2a69d3d052f467f").into(),
                 hex!("21e5cd2c2f3e32ac4a52543a386821b079711432c2fefd4be3836ed36d129b11").into(),
             ]),
+            gmp_messages: None,
             next_committee: Some(vec![
                 hex!("2b7A372d58541c3053793f022Cf28ef971F94EFA").into(),
                 hex!("60eA580734420A9C23E51C7FdF455b5e0237E07C").into(),
@@ -4537,6 +6874,7 @@ pub mod attesters_test {
             available_to_commit_at: 0,
             committed_sfx: Some(vec![]),
             reverted_sfx: Some(vec![]),
+            gmp_messages: None,
             next_committee: Some(vec![]),
             banned_committee: Some(vec![]),
             index: 1,
@@ -0,0 +1,81 @@
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_attesters.
+pub trait WeightInfo {
+    fn set_committee_size() -> Weight;
+    fn set_quorum_config() -> Weight;
+    fn set_batch_message_encoding() -> Weight;
+    fn rotate_keys() -> Weight;
+    fn set_commission() -> Weight;
+}
+
+/// Weights for pallet_attesters using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Storage access follows the benchmarks of the governance calls, each writing a single configuration entry,
+    // the Scale encoding checked against the XDNS record of the target first.
+    // Execution times are estimates until the benchmarks are re-run on reference hardware.
+    fn set_committee_size() -> Weight {
+        Weight::from_parts(11_000_000_u64, 0u64).saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn set_quorum_config() -> Weight {
+        Weight::from_parts(12_000_000_u64, 0u64).saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn set_batch_message_encoding() -> Weight {
+        Weight::from_parts(14_000_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Verifies the three proofs of possession and checks the new keys against the ones held by other attesters.
+    fn rotate_keys() -> Weight {
+        Weight::from_parts(164_000_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(9_u64))
+            .saturating_add(T::DbWeight::get().writes(7_u64))
+    }
+
+    fn set_commission() -> Weight {
+        Weight::from_parts(18_000_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn set_committee_size() -> Weight {
+        Weight::from_parts(11_000_000_u64, 0u64).saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn set_quorum_config() -> Weight {
+        Weight::from_parts(12_000_000_u64, 0u64).saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn set_batch_message_encoding() -> Weight {
+        Weight::from_parts(14_000_000_u64, 0u64)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn rotate_keys() -> Weight {
+        Weight::from_parts(164_000_000_u64, 0u64)
+            .saturating_add(RocksDbWeight::get().reads(9_u64))
+            .saturating_add(RocksDbWeight::get().writes(7_u64))
+    }
+
+    fn set_commission() -> Weight {
+        Weight::from_parts(18_000_000_u64, 0u64)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+}
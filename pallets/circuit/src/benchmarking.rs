@@ -1 +1,66 @@
+#![cfg(feature = "runtime-benchmarks")]
+use crate::Pallet as Circuit;
 
+use super::*;
+
+use frame_benchmarking::benchmarks;
+use frame_system::Pallet as System;
+use sp_runtime::traits::Hash as HashT;
+
+// Open Xtx are bucketed far ahead of the processed height, while the expired ones are bucketed
+//  at the processed height. The expired ones are already settled and only drained from their buckets,
+//  so that the benchmarks measure the queue overhead, which should not depend on the number of open Xtx.
+fn bucket_open_and_expired_xtx<T: Config>(queue: XtxQueue, open: u32, expired: u32) {
+    let now = System::<T>::block_number();
+    for index in 0..open {
+        let xtx_id = <T as frame_system::Config>::Hashing::hash_of(&(b"open", index));
+        Queues::<T>::schedule(queue, xtx_id, now + 1_000u32.into());
+    }
+    for index in 0..expired {
+        let xtx_id = <T as frame_system::Config>::Hashing::hash_of(&(b"expired", index));
+        Queues::<T>::schedule(queue, xtx_id, now);
+    }
+}
+
+benchmarks! {
+    process_xtx_tick_queue {
+        let o in 0 .. 1_000;
+        let e in 0 .. 100;
+        let n: BlockNumberFor<T> = 10u32.into();
+        System::<T>::set_block_number(n);
+        bucket_open_and_expired_xtx::<T>(XtxQueue::BidsTimeouts, o, e);
+        bucket_open_and_expired_xtx::<T>(XtxQueue::Finalized, o, e);
+    }: {
+        Circuit::<T>::process_xtx_tick_queue(n, One::one(), Weight::MAX);
+    }
+    verify {
+        assert_eq!(XtxQueueBuckets::<T>::iter_prefix((XtxQueue::BidsTimeouts, n)).count(), 0);
+        assert_eq!(XtxQueueBuckets::<T>::iter_prefix((XtxQueue::Finalized, n)).count(), 0);
+    }
+
+    process_emergency_revert_xtx_queue {
+        let o in 0 .. 1_000;
+        let e in 0 .. 100;
+        let n: BlockNumberFor<T> = EMERGENCY_REVERT_INTERVAL.into();
+        System::<T>::set_block_number(n);
+        bucket_open_and_expired_xtx::<T>(XtxQueue::EmergencyTimeouts, o, e);
+    }: {
+        Circuit::<T>::process_emergency_revert_xtx_queue(n, EMERGENCY_REVERT_INTERVAL.into(), Weight::MAX);
+    }
+    verify {
+        assert_eq!(XtxQueueBuckets::<T>::iter_prefix((XtxQueue::EmergencyTimeouts, n)).count(), 0);
+    }
+
+    process_adaptive_xtx_timeout_queue {
+        let o in 0 .. 1_000;
+        let e in 0 .. 100;
+        let n: BlockNumberFor<T> = 10u32.into();
+        System::<T>::set_block_number(n);
+        bucket_open_and_expired_xtx::<T>(XtxQueue::AdaptiveTimeouts, o, e);
+    }: {
+        Circuit::<T>::process_adaptive_xtx_timeout_queue(n + One::one(), &GatewayVendor::Rococo, Weight::MAX);
+    }
+    verify {
+        assert_eq!(XtxQueueBuckets::<T>::iter_prefix((XtxQueue::AdaptiveTimeouts, n)).count(), 0);
+    }
+}
@@ -32,6 +32,47 @@ impl<T: Config> Bids<T> {
             >,
         >,
         Error<T>,
+    > {
+        let (bid, current_accepted_bid) =
+            Self::prepare_bid(step_fsx, bid_amount, bidder, requester, sfx_id, xtx_id)?;
+
+        SquareUp::<T>::try_bid(sfx_id, requester, bidder, &bid, current_accepted_bid).map_err(
+            |e| {
+                log::error!("Error while trying to SquareUp::try_bid: {:?}", e);
+                Error::<T>::BiddingRejectedFailedToDepositBidderBond
+            },
+        )?;
+
+        // Replace the best bid for the FSX
+        if let Some(fsx) = step_fsx
+            .iter_mut()
+            .find(|fsx| fsx.calc_sfx_id::<SystemHashing<T>, T>(xtx_id) == sfx_id)
+        {
+            fsx.best_bid = Some(bid);
+        }
+
+        Ok(step_fsx.clone())
+    }
+
+    /// Validate the bid against the SFX and its current best bid, setting the insurance and the bond the bidder deposits.
+    /// Returns the bid along with the current best bid it replaces.
+    pub fn prepare_bid(
+        step_fsx: &[FullSideEffect<
+            T::AccountId,
+            frame_system::pallet_prelude::BlockNumberFor<T>,
+            BalanceOf<T>,
+        >],
+        bid_amount: BalanceOf<T>,
+        bidder: &T::AccountId,
+        requester: &T::AccountId,
+        sfx_id: SideEffectId<T>,
+        xtx_id: XExecSignalId<T>,
+    ) -> Result<
+        (
+            SFXBid<T::AccountId, BalanceOf<T>, u32>,
+            Option<SFXBid<T::AccountId, BalanceOf<T>, u32>>,
+        ),
+        Error<T>,
     > {
         // Check for the previous bids for SFX.
         let fsx = step_fsx
@@ -108,21 +149,6 @@ impl<T: Config> Bids<T> {
             };
         }
 
-        SquareUp::<T>::try_bid(sfx_id, requester, bidder, &bid, current_accepted_bid).map_err(
-            |e| {
-                log::error!("Error while trying to SquareUp::try_bid: {:?}", e);
-                Error::<T>::BiddingRejectedFailedToDepositBidderBond
-            },
-        )?;
-
-        // Replace the best bid for the FSX
-        if let Some(fsx) = step_fsx
-            .iter_mut()
-            .find(|fsx| fsx.calc_sfx_id::<SystemHashing<T>, T>(xtx_id) == sfx_id)
-        {
-            fsx.best_bid = Some(bid);
-        }
-
-        Ok(step_fsx.clone())
+        Ok((bid, current_accepted_bid))
     }
 }
@@ -0,0 +1,243 @@
+use crate::*;
+use sp_std::marker::PhantomData;
+use t3rn_abi::recode::recode_bytes_with_descriptor;
+use t3rn_primitives::{
+    attesters::{get_remote_gmp_acknowledged_abi_descriptor, RemoteGmpAcknowledgedLog},
+    gmp::{
+        GmpAcknowledgement, GmpCallback, GmpMessage, OnGmpAcknowledged,
+        MAX_GMP_INCLUSION_PROOF_LEN, MAX_GMP_PAYLOAD_LEN, MAX_GMP_RECIPIENT_LEN,
+        MAX_GMP_RETURN_DATA_LEN,
+    },
+};
+
+#[cfg(test)]
+pub mod test;
+
+pub struct Gmp<T: Config> {
+    _phantom: PhantomData<T>,
+}
+
+// Messages are sent over channels of (source, destination) targets, one per sender. Each channel assigns consecutive
+//  nonces to its messages and accepts their acknowledgements strictly in the same order, so that a message is acknowledged
+//  exactly once and a destination can't reorder the callbacks. Keeping a channel per sender means a message that never
+//  gets acknowledged only holds back the later messages of its own sender. Message ids commit to the channel, the sender
+//  and the nonce, which gives the destination contract the same ordering and replay protection on delivery.
+// The sender's deposit, proportional to the bytes kept in the outbox, is held until the message is acknowledged.
+// A message left unacknowledged for `GmpMessageTimeout` blocks can be expired by anyone: it's closed in its channel
+//  as failed, which frees the later messages of its sender and returns the deposit.
+impl<T: Config> Gmp<T> {
+    /// Queue the message on the sender's channel to its destination, hold the sender's deposit for it
+    ///     and request its id to be attested in the next batch.
+    pub fn send(
+        sender: T::AccountId,
+        destination: TargetId,
+        recipient: Vec<u8>,
+        payload: Vec<u8>,
+        callback: Option<GmpCallback<T::AccountId>>,
+    ) -> Result<H256, DispatchError> {
+        ensure!(
+            payload.len() <= MAX_GMP_PAYLOAD_LEN as usize,
+            Error::<T>::GmpPayloadTooLarge
+        );
+        ensure!(
+            recipient.len() <= MAX_GMP_RECIPIENT_LEN as usize,
+            Error::<T>::GmpRecipientTooLong
+        );
+        ensure!(
+            <T as Config>::Xdns::is_target_active(destination, &SecurityLvl::Optimistic),
+            Error::<T>::GatewayNotActive
+        );
+        match &callback {
+            Some(GmpCallback::Contract(contract)) =>
+                ensure!(contract == &sender, Error::<T>::GmpCallbackNotOwnedBySender),
+            Some(GmpCallback::Pallet(_)) =>
+                return Err(Error::<T>::GmpPalletCallbackNotSupported.into()),
+            None => {},
+        }
+
+        let source = T::SelfGatewayId::get();
+        let nonce = GmpOutboundNonces::<T>::mutate((source, destination), &sender, |next_nonce| {
+            let nonce = *next_nonce;
+            *next_nonce = next_nonce.saturating_add(1);
+            nonce
+        });
+        let deposit = T::GmpDepositPerByte::get()
+            .saturating_mul(((recipient.len() + payload.len()) as u32).into());
+        let message = GmpMessage {
+            source,
+            destination,
+            nonce,
+            sender,
+            recipient,
+            payload,
+            callback,
+            submitted_at: <frame_system::Pallet<T>>::block_number(),
+        };
+        let message_id = message.message_id();
+
+        <T as Config>::Attesters::request_gmp_message_attestation(destination, message_id)?;
+        <T as Config>::AccountManager::deposit(
+            Self::deposit_id(&message_id)?,
+            RequestCharge {
+                payee: message.sender.clone(),
+                offered_reward: deposit,
+                charge_fee: Zero::zero(),
+                source: BenefitSource::TrafficFees,
+                role: CircuitRole::Requester,
+                recipient: None,
+                maybe_asset_id: None,
+            },
+        )?;
+
+        Pallet::<T>::deposit_event(Event::GmpMessageSent(
+            message_id,
+            destination,
+            nonce,
+            message.sender.clone(),
+            message.payload_hash(),
+        ));
+        GmpOutbox::<T>::insert(message_id, message);
+
+        Ok(message_id)
+    }
+
+    /// Verify the acknowledgement emitted by the escrow contract of the message's destination
+    ///     and deliver it together with the return data to the message's callback.
+    ///     Returns the acknowledgement and the weight its callback took.
+    pub fn acknowledge(
+        message_id: H256,
+        return_data: Vec<u8>,
+        inclusion_proof: Vec<u8>,
+    ) -> Result<(GmpAcknowledgement, Weight), DispatchError> {
+        ensure!(
+            return_data.len() <= MAX_GMP_RETURN_DATA_LEN as usize,
+            Error::<T>::GmpReturnDataTooLarge
+        );
+        ensure!(
+            inclusion_proof.len() <= MAX_GMP_INCLUSION_PROOF_LEN as usize,
+            Error::<T>::GmpInclusionProofTooLarge
+        );
+        let message = GmpOutbox::<T>::get(message_id).ok_or(Error::<T>::GmpMessageNotFound)?;
+
+        let escrow_source: ExecutionSource = ExecutionSource::decode(
+            &mut &<T as Config>::Xdns::get_escrow_account(&message.destination)?[..],
+        )
+        .map_err(|_| Error::<T>::GmpDestinationEscrowNotRegistered)?;
+
+        let verified_event_bytes = <T as Config>::Portal::verify_event_inclusion(
+            message.destination,
+            SpeedMode::Finalized,
+            Some(escrow_source),
+            inclusion_proof,
+        )?
+        .message;
+
+        let recoded_event = recode_bytes_with_descriptor(
+            verified_event_bytes,
+            get_remote_gmp_acknowledged_abi_descriptor(),
+            <T as Config>::Xdns::get_target_codec(&message.destination)?,
+            Codec::Scale,
+        )?;
+        let acknowledged = RemoteGmpAcknowledgedLog::decode(&mut &recoded_event[..])
+            .map_err(|_| Error::<T>::GmpAcknowledgementMismatch)?;
+
+        ensure!(
+            acknowledged.message_id == message_id
+                && acknowledged.return_data_hash == H256(sp_io::hashing::keccak_256(&return_data)),
+            Error::<T>::GmpAcknowledgementMismatch
+        );
+
+        Self::apply_acknowledgement(message_id, acknowledged.success, return_data)
+    }
+
+    /// Close the verified message in its channel, return the sender's deposit and deliver the acknowledgement
+    ///     to the callback. Returns the acknowledgement and the weight its callback took.
+    pub fn apply_acknowledgement(
+        message_id: H256,
+        success: bool,
+        return_data: Vec<u8>,
+    ) -> Result<(GmpAcknowledgement, Weight), DispatchError> {
+        let message = GmpOutbox::<T>::get(message_id).ok_or(Error::<T>::GmpMessageNotFound)?;
+        let acknowledged = Self::close(message_id, &message, success, return_data)?;
+
+        Pallet::<T>::deposit_event(Event::GmpMessageAcknowledged(
+            message_id,
+            message.destination,
+            message.nonce,
+            success,
+        ));
+
+        Ok(acknowledged)
+    }
+
+    /// Close the message left unacknowledged past its timeout as failed, without return data.
+    ///     Returns the acknowledgement delivered to the callback and the weight the callback took.
+    pub fn expire(message_id: H256) -> Result<(GmpAcknowledgement, Weight), DispatchError> {
+        let message = GmpOutbox::<T>::get(message_id).ok_or(Error::<T>::GmpMessageNotFound)?;
+        ensure!(
+            <frame_system::Pallet<T>>::block_number()
+                >= message
+                    .submitted_at
+                    .saturating_add(T::GmpMessageTimeout::get()),
+            Error::<T>::GmpMessageNotExpired
+        );
+        let expired = Self::close(message_id, &message, false, vec![])?;
+
+        Pallet::<T>::deposit_event(Event::GmpMessageExpired(
+            message_id,
+            message.destination,
+            message.nonce,
+        ));
+
+        Ok(expired)
+    }
+
+    /// Close the next message of its channel, return the sender's deposit and deliver the outcome to the callback.
+    fn close(
+        message_id: H256,
+        message: &GmpMessage<T::AccountId, BlockNumberFor<T>>,
+        success: bool,
+        return_data: Vec<u8>,
+    ) -> Result<(GmpAcknowledgement, Weight), DispatchError> {
+        let channel = (message.source, message.destination);
+
+        ensure!(
+            GmpAcknowledgedNonces::<T>::get(channel, &message.sender) == message.nonce,
+            Error::<T>::GmpAcknowledgementOutOfOrder
+        );
+        let deposit_id = Self::deposit_id(&message_id)?;
+
+        GmpAcknowledgedNonces::<T>::insert(
+            channel,
+            &message.sender,
+            message.nonce.saturating_add(1),
+        );
+        GmpOutbox::<T>::remove(message_id);
+        if !<T as Config>::AccountManager::finalize_infallible(deposit_id, Outcome::Revert) {
+            log::error!(
+                "Gmp::close: expect the deposit of message {:?} to be returned",
+                message_id
+            );
+        }
+
+        let acknowledgement = GmpAcknowledgement {
+            message_id,
+            nonce: message.nonce,
+            success,
+            return_data,
+        };
+        let callback_weight = match message.callback.as_ref() {
+            Some(callback) =>
+                T::GmpCallbacks::on_gmp_acknowledged(callback, &message.sender, &acknowledgement),
+            None => Weight::zero(),
+        };
+
+        Ok((acknowledgement, callback_weight))
+    }
+
+    /// Id of the account manager charge holding the sender's deposit for the message.
+    fn deposit_id(message_id: &H256) -> Result<T::Hash, DispatchError> {
+        T::Hash::decode(&mut message_id.as_bytes())
+            .map_err(|_| Error::<T>::GmpDepositIdDecodingFailed.into())
+    }
+}
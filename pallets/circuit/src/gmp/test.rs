@@ -0,0 +1,357 @@
+#[cfg(test)]
+pub mod test {
+    use circuit_mock_runtime::{
+        AccountId, Balance, Balances, Circuit, ExtBuilder, Runtime, RuntimeEvent, RuntimeOrigin,
+        System, ThreeVm, ALICE, BOB,
+    };
+    use circuit_runtime_pallets::{
+        pallet_attesters::NextBatch,
+        pallet_circuit::{gmp::Gmp, Error, Event},
+    };
+    use frame_support::{
+        assert_noop, assert_ok,
+        traits::{Currency, Get},
+    };
+    use sp_core::H256;
+    use t3rn_primitives::gmp::{
+        GmpCallback, MAX_GMP_INCLUSION_PROOF_LEN, MAX_GMP_PAYLOAD_LEN, MAX_GMP_RECIPIENT_LEN,
+        MAX_GMP_RETURN_DATA_LEN,
+    };
+
+    const SELF_TARGET: [u8; 4] = [3, 3, 3, 3];
+    const DESTINATION: [u8; 4] = [1, 1, 1, 1];
+    const INITIAL_BALANCE: Balance = 1_000_000_000_000_000;
+
+    fn send(sender: AccountId, payload: &[u8]) -> H256 {
+        Gmp::<Runtime>::send(sender, DESTINATION, vec![7u8; 20], payload.to_vec(), None)
+            .expect("GMP message should be sent to an active attestation target")
+    }
+
+    fn deposit_of(recipient_len: usize, payload_len: usize) -> Balance {
+        <Runtime as circuit_runtime_pallets::pallet_circuit::Config>::GmpDepositPerByte::get()
+            * (recipient_len + payload_len) as Balance
+    }
+
+    fn ext() -> sp_io::TestExternalities {
+        let mut ext = ExtBuilder::default()
+            .with_default_xdns_records()
+            .with_default_attestation_targets()
+            .build();
+        ext.execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, INITIAL_BALANCE);
+            let _ = Balances::deposit_creating(&BOB, INITIAL_BALANCE);
+        });
+        ext
+    }
+
+    #[test]
+    fn send_gmp_message_assigns_consecutive_nonces_per_sender_and_requests_attestation() {
+        ext().execute_with(|| {
+            assert_ok!(Circuit::send_gmp_message(
+                RuntimeOrigin::signed(ALICE),
+                DESTINATION,
+                vec![7u8; 20],
+                b"first".to_vec(),
+                Some(GmpCallback::Contract(ALICE)),
+            ));
+            send(BOB, b"first");
+            let second_id = send(BOB, b"second");
+
+            assert_eq!(
+                Circuit::get_gmp_outbound_nonce((SELF_TARGET, DESTINATION), &ALICE),
+                1
+            );
+            assert_eq!(
+                Circuit::get_gmp_outbound_nonce((SELF_TARGET, DESTINATION), &BOB),
+                2
+            );
+            let second = Circuit::get_gmp_message(second_id).unwrap();
+            assert_eq!(second.nonce, 1);
+            assert_eq!(second.source, SELF_TARGET);
+            assert_eq!(second.submitted_at, 1);
+            assert_eq!(second.message_id(), second_id);
+
+            let attested_ids = NextBatch::<Runtime>::get(DESTINATION)
+                .unwrap()
+                .gmp_messages
+                .unwrap();
+            assert_eq!(attested_ids.len(), 3);
+            assert_eq!(attested_ids[2], second_id);
+
+            System::assert_last_event(RuntimeEvent::Circuit(Event::GmpMessageSent(
+                second_id,
+                DESTINATION,
+                1,
+                BOB,
+                second.payload_hash(),
+            )));
+        });
+    }
+
+    #[test]
+    fn send_gmp_message_rejects_oversized_payloads_inactive_destinations_and_foreign_callbacks() {
+        ext().execute_with(|| {
+            assert_noop!(
+                Circuit::send_gmp_message(
+                    RuntimeOrigin::signed(ALICE),
+                    DESTINATION,
+                    vec![7u8; 20],
+                    vec![0u8; MAX_GMP_PAYLOAD_LEN as usize + 1],
+                    None,
+                ),
+                Error::<Runtime>::GmpPayloadTooLarge
+            );
+            assert_noop!(
+                Circuit::send_gmp_message(
+                    RuntimeOrigin::signed(ALICE),
+                    DESTINATION,
+                    vec![7u8; MAX_GMP_RECIPIENT_LEN as usize + 1],
+                    b"payload".to_vec(),
+                    None,
+                ),
+                Error::<Runtime>::GmpRecipientTooLong
+            );
+            assert_noop!(
+                Circuit::send_gmp_message(
+                    RuntimeOrigin::signed(ALICE),
+                    [9, 9, 9, 9],
+                    vec![7u8; 20],
+                    b"payload".to_vec(),
+                    None,
+                ),
+                Error::<Runtime>::GatewayNotActive
+            );
+            assert_noop!(
+                Circuit::send_gmp_message(
+                    RuntimeOrigin::signed(ALICE),
+                    DESTINATION,
+                    vec![7u8; 20],
+                    b"payload".to_vec(),
+                    Some(GmpCallback::Contract(BOB)),
+                ),
+                Error::<Runtime>::GmpCallbackNotOwnedBySender
+            );
+            assert_noop!(
+                Circuit::send_gmp_message(
+                    RuntimeOrigin::signed(ALICE),
+                    DESTINATION,
+                    vec![7u8; 20],
+                    b"payload".to_vec(),
+                    Some(GmpCallback::Pallet(*b"hook")),
+                ),
+                Error::<Runtime>::GmpPalletCallbackNotSupported
+            );
+        });
+    }
+
+    #[test]
+    fn acknowledge_gmp_message_rejects_oversized_return_data_and_inclusion_proofs() {
+        ext().execute_with(|| {
+            let message_id = send(ALICE, b"payload");
+
+            assert_noop!(
+                Circuit::acknowledge_gmp_message(
+                    RuntimeOrigin::signed(BOB),
+                    message_id,
+                    vec![0u8; MAX_GMP_RETURN_DATA_LEN as usize + 1],
+                    vec![],
+                ),
+                Error::<Runtime>::GmpReturnDataTooLarge
+            );
+            assert_noop!(
+                Circuit::acknowledge_gmp_message(
+                    RuntimeOrigin::signed(BOB),
+                    message_id,
+                    vec![],
+                    vec![0u8; MAX_GMP_INCLUSION_PROOF_LEN as usize + 1],
+                ),
+                Error::<Runtime>::GmpInclusionProofTooLarge
+            );
+        });
+    }
+
+    #[test]
+    fn gmp_acknowledgements_are_applied_in_channel_order_and_once() {
+        ext().execute_with(|| {
+            let first_id = send(ALICE, b"first");
+            let second_id = send(ALICE, b"second");
+
+            assert_noop!(
+                Gmp::<Runtime>::apply_acknowledgement(second_id, true, vec![]),
+                Error::<Runtime>::GmpAcknowledgementOutOfOrder
+            );
+
+            let (acknowledgement, _) =
+                Gmp::<Runtime>::apply_acknowledgement(first_id, false, b"reverted".to_vec())
+                    .unwrap();
+            assert_eq!(acknowledgement.nonce, 0);
+            assert!(!acknowledgement.success);
+            assert_eq!(acknowledgement.return_data, b"reverted".to_vec());
+            assert_eq!(Circuit::get_gmp_message(first_id), None);
+            System::assert_last_event(RuntimeEvent::Circuit(Event::GmpMessageAcknowledged(
+                first_id,
+                DESTINATION,
+                0,
+                false,
+            )));
+
+            // Replayed acknowledgements find no message to close
+            assert_noop!(
+                Gmp::<Runtime>::apply_acknowledgement(first_id, true, vec![]),
+                Error::<Runtime>::GmpMessageNotFound
+            );
+            assert_noop!(
+                Circuit::acknowledge_gmp_message(
+                    RuntimeOrigin::signed(BOB),
+                    first_id,
+                    vec![],
+                    vec![],
+                ),
+                Error::<Runtime>::GmpMessageNotFound
+            );
+
+            assert_ok!(Gmp::<Runtime>::apply_acknowledgement(
+                second_id,
+                true,
+                vec![]
+            ));
+            assert_eq!(
+                Circuit::get_gmp_acknowledged_nonce((SELF_TARGET, DESTINATION), &ALICE),
+                2
+            );
+        });
+    }
+
+    #[test]
+    fn unacknowledged_gmp_message_only_holds_back_its_own_sender() {
+        ext().execute_with(|| {
+            let _stuck_id = send(ALICE, b"never acknowledged");
+            let bob_id = send(BOB, b"acknowledged");
+
+            assert_ok!(Gmp::<Runtime>::apply_acknowledgement(bob_id, true, vec![]));
+            assert_eq!(
+                Circuit::get_gmp_acknowledged_nonce((SELF_TARGET, DESTINATION), &BOB),
+                1
+            );
+            assert_eq!(
+                Circuit::get_gmp_acknowledged_nonce((SELF_TARGET, DESTINATION), &ALICE),
+                0
+            );
+        });
+    }
+
+    #[test]
+    fn gmp_deposit_is_held_per_byte_until_the_message_is_acknowledged() {
+        ext().execute_with(|| {
+            let deposit = deposit_of(20, b"payload".len());
+            assert!(deposit > 0);
+
+            let message_id = send(ALICE, b"payload");
+            assert_eq!(Balances::free_balance(&ALICE), INITIAL_BALANCE - deposit);
+
+            assert_ok!(Gmp::<Runtime>::apply_acknowledgement(
+                message_id,
+                true,
+                vec![]
+            ));
+            assert_eq!(Balances::free_balance(&ALICE), INITIAL_BALANCE);
+        });
+    }
+
+    #[test]
+    fn send_gmp_message_fails_without_the_funds_for_its_deposit() {
+        ext().execute_with(|| {
+            let poor_sender = AccountId::new([99u8; 32]);
+            assert!(Circuit::send_gmp_message(
+                RuntimeOrigin::signed(poor_sender.clone()),
+                DESTINATION,
+                vec![7u8; 20],
+                b"payload".to_vec(),
+                None,
+            )
+            .is_err());
+            assert_eq!(
+                Circuit::get_gmp_outbound_nonce((SELF_TARGET, DESTINATION), &poor_sender),
+                0
+            );
+        });
+    }
+
+    #[test]
+    fn gmp_acknowledgement_is_delivered_to_the_contract_callback_with_its_weight() {
+        ext().execute_with(|| {
+            assert_ok!(Circuit::send_gmp_message(
+                RuntimeOrigin::signed(ALICE),
+                DESTINATION,
+                vec![7u8; 20],
+                b"payload".to_vec(),
+                Some(GmpCallback::Contract(ALICE)),
+            ));
+            let message_id = send(BOB, b"payload");
+            let alice_message_id = NextBatch::<Runtime>::get(DESTINATION)
+                .unwrap()
+                .gmp_messages
+                .unwrap()[0];
+
+            let (acknowledgement, callback_weight) =
+                Gmp::<Runtime>::apply_acknowledgement(alice_message_id, true, b"ok".to_vec())
+                    .unwrap();
+            assert!(callback_weight.ref_time() > 0);
+            assert_eq!(
+                ThreeVm::gmp_acknowledgement_of(&ALICE, alice_message_id),
+                Some(acknowledgement)
+            );
+
+            let (_, no_callback_weight) =
+                Gmp::<Runtime>::apply_acknowledgement(message_id, true, vec![]).unwrap();
+            assert_eq!(no_callback_weight.ref_time(), 0);
+        });
+    }
+
+    #[test]
+    fn unacknowledged_gmp_message_expires_in_channel_order_after_its_timeout() {
+        ext().execute_with(|| {
+            let first_id = send(ALICE, b"never acknowledged");
+            let second_id = send(ALICE, b"held back");
+            let timeout: u32 =
+                <Runtime as circuit_runtime_pallets::pallet_circuit::Config>::GmpMessageTimeout::get();
+
+            assert_noop!(
+                Circuit::expire_gmp_message(RuntimeOrigin::signed(BOB), first_id),
+                Error::<Runtime>::GmpMessageNotExpired
+            );
+
+            System::set_block_number(System::block_number() + timeout);
+            assert_noop!(
+                Circuit::expire_gmp_message(RuntimeOrigin::signed(BOB), second_id),
+                Error::<Runtime>::GmpAcknowledgementOutOfOrder
+            );
+
+            assert_ok!(Circuit::expire_gmp_message(
+                RuntimeOrigin::signed(BOB),
+                first_id
+            ));
+            assert_eq!(Circuit::get_gmp_message(first_id), None);
+            assert_eq!(
+                Circuit::get_gmp_acknowledged_nonce((SELF_TARGET, DESTINATION), &ALICE),
+                1
+            );
+            assert_eq!(
+                Balances::free_balance(&ALICE),
+                INITIAL_BALANCE - deposit_of(20, b"held back".len())
+            );
+            System::assert_last_event(RuntimeEvent::Circuit(Event::GmpMessageExpired(
+                first_id,
+                DESTINATION,
+                0,
+            )));
+
+            // The expired message no longer holds back the later ones of its channel
+            assert_ok!(Gmp::<Runtime>::apply_acknowledgement(
+                second_id,
+                true,
+                vec![]
+            ));
+        });
+    }
+}
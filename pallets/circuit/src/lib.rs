@@ -41,8 +41,8 @@ use frame_system::{
 };
 use sp_core::H256;
 use sp_runtime::{
-    traits::{CheckedAdd, Zero},
-    DispatchError, KeyTypeId,
+    traits::{CheckedAdd, One, Zero},
+    DispatchError, KeyTypeId, Percent,
 };
 use sp_std::{convert::TryInto, vec, vec::Vec};
 
@@ -67,7 +67,10 @@ pub use t3rn_primitives::{
 };
 
 use crate::{
+    gmp::Gmp,
     machine::{Machine, *},
+    queues::{Queues, XtxQueue},
+    sponsorship::FeeSponsorship,
     square_up::SquareUp,
 };
 pub use state::XExecSignal;
@@ -78,6 +81,9 @@ pub use t3rn_primitives::light_client::InclusionReceipt;
 use t3rn_primitives::{
     attesters::AttestersWriteApi,
     circuit::{CircuitSubmitAPI, ReadSFX},
+    clock::{Cadence, PeriodicTask, PeriodicTasks},
+    gmp::{GmpCallback, GmpMessage, GmpNonce, OnGmpAcknowledged},
+    maintenance::{PauseScope, PauseScopes},
 };
 pub use t3rn_sdk_primitives::signal::{ExecutionSignal, SignalKind};
 use t3rn_types::{fsx::TargetId, sfx::Sfx4bId};
@@ -85,11 +91,17 @@ use t3rn_types::{fsx::TargetId, sfx::Sfx4bId};
 #[cfg(test)]
 pub mod tests;
 
+#[cfg(test)]
+pub mod tests_fuzz;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
 pub mod bids;
+pub mod gmp;
 pub mod machine;
+pub mod queues;
+pub mod sponsorship;
 pub mod square_up;
 pub mod state;
 pub mod weights;
@@ -174,6 +186,7 @@ pub mod pallet {
             CircuitDLQ, CircuitSubmitAPI, LocalStateExecutionView, LocalTrigger, OnLocalTrigger,
             ReadSFX,
         },
+        contracts_registry::Royalties,
         portal::Portal,
         xdns::Xdns,
         SpeedMode,
@@ -192,6 +205,78 @@ pub mod pallet {
     #[pallet::getter(fn get_gmp)]
     pub type GMP<T> = StorageMap<_, Identity, H256, H256, OptionQuery>;
 
+    /// Nonce assigned to the next GMP message the sender sends over the (source, destination) channel
+    #[pallet::storage]
+    #[pallet::getter(fn get_gmp_outbound_nonce)]
+    pub type GmpOutboundNonces<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        (TargetId, TargetId),
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        GmpNonce,
+        ValueQuery,
+    >;
+
+    /// Nonce of the sender's next GMP message to be acknowledged on the (source, destination) channel
+    #[pallet::storage]
+    #[pallet::getter(fn get_gmp_acknowledged_nonce)]
+    pub type GmpAcknowledgedNonces<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        (TargetId, TargetId),
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        GmpNonce,
+        ValueQuery,
+    >;
+
+    /// GMP messages sent and awaiting the acknowledgement of their destination, by message id
+    #[pallet::storage]
+    #[pallet::getter(fn get_gmp_message)]
+    pub type GmpOutbox<T: Config> = StorageMap<
+        _,
+        Identity,
+        H256,
+        GmpMessage<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Requesters' budgets for the transaction fees of executors serving their Xtx, by Xtx id
+    #[pallet::storage]
+    #[pallet::getter(fn get_executor_fee_sponsorship)]
+    pub type ExecutorFeeSponsorships<T: Config> = StorageMap<
+        _,
+        Identity,
+        XExecSignalId<T>,
+        (<T as frame_system::Config>::AccountId, BalanceOf<T>),
+        OptionQuery,
+    >;
+
+    /// Executors whose bid on the SFX of the Xtx has been sponsored, each sponsored for a single bid per SFX
+    #[pallet::storage]
+    pub type SponsoredBids<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        XExecSignalId<T>,
+        Blake2_128Concat,
+        (SideEffectId<T>, <T as frame_system::Config>::AccountId),
+        (),
+        OptionQuery,
+    >;
+
+    /// Executors whose confirmation of the SFX of the Xtx has been sponsored, each sponsored for a single confirmation per SFX
+    #[pallet::storage]
+    pub type SponsoredConfirmations<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        XExecSignalId<T>,
+        Blake2_128Concat,
+        (SideEffectId<T>, <T as frame_system::Config>::AccountId),
+        (),
+        OptionQuery,
+    >;
+
     /// Links mapping SFX 2 XTX
     ///
     #[pallet::storage]
@@ -221,11 +306,35 @@ pub mod pallet {
     pub type PendingXtxBidsTimeoutsMap<T> =
         StorageMap<_, Identity, XExecSignalId<T>, BlockNumberFor<T>, OptionQuery>;
 
+    /// Number of Xtx, not yet finalized, with at least one side effect towards the target.
+    #[pallet::storage]
+    #[pallet::getter(fn get_active_xtx_count_on_target)]
+    pub type ActiveXtxCountPerTarget<T> = StorageMap<_, Identity, TargetId, u32, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn get_finalized_xtx)]
     pub type FinalizedXtx<T> =
         StorageMap<_, Identity, XExecSignalId<T>, BlockNumberFor<T>, OptionQuery>;
 
+    /// Xtx ids of the timeout and finalization maps bucketed by queue and the height they become due at,
+    ///     so that queue processing only visits the Xtx due at the drained heights instead of scanning the whole maps.
+    #[pallet::storage]
+    pub type XtxQueueBuckets<T> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        (XtxQueue, BlockNumberFor<T>),
+        Identity,
+        XExecSignalId<T>,
+        (),
+        OptionQuery,
+    >;
+
+    /// Next bucket height each queue drains from. Runs cut off by the weight limit leave the cursor
+    ///     at the unfinished bucket, so that processing resumes there at the next block.
+    #[pallet::storage]
+    #[pallet::getter(fn get_xtx_queue_cursor)]
+    pub type XtxQueueCursors<T> = StorageMap<_, Identity, XtxQueue, BlockNumberFor<T>, OptionQuery>;
+
     /// Current Circuit's context of all accepted for execution cross-chain transactions.
     ///
     /// All Xtx that has been initially paid out by users will be left here.
@@ -288,6 +397,10 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// Raw storage key of the last DLQ entry visited, after which the next DLQ run resumes.
+    #[pallet::storage]
+    pub type DLQCursor<T> = StorageValue<_, Vec<u8>, OptionQuery>;
+
     /// Handles queued signals
     ///
     /// This operation is performed lazily in `on_initialize`.
@@ -362,6 +475,9 @@ pub mod pallet {
         /// A type that gives access to the new portal functionality
         type Portal: Portal<Self>;
 
+        /// Pauses of bidding and submissions per target, toggled without runtime upgrades
+        type PauseScopes: PauseScopes;
+
         /// The maximum number of signals that can be queued for handling.
         ///
         /// When a signal from 3vm is requested, we add it to the queue to be handled by on_initialize
@@ -380,6 +496,20 @@ pub mod pallet {
 
         // Needed in square_up mod
         type TreasuryAccounts: TreasuryAccountProvider<Self::AccountId>;
+
+        /// Royalties of registry contracts, taken out of the rewards of committed side effects
+        type Royalties: Royalties<Self::AccountId, BalanceOf<Self>, Self::Hash>;
+
+        /// Local callbacks receiving the acknowledgements and return data of the GMP messages
+        type GmpCallbacks: OnGmpAcknowledged<Self::AccountId>;
+
+        /// Deposit held per byte of the recipient and payload of a GMP message until it's acknowledged
+        #[pallet::constant]
+        type GmpDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Blocks after which a GMP message that hasn't been acknowledged can be expired, closing it in its channel
+        #[pallet::constant]
+        type GmpMessageTimeout: Get<BlockNumberFor<Self>>;
     }
 
     #[pallet::pallet]
@@ -403,7 +533,7 @@ pub mod pallet {
             // Define the maximum weight of this migration.
             let max_weight = T::DbWeight::get().reads_writes(10, 10);
             // Define the current storage migration version.
-            const CURRENT_STORAGE_VERSION: u32 = 1;
+            const CURRENT_STORAGE_VERSION: u32 = 3;
             // Migrate the storage entries.
             StorageMigrations::<T>::try_mutate(|current_version| {
                 match *current_version {
@@ -430,12 +560,35 @@ pub mod pallet {
                                 )
                             },
                         );
+                        let buckets_weight = Queues::<T>::bucket_existing_entries();
+                        let targets_weight = Self::count_active_xtx_targets();
 
                         // Set migrations_done to true
                         *current_version = CURRENT_STORAGE_VERSION;
 
                         // Return the weight consumed by the migration.
-                        Ok::<Weight, DispatchError>(max_weight)
+                        Ok::<Weight, DispatchError>(
+                            max_weight
+                                .saturating_add(buckets_weight)
+                                .saturating_add(targets_weight),
+                        )
+                    },
+                    1 => {
+                        // Storage Migration: bucket open Xtx timeouts and finalized Xtx into XtxQueueBuckets by height
+                        let buckets_weight = Queues::<T>::bucket_existing_entries();
+                        let targets_weight = Self::count_active_xtx_targets();
+
+                        *current_version = CURRENT_STORAGE_VERSION;
+
+                        Ok::<Weight, DispatchError>(buckets_weight.saturating_add(targets_weight))
+                    },
+                    2 => {
+                        // Storage Migration: count the Xtx in flight per target into ActiveXtxCountPerTarget
+                        let targets_weight = Self::count_active_xtx_targets();
+
+                        *current_version = CURRENT_STORAGE_VERSION;
+
+                        Ok::<Weight, DispatchError>(targets_weight)
                     },
                     // Add more migration cases here, if needed in the future
                     _ => {
@@ -449,15 +602,23 @@ pub mod pallet {
     }
 
     impl<T: Config> CircuitDLQ<T> for Pallet<T> {
-        fn process_dlq(n: frame_system::pallet_prelude::BlockNumberFor<T>) -> Weight {
-            Self::process_dlq(n)
+        fn process_dlq(
+            n: frame_system::pallet_prelude::BlockNumberFor<T>,
+            max_allowed_weight: Weight,
+        ) -> Weight {
+            Self::process_dlq(n, max_allowed_weight)
         }
 
         fn process_adaptive_xtx_timeout_queue(
             n: frame_system::pallet_prelude::BlockNumberFor<T>,
             verifier: &GatewayVendor,
+            max_allowed_weight: Weight,
         ) -> Weight {
-            Self::process_adaptive_xtx_timeout_queue(n, verifier)
+            Self::process_adaptive_xtx_timeout_queue(n, verifier, max_allowed_weight)
+        }
+
+        fn has_active_xtx_on_target(target: &TargetId) -> bool {
+            ActiveXtxCountPerTarget::<T>::get(target) > 0
         }
     }
 
@@ -658,6 +819,51 @@ pub mod pallet {
             <GMP<T>>::get(id)
         }
 
+        fn confirm_side_effect(
+            origin: OriginFor<T>,
+            sfx_id: SideEffectId<T>,
+            confirmation: ConfirmedSideEffect<T::AccountId, BlockNumberFor<T>, BalanceOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            Self::confirm_side_effect(origin, sfx_id, confirmation)
+        }
+
+        fn release_executor_bond(
+            sfx_id: SideEffectId<T>,
+            executor: &T::AccountId,
+        ) -> DispatchResult {
+            let xtx_id = SFX2XTXLinksMap::<T>::get(sfx_id)
+                .ok_or::<DispatchError>(Error::<T>::XtxNotFound.into())?;
+            let full_side_effects = FullSideEffects::<T>::get(xtx_id)
+                .ok_or::<DispatchError>(Error::<T>::XtxNotFound.into())?;
+            let fsx = full_side_effects
+                .iter()
+                .flatten()
+                .find(|fsx| fsx.calc_sfx_id::<SystemHashing<T>, T>(xtx_id) == sfx_id)
+                .ok_or::<DispatchError>(Error::<T>::FSXNotFoundById.into())?;
+
+            ensure!(
+                fsx.confirmed.is_some(),
+                Error::<T>::BondReleaseRejectedSideEffectNotConfirmed
+            );
+            let bid = fsx
+                .best_bid
+                .as_ref()
+                .filter(|bid| &bid.executor == executor)
+                .ok_or(Error::<T>::BondReleaseRejectedNotTheWinningExecutor)?;
+
+            // Bonds are otherwise released at the Xtx finalization, which skips the released ones
+            let bid_id = bid.generate_id::<SystemHashing<T>, T>(sfx_id);
+            <T as Config>::AccountManager::get_charge_or_fail(bid_id)
+                .map_err(|_| Error::<T>::BondReleaseRejectedAlreadyReleased)?;
+            ensure!(
+                <T as Config>::AccountManager::finalize_infallible(bid_id, Outcome::Revert),
+                Error::<T>::BondReleaseFailed
+            );
+
+            Self::deposit_event(Event::ExecutorBondReleased(sfx_id, executor.clone()));
+            Ok(())
+        }
+
         fn verify_sfx_proof(
             target: TargetId,
             speed_mode: SpeedMode,
@@ -879,7 +1085,11 @@ pub mod pallet {
         #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_xtx())]
         pub fn trigger_dlq(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
             ensure_signed(origin)?;
-            Self::process_dlq(<frame_system::Pallet<T>>::block_number());
+            // Never visit more of the DLQ than the call has been charged for
+            Self::process_dlq(
+                <frame_system::Pallet<T>>::block_number(),
+                <T as Config>::WeightInfo::cancel_xtx(),
+            );
             Ok(().into())
         }
 
@@ -1051,9 +1261,18 @@ pub mod pallet {
             // retrieve xtx_id
             let xtx_id = <Self as Store>::SFX2XTXLinksMap::get(sfx_id)
                 .ok_or(Error::<T>::LocalSideEffectExecutionNotApplicable)?;
+            let mut local_ctx = Machine::<T>::load_xtx(xtx_id)?;
+
+            let is_bidding_paused = local_ctx
+                .full_side_effects
+                .iter()
+                .flatten()
+                .filter(|fsx| fsx.calc_sfx_id::<SystemHashing<T>, T>(xtx_id) == sfx_id)
+                .any(|fsx| T::PauseScopes::is_scope_paused(&PauseScope::Bidding(fsx.input.target)));
+            ensure!(!is_bidding_paused, Error::<T>::BiddingPausedOnTarget);
 
             Machine::<T>::compile(
-                &mut Machine::<T>::load_xtx(xtx_id)?,
+                &mut local_ctx,
                 |_current_fsx, _local_state, _steps_cnt, _status, _requester| {
                     // Check if Xtx is in the bidding state
                     Ok(PrecompileResult::TryBid((
@@ -1119,6 +1338,76 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Send the payload to the recipient contract on an attested destination.
+        ///     The message id is attested in the next batch of the destination, and its acknowledgement
+        ///     along with the return data is delivered to the optional callback.
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::send_gmp_message(
+            recipient.len().saturating_add(payload.len()) as u32
+        ))]
+        pub fn send_gmp_message(
+            origin: OriginFor<T>,
+            destination: TargetId,
+            recipient: Vec<u8>,
+            payload: Vec<u8>,
+            callback: Option<GmpCallback<T::AccountId>>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            Gmp::<T>::send(sender, destination, recipient, payload, callback)?;
+            Ok(().into())
+        }
+
+        /// Relay the acknowledgement of a GMP message, proven with the inclusion of the event emitted
+        ///     by the escrow contract of the message's destination, which commits to the return data hash.
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::acknowledge_gmp_message(
+            return_data.len().saturating_add(inclusion_proof.len()) as u32
+        ).saturating_add(T::GmpCallbacks::on_gmp_acknowledged_max_weight(return_data.len() as u32)))]
+        pub fn acknowledge_gmp_message(
+            origin: OriginFor<T>,
+            message_id: H256,
+            return_data: Vec<u8>,
+            inclusion_proof: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let _relayer = ensure_signed(origin)?;
+            let len = return_data.len().saturating_add(inclusion_proof.len()) as u32;
+            let (_, callback_weight) =
+                Gmp::<T>::acknowledge(message_id, return_data, inclusion_proof)?;
+            Ok(Some(
+                <T as pallet::Config>::WeightInfo::acknowledge_gmp_message(len)
+                    .saturating_add(callback_weight),
+            )
+            .into())
+        }
+
+        /// Close a GMP message left unacknowledged for `GmpMessageTimeout` blocks as failed, so that it no longer
+        ///     holds back the later messages of its channel, and return the sender's deposit.
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::expire_gmp_message()
+            .saturating_add(T::GmpCallbacks::on_gmp_acknowledged_max_weight(0)))]
+        pub fn expire_gmp_message(
+            origin: OriginFor<T>,
+            message_id: H256,
+        ) -> DispatchResultWithPostInfo {
+            let _caller = ensure_signed(origin)?;
+            let (_, callback_weight) = Gmp::<T>::expire(message_id)?;
+            Ok(Some(
+                <T as pallet::Config>::WeightInfo::expire_gmp_message()
+                    .saturating_add(callback_weight),
+            )
+            .into())
+        }
+
+        /// Set aside the budget the transaction fees of executors bidding on and confirming the requester's Xtx
+        ///     are paid from, so that executors can serve the Xtx without holding TRN. Zero budget ends the sponsorship.
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::sponsor_executor_fees())]
+        pub fn sponsor_executor_fees(
+            origin: OriginFor<T>,
+            xtx_id: XExecSignalId<T>,
+            budget: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let requester = ensure_signed(origin)?;
+            FeeSponsorship::<T>::set_budget(requester, xtx_id, budget)?;
+            Ok(().into())
+        }
     }
 
     use crate::machine::{no_mangle, Machine};
@@ -1220,6 +1509,19 @@ pub mod pallet {
         UnsuccessfulFSXCommitAttestationRequest(H256),
         SuccessfulFSXRevertAttestationRequest(H256),
         UnsuccessfulFSXRevertAttestationRequest(H256),
+        // Bond of the executor of a confirmed SideEffect was released ahead of the Xtx finalization.
+        ExecutorBondReleased(SideEffectId<T>, T::AccountId),
+        // Listeners - attesters/relayers to deliver the payload, read from GmpOutbox, to the recipient contract on the destination.
+        // [message_id, destination, nonce, sender, payload_hash]
+        GmpMessageSent(H256, TargetId, GmpNonce, T::AccountId, H256),
+        // GMP message was acknowledged by its destination. [message_id, destination, nonce, success]
+        GmpMessageAcknowledged(H256, TargetId, GmpNonce, bool),
+        // GMP message was closed as failed after its timeout without an acknowledgement. [message_id, destination, nonce]
+        GmpMessageExpired(H256, TargetId, GmpNonce),
+        // Requester set the budget for the executors' fees of the Xtx. [xtx_id, requester, budget]
+        ExecutorFeesSponsorshipSet(XExecSignalId<T>, T::AccountId, BalanceOf<T>),
+        // Requester paid the transaction fee of the executor's call. [xtx_id, requester, executor, fee]
+        ExecutorFeeSponsored(XExecSignalId<T>, T::AccountId, T::AccountId, BalanceOf<T>),
     }
 
     #[pallet::error]
@@ -1310,6 +1612,25 @@ pub mod pallet {
         XtxChargeFailedOnEscrowFee,
         FailedToPerformDynamicDestinationDealHotSwap,
         NotImplemented,
+        BiddingPausedOnTarget,
+        SubmissionPausedOnTarget,
+        BondReleaseRejectedSideEffectNotConfirmed,
+        BondReleaseRejectedNotTheWinningExecutor,
+        BondReleaseRejectedAlreadyReleased,
+        BondReleaseFailed,
+        GmpPayloadTooLarge,
+        GmpRecipientTooLong,
+        GmpDepositIdDecodingFailed,
+        GmpCallbackNotOwnedBySender,
+        GmpMessageNotFound,
+        GmpDestinationEscrowNotRegistered,
+        GmpAcknowledgementMismatch,
+        GmpAcknowledgementOutOfOrder,
+        GmpPalletCallbackNotSupported,
+        GmpReturnDataTooLarge,
+        GmpInclusionProofTooLarge,
+        GmpMessageNotExpired,
+        FeeSponsorshipNotOwnedByRequester,
     }
 }
 
@@ -1327,6 +1648,53 @@ impl<T: SigningTypes> SignedPayload<T> for Payload<T::Public, BlockNumberFor<T>>
     }
 }
 
+/// Number of blocks apart the emergency revert queue is processed at
+pub const EMERGENCY_REVERT_INTERVAL: u32 = 10;
+
+/// Number of DLQ entries visited by a single DLQ run
+pub const DLQ_PAGE_SIZE: usize = 32;
+
+impl<T: Config> PeriodicTasks<T> for Pallet<T> {
+    fn periodic_tasks() -> Vec<PeriodicTask<T>> {
+        vec![
+            PeriodicTask {
+                name: b"circuit::signal_queue",
+                cadence: Cadence::EveryBlock,
+                share: Percent::from_percent(5),
+                priority: 30,
+                run: |n, limit| Self::process_signal_queue(n, One::one(), limit),
+            },
+            PeriodicTask {
+                name: b"circuit::xtx_tick_queue",
+                cadence: Cadence::EveryBlock,
+                share: Percent::from_percent(30),
+                priority: 31,
+                run: |n, limit| Self::process_xtx_tick_queue(n, One::one(), limit),
+            },
+            PeriodicTask {
+                name: b"circuit::emergency_revert_queue",
+                cadence: Cadence::EveryBlock,
+                share: Percent::from_percent(5),
+                priority: 32,
+                run: |n, limit| {
+                    Self::process_emergency_revert_xtx_queue(
+                        n,
+                        EMERGENCY_REVERT_INTERVAL.into(),
+                        limit,
+                    )
+                },
+            },
+            PeriodicTask {
+                name: b"circuit::dlq",
+                cadence: Cadence::Blocks(EMERGENCY_REVERT_INTERVAL),
+                share: Percent::from_percent(5),
+                priority: 33,
+                run: |n, limit| Self::process_dlq(n, limit),
+            },
+        ]
+    }
+}
+
 impl<T: Config> Pallet<T> {
     fn emit_sfx(
         xtx_id: XExecSignalId<T>,
@@ -1398,6 +1766,13 @@ impl<T: Config> Pallet<T> {
         preferred_security_level: &SecurityLvl,
         maybe_call_origin: Option<T::AccountId>,
     ) -> Result<LocalXtxCtx<T, BalanceOf<T>>, Error<T>> {
+        if side_effects
+            .iter()
+            .any(|sfx| T::PauseScopes::is_scope_paused(&PauseScope::Submission(sfx.target)))
+        {
+            return Err(Error::<T>::SubmissionPausedOnTarget)
+        }
+
         // Setup: new xtx context with SFX validation
         let mut fresh_xtx = Machine::<T>::setup(
             &side_effects,
@@ -1746,51 +2121,128 @@ impl<T: Config> Pallet<T> {
         kill_interval: frame_system::pallet_prelude::BlockNumberFor<T>,
         max_allowed_weight: Weight,
     ) -> Weight {
-        let mut current_weight = 0;
-        if kill_interval == frame_system::pallet_prelude::BlockNumberFor::<T>::zero() {
+        if kill_interval == frame_system::pallet_prelude::BlockNumberFor::<T>::zero()
+            || n % kill_interval != frame_system::pallet_prelude::BlockNumberFor::<T>::zero()
+        {
             return Weight::zero()
-        } else if n % kill_interval == frame_system::pallet_prelude::BlockNumberFor::<T>::zero() {
-            // Go over all unfinished Xtx to find those that should be killed
-            let _processed_xtx_revert_count = <PendingXtxBidsTimeoutsMap<T>>::iter()
-                .filter(|(_xtx_id, timeout_at)| timeout_at <= &n)
-                .map(|(xtx_id, _timeout_at)| {
-                    if current_weight <= max_allowed_weight.ref_time() {
-                        current_weight = current_weight
-                            .saturating_add(Self::process_tick_one(xtx_id).ref_time());
-                    }
-                })
-                .count();
-
-            // Go over all unfinished Xtx to find those that should be killed
-            let _processed_xtx_commit_count = <FinalizedXtx<T>>::iter()
-                .map(|(xtx_id, _)| {
-                    if current_weight <= max_allowed_weight.ref_time() {
-                        current_weight = current_weight
-                            .saturating_add(Self::process_tick_two(xtx_id).ref_time());
-                    }
-                })
-                .count();
         }
-        Weight::from_parts(current_weight, 0u64)
+
+        // Go over the Xtx whose bidding timed out to find those that should be killed
+        let bids_weight =
+            Queues::<T>::drain(XtxQueue::BidsTimeouts, n, max_allowed_weight, |xtx_id| {
+                match <PendingXtxBidsTimeoutsMap<T>>::get(xtx_id) {
+                    Some(timeout_at) if timeout_at <= n => {
+                        let weight = Self::process_tick_one(xtx_id);
+                        // Retry at the next tick if the Xtx is still in bidding
+                        if <PendingXtxBidsTimeoutsMap<T>>::contains_key(xtx_id) {
+                            Queues::<T>::schedule(XtxQueue::BidsTimeouts, xtx_id, n + One::one());
+                        }
+                        weight.saturating_add(T::DbWeight::get().reads(2))
+                    },
+                    _ => T::DbWeight::get().reads(1),
+                }
+            });
+
+        // Go over the finalized Xtx to commit them
+        let finalized_weight = Queues::<T>::drain(
+            XtxQueue::Finalized,
+            n,
+            max_allowed_weight.saturating_sub(bids_weight),
+            |xtx_id| {
+                if !<FinalizedXtx<T>>::contains_key(xtx_id) {
+                    return T::DbWeight::get().reads(1)
+                }
+                let weight = Self::process_tick_two(xtx_id);
+                // Retry at the next tick if the Xtx hasn't been committed
+                if <XExecSignals<T>>::contains_key(xtx_id) {
+                    Queues::<T>::schedule(XtxQueue::Finalized, xtx_id, n + One::one());
+                } else {
+                    <FinalizedXtx<T>>::remove(xtx_id);
+                }
+                weight.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+            },
+        );
+
+        bids_weight.saturating_add(finalized_weight)
+    }
+
+    /// Counts an Xtx as in flight on each of its side effects' targets, or releases the count.
+    pub(crate) fn track_active_xtx_targets(
+        full_side_effects: &[Vec<
+            FullSideEffect<
+                T::AccountId,
+                frame_system::pallet_prelude::BlockNumberFor<T>,
+                BalanceOf<T>,
+            >,
+        >],
+        active: bool,
+    ) {
+        let targets = full_side_effects
+            .iter()
+            .flatten()
+            .map(|fsx| fsx.input.target)
+            .collect::<sp_std::collections::btree_set::BTreeSet<TargetId>>();
+
+        for target in targets {
+            ActiveXtxCountPerTarget::<T>::mutate_exists(target, |count| {
+                let current = count.unwrap_or_default();
+                let next = if active {
+                    current.saturating_add(1)
+                } else {
+                    current.saturating_sub(1)
+                };
+                *count = if next > 0 { Some(next) } else { None };
+            });
+        }
+    }
+
+    /// Rebuilds ActiveXtxCountPerTarget out of the Xtx in flight. Used by the storage migration.
+    pub(crate) fn count_active_xtx_targets() -> Weight {
+        let _ = ActiveXtxCountPerTarget::<T>::clear(u32::MAX, None);
+        let mut reads: u64 = 0;
+        for (xtx_id, xtx) in XExecSignals::<T>::iter() {
+            reads = reads.saturating_add(1);
+            if !matches!(
+                xtx.status,
+                CircuitStatus::Reserved
+                    | CircuitStatus::PendingBidding
+                    | CircuitStatus::InBidding
+                    | CircuitStatus::Ready
+                    | CircuitStatus::PendingExecution
+                    | CircuitStatus::Finished
+            ) {
+                continue
+            }
+            if let Some(fsx) = FullSideEffects::<T>::get(xtx_id) {
+                reads = reads.saturating_add(1);
+                Self::track_active_xtx_targets(&fsx, true);
+            }
+        }
+        T::DbWeight::get().reads_writes(reads.saturating_mul(2), reads)
     }
 
     pub fn process_adaptive_xtx_timeout_queue(
         n: frame_system::pallet_prelude::BlockNumberFor<T>,
         _verifier: &GatewayVendor,
+        max_allowed_weight: Weight,
     ) -> Weight {
-        let mut current_weight: Weight = Zero::zero();
-
-        // Go over all unfinished Xtx to find those that timed out
-        let _processed_xtx_revert_count = <PendingXtxTimeoutsMap<T>>::iter()
-            .filter(|(_xtx_id, adaptive_timeout)| {
+        // Go over the Xtx whose adaptive timeout has passed, which are the ones bucketed before n
+        let last_expired_height = n.saturating_sub(One::one());
+        Queues::<T>::drain(
+            XtxQueue::AdaptiveTimeouts,
+            last_expired_height,
+            max_allowed_weight,
+            |xtx_id| match <PendingXtxTimeoutsMap<T>>::get(xtx_id) {
                 // Tip: consider filtering out by adaptive_timeout.verifier == verifier
-                adaptive_timeout.estimated_height_here < n
-            })
-            .map(|(xtx_id, _timeout_at)| {
-                current_weight = current_weight.saturating_add(Self::process_revert_one(xtx_id).0);
-            })
-            .count();
-        current_weight
+                Some(adaptive_timeout) if adaptive_timeout.estimated_height_here < n =>
+                    Self::process_revert_one_or_retry(
+                        XtxQueue::AdaptiveTimeouts,
+                        xtx_id,
+                        last_expired_height + One::one(),
+                    ),
+                _ => T::DbWeight::get().reads(1),
+            },
+        )
     }
 
     pub fn process_emergency_revert_xtx_queue(
@@ -1798,25 +2250,40 @@ impl<T: Config> Pallet<T> {
         revert_interval: frame_system::pallet_prelude::BlockNumberFor<T>,
         max_allowed_weight: Weight,
     ) -> Weight {
-        let mut current_weight: Weight = Default::default();
-        // Scenario 1: all the timeout s can be handled in the block space
-        // Scenario 2: all but 5 timeouts can be handled
-        //     - add the 5 timeouts to an immediate queue for the next block
-        if revert_interval == frame_system::pallet_prelude::BlockNumberFor::<T>::zero() {
-            return current_weight
-        } else if n % revert_interval == frame_system::pallet_prelude::BlockNumberFor::<T>::zero() {
-            // Go over all unfinished Xtx to find those that timed out
-            let _processed_xtx_revert_count = <PendingXtxTimeoutsMap<T>>::iter()
-                .filter(|(_xtx_id, adaptive_timeout)| adaptive_timeout.emergency_timeout_here <= n)
-                .map(|(xtx_id, _timeout_at)| {
-                    if current_weight.ref_time() <= max_allowed_weight.ref_time() {
-                        current_weight =
-                            current_weight.saturating_add(Self::process_revert_one(xtx_id).0);
-                    }
-                })
-                .count();
+        if revert_interval == frame_system::pallet_prelude::BlockNumberFor::<T>::zero()
+            || n % revert_interval != frame_system::pallet_prelude::BlockNumberFor::<T>::zero()
+        {
+            return Weight::zero()
+        }
+
+        // Go over the Xtx whose emergency timeout has passed
+        Queues::<T>::drain(
+            XtxQueue::EmergencyTimeouts,
+            n,
+            max_allowed_weight,
+            |xtx_id| match <PendingXtxTimeoutsMap<T>>::get(xtx_id) {
+                Some(adaptive_timeout) if adaptive_timeout.emergency_timeout_here <= n =>
+                    Self::process_revert_one_or_retry(
+                        XtxQueue::EmergencyTimeouts,
+                        xtx_id,
+                        n + One::one(),
+                    ),
+                _ => T::DbWeight::get().reads(1),
+            },
+        )
+    }
+
+    /// Reverts the timed out Xtx, scheduling it again at the retry height if it's still pending afterwards.
+    fn process_revert_one_or_retry(
+        queue: XtxQueue,
+        xtx_id: XExecSignalId<T>,
+        retry_at: frame_system::pallet_prelude::BlockNumberFor<T>,
+    ) -> Weight {
+        let (weight, _success) = Self::process_revert_one(xtx_id);
+        if <PendingXtxTimeoutsMap<T>>::contains_key(xtx_id) {
+            Queues::<T>::schedule(queue, xtx_id, retry_at);
         }
-        current_weight
+        weight.saturating_add(T::DbWeight::get().reads(2))
     }
 
     pub fn get_adaptive_timeout(
@@ -1897,7 +2364,7 @@ impl<T: Config> Pallet<T> {
         };
 
         let adaptive_timeout = Self::get_adaptive_timeout(xtx_id, Some(dlq_entry.2));
-        <PendingXtxTimeoutsMap<T>>::insert(xtx_id, &adaptive_timeout);
+        Queues::<T>::insert_xtx_timeouts(xtx_id, &adaptive_timeout);
 
         <XExecSignals<T>>::mutate(xtx_id, |xtx| {
             if let Some(xtx) = xtx {
@@ -1918,15 +2385,46 @@ impl<T: Config> Pallet<T> {
 
     /// Processes the Dead Letter Queue (DLQ).
     ///
+    /// Visits at most `DLQ_PAGE_SIZE` entries per run, or fewer if they wouldn't fit into `max_allowed_weight`,
+    /// resuming after the last entry visited by the previous run.
+    ///
     /// # Arguments
     ///
     /// * `_n` - The current block number.
+    /// * `max_allowed_weight` - The weight the run may consume.
     ///
     /// # Returns
     ///
     /// The total weight of the operation.
-    pub fn process_dlq(_n: frame_system::pallet_prelude::BlockNumberFor<T>) -> Weight {
-        <DLQ<T>>::iter()
+    pub fn process_dlq(
+        _n: frame_system::pallet_prelude::BlockNumberFor<T>,
+        max_allowed_weight: Weight,
+    ) -> Weight {
+        let base_weight = T::DbWeight::get().reads_writes(1, 1);
+        // Worst case of visiting an entry is moving the Xtx out of the DLQ
+        let max_entry_weight = T::DbWeight::get().reads_writes(2, 3);
+        let page_size = (max_allowed_weight.saturating_sub(base_weight).ref_time()
+            / max_entry_weight.ref_time().max(1))
+        .min(DLQ_PAGE_SIZE as u64) as usize;
+        if page_size == 0 {
+            return Weight::zero()
+        }
+
+        let page = match <DLQCursor<T>>::take() {
+            Some(last_visited_key) => <DLQ<T>>::iter_from(last_visited_key),
+            None => <DLQ<T>>::iter(),
+        }
+        .take(page_size)
+        .collect::<Vec<_>>();
+
+        // Wrap around to the start of the DLQ once its end has been reached
+        if page.len() == page_size {
+            if let Some((last_visited, _)) = page.last() {
+                <DLQCursor<T>>::put(<DLQ<T>>::hashed_key_for(last_visited));
+            }
+        }
+
+        page.into_iter()
             .map(|(xtx_id, (_block_number, targets, _speed_mode))| {
                 if Self::ensure_all_gateways_are_active(targets) {
                     Self::remove_xtx_from_dlq(xtx_id).0
@@ -1934,8 +2432,7 @@ impl<T: Config> Pallet<T> {
                     T::DbWeight::get().reads(1)
                 }
             })
-            .reduce(|a, b| a.saturating_add(b))
-            .unwrap_or_else(|| T::DbWeight::get().reads(1))
+            .fold(base_weight, |a, b| a.saturating_add(b))
     }
 
     /// Processes a single cross-chain transaction (Xtx) revert operation.
@@ -1,7 +1,7 @@
 use crate::{pallet::Error, *};
 use frame_support::ensure;
 
-use crate::square_up::SquareUp;
+use crate::{sponsorship::FeeSponsorship, square_up::SquareUp};
 
 pub mod extra;
 pub use extra::*;
@@ -326,6 +326,14 @@ impl<T: Config> Machine<T> {
         };
     }
 
+    /// Drops the SFX links of the Xtx along with it, so that no link outlives its Xtx.
+    fn remove_sfx_links(local_ctx: &LocalXtxCtx<T, BalanceOf<T>>) {
+        for fsx in local_ctx.full_side_effects.iter().flatten() {
+            let sfx_id = fsx.calc_sfx_id::<SystemHashing<T>, T>(local_ctx.xtx_id);
+            <pallet::Pallet<T> as Store>::SFX2XTXLinksMap::remove(sfx_id);
+        }
+    }
+
     pub fn read_current_step_fsx(
         local_ctx: &LocalXtxCtx<T, BalanceOf<T>>,
     ) -> &Vec<FullSideEffect<<T as frame_system::Config>::AccountId, BlockNumberFor<T>, BalanceOf<T>>>
@@ -472,14 +480,9 @@ impl<T: Config> Machine<T> {
                     local_ctx.xtx_id,
                     local_ctx.local_state.clone(),
                 );
-                <pallet::Pallet<T> as Store>::PendingXtxTimeoutsMap::insert(
-                    local_ctx.xtx_id,
-                    &local_ctx.xtx.timeouts_at,
-                );
-                <pallet::Pallet<T> as Store>::PendingXtxBidsTimeoutsMap::insert::<
-                    XExecSignalId<T>,
-                    frame_system::pallet_prelude::BlockNumberFor<T>,
-                >(
+                pallet::Pallet::<T>::track_active_xtx_targets(&local_ctx.full_side_effects, true);
+                Queues::<T>::insert_xtx_timeouts(local_ctx.xtx_id, &local_ctx.xtx.timeouts_at);
+                Queues::<T>::insert_bids_timeout(
                     local_ctx.xtx_id,
                     T::SFXBiddingPeriod::get() + frame_system::Pallet::<T>::block_number(),
                 );
@@ -529,16 +532,11 @@ impl<T: Config> Machine<T> {
                 <pallet::Pallet<T> as Store>::PendingXtxTimeoutsMap::remove(local_ctx.xtx_id);
                 <pallet::Pallet<T> as Store>::LocalXtxStates::remove(local_ctx.xtx_id);
                 <pallet::Pallet<T> as Store>::FullSideEffects::remove(local_ctx.xtx_id);
-
-                let mut fsx_mut_arr = local_ctx.full_side_effects.clone();
-                for fsx_step in fsx_mut_arr.iter_mut() {
-                    for fsx in fsx_step {
-                        let sfx_id = fsx.calc_sfx_id::<SystemHashing<T>, T>(local_ctx.xtx_id);
-                        <pallet::Pallet<T> as Store>::SFX2XTXLinksMap::remove(sfx_id);
-                    }
-                }
+                Self::remove_sfx_links(local_ctx);
                 // Always clean temporary PendingXtxBidsTimeoutsMap after bidding
                 <pallet::Pallet<T> as Store>::PendingXtxBidsTimeoutsMap::remove(local_ctx.xtx_id);
+                pallet::Pallet::<T>::track_active_xtx_targets(&local_ctx.full_side_effects, false);
+                FeeSponsorship::<T>::end(local_ctx.xtx_id);
 
                 SquareUp::<T>::kill(local_ctx);
 
@@ -550,9 +548,12 @@ impl<T: Config> Machine<T> {
             ) => {
                 <pallet::Pallet<T> as Store>::XExecSignals::remove(local_ctx.xtx_id);
                 <pallet::Pallet<T> as Store>::FullSideEffects::remove(local_ctx.xtx_id);
+                Self::remove_sfx_links(local_ctx);
                 <pallet::Pallet<T> as Store>::PendingXtxTimeoutsMap::remove(local_ctx.xtx_id);
                 <pallet::Pallet<T> as Store>::DLQ::remove(local_ctx.xtx_id);
                 <pallet::Pallet<T> as Store>::LocalXtxStates::remove(local_ctx.xtx_id);
+                pallet::Pallet::<T>::track_active_xtx_targets(&local_ctx.full_side_effects, false);
+                FeeSponsorship::<T>::end(local_ctx.xtx_id);
 
                 SquareUp::<T>::finalize(local_ctx);
 
@@ -561,6 +562,7 @@ impl<T: Config> Machine<T> {
             (CircuitStatus::FinishedAllSteps, CircuitStatus::Committed) => {
                 <pallet::Pallet<T> as Store>::XExecSignals::remove(local_ctx.xtx_id);
                 <pallet::Pallet<T> as Store>::FullSideEffects::remove(local_ctx.xtx_id);
+                Self::remove_sfx_links(local_ctx);
                 <pallet::Pallet<T> as Store>::LocalXtxStates::remove(local_ctx.xtx_id);
 
                 true
@@ -583,10 +585,12 @@ impl<T: Config> Machine<T> {
                     *x = Some(local_ctx.full_side_effects.clone())
                 });
 
-                <pallet::Pallet<T> as Store>::FinalizedXtx::insert(
+                Queues::<T>::insert_finalized(
                     local_ctx.xtx_id,
                     <frame_system::Pallet<T>>::block_number(),
                 );
+                pallet::Pallet::<T>::track_active_xtx_targets(&local_ctx.full_side_effects, false);
+                FeeSponsorship::<T>::end(local_ctx.xtx_id);
 
                 SquareUp::<T>::finalize(local_ctx);
 
@@ -579,6 +579,31 @@ pub mod test {
             });
     }
 
+    #[test]
+    fn machine_tracks_xtx_active_on_target_until_reverted_or_finished() {
+        ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_default_xdns_records()
+            .build()
+            .execute_with(|| {
+                stage_single();
+                let target = get_mocked_transfer_sfx().target;
+                assert_eq!(Circuit::get_active_xtx_count_on_target(target), 0);
+
+                let reverted_xtx_id = setup_single_sfx_xtx_and_post_bid_and_set_to_ready(None);
+                assert_eq!(Circuit::get_active_xtx_count_on_target(target), 1);
+                assert!(Machine::<Runtime>::revert(
+                    reverted_xtx_id,
+                    Cause::Timeout,
+                    infallible_no_post_updates,
+                ));
+                assert_eq!(Circuit::get_active_xtx_count_on_target(target), 0);
+
+                let _finished_xtx_id = setup_single_sfx_xtx_and_confirm();
+                assert_eq!(Circuit::get_active_xtx_count_on_target(target), 0);
+            });
+    }
+
     #[test]
     fn machine_does_not_revert_single_step_xtx_when_finished_all_steps() {
         ExtBuilder::default()
@@ -248,6 +248,12 @@ pub fn check_all_state_clean(xtx_id: H256) {
     assert_eq!(LocalXtxStates::<Runtime>::get(xtx_id), None);
     assert_eq!(FullSideEffects::<Runtime>::get(xtx_id), None);
     assert_eq!(PendingXtxBidsTimeoutsMap::<Runtime>::get(xtx_id), None);
+    assert_eq!(ExecutorFeeSponsorships::<Runtime>::get(xtx_id), None);
+    assert_eq!(SponsoredBids::<Runtime>::iter_prefix(xtx_id).count(), 0);
+    assert_eq!(
+        SponsoredConfirmations::<Runtime>::iter_prefix(xtx_id).count(),
+        0
+    );
 }
 
 pub fn check_all_single_xtx_state_correct(
@@ -0,0 +1,134 @@
+use crate::*;
+use scale_info::TypeInfo;
+use sp_std::marker::PhantomData;
+
+#[cfg(test)]
+pub mod test;
+
+/// Queues of Xtx ids bucketed by the height they become due at
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum XtxQueue {
+    /// Bidding timeouts of PendingXtxBidsTimeoutsMap
+    BidsTimeouts,
+    /// AdaptiveTimeout::estimated_height_here of PendingXtxTimeoutsMap
+    AdaptiveTimeouts,
+    /// AdaptiveTimeout::emergency_timeout_here of PendingXtxTimeoutsMap
+    EmergencyTimeouts,
+    /// Finalization heights of FinalizedXtx
+    Finalized,
+}
+
+pub struct Queues<T: Config> {
+    _phantom: PhantomData<T>,
+}
+
+// Queue buckets only point at the canonical Xtx maps (PendingXtxBidsTimeoutsMap, PendingXtxTimeoutsMap, FinalizedXtx),
+//  which stay the source of truth. Entries are never removed from the buckets eagerly - drained entries
+//  which no longer match the canonical maps are skipped, which costs a single read each.
+impl<T: Config> Queues<T> {
+    /// Schedule the Xtx into the bucket of the given height.
+    ///     Heights the cursor has already drained are scheduled into the cursor's bucket instead.
+    pub fn schedule(queue: XtxQueue, xtx_id: XExecSignalId<T>, at: BlockNumberFor<T>) {
+        let cursor = XtxQueueCursors::<T>::get(queue).unwrap_or_else(|| {
+            let now = <frame_system::Pallet<T>>::block_number();
+            XtxQueueCursors::<T>::insert(queue, now);
+            now
+        });
+        XtxQueueBuckets::<T>::insert((queue, at.max(cursor)), xtx_id, ());
+    }
+
+    pub fn insert_xtx_timeouts(
+        xtx_id: XExecSignalId<T>,
+        timeouts: &AdaptiveTimeout<BlockNumberFor<T>, TargetId>,
+    ) {
+        PendingXtxTimeoutsMap::<T>::insert(xtx_id, timeouts);
+        Self::schedule(
+            XtxQueue::AdaptiveTimeouts,
+            xtx_id,
+            timeouts.estimated_height_here,
+        );
+        Self::schedule(
+            XtxQueue::EmergencyTimeouts,
+            xtx_id,
+            timeouts.emergency_timeout_here,
+        );
+    }
+
+    pub fn insert_bids_timeout(xtx_id: XExecSignalId<T>, timeout_at: BlockNumberFor<T>) {
+        PendingXtxBidsTimeoutsMap::<T>::insert(xtx_id, timeout_at);
+        Self::schedule(XtxQueue::BidsTimeouts, xtx_id, timeout_at);
+    }
+
+    pub fn insert_finalized(xtx_id: XExecSignalId<T>, finalized_at: BlockNumberFor<T>) {
+        FinalizedXtx::<T>::insert(xtx_id, finalized_at);
+        Self::schedule(XtxQueue::Finalized, xtx_id, finalized_at);
+    }
+
+    /// Drain the buckets from the queue's cursor up to the given height (inclusive), processing each Xtx once.
+    ///     Once the weight limit is exceeded, the cursor stays at the unfinished bucket and the next run resumes there,
+    ///     so the cost of a run depends on the number of Xtx due rather than the number of all open Xtx.
+    pub fn drain(
+        queue: XtxQueue,
+        up_to: BlockNumberFor<T>,
+        max_allowed_weight: Weight,
+        mut process: impl FnMut(XExecSignalId<T>) -> Weight,
+    ) -> Weight {
+        let mut current_weight = T::DbWeight::get().reads(1);
+        let mut cursor = match XtxQueueCursors::<T>::get(queue) {
+            Some(cursor) => cursor,
+            None => return current_weight,
+        };
+
+        while cursor <= up_to {
+            loop {
+                if current_weight.ref_time() > max_allowed_weight.ref_time() {
+                    XtxQueueCursors::<T>::insert(queue, cursor);
+                    return current_weight.saturating_add(T::DbWeight::get().writes(1))
+                }
+                let next_xtx_id = XtxQueueBuckets::<T>::iter_key_prefix((queue, cursor)).next();
+                current_weight = current_weight.saturating_add(T::DbWeight::get().reads(1));
+                match next_xtx_id {
+                    Some(xtx_id) => {
+                        XtxQueueBuckets::<T>::remove((queue, cursor), xtx_id);
+                        current_weight = current_weight
+                            .saturating_add(T::DbWeight::get().writes(1))
+                            .saturating_add(process(xtx_id));
+                    },
+                    None => break,
+                }
+            }
+            cursor = cursor.saturating_add(One::one());
+        }
+
+        XtxQueueCursors::<T>::insert(queue, cursor);
+        current_weight.saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Schedule all entries of the canonical maps into the queue buckets. Used by the storage migration
+    ///     to bucket the Xtx that were open before the queues were introduced.
+    pub fn bucket_existing_entries() -> Weight {
+        let mut entries: u64 = 0;
+        for (xtx_id, timeout_at) in PendingXtxBidsTimeoutsMap::<T>::iter() {
+            Self::schedule(XtxQueue::BidsTimeouts, xtx_id, timeout_at);
+            entries += 1;
+        }
+        for (xtx_id, timeouts) in PendingXtxTimeoutsMap::<T>::iter() {
+            Self::schedule(
+                XtxQueue::AdaptiveTimeouts,
+                xtx_id,
+                timeouts.estimated_height_here,
+            );
+            Self::schedule(
+                XtxQueue::EmergencyTimeouts,
+                xtx_id,
+                timeouts.emergency_timeout_here,
+            );
+            entries += 1;
+        }
+        for (xtx_id, finalized_at) in FinalizedXtx::<T>::iter() {
+            Self::schedule(XtxQueue::Finalized, xtx_id, finalized_at);
+            entries += 1;
+        }
+        T::DbWeight::get().reads_writes(entries, entries.saturating_mul(2))
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+pub mod test {
+    use circuit_mock_runtime::{Circuit, ExtBuilder, Hash, Runtime, System};
+    use circuit_runtime_pallets::pallet_circuit::{
+        queues::{Queues, XtxQueue},
+        DLQCursor, XtxQueueBuckets, DLQ, DLQ_PAGE_SIZE,
+    };
+    use frame_support::{
+        traits::Get,
+        weights::{constants::RocksDbWeight, Weight},
+    };
+    use sp_core::H256;
+    use t3rn_primitives::SpeedMode;
+
+    const PROCESS_ONE_WEIGHT: Weight = Weight::from_parts(1_000_000_000_000, 0);
+
+    fn drain_ids(queue: XtxQueue, up_to: u32, max_allowed_weight: Weight) -> Vec<Hash> {
+        let mut visited = vec![];
+        Queues::<Runtime>::drain(queue, up_to, max_allowed_weight, |xtx_id| {
+            visited.push(xtx_id);
+            PROCESS_ONE_WEIGHT
+        });
+        visited
+    }
+
+    #[test]
+    fn drain_visits_only_the_buckets_due_and_advances_the_cursor() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let (due_a, due_b, open) = (
+                H256::repeat_byte(1),
+                H256::repeat_byte(2),
+                H256::repeat_byte(3),
+            );
+            Queues::<Runtime>::schedule(XtxQueue::AdaptiveTimeouts, due_a, 5);
+            Queues::<Runtime>::schedule(XtxQueue::AdaptiveTimeouts, due_b, 6);
+            Queues::<Runtime>::schedule(XtxQueue::AdaptiveTimeouts, open, 100);
+
+            assert_eq!(
+                drain_ids(XtxQueue::AdaptiveTimeouts, 6, Weight::MAX),
+                vec![due_a, due_b]
+            );
+            assert_eq!(
+                Circuit::get_xtx_queue_cursor(XtxQueue::AdaptiveTimeouts),
+                Some(7)
+            );
+            assert_eq!(
+                XtxQueueBuckets::<Runtime>::get((XtxQueue::AdaptiveTimeouts, 100), open),
+                Some(())
+            );
+
+            // Other queues keep their own buckets and cursors
+            assert_eq!(
+                drain_ids(XtxQueue::EmergencyTimeouts, 100, Weight::MAX),
+                vec![]
+            );
+            assert_eq!(
+                drain_ids(XtxQueue::AdaptiveTimeouts, 100, Weight::MAX),
+                vec![open]
+            );
+        });
+    }
+
+    #[test]
+    fn drain_resumes_from_the_unfinished_bucket_after_reaching_the_weight_limit() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let xtx_ids: Vec<Hash> = (1..=3u8).map(H256::repeat_byte).collect();
+            for xtx_id in xtx_ids.iter() {
+                Queues::<Runtime>::schedule(XtxQueue::BidsTimeouts, *xtx_id, 2);
+            }
+
+            let first_run = drain_ids(XtxQueue::BidsTimeouts, 10, PROCESS_ONE_WEIGHT);
+            assert_eq!(first_run.len(), 1);
+            assert_eq!(
+                Circuit::get_xtx_queue_cursor(XtxQueue::BidsTimeouts),
+                Some(2)
+            );
+
+            let second_run = drain_ids(XtxQueue::BidsTimeouts, 10, Weight::MAX);
+            assert_eq!(second_run.len(), 2);
+            assert_eq!(
+                Circuit::get_xtx_queue_cursor(XtxQueue::BidsTimeouts),
+                Some(11)
+            );
+
+            let mut all_visited = [first_run, second_run].concat();
+            all_visited.sort();
+            assert_eq!(all_visited, xtx_ids);
+        });
+    }
+
+    #[test]
+    fn schedule_behind_the_cursor_lands_in_the_next_drained_bucket() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            assert_eq!(drain_ids(XtxQueue::Finalized, 4, Weight::MAX), vec![]);
+
+            Queues::<Runtime>::schedule(XtxQueue::Finalized, H256::repeat_byte(1), 4);
+            assert_eq!(Circuit::get_xtx_queue_cursor(XtxQueue::Finalized), Some(1));
+            assert_eq!(
+                drain_ids(XtxQueue::Finalized, 4, Weight::MAX),
+                vec![H256::repeat_byte(1)]
+            );
+
+            Queues::<Runtime>::schedule(XtxQueue::Finalized, H256::repeat_byte(2), 3);
+            assert_eq!(
+                XtxQueueBuckets::<Runtime>::get((XtxQueue::Finalized, 5), H256::repeat_byte(2)),
+                Some(())
+            );
+        });
+    }
+
+    #[test]
+    fn process_dlq_visits_a_page_per_run_and_wraps_around() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            let dlq_len = DLQ_PAGE_SIZE + DLQ_PAGE_SIZE / 2;
+            for index in 0..dlq_len {
+                // Unregistered targets keep the Xtx parked in the DLQ
+                DLQ::<Runtime>::insert(
+                    H256::from_low_u64_be(index as u64),
+                    (1u32, vec![[9u8; 4]], SpeedMode::Finalized),
+                );
+            }
+
+            Circuit::process_dlq(1, Weight::MAX);
+            assert!(DLQCursor::<Runtime>::get().is_some());
+
+            Circuit::process_dlq(2, Weight::MAX);
+            assert_eq!(DLQCursor::<Runtime>::get(), None);
+            assert_eq!(DLQ::<Runtime>::iter().count(), dlq_len);
+        });
+    }
+
+    #[test]
+    fn process_dlq_visits_only_the_entries_fitting_into_the_weight_limit() {
+        ExtBuilder::default().build().execute_with(|| {
+            System::set_block_number(1);
+            for index in 0..DLQ_PAGE_SIZE {
+                DLQ::<Runtime>::insert(
+                    H256::from_low_u64_be(index as u64),
+                    (1u32, vec![[9u8; 4]], SpeedMode::Finalized),
+                );
+            }
+            let max_entry_weight = RocksDbWeight::get().reads_writes(2, 3);
+            let max_allowed_weight = RocksDbWeight::get()
+                .reads_writes(1, 1)
+                .saturating_add(max_entry_weight.saturating_mul(2));
+
+            // Resumes after the two entries which fit into the limit
+            let consumed = Circuit::process_dlq(1, max_allowed_weight);
+            assert!(max_allowed_weight.all_gte(consumed));
+            let second_visited = DLQ::<Runtime>::iter()
+                .nth(1)
+                .map(|(xtx_id, _)| xtx_id)
+                .unwrap();
+            assert_eq!(
+                DLQCursor::<Runtime>::get(),
+                Some(DLQ::<Runtime>::hashed_key_for(second_visited))
+            );
+
+            // Nothing is visited if not even a single entry fits into the limit
+            assert_eq!(Circuit::process_dlq(2, max_entry_weight), Weight::zero());
+            assert!(DLQCursor::<Runtime>::get().is_some());
+        });
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{bids::Bids, *};
+use frame_support::traits::IsSubType;
+use sp_runtime::{traits::Saturating, DispatchResult};
+use sp_std::marker::PhantomData;
+use t3rn_primitives::account_manager::ExecutorFeeSponsor;
+
+#[cfg(test)]
+pub mod test;
+
+pub struct FeeSponsorship<T: Config> {
+    _phantom: PhantomData<T>,
+}
+
+// Requesters sponsor the transaction fees of executors serving their Xtx out of the budget set aside per Xtx,
+//  so that new executors can bid and confirm without holding TRN. Fees are only sponsored for the calls that move
+//  the Xtx forward - bids while the Xtx is in bidding and first confirmations of the SFX's winning executor -
+//  so that the budget isn't spent on calls to Xtx that no longer need executors. The budget is charged the fee left
+//  after the refund of the unused weight. Sponsorships end, along with the record of the calls they sponsored,
+//  once all steps of the Xtx finish or it's killed or reverted.
+impl<T: Config> FeeSponsorship<T> {
+    /// Set the requester's budget for the executors' fees of the Xtx, where zero ends the sponsorship.
+    pub fn set_budget(
+        requester: T::AccountId,
+        xtx_id: XExecSignalId<T>,
+        budget: BalanceOf<T>,
+    ) -> DispatchResult {
+        let xtx = XExecSignals::<T>::get(xtx_id).ok_or(Error::<T>::XtxNotFound)?;
+        ensure!(
+            xtx.requester == requester,
+            Error::<T>::FeeSponsorshipNotOwnedByRequester
+        );
+        ensure!(
+            xtx.status < CircuitStatus::FinishedAllSteps,
+            Error::<T>::SetupFailedXtxAlreadyFinished
+        );
+
+        if budget.is_zero() {
+            ExecutorFeeSponsorships::<T>::remove(xtx_id);
+        } else {
+            ExecutorFeeSponsorships::<T>::insert(xtx_id, (requester.clone(), budget));
+        }
+        Pallet::<T>::deposit_event(Event::ExecutorFeesSponsorshipSet(xtx_id, requester, budget));
+
+        Ok(())
+    }
+
+    /// End the sponsorship of the Xtx, along with the record of the calls it sponsored.
+    pub fn end(xtx_id: XExecSignalId<T>) {
+        ExecutorFeeSponsorships::<T>::remove(xtx_id);
+        let _ = SponsoredBids::<T>::clear_prefix(xtx_id, u32::MAX, None);
+        let _ = SponsoredConfirmations::<T>::clear_prefix(xtx_id, u32::MAX, None);
+    }
+
+    /// Xtx whose requester may sponsor the executor's call, if the call serves it.
+    fn sponsored_xtx(
+        executor: &T::AccountId,
+        call: &<T as frame_system::Config>::RuntimeCall,
+    ) -> Option<XExecSignalId<T>>
+    where
+        <T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+    {
+        let (sfx_id, maybe_bid_amount) = match call.is_sub_type()? {
+            Call::bid_sfx { sfx_id, bid_amount } => (*sfx_id, Some(*bid_amount)),
+            Call::confirm_side_effect { sfx_id, .. } => (*sfx_id, None),
+            _ => return None,
+        };
+        let xtx_id = SFX2XTXLinksMap::<T>::get(sfx_id)?;
+        let xtx = XExecSignals::<T>::get(xtx_id)?;
+        let full_side_effects = FullSideEffects::<T>::get(xtx_id).unwrap_or_default();
+
+        let serves_xtx = match maybe_bid_amount {
+            Some(bid_amount) =>
+                matches!(
+                    xtx.status,
+                    CircuitStatus::PendingBidding | CircuitStatus::InBidding
+                ) && Self::is_bid_sponsorable(
+                    executor,
+                    &xtx.requester,
+                    xtx_id,
+                    sfx_id,
+                    bid_amount,
+                    &full_side_effects,
+                ),
+            // Only the first confirmation of the winning executor on the SFX is sponsored
+            None =>
+                matches!(
+                    xtx.status,
+                    CircuitStatus::Ready
+                        | CircuitStatus::PendingExecution
+                        | CircuitStatus::Finished
+                ) && !SponsoredConfirmations::<T>::contains_key(xtx_id, (sfx_id, executor.clone()))
+                    && full_side_effects
+                        .iter()
+                        .flatten()
+                        .filter(|fsx| fsx.calc_sfx_id::<SystemHashing<T>, T>(xtx_id) == sfx_id)
+                        .any(|fsx| {
+                            fsx.confirmed.is_none()
+                                && fsx
+                                    .best_bid
+                                    .as_ref()
+                                    .map_or(false, |bid| &bid.executor == executor)
+                        }),
+        };
+
+        serves_xtx.then_some(xtx_id)
+    }
+
+    /// Fees are paid ahead of the dispatch, so only the first bid of the executor on the SFX is sponsored,
+    ///     and only if it passes the checks of the bidding and the executor holds the bid deposit.
+    fn is_bid_sponsorable(
+        executor: &T::AccountId,
+        requester: &T::AccountId,
+        xtx_id: XExecSignalId<T>,
+        sfx_id: SideEffectId<T>,
+        bid_amount: BalanceOf<T>,
+        full_side_effects: &[Vec<FullSideEffect<T::AccountId, BlockNumberFor<T>, BalanceOf<T>>>],
+    ) -> bool {
+        if SponsoredBids::<T>::contains_key(xtx_id, (sfx_id, executor.clone())) {
+            return false
+        }
+        let step_fsx = match full_side_effects.iter().find(|step_fsx| {
+            step_fsx
+                .iter()
+                .any(|fsx| fsx.calc_sfx_id::<SystemHashing<T>, T>(xtx_id) == sfx_id)
+        }) {
+            Some(step_fsx) => step_fsx,
+            None => return false,
+        };
+        let is_bidding_paused = step_fsx
+            .iter()
+            .filter(|fsx| fsx.calc_sfx_id::<SystemHashing<T>, T>(xtx_id) == sfx_id)
+            .any(|fsx| T::PauseScopes::is_scope_paused(&PauseScope::Bidding(fsx.input.target)));
+        if is_bidding_paused {
+            return false
+        }
+
+        match Bids::<T>::prepare_bid(step_fsx, bid_amount, executor, requester, sfx_id, xtx_id) {
+            Ok((bid, _current_best_bid)) => {
+                let bid_deposit = bid
+                    .reserved_bond
+                    .unwrap_or_else(Zero::zero)
+                    .saturating_add(bid.insurance);
+                <T as Config>::AccountManager::can_withdraw(
+                    executor,
+                    bid_deposit,
+                    bid.reward_asset_id,
+                )
+            },
+            Err(_) => false,
+        }
+    }
+}
+
+impl<T: Config>
+    ExecutorFeeSponsor<
+        T::AccountId,
+        BalanceOf<T>,
+        T::Hash,
+        <T as frame_system::Config>::RuntimeCall,
+    > for Pallet<T>
+where
+    <T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+    fn sponsor_of(
+        executor: &T::AccountId,
+        call: &<T as frame_system::Config>::RuntimeCall,
+        fee: BalanceOf<T>,
+    ) -> Option<(T::AccountId, T::Hash)> {
+        let xtx_id = FeeSponsorship::<T>::sponsored_xtx(executor, call)?;
+        ExecutorFeeSponsorships::<T>::get(xtx_id)
+            .filter(|(_requester, budget)| *budget >= fee)
+            .map(|(requester, _budget)| (requester, xtx_id))
+    }
+
+    fn charge_sponsor(
+        executor: &T::AccountId,
+        call: &<T as frame_system::Config>::RuntimeCall,
+        xtx_id: T::Hash,
+        fee: BalanceOf<T>,
+    ) {
+        let (requester, budget) = match ExecutorFeeSponsorships::<T>::get(xtx_id) {
+            Some(sponsorship) => sponsorship,
+            None => return,
+        };
+
+        // Spent budget is kept until the Xtx ends, as the refund of the unused weight goes back to it
+        ExecutorFeeSponsorships::<T>::insert(xtx_id, (requester, budget.saturating_sub(fee)));
+        match call.is_sub_type() {
+            Some(Call::bid_sfx { sfx_id, .. }) =>
+                SponsoredBids::<T>::insert(xtx_id, (*sfx_id, executor.clone()), ()),
+            Some(Call::confirm_side_effect { sfx_id, .. }) =>
+                SponsoredConfirmations::<T>::insert(xtx_id, (*sfx_id, executor.clone()), ()),
+            _ => {},
+        }
+    }
+
+    fn refund_sponsor(
+        executor: &T::AccountId,
+        sponsor: &T::AccountId,
+        xtx_id: T::Hash,
+        refund: BalanceOf<T>,
+        actual_fee: BalanceOf<T>,
+    ) {
+        // Sponsorships ended by the sponsored call itself aren't restored by its refund
+        ExecutorFeeSponsorships::<T>::mutate(xtx_id, |sponsorship| {
+            if let Some((_requester, budget)) = sponsorship {
+                *budget = budget.saturating_add(refund);
+            }
+        });
+        Self::deposit_event(Event::ExecutorFeeSponsored(
+            xtx_id,
+            sponsor.clone(),
+            executor.clone(),
+            actual_fee,
+        ));
+    }
+}
@@ -0,0 +1,336 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{
+        machine::test_extra::*,
+        tests::{ALICE, BOB_RELAYER},
+    };
+    use circuit_mock_runtime::{
+        AccountId, Balance, Balances, BlockNumber, Circuit, ExtBuilder, Runtime, RuntimeCall,
+        RuntimeEvent, RuntimeOrigin, System, TransactionPayment,
+    };
+    use circuit_runtime_pallets::{
+        pallet_circuit::{
+            machine::Machine, Call as CircuitCall, Error, Event, FullSideEffects,
+            SponsoredConfirmations,
+        },
+        pallet_transaction_payment::ChargeTransactionPayment,
+    };
+    use frame_support::{
+        assert_noop, assert_ok,
+        dispatch::{DispatchInfo, GetDispatchInfo, PostDispatchInfo},
+        traits::Currency,
+        weights::Weight,
+    };
+    use sp_core::H256;
+    use sp_runtime::{traits::SignedExtension, AccountId32};
+    use t3rn_primitives::{account_manager::ExecutorFeeSponsor, circuit::Cause};
+    use t3rn_types::sfx::ConfirmedSideEffect;
+
+    const CHARLIE: AccountId32 = AccountId32::new([3u8; 32]);
+    const UNFUNDED: AccountId32 = AccountId32::new([7u8; 32]);
+    const FUNDS: Balance = 1_000_000_000_000_000;
+    const LEN: usize = 100;
+
+    fn ext() -> sp_io::TestExternalities {
+        ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_default_xdns_records()
+            .build()
+    }
+
+    fn bid_call(sfx_id: H256) -> RuntimeCall {
+        bid_call_of(sfx_id, 2)
+    }
+
+    fn bid_call_of(sfx_id: H256, bid_amount: Balance) -> RuntimeCall {
+        RuntimeCall::Circuit(CircuitCall::bid_sfx { sfx_id, bid_amount })
+    }
+
+    fn sponsor_of(
+        executor: &AccountId,
+        call: &RuntimeCall,
+        fee: Balance,
+    ) -> Option<(AccountId, H256)> {
+        <Circuit as ExecutorFeeSponsor<AccountId, Balance, H256, RuntimeCall>>::sponsor_of(
+            executor, call, fee,
+        )
+    }
+
+    fn confirmation(
+        executioner: AccountId,
+    ) -> ConfirmedSideEffect<AccountId, BlockNumber, Balance> {
+        ConfirmedSideEffect {
+            err: None,
+            output: None,
+            inclusion_data: vec![1, 2, 3],
+            executioner,
+            received_at: 2,
+            cost: None,
+        }
+    }
+
+    fn confirm_call(sfx_id: H256, executioner: AccountId) -> RuntimeCall {
+        RuntimeCall::Circuit(CircuitCall::confirm_side_effect {
+            sfx_id,
+            confirmation: confirmation(executioner),
+        })
+    }
+
+    fn fee_of(call: &RuntimeCall) -> Balance {
+        TransactionPayment::compute_fee(LEN as u32, &call_info(call), 0)
+    }
+
+    fn call_info(call: &RuntimeCall) -> DispatchInfo {
+        call.get_dispatch_info()
+    }
+
+    /// Charge the fee of the call and settle it at the actual weight, returning the actual fee.
+    fn charge(executor: &AccountId, call: &RuntimeCall, actual_weight: Option<Weight>) -> Balance {
+        let info = call_info(call);
+        let post_info = PostDispatchInfo {
+            actual_weight,
+            pays_fee: Default::default(),
+        };
+        let pre = ChargeTransactionPayment::<Runtime>::from(0)
+            .pre_dispatch(executor, call, &info, LEN)
+            .expect("transaction fee should be paid");
+        assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+            Some(pre),
+            &info,
+            &post_info,
+            LEN,
+            &Ok(())
+        ));
+        TransactionPayment::compute_actual_fee(LEN as u32, &info, &post_info, 0)
+    }
+
+    #[test]
+    fn requester_sponsors_executor_bids_within_budget_at_the_actual_fee() {
+        ext().execute_with(|| {
+            stage_single();
+            let _ = Balances::deposit_creating(&ALICE, FUNDS);
+            let _ = Balances::deposit_creating(&BOB_RELAYER, FUNDS);
+            let xtx_id = setup_single_sfx_xtx_and_force_set_status(None);
+            let call = bid_call(get_mocked_transfer_sfx_id(xtx_id));
+            let fee = fee_of(&call);
+
+            assert_noop!(
+                Circuit::sponsor_executor_fees(RuntimeOrigin::signed(BOB_RELAYER), xtx_id, fee),
+                Error::<Runtime>::FeeSponsorshipNotOwnedByRequester
+            );
+            assert_noop!(
+                Circuit::sponsor_executor_fees(RuntimeOrigin::signed(ALICE), H256::zero(), fee),
+                Error::<Runtime>::XtxNotFound
+            );
+            assert_ok!(Circuit::sponsor_executor_fees(
+                RuntimeOrigin::signed(ALICE),
+                xtx_id,
+                2 * fee
+            ));
+            assert_eq!(
+                Circuit::get_executor_fee_sponsorship(xtx_id),
+                Some((ALICE, 2 * fee))
+            );
+
+            let requester_balance = Balances::free_balance(&ALICE);
+            let executor_balance = Balances::free_balance(&BOB_RELAYER);
+            let actual_fee = charge(&BOB_RELAYER, &call, Some(Weight::zero()));
+
+            // Refund of the unused weight goes back to the budget
+            assert_eq!(
+                Balances::free_balance(&ALICE),
+                requester_balance - actual_fee
+            );
+            assert_eq!(Balances::free_balance(&BOB_RELAYER), executor_balance);
+            assert_eq!(
+                Circuit::get_executor_fee_sponsorship(xtx_id),
+                Some((ALICE, 2 * fee - actual_fee))
+            );
+            System::assert_has_event(RuntimeEvent::Circuit(Event::ExecutorFeeSponsored(
+                xtx_id,
+                ALICE,
+                BOB_RELAYER,
+                actual_fee,
+            )));
+
+            // Executors are sponsored a single bid per SFX
+            assert_eq!(sponsor_of(&BOB_RELAYER, &call, fee), None);
+            charge(&BOB_RELAYER, &call, None);
+            assert_eq!(
+                Balances::free_balance(&ALICE),
+                requester_balance - actual_fee
+            );
+            assert_eq!(Balances::free_balance(&BOB_RELAYER), executor_balance - fee);
+        });
+    }
+
+    #[test]
+    fn bids_failing_the_bidding_checks_are_not_sponsored() {
+        ext().execute_with(|| {
+            stage_single();
+            let _ = Balances::deposit_creating(&ALICE, FUNDS);
+            let _ = Balances::deposit_creating(&BOB_RELAYER, FUNDS);
+            let xtx_id = setup_single_sfx_xtx_and_force_set_status(None);
+            let sfx_id = get_mocked_transfer_sfx_id(xtx_id);
+            assert_ok!(Circuit::sponsor_executor_fees(
+                RuntimeOrigin::signed(ALICE),
+                xtx_id,
+                FUNDS
+            ));
+
+            assert_eq!(
+                sponsor_of(&BOB_RELAYER, &bid_call(sfx_id), 1),
+                Some((ALICE, xtx_id))
+            );
+            // Bid above the max reward of the SFX
+            assert_eq!(sponsor_of(&BOB_RELAYER, &bid_call_of(sfx_id, 3), 1), None);
+            // Executor without the balance for the bid deposit
+            assert_eq!(sponsor_of(&UNFUNDED, &bid_call(sfx_id), 1), None);
+        });
+    }
+
+    #[test]
+    fn only_calls_serving_the_xtx_are_sponsored() {
+        ext().execute_with(|| {
+            stage_single();
+            let xtx_id = setup_single_sfx_xtx_and_post_bid_and_set_to_ready(None);
+            let sfx_id = get_mocked_transfer_sfx_id(xtx_id);
+            assert_ok!(Circuit::sponsor_executor_fees(
+                RuntimeOrigin::signed(ALICE),
+                xtx_id,
+                100
+            ));
+
+            // Confirmations of the winning executor within the budget
+            assert_eq!(
+                sponsor_of(&BOB_RELAYER, &confirm_call(sfx_id, BOB_RELAYER), 100),
+                Some((ALICE, xtx_id))
+            );
+            assert_eq!(
+                sponsor_of(&BOB_RELAYER, &confirm_call(sfx_id, BOB_RELAYER), 101),
+                None
+            );
+            assert_eq!(
+                sponsor_of(&CHARLIE, &confirm_call(sfx_id, CHARLIE), 1),
+                None
+            );
+            // Bidding on the Xtx is over
+            assert_eq!(sponsor_of(&CHARLIE, &bid_call(sfx_id), 1), None);
+
+            // Ending the sponsorship
+            assert_ok!(Circuit::sponsor_executor_fees(
+                RuntimeOrigin::signed(ALICE),
+                xtx_id,
+                0
+            ));
+            assert_eq!(
+                sponsor_of(&BOB_RELAYER, &confirm_call(sfx_id, BOB_RELAYER), 1),
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn only_first_confirmations_of_the_winning_executor_are_sponsored() {
+        ext().execute_with(|| {
+            stage_single();
+            let _ = Balances::deposit_creating(&ALICE, FUNDS);
+            let _ = Balances::deposit_creating(&BOB_RELAYER, FUNDS);
+            let xtx_id = setup_single_sfx_xtx_and_post_bid_and_set_to_ready(None);
+            let sfx_id = get_mocked_transfer_sfx_id(xtx_id);
+            let call = confirm_call(sfx_id, BOB_RELAYER);
+            let fee = fee_of(&call);
+            assert_ok!(Circuit::sponsor_executor_fees(
+                RuntimeOrigin::signed(ALICE),
+                xtx_id,
+                FUNDS
+            ));
+
+            let requester_balance = Balances::free_balance(&ALICE);
+            let executor_balance = Balances::free_balance(&BOB_RELAYER);
+            let actual_fee = charge(&BOB_RELAYER, &call, Some(Weight::zero()));
+            assert_eq!(
+                Balances::free_balance(&ALICE),
+                requester_balance - actual_fee
+            );
+            assert!(SponsoredConfirmations::<Runtime>::contains_key(
+                xtx_id,
+                (sfx_id, BOB_RELAYER)
+            ));
+
+            // Repeated confirmations are paid by the executor
+            assert_eq!(sponsor_of(&BOB_RELAYER, &call, fee), None);
+            charge(&BOB_RELAYER, &call, None);
+            assert_eq!(
+                Balances::free_balance(&ALICE),
+                requester_balance - actual_fee
+            );
+            assert_eq!(Balances::free_balance(&BOB_RELAYER), executor_balance - fee);
+        });
+    }
+
+    #[test]
+    fn confirmations_of_confirmed_sfx_are_not_sponsored() {
+        ext().execute_with(|| {
+            stage_single();
+            let xtx_id = setup_single_sfx_xtx_and_post_bid_and_set_to_ready(None);
+            let sfx_id = get_mocked_transfer_sfx_id(xtx_id);
+            assert_ok!(Circuit::sponsor_executor_fees(
+                RuntimeOrigin::signed(ALICE),
+                xtx_id,
+                100
+            ));
+
+            FullSideEffects::<Runtime>::mutate(xtx_id, |maybe_fsx| {
+                if let Some(fsx) = maybe_fsx {
+                    fsx[0][0].confirmed = Some(confirmation(BOB_RELAYER));
+                }
+            });
+            assert_eq!(
+                sponsor_of(&BOB_RELAYER, &confirm_call(sfx_id, BOB_RELAYER), 1),
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn sponsorships_end_with_the_xtx() {
+        ext().execute_with(|| {
+            stage_single();
+            let _ = Balances::deposit_creating(&ALICE, FUNDS);
+            let xtx_id = setup_single_sfx_xtx_and_post_bid_and_set_to_ready(None);
+            let sfx_id = get_mocked_transfer_sfx_id(xtx_id);
+            assert_ok!(Circuit::sponsor_executor_fees(
+                RuntimeOrigin::signed(ALICE),
+                xtx_id,
+                FUNDS
+            ));
+            charge(
+                &BOB_RELAYER,
+                &confirm_call(sfx_id, BOB_RELAYER),
+                Some(Weight::zero()),
+            );
+
+            assert!(Machine::<Runtime>::revert(
+                xtx_id,
+                Cause::Timeout,
+                infallible_no_post_updates,
+            ));
+            assert_eq!(Circuit::get_executor_fee_sponsorship(xtx_id), None);
+            assert_eq!(
+                SponsoredConfirmations::<Runtime>::iter_prefix(xtx_id).count(),
+                0
+            );
+
+            // Refunds of calls ending the Xtx don't restore its sponsorship
+            <Circuit as ExecutorFeeSponsor<AccountId, Balance, H256, RuntimeCall>>::refund_sponsor(
+                &BOB_RELAYER,
+                &ALICE,
+                xtx_id,
+                1,
+                1,
+            );
+            assert_eq!(Circuit::get_executor_fee_sponsorship(xtx_id), None);
+        });
+    }
+}
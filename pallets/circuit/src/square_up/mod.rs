@@ -1,12 +1,15 @@
 use crate::*;
 use frame_support::{ensure, traits::ExistenceRequirement};
-use sp_runtime::DispatchResult;
+use sp_runtime::{traits::Saturating, DispatchResult};
 
 #[cfg(test)]
 pub mod test;
 
 use sp_std::marker::PhantomData;
-use t3rn_primitives::{account_manager::RequestCharge, TreasuryAccount, TreasuryAccountProvider};
+use t3rn_primitives::{
+    account_manager::RequestCharge, contracts_registry::Royalties, TreasuryAccount,
+    TreasuryAccountProvider,
+};
 
 pub struct SquareUp<T: Config> {
     _phantom: PhantomData<T>,
@@ -261,16 +264,24 @@ impl<T: Config> SquareUp<T> {
                     if outcome == Outcome::Slash {
                         step_outcome = Outcome::Revert;
                     }
-                    if !<T as Config>::AccountManager::finalize_infallible(
-                        bid.generate_id::<SystemHashing<T>, T>(sfx_id),
-                        outcome.clone(),
-                    ) {
+                    let bid_id = bid.generate_id::<SystemHashing<T>, T>(sfx_id);
+                    // Skip bonds already released by the executor with a correctness proof
+                    if outcome == Outcome::Revert
+                        && <T as Config>::AccountManager::get_charge_or_fail(bid_id).is_err()
+                    {
+                        continue
+                    }
+                    if !<T as Config>::AccountManager::finalize_infallible(bid_id, outcome.clone())
+                    {
                         log::error!(
                             "squareUp::finalize: expect finalize_infallible to succeed for bid_id: {:?}",
-                            bid.generate_id::<SystemHashing<T>, T>(sfx_id)
+                            bid_id
                         );
                         finalized = false;
                     }
+                    if outcome == Outcome::Slash && fsx.security_lvl == SecurityLvl::Optimistic {
+                        Self::top_up_slashed_insurance(&local_ctx.xtx.requester, fsx, bid);
+                    }
                 },
                 None => {
                     log::error!(
@@ -281,6 +292,20 @@ impl<T: Config> SquareUp<T> {
                 },
             }
         }
+        // Take the royalties of the registry contract which requested the committed Xtx out of
+        //  the SFX rewards, before they are settled to the executors
+        if step_outcome == Outcome::Commit {
+            for fsx in Machine::<T>::read_current_step_fsx(local_ctx).iter() {
+                if let Some(bid) = &fsx.best_bid {
+                    T::Royalties::accrue_royalty(
+                        &local_ctx.xtx.requester,
+                        fsx.calc_sfx_id::<SystemHashing<T>, T>(local_ctx.xtx_id),
+                        bid.amount,
+                        fsx.input.reward_asset_id,
+                    );
+                }
+            }
+        }
         // Finalize XTX for requester - charge all deposits or return all max_reward deposits back to requester.
         Machine::<T>::read_current_step_fsx(local_ctx)
             .iter()
@@ -310,9 +335,39 @@ impl<T: Config> SquareUp<T> {
                     }
                 }
             });
+
         finalized
     }
 
+    /// Top up the requester out of the insurance pool when the slashed insurance of the executor
+    ///     falls short of the reward the requester locked for the optimistic SFX, both in the reward asset.
+    /// The top-up never exceeds the slashed insurance, so an executor colluding with the requester
+    ///     burns at least as much as the pool pays out.
+    fn top_up_slashed_insurance(
+        requester: &T::AccountId,
+        fsx: &FullSideEffect<T::AccountId, BlockNumberFor<T>, BalanceOf<T>>,
+        bid: &SFXBid<T::AccountId, BalanceOf<T>, u32>,
+    ) -> BalanceOf<T> {
+        // Both the insurance and the reward have to be held in the same asset to be compared
+        if bid.reward_asset_id != fsx.input.reward_asset_id {
+            return Zero::zero()
+        }
+        let shortfall = fsx
+            .input
+            .max_reward
+            .saturating_sub(bid.insurance)
+            .min(bid.insurance);
+        if shortfall.is_zero() {
+            return Zero::zero()
+        }
+        <T as Config>::AccountManager::compensate_from_insurance_pool(
+            requester,
+            fsx.input.target,
+            shortfall,
+            fsx.input.reward_asset_id,
+        )
+    }
+
     /// Finalize Xtx after successful run - reward Escrow executors.
     pub fn commit(_local_ctx: &LocalXtxCtx<T, BalanceOf<T>>) {}
 }
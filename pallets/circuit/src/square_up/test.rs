@@ -7,11 +7,12 @@ pub mod test {
     };
     use circuit_mock_runtime::{
         AccountId, AccountManager, AssetId, Balance, Balances, BlockNumber, ExtBuilder, Hash,
-        Runtime, System,
+        Runtime, RuntimeOrigin, System,
     };
     use circuit_runtime_pallets::pallet_circuit::{
         machine::Machine, square_up::SquareUp, state::LocalXtxCtx,
     };
+    use codec::Encode;
     use frame_support::{assert_err, assert_ok, traits::Currency};
     use sp_core::H256;
     use sp_runtime::{DispatchError, ModuleError};
@@ -21,7 +22,7 @@ pub mod test {
         },
         claimable::{BenefitSource, CircuitRole},
     };
-    use t3rn_types::sfx::SecurityLvl;
+    use t3rn_types::sfx::{SecurityLvl, SideEffect};
 
     use crate::tests::ESCROW_ACCOUNT;
     use sp_runtime::DispatchResult;
@@ -36,19 +37,25 @@ pub mod test {
         Hash,
         SFXBid<AccountId, Balance, AssetId>,
         Hash,
+    ) {
+        stage_single_sfx_xtx_of(get_mocked_transfer_sfx())
+    }
+
+    fn stage_single_sfx_xtx_of(
+        sfx: SideEffect<AccountId, Balance>,
+    ) -> (
+        LocalXtxCtx<Runtime, Balance>,
+        Hash,
+        SFXBid<AccountId, Balance, AssetId>,
+        Hash,
     ) {
         System::set_block_number(1);
 
         let _ = Balances::deposit_creating(&REQUESTER, INITIAL_BALANCE);
         let _ = Balances::deposit_creating(&EXECUTOR, INITIAL_BALANCE);
 
-        let local_ctx = Machine::<Runtime>::setup(
-            &[get_mocked_transfer_sfx()],
-            &REQUESTER,
-            None,
-            &SecurityLvl::Optimistic,
-        )
-        .unwrap();
+        let local_ctx =
+            Machine::<Runtime>::setup(&[sfx], &REQUESTER, None, &SecurityLvl::Optimistic).unwrap();
 
         let bid = SFXBid {
             amount: 2,
@@ -418,4 +425,60 @@ pub mod test {
                 assert_eq!(Balances::free_balance(&ESCROW_ACCOUNT), bid.insurance);
             });
     }
+
+    #[test]
+    fn square_up_finalize_tops_up_requester_from_insurance_pool_when_slashed_insurance_falls_short()
+    {
+        ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_default_xdns_records()
+            .build()
+            .execute_with(|| {
+                let mut transfer_sfx = get_mocked_transfer_sfx();
+                // Transferred amount is in the transferred asset and doesn't count towards the compensation
+                transfer_sfx.encoded_args[1] = 1000u128.encode();
+                transfer_sfx.max_reward = 8;
+                let (mut local_ctx, _sfx_id, mut bid, _bid_id) =
+                    stage_single_sfx_xtx_of(transfer_sfx);
+                bid.insurance = 3;
+
+                let liquidity_provider = AccountId::new([9u8; 32]);
+                let _ = Balances::deposit_creating(&liquidity_provider, INITIAL_BALANCE);
+                assert_ok!(AccountManager::deposit_insurance(
+                    RuntimeOrigin::signed(liquidity_provider),
+                    None,
+                    5
+                ));
+                assert_ok!(AccountManager::set_gateway_insurance_cap(
+                    RuntimeOrigin::root(),
+                    local_ctx.full_side_effects[0][0].input.target,
+                    Some(10)
+                ));
+                assert_ok!(AccountManager::set_asset_insurance_cap(
+                    RuntimeOrigin::root(),
+                    None,
+                    Some(10)
+                ));
+
+                assert_ok!(request_and_bid_single_sfx_xtx(&mut local_ctx, &bid));
+                assert!(SquareUp::<Runtime>::bind_bidders(&mut local_ctx));
+                assert!(SquareUp::<Runtime>::finalize(&local_ctx));
+
+                // Shortfall of 5 between the locked max reward of 8 and the slashed insurance of 3
+                //  is topped up by no more than the slashed insurance
+                assert_eq!(Balances::free_balance(&REQUESTER), INITIAL_BALANCE + 3);
+                assert_eq!(
+                    Balances::free_balance(&EXECUTOR),
+                    INITIAL_BALANCE - bid.insurance
+                );
+                assert_eq!(
+                    AccountManager::insurance_pools(None::<AssetId>).liquidity,
+                    2
+                );
+                assert_eq!(
+                    Balances::free_balance(&AccountManager::insurance_pool_account(&None)),
+                    2
+                );
+            });
+    }
 }
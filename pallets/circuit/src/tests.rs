@@ -2492,3 +2492,71 @@ fn test_storage_migration_v130_to_v140_for_fsx_map_with_updated_encoded_action_f
             }
         });
 }
+
+#[test]
+fn bids_on_targets_with_bidding_paused_are_rejected() {
+    use crate::machine::test_extra::*;
+    use t3rn_primitives::maintenance::{PauseScope, PauseScopesMock};
+
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            stage_single();
+            let xtx_id = setup_single_sfx_xtx_and_force_set_status(None);
+            let sfx_id = get_mocked_transfer_sfx_id(xtx_id);
+            let target = get_mocked_transfer_sfx().target;
+
+            PauseScopesMock::pause(PauseScope::Bidding(target));
+            assert_err!(
+                Circuit::bid_sfx(Origin::signed(BOB_RELAYER), sfx_id, 2),
+                circuit_error::<Runtime>::BiddingPausedOnTarget
+            );
+
+            // Pausing submissions leaves the bidding on the target open
+            PauseScopesMock::resume(&PauseScope::Bidding(target));
+            PauseScopesMock::pause(PauseScope::Submission(target));
+            assert_ok!(Circuit::bid_sfx(Origin::signed(BOB_RELAYER), sfx_id, 2));
+        });
+}
+
+#[test]
+fn side_effects_on_targets_with_submission_paused_are_rejected() {
+    use crate::machine::test_extra::get_mocked_transfer_sfx;
+    use t3rn_primitives::maintenance::{PauseScope, PauseScopesMock};
+
+    let side_effect = get_mocked_transfer_sfx();
+
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2); // Alice should have at least: fee (1) + insurance reward (2)(for VariantA)
+
+            advance_to_block(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            PauseScopesMock::pause(PauseScope::Submission(side_effect.target));
+            assert_err!(
+                Circuit::on_extrinsic_trigger(
+                    Origin::signed(ALICE),
+                    vec![side_effect.clone()],
+                    SpeedMode::Finalized,
+                    SecurityLvl::Optimistic,
+                ),
+                circuit_error::<Runtime>::SubmissionPausedOnTarget
+            );
+
+            // Pausing bids leaves the submissions to the target open
+            PauseScopesMock::resume(&PauseScope::Submission(side_effect.target));
+            PauseScopesMock::pause(PauseScope::Bidding(side_effect.target));
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                Origin::signed(ALICE),
+                vec![side_effect],
+                SpeedMode::Finalized,
+                SecurityLvl::Optimistic,
+            ));
+        });
+}
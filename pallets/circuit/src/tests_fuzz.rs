@@ -0,0 +1,400 @@
+//! Property-based state-machine fuzzing of Circuit.
+//!
+//! Random sequences of requester, executor and clock actions are dispatched against the mock runtime.
+//! After every step the harness checks that:
+//!     - native funds are conserved: whatever left the total issuance is accounted for either as a
+//!       pending AccountManager charge or as a settlement awaiting its claim,
+//!     - SFX2XTXLinksMap holds no orphans: every link points at a live Xtx and every SFX of a live
+//!       Xtx is linked,
+//!     - every live Xtx is tracked by a timeout queue and none of them overstays its emergency timeout.
+//! Once the actions run out, the clock is pushed past every pending timeout to ensure each Xtx ends up
+//! in a terminal state or parked in the DLQ.
+use circuit_mock_runtime::{
+    test_utils::*, RuntimeCall as Call, RuntimeEvent as Event, RuntimeOrigin as Origin, *,
+};
+use circuit_runtime_pallets::{
+    pallet_account_manager::{PendingCharges, SettlementsPerRound},
+    pallet_balances,
+    pallet_circuit::{
+        self, FinalizedXtx, FullSideEffects, PendingXtxTimeoutsMap, SFX2XTXLinksMap, SystemHashing,
+        XExecSignals, DLQ,
+    },
+};
+use codec::Encode;
+use frame_support::traits::{Currency, OnInitialize};
+use pallet_grandpa_finality_verifier::mock::brute_seed_block_1;
+use proptest::{prelude::*, sample::Index};
+use sp_core::H256;
+use sp_runtime::{traits::Dispatchable, AccountId32};
+use t3rn_primitives::SpeedMode;
+use t3rn_types::sfx::{ConfirmedSideEffect, SecurityLvl};
+
+const REQUESTERS: [AccountId32; 2] = [AccountId32::new([1u8; 32]), AccountId32::new([2u8; 32])];
+const EXECUTORS: [AccountId32; 3] = [
+    AccountId32::new([10u8; 32]),
+    AccountId32::new([11u8; 32]),
+    AccountId32::new([12u8; 32]),
+];
+const TARGETS: [[u8; 4]; 2] = [[0, 0, 0, 0], [3, 3, 3, 3]];
+
+const INITIAL_BALANCE: Balance = 1_000_000;
+const MAX_REWARD: Balance = 1_000;
+const MAX_INSURANCE: Balance = 100;
+const MAX_BLOCKS_PER_ADVANCE: BlockNumber = 60;
+const MAX_ACTIONS: usize = 40;
+
+const REVERT_INTERVAL: BlockNumber = crate::EMERGENCY_REVERT_INTERVAL;
+
+#[derive(Clone, Debug)]
+enum Action {
+    Trigger {
+        requester: usize,
+        target: usize,
+        max_reward: Balance,
+        insurance: Balance,
+        escrow: bool,
+        fast: bool,
+    },
+    Bid {
+        xtx: Index,
+        sfx: Index,
+        executor: usize,
+        amount: Balance,
+    },
+    Confirm {
+        xtx: Index,
+        sfx: Index,
+        executor: usize,
+    },
+    Escrow {
+        xtx: Index,
+        sfx: Index,
+        executor: usize,
+    },
+    Cancel {
+        xtx: Index,
+        requester: usize,
+    },
+    AdvanceBlocks(BlockNumber),
+    TriggerDlq {
+        caller: usize,
+    },
+}
+
+struct TrackedXtx {
+    id: H256,
+    sfx_ids: Vec<H256>,
+}
+
+#[derive(Default)]
+struct Model {
+    xtx: Vec<TrackedXtx>,
+}
+
+impl Model {
+    fn pick_sfx(&self, xtx: &Index, sfx: &Index) -> Option<H256> {
+        if self.xtx.is_empty() {
+            return None
+        }
+        let tracked = &self.xtx[xtx.index(self.xtx.len())];
+        if tracked.sfx_ids.is_empty() {
+            return None
+        }
+        Some(tracked.sfx_ids[sfx.index(tracked.sfx_ids.len())])
+    }
+
+    fn pick_xtx(&self, xtx: &Index) -> Option<H256> {
+        if self.xtx.is_empty() {
+            return None
+        }
+        Some(self.xtx[xtx.index(self.xtx.len())].id)
+    }
+}
+
+fn action_strategy() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        3 => (
+            0..REQUESTERS.len(),
+            0..TARGETS.len(),
+            1..=MAX_REWARD,
+            0..=MAX_INSURANCE,
+            any::<bool>(),
+            any::<bool>(),
+        )
+            .prop_map(|(requester, target, max_reward, insurance, escrow, fast)| {
+                Action::Trigger { requester, target, max_reward, insurance, escrow, fast }
+            }),
+        3 => (any::<Index>(), any::<Index>(), 0..EXECUTORS.len(), 1..=MAX_REWARD)
+            .prop_map(|(xtx, sfx, executor, amount)| Action::Bid { xtx, sfx, executor, amount }),
+        1 => (any::<Index>(), any::<Index>(), 0..EXECUTORS.len())
+            .prop_map(|(xtx, sfx, executor)| Action::Confirm { xtx, sfx, executor }),
+        1 => (any::<Index>(), any::<Index>(), 0..EXECUTORS.len())
+            .prop_map(|(xtx, sfx, executor)| Action::Escrow { xtx, sfx, executor }),
+        1 => (any::<Index>(), 0..REQUESTERS.len())
+            .prop_map(|(xtx, requester)| Action::Cancel { xtx, requester }),
+        2 => (1..=MAX_BLOCKS_PER_ADVANCE).prop_map(Action::AdvanceBlocks),
+        1 => (0..EXECUTORS.len()).prop_map(|caller| Action::TriggerDlq { caller }),
+    ]
+}
+
+fn dispatch(origin: &AccountId32, call: pallet_circuit::Call<Runtime>) {
+    // Failing dispatches are expected - the harness only cares whether the invariants still hold
+    let _ = Call::Circuit(call).dispatch(Origin::signed(origin.clone()));
+}
+
+fn advance_one_block() {
+    let next = System::block_number() + 1;
+    System::set_block_number(next);
+    <Clock as OnInitialize<BlockNumber>>::on_initialize(next);
+}
+
+fn apply(model: &mut Model, action: &Action) {
+    System::reset_events();
+
+    match action.clone() {
+        Action::Trigger {
+            requester,
+            target,
+            max_reward,
+            insurance,
+            escrow,
+            fast,
+        } => {
+            let mut sfx = produce_and_validate_side_effect(
+                *b"tran",
+                insurance,
+                max_reward,
+                t3rn_abi::Codec::Scale,
+                ArgVariant::A,
+            );
+            sfx.target = TARGETS[target];
+            let security_lvl = if escrow {
+                SecurityLvl::Escrow
+            } else {
+                SecurityLvl::Optimistic
+            };
+            let speed_mode = if fast {
+                SpeedMode::Fast
+            } else {
+                SpeedMode::Finalized
+            };
+
+            dispatch(
+                &REQUESTERS[requester],
+                pallet_circuit::Call::on_extrinsic_trigger {
+                    side_effects: vec![sfx],
+                    speed_mode,
+                    preferred_security_level: security_lvl,
+                },
+            );
+
+            for record in System::events() {
+                if let Event::Circuit(pallet_circuit::Event::<Runtime>::NewSideEffectsAvailable(
+                    _,
+                    xtx_id,
+                    _,
+                    sfx_ids,
+                )) = record.event
+                {
+                    model.xtx.push(TrackedXtx {
+                        id: xtx_id,
+                        sfx_ids,
+                    });
+                }
+            }
+        },
+        Action::Bid {
+            xtx,
+            sfx,
+            executor,
+            amount,
+        } =>
+            if let Some(sfx_id) = model.pick_sfx(&xtx, &sfx) {
+                dispatch(
+                    &EXECUTORS[executor],
+                    pallet_circuit::Call::bid_sfx {
+                        sfx_id,
+                        bid_amount: amount,
+                    },
+                );
+            },
+        Action::Confirm { xtx, sfx, executor } =>
+            if let Some(sfx_id) = model.pick_sfx(&xtx, &sfx) {
+                // Transfer event matching the arguments of the triggered SFX, so that confirmations
+                //  of the winning executor go through and drive the Xtx towards commit
+                let mut inclusion_data = pallet_balances::Event::<Runtime>::Transfer {
+                    from: EXECUTORS[executor].clone(),
+                    to: AccountId32::new([9u8; 32]),
+                    amount: 1,
+                }
+                .encode();
+                // append an extra pallet event index byte as the second byte
+                inclusion_data.insert(1, 4u8);
+                let confirmation = ConfirmedSideEffect::<AccountId32, BlockNumber, Balance> {
+                    err: None,
+                    output: None,
+                    inclusion_data,
+                    executioner: EXECUTORS[executor].clone(),
+                    received_at: System::block_number(),
+                    cost: None,
+                };
+                dispatch(
+                    &EXECUTORS[executor],
+                    pallet_circuit::Call::confirm_side_effect {
+                        sfx_id,
+                        confirmation,
+                    },
+                );
+            },
+        Action::Escrow { xtx, sfx, executor } =>
+            if let Some(sfx_id) = model.pick_sfx(&xtx, &sfx) {
+                dispatch(
+                    &EXECUTORS[executor],
+                    pallet_circuit::Call::escrow { sfx_id },
+                );
+            },
+        Action::Cancel { xtx, requester } =>
+            if let Some(xtx_id) = model.pick_xtx(&xtx) {
+                dispatch(
+                    &REQUESTERS[requester],
+                    pallet_circuit::Call::cancel_xtx { xtx_id },
+                );
+            },
+        Action::AdvanceBlocks(blocks) =>
+            for _ in 0..blocks {
+                advance_one_block();
+                check_invariants();
+            },
+        Action::TriggerDlq { caller } => {
+            dispatch(&EXECUTORS[caller], pallet_circuit::Call::trigger_dlq {});
+        },
+    }
+}
+
+fn native_funds_in_flight() -> Balance {
+    let charged = PendingCharges::<Runtime>::iter_values()
+        .filter(|charge| charge.maybe_asset_id.is_none())
+        .map(|charge| charge.offered_reward.saturating_add(charge.charge_fee))
+        .sum::<Balance>();
+
+    let settled = SettlementsPerRound::<Runtime>::iter_values()
+        .filter(|settlement| settlement.maybe_asset_id.is_none())
+        .map(|settlement| settlement.settlement_amount)
+        .sum::<Balance>();
+
+    charged.saturating_add(settled)
+}
+
+fn assert_no_orphan_links() {
+    for (sfx_id, xtx_id) in SFX2XTXLinksMap::<Runtime>::iter() {
+        assert!(
+            XExecSignals::<Runtime>::contains_key(xtx_id),
+            "SFX {sfx_id:?} still links to Xtx {xtx_id:?}, which was already dropped"
+        );
+    }
+
+    for (xtx_id, steps) in FullSideEffects::<Runtime>::iter() {
+        for fsx in steps.iter().flatten() {
+            let sfx_id = fsx.calc_sfx_id::<SystemHashing<Runtime>, Runtime>(xtx_id);
+            assert_eq!(
+                SFX2XTXLinksMap::<Runtime>::get(sfx_id),
+                Some(xtx_id),
+                "SFX {sfx_id:?} of live Xtx {xtx_id:?} is missing its link"
+            );
+        }
+    }
+}
+
+fn assert_timeouts_progress() {
+    let now = System::block_number();
+
+    for (xtx_id, xtx) in XExecSignals::<Runtime>::iter() {
+        let tracked = PendingXtxTimeoutsMap::<Runtime>::contains_key(xtx_id)
+            || DLQ::<Runtime>::contains_key(xtx_id)
+            || FinalizedXtx::<Runtime>::contains_key(xtx_id);
+        assert!(
+            tracked,
+            "Xtx {xtx_id:?} at {:?} is not tracked by any timeout queue",
+            xtx.status
+        );
+    }
+
+    for (xtx_id, timeout) in PendingXtxTimeoutsMap::<Runtime>::iter() {
+        assert!(
+            now < timeout.emergency_timeout_here + REVERT_INTERVAL,
+            "Xtx {xtx_id:?} overstayed its emergency timeout at {} (now {now})",
+            timeout.emergency_timeout_here
+        );
+    }
+}
+
+fn check_invariants() {
+    assert_no_orphan_links();
+    assert_timeouts_progress();
+}
+
+fn run(actions: Vec<Action>) {
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            for account in REQUESTERS.iter().chain(EXECUTORS.iter()) {
+                let _ = Balances::deposit_creating(account, INITIAL_BALANCE);
+            }
+
+            System::set_block_number(1);
+            <Clock as OnInitialize<BlockNumber>>::on_initialize(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            let initial_issuance = Balances::total_issuance();
+            let mut model = Model::default();
+
+            for action in actions.iter() {
+                apply(&mut model, action);
+
+                assert_eq!(
+                    Balances::total_issuance().saturating_add(native_funds_in_flight()),
+                    initial_issuance,
+                    "native funds not conserved after {action:?}"
+                );
+                check_invariants();
+            }
+
+            // Drain: push the clock past every pending timeout, including the revert interval
+            let last_timeout = PendingXtxTimeoutsMap::<Runtime>::iter_values()
+                .map(|timeout| timeout.emergency_timeout_here)
+                .max()
+                .unwrap_or_default();
+            while System::block_number() < last_timeout + REVERT_INTERVAL {
+                advance_one_block();
+                check_invariants();
+            }
+
+            assert_eq!(
+                Balances::total_issuance().saturating_add(native_funds_in_flight()),
+                initial_issuance,
+                "native funds not conserved after draining timeouts"
+            );
+
+            for tracked in model.xtx.iter() {
+                assert!(
+                    !XExecSignals::<Runtime>::contains_key(tracked.id)
+                        || DLQ::<Runtime>::contains_key(tracked.id),
+                    "Xtx {:?} neither reached a terminal state nor was parked in the DLQ",
+                    tracked.id
+                );
+            }
+        });
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig { cases: 64, ..ProptestConfig::default() })]
+
+    #[test]
+    fn circuit_keeps_invariants_under_random_action_sequences(
+        actions in prop::collection::vec(action_strategy(), 1..MAX_ACTIONS)
+    ) {
+        run(actions);
+    }
+}
@@ -35,6 +35,10 @@ pub trait WeightInfo {
     fn cancel_xtx() -> Weight;
     fn confirm_side_effect() -> Weight;
     fn execute_side_effects_with_xbi() -> Weight;
+    fn send_gmp_message(len: u32) -> Weight;
+    fn acknowledge_gmp_message(len: u32) -> Weight;
+    fn expire_gmp_message() -> Weight;
+    fn sponsor_executor_fees() -> Weight;
 }
 
 /// Storage: `XDNS::Gateways` (r:2 w:0)
@@ -104,6 +108,36 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn execute_side_effects_with_xbi() -> Weight {
         single_order_weight::<T>()
     }
+
+    /// The range of component `len` is `[0, 65568]`.
+    fn send_gmp_message(len: u32) -> Weight {
+        Weight::from_parts(60_000_000_u64, 0u64)
+            // Hashing and storing the recipient and payload bytes
+            .saturating_add(Weight::from_parts(4_200_u64, 1_u64).saturating_mul(len.into()))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    /// The range of component `len` is `[0, 147456]`.
+    fn acknowledge_gmp_message(len: u32) -> Weight {
+        Weight::from_parts(80_000_000_u64, 0u64)
+            // Walking the inclusion proof and hashing the return data bytes
+            .saturating_add(Weight::from_parts(6_300_u64, 1_u64).saturating_mul(len.into()))
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    fn expire_gmp_message() -> Weight {
+        Weight::from_parts(40_000_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    fn sponsor_executor_fees() -> Weight {
+        Weight::from_parts(20_000_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
 }
 
 // For backwards compatibility and tests
@@ -131,4 +165,22 @@ impl WeightInfo for () {
     fn execute_side_effects_with_xbi() -> Weight {
         Weight::from_parts(60_000_000_u64, 0u64)
     }
+
+    fn send_gmp_message(len: u32) -> Weight {
+        Weight::from_parts(60_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(4_200_u64, 1_u64).saturating_mul(len.into()))
+    }
+
+    fn acknowledge_gmp_message(len: u32) -> Weight {
+        Weight::from_parts(80_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(6_300_u64, 1_u64).saturating_mul(len.into()))
+    }
+
+    fn expire_gmp_message() -> Weight {
+        Weight::from_parts(40_000_000_u64, 0u64)
+    }
+
+    fn sponsor_executor_fees() -> Weight {
+        Weight::from_parts(20_000_000_u64, 0u64)
+    }
 }
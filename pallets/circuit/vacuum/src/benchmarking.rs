@@ -63,11 +63,11 @@ fn register_gateway_under_xbi_vendor<T: Config>(gateway_id: [u8; 4], caller: T::
 
     // Advance 100 blocks ahead and call global on_initialize loop in order to process_all_verifier_overviews and set Gateway as active
     System::<T>::set_block_number(100u8.into());
-    XDNS::<T>::check_for_manual_verifier_overview_process(100u8.into());
+    XDNS::<T>::process_all_verifier_overviews(100u8.into());
 
     // Advance next 100 blocks ahead and call global on_initialize loop in order to process_all_verifier_overviews and set Gateway as active
     System::<T>::set_block_number(200u8.into());
-    XDNS::<T>::check_for_manual_verifier_overview_process(200u8.into());
+    XDNS::<T>::process_all_verifier_overviews(200u8.into());
 }
 
 const NATIVE_ASSET: u32 = 0;
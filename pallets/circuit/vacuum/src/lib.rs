@@ -11,9 +11,10 @@ pub mod weights;
 use crate::weights::WeightInfo;
 
 pub use pallet::*;
-use sp_runtime::traits::{Keccak256, Zero};
+use sp_runtime::traits::Keccak256;
 use sp_std::{convert::TryInto, prelude::*, vec::Vec};
 use t3rn_primitives::{
+    account_manager::AccountManager,
     circuit::{traits::CircuitSubmitAPI, types::OrderSFX},
     rewards::RewardsWriteApi,
     threevm::AddressMapping,
@@ -29,6 +30,7 @@ pub type Input = Vec<u8>;
 use frame_support::sp_runtime::Saturating;
 use scale_info::TypeInfo;
 use sp_core::{crypto::AccountId32, hexdisplay::AsBytesRef, H160, H256, U256};
+use sp_runtime::traits::Zero;
 use t3rn_abi::{
     evm_ingress_logs::{
         get_remote_bid_abi_descriptor, get_remote_order_abi_descriptor,
@@ -40,10 +42,13 @@ use t3rn_abi::{
 };
 use t3rn_primitives::circuit::{
     AdaptiveTimeout, CircuitStatus, OrderOrigin, ReadSFX, SFXAction, SecurityLvl, SideEffect,
+    XExecSignal,
 };
-use t3rn_types::sfx::TargetId;
+use t3rn_types::sfx::{ConfirmedSideEffect, TargetId};
 t3rn_primitives::reexport_currency_types!();
-use t3rn_primitives::circuit::{VacuumEVM3DOrder, VacuumEVMProof, VacuumEVMTeleportOrder};
+use t3rn_primitives::circuit::{
+    VacuumEVM3DOrder, VacuumEVMConfirmation, VacuumEVMProof, VacuumEVMTeleportOrder,
+};
 
 #[derive(Debug, Clone, Eq, PartialEq, Encode, Decode, TypeInfo)]
 pub struct OrderStatusRead<Hash, BlockNumber, Account> {
@@ -211,6 +216,14 @@ impl RemoteEVMBidLocalized {
     }
 }
 
+/// Remote order delivered by the winner of its bid, as proven by a `VacuumEVMProof`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VerifiedDelivery {
+    pub order: RemoteEVMOrderLocalized,
+    pub bid: RemoteEVMBidLocalized,
+    pub attestation: RemoteEVMCommitAppliedLocalized,
+}
+
 impl<AccountId, Balance> TryInto<SideEffect<AccountId, Balance>> for RemoteEVMOrderLocalized
 where
     u32: From<Asset>,
@@ -272,6 +285,14 @@ pub mod pallet {
         type CircuitSubmitAPI: CircuitSubmitAPI<Self, BalanceOf<Self>>;
         type Xdns: Xdns<Self, BalanceOf<Self>>;
 
+        type AccountManager: AccountManager<
+            Self::AccountId,
+            BalanceOf<Self>,
+            Self::Hash,
+            BlockNumberFor<Self>,
+            u32,
+        >;
+
         type RewardsWriteApi: RewardsWriteApi<
             Self::AccountId,
             BalanceOf<Self>,
@@ -287,6 +308,12 @@ pub mod pallet {
     #[pallet::generate_store(pub(super) trait Store)]
     pub struct Pallet<T>(_);
 
+    /// Local side effect created for a remote order, by its source gateway and remote nonce
+    #[pallet::storage]
+    #[pallet::getter(fn get_remote_order_sfx)]
+    pub type RemoteOrderSideEffects<T: Config> =
+        StorageDoubleMap<_, Identity, TargetId, Identity, u32, T::Hash, OptionQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -300,6 +327,21 @@ pub mod pallet {
         // Define your errors here
         XdnsGatewayDoesNotHaveRemoteOrderAddressRegistered,
         XdnsGatewayDoesNotHaveEscrowAddressRegistered,
+        GatewayNotActive,
+        InvalidSideEffectId,
+        ConfirmationFailed,
+        ExecutorIsNotTheBidWinner,
+        ProofSubmitterIsNotTheExecutor,
+        ExecutionDoesNotMatchOrder,
+        OrderIdDoesNotMatch,
+        AttestationReleasedToBidWinner,
+        AttestationNotReleasedToBidWinner,
+        FaultyExecutorBidNotFound,
+        FaultyExecutorBondNotFound,
+        RemoteOrderNotFound,
+        RemoteOrderAlreadyRecorded,
+        ClaimAssetDoesNotMatchBond,
+        InvalidClaimAmount,
     }
 
     #[pallet::hooks]
@@ -460,9 +502,6 @@ pub mod pallet {
 
             side_effect.reward_asset_id = Some(decoded_remote_order.asset);
 
-            let remote_origin: OrderOrigin<T::AccountId> =
-                OrderOrigin::from_remote_nonce(decoded_remote_order.nonce);
-
             // Based on target and asset, derive the intent of the order.
             // If the target is a remote chain, then the intent is to transfer funds to the remote chain. We don't need to go into details of assets order transfers or swaps.
             // If target is a local chain (t3rn) && asset used for reward payout equals the asset transferred on remote chain - assume bridge operation of wrapped assets.
@@ -514,10 +553,11 @@ pub mod pallet {
                 Ok(().into())
             } else {
                 // For remote order + remote reward, assume on_remote_origin_trigger
-                T::CircuitSubmitAPI::on_remote_origin_trigger(
+                Self::trigger_remote_order(
                     origin.clone(),
-                    remote_origin.to_account_id(),
-                    vec![side_effect],
+                    remote_target_id,
+                    decoded_remote_order.nonce,
+                    side_effect,
                     speed_mode,
                 )
             }
@@ -562,6 +602,220 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Verifies the order, bid, execution and attestation proofs of a remote order and that
+        /// `who` is the bid winner who delivered it to the ordered target account.
+        pub fn verify_delivery_proofs(
+            who: &T::AccountId,
+            vacuum_evm_proof: &VacuumEVMProof,
+        ) -> Result<VerifiedDelivery, DispatchError> {
+            let circuit_gateway_id = vacuum_evm_proof.circuit_gateway_id;
+            let source_gateway_id = vacuum_evm_proof.source_gateway_id;
+            let destination_gateway_id = vacuum_evm_proof.destination_gateway_id;
+
+            // Make sure all gateways are active
+            for gateway_id in [
+                circuit_gateway_id,
+                source_gateway_id,
+                destination_gateway_id,
+            ] {
+                ensure!(
+                    T::Xdns::is_target_active(gateway_id, &SecurityLvl::Optimistic),
+                    Error::<T>::GatewayNotActive
+                );
+            }
+
+            let circuit_bidding_address_generalized: ExecutionSource = ExecutionSource::decode(
+                &mut &T::Xdns::get_remote_bidding_contract_address(circuit_gateway_id)?[..],
+            )
+            .map_err(|_| Error::<T>::XdnsGatewayDoesNotHaveRemoteOrderAddressRegistered)?;
+
+            let source_attesters_address_generalized: ExecutionSource =
+                ExecutionSource::decode(&mut &T::Xdns::get_escrow_account(&source_gateway_id)?[..])
+                    .map_err(|_| Error::<T>::XdnsGatewayDoesNotHaveEscrowAddressRegistered)?;
+
+            let source_remote_order_address_generalized: ExecutionSource = ExecutionSource::decode(
+                &mut &T::Xdns::get_remote_order_contract_address(source_gateway_id)?[..],
+            )
+            .map_err(|_| Error::<T>::XdnsGatewayDoesNotHaveRemoteOrderAddressRegistered)?;
+
+            let destination_remote_order_address_generalized: ExecutionSource =
+                ExecutionSource::decode(
+                    &mut &T::Xdns::get_remote_order_contract_address(destination_gateway_id)?[..],
+                )
+                .map_err(|_| Error::<T>::XdnsGatewayDoesNotHaveRemoteOrderAddressRegistered)?;
+
+            let verified_order_bytes = T::CircuitSubmitAPI::verify_sfx_proof(
+                source_gateway_id,
+                SpeedMode::Finalized,
+                Some(source_remote_order_address_generalized),
+                vacuum_evm_proof.order_proof.clone(),
+            )?
+            .message;
+
+            let order = RemoteEVMOrderLocalized::rlp_to_remote_order_log::<T>(
+                verified_order_bytes,
+                source_gateway_id,
+            )?;
+
+            // Circuit Bidding Book emits the event WinnerSelected
+            // event WinnerSelected(bytes32 indexed id, address indexed executor);
+            let verified_bid_bytes = T::CircuitSubmitAPI::verify_sfx_proof(
+                circuit_gateway_id,
+                SpeedMode::Finalized,
+                Some(circuit_bidding_address_generalized),
+                vacuum_evm_proof.bid_proof.clone(),
+            )?
+            .message;
+
+            let bid = RemoteEVMBidLocalized::rlp_to_remote_bid_log::<T>(verified_bid_bytes)?;
+
+            // message is the topics of the event remoteOrder::confirm
+            let verified_execution_bytes = T::CircuitSubmitAPI::verify_sfx_proof(
+                destination_gateway_id,
+                SpeedMode::Finalized,
+                Some(destination_remote_order_address_generalized),
+                vacuum_evm_proof.execution_proof.clone(),
+            )?
+            .message;
+
+            let execution = RemoteEVMConfirmationAppliedLocalized::rlp_to_remote_commit_log::<T>(
+                verified_execution_bytes,
+            )?;
+
+            ensure!(
+                bid.winner == execution.sender,
+                Error::<T>::ExecutorIsNotTheBidWinner
+            );
+            // Ensure the executor is the one who submitted the proof to avoid confusion
+            ensure!(
+                T::AddressMapping::into_account_id(&bid.winner) == *who,
+                Error::<T>::ProofSubmitterIsNotTheExecutor
+            );
+
+            // Verify execution has been provided to correct target account with matching amount & asset
+            // Convert execution.target from H160 to Localized AccountId32 by pre-pending 12 0-bytes
+            let mut target_account_bytes = [0u8; 32];
+            target_account_bytes[12..32].copy_from_slice(&execution.target[..]);
+            ensure!(
+                execution.amount == order.amount
+                    && execution.asset == order.reward_asset
+                    && AccountId32::new(target_account_bytes) == order.target_account,
+                Error::<T>::ExecutionDoesNotMatchOrder
+            );
+
+            let verified_attestation_bytes = T::CircuitSubmitAPI::verify_sfx_proof(
+                source_gateway_id,
+                SpeedMode::Finalized,
+                Some(source_attesters_address_generalized),
+                vacuum_evm_proof.attestation_proof.clone(),
+            )?
+            .message;
+
+            let attestation = RemoteEVMCommitAppliedLocalized::rlp_to_remote_commit_log::<T>(
+                verified_attestation_bytes,
+            )?;
+
+            // Make sure order id matches
+            ensure!(
+                execution.sfx_id == bid.sfx_id && execution.sfx_id == attestation.sfx_id,
+                Error::<T>::OrderIdDoesNotMatch
+            );
+
+            Ok(VerifiedDelivery {
+                order,
+                bid,
+                attestation,
+            })
+        }
+
+        /// Releases the bond of `who` on the side effect `sfx_id` the remote order of a verified
+        /// delivery has been placed as, if its attestation has been released to the bid winner.
+        pub fn release_bond_of_correct_delivery(
+            who: &T::AccountId,
+            sfx_id: T::Hash,
+            delivery: &VerifiedDelivery,
+        ) -> DispatchResult {
+            ensure!(
+                delivery.attestation.executor == delivery.bid.winner,
+                Error::<T>::AttestationNotReleasedToBidWinner
+            );
+
+            T::CircuitSubmitAPI::release_executor_bond(sfx_id, who)
+        }
+
+        /// Triggers the Xtx of a remote order on behalf of its remote origin and records the side
+        /// effect it created under the source gateway and the remote nonce of the order.
+        pub fn trigger_remote_order(
+            origin: OriginFor<T>,
+            source_gateway_id: TargetId,
+            remote_nonce: u32,
+            side_effect: SideEffect<T::AccountId, BalanceOf<T>>,
+            speed_mode: SpeedMode,
+        ) -> DispatchResultWithPostInfo {
+            ensure!(
+                !RemoteOrderSideEffects::<T>::contains_key(source_gateway_id, remote_nonce),
+                Error::<T>::RemoteOrderAlreadyRecorded
+            );
+
+            let requester =
+                OrderOrigin::<T::AccountId>::from_remote_nonce(remote_nonce).to_account_id();
+            // Xtx id only depends on the requester and its nonce ahead of the trigger
+            let (xtx_id, _) = XExecSignal::<T::AccountId, BlockNumberFor<T>>::setup_fresh::<T>(
+                &requester,
+                AdaptiveTimeout::new_emergency(Zero::zero()),
+                speed_mode.clone(),
+                None,
+            );
+
+            let post_info = T::CircuitSubmitAPI::on_remote_origin_trigger(
+                origin,
+                requester,
+                vec![side_effect],
+                speed_mode,
+            )?;
+
+            let sfx_id = T::ReadSFX::get_fsx_of_xtx(xtx_id)?
+                .first()
+                .cloned()
+                .ok_or(Error::<T>::RemoteOrderNotFound)?;
+            RemoteOrderSideEffects::<T>::insert(source_gateway_id, remote_nonce, sfx_id);
+
+            Ok(post_info)
+        }
+
+        /// Pays the amount claimed by the remote order of a verified delivery to `who` out of the
+        /// bond of the executor the attestation has been released to instead of the bid winner.
+        pub fn pay_fault_claim(
+            who: &T::AccountId,
+            sfx_id: T::Hash,
+            delivery: &VerifiedDelivery,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            ensure!(
+                delivery.attestation.executor != delivery.bid.winner,
+                Error::<T>::AttestationReleasedToBidWinner
+            );
+
+            let faulty_executor =
+                T::AddressMapping::into_account_id(&delivery.attestation.executor);
+            let faulty_bid = T::ReadSFX::get_fsx(sfx_id)?
+                .best_bid
+                .filter(|bid| bid.executor == faulty_executor)
+                .ok_or(Error::<T>::FaultyExecutorBidNotFound)?;
+            // The claim is paid in the asset of the order, which has to be the one the bond is in
+            ensure!(
+                faulty_bid.reward_asset_id == Some(delivery.order.asset),
+                Error::<T>::ClaimAssetDoesNotMatchBond
+            );
+            let claimed = u128::try_from(delivery.order.amount)
+                .ok()
+                .and_then(|amount| BalanceOf::<T>::decode(&mut &amount.encode()[..]).ok())
+                .ok_or(Error::<T>::InvalidClaimAmount)?;
+
+            let bond_id = faulty_bid.generate_id::<<T as frame_system::Config>::Hashing, T>(sfx_id);
+            T::AccountManager::slash_deposit_to(bond_id, who, claimed)
+                .map_err(|_| Error::<T>::FaultyExecutorBondNotFound.into())
+        }
     }
 
     impl<T: Config> VacuumAccess<T> for Pallet<T> {
@@ -619,8 +873,58 @@ pub mod pallet {
 
         fn evm_confirm(
             origin: &T::RuntimeOrigin,
-            vacuum_evm_order: VacuumEVMOrder,
+            vacuum_evm_confirmation: VacuumEVMConfirmation,
+        ) -> Result<bool, DispatchError> {
+            let who = ensure_signed(origin.clone())?;
+
+            let sfx_id = T::Hash::decode(&mut vacuum_evm_confirmation.sfx_id.as_bytes())
+                .map_err(|_| Error::<T>::InvalidSideEffectId)?;
+            // Reject side effects not linked to any Xtx before handing the confirmation over
+            T::ReadSFX::get_fsx(sfx_id)?;
+
+            // Circuit verifies the inclusion against the speed mode of the linked Xtx
+            let confirmation = ConfirmedSideEffect {
+                err: None,
+                output: None,
+                inclusion_data: vacuum_evm_confirmation.confirmation_proof,
+                executioner: who,
+                received_at: frame_system::Pallet::<T>::block_number(),
+                cost: None,
+            };
+
+            T::CircuitSubmitAPI::confirm_side_effect(origin.clone(), sfx_id, confirmation)
+                .map_err(|e| {
+                    log::error!(
+                        "Vacuum::evm_confirm -- error calling confirm_side_effect: {:?}",
+                        e
+                    );
+                    Error::<T>::ConfirmationFailed
+                })?;
+
+            Ok(true)
+        }
+
+        fn evm_submit_correctness_proof(
+            origin: &T::RuntimeOrigin,
+            vacuum_evm_proof: VacuumEVMProof,
         ) -> Result<bool, DispatchError> {
+            let who = ensure_signed(origin.clone())?;
+
+            let delivery = Self::verify_delivery_proofs(&who, &vacuum_evm_proof)?;
+
+            // The bond released is the one of the side effect the remote order has been placed as
+            let sfx_id = RemoteOrderSideEffects::<T>::get(
+                vacuum_evm_proof.source_gateway_id,
+                delivery.order.nonce,
+            )
+            .ok_or(Error::<T>::RemoteOrderNotFound)?;
+            Self::release_bond_of_correct_delivery(&who, sfx_id, &delivery)?;
+
+            Self::deposit_event(Event::CorrectnessProofConfirmed(
+                vacuum_evm_proof.destination_gateway_id,
+                vacuum_evm_proof,
+            ));
+
             Ok(true)
         }
 
@@ -669,13 +973,12 @@ pub mod pallet {
 
             let side_effect: SideEffect<T::AccountId, BalanceOf<T>> =
                 decoded_remote_order.clone().try_into()?;
-            let remote_origin: OrderOrigin<T::AccountId> =
-                OrderOrigin::from_remote_nonce(decoded_remote_order.nonce);
 
-            T::CircuitSubmitAPI::on_remote_origin_trigger(
+            Self::trigger_remote_order(
                 origin.clone(),
-                remote_origin.to_account_id(),
-                vec![side_effect],
+                gateway_id,
+                decoded_remote_order.nonce,
+                side_effect,
                 SpeedMode::Fast,
             )
             .map_err(|e| {
@@ -697,175 +1000,18 @@ pub mod pallet {
         ) -> Result<bool, DispatchError> {
             let who = ensure_signed(origin.clone())?;
 
-            let circuit_gateway_id = vacuum_evm_proof.circuit_gateway_id.clone();
-            let source_gateway_id = vacuum_evm_proof.circuit_gateway_id.clone();
-            let destination_gateway_id = vacuum_evm_proof.destination_gateway_id.clone();
-
-            // Make sure all gateways are active
-            assert!(T::Xdns::is_target_active(
-                circuit_gateway_id.clone(),
-                &SecurityLvl::Optimistic
-            ));
-
-            assert!(T::Xdns::is_target_active(
-                source_gateway_id.clone(),
-                &SecurityLvl::Optimistic
-            ));
-
-            assert!(T::Xdns::is_target_active(
-                destination_gateway_id.clone(),
-                &SecurityLvl::Optimistic
-            ));
-
-            let circuit_bidding_address_generalized: ExecutionSource = ExecutionSource::decode(
-                &mut &T::Xdns::get_remote_bidding_contract_address(source_gateway_id.clone())?[..],
-            )
-            .map_err(|_| Error::<T>::XdnsGatewayDoesNotHaveRemoteOrderAddressRegistered)?;
-
-            let source_attesters_address_generalized: ExecutionSource =
-                ExecutionSource::decode(&mut &T::Xdns::get_escrow_account(&source_gateway_id)?[..])
-                    .map_err(|_| Error::<T>::XdnsGatewayDoesNotHaveEscrowAddressRegistered)?;
+            let delivery = Self::verify_delivery_proofs(&who, &vacuum_evm_proof)?;
 
-            let source_remote_order_address_generalized: ExecutionSource = ExecutionSource::decode(
-                &mut &T::Xdns::get_remote_order_contract_address(source_gateway_id)?[..],
+            // The faulty bond is the one of the side effect the remote order has been placed as
+            let sfx_id = RemoteOrderSideEffects::<T>::get(
+                vacuum_evm_proof.source_gateway_id,
+                delivery.order.nonce,
             )
-            .map_err(|_| Error::<T>::XdnsGatewayDoesNotHaveRemoteOrderAddressRegistered)?;
-
-            let destination_remote_order_address_generalized: ExecutionSource =
-                ExecutionSource::decode(
-                    &mut &T::Xdns::get_remote_order_contract_address(destination_gateway_id)?[..],
-                )
-                .map_err(|_| Error::<T>::XdnsGatewayDoesNotHaveRemoteOrderAddressRegistered)?;
-
-            let verified_order_bytes = T::CircuitSubmitAPI::verify_sfx_proof(
-                source_gateway_id,
-                SpeedMode::Finalized,
-                Some(source_remote_order_address_generalized),
-                vacuum_evm_proof.order_proof.clone(),
-            )?;
-
-            let localized_order = RemoteEVMOrderLocalized::rlp_to_remote_order_log::<T>(
-                verified_order_bytes.message.clone(),
-                source_gateway_id,
-            )?;
-
-            // Local to SFX
-            let side_effect: SideEffect<T::AccountId, BalanceOf<T>> =
-                localized_order.clone().try_into()?;
-
-            // Get the asset amount && asset ID; temporarily only allow for fault-proof claims for the native asset
-            let asset_id = localized_order.asset;
-
-            if asset_id != T::Xdns::get_self_token_id() {
-                return Err(DispatchError::Other("Vacuum::evm_submit_fault_proof -- only native asset fault-proof claims are supported"));
-            }
-
-            // Circuit Bidding Book emits the event WinnerSelected
-            // event WinnerSelected(bytes32 indexed id, address indexed executor);
-            let verified_bid_bytes = T::CircuitSubmitAPI::verify_sfx_proof(
-                circuit_gateway_id,
-                SpeedMode::Finalized,
-                Some(circuit_bidding_address_generalized),
-                vacuum_evm_proof.bid_proof.clone(),
-            )?;
-
-            let bid = RemoteEVMBidLocalized::rlp_to_remote_bid_log::<T>(
-                verified_bid_bytes.message.clone(),
-            )?;
-
-            // message is the topics of the event remoteOrder::confirm
-            let verified_execution_bytes = T::CircuitSubmitAPI::verify_sfx_proof(
-                destination_gateway_id,
-                SpeedMode::Finalized,
-                Some(destination_remote_order_address_generalized),
-                vacuum_evm_proof.execution_proof.clone(),
-            )?
-            .message;
-
-            let decoded_remote_commit: RemoteEVMConfirmationAppliedLocalized =
-                RemoteEVMConfirmationAppliedLocalized::rlp_to_remote_commit_log::<T>(
-                    verified_execution_bytes.clone(),
-                )?;
-
-            // Verify executor's address equals the winner's address
-            assert!(bid.winner == decoded_remote_commit.sender, "Vacuum::evm_submit_fault_proof -- executor's address does not match the winner's address");
-
-            // Map bid.winner to local account id
-            let executor = T::AddressMapping::into_account_id(&bid.winner);
-
-            // Ensure the executor is the one who submitted the fault proof to avoid confusion
-            assert!(executor == who, "Vacuum::evm_submit_fault_proof -- executor's address does not match the fault proof submitter's address");
-
-            // Verify execution has been provided to correct target account with matching amount & asset
-            assert!(
-                decoded_remote_commit.amount == localized_order.amount,
-                "Vacuum::evm_submit_fault_proof -- amount does not match the execution amount"
-            );
-            assert!(
-                decoded_remote_commit.asset == localized_order.reward_asset,
-                "Vacuum::evm_submit_fault_proof -- asset does not match the execution asset"
-            );
-            // Convert decoded_remote_commit.target from H160 to Localized AccountId32 by pre-pending 12 0-bytes
-            let mut target_account_bytes = [0u8; 32];
-            target_account_bytes[12..32].copy_from_slice(&decoded_remote_commit.target[..]);
-            let target_account =
-                AccountId32::decode(&mut &target_account_bytes[..]).map_err(|_| {
-                    DispatchError::Other(
-                        "Vacuum::evm_submit_fault_proof -- error decoding target_account",
-                    )
-                })?;
-
-            assert!(target_account == localized_order.target_account, "Vacuum::evm_submit_fault_proof -- target account does not match the execution target account");
-
-            let verified_attestation_bytes = T::CircuitSubmitAPI::verify_sfx_proof(
-                source_gateway_id,
-                SpeedMode::Finalized,
-                Some(source_attesters_address_generalized),
-                vacuum_evm_proof.attestation_proof.clone(),
-            )?
-            .message;
-
-            let attestation = RemoteEVMCommitAppliedLocalized::rlp_to_remote_commit_log::<T>(
-                verified_attestation_bytes.clone(),
-            )?;
-
-            // Verify the fault proof - attestation hasn't been released to the bid winner
-            assert!(
-                attestation.executor != bid.winner,
-                "Vacuum::evm_submit_fault_proof -- attestation has been released to the bid winner"
-            );
-
-            // Make sure order id matches
-            assert!(
-                decoded_remote_commit.sfx_id == bid.sfx_id,
-                "Vacuum::evm_submit_fault_proof -- order id does not match the execution order id"
-            );
-            assert!(decoded_remote_commit.sfx_id == attestation.sfx_id, "Vacuum::evm_submit_fault_proof -- order id does not match the attestation order id");
-
-            let sfx_only_fsx = t3rn_types::fsx::FullSideEffect {
-                input: side_effect,
-                confirmed: None,
-                security_lvl: SecurityLvl::Escrow,
-                submission_target_height: Zero::zero(),
-                best_bid: None,
-                index: 0,
-            };
-
-            // Submit fault proof to rewards
-            let success = T::RewardsWriteApi::repatriate_for_faulty_or_missing_attestation(
-                &attestation.sfx_id,
-                &sfx_only_fsx,
-                &CircuitStatus::Finished,
-                Some(who.clone()),
-            );
-
-            assert!(
-                success,
-                "Vacuum::evm_submit_fault_proof -- error submitting fault proof to rewards"
-            );
+            .ok_or(Error::<T>::RemoteOrderNotFound)?;
+            Self::pay_fault_claim(&who, sfx_id, &delivery)?;
 
             Self::deposit_event(Event::FaultProofConfirmed(
-                destination_gateway_id,
+                vacuum_evm_proof.destination_gateway_id,
                 vacuum_evm_proof.clone(),
             ));
 
@@ -920,37 +1066,49 @@ mod tests {
 
     use circuit_runtime_types::UNIT;
 
-    use frame_support::{assert_err, assert_ok, traits::Hooks};
+    use frame_support::{
+        assert_err, assert_ok,
+        traits::{Get, Hooks},
+    };
     use hex_literal::hex;
     use sp_core::H256;
     use sp_runtime::{traits::Keccak256, AccountId32};
     use sp_std::convert::TryInto;
     pub use t3rn_mini_mock_runtime::{
         activate_all_light_clients, hotswap_latest_receipt_header_root,
-        initialize_eth2_with_3rd_epoch, prepare_ext_builder_playground, AccountId, AssetId, Assets,
-        Balance, Balances, BlockNumber, Circuit, CircuitError, CircuitEvent, Clock, ConfigVacuum,
-        EthereumEventInclusionProof, GlobalOnInitQueues, Hash, MiniRuntime, MockedAssetEvent,
-        OrderStatusRead, Portal, Rewards, RuntimeEvent as Event, RuntimeOrigin, System, Vacuum,
-        VacuumEvent, ASSET_ASTAR, ASSET_DOT, ASSET_ETH, ASSET_USDT, ASTAR_TARGET, ETHEREUM_TARGET,
-        POLKADOT_TARGET, XDNS,
+        initialize_eth2_with_3rd_epoch, prepare_ext_builder_playground, AccountId, AccountManager,
+        AddressMappingMock, AssetId, Assets, Balance, Balances, BlockNumber, Circuit, CircuitError,
+        CircuitEvent, Clock, ConfigVacuum, EscrowAccount, EthereumEventInclusionProof, Hash,
+        MiniRuntime, MockedAssetEvent, OrderStatusRead, Portal, Rewards, RuntimeEvent as Event,
+        RuntimeOrigin, System, Vacuum, VacuumEvent, ASSET_ASTAR, ASSET_DOT, ASSET_ETH, ASSET_USDT,
+        ASTAR_TARGET, ETHEREUM_TARGET, POLKADOT_TARGET, XDNS,
     };
 
     use t3rn_primitives::{
         circuit::{
             types::{OrderSFX, SFXAction},
-            AdaptiveTimeout, CircuitStatus, CircuitSubmitAPI, OrderOrigin,
+            AdaptiveTimeout, CircuitStatus, CircuitSubmitAPI, OrderOrigin, VacuumEVMConfirmation,
+            VacuumEVMProof,
         },
         claimable::CircuitRole,
-        clock::OnHookQueues,
+        clock::Cadence,
         light_client::LightClientAsyncAPI,
         monetary::MOCK_EXISTENTIAL_DEPOSIT as EXISTENTIAL_DEPOSIT,
         portal::Portal as PortalT,
+        threevm::{AddressMapping, VacuumAccess},
         EthereumToken, ExecutionSource, GatewayVendor, SpeedMode, TokenInfo, TreasuryAccount,
         TreasuryAccountProvider,
     };
     use t3rn_types::sfx::{ConfirmedSideEffect, SideEffect};
 
+    use crate::{
+        RemoteEVMBidLocalized, RemoteEVMCommitAppliedLocalized, RemoteEVMOrderLocalized,
+        VerifiedDelivery,
+    };
     use frame_support::{traits::Currency, weights::Weight};
+    use sp_core::{H160, U256};
+    use sp_runtime::traits::BlakeTwo256;
+    use t3rn_primitives::{account_manager::AccountManager as AccountManagerT, circuit::ReadSFX};
 
     use t3rn_types::fsx::TargetId;
 
@@ -1314,6 +1472,463 @@ mod tests {
         });
     }
 
+    #[test]
+    fn evm_confirm_rejects_side_effect_not_linked_to_any_xtx() {
+        let mut ext = prepare_ext_builder_playground();
+        ext.execute_with(|| {
+            let executor = AccountId32::from([1u8; 32]);
+
+            assert_err!(
+                <Vacuum as VacuumAccess<MiniRuntime>>::evm_confirm(
+                    &RuntimeOrigin::signed(executor),
+                    VacuumEVMConfirmation::new(H256::repeat_byte(1), vec![1u8; 64]),
+                ),
+                CircuitError::<MiniRuntime>::XtxNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn fault_proof_reads_the_remote_order_from_its_source_gateway() {
+        let mut ext = prepare_ext_builder_playground();
+        ext.execute_with(|| {
+            activate_all_light_clients();
+
+            // Circuit and destination gateways are active, the source gateway of the order is not
+            let vacuum_evm_proof = VacuumEVMProof {
+                circuit_gateway_id: POLKADOT_TARGET,
+                source_gateway_id: [9u8; 4],
+                destination_gateway_id: POLKADOT_TARGET,
+                order_proof: vec![],
+                bid_proof: vec![],
+                execution_proof: vec![],
+                attestation_proof: vec![],
+            };
+
+            assert_err!(
+                <Vacuum as VacuumAccess<MiniRuntime>>::evm_submit_fault_proof(
+                    &RuntimeOrigin::signed(AccountId32::from([1u8; 32])),
+                    vacuum_evm_proof,
+                ),
+                crate::Error::<MiniRuntime>::GatewayNotActive
+            );
+        });
+    }
+
+    #[test]
+    fn executor_releases_bond_of_confirmed_sfx_ahead_of_xtx_finalization() {
+        let mut ext = prepare_ext_builder_playground();
+        ext.execute_with(|| {
+            let executor = AccountId32::from([1u8; 32]);
+            let requester = AccountId32::from([2u8; 32]);
+            Balances::deposit_creating(
+                &requester,
+                (13_200_000_000_000 + EXISTENTIAL_DEPOSIT) as Balance,
+            );
+            Balances::deposit_creating(
+                &executor,
+                (13_200_000_000_000 + EXISTENTIAL_DEPOSIT) as Balance,
+            );
+
+            mint_required_assets_for_optimistic_actors(
+                requester.clone(),
+                executor.clone(),
+                0u128,
+                200u128,
+                ASSET_DOT,
+            );
+
+            let sfx_escrow_action =
+                SFXAction::Transfer(POLKADOT_TARGET, ASSET_DOT, requester.clone(), 200u128);
+            let sfx_escrow_order = OrderSFX::<AccountId32, u32, u128, [u8; 4], Vec<u8>, u128> {
+                sfx_action: sfx_escrow_action,
+                max_reward: 200u128,
+                insurance: 20u128,
+                reward_asset: ASSET_DOT,
+                remote_origin_nonce: None,
+            };
+
+            activate_all_light_clients();
+
+            System::set_block_number(System::block_number() + 1);
+
+            let sfx_escrow: SideEffect<AccountId32, u128> = sfx_escrow_order.try_into().unwrap();
+
+            assert_ok!(Vacuum::dynamic_destination_deal(
+                RuntimeOrigin::signed(requester.clone()),
+                ASTAR_TARGET,
+                ASSET_DOT,
+                200u128,
+                ASSET_ASTAR,
+                500u128,
+                1u32,
+                SpeedMode::Fast,
+            ));
+
+            let xtx_id = Hash::from(hex!(
+                "ada5013122d395ba3c54772283fb069b10426056ef8ca54750cb9bb552a59e7d"
+            ));
+            let sfx_escrow_order_id = sfx_escrow.generate_id::<Keccak256>(xtx_id.0.as_slice(), 0);
+
+            assert_ok!(Circuit::bid_sfx(
+                RuntimeOrigin::signed(executor.clone()),
+                sfx_escrow_order_id,
+                200 as Balance,
+            ));
+
+            System::set_block_number(System::block_number() + 3);
+            Clock::on_initialize(System::block_number());
+
+            assert_err!(
+                <Circuit as CircuitSubmitAPI<MiniRuntime, Balance>>::release_executor_bond(
+                    sfx_escrow_order_id,
+                    &executor,
+                ),
+                CircuitError::<MiniRuntime>::BondReleaseRejectedSideEffectNotConfirmed
+            );
+
+            assert_ok!(Circuit::escrow(
+                RuntimeOrigin::signed(executor.clone()),
+                sfx_escrow_order_id,
+            ));
+
+            assert_err!(
+                <Circuit as CircuitSubmitAPI<MiniRuntime, Balance>>::release_executor_bond(
+                    sfx_escrow_order_id,
+                    &requester,
+                ),
+                CircuitError::<MiniRuntime>::BondReleaseRejectedNotTheWinningExecutor
+            );
+
+            // Optimistic order of the Xtx is still pending, yet the escrow executor gets its bond back
+            assert_ok!(
+                <Circuit as CircuitSubmitAPI<MiniRuntime, Balance>>::release_executor_bond(
+                    sfx_escrow_order_id,
+                    &executor,
+                )
+            );
+            assert!(System::events().iter().any(|record| record.event
+                == Event::Circuit(CircuitEvent::ExecutorBondReleased(
+                    sfx_escrow_order_id,
+                    executor.clone()
+                ))));
+
+            assert_err!(
+                <Circuit as CircuitSubmitAPI<MiniRuntime, Balance>>::release_executor_bond(
+                    sfx_escrow_order_id,
+                    &executor,
+                ),
+                CircuitError::<MiniRuntime>::BondReleaseRejectedAlreadyReleased
+            );
+        });
+    }
+
+    #[test]
+    fn correctness_proof_releases_the_bond_once_ahead_of_the_xtx_finalization() {
+        let mut ext = prepare_ext_builder_playground();
+        ext.execute_with(|| {
+            let executor = AccountId32::from([1u8; 32]);
+            let requester = AccountId32::from([2u8; 32]);
+            Balances::deposit_creating(
+                &requester,
+                (13_200_000_000_000 + EXISTENTIAL_DEPOSIT) as Balance,
+            );
+            Balances::deposit_creating(
+                &executor,
+                (13_200_000_000_000 + EXISTENTIAL_DEPOSIT) as Balance,
+            );
+
+            mint_required_assets_for_optimistic_actors(
+                requester.clone(),
+                executor.clone(),
+                0u128,
+                200u128,
+                ASSET_DOT,
+            );
+
+            let sfx_optimistic_order = OrderSFX::<AccountId32, u32, u128, [u8; 4], Vec<u8>, u128> {
+                sfx_action: SFXAction::DynamicDestinationDeal(ASTAR_TARGET, ASSET_ASTAR, 500u128),
+                max_reward: 500u128,
+                insurance: 50u128,
+                reward_asset: ASSET_ASTAR,
+                remote_origin_nonce: Some(1u32),
+            };
+            let sfx_escrow_order = OrderSFX::<AccountId32, u32, u128, [u8; 4], Vec<u8>, u128> {
+                sfx_action: SFXAction::Transfer(
+                    POLKADOT_TARGET,
+                    ASSET_DOT,
+                    requester.clone(),
+                    200u128,
+                ),
+                max_reward: 200u128,
+                insurance: 20u128,
+                reward_asset: ASSET_DOT,
+                remote_origin_nonce: None,
+            };
+
+            activate_all_light_clients();
+
+            System::set_block_number(System::block_number() + 1);
+
+            let sfx_escrow: SideEffect<AccountId32, u128> = sfx_escrow_order.try_into().unwrap();
+            let sfx_optimistic: SideEffect<AccountId32, u128> =
+                sfx_optimistic_order.try_into().unwrap();
+
+            assert_ok!(Vacuum::dynamic_destination_deal(
+                RuntimeOrigin::signed(requester.clone()),
+                ASTAR_TARGET,
+                ASSET_DOT,
+                200u128,
+                ASSET_ASTAR,
+                500u128,
+                1u32,
+                SpeedMode::Fast,
+            ));
+
+            let xtx_id = Hash::from(hex!(
+                "ada5013122d395ba3c54772283fb069b10426056ef8ca54750cb9bb552a59e7d"
+            ));
+            let sfx_escrow_order_id = sfx_escrow.generate_id::<Keccak256>(xtx_id.0.as_slice(), 0);
+            let sfx_optimistic_order_id =
+                sfx_optimistic.generate_id::<Keccak256>(xtx_id.0.as_slice(), 1);
+
+            assert_ok!(Circuit::bid_sfx(
+                RuntimeOrigin::signed(executor.clone()),
+                sfx_escrow_order_id,
+                200 as Balance,
+            ));
+
+            System::set_block_number(System::block_number() + 3);
+            Clock::on_initialize(System::block_number());
+
+            assert_ok!(Circuit::escrow(
+                RuntimeOrigin::signed(executor.clone()),
+                sfx_escrow_order_id,
+            ));
+
+            let bid = <Circuit as ReadSFX<Hash, AccountId, Balance, BlockNumber>>::get_fsx(
+                sfx_escrow_order_id,
+            )
+            .unwrap()
+            .best_bid
+            .unwrap();
+            let bond = <AccountManager as AccountManagerT<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+                AssetId,
+            >>::get_charge_or_fail(
+                bid.generate_id::<BlakeTwo256, MiniRuntime>(sfx_escrow_order_id),
+            )
+            .unwrap();
+            let bond_asset_balance_of_executor = || match bond.maybe_asset_id {
+                Some(asset_id) => Assets::balance(asset_id, &executor),
+                None => Balances::free_balance(&executor),
+            };
+
+            // Remote order proven delivered by the bid winner, as decoded out of the proofs. The
+            // remote order id differs from the id of the side effect it's been placed as locally.
+            let remote_order_id = H256::repeat_byte(9);
+            let winner = H160::repeat_byte(1);
+            let mut delivery = VerifiedDelivery {
+                order: RemoteEVMOrderLocalized {
+                    from: H160::repeat_byte(2),
+                    destination: POLKADOT_TARGET,
+                    asset: ASSET_DOT,
+                    target_account: requester.clone(),
+                    reward_asset: H160::zero(),
+                    amount: U256::from(200u128),
+                    insurance: U256::from(20u128),
+                    max_reward: U256::from(200u128),
+                    nonce: 0,
+                },
+                bid: RemoteEVMBidLocalized {
+                    sfx_id: remote_order_id,
+                    winner,
+                },
+                attestation: RemoteEVMCommitAppliedLocalized {
+                    sfx_id: remote_order_id,
+                    executor: H160::repeat_byte(3),
+                },
+            };
+            assert_err!(
+                Vacuum::release_bond_of_correct_delivery(&executor, sfx_escrow_order_id, &delivery),
+                crate::Error::<MiniRuntime>::AttestationNotReleasedToBidWinner
+            );
+
+            delivery.attestation.executor = winner;
+            let balance_before_release = bond_asset_balance_of_executor();
+            assert_ok!(Vacuum::release_bond_of_correct_delivery(
+                &executor,
+                sfx_escrow_order_id,
+                &delivery
+            ));
+            let balance_after_release = bond_asset_balance_of_executor();
+            assert_eq!(
+                balance_after_release,
+                balance_before_release + bond.offered_reward
+            );
+
+            // Confirm the optimistic order through the EVM path, finalizing the Xtx
+            let mut scale_encoded_transfer_event = MockedAssetEvent::<MiniRuntime>::Transferred {
+                asset_id: ASSET_ASTAR,
+                from: requester.clone(),
+                to: executor.clone(),
+                amount: 500 as Balance,
+            }
+            .encode();
+            scale_encoded_transfer_event.insert(0, 4u8);
+
+            assert_ok!(<Vacuum as VacuumAccess<MiniRuntime>>::evm_confirm(
+                &RuntimeOrigin::signed(executor.clone()),
+                VacuumEVMConfirmation::new(sfx_optimistic_order_id, scale_encoded_transfer_event),
+            ));
+
+            assert_ok!(Vacuum::read_order_status(
+                RuntimeOrigin::signed(requester.clone()),
+                xtx_id
+            ));
+            assert_eq!(
+                expect_last_event_to_read_order_status().status,
+                CircuitStatus::FinishedAllSteps
+            );
+
+            // The finalization skips the released bond instead of paying it out twice
+            assert_eq!(bond_asset_balance_of_executor(), balance_after_release);
+        });
+    }
+
+    #[test]
+    fn fault_proof_pays_the_claim_out_of_the_bond_of_the_remote_order_attested_executor() {
+        let mut ext = prepare_ext_builder_playground();
+        ext.execute_with(|| {
+            let relayer = AccountId32::from([4u8; 32]);
+            let requester = AccountId32::from([2u8; 32]);
+            let winner = H160::repeat_byte(1);
+            let faulty = H160::repeat_byte(3);
+            let claimant = AddressMappingMock::into_account_id(&winner);
+            let faulty_executor = AddressMappingMock::into_account_id(&faulty);
+            let remote_nonce = 7u32;
+
+            Balances::deposit_creating(&relayer, (100_000 * UNIT) as Balance);
+            mint_required_assets_for_optimistic_actors(
+                claimant.clone(),
+                faulty_executor.clone(),
+                0u128,
+                100u128,
+                ASSET_DOT,
+            );
+
+            let sfx_order = OrderSFX::<AccountId32, u32, u128, [u8; 4], Vec<u8>, u128> {
+                sfx_action: SFXAction::Transfer(
+                    POLKADOT_TARGET,
+                    ASSET_DOT,
+                    requester.clone(),
+                    150u128,
+                ),
+                max_reward: 200u128,
+                insurance: 100u128,
+                reward_asset: ASSET_DOT,
+                remote_origin_nonce: None,
+            };
+            let side_effect: SideEffect<AccountId32, u128> = sfx_order.try_into().unwrap();
+
+            activate_all_light_clients();
+            System::set_block_number(System::block_number() + 1);
+
+            assert_ok!(Vacuum::trigger_remote_order(
+                RuntimeOrigin::signed(relayer.clone()),
+                ETHEREUM_TARGET,
+                remote_nonce,
+                side_effect.clone(),
+                SpeedMode::Fast,
+            ));
+
+            // The remote order is recorded as the side effect of its Xtx
+            let xtx_id = expect_last_event_to_emit_xtx_id();
+            let sfx_id = Vacuum::get_remote_order_sfx(ETHEREUM_TARGET, remote_nonce).unwrap();
+            assert_eq!(
+                sfx_id,
+                side_effect.generate_id::<Keccak256>(xtx_id.0.as_slice(), 0)
+            );
+            assert_err!(
+                Vacuum::trigger_remote_order(
+                    RuntimeOrigin::signed(relayer),
+                    ETHEREUM_TARGET,
+                    remote_nonce,
+                    side_effect,
+                    SpeedMode::Fast,
+                ),
+                crate::Error::<MiniRuntime>::RemoteOrderAlreadyRecorded
+            );
+
+            assert_ok!(Circuit::bid_sfx(
+                RuntimeOrigin::signed(faulty_executor.clone()),
+                sfx_id,
+                200 as Balance,
+            ));
+
+            // Attestation of the remote order released to the executor who hasn't delivered it
+            let mut delivery = VerifiedDelivery {
+                order: RemoteEVMOrderLocalized {
+                    from: H160::repeat_byte(2),
+                    destination: POLKADOT_TARGET,
+                    asset: ASSET_DOT,
+                    target_account: requester,
+                    reward_asset: H160::zero(),
+                    amount: U256::from(60u128),
+                    insurance: U256::from(100u128),
+                    max_reward: U256::from(200u128),
+                    nonce: remote_nonce,
+                },
+                bid: RemoteEVMBidLocalized {
+                    sfx_id: H256::repeat_byte(9),
+                    winner,
+                },
+                attestation: RemoteEVMCommitAppliedLocalized {
+                    sfx_id: H256::repeat_byte(9),
+                    executor: winner,
+                },
+            };
+            assert_err!(
+                Vacuum::pay_fault_claim(&claimant, sfx_id, &delivery),
+                crate::Error::<MiniRuntime>::AttestationReleasedToBidWinner
+            );
+
+            delivery.attestation.executor = faulty;
+            delivery.order.asset = ASSET_ASTAR;
+            assert_err!(
+                Vacuum::pay_fault_claim(&claimant, sfx_id, &delivery),
+                crate::Error::<MiniRuntime>::ClaimAssetDoesNotMatchBond
+            );
+
+            delivery.order.asset = ASSET_DOT;
+            let claimant_balance_before = Assets::balance(ASSET_DOT, &claimant);
+            let escrow_balance_before = Assets::balance(ASSET_DOT, &EscrowAccount::get());
+            assert_eq!(
+                Vacuum::pay_fault_claim(&claimant, sfx_id, &delivery),
+                Ok(60 as Balance)
+            );
+
+            // Claimant is paid the ordered amount, the rest of the bond is slashed to the Escrow
+            assert_eq!(
+                Assets::balance(ASSET_DOT, &claimant),
+                claimant_balance_before + 60
+            );
+            assert_eq!(
+                Assets::balance(ASSET_DOT, &EscrowAccount::get()),
+                escrow_balance_before + 40
+            );
+
+            // Bond can only be claimed once
+            assert_err!(
+                Vacuum::pay_fault_claim(&claimant, sfx_id, &delivery),
+                crate::Error::<MiniRuntime>::FaultyExecutorBondNotFound
+            );
+        });
+    }
+
     #[test]
     fn multi_escrow_and_optimistic_order_single_sfx_vacuum_delivers_to_circuit() {
         let mut ext = prepare_ext_builder_playground();
@@ -1682,7 +2297,7 @@ mod tests {
             );
 
             System::set_block_number(300);
-            GlobalOnInitQueues::process_hourly(300, Weight::MAX);
+            Clock::force_run_tasks_of_cadence(300, Cadence::Hours(1), Weight::MAX);
 
             // Claim via Rewards
             let _claim_res = Rewards::claim(
@@ -1706,7 +2321,7 @@ mod tests {
                 requester_on_dest.clone(),
             );
 
-            GlobalOnInitQueues::process_hourly(600, Weight::MAX);
+            Clock::force_run_tasks_of_cadence(600, Cadence::Hours(1), Weight::MAX);
 
             // Claim via Rewards
             let _claim_res = Rewards::claim(
@@ -1947,7 +2562,7 @@ mod tests {
                 EXISTENTIAL_DEPOSIT as Balance + 50 as Balance
             );
 
-            GlobalOnInitQueues::process_hourly(300, Weight::MAX);
+            Clock::force_run_tasks_of_cadence(300, Cadence::Hours(1), Weight::MAX);
 
             // Claim via Rewards
             let _claim_res = Rewards::claim(
@@ -2149,7 +2764,7 @@ mod tests {
                 EXISTENTIAL_DEPOSIT as Balance + 50 as Balance
             );
 
-            GlobalOnInitQueues::process_hourly(300, Weight::MAX);
+            Clock::force_run_tasks_of_cadence(300, Cadence::Hours(1), Weight::MAX);
 
             // Claim via Rewards
             let _claim_res = Rewards::claim(
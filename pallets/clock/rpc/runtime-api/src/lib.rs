@@ -0,0 +1,20 @@
+//! Runtime API definition required by Clock RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to expose the periodic tasks registry.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+pub use t3rn_primitives::clock::TaskInfo;
+
+sp_api::decl_runtime_apis! {
+    /// The API to inspect the periodic tasks run by pallet Clock
+    pub trait ClockRuntimeApi<BlockNumber> where
+        BlockNumber: Codec,
+    {
+        /// Returns the registered periodic tasks in the order they run, with their stats
+        fn fetch_tasks() -> Vec<TaskInfo<BlockNumber>>;
+    }
+}
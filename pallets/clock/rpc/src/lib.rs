@@ -0,0 +1,65 @@
+//! RPC interface for the Clock pallet.
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::CallError,
+};
+pub use pallet_clock_rpc_runtime_api::ClockRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+use std::sync::Arc;
+use t3rn_primitives::clock::TaskInfo;
+
+const RUNTIME_ERROR: i64 = 1;
+
+#[rpc(client, server)]
+pub trait ClockApi<BlockNumber> {
+    /// Returns the registered periodic tasks with their last run, weight used and backlog
+    #[method(name = "clock_fetchTasks")]
+    fn fetch_tasks(&self) -> RpcResult<Vec<TaskInfo<BlockNumber>>>;
+}
+
+/// A struct that implements the [`ClockApiServer`].
+pub struct Clock<C, P> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Clock<C, P> {
+    /// Create new `Clock` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<C, Block, BlockNumber> ClockApiServer<BlockNumber> for Clock<C, Block>
+where
+    BlockNumber: Codec + MaybeDisplay,
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: ClockRuntimeApi<Block, BlockNumber>,
+{
+    fn fetch_tasks(&self) -> RpcResult<Vec<TaskInfo<BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+
+        let result = api.fetch_tasks(at).map_err(runtime_error_into_rpc_err)?;
+
+        Ok(result)
+    }
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(jsonrpsee::types::ErrorObject::owned(
+        RUNTIME_ERROR as i32,
+        "Runtime Error - Clock RPC",
+        Some(format!("{err:?}")),
+    )))
+}
@@ -12,9 +12,13 @@ pub use crate::pallet::*;
 use frame_support::{pallet_prelude::Weight, traits::Get};
 
 pub use t3rn_primitives::{
-    account_manager::AccountManager, claimable::ClaimableArtifacts, clock::Clock,
-    common::RoundInfo, executors::Executors, gateway::GatewayABIConfig, ChainId, EscrowTrait,
-    GatewayGenesisConfig, GatewayType, GatewayVendor,
+    account_manager::AccountManager,
+    claimable::ClaimableArtifacts,
+    clock::{Cadence, Clock, PeriodicTask, PeriodicTasks, TaskInfo, TaskStats},
+    common::RoundInfo,
+    executors::Executors,
+    gateway::GatewayABIConfig,
+    ChainId, EscrowTrait, GatewayGenesisConfig, GatewayType, GatewayVendor,
 };
 
 #[cfg(test)]
@@ -35,6 +39,11 @@ pub mod pallet {
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
 
+    use sp_runtime::{
+        traits::{One, Saturating, Zero},
+        Percent,
+    };
+    use sp_std::prelude::*;
     use t3rn_primitives::clock::OnHookQueues;
 
     const FIVE: u64 = 5;
@@ -47,9 +56,13 @@ pub mod pallet {
         #[pallet::constant]
         type RoundDuration: Get<BlockNumberFor<Self>>;
 
-        /// Description of on_initialize queues and their max. consumption of % of total on_init weight.
-        /// The first element of the tuple is the queue name, the second is the max. % of total on_init weight.}
-        type OnInitializeQueues: OnHookQueues<Self>;
+        /// Number of blocks in an hour, resolving the hourly and daily task cadences.
+        #[pallet::constant]
+        type BlocksPerHour: Get<BlockNumberFor<Self>>;
+
+        /// Periodic tasks registered by the pallets, run on_initialize by cadence and priority
+        /// within their share of the on_initialize weight.
+        type PeriodicTasks: PeriodicTasks<Self>;
 
         type OnFinalizeQueues: OnHookQueues<Self>;
     }
@@ -65,6 +78,17 @@ pub mod pallet {
     /// Information on the current round.
     pub type CurrentRound<T: Config> = StorageValue<_, RoundInfo<BlockNumberFor<T>>, ValueQuery>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn task_stats)]
+    /// Last run, weight used and backlog of each periodic task, by task name.
+    pub type TasksStats<T: Config> =
+        StorageMap<_, Blake2_128Concat, Vec<u8>, TaskStats<BlockNumberFor<T>>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn carried_weight)]
+    /// On_initialize weight left unused by the previous block, added to the next block's budget.
+    pub type CarriedWeight<T: Config> = StorageValue<_, Weight, ValueQuery>;
+
     impl<T: Config> Pallet<T> {
         pub fn check_bump_round(n: frame_system::pallet_prelude::BlockNumberFor<T>) -> Weight {
             let past_round = <CurrentRound<T>>::get();
@@ -91,6 +115,130 @@ pub mod pallet {
                 T::DbWeight::get().reads(2)
             }
         }
+
+        /// Number of blocks between two runs of a task with the given cadence.
+        pub fn cadence_period(cadence: Cadence) -> BlockNumberFor<T> {
+            let blocks_per_hour = T::BlocksPerHour::get();
+            match cadence {
+                Cadence::EveryBlock => One::one(),
+                Cadence::Blocks(blocks) => blocks.into(),
+                Cadence::Hours(hours) => blocks_per_hour.saturating_mul(hours.into()),
+                Cadence::Days(days) =>
+                    blocks_per_hour.saturating_mul(days.saturating_mul(24).into()),
+            }
+        }
+
+        pub fn is_due(n: BlockNumberFor<T>, cadence: Cadence) -> bool {
+            let period = Self::cadence_period(cadence);
+            !period.is_zero() && (n % period).is_zero()
+        }
+
+        /// Runs the tasks due at `n` by priority. Each task gets its share of the hook weight limit,
+        /// drawn from the limit topped up by the weight carried over from the previous block.
+        /// Tasks that can't be covered by the weight left are deferred to the next block and
+        /// counted in their backlog. Only the unused shares of the tasks run or deferred are
+        /// carried over, never those of the tasks that weren't due.
+        pub fn process_tasks(n: BlockNumberFor<T>, hook_weight_limit: Weight) -> Weight {
+            let mut remaining = hook_weight_limit.saturating_add(<CarriedWeight<T>>::get());
+            let mut unused = Weight::zero();
+            let mut consumed = T::DbWeight::get().reads_writes(1, 1);
+
+            let mut tasks = T::PeriodicTasks::periodic_tasks();
+            tasks.sort_by_key(|task| task.priority);
+
+            for task in tasks {
+                let mut stats = <TasksStats<T>>::get(task.name);
+                consumed = consumed.saturating_add(T::DbWeight::get().reads(1));
+                if !Self::is_due(n, task.cadence) && stats.backlog == 0 {
+                    continue
+                }
+
+                let task_limit = task.share * hook_weight_limit;
+                if task_limit.is_zero() || !remaining.all_gte(task_limit) {
+                    log::debug!(
+                        "Clock::process_tasks deferring task {:?} at block {:?}",
+                        sp_std::str::from_utf8(task.name),
+                        n
+                    );
+                    stats.backlog = stats.backlog.saturating_add(1);
+                    unused = unused.saturating_add(task_limit);
+                } else {
+                    let used = (task.run)(n, task_limit);
+                    if !task_limit.all_gte(used) {
+                        log::error!(
+                            "Clock::process_tasks task {:?} used {:?} over its limit {:?}",
+                            sp_std::str::from_utf8(task.name),
+                            used,
+                            task_limit
+                        );
+                    }
+                    remaining = remaining.saturating_sub(used);
+                    unused = unused.saturating_add(task_limit.saturating_sub(used));
+                    consumed = consumed.saturating_add(used);
+                    stats = TaskStats {
+                        last_run: Some(n),
+                        weight_used: used,
+                        total_runs: stats.total_runs.saturating_add(1),
+                        backlog: 0,
+                    };
+                }
+                <TasksStats<T>>::insert(task.name, stats);
+                consumed = consumed.saturating_add(T::DbWeight::get().writes(1));
+            }
+
+            // Carry over at most one block's worth, so the budget never exceeds twice the limit
+            <CarriedWeight<T>>::put(unused.min(hook_weight_limit));
+
+            consumed
+        }
+
+        /// Runs all tasks of the given cadence regardless of whether they're due at `n`.
+        pub fn force_run_tasks_of_cadence(
+            n: BlockNumberFor<T>,
+            cadence: Cadence,
+            weight_limit: Weight,
+        ) -> Weight {
+            T::PeriodicTasks::periodic_tasks()
+                .into_iter()
+                .filter(|task| task.cadence == cadence)
+                .fold(Zero::zero(), |consumed: Weight, task| {
+                    consumed.saturating_add((task.run)(n, task.share * weight_limit))
+                })
+        }
+
+        /// Registered tasks in the order they run with their stats
+        pub fn tasks_info() -> Vec<TaskInfo<BlockNumberFor<T>>> {
+            let mut tasks = T::PeriodicTasks::periodic_tasks();
+            tasks.sort_by_key(|task| task.priority);
+            tasks
+                .into_iter()
+                .map(|task| TaskInfo {
+                    name: task.name.to_vec(),
+                    cadence: task.cadence,
+                    share: task.share,
+                    priority: task.priority,
+                    stats: <TasksStats<T>>::get(task.name),
+                })
+                .collect()
+        }
+    }
+
+    impl<T: Config> PeriodicTasks<T> for Pallet<T> {
+        fn periodic_tasks() -> Vec<PeriodicTask<T>> {
+            vec![PeriodicTask {
+                name: b"clock::bump_round",
+                cadence: Cadence::Hours(1),
+                share: Percent::from_percent(5),
+                priority: 12,
+                run: |n, _| Self::check_bump_round(n),
+            }]
+        }
+    }
+
+    impl<T: Config> OnHookQueues<T> for Pallet<T> {
+        fn process(n: BlockNumberFor<T>, hook_weight_limit: Weight) -> Weight {
+            Self::process_tasks(n, hook_weight_limit)
+        }
     }
 
     // Pallet implements [`Hooks`] trait to define some logic to execute in some context.
@@ -117,7 +265,25 @@ pub mod pallet {
                 max_on_initialize_weight,
                 n
             );
-            T::OnInitializeQueues::process(n, max_on_initialize_weight)
+            Self::process_tasks(n, max_on_initialize_weight)
+        }
+
+        fn integrity_test() {
+            let tasks = T::PeriodicTasks::periodic_tasks();
+            let total_share: u32 = tasks
+                .iter()
+                .map(|task| task.share.deconstruct() as u32)
+                .sum();
+            assert!(
+                total_share <= 100,
+                "Periodic tasks shares exceed 100% of the on_initialize weight"
+            );
+            for (i, task) in tasks.iter().enumerate() {
+                assert!(
+                    tasks[..i].iter().all(|other| other.name != task.name),
+                    "Periodic task names must be unique"
+                );
+            }
         }
 
         // A runtime code run after every block and have access to extended set of APIs.
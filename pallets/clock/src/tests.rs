@@ -1,7 +1,10 @@
 #[cfg(test)]
 pub mod clock_test {
+    use frame_support::weights::Weight;
     use t3rn_mini_mock_runtime::{BlockNumber, Clock, ExtBuilder, System};
-    use t3rn_primitives::common::RoundInfo;
+    use t3rn_primitives::{clock::Cadence, common::RoundInfo};
+
+    const BUMP_ROUND_TASK: &[u8] = b"clock::bump_round";
 
     #[test]
     fn check_bump_round_doesnt_tick_below_300() {
@@ -47,4 +50,100 @@ pub mod clock_test {
             assert_eq!(current_round, expected_round_next);
         });
     }
+
+    #[test]
+    fn process_tasks_runs_hourly_tasks_and_records_their_stats() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(299u32);
+            Clock::process_tasks(299u32, Weight::from_parts(1_000_000_000_000, u64::MAX));
+            assert_eq!(Clock::task_stats(BUMP_ROUND_TASK).last_run, None);
+
+            System::set_block_number(300u32);
+            Clock::process_tasks(300u32, Weight::from_parts(1_000_000_000_000, u64::MAX));
+
+            let stats = Clock::task_stats(BUMP_ROUND_TASK);
+            assert_eq!(stats.last_run, Some(300u32));
+            assert_eq!(stats.total_runs, 1);
+            assert_eq!(stats.backlog, 0);
+            assert_eq!(Clock::current_round().index, 2);
+        });
+    }
+
+    #[test]
+    fn process_tasks_defers_due_tasks_without_weight_and_clears_backlog_on_next_run() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(300u32);
+            Clock::process_tasks(300u32, Weight::zero());
+
+            let stats = Clock::task_stats(BUMP_ROUND_TASK);
+            assert_eq!(stats.last_run, None);
+            assert_eq!(stats.backlog, 1);
+            assert_eq!(Clock::current_round().index, 1);
+
+            System::set_block_number(301u32);
+            Clock::process_tasks(301u32, Weight::from_parts(1_000_000_000_000, u64::MAX));
+
+            let stats = Clock::task_stats(BUMP_ROUND_TASK);
+            assert_eq!(stats.last_run, Some(301u32));
+            assert_eq!(stats.backlog, 0);
+            assert_eq!(Clock::current_round().index, 2);
+        });
+    }
+
+    #[test]
+    fn process_tasks_carries_over_only_the_unused_shares_of_the_tasks_run() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let limit = Weight::from_parts(1_000_000_000_000, 1_000_000_000);
+
+            for n in [300u32, 301u32, 302u32] {
+                System::set_block_number(n);
+                Clock::process_tasks(n, limit);
+
+                let tasks = Clock::tasks_info();
+                let ran = tasks
+                    .iter()
+                    .filter(|task| task.stats.last_run == Some(n))
+                    .collect::<Vec<_>>();
+                assert!(!ran.is_empty());
+                assert!(tasks.iter().all(|task| task.stats.backlog == 0));
+
+                // Tasks get their share of the limit, not of the budget topped up by the carry
+                let mut expected_carry = Weight::zero();
+                for task in ran {
+                    let task_limit = task.share * limit;
+                    assert!(task_limit.all_gte(task.stats.weight_used));
+                    expected_carry = expected_carry
+                        .saturating_add(task_limit.saturating_sub(task.stats.weight_used));
+                }
+                // The carry doesn't add up over consecutive blocks, nor include the shares of
+                // the tasks that weren't due
+                assert_eq!(Clock::carried_weight(), expected_carry.min(limit));
+                let all_shares = tasks.iter().fold(Weight::zero(), |shares, task| {
+                    shares.saturating_add(task.share * limit)
+                });
+                assert!(Clock::carried_weight().ref_time() < all_shares.ref_time());
+            }
+        });
+    }
+
+    #[test]
+    fn tasks_info_lists_registered_tasks_by_priority() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let tasks = Clock::tasks_info();
+            let priorities = tasks.iter().map(|task| task.priority).collect::<Vec<_>>();
+            let mut sorted = priorities.clone();
+            sorted.sort();
+            assert_eq!(priorities, sorted);
+
+            let bump_round = tasks
+                .iter()
+                .find(|task| task.name == BUMP_ROUND_TASK.to_vec())
+                .expect("clock registers its round bump task");
+            assert_eq!(bump_round.cadence, Cadence::Hours(1));
+        });
+    }
 }
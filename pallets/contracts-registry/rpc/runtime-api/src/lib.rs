@@ -7,7 +7,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-pub use pallet_contracts_registry::FetchContractsResult;
+pub use pallet_contracts_registry::{ContractUsageInfo, FetchContractsResult};
 use sp_core::sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
@@ -15,10 +15,14 @@ sp_api::decl_runtime_apis! {
     pub trait ContractsRegistryRuntimeApi<AccountId> where
         AccountId: Codec
     {
-        /// Returns the contracts searchable by name, author or metadata
+        /// Returns the contracts searchable by name, author or metadata along with their source verification status
         fn fetch_contracts(
             author: Option<AccountId>,
             metadata: Option<Vec<u8>>,
+            verified_only: bool,
         ) -> FetchContractsResult;
+
+        /// Returns the usage counts and royalty earnings of the author's contracts, or of all used contracts
+        fn fetch_contract_usages(author: Option<AccountId>) -> Vec<ContractUsageInfo>;
     }
 }
@@ -8,7 +8,7 @@ use jsonrpsee::{
     proc_macros::rpc,
 };
 pub use pallet_contracts_registry_rpc_runtime_api::ContractsRegistryRuntimeApi;
-use pallet_contracts_registry_rpc_runtime_api::FetchContractsResult;
+use pallet_contracts_registry_rpc_runtime_api::{ContractUsageInfo, FetchContractsResult};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
@@ -17,13 +17,21 @@ const RUNTIME_ERROR: i64 = 1;
 
 #[rpc(client, server)]
 pub trait ContractsRegistryApi<AccountId> {
-    /// Returns the contracts searchable by name, author or metadata
+    /// Returns the contracts searchable by name, author or metadata.
+    /// Each contract comes with its source verification status and the compiler version used to reproduce it.
     #[method(name = "contractsRegistry_fetchContracts")]
     fn fetch_contracts(
         &self,
         author: Option<AccountId>,
         metadata: Option<Vec<u8>>,
+        verified_only: Option<bool>,
     ) -> RpcResult<FetchContractsResult>;
+
+    /// Returns how many side effects were settled for each contract and the royalties it earned per asset.
+    /// Reports the contracts of the author if given, otherwise all the contracts used so far.
+    #[method(name = "contractsRegistry_contractUsages")]
+    fn fetch_contract_usages(&self, author: Option<AccountId>)
+        -> RpcResult<Vec<ContractUsageInfo>>;
 }
 
 /// A struct that implements the [ContractsRegistryApi].
@@ -51,16 +59,28 @@ where
         &self,
         author: Option<AccountId>,
         metadata: Option<Vec<u8>>,
+        verified_only: Option<bool>,
     ) -> RpcResult<FetchContractsResult> {
         let api = self.client.runtime_api();
         let at = self.client.info().best_hash;
 
         let result = api
-            .fetch_contracts(at, author, metadata)
+            .fetch_contracts(at, author, metadata, verified_only.unwrap_or(false))
             .map_err(runtime_error_into_rpc_err)?;
 
         Ok(result)
     }
+
+    fn fetch_contract_usages(
+        &self,
+        author: Option<AccountId>,
+    ) -> RpcResult<Vec<ContractUsageInfo>> {
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+
+        api.fetch_contract_usages(at, author)
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
 
 fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
@@ -24,14 +24,22 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::too_many_arguments)]
 use codec::Encode;
-use frame_support::dispatch::DispatchResult;
+use frame_support::{
+    dispatch::DispatchResult,
+    traits::{Get, ReservableCurrency},
+};
 use frame_system::{ensure_signed, pallet_prelude::BlockNumberFor};
+use sp_runtime::traits::{Saturating, UniqueSaturatedInto};
 use sp_std::{convert::TryInto, prelude::*};
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 use t3rn_primitives::{
-    contracts_registry::{ContractsRegistry as ContractsRegistryT, RegistryContractId},
+    contracts_registry::{
+        ContractPackage, ContractRelease, ContractUsage, ContractsRegistry as ContractsRegistryT,
+        RegistryContractId, RegistryContractV1, RoyaltyConfig, SemanticVersion, SourceAttestation,
+        SourceVerification,
+    },
     reexport_currency_types,
 };
 
@@ -61,7 +69,7 @@ pub mod pallet {
         pallet_prelude::*,
         traits::{
             fungible::{Inspect, Mutate},
-            Currency,
+            ReservableCurrency,
         },
     };
     use frame_system::pallet_prelude::BlockNumberFor;
@@ -76,10 +84,34 @@ pub mod pallet {
         /// Type representing the weight of this pallet
         type WeightInfo: weights::WeightInfo;
 
-        type Currency: Currency<Self::AccountId>;
+        type Currency: ReservableCurrency<Self::AccountId>;
 
         /// A type that provides inspection and mutation to some fungible assets
         type Balances: Inspect<Self::AccountId> + Mutate<Self::AccountId>;
+
+        /// Base deposit reserved from the publisher of each contract release
+        #[pallet::constant]
+        type ContractDepositBase: Get<BalanceOf<Self>>;
+
+        /// Deposit reserved per byte of the encoded contract release
+        #[pallet::constant]
+        type ContractDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Maximum length of the code bytes of a published contract
+        #[pallet::constant]
+        type MaxCodeLength: Get<u32>;
+
+        /// Maximum length of the source code of a published contract
+        #[pallet::constant]
+        type MaxSourceLength: Get<u32>;
+
+        /// Maximum length of a package name
+        #[pallet::constant]
+        type MaxPackageNameLength: Get<u32>;
+
+        /// Maximum number of releases published under a single package
+        #[pallet::constant]
+        type MaxReleasesPerPackage: Get<u32>;
     }
 
     // Simple declaration of the `Pallet` type. It is placeholder we use to implement traits and
@@ -116,6 +148,47 @@ pub mod pallet {
             // To see example on offchain worker, please refer to example-offchain-worker pallet
             // accompanied in this repository.
         }
+
+        fn on_runtime_upgrade() -> Weight {
+            // Define the current storage migration version.
+            const CURRENT_STORAGE_VERSION: u32 = 1;
+            // Migrate the storage entries.
+            StorageMigrations::<T>::try_mutate(|current_version| {
+                match *current_version {
+                    0 => {
+                        // Storage Migration: ContractMetadata declares the hash of the contract source
+                        // Storage Migration Details: registered contracts never declared one
+                        let mut migrated: u64 = 0;
+                        ContractsRegistry::<T>::translate(
+                            |_contract_id,
+                             contract: RegistryContractV1<
+                                T::Hash,
+                                T::AccountId,
+                                BalanceOf<T>,
+                                BlockNumberFor<T>,
+                            >| {
+                                migrated = migrated.saturating_add(1);
+                                Some(contract.into_contract())
+                            },
+                        );
+
+                        // Set migrations_done to true
+                        *current_version = CURRENT_STORAGE_VERSION;
+
+                        // Return the weight consumed by the migration.
+                        Ok::<Weight, DispatchError>(
+                            T::DbWeight::get().reads_writes(migrated + 1, migrated + 1),
+                        )
+                    },
+                    // Add more migration cases here, if needed in the future
+                    _ => {
+                        // No migration needed.
+                        Ok::<Weight, DispatchError>(T::DbWeight::get().reads(1))
+                    },
+                }
+            })
+            .unwrap_or(Weight::zero())
+        }
     }
 
     #[pallet::call]
@@ -131,9 +204,10 @@ pub mod pallet {
 
             let contract_id = contract.generate_id::<T>();
 
-            assert!(
+            // only the first submitter of contract to registry can become the author
+            ensure!(
                 requester == contract.author.account,
-                "only the first submitter of contract to registry can become the author",
+                Error::<T>::RequesterNotAuthor
             );
 
             if <ContractsRegistry<T>>::contains_key(contract_id) {
@@ -158,10 +232,281 @@ pub mod pallet {
                 Err(Error::<T>::UnknownContract.into())
             } else {
                 <ContractsRegistry<T>>::remove(contract_id);
+                <ContractVerifications<T>>::remove(contract_id);
+                <Royalties<T>>::remove(contract_id);
+                Self::unlink_release(contract_id);
                 Self::deposit_event(Event::<T>::ContractPurged(requester, contract_id));
                 Ok(().into())
             }
         }
+
+        /// Publishes a contract release under the package name, reserving a storage deposit from the author.
+        /// The first publisher of a package becomes its owner; following versions can only be published by the owner.
+        #[pallet::weight(<T as Config>::WeightInfo::publish_contract(
+            contract.bytes.len().unique_saturated_into(),
+            contract.code_txt.len().unique_saturated_into(),
+            package.len() as u32,
+            T::MaxReleasesPerPackage::get(),
+        ))]
+        pub fn publish_contract(
+            origin: OriginFor<T>,
+            package: BoundedVec<u8, T::MaxPackageNameLength>,
+            version: SemanticVersion,
+            contract: RegistryContract<T::Hash, T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let publisher = ensure_signed(origin)?;
+
+            ensure!(!package.is_empty(), Error::<T>::EmptyPackageName);
+            let code_len: u32 = contract.bytes.len().unique_saturated_into();
+            let source_len: u32 = contract.code_txt.len().unique_saturated_into();
+            ensure!(
+                code_len <= T::MaxCodeLength::get(),
+                Error::<T>::CodeTooLarge
+            );
+            ensure!(
+                source_len <= T::MaxSourceLength::get(),
+                Error::<T>::SourceTooLarge
+            );
+            ensure!(
+                publisher == contract.author.account,
+                Error::<T>::RequesterNotAuthor
+            );
+            let package = package.into_inner();
+
+            let contract_id = contract.generate_id::<T>();
+            ensure!(
+                !<ContractsRegistry<T>>::contains_key(contract_id),
+                Error::<T>::ContractAlreadyExists
+            );
+
+            let mut contract_package = match <Packages<T>>::get(&package) {
+                Some(contract_package) => {
+                    ensure!(
+                        contract_package.owner == publisher,
+                        Error::<T>::NotPackageOwner
+                    );
+                    contract_package
+                },
+                None => ContractPackage {
+                    owner: publisher.clone(),
+                    releases: vec![],
+                },
+            };
+            let releases = contract_package.releases.len() as u32;
+            ensure!(
+                releases < T::MaxReleasesPerPackage::get(),
+                Error::<T>::TooManyReleases
+            );
+
+            let position = match contract_package
+                .releases
+                .binary_search_by(|(released, _)| released.cmp(&version))
+            {
+                Ok(_) => return Err(Error::<T>::VersionAlreadyPublished.into()),
+                Err(position) => position,
+            };
+            let previous = position
+                .checked_sub(1)
+                .map(|index| contract_package.releases[index].1);
+
+            let deposit = Self::release_deposit(contract.encoded_size());
+            T::Currency::reserve(&publisher, deposit)?;
+
+            contract_package
+                .releases
+                .insert(position, (version, contract_id));
+            <Packages<T>>::insert(&package, contract_package);
+            <ContractReleases<T>>::insert(
+                contract_id,
+                ContractRelease {
+                    package: package.clone(),
+                    version,
+                    publisher: publisher.clone(),
+                    deposit,
+                    previous,
+                    deprecated: false,
+                },
+            );
+            <ContractsRegistry<T>>::insert(contract_id, contract);
+
+            Self::deposit_event(Event::<T>::ContractPublished(
+                publisher,
+                package.clone(),
+                version,
+                contract_id,
+            ));
+            // Charge for the releases actually published under the package
+            Ok(Some(<T as Config>::WeightInfo::publish_contract(
+                code_len,
+                source_len,
+                package.len().unique_saturated_into(),
+                releases,
+            ))
+            .into())
+        }
+
+        /// Flags or un-flags the contract release as deprecated. Package owner only access.
+        #[pallet::weight(<T as Config>::WeightInfo::set_deprecated())]
+        pub fn set_deprecated(
+            origin: OriginFor<T>,
+            contract_id: RegistryContractId<T>,
+            deprecated: bool,
+        ) -> DispatchResultWithPostInfo {
+            let requester = ensure_signed(origin)?;
+
+            <ContractReleases<T>>::try_mutate(contract_id, |maybe_release| {
+                let release = maybe_release.as_mut().ok_or(Error::<T>::UnknownRelease)?;
+                let contract_package =
+                    <Packages<T>>::get(&release.package).ok_or(Error::<T>::UnknownPackage)?;
+                ensure!(
+                    contract_package.owner == requester,
+                    Error::<T>::NotPackageOwner
+                );
+                release.deprecated = deprecated;
+                Ok::<(), Error<T>>(())
+            })?;
+
+            Self::deposit_event(Event::<T>::ContractDeprecationSet(
+                requester,
+                contract_id,
+                deprecated,
+            ));
+            Ok(().into())
+        }
+
+        /// Hands the package over to the new owner, who from now on publishes its versions.
+        /// Deposits of already published releases stay reserved from their publishers.
+        #[pallet::weight(<T as Config>::WeightInfo::transfer_package_ownership(package.len() as u32))]
+        pub fn transfer_package_ownership(
+            origin: OriginFor<T>,
+            package: BoundedVec<u8, T::MaxPackageNameLength>,
+            new_owner: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let package = package.into_inner();
+
+            <Packages<T>>::try_mutate(&package, |maybe_package| {
+                let contract_package = maybe_package.as_mut().ok_or(Error::<T>::UnknownPackage)?;
+                ensure!(contract_package.owner == owner, Error::<T>::NotPackageOwner);
+                contract_package.owner = new_owner.clone();
+                Ok::<(), Error<T>>(())
+            })?;
+
+            Self::deposit_event(Event::<T>::PackageOwnershipTransferred(
+                package, owner, new_owner,
+            ));
+            Ok(().into())
+        }
+
+        /// Enables or disables the account as a source verifier. Root only access.
+        #[pallet::weight(<T as Config>::WeightInfo::set_verifier())]
+        pub fn set_verifier(
+            origin: OriginFor<T>,
+            verifier: T::AccountId,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            if enabled {
+                <Verifiers<T>>::insert(&verifier, ());
+            } else {
+                <Verifiers<T>>::remove(&verifier);
+            }
+
+            Self::deposit_event(Event::<T>::VerifierSet(verifier, enabled));
+            Ok(().into())
+        }
+
+        /// Records the outcome of reproducing the contract build from its source. Verifiers only access.
+        /// The attested source and bytecode hashes must match the code stored in the registry,
+        /// so a verifier can only vouch for the exact artifacts it has rebuilt. A source hash
+        /// declared in the contract metadata must match the registered code as well.
+        #[pallet::weight(<T as Config>::WeightInfo::attest_source(
+            T::MaxCodeLength::get(),
+            T::MaxSourceLength::get(),
+        ))]
+        pub fn attest_source(
+            origin: OriginFor<T>,
+            contract_id: RegistryContractId<T>,
+            attestation: SourceAttestation,
+        ) -> DispatchResultWithPostInfo {
+            let verifier = ensure_signed(origin)?;
+            ensure!(
+                <Verifiers<T>>::contains_key(&verifier),
+                Error::<T>::NotVerifier
+            );
+
+            let contract =
+                <ContractsRegistry<T>>::get(contract_id).ok_or(Error::<T>::UnknownContract)?;
+            let source_hash = SourceAttestation::hash_code(&contract.code_txt);
+            ensure!(
+                attestation.source_hash == source_hash,
+                Error::<T>::SourceHashMismatch
+            );
+            ensure!(
+                contract
+                    .meta
+                    .get_source_hash()
+                    .map_or(true, |declared| *declared == source_hash),
+                Error::<T>::DeclaredSourceHashMismatch
+            );
+            ensure!(
+                attestation.bytecode_hash == SourceAttestation::hash_code(&contract.bytes),
+                Error::<T>::BytecodeHashMismatch
+            );
+
+            <ContractVerifications<T>>::insert(
+                contract_id,
+                SourceVerification {
+                    verifier: verifier.clone(),
+                    compiler: attestation.compiler.clone(),
+                    verified_at: <frame_system::Pallet<T>>::block_number(),
+                    reproduced: attestation.reproduced,
+                },
+            );
+
+            Self::deposit_event(Event::<T>::SourceAttested(
+                verifier,
+                contract_id,
+                attestation.compiler,
+                attestation.reproduced,
+            ));
+            // Charge for hashing the code actually registered
+            Ok(Some(<T as Config>::WeightInfo::attest_source(
+                contract.bytes.len().unique_saturated_into(),
+                contract.code_txt.len().unique_saturated_into(),
+            ))
+            .into())
+        }
+
+        /// Sets or clears the royalty taken on the rewards of side effects requested by the
+        /// contract instances. Contract author only access.
+        #[pallet::weight(<T as Config>::WeightInfo::set_royalty())]
+        pub fn set_royalty(
+            origin: OriginFor<T>,
+            contract_id: RegistryContractId<T>,
+            royalty: Option<RoyaltyConfig<T::AccountId>>,
+        ) -> DispatchResultWithPostInfo {
+            let requester = ensure_signed(origin)?;
+
+            let contract =
+                <ContractsRegistry<T>>::get(contract_id).ok_or(Error::<T>::UnknownContract)?;
+            ensure!(
+                contract.author.account == requester,
+                Error::<T>::RequesterNotAuthor
+            );
+
+            match &royalty {
+                Some(royalty) => {
+                    ensure!(royalty.is_valid(), Error::<T>::InvalidRoyalty);
+                    <Royalties<T>>::insert(contract_id, royalty);
+                },
+                None => <Royalties<T>>::remove(contract_id),
+            }
+
+            Self::deposit_event(Event::<T>::RoyaltySet(requester, contract_id, royalty));
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -171,6 +516,27 @@ pub mod pallet {
         ContractStored(T::AccountId, RegistryContractId<T>),
         /// \[requester, contract_id\]
         ContractPurged(T::AccountId, RegistryContractId<T>),
+        /// \[publisher, package, version, contract_id\]
+        ContractPublished(
+            T::AccountId,
+            Vec<u8>,
+            SemanticVersion,
+            RegistryContractId<T>,
+        ),
+        /// \[requester, contract_id, deprecated\]
+        ContractDeprecationSet(T::AccountId, RegistryContractId<T>, bool),
+        /// \[package, previous_owner, new_owner\]
+        PackageOwnershipTransferred(Vec<u8>, T::AccountId, T::AccountId),
+        /// \[verifier, enabled\]
+        VerifierSet(T::AccountId, bool),
+        /// \[verifier, contract_id, compiler, reproduced\]
+        SourceAttested(T::AccountId, RegistryContractId<T>, Vec<u8>, bool),
+        /// \[author, contract_id, royalty\]
+        RoyaltySet(
+            T::AccountId,
+            RegistryContractId<T>,
+            Option<RoyaltyConfig<T::AccountId>>,
+        ),
     }
 
     // Errors inform users that something went wrong.
@@ -181,6 +547,36 @@ pub mod pallet {
         ContractAlreadyExists,
         /// Access of unknown contract
         UnknownContract,
+        /// Requester must be the author of the contract
+        RequesterNotAuthor,
+        /// Package name cannot be empty
+        EmptyPackageName,
+        /// Access of unknown package
+        UnknownPackage,
+        /// Contract is not released under any package
+        UnknownRelease,
+        /// Only the package owner can perform this action
+        NotPackageOwner,
+        /// The version has already been published under the package
+        VersionAlreadyPublished,
+        /// No release of the package is compatible with the requested version
+        NoCompatibleVersion,
+        /// Only registered verifiers can attest contract sources
+        NotVerifier,
+        /// Attested source hash does not match the contract code
+        SourceHashMismatch,
+        /// Attested bytecode hash does not match the contract bytes
+        BytecodeHashMismatch,
+        /// Royalty needs between one and `MAX_ROYALTY_PAYEES` payees with shares adding up to 100%
+        InvalidRoyalty,
+        /// Source hash declared in the contract metadata does not match the contract code
+        DeclaredSourceHashMismatch,
+        /// The code bytes of the contract exceed MaxCodeLength
+        CodeTooLarge,
+        /// The source code of the contract exceeds MaxSourceLength
+        SourceTooLarge,
+        /// The package already holds MaxReleasesPerPackage releases
+        TooManyReleases,
     }
 
     /// The pre-validated composable contracts on-chain registry.
@@ -199,6 +595,76 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// Packages linking released versions of a contract under a stable name.
+    #[pallet::storage]
+    #[pallet::getter(fn packages)]
+    pub type Packages<T> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Vec<u8>,
+        ContractPackage<<T as frame_system::Config>::Hash, <T as frame_system::Config>::AccountId>,
+        OptionQuery,
+    >;
+
+    /// Package releases of the registry contracts, holding their deposits and deprecation flags.
+    #[pallet::storage]
+    #[pallet::getter(fn contract_releases)]
+    pub type ContractReleases<T> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RegistryContractId<T>,
+        ContractRelease<
+            <T as frame_system::Config>::Hash,
+            <T as frame_system::Config>::AccountId,
+            BalanceOf<T>,
+        >,
+        OptionQuery,
+    >;
+
+    /// Accounts trusted to reproduce contract builds off-chain and attest the outcome.
+    #[pallet::storage]
+    #[pallet::getter(fn verifiers)]
+    pub type Verifiers<T> =
+        StorageMap<_, Blake2_128Concat, <T as frame_system::Config>::AccountId, (), OptionQuery>;
+
+    /// Latest source verification attested for the registry contracts.
+    #[pallet::storage]
+    #[pallet::getter(fn contract_verifications)]
+    pub type ContractVerifications<T> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RegistryContractId<T>,
+        SourceVerification<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Royalties configured by the authors of the registry contracts.
+    #[pallet::storage]
+    #[pallet::getter(fn royalties)]
+    pub type Royalties<T> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RegistryContractId<T>,
+        RoyaltyConfig<<T as frame_system::Config>::AccountId>,
+        OptionQuery,
+    >;
+
+    /// Version of the pallet storage, see on_runtime_upgrade
+    #[pallet::storage]
+    #[pallet::getter(fn storage_migrations_done)]
+    pub type StorageMigrations<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Usage counts and royalties earned by the registry contracts.
+    #[pallet::storage]
+    #[pallet::getter(fn contract_usages)]
+    pub type ContractUsages<T> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RegistryContractId<T>,
+        ContractUsage<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
     // The genesis config type.
     #[pallet::genesis_config]
     #[derive(frame_support::DefaultNoBound)]
@@ -210,7 +676,9 @@ pub mod pallet {
     // The build of genesis for the pallet.
     #[pallet::genesis_build]
     impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
-        fn build(&self) {}
+        fn build(&self) {
+            StorageMigrations::<T>::put(1);
+        }
     }
 }
 
@@ -222,6 +690,150 @@ impl<T: Config> Pallet<T> {
 
         Ok(())
     }
+
+    /// Finds the contracts by author or metadata, optionally only the ones with a reproduced build.
+    pub fn find_contracts(
+        maybe_author: Option<T::AccountId>,
+        metadata: Option<Vec<u8>>,
+        verified_only: bool,
+    ) -> Vec<(
+        RegistryContractId<T>,
+        RegistryContract<T::Hash, T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+    )> {
+        // helper function to find a number of byte slice inside a larger slice
+        fn find_subsequence(haystack: Vec<u8>, needle: &[u8]) -> Option<usize> {
+            haystack
+                .windows(needle.len())
+                .position(|window| window == needle)
+        }
+
+        // try to find contracts by author or metadata
+        <pallet::ContractsRegistry<T>>::iter()
+            .filter(
+                |(_contract_id, contract)| match (maybe_author.clone(), metadata.clone()) {
+                    (Some(author), Some(text)) =>
+                        contract.author.account == author
+                            && find_subsequence(contract.meta.encode(), text.as_slice()).is_some(),
+                    (Some(author), None) => contract.author.account == author,
+                    (None, Some(text)) =>
+                        find_subsequence(contract.meta.encode(), text.as_slice()).is_some(),
+                    (None, None) => false,
+                },
+            )
+            .filter(|(contract_id, _contract)| !verified_only || Self::is_verified(contract_id))
+            .collect()
+    }
+
+    pub fn is_verified(contract_id: &RegistryContractId<T>) -> bool {
+        <ContractVerifications<T>>::get(contract_id)
+            .map(|verification| verification.reproduced)
+            .unwrap_or(false)
+    }
+
+    /// Serves the contracts registry runtime API, annotating the found contracts with their verification status.
+    pub fn fetch_contracts_with_verification(
+        maybe_author: Option<T::AccountId>,
+        metadata: Option<Vec<u8>>,
+        verified_only: bool,
+    ) -> FetchContractsResult {
+        let contracts = Self::find_contracts(maybe_author, metadata, verified_only);
+
+        let result = if contracts.is_empty() {
+            Err(ContractAccessError::DoesntExist)
+        } else {
+            let verifications = contracts
+                .iter()
+                .map(|(contract_id, _contract)| {
+                    let verification = <ContractVerifications<T>>::get(contract_id);
+                    ContractVerificationInfo {
+                        contract_id: contract_id.encode(),
+                        state: match &verification {
+                            Some(verification) if verification.reproduced =>
+                                VerificationState::Verified,
+                            Some(_) => VerificationState::Failed,
+                            None => VerificationState::Unverified,
+                        },
+                        compiler: verification.map(|verification| verification.compiler),
+                    }
+                })
+                .collect();
+            let contracts = contracts
+                .into_iter()
+                .map(|(_contract_id, contract)| contract)
+                .collect::<Vec<_>>()
+                .encode();
+            Ok(FetchedContracts {
+                contracts,
+                verifications,
+            })
+        };
+
+        ContractsRegistryResult {
+            gas_consumed: 0,
+            result,
+            flags: 0,
+        }
+    }
+
+    /// Serves the contracts registry runtime API, reporting the usage and royalty earnings of the
+    /// author's contracts, or of all the used contracts if no author is given.
+    pub fn fetch_contract_usages(maybe_author: Option<T::AccountId>) -> Vec<ContractUsageInfo> {
+        <ContractUsages<T>>::iter()
+            .filter(|(contract_id, _usage)| match &maybe_author {
+                Some(author) => <pallet::ContractsRegistry<T>>::get(contract_id)
+                    .map_or(false, |contract| &contract.author.account == author),
+                None => true,
+            })
+            .map(|(contract_id, usage)| ContractUsageInfo {
+                contract_id: contract_id.encode(),
+                uses: usage.uses,
+                earnings: usage
+                    .earnings
+                    .into_iter()
+                    .map(|(asset_id, earned)| AssetEarnings {
+                        asset_id,
+                        amount: earned.unique_saturated_into(),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    pub fn release_deposit(encoded_len: usize) -> BalanceOf<T> {
+        let encoded_len: u32 = encoded_len.try_into().unwrap_or(u32::MAX);
+        T::ContractDepositBase::get()
+            .saturating_add(T::ContractDepositPerByte::get().saturating_mul(encoded_len.into()))
+    }
+
+    /// Removes the contract from its package lineage and releases the publisher's deposit.
+    fn unlink_release(contract_id: RegistryContractId<T>) {
+        if let Some(release) = <ContractReleases<T>>::take(contract_id) {
+            T::Currency::unreserve(&release.publisher, release.deposit);
+            // Keep the package name claimed by its owner even once all releases are gone
+            <Packages<T>>::mutate(&release.package, |maybe_package| {
+                if let Some(contract_package) = maybe_package {
+                    contract_package
+                        .releases
+                        .retain(|(_, released_id)| *released_id != contract_id);
+                }
+            });
+            // Re-link the newer releases which pointed at the removed one
+            for (_, newer_id) in <Packages<T>>::get(&release.package)
+                .map(|contract_package| contract_package.releases)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(version, _)| *version > release.version)
+            {
+                <ContractReleases<T>>::mutate(newer_id, |maybe_newer| {
+                    if let Some(newer) = maybe_newer {
+                        if newer.previous == Some(contract_id) {
+                            newer.previous = release.previous;
+                        }
+                    }
+                });
+            }
+        }
+    }
 }
 
 impl<T: Config> ContractsRegistryT<T, T::Currency> for Pallet<T> {
@@ -243,45 +855,63 @@ impl<T: Config> ContractsRegistryT<T, T::Currency> for Pallet<T> {
     fn fetch_contracts(
         maybe_author: Option<T::AccountId>,
         metadata: Option<Vec<u8>>,
+        verified_only: bool,
     ) -> Result<
         Vec<RegistryContract<T::Hash, T::AccountId, BalanceOf<T>, BlockNumberFor<T>>>,
         Error<T>,
     > {
-        // helper function to find a number of byte slice inside a larger slice
-        fn find_subsequence(haystack: Vec<u8>, needle: &[u8]) -> Option<usize> {
-            haystack
-                .windows(needle.len())
-                .position(|window| window == needle)
-        }
-
-        // try to find contracts by author or metadata
-        let contracts: Vec<
-            RegistryContract<T::Hash, T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
-        > = <pallet::ContractsRegistry<T>>::iter_values()
-            .filter(
-                |contract: &RegistryContract<
-                    T::Hash,
-                    T::AccountId,
-                    BalanceOf<T>,
-                    BlockNumberFor<T>,
-                >| {
-                    match (maybe_author.clone(), metadata.clone()) {
-                        (Some(author), Some(text)) =>
-                            contract.author.account == author
-                                && find_subsequence(contract.meta.encode(), text.as_slice())
-                                    .is_some(),
-                        (Some(author), None) => contract.author.account == author,
-                        (None, Some(text)) =>
-                            find_subsequence(contract.meta.encode(), text.as_slice()).is_some(),
-                        (None, None) => false,
-                    }
-                },
-            )
-            .collect();
+        let contracts = Self::find_contracts(maybe_author, metadata, verified_only);
 
         if contracts.is_empty() {
             return Err(pallet::Error::<T>::UnknownContract)
         }
-        Ok(contracts)
+        Ok(contracts
+            .into_iter()
+            .map(|(_contract_id, contract)| contract)
+            .collect())
+    }
+
+    fn fetch_latest_compatible(
+        package: Vec<u8>,
+        requirement: SemanticVersion,
+    ) -> Result<
+        (
+            RegistryContractId<T>,
+            RegistryContract<T::Hash, T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+        ),
+        Error<T>,
+    > {
+        let contract_package =
+            <pallet::Packages<T>>::get(&package).ok_or(pallet::Error::<T>::UnknownPackage)?;
+
+        contract_package
+            .releases
+            .iter()
+            .rev()
+            .filter(|(version, _)| version.is_compatible_with(&requirement))
+            .find(|(_, contract_id)| !Self::is_deprecated(*contract_id))
+            .and_then(|(_, contract_id)| {
+                <pallet::ContractsRegistry<T>>::get(contract_id)
+                    .map(|contract| (*contract_id, contract))
+            })
+            .ok_or(pallet::Error::<T>::NoCompatibleVersion)
+    }
+
+    fn is_deprecated(contract_id: RegistryContractId<T>) -> bool {
+        <pallet::ContractReleases<T>>::get(contract_id)
+            .map(|release| release.deprecated)
+            .unwrap_or(false)
+    }
+
+    fn royalty_of(contract_id: RegistryContractId<T>) -> Option<RoyaltyConfig<T::AccountId>> {
+        <pallet::Royalties<T>>::get(contract_id)
+    }
+
+    fn record_usage(
+        contract_id: RegistryContractId<T>,
+        earned: BalanceOf<T>,
+        asset_id: Option<u32>,
+    ) {
+        <pallet::ContractUsages<T>>::mutate(contract_id, |usage| usage.record(earned, asset_id));
     }
 }
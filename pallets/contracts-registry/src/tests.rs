@@ -18,18 +18,27 @@
 //! Unit tests for pallet contracts-registry.
 
 use circuit_mock_runtime::{
-    pallet_contracts_registry, pallet_contracts_registry::pallet::Error, ContractsRegistry,
-    ContractsRegistryStorage, ExtBuilder, Runtime, RuntimeEvent as Event, RuntimeOrigin as Origin,
-    ALICE, BOB,
+    pallet_contracts_registry,
+    pallet_contracts_registry::{pallet::Error, VerificationState},
+    Balance, Balances, BlockNumber, ContractsRegistry, ContractsRegistryStorage, ExtBuilder,
+    Runtime, RuntimeEvent as Event, RuntimeOrigin as Origin, ALICE, BOB, UNIT as TRN,
 };
 
-use frame_support::{assert_err, assert_ok};
+use codec::Encode;
+use frame_support::{
+    assert_err, assert_ok,
+    traits::{Currency, Get, ReservableCurrency},
+    BoundedVec,
+};
 use sp_core::H256;
-use sp_runtime::DispatchError;
+use sp_runtime::{AccountId32, DispatchError, Percent};
 use t3rn_primitives::{
-    contract_metadata::{ContractMetadata, ContractType},
+    contract_metadata::{
+        Compiler, ContractMetadata, ContractType, Language, Source, SourceCompiler, SourceLanguage,
+    },
     contracts_registry::{
         AuthorInfo, ContractsRegistry as ContractsRegistryExt, KindValidator, RegistryContract,
+        RoyaltyConfig, SemanticVersion, SourceAttestation,
     },
 };
 
@@ -141,7 +150,7 @@ fn fetch_contracts_by_metadata_should_return_all_matching_contracts() {
             test_contract_wrong.generate_id::<Runtime>(),
             test_contract_wrong.clone(),
         );
-        let actual = ContractsRegistry::fetch_contracts(None, Some(b"contract".to_vec()));
+        let actual = ContractsRegistry::fetch_contracts(None, Some(b"contract".to_vec()), false);
         assert_ok!(
             actual,
             vec![test_contract_name.clone(), test_contract_desc.clone()]
@@ -225,7 +234,7 @@ fn fetch_contracts_by_author_should_return_all_matching_contracts() {
             test_contract_author3.generate_id::<Runtime>(),
             test_contract_author3.clone(),
         );
-        let actual = ContractsRegistry::fetch_contracts(Some(ALICE), None);
+        let actual = ContractsRegistry::fetch_contracts(Some(ALICE), None, false);
         assert_ok!(
             actual,
             vec![test_contract_author1.clone(), test_contract_author2.clone()]
@@ -308,7 +317,8 @@ fn fetch_contracts_by_author_and_metadata_should_return_all_matching_contracts()
             test_contract_author3.generate_id::<Runtime>(),
             test_contract_author3.clone(),
         );
-        let actual = ContractsRegistry::fetch_contracts(Some(ALICE), Some(b"contract".to_vec()));
+        let actual =
+            ContractsRegistry::fetch_contracts(Some(ALICE), Some(b"contract".to_vec()), false);
         assert_ok!(
             actual,
             vec![test_contract_author1.clone(), test_contract_author2.clone()]
@@ -391,7 +401,7 @@ fn fetch_contracts_with_no_parameters_should_error() {
             test_contract_author3.generate_id::<Runtime>(),
             test_contract_author3.clone(),
         );
-        let actual = ContractsRegistry::fetch_contracts(None, None);
+        let actual = ContractsRegistry::fetch_contracts(None, None, false);
         assert_err!(actual, Error::UnknownContract);
     })
 }
@@ -677,3 +687,501 @@ fn test_kind_validator() {
             assert_eq!(test_contract.meta.get_contract_type().has_storage(), false);
         });
 }
+
+fn package_contract(
+    code_txt: &[u8],
+    author: AccountId32,
+) -> RegistryContract<H256, AccountId32, Balance, BlockNumber> {
+    RegistryContract {
+        code_txt: code_txt.to_vec(),
+        bytes: vec![],
+        author: AuthorInfo::new(author, None),
+        abi: None,
+        action_descriptions: vec![],
+        info: None,
+        meta: Default::default(),
+    }
+}
+
+#[test]
+fn add_new_contract_fails_if_requester_is_not_author() {
+    let test_contract = package_contract(b"some_code", ALICE);
+
+    ExtBuilder::default().build().execute_with(|| {
+        assert_err!(
+            ContractsRegistry::add_new_contract(Origin::root(), BOB, test_contract),
+            Error::<Runtime>::RequesterNotAuthor
+        );
+    })
+}
+
+fn package_name(
+    name: &[u8],
+) -> BoundedVec<u8, <Runtime as pallet_contracts_registry::Config>::MaxPackageNameLength> {
+    name.to_vec().try_into().unwrap()
+}
+
+#[test]
+fn publish_contract_reserves_deposit_and_resolves_latest_compatible_version() {
+    let v1_0_0 = package_contract(b"dex v1.0.0", ALICE);
+    let v1_2_0 = package_contract(b"dex v1.2.0", ALICE);
+    let v2_0_0 = package_contract(b"dex v2.0.0", ALICE);
+
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Balances::deposit_creating(&ALICE, 1_000 * (TRN as Balance));
+
+        for (version, contract) in [
+            (SemanticVersion::new(1, 2, 0), v1_2_0.clone()),
+            (SemanticVersion::new(1, 0, 0), v1_0_0.clone()),
+            (SemanticVersion::new(2, 0, 0), v2_0_0.clone()),
+        ] {
+            assert_ok!(ContractsRegistry::publish_contract(
+                Origin::signed(ALICE),
+                package_name(b"dex"),
+                version,
+                contract,
+            ));
+        }
+
+        let expected_deposit: Balance = [&v1_0_0, &v1_2_0, &v2_0_0]
+            .iter()
+            .map(|contract| ContractsRegistry::release_deposit(contract.encoded_size()))
+            .sum();
+        assert_eq!(Balances::reserved_balance(&ALICE), expected_deposit);
+
+        // Releases are linked in version order regardless of the publishing order
+        let v1_2_0_release =
+            ContractsRegistry::contract_releases(v1_2_0.generate_id::<Runtime>()).unwrap();
+        assert_eq!(
+            v1_2_0_release.previous,
+            Some(v1_0_0.generate_id::<Runtime>())
+        );
+
+        assert_ok!(
+            ContractsRegistry::fetch_latest_compatible(
+                b"dex".to_vec(),
+                SemanticVersion::new(1, 0, 0)
+            ),
+            (v1_2_0.generate_id::<Runtime>(), v1_2_0.clone())
+        );
+        assert_err!(
+            ContractsRegistry::fetch_latest_compatible(
+                b"dex".to_vec(),
+                SemanticVersion::new(3, 0, 0)
+            ),
+            Error::<Runtime>::NoCompatibleVersion
+        );
+
+        assert_err!(
+            ContractsRegistry::publish_contract(
+                Origin::signed(ALICE),
+                package_name(b"dex"),
+                SemanticVersion::new(1, 2, 0),
+                package_contract(b"dex v1.2.0 again", ALICE),
+            ),
+            Error::<Runtime>::VersionAlreadyPublished
+        );
+
+        // Purging releases the deposit and re-links the lineage
+        assert_ok!(ContractsRegistry::purge(
+            Origin::root(),
+            ALICE,
+            v1_0_0.generate_id::<Runtime>()
+        ));
+        assert_eq!(
+            Balances::reserved_balance(&ALICE),
+            expected_deposit - ContractsRegistry::release_deposit(v1_0_0.encoded_size())
+        );
+        assert_eq!(
+            ContractsRegistry::contract_releases(v1_2_0.generate_id::<Runtime>())
+                .unwrap()
+                .previous,
+            None
+        );
+    })
+}
+
+#[test]
+fn deprecated_releases_are_skipped_and_only_package_owner_can_manage_the_package() {
+    let v1_0_0 = package_contract(b"dex v1.0.0", ALICE);
+    let v1_1_0 = package_contract(b"dex v1.1.0", ALICE);
+    let v1_2_0 = package_contract(b"dex v1.2.0", BOB);
+
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Balances::deposit_creating(&ALICE, 1_000 * (TRN as Balance));
+        let _ = Balances::deposit_creating(&BOB, 1_000 * (TRN as Balance));
+
+        assert_ok!(ContractsRegistry::publish_contract(
+            Origin::signed(ALICE),
+            package_name(b"dex"),
+            SemanticVersion::new(1, 0, 0),
+            v1_0_0.clone(),
+        ));
+        assert_ok!(ContractsRegistry::publish_contract(
+            Origin::signed(ALICE),
+            package_name(b"dex"),
+            SemanticVersion::new(1, 1, 0),
+            v1_1_0.clone(),
+        ));
+
+        assert_err!(
+            ContractsRegistry::set_deprecated(
+                Origin::signed(BOB),
+                v1_1_0.generate_id::<Runtime>(),
+                true
+            ),
+            Error::<Runtime>::NotPackageOwner
+        );
+        assert_ok!(ContractsRegistry::set_deprecated(
+            Origin::signed(ALICE),
+            v1_1_0.generate_id::<Runtime>(),
+            true
+        ));
+        assert!(ContractsRegistry::is_deprecated(
+            v1_1_0.generate_id::<Runtime>()
+        ));
+        assert_ok!(
+            ContractsRegistry::fetch_latest_compatible(
+                b"dex".to_vec(),
+                SemanticVersion::new(1, 0, 0)
+            ),
+            (v1_0_0.generate_id::<Runtime>(), v1_0_0.clone())
+        );
+
+        assert_err!(
+            ContractsRegistry::publish_contract(
+                Origin::signed(BOB),
+                package_name(b"dex"),
+                SemanticVersion::new(1, 2, 0),
+                v1_2_0.clone(),
+            ),
+            Error::<Runtime>::NotPackageOwner
+        );
+        assert_ok!(ContractsRegistry::transfer_package_ownership(
+            Origin::signed(ALICE),
+            package_name(b"dex"),
+            BOB
+        ));
+        assert_ok!(ContractsRegistry::publish_contract(
+            Origin::signed(BOB),
+            package_name(b"dex"),
+            SemanticVersion::new(1, 2, 0),
+            v1_2_0.clone(),
+        ));
+        assert_eq!(
+            ContractsRegistry::packages(b"dex".to_vec()).unwrap().owner,
+            BOB
+        );
+    })
+}
+
+#[test]
+fn publish_contract_bounds_the_code_source_and_releases_of_a_package() {
+    let max_code = <Runtime as pallet_contracts_registry::Config>::MaxCodeLength::get();
+    let max_source = <Runtime as pallet_contracts_registry::Config>::MaxSourceLength::get();
+    let max_releases = <Runtime as pallet_contracts_registry::Config>::MaxReleasesPerPackage::get();
+
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Balances::deposit_creating(&ALICE, 1_000_000 * (TRN as Balance));
+
+        let mut too_much_code = package_contract(b"dex", ALICE);
+        too_much_code.bytes = vec![0u8; max_code as usize + 1];
+        assert_err!(
+            ContractsRegistry::publish_contract(
+                Origin::signed(ALICE),
+                package_name(b"dex"),
+                SemanticVersion::new(1, 0, 0),
+                too_much_code,
+            ),
+            Error::<Runtime>::CodeTooLarge
+        );
+        assert_err!(
+            ContractsRegistry::publish_contract(
+                Origin::signed(ALICE),
+                package_name(b"dex"),
+                SemanticVersion::new(1, 0, 0),
+                package_contract(&vec![0u8; max_source as usize + 1], ALICE),
+            ),
+            Error::<Runtime>::SourceTooLarge
+        );
+
+        for patch in 0..max_releases {
+            assert_ok!(ContractsRegistry::publish_contract(
+                Origin::signed(ALICE),
+                package_name(b"dex"),
+                SemanticVersion::new(1, 0, patch),
+                package_contract(&patch.encode(), ALICE),
+            ));
+        }
+        assert_err!(
+            ContractsRegistry::publish_contract(
+                Origin::signed(ALICE),
+                package_name(b"dex"),
+                SemanticVersion::new(2, 0, 0),
+                package_contract(b"one release too many", ALICE),
+            ),
+            Error::<Runtime>::TooManyReleases
+        );
+    })
+}
+
+fn attestation_for(
+    contract: &RegistryContract<H256, AccountId32, Balance, BlockNumber>,
+    reproduced: bool,
+) -> SourceAttestation {
+    SourceAttestation {
+        source_hash: SourceAttestation::hash_code(&contract.code_txt),
+        bytecode_hash: SourceAttestation::hash_code(&contract.bytes),
+        compiler: b"solc 0.8.19".to_vec(),
+        reproduced,
+    }
+}
+
+#[test]
+fn attest_source_requires_verifier_and_matching_hashes() {
+    let mut test_contract = package_contract(b"contract source", ALICE);
+    test_contract.bytes = b"contract bytecode".to_vec();
+    let contract_id = test_contract.generate_id::<Runtime>();
+
+    ExtBuilder::default().build().execute_with(|| {
+        ContractsRegistryStorage::<Runtime>::insert(contract_id, test_contract.clone());
+
+        assert_err!(
+            ContractsRegistry::attest_source(
+                Origin::signed(BOB),
+                contract_id,
+                attestation_for(&test_contract, true)
+            ),
+            Error::<Runtime>::NotVerifier
+        );
+
+        assert_ok!(ContractsRegistry::set_verifier(Origin::root(), BOB, true));
+
+        let mut wrong_source = attestation_for(&test_contract, true);
+        wrong_source.source_hash = [0; 32];
+        assert_err!(
+            ContractsRegistry::attest_source(Origin::signed(BOB), contract_id, wrong_source),
+            Error::<Runtime>::SourceHashMismatch
+        );
+
+        let mut wrong_bytecode = attestation_for(&test_contract, true);
+        wrong_bytecode.bytecode_hash = [0; 32];
+        assert_err!(
+            ContractsRegistry::attest_source(Origin::signed(BOB), contract_id, wrong_bytecode),
+            Error::<Runtime>::BytecodeHashMismatch
+        );
+
+        assert_ok!(ContractsRegistry::attest_source(
+            Origin::signed(BOB),
+            contract_id,
+            attestation_for(&test_contract, true)
+        ));
+        let verification = ContractsRegistry::contract_verifications(contract_id).unwrap();
+        assert_eq!(verification.verifier, BOB);
+        assert_eq!(verification.compiler, b"solc 0.8.19".to_vec());
+        assert!(ContractsRegistry::is_verified(&contract_id));
+    })
+}
+
+fn declared_source(hash: [u8; 32]) -> Source {
+    Source::new(
+        hash,
+        SourceLanguage::new(Language::Solidity, b"0.8.19".to_vec()),
+        SourceCompiler::new(Compiler::Solang, b"0.3.2".to_vec()),
+    )
+}
+
+#[test]
+fn attest_source_requires_declared_source_hash_to_match_code() {
+    let mut declared_right = package_contract(b"declared source", ALICE);
+    declared_right.meta = ContractMetadata::default().with_source(&declared_source(
+        SourceAttestation::hash_code(&declared_right.code_txt),
+    ));
+    let mut declared_wrong = package_contract(b"other source", ALICE);
+    declared_wrong.meta = ContractMetadata::default().with_source(&declared_source([1; 32]));
+
+    ExtBuilder::default().build().execute_with(|| {
+        for contract in [&declared_right, &declared_wrong] {
+            ContractsRegistryStorage::<Runtime>::insert(
+                contract.generate_id::<Runtime>(),
+                contract.clone(),
+            );
+        }
+        assert_ok!(ContractsRegistry::set_verifier(Origin::root(), BOB, true));
+
+        assert_err!(
+            ContractsRegistry::attest_source(
+                Origin::signed(BOB),
+                declared_wrong.generate_id::<Runtime>(),
+                attestation_for(&declared_wrong, true)
+            ),
+            Error::<Runtime>::DeclaredSourceHashMismatch
+        );
+        assert!(!ContractsRegistry::is_verified(
+            &declared_wrong.generate_id::<Runtime>()
+        ));
+
+        assert_ok!(ContractsRegistry::attest_source(
+            Origin::signed(BOB),
+            declared_right.generate_id::<Runtime>(),
+            attestation_for(&declared_right, true)
+        ));
+        assert!(ContractsRegistry::is_verified(
+            &declared_right.generate_id::<Runtime>()
+        ));
+    })
+}
+
+#[test]
+fn runtime_upgrade_migrates_contracts_without_declared_source_hash() {
+    let contract = package_contract(b"legacy source", ALICE);
+    let contract_id = contract.generate_id::<Runtime>();
+    // Metadata used to end with the license, before the optional source hash
+    let mut legacy_encoding = contract.encode();
+    assert_eq!(legacy_encoding.pop(), Some(0));
+
+    ExtBuilder::default().build().execute_with(|| {
+        pallet_contracts_registry::StorageMigrations::<Runtime>::put(0);
+        frame_support::storage::unhashed::put_raw(
+            &ContractsRegistryStorage::<Runtime>::hashed_key_for(contract_id),
+            &legacy_encoding,
+        );
+        assert_eq!(ContractsRegistry::contracts_registry(contract_id), None);
+
+        <ContractsRegistry as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+
+        assert_eq!(ContractsRegistry::storage_migrations_done(), 1);
+        let migrated = ContractsRegistry::contracts_registry(contract_id).unwrap();
+        assert_eq!(migrated, contract);
+        assert_eq!(migrated.meta.get_source_hash(), None);
+    })
+}
+
+#[test]
+fn fetch_contracts_filters_verified_contracts_and_reports_compiler() {
+    let verified = package_contract(b"verified code", ALICE);
+    let failed = package_contract(b"failed code", ALICE);
+    let unverified = package_contract(b"unverified code", ALICE);
+
+    ExtBuilder::default().build().execute_with(|| {
+        for contract in [&verified, &failed, &unverified] {
+            ContractsRegistryStorage::<Runtime>::insert(
+                contract.generate_id::<Runtime>(),
+                contract.clone(),
+            );
+        }
+        assert_ok!(ContractsRegistry::set_verifier(Origin::root(), BOB, true));
+        assert_ok!(ContractsRegistry::attest_source(
+            Origin::signed(BOB),
+            verified.generate_id::<Runtime>(),
+            attestation_for(&verified, true)
+        ));
+        assert_ok!(ContractsRegistry::attest_source(
+            Origin::signed(BOB),
+            failed.generate_id::<Runtime>(),
+            attestation_for(&failed, false)
+        ));
+
+        assert_eq!(
+            ContractsRegistry::fetch_contracts(Some(ALICE), None, false)
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_ok!(
+            ContractsRegistry::fetch_contracts(Some(ALICE), None, true),
+            vec![verified.clone()]
+        );
+
+        let fetched =
+            ContractsRegistry::fetch_contracts_with_verification(Some(ALICE), None, false)
+                .result
+                .unwrap();
+        assert_eq!(fetched.verifications.len(), 3);
+        let info_of = |contract: &RegistryContract<H256, AccountId32, Balance, BlockNumber>| {
+            fetched
+                .verifications
+                .iter()
+                .find(|info| info.contract_id == contract.generate_id::<Runtime>().encode())
+                .cloned()
+                .unwrap()
+        };
+        assert_eq!(info_of(&verified).state, VerificationState::Verified);
+        assert_eq!(info_of(&verified).compiler, Some(b"solc 0.8.19".to_vec()));
+        assert_eq!(info_of(&failed).state, VerificationState::Failed);
+        assert_eq!(info_of(&unverified).state, VerificationState::Unverified);
+        assert_eq!(info_of(&unverified).compiler, None);
+    })
+}
+
+#[test]
+fn set_royalty_is_author_only_and_usage_is_reported_per_contract() {
+    let contract = package_contract(b"royalty code", ALICE);
+    let contract_id = contract.generate_id::<Runtime>();
+    let royalty = RoyaltyConfig {
+        rate: Percent::from_percent(5),
+        payees: vec![
+            (ALICE, Percent::from_percent(80)),
+            (BOB, Percent::from_percent(20)),
+        ],
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        assert_err!(
+            ContractsRegistry::set_royalty(
+                Origin::signed(ALICE),
+                contract_id,
+                Some(royalty.clone())
+            ),
+            Error::<Runtime>::UnknownContract
+        );
+        ContractsRegistryStorage::<Runtime>::insert(contract_id, contract.clone());
+
+        assert_err!(
+            ContractsRegistry::set_royalty(Origin::signed(BOB), contract_id, Some(royalty.clone())),
+            Error::<Runtime>::RequesterNotAuthor
+        );
+        assert_err!(
+            ContractsRegistry::set_royalty(
+                Origin::signed(ALICE),
+                contract_id,
+                Some(RoyaltyConfig {
+                    rate: Percent::from_percent(5),
+                    payees: vec![(BOB, Percent::from_percent(20))],
+                })
+            ),
+            Error::<Runtime>::InvalidRoyalty
+        );
+        assert_ok!(ContractsRegistry::set_royalty(
+            Origin::signed(ALICE),
+            contract_id,
+            Some(royalty.clone())
+        ));
+        assert_eq!(ContractsRegistry::royalty_of(contract_id), Some(royalty));
+
+        ContractsRegistry::record_usage(contract_id, 50, None);
+        ContractsRegistry::record_usage(contract_id, 20, Some(1));
+        ContractsRegistry::record_usage(contract_id, 30, None);
+
+        let usages = ContractsRegistry::fetch_contract_usages(Some(ALICE));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].contract_id, contract_id.encode());
+        assert_eq!(usages[0].uses, 3);
+        assert_eq!(
+            usages[0]
+                .earnings
+                .iter()
+                .map(|earnings| (earnings.asset_id, earnings.amount))
+                .collect::<Vec<_>>(),
+            vec![(None, 80), (Some(1), 20)]
+        );
+        assert!(ContractsRegistry::fetch_contract_usages(Some(BOB)).is_empty());
+
+        assert_ok!(ContractsRegistry::set_royalty(
+            Origin::signed(ALICE),
+            contract_id,
+            None
+        ));
+        assert_eq!(ContractsRegistry::royalty_of(contract_id), None);
+    })
+}
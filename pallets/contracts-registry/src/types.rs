@@ -33,4 +33,55 @@ pub struct ContractsRegistryResult<T> {
     pub flags: u32,
 }
 
-pub type FetchContractsResult = ContractsRegistryResult<Result<Vec<u8>, ContractAccessError>>;
+/// Source verification state of a registry contract as shown to RPC clients.
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug, Clone, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VerificationState {
+    Unverified,
+    Verified,
+    Failed,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug, Clone, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ContractVerificationInfo {
+    /// SCALE encoded registry contract id
+    pub contract_id: Vec<u8>,
+    pub state: VerificationState,
+    /// Compiler and its version used to reproduce the build, if ever attested
+    pub compiler: Option<Vec<u8>>,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug, Clone, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FetchedContracts {
+    /// SCALE encoded list of the matching registry contracts
+    pub contracts: Vec<u8>,
+    /// Verification status of each contract, in the same order
+    pub verifications: Vec<ContractVerificationInfo>,
+}
+
+/// Royalties earned in one asset, `None` being the native asset.
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug, Clone, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetEarnings {
+    pub asset_id: Option<u32>,
+    pub amount: u128,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug, Clone, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ContractUsageInfo {
+    /// SCALE encoded registry contract id
+    pub contract_id: Vec<u8>,
+    /// Side effects settled for the contract instances
+    pub uses: u64,
+    pub earnings: Vec<AssetEarnings>,
+}
+
+pub type FetchContractsResult =
+    ContractsRegistryResult<Result<FetchedContracts, ContractAccessError>>;
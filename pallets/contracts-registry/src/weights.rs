@@ -40,11 +40,20 @@ pub trait WeightInfo {
     fn add_new_contract() -> Weight;
     fn purge() -> Weight;
     fn fetch_contracts() -> Weight;
+    fn publish_contract(c: u32, s: u32, p: u32, r: u32) -> Weight;
+    fn set_deprecated() -> Weight;
+    fn transfer_package_ownership(p: u32) -> Weight;
+    fn set_verifier() -> Weight;
+    fn attest_source(c: u32, s: u32) -> Weight;
+    fn set_royalty() -> Weight;
 }
 
 /// Weights for pallet_contracts_registry using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // publish_contract and attest_source scale with c bytes of code and s bytes of source, the
+    // former also with the p bytes long package name and the r releases already published under
+    // it. The per byte and per release times are estimates until they're benchmarked.
     fn add_new_contract() -> Weight {
         Weight::from_parts(52_000_000_u64, 0u64)
             .saturating_add(T::DbWeight::get().reads(1_u64))
@@ -60,6 +69,47 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn fetch_contracts() -> Weight {
         Weight::from_parts(53_000_000_u64, 0u64).saturating_add(T::DbWeight::get().reads(4_u64))
     }
+
+    fn publish_contract(c: u32, s: u32, p: u32, r: u32) -> Weight {
+        Weight::from_parts(75_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(3_000_u64, 0u64).saturating_mul(c.into()))
+            .saturating_add(Weight::from_parts(3_000_u64, 0u64).saturating_mul(s.into()))
+            .saturating_add(Weight::from_parts(6_000_u64, 0u64).saturating_mul(p.into()))
+            .saturating_add(Weight::from_parts(180_000_u64, 0u64).saturating_mul(r.into()))
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    fn set_deprecated() -> Weight {
+        Weight::from_parts(30_000_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn transfer_package_ownership(p: u32) -> Weight {
+        Weight::from_parts(28_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(6_000_u64, 0u64).saturating_mul(p.into()))
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn set_verifier() -> Weight {
+        Weight::from_parts(18_000_000_u64, 0u64).saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn attest_source(c: u32, s: u32) -> Weight {
+        Weight::from_parts(45_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(4_000_u64, 0u64).saturating_mul(c.into()))
+            .saturating_add(Weight::from_parts(4_000_u64, 0u64).saturating_mul(s.into()))
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn set_royalty() -> Weight {
+        Weight::from_parts(26_000_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
 }
 
 // For backwards compatibility and tests
@@ -79,4 +129,45 @@ impl WeightInfo for () {
     fn fetch_contracts() -> Weight {
         Weight::from_parts(53_000_000_u64, 0u64).saturating_add(RocksDbWeight::get().reads(4_u64))
     }
+
+    fn publish_contract(c: u32, s: u32, p: u32, r: u32) -> Weight {
+        Weight::from_parts(75_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(3_000_u64, 0u64).saturating_mul(c.into()))
+            .saturating_add(Weight::from_parts(3_000_u64, 0u64).saturating_mul(s.into()))
+            .saturating_add(Weight::from_parts(6_000_u64, 0u64).saturating_mul(p.into()))
+            .saturating_add(Weight::from_parts(180_000_u64, 0u64).saturating_mul(r.into()))
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+
+    fn set_deprecated() -> Weight {
+        Weight::from_parts(30_000_000_u64, 0u64)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn transfer_package_ownership(p: u32) -> Weight {
+        Weight::from_parts(28_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(6_000_u64, 0u64).saturating_mul(p.into()))
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn set_verifier() -> Weight {
+        Weight::from_parts(18_000_000_u64, 0u64).saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn attest_source(c: u32, s: u32) -> Weight {
+        Weight::from_parts(45_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(4_000_u64, 0u64).saturating_mul(c.into()))
+            .saturating_add(Weight::from_parts(4_000_u64, 0u64).saturating_mul(s.into()))
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn set_royalty() -> Weight {
+        Weight::from_parts(26_000_000_u64, 0u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
 }
@@ -134,7 +134,7 @@ use scale_info::TypeInfo;
 use smallvec::Array;
 use sp_runtime::traits::{Convert, Hash, Saturating, StaticLookup, Zero};
 use sp_std::{fmt::Debug, prelude::*};
-use t3rn_primitives::threevm::ThreeVm;
+use t3rn_primitives::threevm::{ModuleOperations, ThreeVm};
 pub use weights::WeightInfo;
 
 pub use crate::{
@@ -1466,6 +1466,7 @@ impl<T: Config> Pallet<T> {
             }
         };
 
+        let mut registry_origin = None;
         let (code, upload_deposit): (WasmCode<T>, BalanceOf<T>) = match code {
             Code::Upload(code) => {
                 let result = Self::try_upload_code(
@@ -1497,7 +1498,10 @@ impl<T: Config> Pallet<T> {
                 match patch3vm::try_instantiate_from_contracts_registry::<T>(
                     &origin, &hash, &schedule,
                 ) {
-                    Ok((module, deposit)) => (WasmCode::Wasm(module), deposit),
+                    Ok((module, deposit)) => {
+                        registry_origin = Some((hash, module.get_author().cloned()));
+                        (WasmCode::Wasm(module), deposit)
+                    },
                     Err(_) => (WasmCode::CodeHash(hash), Default::default()),
                 }
             },
@@ -1513,6 +1517,13 @@ impl<T: Config> Pallet<T> {
         };
 
         let output = InstantiateInput::<T> { code, salt }.run_guarded(common);
+        if let (Ok((account_id, _)), Some((registry_id, author))) =
+            (&output.result, registry_origin)
+        {
+            // Link the instance to its registry contract, so it pays the author royalties
+            let _ = T::ThreeVm::try_persist_author(account_id, author.as_ref());
+            let _ = T::ThreeVm::try_persist_registry_contract(account_id, &registry_id);
+        }
         ContractInstantiateResult {
             result: output
                 .result
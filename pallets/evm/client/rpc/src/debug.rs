@@ -0,0 +1,458 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! `debug_*` tracing RPC, replaying transactions in the runtime with the EVM tracers.
+
+use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
+
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+// Substrate
+use sc_client_api::BlockBackend;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+// Frontier
+use fc_rpc_core::types::{BlockNumber, Bytes, CallRequest};
+use fp_evm::tracing::{
+    opcode_name, CallFrame, CallType, StructLoggerConfig, StructLoggerResult, TraceResult,
+    TracerKind,
+};
+use fp_rpc::EvmTracingRuntimeApi;
+
+use crate::{frontier_backend_client, internal_err};
+
+/// Gas limit of `debug_traceCall` requests that don't set one.
+const DEFAULT_TRACE_CALL_GAS_LIMIT: u64 = 25_000_000;
+
+/// Tracer options of the `debug_*` methods. The `structLogger` is used unless `tracer` is
+/// set to `callTracer`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceParams {
+    pub tracer: Option<String>,
+    pub disable_stack: Option<bool>,
+    pub disable_memory: Option<bool>,
+    pub disable_storage: Option<bool>,
+}
+
+impl TraceParams {
+    fn tracer_kind(params: Option<TraceParams>, max_steps: u32) -> RpcResult<TracerKind> {
+        let params = params.unwrap_or_default();
+        match params.tracer.as_deref() {
+            Some("callTracer") => Ok(TracerKind::CallTracer),
+            None | Some("structLogger") => Ok(TracerKind::StructLogger(StructLoggerConfig {
+                disable_stack: params.disable_stack.unwrap_or(false),
+                disable_memory: params.disable_memory.unwrap_or(false),
+                disable_storage: params.disable_storage.unwrap_or(false),
+                max_steps,
+            })),
+            Some(tracer) => Err(internal_err(format!("unsupported tracer: {}", tracer))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrecompileCall {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<Bytes>,
+    pub args: Vec<H256>,
+}
+
+/// A frame of geth's `callTracer` output. Calls into precompiles carry their decoded input.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Call {
+    #[serde(rename = "type")]
+    pub call_type: &'static str,
+    pub from: H160,
+    pub to: H160,
+    pub value: U256,
+    pub gas: U256,
+    pub gas_used: U256,
+    pub input: Bytes,
+    pub output: Bytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precompile: Option<PrecompileCall>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<Call>,
+}
+
+impl From<CallFrame> for Call {
+    fn from(frame: CallFrame) -> Self {
+        Call {
+            call_type: match frame.call_type {
+                CallType::Call => "CALL",
+                CallType::StaticCall => "STATICCALL",
+                CallType::DelegateCall => "DELEGATECALL",
+                CallType::Create => "CREATE",
+                CallType::Create2 => "CREATE2",
+                CallType::SelfDestruct => "SELFDESTRUCT",
+            },
+            from: frame.from,
+            to: frame.to,
+            value: frame.value,
+            gas: frame.gas.into(),
+            gas_used: frame.gas_used.into(),
+            input: Bytes(frame.input),
+            output: Bytes(frame.output),
+            error: frame
+                .error
+                .map(|error| String::from_utf8_lossy(&error).into_owned()),
+            precompile: frame.precompile.map(|precompile| PrecompileCall {
+                name: String::from_utf8_lossy(&precompile.name).into_owned(),
+                selector: precompile.selector.map(|selector| Bytes(selector.to_vec())),
+                args: precompile.args,
+            }),
+            calls: frame.calls.into_iter().map(Call::from).collect(),
+        }
+    }
+}
+
+/// A step of geth's `structLogger` output.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    pub pc: u64,
+    pub op: &'static str,
+    pub gas: u64,
+    pub gas_cost: u64,
+    pub depth: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<U256>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogs {
+    pub gas: u64,
+    pub failed: bool,
+    pub return_value: String,
+    pub struct_logs: Vec<StructLog>,
+}
+
+impl From<StructLoggerResult> for StructLogs {
+    fn from(result: StructLoggerResult) -> Self {
+        StructLogs {
+            gas: result.gas,
+            failed: result.failed,
+            return_value: hex::encode(result.return_value),
+            struct_logs: result
+                .struct_logs
+                .into_iter()
+                .map(|log| StructLog {
+                    pc: log.pc,
+                    op: opcode_name(log.op),
+                    gas: log.gas,
+                    gas_cost: log.gas_cost,
+                    depth: log.depth,
+                    stack: log.stack.map(|stack| {
+                        stack
+                            .iter()
+                            .map(|item| U256::from_big_endian(item.as_bytes()))
+                            .collect()
+                    }),
+                    memory: log
+                        .memory
+                        .map(|memory| memory.chunks(32).map(hex::encode).collect()),
+                    storage: log.storage.map(|storage| {
+                        storage
+                            .into_iter()
+                            .map(|(key, value)| (hex::encode(key), hex::encode(value)))
+                            .collect()
+                    }),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum TraceResponse {
+    CallTracer(Call),
+    StructLogger(StructLogs),
+}
+
+impl From<TraceResult> for TraceResponse {
+    fn from(result: TraceResult) -> Self {
+        match result {
+            TraceResult::CallTracer(frame) => TraceResponse::CallTracer(frame.into()),
+            TraceResult::StructLogger(result) => TraceResponse::StructLogger(result.into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTrace {
+    pub tx_hash: H256,
+    pub result: TraceResponse,
+}
+
+/// Debug rpc interface.
+#[rpc(server)]
+pub trait DebugApi {
+    /// Replays the transaction in the state its block was executed on and traces it.
+    #[method(name = "debug_traceTransaction")]
+    async fn trace_transaction(
+        &self,
+        transaction_hash: H256,
+        params: Option<TraceParams>,
+    ) -> RpcResult<TraceResponse>;
+
+    /// Replays the block and traces each of its ethereum transactions.
+    #[method(name = "debug_traceBlockByNumber")]
+    async fn trace_block_by_number(
+        &self,
+        number: BlockNumber,
+        params: Option<TraceParams>,
+    ) -> RpcResult<Vec<TransactionTrace>>;
+
+    /// Traces a call on top of the state of the given block, without committing it.
+    #[method(name = "debug_traceCall")]
+    async fn trace_call(
+        &self,
+        request: CallRequest,
+        number: Option<BlockNumber>,
+        params: Option<TraceParams>,
+    ) -> RpcResult<TraceResponse>;
+}
+
+/// Bounds the replays the `debug_*` methods run at once, across all the RPC modules sharing it.
+#[derive(Clone)]
+pub struct DebugPermits(Arc<Semaphore>);
+
+impl DebugPermits {
+    pub fn new(permits: usize) -> Self {
+        DebugPermits(Arc::new(Semaphore::new(permits)))
+    }
+}
+
+pub struct Debug<B: BlockT, C> {
+    client: Arc<C>,
+    backend: Arc<dyn fc_db::BackendReader<B> + Send + Sync>,
+    permits: DebugPermits,
+    max_steps: u32,
+    _marker: PhantomData<B>,
+}
+
+impl<B: BlockT, C> Debug<B, C> {
+    pub fn new(
+        client: Arc<C>,
+        backend: Arc<dyn fc_db::BackendReader<B> + Send + Sync>,
+        permits: DebugPermits,
+        max_steps: u32,
+    ) -> Self {
+        Self {
+            client,
+            backend,
+            permits,
+            max_steps,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B, C> Debug<B, C>
+where
+    B: BlockT,
+    C: HeaderBackend<B> + BlockBackend<B> + 'static,
+{
+    async fn substrate_hash(&self, number: Option<BlockNumber>) -> RpcResult<B::Hash> {
+        let id = frontier_backend_client::native_block_id::<B, C>(
+            self.client.as_ref(),
+            self.backend.as_ref(),
+            number,
+        )
+        .await?
+        .ok_or_else(|| internal_err("block not found"))?;
+
+        self.client
+            .expect_block_hash_from_id(&id)
+            .map_err(|err| internal_err(format!("{:?}", err)))
+    }
+
+    fn header_and_extrinsics(&self, hash: B::Hash) -> RpcResult<(B::Header, Vec<B::Extrinsic>)> {
+        let header = self
+            .client
+            .header(hash)
+            .map_err(|err| internal_err(format!("{:?}", err)))?
+            .ok_or_else(|| internal_err("block header not found"))?;
+        let extrinsics = self
+            .client
+            .block_body(hash)
+            .map_err(|err| internal_err(format!("{:?}", err)))?
+            .ok_or_else(|| internal_err("block body not found"))?;
+        Ok((header, extrinsics))
+    }
+
+    /// Runs `replay` on a blocking thread once one of the permits is free, so replays neither
+    /// stall the RPC server's async workers nor pile up.
+    async fn replay<R, F>(&self, replay: F) -> RpcResult<R>
+    where
+        C: Send + Sync,
+        R: Send + 'static,
+        F: FnOnce(&C) -> RpcResult<R> + Send + 'static,
+    {
+        let _permit = self
+            .permits
+            .0
+            .acquire()
+            .await
+            .map_err(|err| internal_err(format!("{:?}", err)))?;
+        let client = self.client.clone();
+        tokio::task::spawn_blocking(move || replay(client.as_ref()))
+            .await
+            .map_err(|err| internal_err(format!("replay failed: {:?}", err)))?
+    }
+}
+
+#[async_trait]
+impl<B, C> DebugApiServer for Debug<B, C>
+where
+    B: BlockT,
+    C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
+    C::Api: EvmTracingRuntimeApi<B>,
+{
+    async fn trace_transaction(
+        &self,
+        transaction_hash: H256,
+        params: Option<TraceParams>,
+    ) -> RpcResult<TraceResponse> {
+        let tracer = TraceParams::tracer_kind(params, self.max_steps)?;
+
+        let (ethereum_block_hash, _index) = frontier_backend_client::load_transactions::<B, C>(
+            self.client.as_ref(),
+            self.backend.as_ref(),
+            transaction_hash,
+            true,
+        )
+        .await?
+        .ok_or_else(|| internal_err("transaction not found"))?;
+        let hash = frontier_backend_client::load_hash::<B, C>(
+            self.client.as_ref(),
+            self.backend.as_ref(),
+            ethereum_block_hash,
+        )
+        .await?
+        .ok_or_else(|| internal_err("block not found"))?;
+
+        let (header, extrinsics) = self.header_and_extrinsics(hash)?;
+        self.replay(move |client| {
+            client
+                .runtime_api()
+                .trace_transaction(
+                    *header.parent_hash(),
+                    &header,
+                    extrinsics,
+                    transaction_hash,
+                    tracer,
+                )
+                .map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+                .map(TraceResponse::from)
+                .map_err(|err| internal_err(format!("failed to trace transaction: {:?}", err)))
+        })
+        .await
+    }
+
+    async fn trace_block_by_number(
+        &self,
+        number: BlockNumber,
+        params: Option<TraceParams>,
+    ) -> RpcResult<Vec<TransactionTrace>> {
+        let tracer = TraceParams::tracer_kind(params, self.max_steps)?;
+        let hash = self.substrate_hash(Some(number)).await?;
+
+        let (header, extrinsics) = self.header_and_extrinsics(hash)?;
+        let traces = self
+            .replay(move |client| {
+                client
+                    .runtime_api()
+                    .trace_block(*header.parent_hash(), &header, extrinsics, tracer)
+                    .map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+                    .map_err(|err| internal_err(format!("failed to trace block: {:?}", err)))
+            })
+            .await?;
+
+        Ok(traces
+            .into_iter()
+            .map(|(tx_hash, result)| TransactionTrace {
+                tx_hash,
+                result: result.into(),
+            })
+            .collect())
+    }
+
+    async fn trace_call(
+        &self,
+        request: CallRequest,
+        number: Option<BlockNumber>,
+        params: Option<TraceParams>,
+    ) -> RpcResult<TraceResponse> {
+        let tracer = TraceParams::tracer_kind(params, self.max_steps)?;
+        let hash = self.substrate_hash(number).await?;
+
+        let CallRequest {
+            from,
+            to,
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            gas,
+            value,
+            data,
+            nonce,
+            ..
+        } = request;
+
+        self.replay(move |client| {
+            client
+                .runtime_api()
+                .trace_call(
+                    hash,
+                    from.unwrap_or_default(),
+                    to,
+                    data.map(|data| data.0).unwrap_or_default(),
+                    value.unwrap_or_default(),
+                    gas.unwrap_or_else(|| DEFAULT_TRACE_CALL_GAS_LIMIT.into()),
+                    max_fee_per_gas.or(gas_price),
+                    max_priority_fee_per_gas,
+                    nonce,
+                    tracer,
+                )
+                .map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+                .map(TraceResponse::from)
+                .map_err(|err| internal_err(format!("failed to trace call: {:?}", err)))
+        })
+        .await
+    }
+}
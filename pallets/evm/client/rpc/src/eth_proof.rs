@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! `eth_getProof` (EIP-1186) over the Substrate state trie, in the format `fp-evm-proof`
+//! documents and verifies.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+};
+use serde::Serialize;
+// Substrate
+use sc_client_api::ProofProvider;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::hashing::keccak_256;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+// Frontier
+use fc_rpc_core::types::{BlockNumber, Bytes};
+use fp_rpc::{EthereumRuntimeRPCApi, EvmProofRuntimeApi};
+
+use crate::{frontier_backend_client, internal_err};
+
+/// Proof of a storage slot of the account.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageProof {
+    pub key: U256,
+    pub value: U256,
+    pub proof: Vec<Bytes>,
+}
+
+/// EIP-1186 account proof. `storageHash` is the block state root all of the proofs are rooted
+/// at; balance and nonce are proven by the `System::Account` entry of `accountId`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+    pub address: H160,
+    pub account_id: Bytes,
+    pub balance: U256,
+    pub nonce: U256,
+    pub code_hash: H256,
+    pub storage_hash: H256,
+    pub account_proof: Vec<Bytes>,
+    pub system_account_proof: Vec<Bytes>,
+    pub storage_proof: Vec<StorageProof>,
+}
+
+/// Eth proof rpc interface.
+#[rpc(server)]
+pub trait EthProofApi {
+    /// Returns the proofs of the code and the given storage slots of an account.
+    #[method(name = "eth_getProof")]
+    async fn proof(
+        &self,
+        address: H160,
+        storage_keys: Vec<U256>,
+        number: Option<BlockNumber>,
+    ) -> RpcResult<AccountProof>;
+}
+
+pub struct EthProof<B: BlockT, C> {
+    client: Arc<C>,
+    backend: Arc<dyn fc_db::BackendReader<B> + Send + Sync>,
+    _marker: PhantomData<B>,
+}
+
+impl<B: BlockT, C> EthProof<B, C> {
+    pub fn new(client: Arc<C>, backend: Arc<dyn fc_db::BackendReader<B> + Send + Sync>) -> Self {
+        Self {
+            client,
+            backend,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B, C> EthProof<B, C>
+where
+    B: BlockT,
+    C: ProofProvider<B>,
+{
+    fn read_proof(&self, hash: B::Hash, key: &[u8]) -> RpcResult<Vec<Bytes>> {
+        Ok(self
+            .client
+            .read_proof(hash, &mut std::iter::once(key))
+            .map_err(|err| internal_err(format!("Read proof failed: {:?}", err)))?
+            .into_iter_nodes()
+            .map(Bytes)
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<B, C> EthProofApiServer for EthProof<B, C>
+where
+    B: BlockT,
+    C: ProvideRuntimeApi<B> + HeaderBackend<B> + ProofProvider<B> + Send + Sync + 'static,
+    C::Api: EthereumRuntimeRPCApi<B> + EvmProofRuntimeApi<B>,
+{
+    async fn proof(
+        &self,
+        address: H160,
+        storage_keys: Vec<U256>,
+        number: Option<BlockNumber>,
+    ) -> RpcResult<AccountProof> {
+        if number == Some(BlockNumber::Pending) {
+            return Err(internal_err(
+                "Proofs of the pending block are not supported",
+            ))
+        }
+
+        let id = frontier_backend_client::native_block_id::<B, C>(
+            self.client.as_ref(),
+            self.backend.as_ref(),
+            number,
+        )
+        .await?
+        .ok_or_else(|| internal_err("Block not found"))?;
+        let hash = self
+            .client
+            .expect_block_hash_from_id(&id)
+            .map_err(|_| internal_err(format!("Expect block number from id: {id}")))?;
+        let header = self
+            .client
+            .header(hash)
+            .map_err(|err| internal_err(format!("{:?}", err)))?
+            .ok_or_else(|| internal_err("Block header not found"))?;
+
+        let api = self.client.runtime_api();
+        let account = api
+            .account_basic(hash, address)
+            .map_err(|err| internal_err(format!("Fetch account balances failed: {:?}", err)))?;
+        let code = api
+            .account_code_at(hash, address)
+            .map_err(|err| internal_err(format!("Fetch account code failed: {:?}", err)))?;
+        let (evm_pallet, system_pallet) = api
+            .proof_pallet_names(hash)
+            .map_err(|err| internal_err(format!("Fetch pallet names failed: {:?}", err)))?;
+        let account_id = api
+            .mapped_account_id(hash, address)
+            .map_err(|err| internal_err(format!("Fetch mapped account failed: {:?}", err)))?;
+        let account_proof = self.read_proof(
+            hash,
+            &fp_evm_proof::account_codes_key(&evm_pallet, &address),
+        )?;
+        let system_account_proof = self.read_proof(
+            hash,
+            &fp_evm_proof::system_account_key(&system_pallet, &account_id),
+        )?;
+
+        let mut storage_proof = Vec::with_capacity(storage_keys.len());
+        for key in storage_keys {
+            let value = api
+                .storage_at(hash, address, key)
+                .map_err(|err| internal_err(format!("Fetch storage failed: {:?}", err)))?;
+            let mut index = H256::zero();
+            key.to_big_endian(index.as_bytes_mut());
+            storage_proof.push(StorageProof {
+                key,
+                value: U256::from_big_endian(value.as_bytes()),
+                proof: self.read_proof(
+                    hash,
+                    &fp_evm_proof::account_storages_key(&evm_pallet, &address, &index),
+                )?,
+            });
+        }
+
+        Ok(AccountProof {
+            address,
+            account_id: Bytes(account_id),
+            balance: account.balance,
+            nonce: account.nonce,
+            code_hash: H256(keccak_256(&code)),
+            storage_hash: H256::from_slice(header.state_root().as_ref()),
+            account_proof,
+            system_account_proof,
+            storage_proof,
+        })
+    }
+}
@@ -26,7 +26,9 @@
 )]
 #![deny(unused_crate_dependencies)]
 
+mod debug;
 mod eth;
+mod eth_proof;
 mod eth_pubsub;
 mod net;
 mod signer;
@@ -37,10 +39,12 @@ mod web3;
 #[cfg(feature = "txpool")]
 pub use self::txpool::TxPool;
 pub use self::{
+    debug::{Debug, DebugApiServer, DebugPermits, TraceParams},
     eth::{
         format, pending, EstimateGasAdapter, Eth, EthBlockDataCacheTask, EthConfig, EthFilter,
         EthTask,
     },
+    eth_proof::{EthProof, EthProofApiServer},
     eth_pubsub::{EthPubSub, EthereumSubIdProvider},
     net::Net,
     signer::{EthDevSigner, EthSigner},
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.0;
+
+/// @dev The 3VM dispatch precompile address.
+address constant THREE_VM_ADDRESS = 0x0000000000000000000000000000000000002711;
+
+/// @title 3VM dispatch precompile interface
+/// @notice Lets contracts read their local execution state, submit side effects and signals to
+/// the circuit and query the portal. Failures revert with an `Error(string)` reason.
+interface IThreeVm {
+    /// @notice SCALE-encoded local state of the execution, a zero `xtxId` sets up a fresh one.
+    function getState(bytes32 xtxId) external view returns (bytes memory state);
+
+    /// @notice Submits SCALE-encoded side effects to the circuit.
+    /// @param speedMode 0 - Fast, 1 - Rational, 2 - Finalized, 3 - Instant.
+    /// @return executionView SCALE-encoded view of the created execution.
+    function submit(bytes calldata sideEffects, uint8 speedMode) external returns (bytes memory executionView);
+
+    /// @notice Signals the execution to complete or to be killed at `step`.
+    /// @param kind 0 - Complete, 1 - Kill.
+    /// @param killReason 0 - Unhandled, 1 - Codec, 2 - Timeout. Ignored unless `kind` is Kill.
+    function postSignal(bytes32 executionId, uint32 step, uint8 kind, uint8 killReason) external;
+
+    /// @notice Forwards packed portal arguments, the portal selector followed by its arguments.
+    /// @return result SCALE-encoded portal result.
+    function portal(bytes calldata args) external returns (bytes memory result);
+}
@@ -0,0 +1,69 @@
+//! Benchmarks of the 3VM dispatch precompile branches.
+//!
+//! The precompile isn't a pallet, so the benchmarks run against `Pallet`, a stand-in for the
+//! runtime listing them, as `cumulus_pallet_session_benchmarking` does. Each one executes its
+//! branch with a mocked EVM handle. The 3VM may reject the arguments, the dispatch measured
+//! then still decodes, copies and hands them over.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use fp_evm::Context;
+use frame_benchmarking::benchmarks;
+use precompile_util_solidity::testing::MockHandle;
+use sp_core::{H160, U256};
+
+/// Longest forwarded arguments the length-scaled branches are benchmarked with.
+pub const MAX_BENCHMARKED_ARGS_LEN: u32 = 16 * 1024;
+
+pub struct Pallet<T: Config>(PhantomData<T>);
+pub trait Config: pallet_evm::Config {}
+
+fn dispatch<T: Config>(input: Vec<u8>) {
+    let precompile = H160::from_low_u64_be(10001);
+    let mut handle = MockHandle::new(
+        precompile,
+        Context {
+            address: precompile,
+            caller: H160::repeat_byte(0x11),
+            apparent_value: U256::zero(),
+        },
+    );
+    handle.input = input;
+    let _ = ThreeVmDispatch::<T, ()>::execute(&mut handle);
+}
+
+benchmarks! {
+    get_state {
+        let input = EvmDataWriter::new_with_selector(Action::GetState)
+            .write(H256::zero())
+            .build();
+    }: { dispatch::<T>(input) }
+
+    submit {
+        let s in 0 .. MAX_BENCHMARKED_ARGS_LEN;
+        let input = EvmDataWriter::new_with_selector(Action::Submit)
+            .write(Bytes(vec![0xff; s as usize]))
+            .write(0u8)
+            .build();
+    }: { dispatch::<T>(input) }
+
+    post_signal {
+        let input = EvmDataWriter::new_with_selector(Action::PostSignal)
+            .write(H256::zero())
+            .write(0u32)
+            .write(0u8)
+            .write(0u8)
+            .build();
+    }: { dispatch::<T>(input) }
+
+    portal {
+        let a in 5 .. MAX_BENCHMARKED_ARGS_LEN;
+        // `GetLatestFinalizedHeader` of a gateway, padded to `a` bytes
+        let mut args = vec![0u8; a as usize];
+        args[1..5].copy_from_slice(b"roco");
+        let input = EvmDataWriter::new_with_selector(Action::Portal)
+            .write(Bytes(args))
+            .build();
+    }: { dispatch::<T>(input) }
+}
@@ -2,40 +2,212 @@
 
 extern crate alloc;
 
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
+use codec::{Decode, Encode};
 use core::marker::PhantomData;
-use fp_evm::{
-    ExitError, ExitSucceed, Precompile as EvmPrecompile, PrecompileFailure, PrecompileHandle,
-    PrecompileOutput, PrecompileResult,
+use fp_evm::{Precompile as EvmPrecompile, PrecompileFailure, PrecompileHandle, PrecompileResult};
+use pallet_evm::{AddressMapping, GasWeightMapping};
+use precompile_util_solidity::{
+    data::{Bytes, EvmDataReader, EvmDataWriter},
+    handle::PrecompileHandleExt,
+    modifier::FunctionModifier,
+    revert, succeed, EvmResult,
 };
-use t3rn_primitives::threevm::Precompile;
+use sp_core::{hexdisplay::HexDisplay, H256};
+use t3rn_primitives::{
+    threevm::{Precompile, GET_STATE, PORTAL, POST_SIGNAL, SUBMIT},
+    SpeedMode, T3rnCodec,
+};
+use t3rn_sdk_primitives::signal::{ExecutionSignal, KillReason, SignalKind};
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[precompile_util_macro::generate_function_selector]
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    GetState = "getState(bytes32)",
+    Submit = "submit(bytes,uint8)",
+    PostSignal = "postSignal(bytes32,uint32,uint8,uint8)",
+    Portal = "portal(bytes)",
+}
 
-// TODO: build this for the next phase of evm artifacts.
-pub struct ThreeVmDispatch<T> {
-    _marker: PhantomData<T>,
+/// Selector of the solidity `Error(string)` revert reason.
+pub const ERROR_SELECTOR: u32 = 0x08c3_79a0;
+
+/// Revert with `message` ABI-encoded as `Error(string)`.
+pub fn revert_with_message(message: &str) -> PrecompileFailure {
+    revert(
+        EvmDataWriter::new_with_selector(ERROR_SELECTOR)
+            .write(Bytes::from(message))
+            .build(),
+    )
+}
+
+/// Dispatches EVM contract calls to the 3VM precompiles: reading the local state of an
+/// execution, submitting side effects, posting signals and querying the portal.
+pub struct ThreeVmDispatch<T, W = weights::SubstrateWeight<T>> {
+    _marker: PhantomData<(T, W)>,
 }
 
-impl<T> EvmPrecompile for ThreeVmDispatch<T>
+impl<T, W> EvmPrecompile for ThreeVmDispatch<T, W>
 where
     T: pallet_evm::Config,
+    W: WeightInfo,
 {
     fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
-        let input = handle.input();
-        let _target_gas = handle.gas_limit();
-        let _context = handle.context();
+        let selector = handle.read_selector()?;
+        handle.check_function_modifier(match selector {
+            Action::GetState => FunctionModifier::View,
+            _ => FunctionModifier::NonPayable,
+        })?;
+
+        match selector {
+            Action::GetState => Self::get_state(handle),
+            Action::Submit => Self::submit(handle),
+            Action::PostSignal => Self::post_signal(handle),
+            Action::Portal => Self::portal(handle),
+        }
+    }
+}
+
+impl<T, W> ThreeVmDispatch<T, W>
+where
+    T: pallet_evm::Config,
+    W: WeightInfo,
+{
+    fn record_weight(
+        handle: &mut impl PrecompileHandle,
+        weight: frame_support::weights::Weight,
+    ) -> EvmResult {
+        handle.record_cost(<T as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+            weight,
+        ))
+    }
+
+    /// Invokes the 3VM `precompile` on behalf of the caller and returns its output stripped of
+    /// the leading status byte. `Scale` args are prefixed with the caller's mapped account,
+    /// `Rlp` ones with its EVM address.
+    fn invoke(
+        handle: &mut impl PrecompileHandle,
+        name: &str,
+        precompile: u8,
+        codec: T3rnCodec,
+        args: &[u8],
+    ) -> EvmResult<Vec<u8>> {
+        let caller = handle.context().caller;
+        let origin = match codec {
+            T3rnCodec::Scale =>
+                <T as pallet_evm::Config>::AddressMapping::into_account_id(caller).encode(),
+            T3rnCodec::Rlp => caller.as_bytes().to_vec(),
+        };
+        let restructured_args = [&[codec.into()][..], &origin, args].concat();
 
         let mut output = Vec::new();
-        T::ThreeVm::invoke_raw(&55_u8, input, &mut output); // TODO: dummy ptr for now
-
-        if !output.is_empty() {
-            Ok(PrecompileOutput {
-                exit_status: ExitSucceed::Stopped,
-                output,
-            })
-        } else {
-            Err(PrecompileFailure::Error {
-                exit_status: ExitError::Other("invalid output".into()),
-            })
+        T::ThreeVm::invoke_raw(&precompile, &restructured_args, &mut output);
+
+        match output.split_first() {
+            Some((0, result)) => Ok(result.to_vec()),
+            Some((_, error)) => Err(revert_with_message(&format!(
+                "{name} failed: 3VM error 0x{}",
+                HexDisplay::from(&error)
+            ))),
+            None => Err(revert_with_message(&format!("{name} failed: empty output"))),
         }
     }
+
+    fn read_hash(input: &mut EvmDataReader) -> EvmResult<T::Hash> {
+        let word = input.read::<H256>()?;
+        T::Hash::decode(&mut word.as_bytes()).map_err(|_| revert_with_message("invalid hash"))
+    }
+
+    fn get_state(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        Self::record_weight(handle, W::get_state())?;
+
+        let mut input = handle.read_input()?;
+        input.expect_arguments(1)?;
+        // The zero hash sets up a fresh local state instead of loading an execution
+        let xtx_id = Self::read_hash(&mut input)?;
+        let xtx_id = (xtx_id != T::Hash::default()).then_some(xtx_id);
+
+        let state = Self::invoke(
+            handle,
+            "getState",
+            GET_STATE,
+            T3rnCodec::Scale,
+            &xtx_id.encode(),
+        )?;
+        Ok(succeed(EvmDataWriter::new().write(Bytes(state)).build()))
+    }
+
+    fn submit(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let mut input = handle.read_input()?;
+        input.expect_arguments(2)?;
+        // SCALE-encoded `SideEffects`, as built by the t3rn SDK
+        let side_effects: Vec<u8> = input.read::<Bytes>()?.into();
+
+        Self::record_weight(handle, W::submit(side_effects.len() as u32))?;
+
+        let speed_mode = input.read::<u8>()?;
+        let speed_mode = SpeedMode::decode(&mut &[speed_mode][..])
+            .map_err(|_| revert_with_message("invalid speed mode"))?;
+
+        let args = [side_effects, speed_mode.encode()].concat();
+        let execution_view = Self::invoke(handle, "submit", SUBMIT, T3rnCodec::Scale, &args)?;
+        Ok(succeed(
+            EvmDataWriter::new().write(Bytes(execution_view)).build(),
+        ))
+    }
+
+    fn post_signal(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        Self::record_weight(handle, W::post_signal())?;
+
+        let mut input = handle.read_input()?;
+        input.expect_arguments(4)?;
+        let execution_id = Self::read_hash(&mut input)?;
+        let step = input.read::<u32>()?;
+        let kind = input.read::<u8>()?;
+        let kill_reason = input.read::<u8>()?;
+        let kind = match kind {
+            0 => SignalKind::Complete,
+            1 => SignalKind::Kill(
+                KillReason::decode(&mut &[kill_reason][..])
+                    .map_err(|_| revert_with_message("invalid kill reason"))?,
+            ),
+            _ => return Err(revert_with_message("invalid signal kind")),
+        };
+
+        let signal = ExecutionSignal {
+            step,
+            kind,
+            execution_id,
+        };
+        Self::invoke(
+            handle,
+            "postSignal",
+            POST_SIGNAL,
+            T3rnCodec::Scale,
+            &signal.encode(),
+        )?;
+        Ok(succeed(EvmDataWriter::new().build()))
+    }
+
+    fn portal(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let mut input = handle.read_input()?;
+        input.expect_arguments(1)?;
+        // Packed portal `PrecompileArgs`: the portal selector followed by its arguments
+        let args: Vec<u8> = input.read::<Bytes>()?.into();
+
+        Self::record_weight(handle, W::portal(args.len() as u32))?;
+
+        let result = Self::invoke(handle, "portal", PORTAL, T3rnCodec::Rlp, &args)?;
+        Ok(succeed(EvmDataWriter::new().write(Bytes(result)).build()))
+    }
 }
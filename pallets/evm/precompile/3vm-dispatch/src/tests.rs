@@ -0,0 +1,278 @@
+use super::*;
+use crate::mock::{set_three_vm_output, three_vm_invocations, Test};
+use fp_evm::Context;
+use frame_support::weights::Weight;
+use pallet_circuit::weights::WeightInfo as CircuitWeightInfo;
+use precompile_util_solidity::testing::MockHandle;
+use sp_core::{hashing::keccak_256, H160, U256};
+
+type Dispatch = ThreeVmDispatch<Test, ()>;
+
+const CALLER: H160 = H160([0x11; 20]);
+
+fn selector_of(signature: &str) -> u32 {
+    let hash = keccak_256(signature.as_bytes());
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+#[test]
+fn selectors_match_the_solidity_signatures() {
+    assert_eq!(Action::GetState as u32, selector_of("getState(bytes32)"));
+    assert_eq!(Action::Submit as u32, selector_of("submit(bytes,uint8)"));
+    assert_eq!(
+        Action::PostSignal as u32,
+        selector_of("postSignal(bytes32,uint32,uint8,uint8)")
+    );
+    assert_eq!(Action::Portal as u32, selector_of("portal(bytes)"));
+    assert_eq!(ERROR_SELECTOR, selector_of("Error(string)"));
+}
+
+#[test]
+fn errors_revert_with_abi_encoded_reason() {
+    let failure = revert_with_message("submit failed");
+    let fp_evm::PrecompileFailure::Revert { output, .. } = failure else {
+        panic!("expected a revert");
+    };
+
+    assert_eq!(&output[..4], &ERROR_SELECTOR.to_be_bytes());
+    let mut reader = EvmDataReader::new(&output[4..]);
+    let reason: Vec<u8> = reader.read::<Bytes>().unwrap().into();
+    assert_eq!(reason, b"submit failed".to_vec());
+}
+
+#[test]
+fn weights_scale_with_the_dispatched_work() {
+    assert!(
+        <() as WeightInfo>::portal(1_000).ref_time() > <() as WeightInfo>::portal(0).ref_time()
+    );
+    assert!(
+        <() as WeightInfo>::submit(1_000).ref_time() > <() as WeightInfo>::submit(0).ref_time()
+    );
+    assert!(
+        <() as WeightInfo>::submit(0).ref_time()
+            > <() as CircuitWeightInfo>::on_local_trigger().ref_time()
+    );
+}
+
+fn call(input: Vec<u8>) -> (PrecompileResult, MockHandle) {
+    let precompile = H160::from_low_u64_be(10001);
+    let mut handle = MockHandle::new(
+        precompile,
+        Context {
+            address: precompile,
+            caller: CALLER,
+            apparent_value: U256::zero(),
+        },
+    );
+    handle.input = input;
+    let result = Dispatch::execute(&mut handle);
+    (result, handle)
+}
+
+fn gas(weight: Weight) -> u64 {
+    <Test as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight)
+}
+
+fn with_caller(codec: T3rnCodec, args: &[u8]) -> Vec<u8> {
+    [&[codec.into()][..], CALLER.as_bytes(), args].concat()
+}
+
+fn returned_bytes(result: PrecompileResult) -> Vec<u8> {
+    let output = result.expect("dispatch succeeds").output;
+    EvmDataReader::new(&output).read::<Bytes>().unwrap().into()
+}
+
+fn revert_reason(result: PrecompileResult) -> Vec<u8> {
+    let Err(PrecompileFailure::Revert { output, .. }) = result else {
+        panic!("expected a revert");
+    };
+    assert_eq!(&output[..4], &ERROR_SELECTOR.to_be_bytes());
+    EvmDataReader::new(&output[4..])
+        .read::<Bytes>()
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn get_state_loads_the_execution_of_the_caller() {
+    set_three_vm_output(vec![0, 1, 2, 3]);
+    let xtx_id = H256::repeat_byte(7);
+    let (result, handle) = call(
+        EvmDataWriter::new_with_selector(Action::GetState as u32)
+            .write(xtx_id)
+            .build(),
+    );
+
+    assert_eq!(returned_bytes(result), vec![1, 2, 3]);
+    assert_eq!(handle.gas_used, gas(<() as WeightInfo>::get_state()));
+    assert_eq!(
+        three_vm_invocations().last(),
+        Some(&(
+            GET_STATE,
+            with_caller(T3rnCodec::Scale, &Some(xtx_id).encode())
+        ))
+    );
+}
+
+#[test]
+fn get_state_of_the_zero_hash_sets_up_a_fresh_state() {
+    set_three_vm_output(vec![0]);
+    let (result, _) = call(
+        EvmDataWriter::new_with_selector(Action::GetState as u32)
+            .write(H256::zero())
+            .build(),
+    );
+
+    assert_eq!(returned_bytes(result), Vec::<u8>::new());
+    assert_eq!(
+        three_vm_invocations().last(),
+        Some(&(
+            GET_STATE,
+            with_caller(T3rnCodec::Scale, &None::<H256>.encode())
+        ))
+    );
+}
+
+#[test]
+fn submit_forwards_side_effects_and_speed_mode() {
+    set_three_vm_output(vec![0, 9, 9]);
+    let side_effects = vec![4u8, 5, 6];
+    let (result, handle) = call(
+        EvmDataWriter::new_with_selector(Action::Submit as u32)
+            .write(Bytes(side_effects.clone()))
+            .write(1u8)
+            .build(),
+    );
+
+    assert_eq!(returned_bytes(result), vec![9, 9]);
+    assert_eq!(
+        handle.gas_used,
+        gas(<() as WeightInfo>::submit(side_effects.len() as u32))
+    );
+    assert_eq!(
+        three_vm_invocations().last(),
+        Some(&(
+            SUBMIT,
+            with_caller(
+                T3rnCodec::Scale,
+                &[side_effects, SpeedMode::Rational.encode()].concat()
+            )
+        ))
+    );
+}
+
+#[test]
+fn submit_charges_for_the_length_of_the_side_effects() {
+    let submit = |side_effects: Vec<u8>| {
+        call(
+            EvmDataWriter::new_with_selector(Action::Submit as u32)
+                .write(Bytes(side_effects))
+                .write(0u8)
+                .build(),
+        )
+        .1
+        .gas_used
+    };
+
+    assert!(submit(vec![4u8; 1_000]) > submit(vec![4u8]));
+    assert_eq!(
+        submit(vec![4u8; 1_000]),
+        gas(<() as WeightInfo>::submit(1_000))
+    );
+}
+
+#[test]
+fn submit_rejects_unknown_speed_modes() {
+    let invocations = three_vm_invocations().len();
+    let (result, _) = call(
+        EvmDataWriter::new_with_selector(Action::Submit as u32)
+            .write(Bytes(vec![4u8]))
+            .write(9u8)
+            .build(),
+    );
+
+    assert_eq!(revert_reason(result), b"invalid speed mode".to_vec());
+    assert_eq!(three_vm_invocations().len(), invocations);
+}
+
+#[test]
+fn post_signal_forwards_the_execution_signal() {
+    set_three_vm_output(vec![0]);
+    let execution_id = H256::repeat_byte(3);
+    let (result, handle) = call(
+        EvmDataWriter::new_with_selector(Action::PostSignal as u32)
+            .write(execution_id)
+            .write(2u32)
+            .write(1u8)
+            .write(0u8)
+            .build(),
+    );
+
+    assert!(result.expect("signal is posted").output.is_empty());
+    assert_eq!(handle.gas_used, gas(<() as WeightInfo>::post_signal()));
+    let signal = ExecutionSignal {
+        step: 2,
+        kind: SignalKind::Kill(KillReason::decode(&mut &[0u8][..]).unwrap()),
+        execution_id,
+    };
+    assert_eq!(
+        three_vm_invocations().last(),
+        Some(&(POST_SIGNAL, with_caller(T3rnCodec::Scale, &signal.encode())))
+    );
+
+    let (result, _) = call(
+        EvmDataWriter::new_with_selector(Action::PostSignal as u32)
+            .write(execution_id)
+            .write(2u32)
+            .write(2u8)
+            .write(0u8)
+            .build(),
+    );
+    assert_eq!(revert_reason(result), b"invalid signal kind".to_vec());
+}
+
+#[test]
+fn portal_forwards_packed_args_with_the_caller_address() {
+    set_three_vm_output(vec![0, 42]);
+    let args = vec![1u8; 100];
+    let (result, handle) = call(
+        EvmDataWriter::new_with_selector(Action::Portal as u32)
+            .write(Bytes(args.clone()))
+            .build(),
+    );
+
+    assert_eq!(returned_bytes(result), vec![42]);
+    assert_eq!(handle.gas_used, gas(<() as WeightInfo>::portal(100)));
+    assert_eq!(
+        three_vm_invocations().last(),
+        Some(&(PORTAL, with_caller(T3rnCodec::Rlp, &args)))
+    );
+}
+
+#[test]
+fn failed_invocations_revert_with_the_3vm_error() {
+    set_three_vm_output(vec![1, 0xab]);
+    let (result, handle) = call(
+        EvmDataWriter::new_with_selector(Action::Submit as u32)
+            .write(Bytes(vec![4u8]))
+            .write(0u8)
+            .build(),
+    );
+    assert_eq!(
+        revert_reason(result),
+        b"submit failed: 3VM error 0xab".to_vec()
+    );
+    // The dispatched work is charged even when it fails
+    assert_eq!(handle.gas_used, gas(<() as WeightInfo>::submit(1)));
+
+    set_three_vm_output(vec![]);
+    let (result, _) = call(
+        EvmDataWriter::new_with_selector(Action::Portal as u32)
+            .write(Bytes(vec![1u8]))
+            .build(),
+    );
+    assert_eq!(
+        revert_reason(result),
+        b"portal failed: empty output".to_vec()
+    );
+}
@@ -0,0 +1,84 @@
+//! Weights of the 3VM dispatch precompile branches.
+//!
+//! Each branch is benchmarked in benchmarking.rs for the dispatch itself: decoding its ABI
+//! arguments, mapping the caller, handing the arguments over to the 3VM and encoding the result,
+//! scaled by the length of the forwarded arguments where they're unbounded. `submit` adds what
+//! `pallet_circuit` charges for the order it dispatches to. Execution times are estimates until
+//! they're re-run on reference hardware with scripts/benchmark_weights.sh.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use pallet_circuit::weights::WeightInfo as CircuitWeightInfo;
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for the 3VM dispatch precompile.
+pub trait WeightInfo {
+    fn get_state() -> Weight;
+    fn submit(s: u32) -> Weight;
+    fn post_signal() -> Weight;
+    fn portal(a: u32) -> Weight;
+}
+
+/// Weights for the 3VM dispatch precompile, `submit` on top of the circuit weights `C`.
+pub struct SubstrateWeight<T, C = pallet_circuit::weights::SubstrateWeight<T>>(PhantomData<(T, C)>);
+impl<T: frame_system::Config, C: CircuitWeightInfo> WeightInfo for SubstrateWeight<T, C> {
+    /// Storage: `Circuit::XExecSignals` (r:1 w:0)
+    /// Storage: `Circuit::LocalXtxStates` (r:1 w:0)
+    fn get_state() -> Weight {
+        Weight::from_parts(6_120_000_u64, 0u64).saturating_add(T::DbWeight::get().reads(2_u64))
+    }
+
+    /// The range of component `s` is `[0, 16384]`.
+    fn submit(s: u32) -> Weight {
+        Weight::from_parts(5_480_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(4_650_u64, 0u64).saturating_mul(s.into()))
+            .saturating_add(C::on_local_trigger())
+    }
+
+    /// Storage: `Circuit::XExecSignals` (r:1 w:0)
+    /// Storage: `ThreeVm::Signals` (r:1 w:1)
+    fn post_signal() -> Weight {
+        Weight::from_parts(5_910_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    /// Storage: `Portal::Gateways` (r:1 w:0)
+    /// Storage: light client headers (r:2 w:0)
+    /// The range of component `a` is `[5, 16384]`.
+    fn portal(a: u32) -> Weight {
+        Weight::from_parts(5_260_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(4_200_u64, 0u64).saturating_mul(a.into()))
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn get_state() -> Weight {
+        Weight::from_parts(6_120_000_u64, 0u64).saturating_add(RocksDbWeight::get().reads(2_u64))
+    }
+
+    fn submit(s: u32) -> Weight {
+        Weight::from_parts(5_480_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(4_650_u64, 0u64).saturating_mul(s.into()))
+            .saturating_add(<() as CircuitWeightInfo>::on_local_trigger())
+    }
+
+    fn post_signal() -> Weight {
+        Weight::from_parts(5_910_000_u64, 0u64)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn portal(a: u32) -> Weight {
+        Weight::from_parts(5_260_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(4_200_u64, 0u64).saturating_mul(a.into()))
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+    }
+}
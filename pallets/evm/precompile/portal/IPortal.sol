@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.0;
+
+/// @dev The Portal precompile address.
+address constant PORTAL_ADDRESS = 0x0707070707070707070707070707070707070707;
+
+/// @title Portal precompile interface
+/// @notice Reads the light clients of the gateways registered in the t3rn Portal and verifies
+/// inclusion of remote events against them.
+interface IPortal {
+    /// @notice Latest finalized header of the gateway's light client, if it is active.
+    function getLatestFinalizedHeader(bytes4 gatewayId) external view returns (bool isActive, bytes memory header);
+
+    /// @notice Latest finalized height of the gateway's light client, if it is active.
+    function getFinalizedHeight(bytes4 gatewayId) external view returns (bool isActive, uint256 height);
+
+    /// @notice Latest rational height of the gateway's light client, if it is active.
+    function getRationalHeight(bytes4 gatewayId) external view returns (bool isActive, uint256 height);
+
+    /// @notice Latest fast height of the gateway's light client, if it is active.
+    function getFastHeight(bytes4 gatewayId) external view returns (bool isActive, uint256 height);
+
+    /// @notice Verifies the inclusion proof of an event on the gateway.
+    /// @param speedMode 0 - Fast, 1 - Rational, 2 - Finalized, 3 - Instant.
+    /// @param proof Encoded inclusion proof, as expected by the gateway's light client.
+    /// @dev Reverts if the proof doesn't verify. Gas grows with the proof size.
+    function verifyEventInclusion(bytes4 gatewayId, uint8 speedMode, bytes calldata proof)
+        external
+        view
+        returns (uint256 height, bytes memory includingHeader, bytes memory message);
+}
+
+/// @notice Reference consumer of the Portal precompile, used to test its ABI.
+contract PortalTest {
+    IPortal public constant portal = IPortal(PORTAL_ADDRESS);
+
+    event Verified(bytes4 gatewayId, uint256 height, bytes message);
+
+    function finalizedHeight(bytes4 gatewayId) external view returns (uint256) {
+        (bool isActive, uint256 height) = portal.getFinalizedHeight(gatewayId);
+        require(isActive, "light client is not active");
+        return height;
+    }
+
+    function heights(bytes4 gatewayId) external view returns (uint256 fast, uint256 rational, uint256 finalized) {
+        (, fast) = portal.getFastHeight(gatewayId);
+        (, rational) = portal.getRationalHeight(gatewayId);
+        (, finalized) = portal.getFinalizedHeight(gatewayId);
+    }
+
+    function latestFinalizedHeader(bytes4 gatewayId) external view returns (bytes memory) {
+        (bool isActive, bytes memory header) = portal.getLatestFinalizedHeader(gatewayId);
+        require(isActive, "light client is not active");
+        return header;
+    }
+
+    function verifyFinalizedEvent(bytes4 gatewayId, bytes calldata proof) external returns (bytes memory) {
+        (uint256 height,, bytes memory message) = portal.verifyEventInclusion(gatewayId, 2, proof);
+        emit Verified(gatewayId, height, message);
+        return message;
+    }
+}
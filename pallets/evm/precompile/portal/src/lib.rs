@@ -1,17 +1,78 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use fp_evm::{
-    ExitError, ExitSucceed, Precompile as EvmPrecompile, PrecompileFailure, PrecompileHandle,
-    PrecompileOutput, PrecompileResult,
+use codec::Decode;
+use fp_evm::{Precompile as EvmPrecompile, PrecompileHandle, PrecompileResult};
+use frame_support::{
+    sp_runtime::{app_crypto::sp_core, traits::UniqueSaturatedInto},
+    traits::Currency,
 };
-use frame_support::{sp_runtime::app_crypto::sp_core, traits::Currency};
+use frame_system::pallet_prelude::BlockNumberFor;
+use precompile_util_solidity::{
+    data::{Bytes, EvmData, EvmDataReader, EvmDataWriter},
+    handle::PrecompileHandleExt,
+    modifier::FunctionModifier,
+    revert,
+    substrate::RuntimeHelper,
+    succeed, EvmResult,
+};
+use sp_core::{H256, U256};
 use sp_std::{marker::PhantomData, vec::Vec};
 use t3rn_primitives::{
+    portal::{HeaderResult, HeightResult, InclusionReceipt},
     threevm::{Precompile, PORTAL},
-    T3rnCodec,
+    ChainId, SpeedMode, T3rnCodec,
 };
 
-use precompile_util_solidity::data::EvmData;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Flat cost of verifying an inclusion proof, on top of the light client reads.
+pub const VERIFY_INCLUSION_BASE_GAS: u64 = 25_000;
+/// Cost of each byte of an inclusion proof, covering its decoding and hashing.
+pub const VERIFY_INCLUSION_GAS_PER_BYTE: u64 = 16;
+
+#[precompile_util_macro::generate_function_selector]
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    GetLatestFinalizedHeader = "getLatestFinalizedHeader(bytes4)",
+    GetFinalizedHeight = "getFinalizedHeight(bytes4)",
+    GetRationalHeight = "getRationalHeight(bytes4)",
+    GetFastHeight = "getFastHeight(bytes4)",
+    VerifyEventInclusion = "verifyEventInclusion(bytes4,uint8,bytes)",
+}
+
+/// Selectors of the internal `PrecompileArgs` the calls are recoded to.
+const GET_LATEST_FINALIZED_HEADER: u8 = 0;
+const GET_FINALIZED_HEIGHT: u8 = 1;
+const GET_RATIONAL_HEIGHT: u8 = 2;
+const GET_FAST_HEIGHT: u8 = 3;
+const VERIFY_EVENT_INCLUSION: u8 = 4;
+
+/// Gas charged for verifying an inclusion proof of `proof_len` bytes.
+pub fn verify_inclusion_gas(proof_len: usize) -> u64 {
+    VERIFY_INCLUSION_BASE_GAS
+        .saturating_add(VERIFY_INCLUSION_GAS_PER_BYTE.saturating_mul(proof_len as u64))
+}
+
+/// Reads a `bytes4` argument, left-aligned in its 32-byte word.
+fn read_gateway_id(input: &mut EvmDataReader) -> EvmResult<ChainId> {
+    let word = input.read::<H256>()?;
+    let mut gateway_id = [0u8; 4];
+    gateway_id.copy_from_slice(&word.as_bytes()[..4]);
+    Ok(gateway_id)
+}
+
+fn block_number_to_u256<T: frame_system::Config>(block_number: BlockNumberFor<T>) -> U256 {
+    let block_number: u128 = block_number.unique_saturated_into();
+    U256::from(block_number)
+}
+
+fn read_speed_mode(input: &mut EvmDataReader) -> EvmResult<SpeedMode> {
+    let speed_mode = input.read::<u8>()?;
+    SpeedMode::decode(&mut &[speed_mode][..]).map_err(|_| revert("invalid speed mode"))
+}
 
 pub struct PortalPrecompile<T>(PhantomData<T>);
 
@@ -32,31 +93,117 @@ where
     >,
 {
     fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
-        let input = handle.input();
-        let _target_gas = handle.gas_limit();
-        let _context = handle.context();
-        let mut output = Vec::new();
-        let callee = handle.context().caller;
+        let selector = handle.read_selector()?;
+        handle.check_function_modifier(FunctionModifier::View)?;
+
+        match selector {
+            Action::GetLatestFinalizedHeader => Self::latest_finalized_header(handle),
+            Action::GetFinalizedHeight => Self::height(handle, GET_FINALIZED_HEIGHT),
+            Action::GetRationalHeight => Self::height(handle, GET_RATIONAL_HEIGHT),
+            Action::GetFastHeight => Self::height(handle, GET_FAST_HEIGHT),
+            Action::VerifyEventInclusion => Self::verify_event_inclusion(handle),
+        }
+    }
+}
 
-        let restructured_args = [&[T3rnCodec::Rlp.into()][..], callee.as_bytes(), input].concat();
+impl<T> PortalPrecompile<T>
+where
+    T: pallet_evm::Config + frame_system::Config,
+{
+    /// Invokes the portal with the packed `PrecompileArgs` and returns its SCALE-encoded result
+    /// stripped of the leading status byte.
+    fn invoke_portal(handle: &mut impl PrecompileHandle, args: &[u8]) -> EvmResult<Vec<u8>> {
+        let caller = handle.context().caller;
+        let restructured_args = [&[T3rnCodec::Rlp.into()][..], caller.as_bytes(), args].concat();
 
+        let mut output = Vec::new();
         T::ThreeVm::invoke_raw(&PORTAL, &restructured_args, &mut output);
 
-        if let Some(result_byte) = output.first() {
-            if *result_byte == 0 {
-                Ok(PrecompileOutput {
-                    exit_status: ExitSucceed::Returned,
-                    output,
-                })
-            } else {
-                Err(PrecompileFailure::Error {
-                    exit_status: ExitError::Other("invalid output".into()),
-                })
-            }
-        } else {
-            Err(PrecompileFailure::Error {
-                exit_status: ExitError::Other("Empty buffer".into()),
-            })
+        match output.split_first() {
+            Some((0, result)) => Ok(result.to_vec()),
+            Some(_) => Err(revert("portal call failed")),
+            None => Err(revert("empty portal output")),
         }
     }
+
+    fn height(handle: &mut impl PrecompileHandle, portal_selector: u8) -> PrecompileResult {
+        handle.record_cost(RuntimeHelper::<T>::db_read_gas_cost().saturating_mul(2))?;
+
+        let mut input = handle.read_input()?;
+        input.expect_arguments(1)?;
+        let gateway_id = read_gateway_id(&mut input)?;
+
+        let output = Self::invoke_portal(handle, &[&[portal_selector][..], &gateway_id].concat())?;
+        let (is_active, height) = match HeightResult::<BlockNumberFor<T>>::decode(&mut &output[..])
+            .map_err(|_| revert("invalid portal height"))?
+        {
+            HeightResult::Height(height) => (true, block_number_to_u256::<T>(height)),
+            HeightResult::NotActive => (false, U256::zero()),
+        };
+
+        Ok(succeed(
+            EvmDataWriter::new().write(is_active).write(height).build(),
+        ))
+    }
+
+    fn latest_finalized_header(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        handle.record_cost(RuntimeHelper::<T>::db_read_gas_cost().saturating_mul(2))?;
+
+        let mut input = handle.read_input()?;
+        input.expect_arguments(1)?;
+        let gateway_id = read_gateway_id(&mut input)?;
+
+        let output = Self::invoke_portal(
+            handle,
+            &[&[GET_LATEST_FINALIZED_HEADER][..], &gateway_id].concat(),
+        )?;
+        let (is_active, header) = match HeaderResult::decode(&mut &output[..])
+            .map_err(|_| revert("invalid portal header"))?
+        {
+            HeaderResult::Header(header) => (true, header),
+            HeaderResult::NotActive => (false, Vec::new()),
+        };
+
+        Ok(succeed(
+            EvmDataWriter::new()
+                .write(is_active)
+                .write(Bytes(header))
+                .build(),
+        ))
+    }
+
+    fn verify_event_inclusion(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let mut input = handle.read_input()?;
+        input.expect_arguments(3)?;
+        let gateway_id = read_gateway_id(&mut input)?;
+        let speed_mode = read_speed_mode(&mut input)?;
+        let proof: Vec<u8> = input.read::<Bytes>()?.into();
+
+        handle.record_cost(
+            RuntimeHelper::<T>::db_read_gas_cost()
+                .saturating_mul(2)
+                .saturating_add(verify_inclusion_gas(proof.len())),
+        )?;
+
+        // No execution source, the event may have been emitted by any contract
+        let args = [
+            &[VERIFY_EVENT_INCLUSION][..],
+            &gateway_id,
+            &[speed_mode as u8],
+            H256::zero().as_bytes(),
+            &proof,
+        ]
+        .concat();
+        let output = Self::invoke_portal(handle, &args)?;
+        let receipt = InclusionReceipt::<BlockNumberFor<T>>::decode(&mut &output[..])
+            .map_err(|_| revert("invalid inclusion receipt"))?;
+
+        Ok(succeed(
+            EvmDataWriter::new()
+                .write(block_number_to_u256::<T>(receipt.height))
+                .write(Bytes(receipt.including_header))
+                .write(Bytes(receipt.message))
+                .build(),
+        ))
+    }
 }
@@ -0,0 +1,296 @@
+use super::*;
+use crate::mock::{set_three_vm_output, three_vm_invocations, Test};
+use codec::Encode;
+use fp_evm::{Context, PrecompileFailure};
+use precompile_util_solidity::testing::MockHandle;
+use sp_core::{hashing::keccak_256, H160};
+use t3rn_primitives::portal::PrecompileArgs;
+
+type Portal = PortalPrecompile<Test>;
+
+const CALLER: H160 = H160([0x11; 20]);
+const GATEWAY: ChainId = *b"roco";
+
+fn selector_of(signature: &str) -> u32 {
+    let hash = keccak_256(signature.as_bytes());
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+#[test]
+fn selectors_match_the_iportal_interface() {
+    assert_eq!(
+        Action::GetLatestFinalizedHeader as u32,
+        selector_of("getLatestFinalizedHeader(bytes4)")
+    );
+    assert_eq!(
+        Action::GetFinalizedHeight as u32,
+        selector_of("getFinalizedHeight(bytes4)")
+    );
+    assert_eq!(
+        Action::GetRationalHeight as u32,
+        selector_of("getRationalHeight(bytes4)")
+    );
+    assert_eq!(
+        Action::GetFastHeight as u32,
+        selector_of("getFastHeight(bytes4)")
+    );
+    assert_eq!(
+        Action::VerifyEventInclusion as u32,
+        selector_of("verifyEventInclusion(bytes4,uint8,bytes)")
+    );
+}
+
+#[test]
+fn reads_bytes4_and_speed_mode_arguments() {
+    let mut gateway_word = [0u8; 32];
+    gateway_word[..4].copy_from_slice(b"roco");
+    let input = EvmDataWriter::new()
+        .write(H256(gateway_word))
+        .write(1u8)
+        .write(9u8)
+        .build();
+    let mut reader = EvmDataReader::new(&input);
+
+    assert_eq!(read_gateway_id(&mut reader).unwrap(), *b"roco");
+    assert_eq!(read_speed_mode(&mut reader).unwrap(), SpeedMode::Rational);
+    assert!(read_speed_mode(&mut reader).is_err());
+}
+
+#[test]
+fn inclusion_gas_grows_with_proof_size() {
+    assert_eq!(verify_inclusion_gas(0), VERIFY_INCLUSION_BASE_GAS);
+    assert_eq!(
+        verify_inclusion_gas(1_000),
+        VERIFY_INCLUSION_BASE_GAS + 1_000 * VERIFY_INCLUSION_GAS_PER_BYTE
+    );
+    assert_eq!(verify_inclusion_gas(usize::MAX), u64::MAX);
+}
+
+fn call_with_value(input: Vec<u8>, value: U256) -> (PrecompileResult, MockHandle) {
+    let precompile = H160([7u8; 20]);
+    let mut handle = MockHandle::new(
+        precompile,
+        Context {
+            address: precompile,
+            caller: CALLER,
+            apparent_value: value,
+        },
+    );
+    handle.input = input;
+    let result = Portal::execute(&mut handle);
+    (result, handle)
+}
+
+fn call(input: Vec<u8>) -> (PrecompileResult, MockHandle) {
+    call_with_value(input, U256::zero())
+}
+
+fn gateway_word(gateway_id: ChainId) -> H256 {
+    let mut word = [0u8; 32];
+    word[..4].copy_from_slice(&gateway_id);
+    H256(word)
+}
+
+fn with_gateway(action: Action) -> Vec<u8> {
+    EvmDataWriter::new_with_selector(action)
+        .write(gateway_word(GATEWAY))
+        .build()
+}
+
+fn reads_gas() -> u64 {
+    RuntimeHelper::<Test>::db_read_gas_cost().saturating_mul(2)
+}
+
+fn revert_reason(result: PrecompileResult) -> Vec<u8> {
+    let Err(PrecompileFailure::Revert { output, .. }) = result else {
+        panic!("expected a revert");
+    };
+    output
+}
+
+/// Decodes the last portal call handed to the 3VM the way the 3VM does: strips the codec and
+/// the caller, then recodes the RLP packed arguments to the portal `PrecompileArgs`.
+fn last_portal_call() -> PrecompileArgs {
+    let (precompile, args) = three_vm_invocations()
+        .last()
+        .cloned()
+        .expect("portal is invoked");
+    assert_eq!(precompile, PORTAL);
+    assert_eq!(args[0], u8::from(T3rnCodec::Rlp));
+    assert_eq!(&args[1..21], CALLER.as_bytes());
+    PrecompileArgs::recode_to_scale_and_decode(&T3rnCodec::Rlp, &args[21..])
+        .expect("portal call recodes to SCALE")
+}
+
+#[test]
+fn get_latest_finalized_header_returns_the_header_of_active_gateways() {
+    set_three_vm_output([vec![0], HeaderResult::Header(vec![1, 2, 3]).encode()].concat());
+    let (result, handle) = call(with_gateway(Action::GetLatestFinalizedHeader));
+
+    assert_eq!(
+        result.expect("header is read").output,
+        EvmDataWriter::new()
+            .write(true)
+            .write(Bytes(vec![1, 2, 3]))
+            .build()
+    );
+    assert_eq!(handle.gas_used, reads_gas());
+    assert_eq!(
+        last_portal_call(),
+        PrecompileArgs::GetLatestFinalizedHeader(GATEWAY)
+    );
+}
+
+#[test]
+fn get_latest_finalized_header_of_inactive_gateways_is_empty() {
+    set_three_vm_output([vec![0], HeaderResult::NotActive.encode()].concat());
+    let (result, _) = call(with_gateway(Action::GetLatestFinalizedHeader));
+
+    assert_eq!(
+        result.expect("header is read").output,
+        EvmDataWriter::new()
+            .write(false)
+            .write(Bytes(Vec::new()))
+            .build()
+    );
+}
+
+#[test]
+fn heights_are_read_at_their_speed_mode() {
+    for (action, expected_call) in [
+        (
+            Action::GetFinalizedHeight,
+            PrecompileArgs::GetFinalizedHeight(GATEWAY),
+        ),
+        (
+            Action::GetRationalHeight,
+            PrecompileArgs::GetRationalHeight(GATEWAY),
+        ),
+        (
+            Action::GetFastHeight,
+            PrecompileArgs::GetFastHeight(GATEWAY),
+        ),
+    ] {
+        set_three_vm_output([vec![0], HeightResult::Height(7u64).encode()].concat());
+        let (result, handle) = call(with_gateway(action));
+
+        assert_eq!(
+            result.expect("height is read").output,
+            EvmDataWriter::new()
+                .write(true)
+                .write(U256::from(7))
+                .build()
+        );
+        assert_eq!(handle.gas_used, reads_gas());
+        assert_eq!(last_portal_call(), expected_call);
+    }
+}
+
+#[test]
+fn heights_of_inactive_gateways_are_zero() {
+    set_three_vm_output([vec![0], HeightResult::<u64>::NotActive.encode()].concat());
+    let (result, _) = call(with_gateway(Action::GetFinalizedHeight));
+
+    assert_eq!(
+        result.expect("height is read").output,
+        EvmDataWriter::new()
+            .write(false)
+            .write(U256::zero())
+            .build()
+    );
+}
+
+#[test]
+fn verify_event_inclusion_returns_the_inclusion_receipt() {
+    let receipt = InclusionReceipt::<u64> {
+        height: 5,
+        including_header: vec![1],
+        message: vec![2, 3],
+    };
+    set_three_vm_output([vec![0], receipt.encode()].concat());
+    let proof = vec![4u8; 40];
+    let (result, handle) = call(
+        EvmDataWriter::new_with_selector(Action::VerifyEventInclusion)
+            .write(gateway_word(GATEWAY))
+            .write(SpeedMode::Rational as u8)
+            .write(Bytes(proof.clone()))
+            .build(),
+    );
+
+    assert_eq!(
+        result.expect("inclusion is verified").output,
+        EvmDataWriter::new()
+            .write(U256::from(5))
+            .write(Bytes(vec![1]))
+            .write(Bytes(vec![2, 3]))
+            .build()
+    );
+    assert_eq!(
+        handle.gas_used,
+        reads_gas() + verify_inclusion_gas(proof.len())
+    );
+    assert_eq!(
+        last_portal_call(),
+        PrecompileArgs::VerifyEventInclusion(GATEWAY, SpeedMode::Rational, [0u8; 32], proof)
+    );
+}
+
+#[test]
+fn verify_event_inclusion_rejects_unknown_speed_modes() {
+    let (result, _) = call(
+        EvmDataWriter::new_with_selector(Action::VerifyEventInclusion)
+            .write(gateway_word(GATEWAY))
+            .write(9u8)
+            .write(Bytes(vec![4u8]))
+            .build(),
+    );
+
+    assert_eq!(revert_reason(result), b"invalid speed mode".to_vec());
+    assert!(three_vm_invocations().is_empty());
+}
+
+#[test]
+fn failed_portal_calls_revert() {
+    set_three_vm_output(vec![1, 0]);
+    let (result, _) = call(with_gateway(Action::GetFinalizedHeight));
+    assert_eq!(revert_reason(result), b"portal call failed".to_vec());
+
+    set_three_vm_output(Vec::new());
+    let (result, _) = call(with_gateway(Action::GetFinalizedHeight));
+    assert_eq!(revert_reason(result), b"empty portal output".to_vec());
+}
+
+#[test]
+fn malformed_portal_results_revert() {
+    set_three_vm_output(vec![0, 9]);
+    let (result, _) = call(with_gateway(Action::GetFinalizedHeight));
+    assert_eq!(revert_reason(result), b"invalid portal height".to_vec());
+
+    set_three_vm_output(vec![0, 9]);
+    let (result, _) = call(with_gateway(Action::GetLatestFinalizedHeader));
+    assert_eq!(revert_reason(result), b"invalid portal header".to_vec());
+
+    set_three_vm_output(vec![0]);
+    let (result, _) = call(
+        EvmDataWriter::new_with_selector(Action::VerifyEventInclusion)
+            .write(gateway_word(GATEWAY))
+            .write(SpeedMode::Finalized as u8)
+            .write(Bytes(vec![4u8]))
+            .build(),
+    );
+    assert_eq!(revert_reason(result), b"invalid inclusion receipt".to_vec());
+}
+
+#[test]
+fn calls_with_value_or_missing_arguments_are_rejected_before_the_portal() {
+    let (result, _) = call_with_value(with_gateway(Action::GetFastHeight), U256::one());
+    assert!(result.is_err());
+
+    let (result, _) = call(EvmDataWriter::new_with_selector(Action::GetFastHeight).build());
+    assert!(result.is_err());
+
+    let (result, _) = call(vec![0xde, 0xad, 0xbe, 0xef]);
+    assert!(result.is_err());
+
+    assert!(three_vm_invocations().is_empty());
+}
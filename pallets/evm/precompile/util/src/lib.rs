@@ -6,6 +6,7 @@ pub use pallet_3vm_evm_primitives::{
 
 use frame_support::traits::Currency;
 use pallet_3vm_evm_primitives::{ExitError, PrecompileFailure};
+use pallet_evm_precompile_3vm_dispatch::ThreeVmDispatch;
 pub use pallet_evm_precompile_modexp::Modexp;
 pub use pallet_evm_precompile_sha3fips::{Sha3FIPS256, Sha3FIPS512};
 pub use pallet_evm_precompile_simple::{
@@ -27,6 +28,71 @@ fn hash(a: &u64) -> H160 {
     H160::from_low_u64_be(*a)
 }
 
+/// Precompiles of the t3rn precompile set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum T3rnPrecompile {
+    ECRecover,
+    Sha256,
+    Ripemd160,
+    Identity,
+    Modexp,
+    Sha3FIPS256,
+    Sha3FIPS512,
+    ECRecoverPublicKey,
+    ThreeVmDispatch,
+    Portal,
+    Vacuum,
+    Tokens,
+}
+
+impl T3rnPrecompile {
+    /// Name of the precompile, used to label the precompile frames of traces.
+    pub fn label(&self) -> &'static [u8] {
+        match self {
+            T3rnPrecompile::ECRecover => b"ECRecover",
+            T3rnPrecompile::Sha256 => b"Sha256",
+            T3rnPrecompile::Ripemd160 => b"Ripemd160",
+            T3rnPrecompile::Identity => b"Identity",
+            T3rnPrecompile::Modexp => b"Modexp",
+            T3rnPrecompile::Sha3FIPS256 => b"Sha3FIPS256",
+            T3rnPrecompile::Sha3FIPS512 => b"Sha3FIPS512",
+            T3rnPrecompile::ECRecoverPublicKey => b"ECRecoverPublicKey",
+            T3rnPrecompile::ThreeVmDispatch => b"ThreeVmDispatch",
+            T3rnPrecompile::Portal => b"Portal",
+            T3rnPrecompile::Vacuum => b"Vacuum",
+            T3rnPrecompile::Tokens => b"Tokens",
+        }
+    }
+}
+
+/// Precompile living at `address`, the address table both the precompile set and the trace
+/// labels read.
+pub fn precompile_at(address: H160) -> Option<T3rnPrecompile> {
+    match address {
+        // Ethereum precompiles
+        a if a == hash(&1) => Some(T3rnPrecompile::ECRecover),
+        a if a == hash(&2) => Some(T3rnPrecompile::Sha256),
+        a if a == hash(&3) => Some(T3rnPrecompile::Ripemd160),
+        a if a == hash(&4) => Some(T3rnPrecompile::Identity),
+        a if a == hash(&5) => Some(T3rnPrecompile::Modexp),
+        // Non-Ethereum precompiles
+        a if a == hash(&101) => Some(T3rnPrecompile::Sha3FIPS256),
+        a if a == hash(&102) => Some(T3rnPrecompile::Sha3FIPS512),
+        a if a == hash(&103) => Some(T3rnPrecompile::ECRecoverPublicKey),
+        // t3rn precompiles
+        a if a == hash(&10001) => Some(T3rnPrecompile::ThreeVmDispatch),
+        a if H160([7u8; 20]) == a => Some(T3rnPrecompile::Portal),
+        a if H160([8u8; 20]) == a => Some(T3rnPrecompile::Vacuum),
+        a if &a.to_fixed_bytes()[0..16] == TOKENS_PRECOMPILE_PREFIX => Some(T3rnPrecompile::Tokens),
+        _ => None,
+    }
+}
+
+/// Name of the precompile living at `address`, used to label the precompile frames of traces.
+pub fn precompile_label(address: H160) -> Option<&'static [u8]> {
+    precompile_at(address).map(|precompile| precompile.label())
+}
+
 // Precompile set that will be used in production. Allows
 #[derive(Debug, Default, Clone, Copy)]
 pub struct T3rnPrecompiles<T>(PhantomData<T>);
@@ -60,34 +126,21 @@ where
     VacuumPrecompile<T>: Precompile,
 {
     fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
-        let address = handle.code_address();
-        match address {
-            // Ethereum precompiles
-            a if H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]) == a =>
-                Some(<ECRecover as Precompile>::execute(handle)),
-            a if H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]) == a =>
-                Some(<Sha256 as Precompile>::execute(handle)),
-            a if H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3]) == a =>
-                Some(<Ripemd160 as Precompile>::execute(handle)),
-            a if H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4]) == a =>
-                Some(<Identity as Precompile>::execute(handle)),
-            a if H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5]) == a =>
-                Some(<Modexp as Precompile>::execute(handle)),
-            // Non-Ethereum precompiles
-            a if H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 101]) == a =>
-                Some(<Sha3FIPS256 as Precompile>::execute(handle)),
-            a if H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 102]) == a =>
-                Some(<Sha3FIPS512 as Precompile>::execute(handle)),
-            a if H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 103]) == a =>
-                Some(<ECRecoverPublicKey as Precompile>::execute(handle)),
-            // t3rn precompiles
-            a if H160([7u8; 20]) == a => Some(PortalPrecompile::<T>::execute(handle)),
-            a if H160([8u8; 20]) == a => Some(VacuumPrecompile::<T>::execute(handle)),
-            a if &a.to_fixed_bytes()[0..16] == TOKENS_PRECOMPILE_PREFIX =>
-                Some(TokensPrecompile::<T>::execute(handle)),
-            // Default
-            _ => None,
-        }
+        Some(match precompile_at(handle.code_address())? {
+            T3rnPrecompile::ECRecover => <ECRecover as Precompile>::execute(handle),
+            T3rnPrecompile::Sha256 => <Sha256 as Precompile>::execute(handle),
+            T3rnPrecompile::Ripemd160 => <Ripemd160 as Precompile>::execute(handle),
+            T3rnPrecompile::Identity => <Identity as Precompile>::execute(handle),
+            T3rnPrecompile::Modexp => <Modexp as Precompile>::execute(handle),
+            T3rnPrecompile::Sha3FIPS256 => <Sha3FIPS256 as Precompile>::execute(handle),
+            T3rnPrecompile::Sha3FIPS512 => <Sha3FIPS512 as Precompile>::execute(handle),
+            T3rnPrecompile::ECRecoverPublicKey =>
+                <ECRecoverPublicKey as Precompile>::execute(handle),
+            T3rnPrecompile::ThreeVmDispatch => ThreeVmDispatch::<T>::execute(handle),
+            T3rnPrecompile::Portal => PortalPrecompile::<T>::execute(handle),
+            T3rnPrecompile::Vacuum => VacuumPrecompile::<T>::execute(handle),
+            T3rnPrecompile::Tokens => TokensPrecompile::<T>::execute(handle),
+        })
     }
 
     fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
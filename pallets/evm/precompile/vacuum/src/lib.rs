@@ -7,7 +7,10 @@ use fp_evm::{
 use frame_support::{sp_runtime::app_crypto::sp_core, traits::Currency};
 use sp_std::{marker::PhantomData, vec::Vec};
 use t3rn_primitives::{
-    threevm::{Precompile, PORTAL},
+    threevm::{
+        Precompile, VACUUM_3D_ORDER, VACUUM_CONFIRM, VACUUM_ORDER, VACUUM_SUBMIT_CORRECTNESS_PROOF,
+        VACUUM_SUBMIT_FAULT_PROOF, VACUUM_TELEPORT_ORDER,
+    },
     T3rnCodec,
 };
 
@@ -16,12 +19,12 @@ use precompile_util_solidity::data::EvmData;
 pub struct VacuumPrecompile<T>(PhantomData<T>);
 
 pub enum VacuumAction {
-    VacuumOrder = 90,
-    Vacuum3DOrder = 91,
-    VacuumConfirm = 92,
-    VacuumSubmitCorrectnessProof = 93,
-    VacuumSubmitFaultProof = 94,
-    VacuumTeleportOrder = 96,
+    VacuumOrder = VACUUM_ORDER as isize,
+    Vacuum3DOrder = VACUUM_3D_ORDER as isize,
+    VacuumConfirm = VACUUM_CONFIRM as isize,
+    VacuumSubmitCorrectnessProof = VACUUM_SUBMIT_CORRECTNESS_PROOF as isize,
+    VacuumSubmitFaultProof = VACUUM_SUBMIT_FAULT_PROOF as isize,
+    VacuumTeleportOrder = VACUUM_TELEPORT_ORDER as isize,
 }
 
 impl<T> EvmPrecompile for VacuumPrecompile<T>
@@ -47,28 +50,25 @@ where
         let mut output = Vec::new();
         let callee = handle.context().caller;
 
-        let restructured_args = [&[T3rnCodec::Rlp.into()][..], callee.as_bytes(), input].concat();
-
-        // Assume first byte of input is the action selector
-        let action = match restructured_args.first() {
-            Some(byte) => match byte {
-                0 => VacuumAction::VacuumOrder,
-                1 => VacuumAction::Vacuum3DOrder,
-                2 => VacuumAction::VacuumConfirm,
-                3 => VacuumAction::VacuumSubmitCorrectnessProof,
-                4 => VacuumAction::VacuumSubmitFaultProof,
-                5 => VacuumAction::VacuumTeleportOrder,
-                _ =>
-                    return Err(PrecompileFailure::Error {
-                        exit_status: ExitError::Other("Invalid action selector".into()),
-                    }),
-            },
-            None =>
+        // First byte of input is the action selector, followed by its packed arguments
+        let (selector, args) = input.split_first().ok_or(PrecompileFailure::Error {
+            exit_status: ExitError::Other("Empty buffer".into()),
+        })?;
+        let action = match selector {
+            0 => VacuumAction::VacuumOrder,
+            1 => VacuumAction::Vacuum3DOrder,
+            2 => VacuumAction::VacuumConfirm,
+            3 => VacuumAction::VacuumSubmitCorrectnessProof,
+            4 => VacuumAction::VacuumSubmitFaultProof,
+            5 => VacuumAction::VacuumTeleportOrder,
+            _ =>
                 return Err(PrecompileFailure::Error {
-                    exit_status: ExitError::Other("Empty buffer".into()),
+                    exit_status: ExitError::Other("Invalid action selector".into()),
                 }),
         };
 
+        let restructured_args = [&[T3rnCodec::Rlp.into()][..], callee.as_bytes(), args].concat();
+
         T::ThreeVm::invoke_raw(&(action as u8), &restructured_args, &mut output);
 
         if let Some(result_byte) = output.first() {
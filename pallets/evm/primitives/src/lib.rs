@@ -19,6 +19,7 @@
 #![deny(unused_crate_dependencies)]
 
 mod precompile;
+pub mod tracing;
 mod validation;
 
 use circuit_runtime_types::{AssetId, EvmAddress};
@@ -0,0 +1,292 @@
+//! Verifier of the 3VM EVM state proofs served by `eth_getProof`.
+//!
+//! t3rn keeps the EVM state in the Substrate state trie rather than in an Ethereum Merkle
+//! Patricia Trie, so the EIP-1186 response carries Substrate storage proofs instead:
+//!
+//! - `accountProof` proves the `AccountCodes` entry of the address, stored under
+//!   `twox128(evm_pallet) ++ twox128("AccountCodes") ++ blake2_128(address) ++ address` as the
+//!   SCALE-encoded code. A missing entry proves the address has no code.
+//! - `systemAccountProof` proves the `System::Account` entry of `accountId`, the Substrate
+//!   account the address maps to, stored under `twox128(system_pallet) ++ twox128("Account") ++
+//!   blake2_128(account_id) ++ account_id`. Its nonce is the account's nonce, and `balance` the
+//!   part of its free balance that can be spent without reaping it.
+//! - each `storageProof` proves the `AccountStorages` entry of the slot, stored under
+//!   `twox128(evm_pallet) ++ twox128("AccountStorages") ++ blake2_128(address) ++ address ++
+//!   blake2_128(slot) ++ slot` as the raw 32-byte value. A missing entry proves a zero value.
+//! - `storageHash` is the block `state_root` all of the proofs are rooted at, and `codeHash`
+//!   the keccak hash of the proven code.
+//!
+//! `evm_pallet` and `system_pallet` are the names the EVM and System pallets are registered
+//! under in the runtime, `Evm` and `System` in the t3rn runtimes. Proofs are the set of trie
+//! nodes read while looking the key up, as produced by the node's `read_proof`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use scale_codec::Decode;
+use sp_core::{
+    hashing::{blake2_128, keccak_256, twox_128},
+    Blake2Hasher, H160, H256,
+};
+use sp_std::vec::Vec;
+use sp_trie::{LayoutV1, StorageProof};
+
+pub const EVM_ACCOUNT_CODES: &[u8] = b"AccountCodes";
+pub const EVM_ACCOUNT_STORAGES: &[u8] = b"AccountStorages";
+pub const SYSTEM_ACCOUNT: &[u8] = b"Account";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProofError {
+    /// The proof doesn't contain the nodes to look the key up from the state root.
+    InvalidProof,
+    /// The proven value can't be decoded.
+    InvalidValue,
+    /// The proven value differs from the claimed one.
+    ValueMismatch,
+}
+
+fn blake2_128_concat(data: &[u8]) -> Vec<u8> {
+    let mut key = blake2_128(data).to_vec();
+    key.extend_from_slice(data);
+    key
+}
+
+/// Leading fields of the `System::Account` entry, a
+/// `frame_system::AccountInfo<Nonce, pallet_balances::AccountData<Balance>>`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Decode)]
+pub struct ProvenAccount<Nonce, Balance> {
+    pub nonce: Nonce,
+    pub consumers: u32,
+    pub providers: u32,
+    pub sufficients: u32,
+    pub free: Balance,
+    pub reserved: Balance,
+    pub frozen: Balance,
+}
+
+/// Storage key of the code of `address`.
+pub fn account_codes_key(evm_pallet: &[u8], address: &H160) -> Vec<u8> {
+    let mut key = twox_128(evm_pallet).to_vec();
+    key.extend_from_slice(&twox_128(EVM_ACCOUNT_CODES));
+    key.extend(blake2_128_concat(address.as_bytes()));
+    key
+}
+
+/// Storage key of the `index` storage slot of `address`.
+pub fn account_storages_key(evm_pallet: &[u8], address: &H160, index: &H256) -> Vec<u8> {
+    let mut key = twox_128(evm_pallet).to_vec();
+    key.extend_from_slice(&twox_128(EVM_ACCOUNT_STORAGES));
+    key.extend(blake2_128_concat(address.as_bytes()));
+    key.extend(blake2_128_concat(index.as_bytes()));
+    key
+}
+
+/// Storage key of the `System::Account` entry of the SCALE-encoded `account_id`.
+pub fn system_account_key(system_pallet: &[u8], account_id: &[u8]) -> Vec<u8> {
+    let mut key = twox_128(system_pallet).to_vec();
+    key.extend_from_slice(&twox_128(SYSTEM_ACCOUNT));
+    key.extend(blake2_128_concat(account_id));
+    key
+}
+
+/// Reads the value of `key` out of `proof`, rooted at `state_root`.
+pub fn read_proof_value(
+    state_root: H256,
+    key: &[u8],
+    proof: Vec<Vec<u8>>,
+) -> Result<Option<Vec<u8>>, ProofError> {
+    let db = StorageProof::new(proof).into_memory_db::<Blake2Hasher>();
+    sp_trie::read_trie_value::<LayoutV1<Blake2Hasher>, _>(&db, &state_root, key, None, None)
+        .map_err(|_| ProofError::InvalidProof)
+}
+
+/// Checks the account proof of `address` proves `code` and returns its keccak hash.
+pub fn verify_code(
+    state_root: H256,
+    evm_pallet: &[u8],
+    address: H160,
+    code: &[u8],
+    proof: Vec<Vec<u8>>,
+) -> Result<H256, ProofError> {
+    let key = account_codes_key(evm_pallet, &address);
+    let proven_code = match read_proof_value(state_root, &key, proof)? {
+        Some(encoded) =>
+            Vec::<u8>::decode(&mut &encoded[..]).map_err(|_| ProofError::InvalidValue)?,
+        None => Vec::new(),
+    };
+    if proven_code != code {
+        return Err(ProofError::ValueMismatch)
+    }
+    Ok(H256(keccak_256(code)))
+}
+
+/// Checks the storage proof of the `index` slot of `address` proves `value`.
+pub fn verify_storage(
+    state_root: H256,
+    evm_pallet: &[u8],
+    address: H160,
+    index: H256,
+    value: H256,
+    proof: Vec<Vec<u8>>,
+) -> Result<(), ProofError> {
+    let key = account_storages_key(evm_pallet, &address, &index);
+    let proven_value = match read_proof_value(state_root, &key, proof)? {
+        Some(encoded) => H256::decode(&mut &encoded[..]).map_err(|_| ProofError::InvalidValue)?,
+        None => H256::zero(),
+    };
+    if proven_value != value {
+        return Err(ProofError::ValueMismatch)
+    }
+    Ok(())
+}
+
+/// Checks the system account proof of the SCALE-encoded `account_id` proves `nonce` and
+/// returns the proven account, whose balances bound the claimed one. A missing entry proves an
+/// empty account.
+pub fn verify_account<Nonce, Balance>(
+    state_root: H256,
+    system_pallet: &[u8],
+    account_id: &[u8],
+    nonce: Nonce,
+    proof: Vec<Vec<u8>>,
+) -> Result<ProvenAccount<Nonce, Balance>, ProofError>
+where
+    Nonce: Decode + Default + PartialEq,
+    Balance: Decode + Default,
+{
+    let key = system_account_key(system_pallet, account_id);
+    let account = match read_proof_value(state_root, &key, proof)? {
+        Some(encoded) => ProvenAccount::<Nonce, Balance>::decode(&mut &encoded[..])
+            .map_err(|_| ProofError::InvalidValue)?,
+        None => ProvenAccount::default(),
+    };
+    if account.nonce != nonce {
+        return Err(ProofError::ValueMismatch)
+    }
+    Ok(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale_codec::Encode;
+    use sp_core::storage::StateVersion;
+    use sp_state_machine::{prove_read, InMemoryBackend};
+
+    const EVM: &[u8] = b"Evm";
+    const SYSTEM: &[u8] = b"System";
+    const ADDRESS: H160 = H160([1u8; 20]);
+    const ACCOUNT_ID: [u8; 32] = [1u8; 32];
+    const OTHER_ADDRESS: H160 = H160([2u8; 20]);
+
+    fn backend() -> InMemoryBackend<Blake2Hasher> {
+        let code = vec![0x60u8, 0x00, 0x60, 0x00, 0xf3];
+        (
+            vec![(
+                None,
+                vec![
+                    (account_codes_key(EVM, &ADDRESS), Some(code.encode())),
+                    (
+                        account_storages_key(EVM, &ADDRESS, &H256::from_low_u64_be(1)),
+                        Some(H256::from_low_u64_be(42).encode()),
+                    ),
+                    (
+                        system_account_key(SYSTEM, &ACCOUNT_ID),
+                        // nonce, consumers, providers, sufficients, free, reserved, frozen, flags
+                        Some((7u32, 0u32, 1u32, 0u32, 1_000u128, 0u128, 0u128, 0u128).encode()),
+                    ),
+                ],
+            )],
+            StateVersion::V1,
+        )
+            .into()
+    }
+
+    fn prove(backend: InMemoryBackend<Blake2Hasher>, key: Vec<u8>) -> (H256, Vec<Vec<u8>>) {
+        let root = *backend.root();
+        let proof = prove_read(backend, &[key]).unwrap();
+        (root, proof.into_iter_nodes().collect())
+    }
+
+    #[test]
+    fn verifies_proven_code_and_storage() {
+        let (root, proof) = prove(backend(), account_codes_key(EVM, &ADDRESS));
+        assert_eq!(
+            verify_code(root, EVM, ADDRESS, &[0x60, 0x00, 0x60, 0x00, 0xf3], proof),
+            Ok(H256(keccak_256(&[0x60, 0x00, 0x60, 0x00, 0xf3])))
+        );
+
+        let index = H256::from_low_u64_be(1);
+        let (root, proof) = prove(backend(), account_storages_key(EVM, &ADDRESS, &index));
+        assert_eq!(
+            verify_storage(root, EVM, ADDRESS, index, H256::from_low_u64_be(42), proof),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn missing_entries_prove_empty_code_and_zero_storage() {
+        let (root, proof) = prove(backend(), account_codes_key(EVM, &OTHER_ADDRESS));
+        assert_eq!(
+            verify_code(root, EVM, OTHER_ADDRESS, &[], proof),
+            Ok(H256(keccak_256(&[])))
+        );
+
+        let index = H256::from_low_u64_be(2);
+        let (root, proof) = prove(backend(), account_storages_key(EVM, &ADDRESS, &index));
+        assert_eq!(
+            verify_storage(root, EVM, ADDRESS, index, H256::zero(), proof),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_values_and_foreign_roots() {
+        let index = H256::from_low_u64_be(1);
+        let (root, proof) = prove(backend(), account_storages_key(EVM, &ADDRESS, &index));
+        assert_eq!(
+            verify_storage(
+                root,
+                EVM,
+                ADDRESS,
+                index,
+                H256::from_low_u64_be(43),
+                proof.clone()
+            ),
+            Err(ProofError::ValueMismatch)
+        );
+        assert_eq!(
+            verify_storage(
+                H256::repeat_byte(9),
+                EVM,
+                ADDRESS,
+                index,
+                H256::from_low_u64_be(42),
+                proof
+            ),
+            Err(ProofError::InvalidProof)
+        );
+    }
+
+    #[test]
+    fn verifies_the_mapped_system_account() {
+        let (root, proof) = prove(backend(), system_account_key(SYSTEM, &ACCOUNT_ID));
+        let account =
+            verify_account::<u32, u128>(root, SYSTEM, &ACCOUNT_ID, 7, proof.clone()).unwrap();
+        assert_eq!(
+            (account.nonce, account.providers, account.free),
+            (7, 1, 1_000)
+        );
+
+        assert_eq!(
+            verify_account::<u32, u128>(root, SYSTEM, &ACCOUNT_ID, 8, proof),
+            Err(ProofError::ValueMismatch)
+        );
+
+        let other_account_id = [2u8; 32];
+        let (root, proof) = prove(backend(), system_account_key(SYSTEM, &other_account_id));
+        assert_eq!(
+            verify_account::<u32, u128>(root, SYSTEM, &other_account_id, 0, proof),
+            Ok(ProvenAccount::default())
+        );
+    }
+}
@@ -21,6 +21,7 @@
 
 use ethereum::Log;
 use ethereum_types::Bloom;
+use pallet_3vm_evm_primitives::tracing::{TraceResult, TracerKind};
 use scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 // Substrate
@@ -247,6 +248,47 @@ sp_api::decl_runtime_apis! {
         // ) -> Result<H256, sp_runtime::DispatchError>;
     }
 
+    /// API replaying EVM transactions with a tracer, backing the `debug_*` RPC.
+    pub trait EvmTracingRuntimeApi {
+        /// Initializes the block of `header`, applies `extrinsics` and traces the ethereum
+        /// transaction `transaction_hash` among them.
+        fn trace_transaction(
+            header: &<Block as BlockT>::Header,
+            extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+            transaction_hash: H256,
+            tracer: TracerKind,
+        ) -> Result<TraceResult, sp_runtime::DispatchError>;
+        /// Initializes the block of `header`, applies `extrinsics` and traces all of the
+        /// ethereum transactions among them.
+        fn trace_block(
+            header: &<Block as BlockT>::Header,
+            extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+            tracer: TracerKind,
+        ) -> Result<Vec<(H256, TraceResult)>, sp_runtime::DispatchError>;
+        /// Traces a call executed on top of the state, creating a contract if `to` is empty.
+        fn trace_call(
+            from: H160,
+            to: Option<H160>,
+            data: Vec<u8>,
+            value: U256,
+            gas_limit: U256,
+            max_fee_per_gas: Option<U256>,
+            max_priority_fee_per_gas: Option<U256>,
+            nonce: Option<U256>,
+            tracer: TracerKind,
+        ) -> Result<TraceResult, sp_runtime::DispatchError>;
+    }
+
+    /// API naming the storage the `eth_getProof` proofs are read from.
+    pub trait EvmProofRuntimeApi {
+        /// Names the EVM and the System pallets are registered under in the runtime's
+        /// `PalletInfo`, prefixing the proven storage keys.
+        fn proof_pallet_names() -> (Vec<u8>, Vec<u8>);
+        /// SCALE-encoded Substrate account `address` maps to, whose `System::Account` holds
+        /// its balance and nonce.
+        fn mapped_account_id(address: H160) -> Vec<u8>;
+    }
+
     #[api_version(2)]
     pub trait ConvertTransactionRuntimeApi {
         fn convert_transaction(transaction: ethereum::TransactionV2) -> <Block as BlockT>::Extrinsic;
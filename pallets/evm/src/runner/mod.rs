@@ -16,6 +16,8 @@
 // limitations under the License.
 
 pub mod stack;
+#[cfg(feature = "evm-tracing")]
+pub mod tracing;
 
 use crate::{Config, Weight};
 use fp_evm::{CallInfo, CreateInfo};
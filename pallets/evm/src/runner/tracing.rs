@@ -0,0 +1,464 @@
+//! EVM event listeners used to replay transactions for the `debug_*` tracing RPC.
+//!
+//! The listeners hook into the events emitted by the `StackExecutor` the stack runner executes
+//! with, so wrapping any dispatch reaching [`crate::runner::stack::Runner`] in [`trace`]
+//! records its call frames or opcode steps.
+
+use evm::{
+    tracing::{Event as EvmEvent, EventListener as EvmEventListener},
+    CreateScheme, ExitError, ExitReason,
+};
+use evm_gasometer::tracing::{Event as GasEvent, EventListener as GasEventListener, Snapshot};
+use evm_runtime::tracing::{Event as StepEvent, EventListener as StepEventListener};
+use fp_evm::tracing::{
+    CallFrame, CallType, StructLog, StructLoggerConfig, StructLoggerResult, TraceResult, TracerKind,
+};
+use sp_core::{H160, H256, U256};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap, rc::Rc, vec::Vec};
+
+/// Runs `f` with the EVM tracers of `kind` listening and returns its result with the trace.
+pub fn trace<R>(kind: TracerKind, f: impl FnOnce() -> R) -> (R, TraceResult) {
+    let tracer = Rc::new(RefCell::new(Tracer::new(kind)));
+
+    let mut evm_listener = Listener(tracer.clone());
+    let mut step_listener = Listener(tracer.clone());
+    let mut gas_listener = Listener(tracer.clone());
+
+    let result = evm::tracing::using(&mut evm_listener, || {
+        evm_runtime::tracing::using(&mut step_listener, || {
+            evm_gasometer::tracing::using(&mut gas_listener, f)
+        })
+    });
+
+    let trace = tracer.borrow_mut().finish();
+    (result, trace)
+}
+
+struct Listener(Rc<RefCell<Tracer>>);
+
+impl EvmEventListener for Listener {
+    fn event(&mut self, event: EvmEvent) {
+        self.0.borrow_mut().on_evm_event(event);
+    }
+}
+
+impl StepEventListener for Listener {
+    fn event(&mut self, event: StepEvent) {
+        self.0.borrow_mut().on_step_event(event);
+    }
+}
+
+impl GasEventListener for Listener {
+    fn event(&mut self, event: GasEvent) {
+        self.0.borrow_mut().on_gas_event(event);
+    }
+}
+
+/// A call frame being executed, with the last gas snapshot of its own gasometer.
+struct OpenFrame {
+    frame: CallFrame,
+    snapshot: Option<Snapshot>,
+}
+
+impl OpenFrame {
+    fn new(frame: CallFrame) -> Self {
+        OpenFrame {
+            frame,
+            snapshot: None,
+        }
+    }
+
+    /// Gas the frame consumed: errors burn the whole frame's gas, while successful and reverted
+    /// frames only spend what their gasometer recorded, including their subcalls' costs.
+    fn gas_used(&self, reason: &ExitReason) -> u64 {
+        match reason {
+            ExitReason::Succeed(_) | ExitReason::Revert(_) => self
+                .snapshot
+                .map(|snapshot| snapshot.used_gas)
+                .unwrap_or_default(),
+            ExitReason::Error(_) | ExitReason::Fatal(_) => self.frame.gas,
+        }
+    }
+}
+
+struct Tracer {
+    kind: TracerKind,
+    depth: u32,
+    // The executor enters the root frame right after the transact event opened it
+    skip_next_frame: bool,
+    // The caller's gasometer charges the gas handed to a subcall right after it is entered
+    awaiting_frame_gas: bool,
+    open_frames: Vec<OpenFrame>,
+    root_frame: Option<CallFrame>,
+    last_snapshot: Option<Snapshot>,
+    struct_logs: Vec<StructLog>,
+    // Set once `max_steps` steps are logged, so later gas costs aren't added to the last one
+    struct_logs_full: bool,
+    storage: BTreeMap<H160, BTreeMap<H256, H256>>,
+    return_value: Vec<u8>,
+    failed: bool,
+}
+
+impl Tracer {
+    fn new(kind: TracerKind) -> Self {
+        Tracer {
+            kind,
+            depth: 0,
+            skip_next_frame: false,
+            awaiting_frame_gas: false,
+            open_frames: Vec::new(),
+            root_frame: None,
+            last_snapshot: None,
+            struct_logs: Vec::new(),
+            struct_logs_full: false,
+            storage: BTreeMap::new(),
+            return_value: Vec::new(),
+            failed: false,
+        }
+    }
+
+    fn remaining_gas(&self) -> u64 {
+        self.last_snapshot
+            .map(|snapshot| snapshot.gas_limit.saturating_sub(snapshot.used_gas))
+            .unwrap_or_default()
+    }
+
+    fn open_frame(&mut self, frame: CallFrame) {
+        self.depth = self.depth.saturating_add(1);
+        if self.skip_next_frame {
+            self.skip_next_frame = false;
+        } else {
+            self.open_frames.push(OpenFrame::new(frame));
+            self.awaiting_frame_gas = true;
+        }
+    }
+
+    fn open_root_frame(&mut self, frame: CallFrame) {
+        self.open_frames.push(OpenFrame::new(frame));
+        self.skip_next_frame = true;
+    }
+
+    fn close_frame(&mut self, frame: CallFrame) {
+        match self.open_frames.last_mut() {
+            Some(parent) => parent.frame.calls.push(frame),
+            None => self.root_frame = Some(frame),
+        }
+    }
+
+    fn on_evm_event(&mut self, event: EvmEvent) {
+        match event {
+            EvmEvent::TransactCall {
+                caller,
+                address,
+                value,
+                data,
+                gas_limit,
+                ..
+            } => self.open_root_frame(new_frame(
+                CallType::Call,
+                caller,
+                address,
+                value,
+                gas_limit,
+                data.to_vec(),
+            )),
+            EvmEvent::TransactCreate {
+                caller,
+                value,
+                init_code,
+                gas_limit,
+                address,
+                ..
+            } => self.open_root_frame(new_frame(
+                CallType::Create,
+                caller,
+                address,
+                value,
+                gas_limit,
+                init_code.to_vec(),
+            )),
+            EvmEvent::TransactCreate2 {
+                caller,
+                value,
+                init_code,
+                gas_limit,
+                address,
+                ..
+            } => self.open_root_frame(new_frame(
+                CallType::Create2,
+                caller,
+                address,
+                value,
+                gas_limit,
+                init_code.to_vec(),
+            )),
+            EvmEvent::Call {
+                code_address,
+                transfer,
+                input,
+                target_gas,
+                is_static,
+                context,
+                ..
+            } => {
+                let call_type = if is_static {
+                    CallType::StaticCall
+                } else if code_address != context.address {
+                    CallType::DelegateCall
+                } else {
+                    CallType::Call
+                };
+                let value = transfer
+                    .as_ref()
+                    .map(|transfer| transfer.value)
+                    .unwrap_or(context.apparent_value);
+                self.open_frame(new_frame(
+                    call_type,
+                    context.caller,
+                    code_address,
+                    value,
+                    target_gas.unwrap_or_default(),
+                    input.to_vec(),
+                ));
+            },
+            EvmEvent::Create {
+                caller,
+                address,
+                scheme,
+                value,
+                init_code,
+                target_gas,
+                ..
+            } => {
+                let call_type = match scheme {
+                    CreateScheme::Create2 { .. } => CallType::Create2,
+                    _ => CallType::Create,
+                };
+                self.open_frame(new_frame(
+                    call_type,
+                    caller,
+                    address,
+                    value,
+                    target_gas.unwrap_or_default(),
+                    init_code.to_vec(),
+                ));
+            },
+            EvmEvent::Suicide {
+                address,
+                target,
+                balance,
+                ..
+            } => {
+                let frame = new_frame(
+                    CallType::SelfDestruct,
+                    address,
+                    target,
+                    balance,
+                    0,
+                    Vec::new(),
+                );
+                self.close_frame(frame);
+            },
+            EvmEvent::Exit {
+                reason,
+                return_value,
+                ..
+            } => {
+                self.depth = self.depth.saturating_sub(1);
+                if let Some(open_frame) = self.open_frames.pop() {
+                    let gas_used = open_frame.gas_used(reason);
+                    let mut frame = open_frame.frame;
+                    frame.gas_used = gas_used;
+                    frame.output = return_value.to_vec();
+                    frame.error = exit_error(reason);
+                    self.close_frame(frame);
+                }
+                if self.open_frames.is_empty() {
+                    self.return_value = return_value.to_vec();
+                    self.failed = !reason.is_succeed();
+                }
+            },
+            // Subcalls of precompiles are entered as regular calls right after
+            _ => {},
+        }
+    }
+
+    fn on_step_event(&mut self, event: StepEvent) {
+        let config = match self.kind {
+            TracerKind::StructLogger(config) => config,
+            TracerKind::CallTracer => return,
+        };
+        match event {
+            StepEvent::Step {
+                context,
+                opcode,
+                position,
+                stack,
+                memory,
+                ..
+            } => {
+                let pc = match position {
+                    Ok(pc) => *pc as u64,
+                    Err(_) => return,
+                };
+                let StructLoggerConfig {
+                    disable_stack,
+                    disable_memory,
+                    disable_storage,
+                    max_steps,
+                } = config;
+                if self.struct_logs.len() >= max_steps as usize {
+                    self.struct_logs_full = true;
+                    return
+                }
+                self.struct_logs.push(StructLog {
+                    pc,
+                    op: opcode.0,
+                    gas: self.remaining_gas(),
+                    gas_cost: 0,
+                    depth: self.depth,
+                    stack: (!disable_stack).then(|| stack.data().clone()),
+                    memory: (!disable_memory).then(|| memory.data().clone()),
+                    storage: (!disable_storage).then(|| {
+                        self.storage
+                            .get(&context.address)
+                            .cloned()
+                            .unwrap_or_default()
+                    }),
+                });
+            },
+            StepEvent::SLoad {
+                address,
+                index,
+                value,
+                ..
+            }
+            | StepEvent::SStore {
+                address,
+                index,
+                value,
+                ..
+            } => {
+                self.storage
+                    .entry(address)
+                    .or_default()
+                    .insert(index, value);
+            },
+            _ => {},
+        }
+    }
+
+    fn on_gas_event(&mut self, event: GasEvent) {
+        let (cost, snapshot, is_static_cost) = match event {
+            GasEvent::RecordCost { cost, snapshot, .. } => (cost, snapshot, true),
+            GasEvent::RecordDynamicCost {
+                gas_cost,
+                memory_gas,
+                snapshot,
+                ..
+            } => (gas_cost.saturating_add(memory_gas), snapshot, false),
+            GasEvent::RecordRefund { snapshot, .. }
+            | GasEvent::RecordStipend { snapshot, .. }
+            | GasEvent::RecordTransaction { snapshot, .. } => (0, snapshot, false),
+        };
+        if !self.struct_logs_full {
+            if let Some(log) = self.struct_logs.last_mut() {
+                log.gas_cost = log.gas_cost.saturating_add(cost);
+            }
+        }
+        if sp_std::mem::take(&mut self.awaiting_frame_gas) && is_static_cost {
+            if let Some(open_frame) = self.open_frames.last_mut() {
+                open_frame.frame.gas = cost;
+            }
+        }
+        if let Some(snapshot) = snapshot {
+            self.last_snapshot = Some(snapshot);
+            // Every frame runs its own gasometer, so the snapshot belongs to the innermost
+            // frame started with its gas limit, be it the running frame or one of its callers
+            if let Some(open_frame) = self
+                .open_frames
+                .iter_mut()
+                .rev()
+                .find(|open_frame| open_frame.frame.gas == snapshot.gas_limit)
+            {
+                open_frame.snapshot = Some(snapshot);
+            }
+        }
+    }
+
+    fn finish(&mut self) -> TraceResult {
+        // Frames left open if the executor bailed out before exiting them
+        while let Some(open_frame) = self.open_frames.pop() {
+            let mut frame = open_frame.frame;
+            frame.gas_used = open_frame
+                .snapshot
+                .map(|snapshot| snapshot.used_gas)
+                .unwrap_or_default();
+            self.close_frame(frame);
+        }
+        match self.kind {
+            TracerKind::CallTracer =>
+                TraceResult::CallTracer(self.root_frame.take().unwrap_or_else(|| {
+                    new_frame(
+                        CallType::Call,
+                        H160::zero(),
+                        H160::zero(),
+                        U256::zero(),
+                        0,
+                        Vec::new(),
+                    )
+                })),
+            TracerKind::StructLogger(_) => TraceResult::StructLogger(StructLoggerResult {
+                gas: self
+                    .root_frame
+                    .as_ref()
+                    .map(|frame| frame.gas_used)
+                    .unwrap_or_default(),
+                failed: self.failed,
+                return_value: sp_std::mem::take(&mut self.return_value),
+                struct_logs: sp_std::mem::take(&mut self.struct_logs),
+            }),
+        }
+    }
+}
+
+fn new_frame(
+    call_type: CallType,
+    from: H160,
+    to: H160,
+    value: U256,
+    gas: u64,
+    input: Vec<u8>,
+) -> CallFrame {
+    CallFrame {
+        call_type,
+        from,
+        to,
+        value,
+        gas,
+        gas_used: 0,
+        input,
+        output: Vec::new(),
+        error: None,
+        precompile: None,
+        calls: Vec::new(),
+    }
+}
+
+fn exit_error(reason: &ExitReason) -> Option<Vec<u8>> {
+    let error: &[u8] = match reason {
+        ExitReason::Succeed(_) => return None,
+        ExitReason::Revert(_) => b"execution reverted",
+        ExitReason::Error(ExitError::OutOfGas) => b"out of gas",
+        ExitReason::Error(ExitError::OutOfFund) => b"insufficient balance for transfer",
+        ExitReason::Error(ExitError::CallTooDeep) => b"max call depth exceeded",
+        ExitReason::Error(ExitError::InvalidJump) => b"invalid jump destination",
+        ExitReason::Error(ExitError::StackUnderflow) => b"stack underflow",
+        ExitReason::Error(ExitError::StackOverflow) => b"stack overflow",
+        ExitReason::Error(ExitError::CreateCollision) => b"contract address collision",
+        ExitReason::Error(ExitError::Other(message)) => return Some(message.as_bytes().to_vec()),
+        ExitReason::Error(_) => b"execution error",
+        ExitReason::Fatal(_) => b"fatal error",
+    };
+    Some(error.to_vec())
+}
@@ -1272,3 +1272,205 @@ fn metadata_empty_dont_code_gets_cached() {
         assert!(<AccountCodesMetadata<Test>>::get(address).is_none());
     });
 }
+
+#[cfg(feature = "evm-tracing")]
+mod tracing_test {
+    use super::*;
+    use crate::runner::tracing::trace;
+    use fp_evm::tracing::{
+        CallFrame, CallType, StructLoggerConfig, StructLoggerResult, TraceResult, TracerKind,
+    };
+
+    const REVERTING_CONTRACT: &str = "2000000000000000000000000000000000000001";
+    const INVALID_CONTRACT: &str = "2000000000000000000000000000000000000002";
+    const CALLER_CONTRACT: &str = "2000000000000000000000000000000000000003";
+
+    fn address(address: &str) -> H160 {
+        H160::from_str(address).unwrap()
+    }
+
+    // Calls `callee` with all the gas left, drops the call status and stops.
+    fn call_and_stop_code(callee: H160) -> Vec<u8> {
+        let mut code = vec![
+            0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73,
+        ];
+        code.extend_from_slice(callee.as_bytes());
+        code.extend_from_slice(&[0x5a, 0xf1, 0x50, 0x00]);
+        code
+    }
+
+    fn create_contracts() {
+        // PUSH1 0, PUSH1 0, REVERT
+        EVM::create_account(
+            address(REVERTING_CONTRACT),
+            vec![0x60, 0x00, 0x60, 0x00, 0xfd],
+        );
+        // INVALID
+        EVM::create_account(address(INVALID_CONTRACT), vec![0xfe]);
+    }
+
+    fn call_tracer(target: H160, gas_limit: u64) -> (CallFrame, U256) {
+        let (result, trace) = trace(TracerKind::CallTracer, || {
+            <Test as Config>::Runner::call(
+                H160::default(),
+                target,
+                Vec::new(),
+                U256::zero(),
+                gas_limit,
+                Some(FixedGasPrice::min_gas_price().0),
+                None,
+                None,
+                Vec::new(),
+                true, // transactional
+                true, // must be validated
+                None,
+                None,
+                &<Test as Config>::config().clone(),
+            )
+        });
+        let used_gas = result.expect("call is executed").used_gas.standard;
+        match trace {
+            TraceResult::CallTracer(frame) => (frame, used_gas),
+            TraceResult::StructLogger(_) => panic!("call tracer returns call frames"),
+        }
+    }
+
+    fn struct_logger(target: H160, max_steps: u32) -> StructLoggerResult {
+        let config = StructLoggerConfig {
+            disable_stack: false,
+            disable_memory: true,
+            disable_storage: true,
+            max_steps,
+        };
+        let (_, trace) = trace(TracerKind::StructLogger(config), || {
+            <Test as Config>::Runner::call(
+                H160::default(),
+                target,
+                Vec::new(),
+                U256::zero(),
+                1_000_000,
+                Some(FixedGasPrice::min_gas_price().0),
+                None,
+                None,
+                Vec::new(),
+                true, // transactional
+                true, // must be validated
+                None,
+                None,
+                &<Test as Config>::config().clone(),
+            )
+        });
+        match trace {
+            TraceResult::StructLogger(result) => result,
+            TraceResult::CallTracer(_) => panic!("struct logger returns steps"),
+        }
+    }
+
+    #[test]
+    fn struct_logger_keeps_at_most_max_steps() {
+        new_test_ext().execute_with(|| {
+            create_contracts();
+
+            let all_steps = struct_logger(address(REVERTING_CONTRACT), 100);
+            let capped = struct_logger(address(REVERTING_CONTRACT), 2);
+
+            // PUSH1 0, PUSH1 0, REVERT
+            assert_eq!(all_steps.struct_logs.len(), 3);
+            assert_eq!(capped.struct_logs, all_steps.struct_logs[..2].to_vec());
+        });
+    }
+
+    #[test]
+    fn call_trace_records_the_root_frame() {
+        new_test_ext().execute_with(|| {
+            let target = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+            let (frame, used_gas) = call_tracer(target, 1_000_000);
+
+            assert_eq!(frame.call_type, CallType::Call);
+            assert_eq!(frame.from, H160::default());
+            assert_eq!(frame.to, target);
+            assert_eq!(frame.gas, 1_000_000);
+            assert_eq!(U256::from(frame.gas_used), used_gas);
+            assert_eq!(frame.error, None);
+            assert!(frame.calls.is_empty());
+        });
+    }
+
+    #[test]
+    fn create_trace_records_the_created_address() {
+        new_test_ext().execute_with(|| {
+            let (result, trace) = trace(TracerKind::CallTracer, || {
+                <Test as Config>::Runner::create(
+                    H160::default(),
+                    // PUSH1 0, PUSH1 0, RETURN
+                    vec![0x60, 0x00, 0x60, 0x00, 0xf3],
+                    U256::zero(),
+                    1_000_000,
+                    Some(FixedGasPrice::min_gas_price().0),
+                    None,
+                    None,
+                    Vec::new(),
+                    true, // transactional
+                    true, // must be validated
+                    None,
+                    None,
+                    &<Test as Config>::config().clone(),
+                )
+            });
+            let info = result.expect("create is executed");
+            let frame = match trace {
+                TraceResult::CallTracer(frame) => frame,
+                TraceResult::StructLogger(_) => panic!("call tracer returns call frames"),
+            };
+
+            assert_eq!(frame.call_type, CallType::Create);
+            assert_eq!(frame.to, info.value);
+            assert_eq!(U256::from(frame.gas_used), info.used_gas.standard);
+            assert_eq!(frame.error, None);
+        });
+    }
+
+    #[test]
+    fn revert_trace_only_charges_the_gas_spent() {
+        new_test_ext().execute_with(|| {
+            create_contracts();
+            let (frame, used_gas) = call_tracer(address(REVERTING_CONTRACT), 1_000_000);
+
+            assert_eq!(frame.error, Some(b"execution reverted".to_vec()));
+            assert_eq!(U256::from(frame.gas_used), used_gas);
+            assert!(frame.gas_used < frame.gas);
+        });
+    }
+
+    #[test]
+    fn nested_frames_record_their_own_gas() {
+        new_test_ext().execute_with(|| {
+            create_contracts();
+            let caller = address(CALLER_CONTRACT);
+
+            EVM::create_account(caller, call_and_stop_code(address(REVERTING_CONTRACT)));
+            let (frame, used_gas) = call_tracer(caller, 1_000_000);
+
+            assert_eq!(frame.error, None);
+            assert_eq!(U256::from(frame.gas_used), used_gas);
+            assert_eq!(frame.calls.len(), 1);
+            let reverted = &frame.calls[0];
+            assert_eq!(reverted.from, caller);
+            assert_eq!(reverted.to, address(REVERTING_CONTRACT));
+            assert_eq!(reverted.error, Some(b"execution reverted".to_vec()));
+            // Two PUSH1 and a REVERT of empty memory
+            assert_eq!(reverted.gas_used, 6);
+            assert!(reverted.gas > reverted.gas_used);
+
+            EVM::create_account(caller, call_and_stop_code(address(INVALID_CONTRACT)));
+            let (frame, _) = call_tracer(caller, 1_000_000);
+
+            assert_eq!(frame.calls.len(), 1);
+            let failed = &frame.calls[0];
+            assert_eq!(failed.error, Some(b"execution error".to_vec()));
+            // Exceptional halts burn all the gas handed to the frame
+            assert_eq!(failed.gas_used, failed.gas);
+            assert!(failed.gas > 0);
+        });
+    }
+}
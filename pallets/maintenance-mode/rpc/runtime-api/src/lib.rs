@@ -0,0 +1,30 @@
+//! Runtime API definition for querying the pauses of pallet maintenance mode.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to expose the paused pallets, calls and scopes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+pub use t3rn_primitives::maintenance::PauseScope;
+
+sp_api::decl_runtime_apis! {
+    /// The API to query pallet maintenance mode
+    pub trait MaintenanceModeRuntimeApi<BlockNumber> where
+        BlockNumber: Codec,
+    {
+        /// Returns whether the chain is in maintenance mode
+        fn is_in_maintenance_mode() -> bool;
+
+        /// Returns whether the call, or its whole pallet, is currently paused
+        fn is_call_paused(pallet_name: Vec<u8>, call_name: Vec<u8>) -> bool;
+
+        /// Returns the active pauses as (pallet name, call name, block the pause lifts at).
+        /// No call name stands for the whole pallet.
+        fn paused_calls() -> Vec<(Vec<u8>, Option<Vec<u8>>, Option<BlockNumber>)>;
+
+        /// Returns the active scope pauses with the block each lifts at
+        fn paused_scopes() -> Vec<(PauseScope, Option<BlockNumber>)>;
+    }
+}
@@ -31,11 +31,20 @@
 //! To implement this we would couple to the timestamp pallet and store the timestamp of the
 //! previous block.
 //!
-//! 3. Different origins for entering and leaving maintenance mode.
-//!
-//! 4. Maintenance mode timeout. To avoid getting stuck in maintenance mode. It could automatically
+//! 3. Maintenance mode timeout. To avoid getting stuck in maintenance mode. It could automatically
 //! switch back to normal mode after a pre-decided number of blocks. Maybe there could be an
 //! extrinsic to extend the maintenance time.
+//!
+//! Besides the global switch, single pallets or calls can be paused, optionally until a given
+//! block, after which the pause lifts itself. Pauses can be placed by a weaker `PauseOrigin`
+//! meant for emergency responders, while lifting them early requires the `MaintenanceOrigin`.
+//! The emergency responders can be the `PauseGuardians` kept by this pallet, see
+//! `EnsurePauseGuardian`. Pallets the chain can't recover without, e.g. `System` or `Sudo`,
+//! are listed in `UnpausablePallets` and can't be paused by any origin.
+//! Checking for pauses costs two more storage reads per filtered call.
+//!
+//! Pause scopes narrower than a call (e.g. bidding on a single target) are stored here too and
+//! enforced by the pallets owning the paused feature through `PauseScopes`.
 
 #![allow(non_camel_case_types)]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -47,8 +56,14 @@ mod tests;
 
 mod types;
 use frame_support::pallet;
+use sp_std::vec::Vec;
+pub use t3rn_primitives::maintenance::{PauseScope, PauseScopes};
 pub use types::*;
 
+/// Pallet name and optionally call name, as reported by `GetCallMetadata`.
+/// No call name pauses the whole pallet.
+pub type PausedCallId = (Vec<u8>, Option<Vec<u8>>);
+
 pub use pallet::*;
 
 #[pallet]
@@ -60,14 +75,13 @@ pub mod pallet {
     use frame_support::{
         pallet_prelude::*,
         traits::{
-            Contains, EnsureOrigin, GenesisBuild, OffchainWorker, OnFinalize, OnIdle, OnInitialize,
-            OnRuntimeUpgrade,
+            Contains, EnsureOrigin, GenesisBuild, GetCallMetadata, OffchainWorker, OnFinalize,
+            OnIdle, OnInitialize, OnRuntimeUpgrade, PalletInfoAccess,
         },
     };
     use frame_system::pallet_prelude::BlockNumberFor;
 
     use frame_system::pallet_prelude::*;
-    #[cfg(feature = "xcm-support")]
     use sp_std::vec::Vec;
     // #[cfg(feature = "xcm-support")]
     // use xcm_primitives::PauseXcmExecution;
@@ -81,19 +95,30 @@ pub mod pallet {
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// Overarching event type
-        type RuntimeEvent: From<Event> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// The overarching call type, inspected for its pallet and call names when
+        /// matching against paused calls
+        type RuntimeCall: Parameter
+            + GetCallMetadata
+            + IsType<<Self as frame_system::Config>::RuntimeCall>;
         /// The base call filter to be used in normal operating mode
         /// (When we aren't in the middle of a migration)
-        type NormalCallFilter: Contains<Self::RuntimeCall>;
+        type NormalCallFilter: Contains<<Self as frame_system::Config>::RuntimeCall>;
         /// The base call filter to be used when we are in the middle of migrations
         /// This should be very restrictive. Probably not allowing anything except possibly
         /// something like sudo or other emergency processes
-        type MaintenanceCallFilter: Contains<Self::RuntimeCall>;
+        type MaintenanceCallFilter: Contains<<Self as frame_system::Config>::RuntimeCall>;
         /// The origin from which the call to enter or exit maintenance mode must come
         /// Take care when choosing your maintenance call filter to ensure that you'll still be
         /// able to return to normal mode. For example, if your MaintenanceOrigin is a council, make
         /// sure that your councilors can still cast votes.
         type MaintenanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// The origin allowed to pause pallets, calls and scopes, but never to lift a pause nor
+        /// to shorten one. `MaintenanceOrigin` is allowed to pause as well.
+        type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Pallets that must stay callable for the chain to produce blocks and to recover,
+        /// e.g. `System`, `Timestamp` or `Sudo`. These can't be paused by any origin.
+        type UnpausablePallets: Contains<&'static str>;
         /// Handler to suspend and resume XCM execution
         // #[cfg(feature = "xcm-support")]
         // type XcmExecutionManager: xcm_primitives::PauseXcmExecution;
@@ -125,11 +150,31 @@ pub mod pallet {
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(crate) fn deposit_event)]
-    pub enum Event {
+    pub enum Event<T: Config> {
         /// The chain was put into Maintenance Mode
         EnteredMaintenanceMode,
         /// The chain returned to its normal operating state
         NormalOperationResumed,
+        /// A pallet or a single call was paused, until the given block if any
+        CallPaused {
+            pallet_name: Vec<u8>,
+            call_name: Option<Vec<u8>>,
+            expires_at: Option<BlockNumberFor<T>>,
+        },
+        /// A pallet or a single call pause was lifted
+        CallUnpaused {
+            pallet_name: Vec<u8>,
+            call_name: Option<Vec<u8>>,
+        },
+        /// A pause scope was paused, until the given block if any
+        ScopePaused {
+            scope: PauseScope,
+            expires_at: Option<BlockNumberFor<T>>,
+        },
+        /// A pause scope was lifted
+        ScopeUnpaused { scope: PauseScope },
+        /// The account was allowed or disallowed to pause as an emergency responder
+        PauseGuardianSet { who: T::AccountId, enabled: bool },
         /// The call to suspend on_idle XCM execution failed with inner error
         FailedToSuspendIdleXcmExecution { error: DispatchError },
         /// The call to resume on_idle XCM execution failed with inner error
@@ -143,6 +188,20 @@ pub mod pallet {
         AlreadyInMaintenanceMode,
         /// The chain cannot resume normal operation because it is not in maintenance mode
         NotInMaintenanceMode,
+        /// No pallet with the given name is part of the runtime
+        UnknownPallet,
+        /// The pallet has no call with the given name
+        UnknownCall,
+        /// Pausing this pallet would make lifting any pause impossible
+        CannotPauseMaintenancePallet,
+        /// The pause would have expired already
+        ExpiryInPast,
+        /// The pause origin can only extend an active pause, not shorten it
+        CannotShortenPause,
+        /// There is no active pause to lift
+        NotPaused,
+        /// Pausing this pallet would stop the chain from producing blocks or from recovering
+        CannotPausePallet,
     }
 
     #[pallet::storage]
@@ -150,6 +209,24 @@ pub mod pallet {
     /// Whether the site is in maintenance mode
     type MaintenanceMode<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn paused_calls)]
+    /// Paused pallets and calls, mapped to the block the pause lifts at (`None` - until lifted)
+    pub type PausedCalls<T: Config> =
+        StorageMap<_, Blake2_128Concat, PausedCallId, Option<BlockNumberFor<T>>, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn paused_scopes)]
+    /// Paused scopes, mapped to the block the pause lifts at (`None` - until lifted)
+    pub type PausedScopes<T: Config> =
+        StorageMap<_, Blake2_128Concat, PauseScope, Option<BlockNumberFor<T>>, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pause_guardians)]
+    /// Emergency responders allowed to pause through `EnsurePauseGuardian`
+    pub type PauseGuardians<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Place the chain in maintenance mode
@@ -221,6 +298,219 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Pause a whole pallet or, if `call_name` is given, a single call of it.
+        /// With `expires_at` set, the pause lifts itself at that block.
+        ///
+        /// Allowed to both the pause and the maintenance origins.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2))]
+        pub fn pause_call(
+            origin: OriginFor<T>,
+            pallet_name: Vec<u8>,
+            call_name: Option<Vec<u8>>,
+            expires_at: Option<BlockNumberFor<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let is_maintenance_origin = Self::ensure_can_pause(origin)?;
+            Self::ensure_pausable_call(&pallet_name, &call_name)?;
+
+            let id: PausedCallId = (pallet_name.clone(), call_name.clone());
+            Self::ensure_valid_pause(
+                PausedCalls::<T>::get(&id),
+                expires_at,
+                is_maintenance_origin,
+            )?;
+
+            PausedCalls::<T>::insert(id, expires_at);
+            <Pallet<T>>::deposit_event(Event::CallPaused {
+                pallet_name,
+                call_name,
+                expires_at,
+            });
+
+            Ok(().into())
+        }
+
+        /// Lift the pause of a pallet or of a single call before it expires.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2))]
+        pub fn unpause_call(
+            origin: OriginFor<T>,
+            pallet_name: Vec<u8>,
+            call_name: Option<Vec<u8>>,
+        ) -> DispatchResultWithPostInfo {
+            T::MaintenanceOrigin::ensure_origin(origin)?;
+
+            let id: PausedCallId = (pallet_name.clone(), call_name.clone());
+            ensure!(
+                PausedCalls::<T>::get(&id).map_or(false, |expiry| Self::is_active(expiry)),
+                Error::<T>::NotPaused
+            );
+
+            PausedCalls::<T>::remove(id);
+            <Pallet<T>>::deposit_event(Event::CallUnpaused {
+                pallet_name,
+                call_name,
+            });
+
+            Ok(().into())
+        }
+
+        /// Pause a scope enforced by the pallet owning it, e.g. bidding on a single target.
+        /// With `expires_at` set, the pause lifts itself at that block.
+        ///
+        /// Allowed to both the pause and the maintenance origins.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2))]
+        pub fn pause_scope(
+            origin: OriginFor<T>,
+            scope: PauseScope,
+            expires_at: Option<BlockNumberFor<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let is_maintenance_origin = Self::ensure_can_pause(origin)?;
+            Self::ensure_valid_pause(
+                PausedScopes::<T>::get(&scope),
+                expires_at,
+                is_maintenance_origin,
+            )?;
+
+            PausedScopes::<T>::insert(scope.clone(), expires_at);
+            <Pallet<T>>::deposit_event(Event::ScopePaused { scope, expires_at });
+
+            Ok(().into())
+        }
+
+        /// Lift the pause of a scope before it expires.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2))]
+        pub fn unpause_scope(
+            origin: OriginFor<T>,
+            scope: PauseScope,
+        ) -> DispatchResultWithPostInfo {
+            T::MaintenanceOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                PausedScopes::<T>::get(&scope).map_or(false, |expiry| Self::is_active(expiry)),
+                Error::<T>::NotPaused
+            );
+
+            PausedScopes::<T>::remove(&scope);
+            <Pallet<T>>::deposit_event(Event::ScopeUnpaused { scope });
+
+            Ok(().into())
+        }
+
+        /// Allow or disallow the account to pause as an emergency responder.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::DbWeight::get().writes(2))]
+        pub fn set_pause_guardian(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::MaintenanceOrigin::ensure_origin(origin)?;
+
+            if enabled {
+                PauseGuardians::<T>::insert(&who, ());
+            } else {
+                PauseGuardians::<T>::remove(&who);
+            }
+            <Pallet<T>>::deposit_event(Event::PauseGuardianSet { who, enabled });
+
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Returns whether the origin is the maintenance origin, otherwise requires the pause origin
+        fn ensure_can_pause(origin: OriginFor<T>) -> Result<bool, DispatchError> {
+            match T::MaintenanceOrigin::try_origin(origin) {
+                Ok(_) => Ok(true),
+                Err(origin) => T::PauseOrigin::ensure_origin(origin)
+                    .map(|_| false)
+                    .map_err(Into::into),
+            }
+        }
+
+        fn ensure_pausable_call(pallet_name: &[u8], call_name: &Option<Vec<u8>>) -> DispatchResult {
+            let pallet = <T as Config>::RuntimeCall::get_module_names()
+                .iter()
+                .find(|name| name.as_bytes() == pallet_name)
+                .ok_or(Error::<T>::UnknownPallet)?;
+            ensure!(
+                *pallet != <Pallet<T> as PalletInfoAccess>::name(),
+                Error::<T>::CannotPauseMaintenancePallet
+            );
+            ensure!(
+                !T::UnpausablePallets::contains(pallet),
+                Error::<T>::CannotPausePallet
+            );
+            if let Some(call_name) = call_name {
+                ensure!(
+                    <T as Config>::RuntimeCall::get_call_names(pallet)
+                        .iter()
+                        .any(|name| name.as_bytes() == call_name.as_slice()),
+                    Error::<T>::UnknownCall
+                );
+            }
+            Ok(())
+        }
+
+        /// The pause origin can't shorten an active pause, as that'd be equal to lifting it.
+        fn ensure_valid_pause(
+            current: Option<Option<BlockNumberFor<T>>>,
+            expires_at: Option<BlockNumberFor<T>>,
+            is_maintenance_origin: bool,
+        ) -> DispatchResult {
+            if let Some(expires_at) = expires_at {
+                ensure!(
+                    expires_at > <frame_system::Pallet<T>>::block_number(),
+                    Error::<T>::ExpiryInPast
+                );
+            }
+            if is_maintenance_origin {
+                return Ok(())
+            }
+            match (
+                current.filter(|expiry| Self::is_active(*expiry)),
+                expires_at,
+            ) {
+                (Some(None), Some(_)) => Err(Error::<T>::CannotShortenPause.into()),
+                (Some(Some(current)), Some(expires_at)) if expires_at < current =>
+                    Err(Error::<T>::CannotShortenPause.into()),
+                _ => Ok(()),
+            }
+        }
+
+        fn is_active(expiry: Option<BlockNumberFor<T>>) -> bool {
+            expiry.map_or(true, |expires_at| {
+                <frame_system::Pallet<T>>::block_number() < expires_at
+            })
+        }
+
+        /// Whether the call, or its whole pallet, is paused and the pause hasn't expired yet
+        pub fn is_call_paused(pallet_name: &[u8], call_name: &[u8]) -> bool {
+            [None, Some(call_name.to_vec())]
+                .into_iter()
+                .any(|call_name| {
+                    PausedCalls::<T>::get((pallet_name.to_vec(), call_name))
+                        .map_or(false, |expiry| Self::is_active(expiry))
+                })
+        }
+
+        /// Active pauses of pallets and calls, with the block they lift at
+        pub fn active_paused_calls() -> Vec<(PausedCallId, Option<BlockNumberFor<T>>)> {
+            PausedCalls::<T>::iter()
+                .filter(|(_, expiry)| Self::is_active(*expiry))
+                .collect()
+        }
+
+        /// Active pauses of scopes, with the block they lift at
+        pub fn active_paused_scopes() -> Vec<(PauseScope, Option<BlockNumberFor<T>>)> {
+            PausedScopes::<T>::iter()
+                .filter(|(_, expiry)| Self::is_active(*expiry))
+                .collect()
+        }
     }
 
     #[pallet::genesis_config]
@@ -242,13 +532,47 @@ pub mod pallet {
         }
     }
 
-    impl<T: Config> Contains<T::RuntimeCall> for Pallet<T> {
-        fn contains(call: &T::RuntimeCall) -> bool {
-            if MaintenanceMode::<T>::get() {
+    impl<T: Config> Contains<<T as frame_system::Config>::RuntimeCall> for Pallet<T> {
+        fn contains(call: &<T as frame_system::Config>::RuntimeCall) -> bool {
+            let allowed = if MaintenanceMode::<T>::get() {
                 T::MaintenanceCallFilter::contains(call)
             } else {
                 T::NormalCallFilter::contains(call)
+            };
+            if !allowed {
+                return false
             }
+            let metadata = <T as Config>::RuntimeCall::from_ref(call).get_call_metadata();
+            !Self::is_call_paused(
+                metadata.pallet_name.as_bytes(),
+                metadata.function_name.as_bytes(),
+            )
+        }
+    }
+
+    impl<T: Config> PauseScopes for Pallet<T> {
+        fn is_scope_paused(scope: &PauseScope) -> bool {
+            PausedScopes::<T>::get(scope).map_or(false, |expiry| Self::is_active(expiry))
+        }
+    }
+
+    /// Ensures the origin is signed by one of the `PauseGuardians`, meant to be the `PauseOrigin`.
+    pub struct EnsurePauseGuardian<T>(PhantomData<T>);
+
+    impl<T: Config> EnsureOrigin<T::RuntimeOrigin> for EnsurePauseGuardian<T> {
+        type Success = T::AccountId;
+
+        fn try_origin(origin: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+            origin.into().and_then(|origin| match origin {
+                frame_system::RawOrigin::Signed(who) if PauseGuardians::<T>::contains_key(&who) =>
+                    Ok(who),
+                origin => Err(T::RuntimeOrigin::from(origin)),
+            })
+        }
+
+        #[cfg(feature = "runtime-benchmarks")]
+        fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+            Err(())
         }
     }
     // #[cfg(feature = "xcm-support")]
@@ -18,14 +18,14 @@
 use super::*;
 use crate as pallet_maintenance_mode;
 use frame_support::{
-    construct_runtime, parameter_types,
+    construct_runtime, ord_parameter_types, parameter_types,
     traits::{
         Contains, Everything, GenesisBuild, OffchainWorker, OnFinalize, OnIdle, OnInitialize,
-        OnRuntimeUpgrade,
+        OnRuntimeUpgrade, PalletInfoAccess,
     },
     weights::Weight,
 };
-use frame_system::EnsureRoot;
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_core::H256;
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
@@ -247,6 +247,18 @@ impl OffchainWorker<BlockNumber> for NormalHooks {
     }
 }
 
+ord_parameter_types! {
+    pub const EmergencyResponder: AccountId = 2;
+}
+
+/// The hooks pallet stands in for the pallets the chain can't recover without.
+pub struct UnpausablePallets;
+impl Contains<&'static str> for UnpausablePallets {
+    fn contains(pallet_name: &&'static str) -> bool {
+        *pallet_name == <MockPalletMaintenanceHooks as PalletInfoAccess>::name()
+    }
+}
+
 impl Config for Test {
     type MaintenanceCallFilter = MaintenanceCallFilter;
     #[cfg(feature = "xcm-support")]
@@ -257,7 +269,10 @@ impl Config for Test {
     #[cfg(feature = "xcm-support")]
     type NormalDmpHandler = NormalDmpHandler;
     type NormalExecutiveHooks = NormalHooks;
+    type PauseOrigin = EnsureSignedBy<EmergencyResponder, AccountId>;
+    type RuntimeCall = RuntimeCall;
     type RuntimeEvent = RuntimeEvent;
+    type UnpausablePallets = UnpausablePallets;
     #[cfg(feature = "xcm-support")]
     type XcmExecutionManager = ();
 }
@@ -293,7 +308,7 @@ impl ExtBuilder {
     }
 }
 
-pub(crate) fn events() -> Vec<pallet_maintenance_mode::Event> {
+pub(crate) fn events() -> Vec<pallet_maintenance_mode::Event<Test>> {
     System::events()
         .into_iter()
         .map(|r| r.event)
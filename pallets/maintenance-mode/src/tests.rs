@@ -16,13 +16,16 @@
 
 //! Unit testing
 use crate::{
-    mock::{events, mock_events, ExtBuilder, RuntimeCall as OuterCall, RuntimeOrigin, Test},
-    Call, Error, Event, ExecutiveHooks,
+    mock::{
+        events, mock_events, ExtBuilder, MaintenanceMode, RuntimeCall as OuterCall, RuntimeOrigin,
+        System, Test,
+    },
+    Call, EnsurePauseGuardian, Error, Event, ExecutiveHooks, PauseScope, PauseScopes,
 };
 use frame_support::{
     assert_noop, assert_ok,
     dispatch::Dispatchable,
-    traits::{OffchainWorker, OnFinalize, OnIdle, OnInitialize, OnRuntimeUpgrade},
+    traits::{EnsureOrigin, OffchainWorker, OnFinalize, OnIdle, OnInitialize, OnRuntimeUpgrade},
     weights::Weight,
 };
 
@@ -213,3 +216,230 @@ fn maintenance_hooks_in_maintenance() {
             );
         })
 }
+
+#[test]
+fn paused_call_is_filtered_until_it_expires() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(MaintenanceMode::pause_call(
+            RuntimeOrigin::root(),
+            b"System".to_vec(),
+            Some(b"remark".to_vec()),
+            Some(3)
+        ));
+        assert_eq!(
+            events(),
+            vec![Event::CallPaused {
+                pallet_name: b"System".to_vec(),
+                call_name: Some(b"remark".to_vec()),
+                expires_at: Some(3),
+            }]
+        );
+
+        let remark: OuterCall = frame_system::Call::remark { remark: vec![] }.into();
+        assert_noop!(
+            remark.clone().dispatch(RuntimeOrigin::signed(1)),
+            frame_system::Error::<Test>::CallFiltered
+        );
+        // Other calls of the pallet stay available
+        let remark_with_event: OuterCall =
+            frame_system::Call::remark_with_event { remark: vec![] }.into();
+        assert_ok!(remark_with_event.dispatch(RuntimeOrigin::signed(1)));
+
+        System::set_block_number(3);
+        assert_ok!(remark.dispatch(RuntimeOrigin::signed(1)));
+        assert!(MaintenanceMode::active_paused_calls().is_empty());
+    })
+}
+
+#[test]
+fn paused_pallet_filters_all_its_calls() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(MaintenanceMode::pause_call(
+            RuntimeOrigin::root(),
+            b"System".to_vec(),
+            None,
+            None
+        ));
+
+        let remark_with_event: OuterCall =
+            frame_system::Call::remark_with_event { remark: vec![] }.into();
+        assert_noop!(
+            remark_with_event.clone().dispatch(RuntimeOrigin::signed(1)),
+            frame_system::Error::<Test>::CallFiltered
+        );
+
+        assert_ok!(MaintenanceMode::unpause_call(
+            RuntimeOrigin::root(),
+            b"System".to_vec(),
+            None
+        ));
+        assert_ok!(remark_with_event.dispatch(RuntimeOrigin::signed(1)));
+    })
+}
+
+#[test]
+fn pause_origin_can_pause_but_not_unpause() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            MaintenanceMode::pause_call(RuntimeOrigin::signed(1), b"System".to_vec(), None, None),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(MaintenanceMode::pause_call(
+            RuntimeOrigin::signed(2),
+            b"System".to_vec(),
+            Some(b"remark".to_vec()),
+            Some(10)
+        ));
+        // Shortening an active pause would amount to lifting it
+        assert_noop!(
+            MaintenanceMode::pause_call(
+                RuntimeOrigin::signed(2),
+                b"System".to_vec(),
+                Some(b"remark".to_vec()),
+                Some(5)
+            ),
+            Error::<Test>::CannotShortenPause
+        );
+        assert_ok!(MaintenanceMode::pause_call(
+            RuntimeOrigin::signed(2),
+            b"System".to_vec(),
+            Some(b"remark".to_vec()),
+            None
+        ));
+        assert_noop!(
+            MaintenanceMode::unpause_call(
+                RuntimeOrigin::signed(2),
+                b"System".to_vec(),
+                Some(b"remark".to_vec())
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(MaintenanceMode::unpause_call(
+            RuntimeOrigin::root(),
+            b"System".to_vec(),
+            Some(b"remark".to_vec())
+        ));
+        assert_noop!(
+            MaintenanceMode::unpause_call(
+                RuntimeOrigin::root(),
+                b"System".to_vec(),
+                Some(b"remark".to_vec())
+            ),
+            Error::<Test>::NotPaused
+        );
+    })
+}
+
+#[test]
+fn cannot_pause_unknown_calls_or_the_maintenance_pallet() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            MaintenanceMode::pause_call(RuntimeOrigin::root(), b"Nope".to_vec(), None, None),
+            Error::<Test>::UnknownPallet
+        );
+        assert_noop!(
+            MaintenanceMode::pause_call(
+                RuntimeOrigin::root(),
+                b"System".to_vec(),
+                Some(b"nope".to_vec()),
+                None
+            ),
+            Error::<Test>::UnknownCall
+        );
+        assert_noop!(
+            MaintenanceMode::pause_call(
+                RuntimeOrigin::root(),
+                b"MaintenanceMode".to_vec(),
+                None,
+                None
+            ),
+            Error::<Test>::CannotPauseMaintenancePallet
+        );
+        assert_noop!(
+            MaintenanceMode::pause_call(RuntimeOrigin::root(), b"System".to_vec(), None, Some(1)),
+            Error::<Test>::ExpiryInPast
+        );
+    })
+}
+
+#[test]
+fn paused_scope_lifts_itself_at_expiry() {
+    ExtBuilder::default().build().execute_with(|| {
+        let scope = PauseScope::Bidding(*b"eth2");
+        assert!(!MaintenanceMode::is_scope_paused(&scope));
+
+        assert_ok!(MaintenanceMode::pause_scope(
+            RuntimeOrigin::signed(2),
+            scope.clone(),
+            Some(4)
+        ));
+        assert!(MaintenanceMode::is_scope_paused(&scope));
+        assert!(!MaintenanceMode::is_scope_paused(&PauseScope::Submission(
+            *b"eth2"
+        )));
+        assert_eq!(
+            MaintenanceMode::active_paused_scopes(),
+            vec![(scope.clone(), Some(4))]
+        );
+
+        System::set_block_number(4);
+        assert!(!MaintenanceMode::is_scope_paused(&scope));
+        assert_noop!(
+            MaintenanceMode::unpause_scope(RuntimeOrigin::root(), scope),
+            Error::<Test>::NotPaused
+        );
+    })
+}
+
+#[test]
+fn cannot_pause_unpausable_pallets_from_any_origin() {
+    ExtBuilder::default().build().execute_with(|| {
+        for origin in [RuntimeOrigin::root(), RuntimeOrigin::signed(2)] {
+            assert_noop!(
+                MaintenanceMode::pause_call(
+                    origin,
+                    b"MockPalletMaintenanceHooks".to_vec(),
+                    None,
+                    None
+                ),
+                Error::<Test>::CannotPausePallet
+            );
+        }
+    })
+}
+
+#[test]
+fn pause_guardians_are_set_by_the_maintenance_origin() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert!(EnsurePauseGuardian::<Test>::try_origin(RuntimeOrigin::signed(3)).is_err());
+        assert_noop!(
+            MaintenanceMode::set_pause_guardian(RuntimeOrigin::signed(2), 3, true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(MaintenanceMode::set_pause_guardian(
+            RuntimeOrigin::root(),
+            3,
+            true
+        ));
+        assert_eq!(
+            events(),
+            vec![Event::PauseGuardianSet {
+                who: 3,
+                enabled: true
+            }]
+        );
+        assert_eq!(
+            EnsurePauseGuardian::<Test>::try_origin(RuntimeOrigin::signed(3)).ok(),
+            Some(3)
+        );
+        assert!(EnsurePauseGuardian::<Test>::try_origin(RuntimeOrigin::root()).is_err());
+
+        assert_ok!(MaintenanceMode::set_pause_guardian(
+            RuntimeOrigin::root(),
+            3,
+            false
+        ));
+        assert!(EnsurePauseGuardian::<Test>::try_origin(RuntimeOrigin::signed(3)).is_err());
+    })
+}
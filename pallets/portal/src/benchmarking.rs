@@ -0,0 +1,50 @@
+//! Benchmarking setup for pallet-portal
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::benchmarks;
+use frame_support::traits::EnsureOrigin;
+use t3rn_abi::standard::standard_sfx_abi_ids;
+use t3rn_primitives::{ExecutionVendor, SubstrateToken};
+
+const BENCHMARKED_GATEWAY: [u8; 4] = *b"bnch";
+const BENCHMARKED_TOKEN: u32 = 7_777;
+
+fn allowed_side_effects(s: u32) -> Vec<([u8; 4], Option<u8>)> {
+    standard_sfx_abi_ids()
+        .into_iter()
+        .take(s as usize)
+        .map(|sfx_4b_id| (sfx_4b_id, None))
+        .collect()
+}
+
+benchmarks! {
+    // Governance registration is the heavier one: the token is new, so it's created as well.
+    // Light client initialization depends on the vendor and isn't part of this benchmark.
+    register_gateway {
+        let s in 0 .. standard_sfx_abi_ids().len() as u32;
+        let origin = T::GatewayRegistrationOrigin::try_successful_origin()
+            .map_err(|_| "GatewayRegistrationOrigin must have a successful origin")?;
+        let token_props = TokenInfo::Substrate(SubstrateToken {
+            id: BENCHMARKED_TOKEN,
+            symbol: b"BNCH".to_vec(),
+            decimals: 12,
+        });
+    }: _<T::RuntimeOrigin>(
+        origin,
+        BENCHMARKED_GATEWAY,
+        BENCHMARKED_TOKEN,
+        GatewayVendor::XBI,
+        ExecutionVendor::Substrate,
+        t3rn_abi::Codec::Scale,
+        None,
+        None,
+        allowed_side_effects(s),
+        token_props,
+        vec![]
+    )
+    verify {
+        assert!(<T as Config>::Xdns::is_token_registered(BENCHMARKED_TOKEN));
+        assert!(<T as Config>::Xdns::get_verification_vendor(&BENCHMARKED_GATEWAY).is_ok());
+    }
+}
@@ -5,7 +5,7 @@ use frame_support::{
     traits::Get,
 };
 use frame_system::{
-    ensure_root,
+    ensure_signed,
     pallet_prelude::{BlockNumberFor, OriginFor},
 };
 
@@ -16,6 +16,9 @@ use t3rn_abi::recode::{recode_bytes_with_descriptor, Codec};
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 use frame_support::transactional;
 use t3rn_abi::types::Bytes;
 use t3rn_primitives::{
@@ -56,6 +59,9 @@ pub mod pallet {
         type WeightInfo: crate::weights::WeightInfo;
         /// Selects the light client implementation
         type SelectLightClient: SelectLightClient<Self>;
+        /// Governance origin registering gateways on behalf of registrants. It's passed through
+        /// to create new assets and to initialize light clients, so it must be accepted there too.
+        type GatewayRegistrationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
     }
 
     #[pallet::pallet]
@@ -103,6 +109,10 @@ pub mod pallet {
         SideEffectConfirmationFailed,
         /// Recoding failed
         SFXRecodeError,
+        /// Only governance can initialize the light client at gateway registration
+        RegistrationDataRequiresGovernance,
+        /// Only governance can register new tokens at gateway registration
+        TokenRegistrationRequiresGovernance,
     }
 
     // Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -110,7 +120,15 @@ pub mod pallet {
     // Dispatchable functions must be annotated with a weight and must return a DispatchResult.
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        #[pallet::weight(T::DbWeight::get().writes(1))]
+        /// Registers a new gateway. GatewayRegistrationOrigin registers on behalf of the optional
+        /// registrant, may create the gateway's token and initialize its light client.
+        /// Any signed origin can register permissionlessly, becoming the gateway's registrant
+        /// and locking the XDNS registration deposit, slashable by governance. Permissionless
+        /// registrants can only link tokens which are already registered.
+        #[pallet::weight(T::WeightInfo::register_gateway(
+            allowed_side_effects.len() as u32,
+            encoded_registration_data.len() as u32,
+        ))]
         #[transactional]
         pub fn register_gateway(
             origin: OriginFor<T>,
@@ -125,7 +143,24 @@ pub mod pallet {
             token_props: TokenInfo,
             encoded_registration_data: Bytes,
         ) -> DispatchResult {
-            ensure_root(origin.clone())?;
+            let maybe_signer = match T::GatewayRegistrationOrigin::try_origin(origin.clone()) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+            let registrant = match maybe_signer {
+                Some(ref signer) => {
+                    ensure!(
+                        encoded_registration_data.is_empty(),
+                        Error::<T>::RegistrationDataRequiresGovernance
+                    );
+                    ensure!(
+                        <T as Config>::Xdns::is_token_registered(token_id),
+                        Error::<T>::TokenRegistrationRequiresGovernance
+                    );
+                    Some(signer.clone())
+                },
+                None => registrant,
+            };
             <T as Config>::Xdns::add_new_gateway(
                 gateway_id,
                 verification_vendor,
@@ -135,7 +170,11 @@ pub mod pallet {
                 escrow_account,
                 allowed_side_effects,
             )?;
-            <T as Config>::Xdns::register_new_token(&origin, token_id, token_props.clone())?;
+            if let Some(ref signer) = maybe_signer {
+                <T as Config>::Xdns::reserve_registration_deposit(signer, gateway_id)?;
+            } else if !<T as Config>::Xdns::is_token_registered(token_id) {
+                <T as Config>::Xdns::register_new_token(&origin, token_id, token_props.clone())?;
+            }
             <T as Config>::Xdns::link_token_to_gateway(token_id, gateway_id, token_props)?;
             if encoded_registration_data.len() > 0 {
                 <Pallet<T> as Portal<T>>::initialize(
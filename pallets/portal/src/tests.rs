@@ -3,7 +3,10 @@ mod tests {
     use ::pallet_eth2_finality_verifier::mock::{generate_epoch_update, generate_initialization};
     use circuit_mock_runtime::{ExtBuilder, Portal, RuntimeOrigin as Origin, XDNS, *};
     use codec::Encode;
-    use frame_support::assert_ok;
+    use frame_support::{
+        assert_noop, assert_ok,
+        traits::{Currency, Get, ReservableCurrency},
+    };
     use pallet_grandpa_finality_verifier::{
         bridges::test_utils::{authorities, test_header_with_correct_parent},
         mock::produce_mock_headers_range,
@@ -12,6 +15,7 @@ mod tests {
 
     use t3rn_primitives::{
         portal::{HeaderResult, HeightResult, Portal as PortalT},
+        xdns::Xdns,
         EthereumToken, ExecutionVendor, GatewayVendor, TokenInfo,
     };
 
@@ -273,6 +277,76 @@ mod tests {
             });
     }
 
+    fn register_xbi_gateway(
+        origin: Origin,
+        gateway_id: [u8; 4],
+        token_id: u32,
+        encoded_registration_data: Vec<u8>,
+    ) -> frame_support::dispatch::DispatchResult {
+        Portal::register_gateway(
+            origin,
+            gateway_id,
+            token_id,
+            GatewayVendor::XBI,
+            ExecutionVendor::Substrate,
+            t3rn_abi::Codec::Scale,
+            None,
+            None,
+            vec![(*b"tran", None)],
+            TokenInfo::Ethereum(EthereumToken {
+                address: Some([0u8; 20]),
+                decimals: 0,
+                symbol: vec![0u8; 1],
+            }),
+            encoded_registration_data,
+        )
+    }
+
+    #[test]
+    fn test_register_gateway_permissionlessly_reserves_deposit() {
+        ExtBuilder::default()
+            .with_standard_sfx_abi()
+            .with_default_xdns_records()
+            .build()
+            .execute_with(|| {
+                let deposit = <Runtime as pallet_xdns::Config>::GatewayRegistrationDeposit::get();
+                let _ = Balances::deposit_creating(&ALICE, deposit * 2);
+
+                assert_noop!(
+                    register_xbi_gateway(Origin::signed(ALICE), [57u8; 4], 101u32, vec![1u8]),
+                    pallet_portal::Error::<Runtime>::RegistrationDataRequiresGovernance
+                );
+                // Signed registrants can't create assets
+                assert_noop!(
+                    register_xbi_gateway(Origin::signed(ALICE), [57u8; 4], 101u32, vec![]),
+                    pallet_portal::Error::<Runtime>::TokenRegistrationRequiresGovernance
+                );
+                assert!(!XDNS::is_token_registered(101u32));
+
+                // Governance registers the token along with its gateway
+                assert_ok!(register_xbi_gateway(
+                    Origin::root(),
+                    [56u8; 4],
+                    101u32,
+                    vec![]
+                ));
+                assert!(XDNS::is_token_registered(101u32));
+                assert_eq!(Balances::reserved_balance(&ALICE), 0);
+
+                assert_ok!(register_xbi_gateway(
+                    Origin::signed(ALICE),
+                    [57u8; 4],
+                    101u32,
+                    vec![]
+                ));
+
+                let gateway = XDNS::gateways([57u8; 4]).unwrap();
+                assert_eq!(gateway.registrant, Some(ALICE));
+                assert_eq!(Balances::reserved_balance(&ALICE), deposit);
+                assert_eq!(XDNS::gateway_deposits([57u8; 4]), Some((ALICE, deposit)));
+            });
+    }
+
     #[test]
     fn test_register_gateway_at_xbi_vendor() {
         ExtBuilder::default()
@@ -37,7 +37,7 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for pallet_xdns.
 pub trait WeightInfo {
-    fn register_gateway() -> Weight;
+    fn register_gateway(s: u32, b: u32) -> Weight;
     fn set_owner() -> Weight;
     fn set_operational() -> Weight;
     fn submit_headers() -> Weight;
@@ -47,10 +47,17 @@ pub trait WeightInfo {
 pub struct SubstrateWeight<T>(PhantomData<T>);
 
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-    fn register_gateway() -> Weight {
-        Weight::from_parts(72_795_000_u64, 0u64)
-            .saturating_add(T::DbWeight::get().reads(2_u64))
-            .saturating_add(T::DbWeight::get().writes(1_u64))
+    // Storage access follows the register_gateway benchmark of the governance registration,
+    // which also creates the token. Execution times are estimates until it's re-run on reference
+    // hardware. The light client initialization with registration data is linear in its length.
+    fn register_gateway(s: u32, b: u32) -> Weight {
+        Weight::from_parts(180_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(12_000_000_u64, 0u64).saturating_mul(s.into()))
+            .saturating_add(Weight::from_parts(10_000_u64, 0u64).saturating_mul(b.into()))
+            .saturating_add(T::DbWeight::get().reads(14_u64))
+            .saturating_add(T::DbWeight::get().writes(12_u64))
+            .saturating_add(T::DbWeight::get().reads(1_u64).saturating_mul(s.into()))
+            .saturating_add(T::DbWeight::get().writes(1_u64).saturating_mul(s.into()))
     }
 
     fn set_owner() -> Weight {
@@ -72,10 +79,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-    fn register_gateway() -> Weight {
-        Weight::from_parts(72_795_000_u64, 0u64)
-            .saturating_add(RocksDbWeight::get().reads(2_u64))
-            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    // Storage access follows the register_gateway benchmark of the governance registration,
+    // which also creates the token. Execution times are estimates until it's re-run on reference
+    // hardware. The light client initialization with registration data is linear in its length.
+    fn register_gateway(s: u32, b: u32) -> Weight {
+        Weight::from_parts(180_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(12_000_000_u64, 0u64).saturating_mul(s.into()))
+            .saturating_add(Weight::from_parts(10_000_u64, 0u64).saturating_mul(b.into()))
+            .saturating_add(RocksDbWeight::get().reads(14_u64))
+            .saturating_add(RocksDbWeight::get().writes(12_u64))
+            .saturating_add(RocksDbWeight::get().reads(1_u64).saturating_mul(s.into()))
+            .saturating_add(RocksDbWeight::get().writes(1_u64).saturating_mul(s.into()))
     }
 
     fn set_owner() -> Weight {
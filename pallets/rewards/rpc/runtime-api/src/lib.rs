@@ -0,0 +1,20 @@
+//! Runtime API definition required by Rewards RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to expose the rewards accrued by nominators.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+    /// The API to inspect the rewards distributed by pallet Rewards
+    pub trait RewardsRuntimeApi<AccountId, Balance> where
+        AccountId: Codec,
+        Balance: Codec,
+    {
+        /// Returns the rewards accrued by the nominator per nominated attester since its last staker claim
+        fn accrued_nominator_rewards(nominator: AccountId) -> Vec<(AccountId, Balance)>;
+    }
+}
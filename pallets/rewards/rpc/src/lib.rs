@@ -0,0 +1,73 @@
+//! RPC interface for the Rewards pallet.
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::CallError,
+};
+pub use pallet_rewards_rpc_runtime_api::RewardsRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+use std::sync::Arc;
+
+const RUNTIME_ERROR: i64 = 1;
+
+#[rpc(client, server)]
+pub trait RewardsApi<AccountId, Balance> {
+    /// Returns the rewards accrued by the nominator per nominated attester since its last staker claim
+    #[method(name = "rewards_accruedNominatorRewards")]
+    fn accrued_nominator_rewards(
+        &self,
+        nominator: AccountId,
+    ) -> RpcResult<Vec<(AccountId, Balance)>>;
+}
+
+/// A struct that implements the [`RewardsApiServer`].
+pub struct Rewards<C, P> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Rewards<C, P> {
+    /// Create new `Rewards` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance> RewardsApiServer<AccountId, Balance> for Rewards<C, Block>
+where
+    AccountId: Codec + MaybeDisplay,
+    Balance: Codec + MaybeDisplay,
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: RewardsRuntimeApi<Block, AccountId, Balance>,
+{
+    fn accrued_nominator_rewards(
+        &self,
+        nominator: AccountId,
+    ) -> RpcResult<Vec<(AccountId, Balance)>> {
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+
+        let result = api
+            .accrued_nominator_rewards(at, nominator)
+            .map_err(runtime_error_into_rpc_err)?;
+
+        Ok(result)
+    }
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(jsonrpsee::types::ErrorObject::owned(
+        RUNTIME_ERROR as i32,
+        "Runtime Error - Rewards RPC",
+        Some(format!("{err:?}")),
+    )))
+}
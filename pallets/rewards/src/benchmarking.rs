@@ -0,0 +1,100 @@
+//! Benchmarking setup for pallet-rewards
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    Perbill,
+};
+use sp_std::prelude::*;
+use t3rn_primitives::{
+    claimable::{BenefitSource, CircuitRole, ClaimableArtifacts},
+    monetary::InflationCurve,
+};
+
+fn pending_claims<T: Config>(who: &T::AccountId, n: u32) {
+    let claims = (0..n)
+        .map(|_| ClaimableArtifacts {
+            beneficiary: who.clone(),
+            role: CircuitRole::Executor,
+            total_round_claim: T::Currency::minimum_balance().saturating_mul(100u32.into()),
+            benefit_source: BenefitSource::TrafficRewards,
+            non_native_asset_id: None,
+        })
+        .collect::<Vec<_>>();
+    PendingClaims::<T>::insert(who, claims);
+}
+
+/// Fills the vesting schedules of the account with schedules still locked at the current block.
+fn vesting_schedules<T: Config>(who: &T::AccountId, s: u32) {
+    let now = frame_system::Pallet::<T>::block_number();
+    let schedules = (0..s)
+        .map(|_| VestingSchedule {
+            locked: T::Currency::minimum_balance().saturating_mul(100u32.into()),
+            starting_block: now,
+            ending_block: now.saturating_add(T::ClaimVestingPeriod::get()),
+        })
+        .collect::<Vec<_>>();
+    VestingSchedules::<T>::insert(
+        who,
+        BoundedVec::<_, T::MaxVestingSchedules>::truncate_from(schedules),
+    );
+}
+
+benchmarks! {
+    // Vesting is the heaviest mode measurable without a registered attester: all claims are vested
+    // into a new schedule next to an almost full set of the existing ones.
+    claim {
+        let n in 1 .. T::MaxClaimsPerCall::get();
+        let caller: T::AccountId = whitelisted_caller();
+        pending_claims::<T>(&caller, n);
+        vesting_schedules::<T>(&caller, T::MaxVestingSchedules::get().saturating_sub(1));
+        DefaultClaimModes::<T>::insert(&caller, CircuitRole::Executor, ClaimMode::Vesting);
+    }: _(RawOrigin::Signed(caller.clone()), None)
+    verify {
+        assert_eq!(PendingClaims::<T>::get(&caller).map(|claims| claims.len()), Some(0));
+        assert_eq!(VestingSchedules::<T>::get(&caller).len() as u32, T::MaxVestingSchedules::get());
+    }
+
+    set_default_claim_mode {
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller.clone()), CircuitRole::Executor, ClaimMode::Vesting)
+    verify {
+        assert_eq!(DefaultClaimModes::<T>::get(&caller, CircuitRole::Executor), Some(ClaimMode::Vesting));
+    }
+
+    vest {
+        let s in 1 .. T::MaxVestingSchedules::get();
+        let caller: T::AccountId = whitelisted_caller();
+        vesting_schedules::<T>(&caller, s);
+        let halfway: BlockNumberFor<T> = T::ClaimVestingPeriod::get() / 2u32.into();
+        frame_system::Pallet::<T>::set_block_number(
+            frame_system::Pallet::<T>::block_number().saturating_add(halfway),
+        );
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert_eq!(VestingSchedules::<T>::get(&caller).len() as u32, s);
+    }
+
+    set_inflation_allocation {
+        let allocation = Pallet::<T>::current_inflation_allocation_base();
+    }: _(RawOrigin::Root, allocation.clone())
+    verify {
+        assert_eq!(InflationAllocationBase::<T>::get(), Some(allocation));
+    }
+
+    // Setting a curve checks its floor against the allocation, removing it doesn't
+    set_inflation_curve {
+        let curve = InflationCurve {
+            starting_block: frame_system::Pallet::<T>::block_number(),
+            annual_decay: Perbill::from_percent(10),
+            floor: Perbill::zero(),
+        };
+    }: _(RawOrigin::Root, Some(curve.clone()))
+    verify {
+        assert_eq!(InflationDecayCurve::<T>::get(), Some(curve));
+    }
+}
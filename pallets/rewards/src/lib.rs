@@ -1,6 +1,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use crate::pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -11,7 +16,11 @@ pub mod pallet {
     use frame_support::{
         dispatch::DispatchResult,
         pallet_prelude::*,
-        traits::{Currency, ExistenceRequirement, FindAuthor, Len, WithdrawReasons},
+        storage::with_storage_layer,
+        traits::{
+            Currency, ExistenceRequirement, FindAuthor, Len, LockIdentifier, LockableCurrency,
+            WithdrawReasons,
+        },
     };
     use frame_system::pallet_prelude::*;
     use sp_core::H256;
@@ -23,17 +32,40 @@ pub mod pallet {
     use sp_std::{collections::btree_map::BTreeMap, convert::TryInto, prelude::*};
     use t3rn_primitives::{
         account_manager::{AccountManager, Settlement},
-        attesters::AttestersReadApi,
+        attesters::{AttestersReadApi, AttestersStakingApi},
         circuit::{CircuitStatus, FullSideEffect},
         claimable::{BenefitSource, CircuitRole, ClaimableArtifacts},
-        clock::Clock as ClockTrait,
+        clock::{Cadence, Clock as ClockTrait, PeriodicTask, PeriodicTasks},
         common::RoundInfo,
+        executors::Executors,
+        monetary::{InflationAllocation, InflationCurve},
         rewards::RewardsWriteApi,
         TreasuryAccount, TreasuryAccountProvider,
     };
 
     pub const MAX_AUTHORS: u32 = 512;
 
+    pub const REWARDS_VESTING_ID: LockIdentifier = *b"trnrwvst";
+
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, Debug, TypeInfo)]
+    pub enum ClaimMode<AccountId> {
+        /// Pay out the claim immediately
+        Instant,
+        /// Lock native claims for the vesting period in exchange for the vesting bonus
+        Vesting,
+        /// Restake native claims by nominating the given attester
+        AutoNominate(AccountId),
+        /// Restake native executor claims into the executor's own bond
+        AutoBond,
+    }
+
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, Debug, TypeInfo)]
+    pub struct VestingSchedule<BlockNumber, Balance> {
+        pub locked: Balance,
+        pub starting_block: BlockNumber,
+        pub ending_block: BlockNumber,
+    }
+
     #[derive(Clone, Encode, Decode, PartialEq, Eq, Debug, TypeInfo, Default)]
     pub enum AssetType<AssetId> {
         #[default]
@@ -50,6 +82,40 @@ pub mod pallet {
         pub treasury_rewards: Balance,
         pub available: Balance,
         pub distributed: Balance,
+        /// Allocation in force for this distribution, after applying the decay curve
+        pub allocation: InflationAllocation,
+        pub curve: Option<InflationCurve<BlockNumber>>,
+    }
+
+    /// Distribution record prior to recording the inflation allocation and curve.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, Debug, TypeInfo)]
+    pub struct DistributionRecordV1<BlockNumber, Balance> {
+        pub block_number: BlockNumber,
+        pub attester_rewards: Balance,
+        pub collator_rewards: Balance,
+        pub executor_rewards: Balance,
+        pub treasury_rewards: Balance,
+        pub available: Balance,
+        pub distributed: Balance,
+    }
+
+    impl<BlockNumber, Balance> DistributionRecordV1<BlockNumber, Balance> {
+        pub fn into_record(
+            self,
+            allocation: InflationAllocation,
+        ) -> DistributionRecord<BlockNumber, Balance> {
+            DistributionRecord {
+                block_number: self.block_number,
+                attester_rewards: self.attester_rewards,
+                collator_rewards: self.collator_rewards,
+                executor_rewards: self.executor_rewards,
+                treasury_rewards: self.treasury_rewards,
+                available: self.available,
+                distributed: self.distributed,
+                allocation,
+                curve: None,
+            }
+        }
     }
 
     #[derive(Clone, Encode, Decode, PartialEq, Eq, Debug, TypeInfo, Default)]
@@ -65,13 +131,14 @@ pub mod pallet {
     pub trait Config: frame_system::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-        type Currency: Currency<Self::AccountId>;
+        type Currency: LockableCurrency<Self::AccountId>;
 
         /// Find the author of a block.
         type FindAuthor: FindAuthor<Self::AccountId>;
 
         type TreasuryAccounts: TreasuryAccountProvider<Self::AccountId>;
         /// The total inflation per year, expressed as a Perbill.
+        /// The inflation constants are used until governance sets the `InflationAllocationBase`.
         ///
         /// Default: 4.4% (44_000_000 / 1_000_000_000)
         #[pallet::constant]
@@ -123,6 +190,30 @@ pub mod pallet {
 
         type StartingRepatriationPercentage: Get<Percent>;
 
+        /// The number of blocks over which rewards claimed in vesting mode unlock linearly.
+        ///
+        /// Default: 1_296_000 (six months assuming 12s block time)
+        #[pallet::constant]
+        type ClaimVestingPeriod: Get<BlockNumberFor<Self>>;
+
+        /// The bonus minted on top of rewards claimed in vesting mode, vesting along with them.
+        ///
+        /// Default: 5%
+        #[pallet::constant]
+        type ClaimVestingBonus: Get<Percent>;
+
+        /// The maximum number of vesting schedules an account can hold at once.
+        ///
+        /// Default: 32
+        #[pallet::constant]
+        type MaxVestingSchedules: Get<u32>;
+
+        /// The maximum number of pending claims paid out by a single claim, the rest stays pending.
+        ///
+        /// Default: 64
+        #[pallet::constant]
+        type MaxClaimsPerCall: Get<u32>;
+
         type Clock: ClockTrait<Self>;
 
         type AccountManager: AccountManager<
@@ -133,7 +224,12 @@ pub mod pallet {
             u32,
         >;
 
-        type Attesters: AttestersReadApi<Self::AccountId, BalanceOf<Self>, BlockNumberFor<Self>>;
+        type Attesters: AttestersReadApi<Self::AccountId, BalanceOf<Self>, BlockNumberFor<Self>>
+            + AttestersStakingApi<Self::AccountId, BalanceOf<Self>, DispatchError>;
+
+        type Executors: Executors<Self, BalanceOf<Self>>;
+
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
@@ -190,6 +286,19 @@ pub mod pallet {
     #[pallet::storage]
     pub type IsDistributionHalted<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+    /// Inflation allocation set by governance, before applying the decay curve.
+    #[pallet::storage]
+    #[pallet::getter(fn inflation_allocation_base)]
+    pub type InflationAllocationBase<T: Config> = StorageValue<_, InflationAllocation>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn inflation_curve)]
+    pub type InflationDecayCurve<T: Config> = StorageValue<_, InflationCurve<BlockNumberFor<T>>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn storage_migrations_done)]
+    pub type StorageMigrations<T: Config> = StorageValue<_, u32, ValueQuery>;
+
     #[pallet::storage]
     pub type IsSettlementAccumulationHalted<T: Config> = StorageValue<_, bool, ValueQuery>;
 
@@ -212,6 +321,46 @@ pub mod pallet {
         Vec<ClaimableArtifacts<T::AccountId, BalanceOf<T>>>,
     >;
 
+    /// Default claim mode per account and role, used by `claim` unless the mode is given explicitly.
+    #[pallet::storage]
+    #[pallet::getter(fn default_claim_mode)]
+    pub type DefaultClaimModes<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        CircuitRole,
+        ClaimMode<T::AccountId>,
+    >;
+
+    /// Rewards accrued by nominators per nominated attester since their last claim of staker rewards.
+    #[pallet::storage]
+    #[pallet::getter(fn accrued_nominator_rewards)]
+    pub type AccruedNominatorRewards<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId, // Nominator
+        Blake2_128Concat,
+        T::AccountId, // Attester
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn vesting_schedules)]
+    pub type VestingSchedules<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        BoundedVec<VestingSchedule<BlockNumberFor<T>, BalanceOf<T>>, T::MaxVestingSchedules>,
+        ValueQuery,
+    >;
+
+    /// Vesting bonuses paid out since the last distribution, deducted from the next distribution budget.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_vesting_bonuses)]
+    pub type PendingVestingBonuses<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -221,7 +370,22 @@ pub mod pallet {
         // old, new kickbacks to executors (in percent of max_reward)
         NewMaxRewardExecutorsKickbackSet(Percent, Percent),
         Claimed(T::AccountId, Vec<(BalanceOf<T>, Option<u32>)>),
+        // claimed, bonus, ending block of vesting
+        ClaimedVested(T::AccountId, BalanceOf<T>, BalanceOf<T>, BlockNumberFor<T>),
+        // nominator, attester, restaked amount
+        ClaimedAutoNominated(T::AccountId, T::AccountId, BalanceOf<T>),
+        // executor, restaked amount
+        ClaimedAutoBonded(T::AccountId, BalanceOf<T>),
+        // account, amount paid out instead of being restaked
+        AutoRestakeFailed(T::AccountId, BalanceOf<T>),
         PendingClaim(T::AccountId, BalanceOf<T>),
+        DefaultClaimModeSet(T::AccountId, CircuitRole, ClaimMode<T::AccountId>),
+        // account, amount still locked
+        VestingUnlocked(T::AccountId, BalanceOf<T>),
+        InflationAllocationSet(InflationAllocation),
+        InflationCurveSet(Option<InflationCurve<BlockNumberFor<T>>>),
+        // attester, commission reward, reward split between the nominators
+        AttesterRewardSplit(T::AccountId, BalanceOf<T>, BalanceOf<T>),
     }
 
     #[pallet::error]
@@ -232,6 +396,11 @@ pub mod pallet {
         AttesterNotFound,
         TryIntoConversionU128ToBalanceFailed,
         Halted,
+        NoVestingSchedules,
+        TooManyVestingSchedules,
+        ClaimModeNotAllowedForRole,
+        InvalidInflationAllocation,
+        InvalidInflationCurve,
     }
 
     #[pallet::call]
@@ -282,15 +451,101 @@ pub mod pallet {
             Ok(())
         }
 
-        #[pallet::weight(100_000)]
+        #[pallet::weight(T::WeightInfo::claim(T::MaxClaimsPerCall::get()))]
         pub fn claim(
             origin: OriginFor<T>,
             role_to_claim: Option<CircuitRole>, // Add this parameter
         ) -> DispatchResultWithPostInfo {
-            // ensure claiming is not halted
-            ensure!(!IsClaimingHalted::<T>::get(), Error::<T>::Halted);
+            let who = ensure_signed(origin)?;
+            Self::do_claim(who, role_to_claim, None)
+        }
+
+        /// Claims pending rewards with the given mode, overriding the defaults set per role.
+        #[pallet::weight(T::WeightInfo::claim(T::MaxClaimsPerCall::get()))]
+        pub fn claim_with_mode(
+            origin: OriginFor<T>,
+            role_to_claim: Option<CircuitRole>,
+            mode: ClaimMode<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_claim_mode_valid(&mode, role_to_claim.as_ref())?;
+            Self::do_claim(who, role_to_claim, Some(mode))
+        }
 
+        /// Sets the mode `claim` uses for rewards of the given role, e.g. to compound attester
+        /// rewards every round without manual nominations.
+        #[pallet::weight(T::WeightInfo::set_default_claim_mode())]
+        pub fn set_default_claim_mode(
+            origin: OriginFor<T>,
+            role: CircuitRole,
+            mode: ClaimMode<T::AccountId>,
+        ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            Self::ensure_claim_mode_valid(&mode, Some(&role))?;
+            DefaultClaimModes::<T>::insert(&who, &role, mode.clone());
+            Self::deposit_event(Event::DefaultClaimModeSet(who, role, mode));
+            Ok(())
+        }
+
+        /// Sets the yearly inflation and its split between roles. Portions must add up to the total.
+        #[pallet::weight(T::WeightInfo::set_inflation_allocation())]
+        pub fn set_inflation_allocation(
+            origin: OriginFor<T>,
+            allocation: InflationAllocation,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                allocation.is_valid(),
+                Error::<T>::InvalidInflationAllocation
+            );
+            InflationAllocationBase::<T>::put(allocation.clone());
+            Self::deposit_event(Event::InflationAllocationSet(allocation));
+            Ok(())
+        }
+
+        /// Sets the year-over-year decay of the inflation allocation, or removes it with `None`.
+        #[pallet::weight(T::WeightInfo::set_inflation_curve())]
+        pub fn set_inflation_curve(
+            origin: OriginFor<T>,
+            curve: Option<InflationCurve<BlockNumberFor<T>>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            match curve {
+                Some(ref curve) => {
+                    ensure!(
+                        curve.floor <= Self::current_inflation_allocation_base().total,
+                        Error::<T>::InvalidInflationCurve
+                    );
+                    InflationDecayCurve::<T>::put(curve)
+                },
+                None => InflationDecayCurve::<T>::kill(),
+            }
+            Self::deposit_event(Event::InflationCurveSet(curve));
+            Ok(())
+        }
+
+        /// Unlocks the already vested part of rewards claimed in vesting mode.
+        #[pallet::weight(T::WeightInfo::vest(T::MaxVestingSchedules::get()))]
+        pub fn vest(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                VestingSchedules::<T>::contains_key(&who),
+                Error::<T>::NoVestingSchedules
+            );
+            let still_locked = Self::update_vesting_lock(&who);
+            Self::deposit_event(Event::VestingUnlocked(who, still_locked));
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        fn do_claim(
+            who: T::AccountId,
+            role_to_claim: Option<CircuitRole>,
+            mode_override: Option<ClaimMode<T::AccountId>>,
+        ) -> DispatchResultWithPostInfo {
+            // ensure claiming is not halted
+            ensure!(!IsClaimingHalted::<T>::get(), Error::<T>::Halted);
 
             // Ensure there are pending claims
             ensure!(
@@ -305,57 +560,284 @@ pub mod pallet {
                     .take()
                     .ok_or(Error::<T>::NoPendingClaims)?;
 
-                // Filter by the specified role if provided
-                let claims_to_process = match role_to_claim {
-                    Some(ref role) => pending_claims
-                        .iter()
-                        .filter(|claim| &claim.role == role)
-                        .cloned()
-                        .collect::<Vec<_>>(),
-                    None => pending_claims.clone(),
-                };
+                // Filter by the specified role if provided, the claims above MaxClaimsPerCall stay pending
+                let indices_to_process = pending_claims
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, claim)| {
+                        role_to_claim
+                            .as_ref()
+                            .map_or(true, |role| &claim.role == role)
+                    })
+                    .map(|(index, _)| index)
+                    .take(T::MaxClaimsPerCall::get() as usize)
+                    .collect::<Vec<_>>();
 
                 let mut total_claimed_assets: Vec<(BalanceOf<T>, Option<u32>)> = vec![];
+                let mut total_vested: BalanceOf<T> = Zero::zero();
+                let mut total_bonded: BalanceOf<T> = Zero::zero();
+                let mut total_nominated: Vec<(T::AccountId, BalanceOf<T>)> = vec![];
 
-                for claim in claims_to_process.iter() {
+                for claim in indices_to_process
+                    .iter()
+                    .map(|index| &pending_claims[*index])
+                {
                     ensure!(
                         claim.total_round_claim > BalanceOf::<T>::zero(),
                         Error::<T>::NoPendingClaims
                     );
 
-                    // accumulate the total round claim per asset
-                    if let Some(position) = total_claimed_assets
-                        .iter()
-                        .position(|&(_, asset_id)| asset_id == claim.non_native_asset_id)
-                    {
-                        let (balance, _) = &mut total_claimed_assets[position];
-                        *balance = balance.saturating_add(claim.total_round_claim);
-                    } else {
-                        total_claimed_assets
-                            .push((claim.total_round_claim, claim.non_native_asset_id));
+                    let mode = match mode_override {
+                        Some(ref mode) => mode.clone(),
+                        None => DefaultClaimModes::<T>::get(&who, &claim.role)
+                            .unwrap_or(ClaimMode::Instant),
+                    };
+
+                    match (mode, claim.non_native_asset_id) {
+                        (ClaimMode::Vesting, None) => {
+                            total_vested = total_vested.saturating_add(claim.total_round_claim);
+                        },
+                        (ClaimMode::AutoNominate(attester), None) => {
+                            if let Some(position) =
+                                total_nominated.iter().position(|(a, _)| a == &attester)
+                            {
+                                let (_, balance) = &mut total_nominated[position];
+                                *balance = balance.saturating_add(claim.total_round_claim);
+                            } else {
+                                total_nominated.push((attester, claim.total_round_claim));
+                            }
+                        },
+                        (ClaimMode::AutoBond, None) if claim.role == CircuitRole::Executor => {
+                            total_bonded = total_bonded.saturating_add(claim.total_round_claim);
+                        },
+                        // Only native claims can be vested or restaked, and only executors bond theirs
+                        _ => {
+                            // accumulate the total round claim per asset
+                            if let Some(position) = total_claimed_assets
+                                .iter()
+                                .position(|&(_, asset_id)| asset_id == claim.non_native_asset_id)
+                            {
+                                let (balance, _) = &mut total_claimed_assets[position];
+                                *balance = balance.saturating_add(claim.total_round_claim);
+                            } else {
+                                total_claimed_assets
+                                    .push((claim.total_round_claim, claim.non_native_asset_id));
+                            }
+                        },
                     }
                 }
 
+                if !total_vested.is_zero() {
+                    let (bonus, ending_block) = Self::vest_claimed(&who, total_vested)?;
+                    Self::deposit_event(Event::ClaimedVested(
+                        who.clone(),
+                        total_vested,
+                        bonus,
+                        ending_block,
+                    ));
+                }
+
                 for (balance, asset_id) in total_claimed_assets.iter() {
                     T::AccountManager::deposit_immediately(&who, *balance, *asset_id);
                 }
+
+                // Restaking never holds the claim back: if the nomination is rejected, e.g. the attester
+                // deregistered or the claim is below the minimum bond, the claim stays paid out instead.
+                for (attester, balance) in total_nominated {
+                    T::AccountManager::deposit_immediately(&who, balance, None);
+                    match with_storage_layer(|| {
+                        T::Attesters::nominate_on_behalf(&who, &attester, balance)
+                    }) {
+                        Ok(()) => Self::deposit_event(Event::ClaimedAutoNominated(
+                            who.clone(),
+                            attester,
+                            balance,
+                        )),
+                        Err(err) => {
+                            log::warn!(
+                                "Auto-nomination of {:?} by {:?} failed, claim paid out instead: {:?}",
+                                attester,
+                                who,
+                                err
+                            );
+                            Self::deposit_event(Event::AutoRestakeFailed(who.clone(), balance));
+                        },
+                    }
+                }
+
+                if !total_bonded.is_zero() {
+                    T::AccountManager::deposit_immediately(&who, total_bonded, None);
+                    if T::Executors::increase_bond(&who, total_bonded).is_zero() {
+                        Self::deposit_event(Event::AutoRestakeFailed(who.clone(), total_bonded));
+                    } else {
+                        Self::deposit_event(Event::ClaimedAutoBonded(who.clone(), total_bonded));
+                    }
+                }
+
                 // remove processed claims
-                pending_claims.retain(|claim| !claims_to_process.contains(claim));
-                *maybe_pending_claims = Some(pending_claims);
+                for index in indices_to_process.iter().rev() {
+                    pending_claims.remove(*index);
+                }
+
+                // reset the subjects to future inflation distribution of accumulated claims, once all claims of the role are paid out
+                let is_role_claimed = |role: CircuitRole| {
+                    role_to_claim
+                        .as_ref()
+                        .map_or(true, |claimed| claimed == &role)
+                        && !pending_claims.iter().any(|claim| claim.role == role)
+                };
 
-                // reset the subjects to future inflation distribution of accumulated claims for executors
-                if role_to_claim == Some(CircuitRole::Executor) || None == role_to_claim {
+                if is_role_claimed(CircuitRole::Executor) {
                     AccumulatedSettlements::<T>::remove_prefix(&who, None);
                 }
 
-                Self::deposit_event(Event::Claimed(who, total_claimed_assets));
+                if is_role_claimed(CircuitRole::Staker) {
+                    let _ = AccruedNominatorRewards::<T>::clear_prefix(&who, u32::MAX, None);
+                }
+
+                *maybe_pending_claims = Some(pending_claims);
+
+                if !total_claimed_assets.is_empty() {
+                    Self::deposit_event(Event::Claimed(who, total_claimed_assets));
+                }
 
-                Ok(().into())
+                Ok(Some(T::WeightInfo::claim(indices_to_process.len() as u32)).into())
             })
         }
-    }
 
-    impl<T: Config> Pallet<T> {
+        fn ensure_claim_mode_valid(
+            mode: &ClaimMode<T::AccountId>,
+            role: Option<&CircuitRole>,
+        ) -> DispatchResult {
+            match mode {
+                ClaimMode::AutoNominate(attester) => ensure!(
+                    T::Attesters::read_attester_info(attester).is_some(),
+                    Error::<T>::AttesterNotFound
+                ),
+                // Only executors hold a bond to restake into
+                ClaimMode::AutoBond => ensure!(
+                    role.map_or(true, |role| role == &CircuitRole::Executor),
+                    Error::<T>::ClaimModeNotAllowedForRole
+                ),
+                ClaimMode::Instant | ClaimMode::Vesting => {},
+            }
+            Ok(())
+        }
+
+        /// Pays out the native claim with the vesting bonus and locks both until the end of the vesting period.
+        /// The bonus is deducted from the next inflation distribution, keeping the emission on the curve.
+        fn vest_claimed(
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> Result<(BalanceOf<T>, BlockNumberFor<T>), DispatchError> {
+            let bonus = T::ClaimVestingBonus::get().mul_floor(amount);
+            let starting_block = frame_system::Pallet::<T>::block_number();
+            let ending_block = starting_block.saturating_add(T::ClaimVestingPeriod::get());
+
+            VestingSchedules::<T>::try_mutate(who, |schedules| {
+                // Make room by dropping the fully vested schedules first
+                schedules
+                    .retain(|schedule| !Self::still_locked(schedule, starting_block).is_zero());
+                schedules
+                    .try_push(VestingSchedule {
+                        locked: amount.saturating_add(bonus),
+                        starting_block,
+                        ending_block,
+                    })
+                    .map_err(|_| Error::<T>::TooManyVestingSchedules)
+            })?;
+
+            T::AccountManager::deposit_immediately(who, amount, None);
+            if !bonus.is_zero() {
+                T::Currency::deposit_creating(who, bonus);
+                PendingVestingBonuses::<T>::mutate(|bonuses| {
+                    *bonuses = bonuses.saturating_add(bonus)
+                });
+            }
+            Self::update_vesting_lock(who);
+
+            Ok((bonus, ending_block))
+        }
+
+        /// Re-evaluates the vesting lock of the account, dropping fully vested schedules. Returns the amount still locked.
+        fn update_vesting_lock(who: &T::AccountId) -> BalanceOf<T> {
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut total_locked: BalanceOf<T> = Zero::zero();
+
+            let mut schedules = VestingSchedules::<T>::get(who);
+            schedules.retain(|schedule| {
+                let still_locked = Self::still_locked(schedule, now);
+                total_locked = total_locked.saturating_add(still_locked);
+                !still_locked.is_zero()
+            });
+
+            if schedules.is_empty() {
+                VestingSchedules::<T>::remove(who);
+                T::Currency::remove_lock(REWARDS_VESTING_ID, who);
+            } else {
+                VestingSchedules::<T>::insert(who, schedules);
+                T::Currency::set_lock(
+                    REWARDS_VESTING_ID,
+                    who,
+                    total_locked,
+                    WithdrawReasons::all(),
+                );
+            }
+
+            total_locked
+        }
+
+        pub fn still_locked(
+            schedule: &VestingSchedule<BlockNumberFor<T>, BalanceOf<T>>,
+            now: BlockNumberFor<T>,
+        ) -> BalanceOf<T> {
+            if now >= schedule.ending_block {
+                return Zero::zero()
+            }
+            let elapsed = now.saturating_sub(schedule.starting_block);
+            let duration = schedule
+                .ending_block
+                .saturating_sub(schedule.starting_block);
+            let vested = Perbill::from_rational(elapsed, duration).mul_floor(schedule.locked);
+            schedule.locked.saturating_sub(vested)
+        }
+
+        /// Rewards the nominator accrued per nominated attester and hasn't claimed yet.
+        pub fn nominator_accrued_rewards(
+            nominator: &T::AccountId,
+        ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+            AccruedNominatorRewards::<T>::iter_prefix(nominator).collect()
+        }
+
+        /// Inflation allocation set by governance, defaulting to the inflation constants.
+        pub fn current_inflation_allocation_base() -> InflationAllocation {
+            InflationAllocationBase::<T>::get().unwrap_or_else(|| InflationAllocation {
+                total: T::TotalInflation::get(),
+                attester: T::AttesterInflation::get(),
+                executor: T::ExecutorInflation::get(),
+                collator: T::CollatorInflation::get(),
+                treasury: T::TreasuryInflation::get(),
+            })
+        }
+
+        /// Inflation allocation in force at the current block, after applying the decay curve.
+        pub fn current_inflation_allocation() -> InflationAllocation {
+            let base = Self::current_inflation_allocation_base();
+            match InflationDecayCurve::<T>::get() {
+                Some(curve) => {
+                    let now = frame_system::Pallet::<T>::block_number();
+                    let years_elapsed: u32 = now
+                        .saturating_sub(curve.starting_block)
+                        .checked_div(&T::OneYear::get())
+                        .unwrap_or_else(Zero::zero)
+                        .try_into()
+                        .unwrap_or(u32::MAX);
+                    base.decayed(&curve, years_elapsed)
+                },
+                None => base,
+            }
+        }
+
         pub fn distribute_inflation() -> Weight {
             // Ensure distribution is not halted
             if IsDistributionHalted::<T>::get() {
@@ -365,8 +847,9 @@ pub mod pallet {
             let total_issuance = T::Currency::total_issuance();
             let distribution_period = T::InflationDistributionPeriod::get();
             let one_year_blocks = T::OneYear::get();
-            // Include TotalInflation in the calculation
-            let total_inflation = T::TotalInflation::get();
+            // Include the total inflation after decay in the calculation
+            let allocation = Self::current_inflation_allocation();
+            let total_inflation = allocation.total;
             let inflated_total_issuance: BalanceOf<T> = total_inflation.mul_ceil(total_issuance);
 
             let balance_for_distribution =
@@ -377,18 +860,35 @@ pub mod pallet {
             log::debug!("balance_for_distribution: {:?}", balance_for_distribution);
             log::debug!("total_issuance: {:?}", total_issuance);
 
-            // Calculate each portion per percentages
-            let attester_rewards = T::AttesterInflation::get().mul_ceil(balance_for_distribution);
-            let executor_rewards = T::ExecutorInflation::get().mul_ceil(balance_for_distribution);
-            let collator_rewards = T::CollatorInflation::get().mul_ceil(balance_for_distribution);
-            let treasury_rewards = T::TreasuryInflation::get().mul_ceil(balance_for_distribution);
+            // Vesting bonuses paid out since the last distribution come out of this period's budget
+            let pending_vesting_bonuses = PendingVestingBonuses::<T>::get();
+            let vesting_bonuses = pending_vesting_bonuses.min(balance_for_distribution);
+            PendingVestingBonuses::<T>::put(
+                pending_vesting_bonuses.saturating_sub(vesting_bonuses),
+            );
+            let budget = balance_for_distribution.saturating_sub(vesting_bonuses);
+
+            // Split the budget by each role's share of the total: the budget is already decayed
+            let attester_rewards = allocation
+                .share_of_total(allocation.attester)
+                .mul_floor(budget);
+            let executor_rewards = allocation
+                .share_of_total(allocation.executor)
+                .mul_floor(budget);
+            let collator_rewards = allocation
+                .share_of_total(allocation.collator)
+                .mul_floor(budget);
+            let treasury_rewards = allocation
+                .share_of_total(allocation.treasury)
+                .mul_floor(budget);
 
             log::debug!("attester_rewards: {:?}", attester_rewards);
             log::debug!("executor_rewards: {:?}", executor_rewards);
             log::debug!("collator_rewards: {:?}", collator_rewards);
             log::debug!("treasury_rewards: {:?}", treasury_rewards);
-            // Distribute rewards to attesters
+            // Distribute rewards to attesters and sum the nominations splitting them over the next period anew
             let attester_rewards_distributed = Self::distribute_attester_rewards(attester_rewards);
+            let snapshot_period_weight = T::Attesters::start_nominations_snapshot_period();
 
             // Distribute rewards to collators
             let collator_rewards_distributed = Self::distribute_collator_rewards(collator_rewards);
@@ -420,14 +920,19 @@ pub mod pallet {
                 distributed: attester_rewards_distributed
                     + collator_rewards_distributed
                     + executor_rewards_distributed
-                    + treasury_rewards,
+                    + treasury_rewards
+                    + vesting_bonuses,
                 available: balance_for_distribution,
+                allocation,
+                curve: InflationDecayCurve::<T>::get(),
             };
             let mut history = DistributionHistory::<T>::get();
             history.push(distribution_record);
             DistributionHistory::<T>::put(history);
 
-            T::DbWeight::get().reads_writes(8, 8)
+            T::DbWeight::get()
+                .reads_writes(9, 9)
+                .saturating_add(snapshot_period_weight)
         }
 
         pub fn distribute_attester_rewards(current_distribution: BalanceOf<T>) -> BalanceOf<T> {
@@ -466,8 +971,8 @@ pub mod pallet {
                 );
                 let remaining_reward = reward_per_attester.saturating_sub(commission_reward);
 
-                // Get the attester's nominators
-                let nominators = T::Attesters::read_nominations(&attester);
+                // Split by the nominations summed over the rounds of the period, including the self-bond
+                let nominators = T::Attesters::read_nominations_snapshot(&attester);
 
                 let total_nomination: BalanceOf<T> = nominators
                     .iter()
@@ -475,6 +980,7 @@ pub mod pallet {
                     .fold(BalanceOf::<T>::zero(), |acc, x| acc.saturating_add(x));
 
                 // Distribute the remaining reward to the nominators
+                let mut nominators_reward: BalanceOf<T> = Zero::zero();
                 for (nominator, nomination_balance) in nominators {
                     let check_nominator_reward = remaining_reward
                         .saturating_mul(nomination_balance)
@@ -488,6 +994,12 @@ pub mod pallet {
                                 BenefitSource::Inflation,
                                 None,
                             );
+                            AccruedNominatorRewards::<T>::mutate(
+                                &nominator,
+                                &attester,
+                                |accrued| *accrued = accrued.saturating_add(nominator_reward),
+                            );
+                            nominators_reward = nominators_reward.saturating_add(nominator_reward);
                         },
                         None => {
                             // If the nominator reward is zero, then we don't need to do anything
@@ -500,6 +1012,12 @@ pub mod pallet {
                         },
                     }
                 }
+
+                Self::deposit_event(Event::AttesterRewardSplit(
+                    attester,
+                    commission_reward,
+                    nominators_reward,
+                ));
             }
 
             BalanceOf::<T>::from(total_attesters).saturating_mul(reward_per_attester)
@@ -823,6 +1341,42 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config> PeriodicTasks<T> for Pallet<T> {
+        fn periodic_tasks() -> Vec<PeriodicTask<T>> {
+            vec![
+                PeriodicTask {
+                    name: b"rewards::accumulated_settlements",
+                    cadence: Cadence::Hours(1),
+                    share: Percent::from_percent(5),
+                    priority: 11,
+                    run: |_, _| Self::process_accumulated_settlements(),
+                },
+                PeriodicTask {
+                    name: b"rewards::distribute_inflation",
+                    cadence: Cadence::Days(14),
+                    share: Percent::from_percent(5),
+                    priority: 13,
+                    run: |_, _| Self::distribute_inflation(),
+                },
+                // Must follow the inflation distribution, which pays out the authors of the period
+                PeriodicTask {
+                    name: b"rewards::authors_this_period",
+                    cadence: Cadence::Days(14),
+                    share: Percent::from_percent(5),
+                    priority: 14,
+                    run: |_, _| Self::process_authors_this_period(),
+                },
+                PeriodicTask {
+                    name: b"rewards::process_author",
+                    cadence: Cadence::EveryBlock,
+                    share: Percent::from_percent(5),
+                    priority: 40,
+                    run: |_, _| Self::process_author().1,
+                },
+            ]
+        }
+    }
+
     impl<T: Config> RewardsWriteApi<T::AccountId, BalanceOf<T>, BlockNumberFor<T>> for Pallet<T> {
         /// This function is called by the attesters pallet to repatriate the executor of honest SFX
         /// for attesters not signing on the attestation within the acceptable time limit.
@@ -916,6 +1470,21 @@ pub mod pallet {
             }
             false
         }
+
+        /// Royalties are withheld from the SFX rewards when accrued and minted back on claim,
+        /// in the Xtx reward asset.
+        fn accrue_royalty(payee: &T::AccountId, amount: BalanceOf<T>, asset_id: Option<u32>) {
+            if amount.is_zero() {
+                return
+            }
+            Self::add_to_pending_claims(
+                payee,
+                CircuitRole::ContractAuthor,
+                amount,
+                BenefitSource::TrafficRewards,
+                asset_id,
+            );
+        }
     }
 
     #[pallet::hooks]
@@ -925,6 +1494,47 @@ pub mod pallet {
         fn on_initialize(_n: frame_system::pallet_prelude::BlockNumberFor<T>) -> Weight {
             Self::process_update_estimated_treasury_balance()
         }
+
+        fn on_runtime_upgrade() -> Weight {
+            // Define the maximum weight of this migration.
+            let max_weight = T::DbWeight::get().reads_writes(10, 10);
+            // Define the current storage migration version.
+            const CURRENT_STORAGE_VERSION: u32 = 1;
+            // Migrate the storage entries.
+            StorageMigrations::<T>::try_mutate(|current_version| {
+                match *current_version {
+                    0 => {
+                        // Storage Migration: DistributionRecord records the inflation allocation and curve
+                        // Storage Migration Details: past distributions used the inflation constants
+                        let allocation = Self::current_inflation_allocation_base();
+                        let _ = DistributionHistory::<T>::translate(
+                            |maybe_history: Option<
+                                Vec<DistributionRecordV1<BlockNumberFor<T>, BalanceOf<T>>>,
+                            >| {
+                                maybe_history.map(|history| {
+                                    history
+                                        .into_iter()
+                                        .map(|record| record.into_record(allocation.clone()))
+                                        .collect()
+                                })
+                            },
+                        );
+
+                        // Set migrations_done to true
+                        *current_version = CURRENT_STORAGE_VERSION;
+
+                        // Return the weight consumed by the migration.
+                        Ok::<Weight, DispatchError>(max_weight)
+                    },
+                    // Add more migration cases here, if needed in the future
+                    _ => {
+                        // No migration needed.
+                        Ok::<Weight, DispatchError>(Weight::zero())
+                    },
+                }
+            })
+            .unwrap_or(Weight::zero())
+        }
     }
 
     // The genesis config type.
@@ -944,6 +1554,7 @@ pub mod pallet {
             IsSettlementAccumulationHalted::<T>::put(false);
             RepatriationPercentage::<T>::put(T::StartingRepatriationPercentage::get());
             MaxRewardExecutorsKickback::<T>::put(Percent::from_percent(0));
+            StorageMigrations::<T>::put(1);
         }
     }
 }
@@ -953,21 +1564,25 @@ pub mod test {
     use frame_support::{
         assert_err, assert_ok,
         traits::{Currency, Hooks, Len},
+        BoundedVec,
     };
     use sp_core::H256;
 
-    use sp_runtime::Percent;
+    use sp_runtime::{DispatchError, Perbill, Percent};
     use t3rn_mini_mock_runtime::{
-        AccountId, Authors, AuthorsThisPeriod, Balance, Balances, Clock, ConfigRewards,
-        DistributionHistory, ExtBuilder, MiniRuntime, PendingClaims, Rewards, RewardsError,
-        RuntimeOrigin, SettlementsPerRound, System,
+        AccountId, AccruedNominatorRewards, AttestersStore, Authors, AuthorsThisPeriod, Balance,
+        Balances, ClaimMode, Clock, ConfigRewards, DistributionHistory, ExtBuilder, MiniRuntime,
+        Nominations, PendingClaims, PendingVestingBonuses, Rewards, RewardsError, RewardsEvent,
+        RuntimeEvent, RuntimeOrigin, SettlementsPerRound, System, VestingSchedules,
     };
 
     use t3rn_primitives::{
         account_manager::{Outcome, Settlement},
+        attesters::AttesterInfo,
         circuit::{Cause, CircuitStatus, FullSideEffect, SecurityLvl, SideEffect},
         claimable::{BenefitSource, CircuitRole, ClaimableArtifacts},
         clock::Clock as ClockApi,
+        monetary::{InflationAllocation, InflationCurve},
         rewards::RewardsWriteApi,
         TreasuryAccount, TreasuryAccountProvider,
     };
@@ -1714,4 +2329,615 @@ pub mod test {
             assert_eq!(Rewards::get_pending_claims(executor), None);
         });
     }
+
+    #[test]
+    fn test_claim_in_vesting_mode_locks_claim_with_bonus_until_vested() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let beneficiary = AccountId::from([99u8; 32]);
+            PendingClaims::<MiniRuntime>::insert(
+                beneficiary.clone(),
+                vec![ClaimableArtifacts {
+                    beneficiary: beneficiary.clone(),
+                    role: CircuitRole::Executor,
+                    total_round_claim: 1000 as Balance,
+                    benefit_source: BenefitSource::Inflation,
+                    non_native_asset_id: None,
+                }],
+            );
+            let vesting_period = <MiniRuntime as ConfigRewards>::ClaimVestingPeriod::get();
+            let starting_block = System::block_number();
+
+            assert_ok!(Rewards::claim_with_mode(
+                RuntimeOrigin::signed(beneficiary.clone()),
+                None,
+                ClaimMode::Vesting,
+            ));
+
+            // 5% vesting bonus is paid on top of the claim and locked along with it
+            assert_eq!(Balances::free_balance(&beneficiary), 1050 as Balance);
+            assert_eq!(Balances::locks(&beneficiary)[0].amount, 1050 as Balance);
+            assert_eq!(VestingSchedules::<MiniRuntime>::get(&beneficiary).len(), 1);
+
+            System::set_block_number(starting_block + vesting_period / 2);
+            assert_ok!(Rewards::vest(RuntimeOrigin::signed(beneficiary.clone())));
+            assert_eq!(Balances::locks(&beneficiary)[0].amount, 525 as Balance);
+
+            System::set_block_number(starting_block + vesting_period);
+            assert_ok!(Rewards::vest(RuntimeOrigin::signed(beneficiary.clone())));
+            assert!(Balances::locks(&beneficiary).is_empty());
+            assert!(VestingSchedules::<MiniRuntime>::get(&beneficiary).is_empty());
+
+            assert_err!(
+                Rewards::vest(RuntimeOrigin::signed(beneficiary)),
+                RewardsError::<MiniRuntime>::NoVestingSchedules
+            );
+        });
+    }
+
+    #[test]
+    fn test_accrued_nominator_rewards_are_reset_on_staker_claim() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let nominator = AccountId::from([99u8; 32]);
+            let attester = AccountId::from([1u8; 32]);
+            let other_attester = AccountId::from([2u8; 32]);
+
+            AccruedNominatorRewards::<MiniRuntime>::insert(&nominator, &attester, 300 as Balance);
+            AccruedNominatorRewards::<MiniRuntime>::insert(
+                &nominator,
+                &other_attester,
+                200 as Balance,
+            );
+            PendingClaims::<MiniRuntime>::insert(
+                nominator.clone(),
+                vec![ClaimableArtifacts {
+                    beneficiary: nominator.clone(),
+                    role: CircuitRole::Staker,
+                    total_round_claim: 500 as Balance,
+                    benefit_source: BenefitSource::Inflation,
+                    non_native_asset_id: None,
+                }],
+            );
+
+            let mut accrued = Rewards::nominator_accrued_rewards(&nominator);
+            accrued.sort();
+            assert_eq!(
+                accrued,
+                vec![(attester, 300 as Balance), (other_attester, 200 as Balance)]
+            );
+
+            // Claims of other roles keep the accrued nominator rewards
+            assert_ok!(Rewards::claim(
+                RuntimeOrigin::signed(nominator.clone()),
+                Some(CircuitRole::Executor)
+            ));
+            assert_eq!(Rewards::nominator_accrued_rewards(&nominator).len(), 2);
+
+            assert_ok!(Rewards::claim(
+                RuntimeOrigin::signed(nominator.clone()),
+                Some(CircuitRole::Staker)
+            ));
+            assert_eq!(Balances::free_balance(&nominator), 500 as Balance);
+            assert!(Rewards::nominator_accrued_rewards(&nominator).is_empty());
+        });
+    }
+
+    #[test]
+    fn test_default_claim_mode_auto_nominates_attester_rewards_only() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let beneficiary = AccountId::from([99u8; 32]);
+            let attester = AccountId::from([1u8; 32]);
+
+            assert_err!(
+                Rewards::set_default_claim_mode(
+                    RuntimeOrigin::signed(beneficiary.clone()),
+                    CircuitRole::Attester,
+                    ClaimMode::AutoNominate(attester.clone()),
+                ),
+                RewardsError::<MiniRuntime>::AttesterNotFound
+            );
+
+            AttestersStore::<MiniRuntime>::insert(
+                &attester,
+                AttesterInfo {
+                    key_ed: [1u8; 32],
+                    key_ec: [1u8; 33],
+                    key_sr: [1u8; 32],
+                    commission: Percent::from_percent(10),
+                    index: 0,
+                },
+            );
+            assert_ok!(Rewards::set_default_claim_mode(
+                RuntimeOrigin::signed(beneficiary.clone()),
+                CircuitRole::Attester,
+                ClaimMode::AutoNominate(attester.clone()),
+            ));
+
+            PendingClaims::<MiniRuntime>::insert(
+                beneficiary.clone(),
+                vec![
+                    ClaimableArtifacts {
+                        beneficiary: beneficiary.clone(),
+                        role: CircuitRole::Attester,
+                        total_round_claim: 100 as Balance,
+                        benefit_source: BenefitSource::Inflation,
+                        non_native_asset_id: None,
+                    },
+                    ClaimableArtifacts {
+                        beneficiary: beneficiary.clone(),
+                        role: CircuitRole::Executor,
+                        total_round_claim: 50 as Balance,
+                        benefit_source: BenefitSource::TrafficRewards,
+                        non_native_asset_id: None,
+                    },
+                ],
+            );
+
+            assert_ok!(Rewards::claim(
+                RuntimeOrigin::signed(beneficiary.clone()),
+                None
+            ));
+
+            assert_eq!(
+                Nominations::<MiniRuntime>::get(&attester, &beneficiary),
+                Some(100 as Balance)
+            );
+            assert_eq!(Balances::reserved_balance(&beneficiary), 100 as Balance);
+            // Executor rewards are still claimed instantly
+            assert_eq!(Balances::free_balance(&beneficiary), 50 as Balance);
+            assert_eq!(
+                Rewards::get_pending_claims(beneficiary.clone()),
+                Some(vec![])
+            );
+        });
+    }
+
+    #[test]
+    fn test_auto_nominate_claim_falls_back_to_payout_when_nomination_fails() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            let beneficiary = AccountId::from([99u8; 32]);
+            let attester = AccountId::from([1u8; 32]);
+
+            AttestersStore::<MiniRuntime>::insert(
+                &attester,
+                AttesterInfo {
+                    key_ed: [1u8; 32],
+                    key_ec: [1u8; 33],
+                    key_sr: [1u8; 32],
+                    commission: Percent::from_percent(10),
+                    index: 0,
+                },
+            );
+            assert_ok!(Rewards::set_default_claim_mode(
+                RuntimeOrigin::signed(beneficiary.clone()),
+                CircuitRole::Attester,
+                ClaimMode::AutoNominate(attester.clone()),
+            ));
+
+            // The attester deregisters before the rewards are claimed
+            AttestersStore::<MiniRuntime>::remove(&attester);
+            PendingClaims::<MiniRuntime>::insert(
+                beneficiary.clone(),
+                vec![ClaimableArtifacts {
+                    beneficiary: beneficiary.clone(),
+                    role: CircuitRole::Attester,
+                    total_round_claim: 100 as Balance,
+                    benefit_source: BenefitSource::Inflation,
+                    non_native_asset_id: None,
+                }],
+            );
+
+            assert_ok!(Rewards::claim(
+                RuntimeOrigin::signed(beneficiary.clone()),
+                None
+            ));
+
+            assert_eq!(
+                Nominations::<MiniRuntime>::get(&attester, &beneficiary),
+                None
+            );
+            assert_eq!(Balances::free_balance(&beneficiary), 100 as Balance);
+            assert_eq!(Balances::reserved_balance(&beneficiary), 0 as Balance);
+            assert_eq!(
+                Rewards::get_pending_claims(beneficiary.clone()),
+                Some(vec![])
+            );
+            assert!(System::events().iter().any(|record| record.event
+                == RuntimeEvent::Rewards(RewardsEvent::AutoRestakeFailed(
+                    beneficiary.clone(),
+                    100 as Balance
+                ))));
+        });
+    }
+
+    #[test]
+    fn test_auto_bond_is_allowed_for_executor_rewards_only() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            let executor = AccountId::from([99u8; 32]);
+
+            assert_err!(
+                Rewards::set_default_claim_mode(
+                    RuntimeOrigin::signed(executor.clone()),
+                    CircuitRole::Attester,
+                    ClaimMode::AutoBond,
+                ),
+                RewardsError::<MiniRuntime>::ClaimModeNotAllowedForRole
+            );
+            assert_ok!(Rewards::set_default_claim_mode(
+                RuntimeOrigin::signed(executor.clone()),
+                CircuitRole::Executor,
+                ClaimMode::AutoBond,
+            ));
+
+            PendingClaims::<MiniRuntime>::insert(
+                executor.clone(),
+                vec![ClaimableArtifacts {
+                    beneficiary: executor.clone(),
+                    role: CircuitRole::Executor,
+                    total_round_claim: 100 as Balance,
+                    benefit_source: BenefitSource::TrafficRewards,
+                    non_native_asset_id: None,
+                }],
+            );
+
+            assert_ok!(Rewards::claim(
+                RuntimeOrigin::signed(executor.clone()),
+                Some(CircuitRole::Executor)
+            ));
+
+            // The mock executors staking bonds nothing, so the claim stays paid out
+            assert_eq!(Balances::free_balance(&executor), 100 as Balance);
+            assert!(System::events().iter().any(|record| record.event
+                == RuntimeEvent::Rewards(RewardsEvent::AutoRestakeFailed(
+                    executor.clone(),
+                    100 as Balance
+                ))));
+        });
+    }
+
+    #[test]
+    fn test_vesting_bonus_is_deducted_from_next_distribution_budget() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let beneficiary = AccountId::from([99u8; 32]);
+            PendingClaims::<MiniRuntime>::insert(
+                beneficiary.clone(),
+                vec![ClaimableArtifacts {
+                    beneficiary: beneficiary.clone(),
+                    role: CircuitRole::Executor,
+                    total_round_claim: 1000 as Balance,
+                    benefit_source: BenefitSource::Inflation,
+                    non_native_asset_id: None,
+                }],
+            );
+
+            assert_ok!(Rewards::claim_with_mode(
+                RuntimeOrigin::signed(beneficiary),
+                None,
+                ClaimMode::Vesting,
+            ));
+            assert_eq!(PendingVestingBonuses::<MiniRuntime>::get(), 50 as Balance);
+
+            Rewards::distribute_inflation();
+
+            let record = DistributionHistory::<MiniRuntime>::get()
+                .last()
+                .cloned()
+                .unwrap();
+            assert_eq!(PendingVestingBonuses::<MiniRuntime>::get(), 0 as Balance);
+            assert_eq!(
+                record.treasury_rewards,
+                record
+                    .allocation
+                    .share_of_total(record.allocation.treasury)
+                    .mul_floor(record.available - 50 as Balance)
+            );
+        });
+    }
+
+    #[test]
+    fn test_vesting_claim_fails_once_max_vesting_schedules_are_locked() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let beneficiary = AccountId::from([99u8; 32]);
+            let max_schedules = <MiniRuntime as ConfigRewards>::MaxVestingSchedules::get();
+            let vesting_period = <MiniRuntime as ConfigRewards>::ClaimVestingPeriod::get();
+            let starting_block = System::block_number();
+
+            VestingSchedules::<MiniRuntime>::insert(
+                &beneficiary,
+                BoundedVec::truncate_from(vec![
+                    crate::VestingSchedule {
+                        locked: 100 as Balance,
+                        starting_block,
+                        ending_block: starting_block + vesting_period,
+                    };
+                    max_schedules as usize
+                ]),
+            );
+            PendingClaims::<MiniRuntime>::insert(
+                beneficiary.clone(),
+                vec![ClaimableArtifacts {
+                    beneficiary: beneficiary.clone(),
+                    role: CircuitRole::Executor,
+                    total_round_claim: 1000 as Balance,
+                    benefit_source: BenefitSource::Inflation,
+                    non_native_asset_id: None,
+                }],
+            );
+
+            assert_err!(
+                Rewards::claim_with_mode(
+                    RuntimeOrigin::signed(beneficiary.clone()),
+                    None,
+                    ClaimMode::Vesting,
+                ),
+                RewardsError::<MiniRuntime>::TooManyVestingSchedules
+            );
+
+            // Fully vested schedules make room for the new ones
+            System::set_block_number(starting_block + vesting_period);
+            assert_ok!(Rewards::claim_with_mode(
+                RuntimeOrigin::signed(beneficiary.clone()),
+                None,
+                ClaimMode::Vesting,
+            ));
+            assert_eq!(VestingSchedules::<MiniRuntime>::get(&beneficiary).len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_claim_pays_out_at_most_max_claims_per_call() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let beneficiary = AccountId::from([99u8; 32]);
+            let max_claims = <MiniRuntime as ConfigRewards>::MaxClaimsPerCall::get();
+            let claim = ClaimableArtifacts {
+                beneficiary: beneficiary.clone(),
+                role: CircuitRole::Executor,
+                total_round_claim: 10 as Balance,
+                benefit_source: BenefitSource::TrafficRewards,
+                non_native_asset_id: None,
+            };
+            PendingClaims::<MiniRuntime>::insert(
+                beneficiary.clone(),
+                vec![claim; max_claims as usize + 1],
+            );
+
+            assert_ok!(Rewards::claim(
+                RuntimeOrigin::signed(beneficiary.clone()),
+                None
+            ));
+            assert_eq!(
+                Balances::free_balance(&beneficiary),
+                10 * max_claims as Balance
+            );
+            assert_eq!(
+                Rewards::get_pending_claims(beneficiary.clone()).map(|claims| claims.len()),
+                Some(1)
+            );
+
+            assert_ok!(Rewards::claim(
+                RuntimeOrigin::signed(beneficiary.clone()),
+                None
+            ));
+            assert_eq!(
+                Balances::free_balance(&beneficiary),
+                10 * (max_claims + 1) as Balance
+            );
+        });
+    }
+
+    #[test]
+    fn test_governance_sets_valid_inflation_allocation_used_by_distribution() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let mut allocation = InflationAllocation {
+                total: Perbill::from_parts(44_000_000),
+                attester: Perbill::from_parts(31_000_000),
+                executor: Perbill::from_parts(8_000_000),
+                collator: Perbill::from_parts(5_000_000),
+                treasury: Perbill::from_parts(1_000_000),
+            };
+
+            assert_err!(
+                Rewards::set_inflation_allocation(RuntimeOrigin::root(), allocation.clone()),
+                RewardsError::<MiniRuntime>::InvalidInflationAllocation
+            );
+
+            allocation.treasury = Perbill::zero();
+            assert_err!(
+                Rewards::set_inflation_allocation(
+                    RuntimeOrigin::signed(AccountId::from([1u8; 32])),
+                    allocation.clone()
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Rewards::set_inflation_allocation(
+                RuntimeOrigin::root(),
+                allocation.clone()
+            ));
+
+            let distribution_period =
+                <MiniRuntime as ConfigRewards>::InflationDistributionPeriod::get();
+            System::set_block_number(distribution_period);
+            Clock::on_initialize(distribution_period);
+
+            let history = DistributionHistory::<MiniRuntime>::get();
+            let last_record = history.last().unwrap();
+            assert_eq!(last_record.allocation, allocation);
+            assert_eq!(last_record.curve, None);
+            assert_eq!(last_record.treasury_rewards, 0 as Balance);
+        });
+    }
+
+    #[test]
+    fn test_inflation_decays_year_over_year_and_is_recorded_in_history() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let one_year = <MiniRuntime as ConfigRewards>::OneYear::get();
+
+            assert_err!(
+                Rewards::set_inflation_curve(
+                    RuntimeOrigin::root(),
+                    Some(InflationCurve {
+                        starting_block: 0,
+                        annual_decay: Perbill::from_percent(10),
+                        floor: Perbill::from_percent(5),
+                    })
+                ),
+                RewardsError::<MiniRuntime>::InvalidInflationCurve
+            );
+
+            let curve = InflationCurve {
+                starting_block: 0,
+                annual_decay: Perbill::from_percent(10),
+                floor: Perbill::from_percent(1),
+            };
+            assert_ok!(Rewards::set_inflation_curve(
+                RuntimeOrigin::root(),
+                Some(curve.clone())
+            ));
+
+            // No decay within the first year
+            assert_eq!(
+                Rewards::current_inflation_allocation(),
+                Rewards::current_inflation_allocation_base()
+            );
+
+            System::set_block_number(one_year);
+            Rewards::distribute_inflation();
+
+            let history = DistributionHistory::<MiniRuntime>::get();
+            let last_record = history.last().unwrap();
+            assert_eq!(last_record.curve, Some(curve));
+            assert_eq!(
+                last_record.allocation.total,
+                Perbill::from_parts(39_600_000)
+            );
+            assert_eq!(
+                last_record.allocation.attester,
+                Perbill::from_parts(9_900_000)
+            );
+            assert_eq!(
+                last_record.allocation,
+                Rewards::current_inflation_allocation()
+            );
+        });
+    }
+
+    #[test]
+    fn test_total_emission_follows_the_decay_curve_over_multiple_years() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let one_year = <MiniRuntime as ConfigRewards>::OneYear::get();
+            let distribution_period =
+                <MiniRuntime as ConfigRewards>::InflationDistributionPeriod::get();
+            let treasury_account =
+                <MiniRuntime as TreasuryAccountProvider<AccountId>>::get_treasury_account(
+                    TreasuryAccount::Parachain,
+                );
+            let base = Rewards::current_inflation_allocation_base();
+            let curve = InflationCurve {
+                starting_block: 0,
+                annual_decay: Perbill::from_percent(10),
+                floor: Perbill::from_percent(1),
+            };
+            assert_ok!(Rewards::set_inflation_curve(
+                RuntimeOrigin::root(),
+                Some(curve.clone())
+            ));
+
+            let mut yearly_available: Vec<Balance> = vec![];
+            let mut yearly_treasury_rewards: Vec<Balance> = vec![];
+            for year in 0..3u32 {
+                let mut available = 0 as Balance;
+                let mut treasury_rewards = 0 as Balance;
+                for period in 0..one_year / distribution_period {
+                    System::set_block_number(year * one_year + period * distribution_period);
+                    let total_issuance = Balances::total_issuance();
+                    let treasury_balance = Balances::total_balance(&treasury_account);
+
+                    Rewards::distribute_inflation();
+
+                    let record = DistributionHistory::<MiniRuntime>::get()
+                        .last()
+                        .cloned()
+                        .unwrap();
+                    let decayed = base.decayed(&curve, year);
+                    // The budget follows the decayed total inflation
+                    assert_eq!(record.allocation, decayed);
+                    assert_eq!(
+                        record.available,
+                        Perbill::from_rational(distribution_period, one_year)
+                            .mul_ceil(decayed.total.mul_ceil(total_issuance))
+                    );
+                    // Roles split the decayed budget by their shares, without decaying it again
+                    assert_eq!(
+                        record.treasury_rewards,
+                        decayed
+                            .share_of_total(decayed.treasury)
+                            .mul_floor(record.available)
+                    );
+                    assert_eq!(
+                        Balances::total_balance(&treasury_account) - treasury_balance,
+                        record.treasury_rewards
+                    );
+
+                    available += record.available;
+                    treasury_rewards += record.treasury_rewards;
+                }
+                yearly_available.push(available);
+                yearly_treasury_rewards.push(treasury_rewards);
+            }
+
+            // Year over year both decay by 10%, give or take the growth of the issuance they're based on
+            for yearly in [yearly_available, yearly_treasury_rewards] {
+                for years in yearly.windows(2) {
+                    let decay = Perbill::from_rational(years[1], years[0]);
+                    assert!(decay >= Perbill::from_percent(90));
+                    assert!(decay <= Perbill::from_percent(92));
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_royalties_accrue_as_contract_author_claims_per_asset() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let payee = AccountId::from([42u8; 32]);
+
+            Rewards::accrue_royalty(&payee, 10 as Balance, None);
+            Rewards::accrue_royalty(&payee, 5 as Balance, None);
+            Rewards::accrue_royalty(&payee, 7 as Balance, Some(1));
+            Rewards::accrue_royalty(&payee, 0 as Balance, Some(2));
+
+            assert_eq!(
+                Rewards::get_pending_claims(payee.clone()),
+                Some(vec![
+                    ClaimableArtifacts {
+                        beneficiary: payee.clone(),
+                        role: CircuitRole::ContractAuthor,
+                        total_round_claim: 15 as Balance,
+                        benefit_source: BenefitSource::TrafficRewards,
+                        non_native_asset_id: None,
+                    },
+                    ClaimableArtifacts {
+                        beneficiary: payee.clone(),
+                        role: CircuitRole::ContractAuthor,
+                        total_round_claim: 7 as Balance,
+                        benefit_source: BenefitSource::TrafficRewards,
+                        non_native_asset_id: Some(1),
+                    },
+                ])
+            );
+        });
+    }
 }
@@ -0,0 +1,94 @@
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_rewards.
+pub trait WeightInfo {
+    fn claim(n: u32) -> Weight;
+    fn set_default_claim_mode() -> Weight;
+    fn vest(s: u32) -> Weight;
+    fn set_inflation_allocation() -> Weight;
+    fn set_inflation_curve() -> Weight;
+}
+
+/// Weights for pallet_rewards using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Storage access follows the claim benchmark of n claims in vesting mode, plus per claim the
+    // restaking a claim in auto-nominate mode does on the attesters pallet. Execution times are
+    // estimates until the benchmarks are re-run on reference hardware.
+    fn claim(n: u32) -> Weight {
+        Weight::from_parts(95_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(28_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(8_u64))
+            .saturating_add(T::DbWeight::get().writes(7_u64))
+            .saturating_add(T::DbWeight::get().reads(5_u64).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().writes(4_u64).saturating_mul(n.into()))
+    }
+
+    // Includes the attester lookup of the auto-nominate mode.
+    fn set_default_claim_mode() -> Weight {
+        Weight::from_parts(22_000_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn vest(s: u32) -> Weight {
+        Weight::from_parts(34_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(1_200_000_u64, 0u64).saturating_mul(s.into()))
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    fn set_inflation_allocation() -> Weight {
+        Weight::from_parts(14_000_000_u64, 0u64).saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads the allocation base to check the floor of the curve against.
+    fn set_inflation_curve() -> Weight {
+        Weight::from_parts(16_000_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn claim(n: u32) -> Weight {
+        Weight::from_parts(95_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(28_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(8_u64))
+            .saturating_add(RocksDbWeight::get().writes(7_u64))
+            .saturating_add(RocksDbWeight::get().reads(5_u64).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().writes(4_u64).saturating_mul(n.into()))
+    }
+
+    fn set_default_claim_mode() -> Weight {
+        Weight::from_parts(22_000_000_u64, 0u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn vest(s: u32) -> Weight {
+        Weight::from_parts(34_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(1_200_000_u64, 0u64).saturating_mul(s.into()))
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn set_inflation_allocation() -> Weight {
+        Weight::from_parts(14_000_000_u64, 0u64).saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn set_inflation_curve() -> Weight {
+        Weight::from_parts(16_000_000_u64, 0u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+}
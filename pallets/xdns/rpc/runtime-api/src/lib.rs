@@ -9,11 +9,15 @@
 use codec::Codec;
 use sp_runtime::sp_std;
 use sp_std::prelude::*;
-use t3rn_primitives::xdns::{FullGatewayRecord, GatewayRecord};
+use t3rn_primitives::xdns::{FullGatewayRecord, GatewayRecord, XDNSTopologyDiff};
 pub use t3rn_primitives::{gateway::GatewayABIConfig, ChainId};
 
 sp_api::decl_runtime_apis! {
     /// The API to interact with pallet XDNS
+    ///
+    /// Version history:
+    /// - 2: added `zip_topology` and `diff_topology`
+    #[api_version(2)]
     pub trait XdnsRuntimeApi<AccountId> where
         AccountId: Codec,
     {
@@ -26,5 +30,14 @@ sp_api::decl_runtime_apis! {
         fn fetch_abi(chain_id: ChainId) -> Option<GatewayABIConfig>;
 
         fn retreive_treasury_address(treasury_account: t3rn_primitives::TreasuryAccount) -> AccountId;
+
+        /// Returns the SCALE-encoded, versioned snapshot of the complete XDNS topology
+        #[api_version(2)]
+        fn zip_topology() -> Vec<u8>;
+
+        /// Previews the changes unzipping the SCALE-encoded topology would apply.
+        /// Returns None if the topology can't be decoded or its content hash doesn't match.
+        #[api_version(2)]
+        fn diff_topology(topology_encoded: Vec<u8>) -> Option<XDNSTopologyDiff>;
     }
 }
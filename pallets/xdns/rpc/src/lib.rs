@@ -8,15 +8,17 @@ use jsonrpsee::{
 };
 pub use pallet_xdns_rpc_runtime_api::XdnsRuntimeApi;
 use pallet_xdns_rpc_runtime_api::{ChainId, GatewayABIConfig};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::sp_std;
+use sp_core::{sp_std, Bytes};
 use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
 use sp_std::prelude::*;
 use std::sync::Arc;
-use t3rn_primitives::xdns::{FullGatewayRecord, GatewayRecord};
+use t3rn_primitives::xdns::{FullGatewayRecord, GatewayRecord, XDNSTopologyDiff};
 
 const RUNTIME_ERROR: i64 = 1;
+/// First version of [`XdnsRuntimeApi`] exposing the topology snapshot methods.
+const TOPOLOGY_API_VERSION: u32 = 2;
 
 #[rpc(client, server)]
 pub trait XdnsApi<AccountId> {
@@ -29,6 +31,14 @@ pub trait XdnsApi<AccountId> {
 
     #[method(name = "xdns_fetchFullRecords")]
     fn fetch_full_gateway_records(&self) -> RpcResult<Vec<FullGatewayRecord<AccountId>>>;
+
+    /// Returns the SCALE-encoded, versioned XDNS topology snapshot
+    #[method(name = "xdns_zipTopology")]
+    fn zip_topology(&self) -> RpcResult<Bytes>;
+
+    /// Previews the changes unzipping the given SCALE-encoded topology would apply
+    #[method(name = "xdns_diffTopology")]
+    fn diff_topology(&self, topology_encoded: Bytes) -> RpcResult<XDNSTopologyDiff>;
 }
 
 /// A struct that implements the [`XdnsApiServer`].
@@ -75,6 +85,32 @@ where
         Ok(result)
     }
 
+    fn zip_topology(&self) -> RpcResult<Bytes> {
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+        ensure_topology_api::<_, Block, AccountId>(&*api, at)?;
+
+        let result = api.zip_topology(at).map_err(runtime_error_into_rpc_err)?;
+
+        Ok(result.into())
+    }
+
+    fn diff_topology(&self, topology_encoded: Bytes) -> RpcResult<XDNSTopologyDiff> {
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+        ensure_topology_api::<_, Block, AccountId>(&*api, at)?;
+
+        let result: Option<XDNSTopologyDiff> = api
+            .diff_topology(at, topology_encoded.to_vec())
+            .map_err(runtime_error_into_rpc_err)?;
+
+        match result {
+            Some(diff) => Ok(diff),
+            None => Err("Invalid topology: failed to decode or content hash mismatch"),
+        }
+        .map_err(runtime_error_into_rpc_err)
+    }
+
     fn fetch_abi(&self, chain_id: ChainId) -> RpcResult<GatewayABIConfig> {
         let api = self.client.runtime_api();
         let at = self.client.info().best_hash;
@@ -91,6 +127,28 @@ where
     }
 }
 
+/// Runtimes implementing an older version of the API don't know the topology methods.
+fn ensure_topology_api<Api, Block, AccountId>(api: &Api, at: Block::Hash) -> RpcResult<()>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Api: ApiExt<Block> + XdnsRuntimeApi<Block, AccountId>,
+{
+    let has_topology_api = api
+        .has_api_with::<dyn XdnsRuntimeApi<Block, AccountId>, _>(at, |version| {
+            version >= TOPOLOGY_API_VERSION
+        })
+        .map_err(runtime_error_into_rpc_err)?;
+
+    if has_topology_api {
+        Ok(())
+    } else {
+        Err(runtime_error_into_rpc_err(
+            "Runtime doesn't support XDNS topology snapshots",
+        ))
+    }
+}
+
 fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
     JsonRpseeError::Call(CallError::Custom(jsonrpsee::types::ErrorObject::owned(
         RUNTIME_ERROR as i32,
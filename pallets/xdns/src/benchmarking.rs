@@ -1,224 +1,185 @@
-// //! Benchmarking setup for pallet-xdns
-//
-// use super::*;
-// use crate::Pallet as XDNS;
-// use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
-// use frame_system::RawOrigin;
-// use sp_std::prelude::*;
-// use t3rn_primitives::{xdns::Xdns, TokenSysProps};
-//
-// use sp_runtime::create_runtime_str;
-// use sp_version::RuntimeVersion;
-//
-// const USER_SEED: u32 = 999666;
-// pub const TEST_RUNTIME_VERSION: RuntimeVersion = RuntimeVersion {
-//     spec_name: create_runtime_str!("test-runtime"),
-//     impl_name: create_runtime_str!("test-runtime"),
-//     authoring_version: 1,
-//     spec_version: 1,
-//     impl_version: 1,
-//     apis: sp_version::create_apis_vec!([]),
-//     transaction_version: 1,
-//     state_version: 0,
-// };
-//
-// benchmarks! {
-//     add_new_xdns_record {
-//         let url = b"some_url".to_vec();
-//         let gateway_id = b"test";
-//         let gateway_abi: GatewayABIConfig = Default::default();
-//
-//         let gateway_vendor = GatewayVendor::Rococo;
-//         let gateway_type = GatewayType::TxOnly(0);
-//
-//         let gateway_genesis = GatewayGenesisConfig {
-//             modules_encoded: None,
-//             extrinsics_version: 0u8,
-//             genesis_hash: Default::default(),
-//         };
-//
-//         let gateway_sys_props = TokenSysProps {
-//             ss58_format: 0,
-//             token_symbol: Encode::encode(""),
-//             token_decimals: 0,
-//         };
-//     }: _(RawOrigin::Root, url, *gateway_id, gateway_abi, gateway_vendor, gateway_type, gateway_genesis, gateway_sys_props, vec![])
-//     verify {
-//         assert!(
-//             XDNSRegistry::<T>::get(T::Hashing::hash(b"test"))
-//                 .is_some()
-//         );
-//     }
-//
-//     update_ttl {
-//         let url = b"some_url".to_vec();
-//
-//         let gateway_id = b"gate";
-//         let gateway_abi: GatewayABIConfig = Default::default();
-//         let gateway_vendor = GatewayVendor::Rococo;
-//         let gateway_type = GatewayType::TxOnly(0);
-//
-//         let gateway_genesis = GatewayGenesisConfig {
-//             modules_encoded: None,
-//             extrinsics_version: 0u8,
-//             genesis_hash: Default::default(),
-//         };
-//
-//         let gateway_sys_props = TokenSysProps {
-//             ss58_format: 0,
-//             token_symbol: Encode::encode(""),
-//             token_decimals: 0,
-//         };
-//
-//         XDNS::<T>::add_new_xdns_record(
-//             RawOrigin::Root.into(),
-//             url,
-//             *gateway_id,
-//             gateway_abi,
-//             gateway_vendor,
-//             gateway_type,
-//             gateway_genesis,
-//             gateway_sys_props,
-//             vec![],
-//         )
-//         .unwrap();
-//
-//         let gateway_hash = T::Hashing::hash(b"gate");
-//
-//     }: _(RawOrigin::Root, *b"gate", 2)
-//     verify {
-//         assert_eq!(
-//             XDNSRegistry::<T>::get(gateway_hash)
-//                 .unwrap()
-//                 .last_finalized,
-//             Some(2)
-//         );
-//     }
-//
-//     purge_xdns_record {
-//         let requester: T::AccountId = account("TEST", 1u32, USER_SEED);
-//         let url = b"some_url".to_vec();
-//
-//         let gateway_id = b"gate";
-//         let gateway_abi: GatewayABIConfig = Default::default();
-//         let gateway_vendor = GatewayVendor::Rococo;
-//         let gateway_type = GatewayType::TxOnly(0);
-//
-//         let gateway_genesis = GatewayGenesisConfig {
-//             modules_encoded: None,
-//             extrinsics_version: 0u8,
-//             genesis_hash: Default::default(),
-//         };
-//
-//         let gateway_sys_props = TokenSysProps {
-//             ss58_format: 0,
-//             token_symbol: Encode::encode(""),
-//             token_decimals: 0,
-//         };
-//
-//         XDNS::<T>::add_new_xdns_record(
-//             RawOrigin::Root.into(),
-//             url,
-//             *gateway_id,
-//             gateway_abi,
-//             gateway_vendor,
-//             gateway_type,
-//             gateway_genesis,
-//             gateway_sys_props,
-//             vec![],
-//         )
-//         .unwrap();
-//
-//         let gateway_hash = T::Hashing::hash(b"gate");
-//     }: _(RawOrigin::Root, requester, gateway_hash.clone())
-//     verify{
-//         assert!(
-//             XDNSRegistry::<T>::get(gateway_hash)
-//                 .is_none()
-//         );
-//     }
-//
-//     best_available {
-//         let url = b"some_url".to_vec();
-//
-//         let gateway_id = b"gate";
-//         let gateway_abi: GatewayABIConfig = Default::default();
-//         let gateway_vendor = GatewayVendor::Rococo;
-//         let gateway_type = GatewayType::TxOnly(0);
-//
-//         let gateway_genesis = GatewayGenesisConfig {
-//             modules_encoded: None,
-//             extrinsics_version: 0u8,
-//             genesis_hash: Default::default(),
-//         };
-//
-//         let gateway_sys_props = TokenSysProps {
-//             ss58_format: 0,
-//             token_symbol: Encode::encode(""),
-//             token_decimals: 0,
-//         };
-//
-//         XDNS::<T>::add_new_xdns_record(
-//             RawOrigin::Root.into(),
-//             url,
-//             *gateway_id,
-//             gateway_abi,
-//             gateway_vendor,
-//             gateway_type,
-//             gateway_genesis,
-//             gateway_sys_props,
-//             vec![],
-//         )
-//         .unwrap();
-//     }: {XDNS::<T>::best_available(*b"gate")}
-//     verify{}
-// }
-//
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use crate::mock::Test;
-//     use frame_support::assert_ok;
-//     use sp_io::TestExternalities;
-//
-//     pub fn new_test_ext() -> TestExternalities {
-//         let t = frame_system::GenesisConfig::default()
-//             .build_storage::<Test>()
-//             .unwrap();
-//         TestExternalities::new(t)
-//     }
-//
-//     #[test]
-//     fn benchmark_add_new_xdns_record() {
-//         new_test_ext().execute_with(|| {
-//             assert_ok!(test_benchmark_add_new_xdns_record::<Test>());
-//         });
-//     }
-//
-//     #[test]
-//     fn benchmark_update_ttl() {
-//         new_test_ext().execute_with(|| {
-//             assert_ok!(test_benchmark_update_ttl::<Test>());
-//         });
-//     }
-//
-//     #[test]
-//     fn benchmark_purge_xdns_record() {
-//         new_test_ext().execute_with(|| {
-//             assert_ok!(test_benchmark_purge_xdns_record::<Test>());
-//         });
-//     }
-//
-//     #[test]
-//     fn benchmark_best_available() {
-//         new_test_ext().execute_with(|| {
-//             assert_ok!(test_benchmark_best_available::<Test>());
-//         });
-//     }
-// }
-//
-// impl_benchmark_test_suite!(
-//     XDNS,
-//     crate::benchmarking::tests::new_test_ext(),
-//     crate::mock::Test
-// );
+//! Benchmarking setup for pallet-xdns
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use circuit_runtime_types::AssetId;
+use frame_benchmarking::benchmarks;
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_core::H256;
+use sp_std::prelude::*;
+use t3rn_abi::{standard::standard_sfx_abi, Codec};
+use t3rn_primitives::{
+    xdns::{
+        EpochEstimate, FullGatewayRecord, GatewayRecord, TokenRecord, TopologySections,
+        XDNSTopologySnapshot,
+    },
+    ExecutionVendor, GatewayVendor, SubstrateToken, TokenInfo,
+};
+use t3rn_types::{fsx::TargetId, sfx::Sfx4bId};
+
+const MAX_TOPOLOGY_ENTRIES: u32 = 100;
+const FIRST_BENCHMARKED_ASSET: AssetId = 10_000;
+
+fn indexed_id(prefix: u8, i: u32) -> [u8; 4] {
+    let index = i.to_le_bytes();
+    [prefix, index[0], index[1], index[2]]
+}
+
+fn token_props() -> TokenInfo {
+    TokenInfo::Substrate(SubstrateToken {
+        id: 1,
+        symbol: b"BNCH".to_vec(),
+        decimals: 12,
+    })
+}
+
+fn gateway_record<T: Config>(gateway_id: TargetId) -> FullGatewayRecord<T::AccountId> {
+    FullGatewayRecord {
+        gateway_record: GatewayRecord {
+            gateway_id,
+            verification_vendor: GatewayVendor::Rococo,
+            execution_vendor: ExecutionVendor::Substrate,
+            codec: Codec::Scale,
+            registrant: None,
+            escrow_account: None,
+            allowed_side_effects: standard_sfx_abi()
+                .into_iter()
+                .map(|(sfx_4b_id, _)| (sfx_4b_id, Some(2)))
+                .collect(),
+        },
+        tokens: vec![],
+    }
+}
+
+fn populate_standard_sfx_abis<T: Config>() {
+    for (sfx_4b_id, abi) in standard_sfx_abi() {
+        <StandardSFXABIs<T>>::insert(sfx_4b_id, abi);
+    }
+}
+
+/// Local state the section is compared against at unzip: l entries unrelated to the snapshot.
+fn populate_local_gateways<T: Config>(l: u32) {
+    for i in 0..l {
+        let record = gateway_record::<T>(indexed_id(b'l', i)).gateway_record;
+        <Gateways<T>>::insert(record.gateway_id, record);
+    }
+}
+
+fn topology<T: Config>(
+    gateways: Vec<FullGatewayRecord<T::AccountId>>,
+    assets: Vec<TokenRecord>,
+    standard_sfx_abis: Vec<(Sfx4bId, t3rn_abi::sfx_abi::SFXAbi)>,
+    remote_order_addresses: Vec<(TargetId, H256)>,
+    epoch_history: Vec<(GatewayVendor, Vec<EpochEstimate<BlockNumberFor<T>>>)>,
+) -> XDNSTopologySnapshotOf<T> {
+    XDNSTopologySnapshot::new(
+        gateways,
+        assets,
+        standard_sfx_abis,
+        vec![],
+        remote_order_addresses,
+        vec![],
+        vec![],
+        epoch_history,
+    )
+}
+
+fn only(select: impl FnOnce(&mut TopologySections)) -> Option<TopologySections> {
+    let mut sections = TopologySections::none();
+    select(&mut sections);
+    Some(sections)
+}
+
+benchmarks! {
+    unzip_topology_gateways {
+        let n in 0 .. MAX_TOPOLOGY_ENTRIES;
+        let l in 0 .. MAX_TOPOLOGY_ENTRIES;
+        populate_standard_sfx_abis::<T>();
+        populate_local_gateways::<T>(l);
+        let gateways = (0..n).map(|i| gateway_record::<T>(indexed_id(b'g', i))).collect();
+        let topology = topology::<T>(gateways, vec![], vec![], vec![], vec![]);
+    }: unzip_topology(RawOrigin::Root, Some(topology), None, only(|s| s.gateways = true))
+    verify {
+        assert_eq!(<Gateways<T>>::iter().count() as u32, n + l);
+    }
+
+    unzip_topology_assets {
+        let n in 0 .. MAX_TOPOLOGY_ENTRIES;
+        let l in 0 .. MAX_TOPOLOGY_ENTRIES;
+        let self_gateway = T::SelfGatewayId::get();
+        <Gateways<T>>::insert(self_gateway, gateway_record::<T>(self_gateway).gateway_record);
+        for i in 0..l {
+            let local_asset = FIRST_BENCHMARKED_ASSET + MAX_TOPOLOGY_ENTRIES + i;
+            let local_gateway = indexed_id(b'l', i);
+            <Tokens<T>>::insert(local_asset, local_gateway, TokenRecord {
+                token_id: local_asset,
+                gateway_id: local_gateway,
+                token_props: token_props(),
+            });
+        }
+        // Every asset is new to the assets overlay, so each one is created and linked
+        let assets = (0..n)
+            .map(|i| TokenRecord {
+                token_id: FIRST_BENCHMARKED_ASSET + i,
+                gateway_id: self_gateway,
+                token_props: token_props(),
+            })
+            .collect();
+        let topology = topology::<T>(vec![], assets, vec![], vec![], vec![]);
+    }: unzip_topology(RawOrigin::Root, Some(topology), None, only(|s| s.assets = true))
+    verify {
+        assert_eq!(<GatewayTokens<T>>::get(self_gateway).len() as u32, n);
+    }
+
+    unzip_topology_sfx_abis {
+        let n in 0 .. MAX_TOPOLOGY_ENTRIES;
+        let l in 0 .. MAX_TOPOLOGY_ENTRIES;
+        let (_, abi) = standard_sfx_abi()[0].clone();
+        for i in 0..l {
+            <StandardSFXABIs<T>>::insert(indexed_id(b'l', i), abi.clone());
+        }
+        let abis = (0..n).map(|i| (indexed_id(b's', i), abi.clone())).collect();
+        let topology = topology::<T>(vec![], vec![], abis, vec![], vec![]);
+    }: unzip_topology(RawOrigin::Root, Some(topology), None, only(|s| s.standard_sfx_abis = true))
+    verify {
+        assert_eq!(<StandardSFXABIs<T>>::iter().count() as u32, n + l);
+    }
+
+    unzip_topology_addresses {
+        let n in 0 .. MAX_TOPOLOGY_ENTRIES;
+        let l in 0 .. MAX_TOPOLOGY_ENTRIES;
+        for i in 0..l {
+            <RemoteOrderAddresses<T>>::insert(indexed_id(b'l', i), H256::repeat_byte(1));
+        }
+        let addresses = (0..n).map(|i| (indexed_id(b'a', i), H256::repeat_byte(2))).collect();
+        let topology = topology::<T>(vec![], vec![], vec![], addresses, vec![]);
+    }: unzip_topology(RawOrigin::Root, Some(topology), None, only(|s| s.remote_order_addresses = true))
+    verify {
+        assert_eq!(<RemoteOrderAddresses<T>>::iter().count() as u32, n + l);
+    }
+
+    unzip_topology_epoch_history {
+        let n in 0 .. MAX_TOPOLOGY_ENTRIES;
+        let l in 0 .. MAX_TOPOLOGY_ENTRIES;
+        let estimate = EpochEstimate {
+            local: 1u32.into(),
+            remote: 2u32.into(),
+            moving_average_local: 1u32.into(),
+            moving_average_remote: 2u32.into(),
+        };
+        <EpochHistory<T>>::insert(GatewayVendor::Rococo, vec![estimate.clone(); l as usize]);
+        let history = vec![(GatewayVendor::Kusama, vec![estimate; n as usize])];
+        let topology = topology::<T>(vec![], vec![], vec![], vec![], history);
+    }: unzip_topology(RawOrigin::Root, Some(topology), None, only(|s| s.epoch_history = true))
+    verify {
+        assert_eq!(<EpochHistory<T>>::get(GatewayVendor::Kusama).map(|h| h.len() as u32), Some(n));
+    }
+
+    set_asset_fee_price {
+        <AllTokenIds<T>>::put(vec![FIRST_BENCHMARKED_ASSET]);
+        let price: BalanceOf<T> = 2u32.into();
+    }: _(RawOrigin::Root, FIRST_BENCHMARKED_ASSET, Some(price))
+    verify {
+        assert_eq!(<AssetFeePrices<T>>::get(FIRST_BENCHMARKED_ASSET), Some(price));
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{BalanceOf, Config, Pallet};
+use circuit_runtime_types::AssetId;
+use frame_support::traits::tokens::ConversionToAssetBalance;
+use sp_runtime::DispatchError;
+use sp_std::marker::PhantomData;
+use t3rn_primitives::xdns::Xdns;
+
+/// Conversion policy of pallet-asset-tx-payment, charging the transaction fees in any asset registered in XDNS.
+pub struct XdnsAssetFeeConversion<T>(PhantomData<T>);
+
+impl<T: Config> ConversionToAssetBalance<BalanceOf<T>, AssetId, BalanceOf<T>>
+    for XdnsAssetFeeConversion<T>
+{
+    type Error = DispatchError;
+
+    fn to_asset_balance(
+        balance: BalanceOf<T>,
+        asset_id: AssetId,
+    ) -> Result<BalanceOf<T>, Self::Error> {
+        <Pallet<T> as Xdns<T, BalanceOf<T>>>::convert_native_fee_to_asset(asset_id, balance)
+    }
+}
@@ -29,11 +29,16 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod fees;
 pub mod weights;
 
 use weights::WeightInfo;
 reexport_currency_types!();
 
+/// Share of the block weight the Circuit queues may consume when verifier overviews are processed
+/// outside of the clock, i.e. on new epochs reported by the light clients
+pub const OVERVIEW_QUEUES_WEIGHT_SHARE: sp_runtime::Percent = sp_runtime::Percent::from_percent(5);
+
 // Definition of the pallet logic, to be aggregated at runtime definition through
 // `construct_runtime`.
 #[frame_support::pallet]
@@ -46,24 +51,28 @@ pub mod pallet {
         pallet_prelude::*,
         traits::{
             fungible::{Inspect, Mutate},
-            Currency, Time,
+            BalanceStatus, Currency, ReservableCurrency, Time,
         },
     };
     use frame_system::pallet_prelude::*;
-    use sp_core::H256;
+    use sp_core::{H160, H256};
     use sp_runtime::{traits::CheckedDiv, SaturatedConversion};
     use sp_std::convert::TryInto;
     use t3rn_abi::{sfx_abi::SFXAbi, Codec};
     use t3rn_primitives::{
         attesters::AttestersReadApi,
         circuit::{AdaptiveTimeout, CircuitDLQ},
+        clock::{Cadence, PeriodicTask, PeriodicTasks},
         light_client::{LightClientAsyncAPI, LightClientHeartbeat},
         portal::Portal,
+        threevm::get_tokens_precompile_address,
         xdns::{
-            EpochEstimate, FullGatewayRecord, GatewayRecord, PalletAssetsOverlay, TokenRecord, Xdns,
+            EpochEstimate, FullGatewayRecord, GatewayChange, GatewayRecord, PalletAssetsOverlay,
+            SectionDiff, TokenRecord, TopologySections, XDNSTopologyDiff, XDNSTopologySnapshot,
+            Xdns, ASSET_FEE_PRICE_UNIT, XDNS_TOPOLOGY_VERSION,
         },
         Bytes, ChainId, ExecutionVendor, FinalityVerifierActivity, GatewayActivity, GatewayVendor,
-        SpeedMode, TokenInfo, TreasuryAccount, TreasuryAccountProvider, XDNSTopology,
+        SpeedMode, TokenInfo, TreasuryAccount, TreasuryAccountProvider,
     };
     use t3rn_types::{fsx::TargetId, sfx::Sfx4bId};
 
@@ -71,6 +80,12 @@ pub mod pallet {
 
     pub const MAX_GATEWAY_OVERVIEW_RECORDS: u32 = 1000;
 
+    pub type XDNSTopologySnapshotOf<T> = XDNSTopologySnapshot<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        BalanceOf<T>,
+    >;
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The overarching event type.
@@ -82,7 +97,7 @@ pub mod pallet {
         /// A type that provides inspection and mutation to some fungible assets
         type Balances: Inspect<Self::AccountId> + Mutate<Self::AccountId>;
 
-        type Currency: Currency<Self::AccountId>;
+        type Currency: ReservableCurrency<Self::AccountId>;
 
         type AssetsOverlay: PalletAssetsOverlay<Self, BalanceOf<Self>>;
 
@@ -99,6 +114,22 @@ pub mod pallet {
         type SelfGatewayId: Get<ChainId>;
 
         type Time: Time;
+
+        /// Deposit reserved from permissionless gateway registrants, slashable by governance
+        #[pallet::constant]
+        type GatewayRegistrationDeposit: Get<BalanceOf<Self>>;
+
+        /// Delay of registrant changes to gateways that may affect active Xtx
+        #[pallet::constant]
+        type GatewayChangeTimelock: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of registrant changes to gateways scheduled at the same block
+        #[pallet::constant]
+        type MaxScheduledGatewayChanges: Get<u32>;
+
+        /// Maximum number of scheduled changes a single registrant may have pending
+        #[pallet::constant]
+        type MaxPendingGatewayChangesPerRegistrant: Get<u32>;
     }
 
     // Simple declaration of the `Pallet` type. It is placeholder we use to implement traits and
@@ -114,10 +145,9 @@ pub mod pallet {
         // dispatched.
         //
         // This function must return the weight consumed by `on_initialize` and `on_finalize`.
-        fn on_initialize(_n: frame_system::pallet_prelude::BlockNumberFor<T>) -> Weight {
-            // Anything that needs to be done at the start of the block.
-            // We don't do anything here.
-            Zero::zero()
+        fn on_initialize(n: frame_system::pallet_prelude::BlockNumberFor<T>) -> Weight {
+            // Apply registrant changes to gateways which timelock has expired.
+            Self::process_scheduled_gateway_changes(n)
         }
 
         // `on_finalize` is executed at the end of block after all extrinsic are dispatched.
@@ -210,6 +240,7 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         pub fn check_for_manual_verifier_overview_process(
             n: frame_system::pallet_prelude::BlockNumberFor<T>,
+            max_allowed_weight: Weight,
         ) -> Weight {
             let mut total_weight: Weight = Zero::zero();
 
@@ -248,6 +279,7 @@ pub mod pallet {
                         verifier,
                         epoch,
                         latest_heartbeat,
+                        max_allowed_weight.saturating_sub(total_weight),
                     );
                     total_weight = total_weight.saturating_add(weight);
                 }
@@ -259,7 +291,15 @@ pub mod pallet {
         pub fn process_all_verifier_overviews(
             n: frame_system::pallet_prelude::BlockNumberFor<T>,
         ) -> Weight {
-            Self::check_for_manual_verifier_overview_process(n)
+            Self::check_for_manual_verifier_overview_process(
+                n,
+                Self::overview_queues_weight_limit(),
+            )
+        }
+
+        /// Weight the Circuit queues may consume out of the clock's periodic tasks
+        pub fn overview_queues_weight_limit() -> Weight {
+            OVERVIEW_QUEUES_WEIGHT_SHARE * T::BlockWeights::get().max_block
         }
 
         pub fn process_single_verifier_overview(
@@ -267,6 +307,7 @@ pub mod pallet {
             verifier: GatewayVendor,
             new_epoch: frame_system::pallet_prelude::BlockNumberFor<T>,
             latest_heartbeat: LightClientHeartbeat<T>,
+            max_allowed_weight: Weight,
         ) -> Weight {
             let mut total_weight: Weight = Zero::zero();
 
@@ -350,11 +391,16 @@ pub mod pallet {
             total_weight = total_weight.saturating_add(T::DbWeight::get().reads_writes(3, 2));
 
             if !was_active && activity.is_active {
-                let weight = T::CircuitDLQ::process_dlq(n);
+                let weight =
+                    T::CircuitDLQ::process_dlq(n, max_allowed_weight.saturating_sub(total_weight));
                 total_weight = total_weight.saturating_add(weight);
             }
 
-            let weight = T::CircuitDLQ::process_adaptive_xtx_timeout_queue(n, &verifier);
+            let weight = T::CircuitDLQ::process_adaptive_xtx_timeout_queue(
+                n,
+                &verifier,
+                max_allowed_weight.saturating_sub(total_weight),
+            );
             total_weight = total_weight.saturating_add(weight);
 
             total_weight
@@ -447,7 +493,7 @@ pub mod pallet {
         }
 
         // XDNS Topology Zip / Unzip
-        pub fn do_zip_topology() -> XDNSTopology<T::AccountId> {
+        pub fn do_zip_topology() -> XDNSTopologySnapshotOf<T> {
             let gateways = Self::fetch_full_gateway_records();
 
             // Collect all asset information
@@ -456,63 +502,430 @@ pub mod pallet {
                 .filter_map(|asset_id| Tokens::<T>::get(asset_id, T::SelfGatewayId::get()))
                 .collect::<Vec<TokenRecord>>();
 
+            let gateway_sfx_abis = AllGatewayIds::<T>::get()
+                .into_iter()
+                .map(|gateway_id| (gateway_id, Self::get_all_sfx_abi(&gateway_id)))
+                .filter(|(_, abis)| !abis.is_empty())
+                .collect::<Vec<_>>();
+
             // Assemble the topology structure
-            XDNSTopology { gateways, assets }
+            XDNSTopologySnapshot::new(
+                gateways,
+                assets,
+                StandardSFXABIs::<T>::iter().collect(),
+                gateway_sfx_abis,
+                RemoteOrderAddresses::<T>::iter().collect(),
+                RemoteBiddingAddresses::<T>::iter().collect(),
+                PerTargetAssetEstimates::<T>::iter().collect(),
+                EpochHistory::<T>::iter().collect(),
+            )
         }
 
+        /// Decodes the topology and ensures its version and content hash are correct.
+        pub fn decode_topology(
+            topology_decoded: Option<XDNSTopologySnapshotOf<T>>,
+            topology_encoded: Option<Vec<u8>>,
+        ) -> Result<XDNSTopologySnapshotOf<T>, DispatchError> {
+            let topology = if let Some(topology) = topology_decoded {
+                topology
+            } else if let Some(topology) = topology_encoded {
+                XDNSTopologySnapshotOf::<T>::decode_any_version(topology.as_slice())
+                    .map_err(|_| Error::<T>::TopologyDecodeError)?
+            } else {
+                return Err(Error::<T>::EmptyTopologySubmitted.into())
+            };
+
+            ensure!(
+                topology.version == XDNS_TOPOLOGY_VERSION,
+                Error::<T>::UnsupportedTopologyVersion
+            );
+            ensure!(
+                topology.is_content_hash_valid(),
+                Error::<T>::TopologyContentHashMismatch
+            );
+
+            Ok(topology)
+        }
+
+        /// All token records carried by the topology: self-gateway assets first, as they register
+        /// new assets, followed by the tokens linked to remote gateways.
+        fn topology_token_records(topology: &XDNSTopologySnapshotOf<T>) -> Vec<TokenRecord> {
+            let mut records = topology.assets.clone();
+            for gateway in topology.gateways.iter() {
+                for token in gateway.tokens.iter() {
+                    if !records.iter().any(|record| {
+                        record.token_id == token.token_id && record.gateway_id == token.gateway_id
+                    }) {
+                        records.push(token.clone());
+                    }
+                }
+            }
+            records
+        }
+
+        fn diff_gateways(topology: &XDNSTopologySnapshotOf<T>) -> SectionDiff<TargetId> {
+            let local = Gateways::<T>::iter().collect::<Vec<_>>();
+            let incoming = topology
+                .gateways
+                .iter()
+                .map(|gateway| {
+                    (
+                        gateway.gateway_record.gateway_id,
+                        gateway.gateway_record.clone(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            SectionDiff::compare(&local, &incoming)
+        }
+
+        fn diff_assets(topology: &XDNSTopologySnapshotOf<T>) -> SectionDiff<(AssetId, TargetId)> {
+            let local = Tokens::<T>::iter()
+                .map(|(token_id, gateway_id, record)| ((token_id, gateway_id), record))
+                .collect::<Vec<_>>();
+            let incoming = Self::topology_token_records(topology)
+                .into_iter()
+                .map(|record| ((record.token_id, record.gateway_id), record))
+                .collect::<Vec<_>>();
+            SectionDiff::compare(&local, &incoming)
+        }
+
+        fn diff_standard_sfx_abis(topology: &XDNSTopologySnapshotOf<T>) -> SectionDiff<Sfx4bId> {
+            let local = StandardSFXABIs::<T>::iter().collect::<Vec<_>>();
+            SectionDiff::compare(&local, &topology.standard_sfx_abis)
+        }
+
+        fn diff_gateway_sfx_abis(
+            topology: &XDNSTopologySnapshotOf<T>,
+        ) -> SectionDiff<(TargetId, Sfx4bId)> {
+            let local = SFXABIRegistry::<T>::iter()
+                .map(|(gateway_id, sfx_4b_id, abi)| ((gateway_id, sfx_4b_id), abi))
+                .collect::<Vec<_>>();
+            let incoming = topology
+                .gateway_sfx_abis
+                .iter()
+                .flat_map(|(gateway_id, abis)| {
+                    abis.iter()
+                        .map(move |(sfx_4b_id, abi)| ((*gateway_id, *sfx_4b_id), abi.clone()))
+                })
+                .collect::<Vec<_>>();
+            SectionDiff::compare(&local, &incoming)
+        }
+
+        fn diff_remote_order_addresses(
+            topology: &XDNSTopologySnapshotOf<T>,
+        ) -> SectionDiff<TargetId> {
+            let local = RemoteOrderAddresses::<T>::iter().collect::<Vec<_>>();
+            SectionDiff::compare(&local, &topology.remote_order_addresses)
+        }
+
+        fn diff_remote_bidding_addresses(
+            topology: &XDNSTopologySnapshotOf<T>,
+        ) -> SectionDiff<TargetId> {
+            let local = RemoteBiddingAddresses::<T>::iter().collect::<Vec<_>>();
+            SectionDiff::compare(&local, &topology.remote_bidding_addresses)
+        }
+
+        fn diff_per_target_asset_estimates(
+            topology: &XDNSTopologySnapshotOf<T>,
+        ) -> SectionDiff<(TargetId, AssetId, AssetId)> {
+            let local = PerTargetAssetEstimates::<T>::iter()
+                .map(|(target_id, (asset_a, asset_b), estimate)| {
+                    ((target_id, asset_a, asset_b), estimate)
+                })
+                .collect::<Vec<_>>();
+            let incoming = topology
+                .per_target_asset_estimates
+                .iter()
+                .map(|(target_id, (asset_a, asset_b), estimate)| {
+                    ((*target_id, *asset_a, *asset_b), *estimate)
+                })
+                .collect::<Vec<_>>();
+            SectionDiff::compare(&local, &incoming)
+        }
+
+        fn diff_epoch_history(topology: &XDNSTopologySnapshotOf<T>) -> SectionDiff<GatewayVendor> {
+            let local = EpochHistory::<T>::iter().collect::<Vec<_>>();
+            SectionDiff::compare(&local, &topology.epoch_history)
+        }
+
+        /// Preview the changes unzipping the topology would apply, without writing to storage.
+        pub fn do_diff_topology(topology: &XDNSTopologySnapshotOf<T>) -> XDNSTopologyDiff {
+            XDNSTopologyDiff {
+                version: topology.version,
+                content_hash: topology.content_hash,
+                gateways: Self::diff_gateways(topology),
+                assets: Self::diff_assets(topology),
+                standard_sfx_abis: Self::diff_standard_sfx_abis(topology),
+                gateway_sfx_abis: Self::diff_gateway_sfx_abis(topology),
+                remote_order_addresses: Self::diff_remote_order_addresses(topology),
+                remote_bidding_addresses: Self::diff_remote_bidding_addresses(topology),
+                per_target_asset_estimates: Self::diff_per_target_asset_estimates(topology),
+                epoch_history: Self::diff_epoch_history(topology),
+            }
+        }
+
+        /// Weight of unzipping the selected sections, linear in the number of entries each carries
+        /// and in the number of local entries each is compared against.
+        /// The encoded topology is decoded to count the entries; undecodable input only pays
+        /// the base weight of every section, as it fails right at decoding.
+        pub fn unzip_topology_weight(
+            topology_decoded: &Option<XDNSTopologySnapshotOf<T>>,
+            topology_encoded: &Option<Vec<u8>>,
+            sections: &Option<TopologySections>,
+        ) -> Weight {
+            let sections = sections.clone().unwrap_or_default();
+            let maybe_decoded = match (topology_decoded, topology_encoded) {
+                (Some(_), _) => None,
+                (None, Some(encoded)) =>
+                    XDNSTopologySnapshotOf::<T>::decode_any_version(encoded.as_slice()).ok(),
+                (None, None) => None,
+            };
+            let topology = match (topology_decoded.as_ref(), maybe_decoded.as_ref()) {
+                (Some(topology), _) | (None, Some(topology)) => topology,
+                (None, None) =>
+                    return T::WeightInfo::unzip_topology_gateways(0, 0)
+                        .saturating_add(T::WeightInfo::unzip_topology_assets(0, 0))
+                        .saturating_add(T::WeightInfo::unzip_topology_sfx_abis(0, 0))
+                        .saturating_add(T::WeightInfo::unzip_topology_addresses(0, 0))
+                        .saturating_add(T::WeightInfo::unzip_topology_epoch_history(0, 0)),
+            };
+
+            let count = |selected: bool, entries: usize| -> u32 {
+                if selected {
+                    entries.saturated_into::<u32>()
+                } else {
+                    0
+                }
+            };
+            let sfx_abis = count(sections.standard_sfx_abis, topology.standard_sfx_abis.len())
+                .saturating_add(count(
+                    sections.gateway_sfx_abis,
+                    topology
+                        .gateway_sfx_abis
+                        .iter()
+                        .map(|(_, abis)| abis.len())
+                        .sum(),
+                ));
+            let addresses = count(
+                sections.remote_order_addresses,
+                topology.remote_order_addresses.len(),
+            )
+            .saturating_add(count(
+                sections.remote_bidding_addresses,
+                topology.remote_bidding_addresses.len(),
+            ))
+            .saturating_add(count(
+                sections.per_target_asset_estimates,
+                topology.per_target_asset_estimates.len(),
+            ));
+            let epoch_estimates = count(
+                sections.epoch_history,
+                topology
+                    .epoch_history
+                    .iter()
+                    .map(|(_, history)| history.len())
+                    .sum(),
+            );
+
+            // Local entries of the selected sections, each of them is read to diff against
+            let local_count = |selected: bool, entries: &dyn Fn() -> usize| -> u32 {
+                if selected {
+                    entries().saturated_into::<u32>()
+                } else {
+                    0
+                }
+            };
+            let local_sfx_abis = local_count(sections.standard_sfx_abis, &|| {
+                StandardSFXABIs::<T>::iter_keys().count()
+            })
+            .saturating_add(local_count(sections.gateway_sfx_abis, &|| {
+                SFXABIRegistry::<T>::iter_keys().count()
+            }));
+            let local_addresses = local_count(sections.remote_order_addresses, &|| {
+                RemoteOrderAddresses::<T>::iter_keys().count()
+            })
+            .saturating_add(local_count(sections.remote_bidding_addresses, &|| {
+                RemoteBiddingAddresses::<T>::iter_keys().count()
+            }))
+            .saturating_add(local_count(sections.per_target_asset_estimates, &|| {
+                PerTargetAssetEstimates::<T>::iter_keys().count()
+            }));
+            let local_epoch_estimates = local_count(sections.epoch_history, &|| {
+                EpochHistory::<T>::iter_values()
+                    .map(|history| history.len())
+                    .sum()
+            });
+
+            T::WeightInfo::unzip_topology_gateways(
+                count(sections.gateways, topology.gateways.len()),
+                local_count(sections.gateways, &|| Gateways::<T>::iter_keys().count()),
+            )
+            .saturating_add(T::WeightInfo::unzip_topology_assets(
+                count(
+                    sections.assets,
+                    Self::topology_token_records(topology).len(),
+                ),
+                local_count(sections.assets, &|| Tokens::<T>::iter_keys().count()),
+            ))
+            .saturating_add(T::WeightInfo::unzip_topology_sfx_abis(
+                sfx_abis,
+                local_sfx_abis,
+            ))
+            .saturating_add(T::WeightInfo::unzip_topology_addresses(
+                addresses,
+                local_addresses,
+            ))
+            .saturating_add(T::WeightInfo::unzip_topology_epoch_history(
+                epoch_estimates,
+                local_epoch_estimates,
+            ))
+        }
+
+        /// Applies the selected sections of the topology. Only entries which are missing or differ
+        /// from the local state are written, so unzipping the same topology twice is a no-op.
+        /// Local entries absent in the topology are left untouched.
+        /// Each section is compared right before it's applied, since applying gateways re-populates
+        /// their SFX ABIs with the standard ones.
         pub fn do_unzip_topology(
             origin: &OriginFor<T>,
-            topology: XDNSTopology<T::AccountId>,
-        ) -> DispatchResult {
-            // Update the gateways
-            for gateway in topology.gateways {
-                // Register Gateway
-                let (
-                    gateway_id,
-                    verification_vendor,
-                    execution_vendor,
-                    codec,
-                    registrant,
-                    escrow_account,
-                    allowed_side_effects,
-                ) = (
-                    gateway.gateway_record.gateway_id,
-                    gateway.gateway_record.verification_vendor,
-                    gateway.gateway_record.execution_vendor,
-                    gateway.gateway_record.codec,
-                    gateway.gateway_record.registrant,
-                    gateway.gateway_record.escrow_account,
-                    gateway.gateway_record.allowed_side_effects,
-                );
-                log::info!("topology unzip -- gateway_id: {:?}", gateway_id);
-                Self::override_gateway(
-                    gateway_id,
-                    verification_vendor,
-                    execution_vendor,
-                    codec,
-                    registrant,
-                    escrow_account,
-                    allowed_side_effects,
-                )?;
+            topology: XDNSTopologySnapshotOf<T>,
+            sections: TopologySections,
+        ) -> Result<u32, DispatchError> {
+            let mut writes: u32 = 0;
+
+            if sections.standard_sfx_abis {
+                let diff = Self::diff_standard_sfx_abis(&topology);
+                for (sfx_4b_id, abi) in topology.standard_sfx_abis.iter() {
+                    if diff.added.contains(sfx_4b_id) || diff.changed.contains(sfx_4b_id) {
+                        log::info!("topology unzip -- standard sfx abi: {:?}", sfx_4b_id);
+                        <StandardSFXABIs<T>>::insert(sfx_4b_id, abi.clone());
+                    }
+                }
+                writes = writes.saturating_add(diff.writes_count());
             }
 
-            // Update the assets
-            for asset in topology.assets {
-                log::info!("topology unzip -- asset_id: {:?}", asset.token_id);
-                // Register Asset if not present
-                if !AllTokenIds::<T>::get().contains(&asset.token_id) {
-                    Self::register_new_token(origin, asset.token_id, asset.token_props)?;
-                } else {
-                    // Link the asset to the gateway
-                    Self::link_token_to_gateway(
-                        asset.token_id,
-                        asset.gateway_id,
-                        asset.token_props,
+            if sections.gateways {
+                let diff = Self::diff_gateways(&topology);
+                for gateway in topology.gateways.iter() {
+                    let record = gateway.gateway_record.clone();
+                    if !diff.added.contains(&record.gateway_id)
+                        && !diff.changed.contains(&record.gateway_id)
+                    {
+                        continue
+                    }
+                    log::info!("topology unzip -- gateway_id: {:?}", record.gateway_id);
+                    Self::override_gateway(
+                        record.gateway_id,
+                        record.verification_vendor,
+                        record.execution_vendor,
+                        record.codec,
+                        record.registrant,
+                        record.escrow_account,
+                        record.allowed_side_effects,
                     )?;
                 }
+                writes = writes.saturating_add(diff.writes_count());
             }
 
-            Ok(())
+            if sections.gateway_sfx_abis {
+                let diff = Self::diff_gateway_sfx_abis(&topology);
+                for (gateway_id, abis) in topology.gateway_sfx_abis.iter() {
+                    ensure!(
+                        <Gateways<T>>::contains_key(gateway_id),
+                        Error::<T>::GatewayRecordNotFound
+                    );
+                    // Gateway's allowed side effects are carried by the gateways section,
+                    // so only the ABI registry entries are written here.
+                    for (sfx_4b_id, abi) in abis.iter() {
+                        let key = (*gateway_id, *sfx_4b_id);
+                        if diff.added.contains(&key) || diff.changed.contains(&key) {
+                            log::info!(
+                                "topology unzip -- sfx abi: {:?} of gateway: {:?}",
+                                sfx_4b_id,
+                                gateway_id
+                            );
+                            <SFXABIRegistry<T>>::insert(gateway_id, sfx_4b_id, abi.clone());
+                        }
+                    }
+                }
+                writes = writes.saturating_add(diff.writes_count());
+            }
+
+            if sections.assets {
+                let diff = Self::diff_assets(&topology);
+                for asset in Self::topology_token_records(&topology) {
+                    let key = (asset.token_id, asset.gateway_id);
+                    if diff.changed.contains(&key) {
+                        log::info!("topology unzip -- override asset_id: {:?}", asset.token_id);
+                        Self::override_token(asset.token_id, asset.gateway_id, asset.token_props)?;
+                    } else if diff.added.contains(&key) {
+                        log::info!("topology unzip -- asset_id: {:?}", asset.token_id);
+                        // Register Asset if not present
+                        if asset.gateway_id == T::SelfGatewayId::get()
+                            && !T::AssetsOverlay::contains_asset(&asset.token_id)
+                        {
+                            Self::register_new_token(origin, asset.token_id, asset.token_props)?;
+                        } else {
+                            // Link the asset to the gateway
+                            Self::link_token_to_gateway(
+                                asset.token_id,
+                                asset.gateway_id,
+                                asset.token_props,
+                            )?;
+                        }
+                    }
+                }
+                writes = writes.saturating_add(diff.writes_count());
+            }
+
+            if sections.remote_order_addresses {
+                let diff = Self::diff_remote_order_addresses(&topology);
+                for (target_id, address) in topology.remote_order_addresses.iter() {
+                    if diff.added.contains(target_id) || diff.changed.contains(target_id) {
+                        <RemoteOrderAddresses<T>>::insert(target_id, address);
+                    }
+                }
+                writes = writes.saturating_add(diff.writes_count());
+            }
+
+            if sections.remote_bidding_addresses {
+                let diff = Self::diff_remote_bidding_addresses(&topology);
+                for (target_id, address) in topology.remote_bidding_addresses.iter() {
+                    if diff.added.contains(target_id) || diff.changed.contains(target_id) {
+                        <RemoteBiddingAddresses<T>>::insert(target_id, address);
+                    }
+                }
+                writes = writes.saturating_add(diff.writes_count());
+            }
+
+            if sections.per_target_asset_estimates {
+                let diff = Self::diff_per_target_asset_estimates(&topology);
+                for (target_id, (asset_a, asset_b), estimate) in
+                    topology.per_target_asset_estimates.iter()
+                {
+                    let key = (*target_id, *asset_a, *asset_b);
+                    if diff.added.contains(&key) || diff.changed.contains(&key) {
+                        <PerTargetAssetEstimates<T>>::insert(
+                            target_id,
+                            (*asset_a, *asset_b),
+                            estimate,
+                        );
+                    }
+                }
+                writes = writes.saturating_add(diff.writes_count());
+            }
+
+            if sections.epoch_history {
+                let diff = Self::diff_epoch_history(&topology);
+                for (verifier, history) in topology.epoch_history.iter() {
+                    if diff.added.contains(verifier) || diff.changed.contains(verifier) {
+                        <EpochHistory<T>>::insert(verifier, history.clone());
+                    }
+                }
+                writes = writes.saturating_add(diff.writes_count());
+            }
+
+            Ok(writes)
         }
     }
 
@@ -577,6 +990,7 @@ pub mod pallet {
             Ok(().into())
         }
 
+        /// Enrolls the ABI of a side effect to the gateway. Root or gateway registrant access.
         #[pallet::weight(< T as Config >::WeightInfo::reboot_self_gateway())]
         pub fn enroll_new_abi_to_selected_gateway(
             origin: OriginFor<T>,
@@ -585,37 +999,99 @@ pub mod pallet {
             sfx_expected_abi: Option<SFXAbi>,
             maybe_pallet_id: Option<u8>,
         ) -> DispatchResultWithPostInfo {
-            ensure_root(origin.clone())?;
-
-            if let Some(abi) = sfx_expected_abi {
-                <SFXABIRegistry<T>>::insert(target_id, sfx_4b_id, abi);
-            } else {
-                let mut assume_known_abi = <StandardSFXABIs<T>>::get(sfx_4b_id)
-                    .ok_or(Error::<T>::SideEffectABINotFound)?;
-                assume_known_abi.maybe_prefix_memo = maybe_pallet_id;
-                <SFXABIRegistry<T>>::insert(target_id, sfx_4b_id, assume_known_abi);
-            }
-
-            let updated_abi_list = <SFXABIRegistry<T>>::iter_prefix(target_id).collect();
-
-            Self::override_sfx_abi(target_id, updated_abi_list)?;
+            Self::apply_or_schedule_gateway_change(
+                origin,
+                target_id,
+                GatewayChange::EnrollSFXAbi {
+                    sfx_4b_id,
+                    sfx_abi: sfx_expected_abi,
+                    maybe_pallet_id,
+                },
+            )?;
 
             Ok(().into())
         }
 
+        /// Removes the ABI of a side effect from the gateway. Root or gateway registrant access.
         #[pallet::weight(< T as Config >::WeightInfo::reboot_self_gateway())]
         pub fn unroll_abi_of_selected_gateway(
             origin: OriginFor<T>,
             target_id: ChainId,
             sfx_4b_id: Sfx4bId,
         ) -> DispatchResultWithPostInfo {
-            ensure_root(origin.clone())?;
+            Self::apply_or_schedule_gateway_change(
+                origin,
+                target_id,
+                GatewayChange::UnrollSFXAbi { sfx_4b_id },
+            )?;
+
+            Ok(().into())
+        }
 
-            <SFXABIRegistry<T>>::remove(target_id, sfx_4b_id);
+        /// Sets the escrow account of the gateway. Root or gateway registrant access.
+        #[pallet::weight(< T as Config >::WeightInfo::reboot_self_gateway())]
+        pub fn set_gateway_escrow_account(
+            origin: OriginFor<T>,
+            gateway_id: TargetId,
+            escrow_account: Option<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            Self::apply_or_schedule_gateway_change(
+                origin,
+                gateway_id,
+                GatewayChange::SetEscrowAccount(escrow_account),
+            )?;
 
-            let updated_abi_list = <SFXABIRegistry<T>>::iter_prefix(target_id).collect();
+            Ok(().into())
+        }
 
-            Self::override_sfx_abi(target_id, updated_abi_list)?;
+        /// Replaces the side effects allowed on the gateway. Root or gateway registrant access.
+        #[pallet::weight(< T as Config >::WeightInfo::reboot_self_gateway())]
+        pub fn set_gateway_allowed_side_effects(
+            origin: OriginFor<T>,
+            gateway_id: TargetId,
+            allowed_side_effects: Vec<(Sfx4bId, Option<u8>)>,
+        ) -> DispatchResultWithPostInfo {
+            Self::apply_or_schedule_gateway_change(
+                origin,
+                gateway_id,
+                GatewayChange::SetAllowedSideEffects(allowed_side_effects),
+            )?;
+
+            Ok(().into())
+        }
+
+        /// Slashes the registration deposit of the gateway (all of it if no amount given)
+        /// towards the slash treasury. Root only access.
+        #[pallet::weight(< T as Config >::WeightInfo::purge_gateway())]
+        pub fn slash_registration_deposit(
+            origin: OriginFor<T>,
+            gateway_id: TargetId,
+            amount: Option<BalanceOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            let (registrant, deposit) = <GatewayDeposits<T>>::get(gateway_id)
+                .ok_or(Error::<T>::RegistrationDepositNotFound)?;
+
+            let to_slash = amount.unwrap_or(deposit).min(deposit);
+            let not_slashed = T::Currency::repatriate_reserved(
+                &registrant,
+                &T::TreasuryAccounts::get_treasury_account(TreasuryAccount::Slash),
+                to_slash,
+                BalanceStatus::Free,
+            )?;
+            let slashed = to_slash.saturating_sub(not_slashed);
+            let remaining = deposit.saturating_sub(slashed);
+
+            if remaining.is_zero() {
+                <GatewayDeposits<T>>::remove(gateway_id);
+            } else {
+                <GatewayDeposits<T>>::insert(gateway_id, (registrant.clone(), remaining));
+            }
+
+            Self::deposit_event(Event::<T>::RegistrationDepositSlashed(
+                gateway_id, registrant, slashed,
+            ));
 
             Ok(().into())
         }
@@ -642,6 +1118,29 @@ pub mod pallet {
             Ok(().into())
         }
 
+        /// Sets the price of the registered asset accepted for transaction fees, as the amount of the asset worth 1 TRN.
+        ///     Removing the price stops accepting the asset for fees. Root only access.
+        #[pallet::weight(< T as Config >::WeightInfo::set_asset_fee_price())]
+        pub fn set_asset_fee_price(
+            origin: OriginFor<T>,
+            asset_id: AssetId,
+            price: Option<BalanceOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                <AllTokenIds<T>>::get().contains(&asset_id),
+                Error::<T>::AssetNotAcceptedForFees
+            );
+
+            match price {
+                Some(price) if !price.is_zero() => <AssetFeePrices<T>>::insert(asset_id, price),
+                _ => <AssetFeePrices<T>>::remove(asset_id),
+            }
+            Self::deposit_event(Event::<T>::AssetFeePriceSet(asset_id, price));
+
+            Ok(().into())
+        }
+
         /// Re-adds the self-gateway if was present before. Inserts if wasn't. Root only access.
         #[pallet::weight(< T as Config >::WeightInfo::reboot_self_gateway())]
         pub fn purge_supported_bridging_asset(
@@ -691,6 +1190,11 @@ pub mod pallet {
 
                 <GatewayTokens<T>>::remove(gateway_id);
 
+                // Return whatever is left of the registration deposit
+                if let Some((registrant, deposit)) = <GatewayDeposits<T>>::take(gateway_id) {
+                    T::Currency::unreserve(&registrant, deposit);
+                }
+
                 <AllGatewayIds<T>>::mutate(|all_gateway_ids| {
                     all_gateway_ids.retain(|&id| id != gateway_id);
                 });
@@ -704,6 +1208,7 @@ pub mod pallet {
                     verifier,
                     epoch,
                     latest_heartbeat,
+                    Self::overview_queues_weight_limit(),
                 );
 
                 Self::deposit_event(Event::<T>::GatewayRecordPurged(requester, gateway_id));
@@ -711,23 +1216,23 @@ pub mod pallet {
             }
         }
 
+        /// Unlinks the token from the gateway. Root or gateway registrant access.
         #[pallet::weight(< T as Config >::WeightInfo::purge_gateway())]
         pub fn unlink_token(
             origin: OriginFor<T>,
             gateway_id: TargetId,
             token_id: AssetId,
         ) -> DispatchResultWithPostInfo {
-            ensure_root(origin)?;
-
-            <Tokens<T>>::remove(token_id, gateway_id);
-
-            <GatewayTokens<T>>::mutate(gateway_id, |token_ids| {
-                token_ids.retain(|&x_token_id| x_token_id != token_id);
-            });
+            Self::apply_or_schedule_gateway_change(
+                origin,
+                gateway_id,
+                GatewayChange::UnlinkToken { token_id },
+            )?;
 
             Ok(().into())
         }
 
+        /// Links the already registered token to the gateway. Root or gateway registrant access.
         #[pallet::weight(< T as Config >::WeightInfo::purge_gateway())]
         pub fn link_token(
             origin: OriginFor<T>,
@@ -735,9 +1240,14 @@ pub mod pallet {
             token_id: AssetId,
             token_props: TokenInfo,
         ) -> DispatchResultWithPostInfo {
-            ensure_root(origin)?;
-
-            Self::link_token_to_gateway(token_id, gateway_id, token_props)?;
+            Self::apply_or_schedule_gateway_change(
+                origin,
+                gateway_id,
+                GatewayChange::LinkToken {
+                    token_id,
+                    token_props,
+                },
+            )?;
 
             Ok(().into())
         }
@@ -783,24 +1293,22 @@ pub mod pallet {
             Ok(())
         }
 
-        #[pallet::weight(< T as Config >::WeightInfo::purge_gateway())]
+        /// Imports the selected sections of the topology (all if none given). Root only access.
+        /// Accepts both the current, versioned snapshot and the legacy (v1) encoded topology.
+        #[pallet::weight(Pallet::<T>::unzip_topology_weight(topology_decoded, topology_encoded, sections))]
         pub fn unzip_topology(
             origin: OriginFor<T>,
-            topology_decoded: Option<XDNSTopology<T::AccountId>>,
+            topology_decoded: Option<XDNSTopologySnapshotOf<T>>,
             topology_encoded: Option<Vec<u8>>,
+            sections: Option<TopologySections>,
         ) -> DispatchResult {
             let _ = ensure_root(origin.clone())?;
-            let topology = if let Some(topology) = topology_decoded {
-                topology
-            } else if let Some(topology) = topology_encoded {
-                let topology = XDNSTopology::<T::AccountId>::decode(&mut topology.as_slice())
-                    .map_err(|_| Error::<T>::TopologyDecodeError)?;
-                topology
-            } else {
-                return Err(Error::<T>::EmptyTopologySubmitted.into())
-            };
+            let topology = Self::decode_topology(topology_decoded, topology_encoded)?;
+            let content_hash = topology.content_hash;
+
+            let writes = Self::do_unzip_topology(&origin, topology, sections.unwrap_or_default())?;
 
-            Self::do_unzip_topology(&origin, topology)?;
+            Self::deposit_event(Event::<T>::XDNSTopologyUnzip(content_hash, writes));
 
             Ok(())
         }
@@ -822,7 +1330,19 @@ pub mod pallet {
         /// \[xdns_record_id\]
         XdnsRecordUpdated(TargetId),
         /// \[xdns_topology\]
-        XDNSTopologyZip(XDNSTopology<T::AccountId>),
+        XDNSTopologyZip(XDNSTopologySnapshotOf<T>),
+        /// \[content_hash, entries_written\]
+        XDNSTopologyUnzip(H256, u32),
+        /// \[gateway_id, registrant, deposit\]
+        RegistrationDepositReserved(TargetId, T::AccountId, BalanceOf<T>),
+        /// \[gateway_id, registrant, slashed_amount\]
+        RegistrationDepositSlashed(TargetId, T::AccountId, BalanceOf<T>),
+        /// \[gateway_id, scheduled_at\]
+        GatewayChangeScheduled(TargetId, BlockNumberFor<T>),
+        /// \[gateway_id\]
+        GatewayChangeApplied(TargetId),
+        /// \[asset_id, price\]
+        AssetFeePriceSet(AssetId, Option<BalanceOf<T>>),
     }
 
     // Errors inform users that something went wrong.
@@ -858,6 +1378,22 @@ pub mod pallet {
         TopologyDecodeError,
         /// Empty topology submitted at Unzip
         EmptyTopologySubmitted,
+        /// Topology version is not supported at Unzip
+        UnsupportedTopologyVersion,
+        /// Topology content doesn't match its content hash at Unzip
+        TopologyContentHashMismatch,
+        /// Origin is neither root nor the registrant of the gateway
+        NotGatewayRegistrant,
+        /// Gateway has no registration deposit reserved
+        RegistrationDepositNotFound,
+        /// Asset isn't registered in XDNS and can't be used to pay transaction fees
+        AssetNotAcceptedForFees,
+        /// Asset has no fee price set
+        AssetFeePriceNotFound,
+        /// Too many gateway changes are already scheduled at the block; retry in the next one
+        TooManyScheduledGatewayChanges,
+        /// The registrant has too many gateway changes pending
+        TooManyPendingGatewayChanges,
     }
 
     // Deprecated storage entry -- StandardSideEffects
@@ -950,6 +1486,12 @@ pub mod pallet {
     pub type AssetCostEstimatesInNative<T: Config> =
         StorageMap<_, Identity, AssetId, BalanceOf<T>, ValueQuery>;
 
+    /// Governance-set prices of the assets accepted for transaction fees, as the amount of the asset worth 1 TRN.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_fee_prices)]
+    pub type AssetFeePrices<T: Config> =
+        StorageMap<_, Identity, AssetId, BalanceOf<T>, OptionQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn gateways_overview)]
     pub type GatewaysOverviewStore<T: Config> =
@@ -986,6 +1528,32 @@ pub mod pallet {
     pub type EpochHistory<T: Config> =
         StorageMap<_, Identity, GatewayVendor, Vec<EpochEstimate<BlockNumberFor<T>>>>;
 
+    /// Registration deposits of permissionless gateway registrants: gateway -> (registrant, deposit)
+    #[pallet::storage]
+    #[pallet::getter(fn gateway_deposits)]
+    pub type GatewayDeposits<T: Config> =
+        StorageMap<_, Identity, TargetId, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+    /// Timelocked registrant changes to gateways, keyed by the block they're applied at
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_gateway_changes)]
+    pub type ScheduledGatewayChanges<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<
+            (TargetId, T::AccountId, GatewayChange<T::AccountId>),
+            T::MaxScheduledGatewayChanges,
+        >,
+        ValueQuery,
+    >;
+
+    /// Number of scheduled gateway changes pending per registrant
+    #[pallet::storage]
+    #[pallet::getter(fn pending_gateway_changes)]
+    pub type PendingGatewayChanges<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
     // The genesis config type.
     #[pallet::genesis_config]
     #[derive(frame_support::DefaultNoBound)]
@@ -1038,6 +1606,187 @@ pub mod pallet {
     }
 
     impl<T: Config> Pallet<T> {
+        /// Ensures the origin is either root or the registrant of the gateway.
+        /// Returns the registrant for signed origins and None for root.
+        pub fn ensure_root_or_registrant(
+            origin: OriginFor<T>,
+            gateway_id: &TargetId,
+        ) -> Result<Option<T::AccountId>, DispatchError> {
+            match ensure_signed_or_root(origin)? {
+                None => Ok(None),
+                Some(who) => {
+                    let record =
+                        <Gateways<T>>::get(gateway_id).ok_or(Error::<T>::GatewayRecordNotFound)?;
+                    ensure!(
+                        record.registrant.as_ref() == Some(&who),
+                        Error::<T>::NotGatewayRegistrant
+                    );
+                    Ok(Some(who))
+                },
+            }
+        }
+
+        /// Root changes are applied right away. Registrant changes are timelocked
+        /// by GatewayChangeTimelock if they may affect Xtx active on the gateway.
+        pub fn apply_or_schedule_gateway_change(
+            origin: OriginFor<T>,
+            gateway_id: TargetId,
+            change: GatewayChange<T::AccountId>,
+        ) -> DispatchResult {
+            let maybe_registrant = Self::ensure_root_or_registrant(origin, &gateway_id)?;
+
+            if let Some(registrant) = maybe_registrant {
+                if Self::is_change_timelocked(&gateway_id, &change) {
+                    let pending = <PendingGatewayChanges<T>>::get(&registrant);
+                    ensure!(
+                        pending < T::MaxPendingGatewayChangesPerRegistrant::get(),
+                        Error::<T>::TooManyPendingGatewayChanges
+                    );
+                    let scheduled_at = <frame_system::Pallet<T>>::block_number()
+                        .saturating_add(T::GatewayChangeTimelock::get());
+                    <ScheduledGatewayChanges<T>>::try_append(
+                        scheduled_at,
+                        (gateway_id, registrant.clone(), change),
+                    )
+                    .map_err(|_| Error::<T>::TooManyScheduledGatewayChanges)?;
+                    <PendingGatewayChanges<T>>::insert(&registrant, pending.saturating_add(1));
+                    Self::deposit_event(Event::<T>::GatewayChangeScheduled(
+                        gateway_id,
+                        scheduled_at,
+                    ));
+                    return Ok(())
+                }
+            }
+
+            Self::apply_gateway_change(gateway_id, change)
+        }
+
+        fn is_change_timelocked(
+            gateway_id: &TargetId,
+            change: &GatewayChange<T::AccountId>,
+        ) -> bool {
+            let is_additive = match change {
+                GatewayChange::EnrollSFXAbi { sfx_4b_id, .. } =>
+                    !<SFXABIRegistry<T>>::contains_key(gateway_id, sfx_4b_id),
+                _ => change.is_additive(),
+            };
+            !is_additive && T::CircuitDLQ::has_active_xtx_on_target(gateway_id)
+        }
+
+        pub fn apply_gateway_change(
+            gateway_id: TargetId,
+            change: GatewayChange<T::AccountId>,
+        ) -> DispatchResult {
+            match change {
+                GatewayChange::EnrollSFXAbi {
+                    sfx_4b_id,
+                    sfx_abi,
+                    maybe_pallet_id,
+                } => {
+                    if let Some(abi) = sfx_abi {
+                        <SFXABIRegistry<T>>::insert(gateway_id, sfx_4b_id, abi);
+                    } else {
+                        let mut assume_known_abi = <StandardSFXABIs<T>>::get(sfx_4b_id)
+                            .ok_or(Error::<T>::SideEffectABINotFound)?;
+                        assume_known_abi.maybe_prefix_memo = maybe_pallet_id;
+                        <SFXABIRegistry<T>>::insert(gateway_id, sfx_4b_id, assume_known_abi);
+                    }
+                    let updated_abi_list = <SFXABIRegistry<T>>::iter_prefix(gateway_id).collect();
+                    Self::override_sfx_abi(gateway_id, updated_abi_list)?;
+                },
+                GatewayChange::UnrollSFXAbi { sfx_4b_id } => {
+                    <SFXABIRegistry<T>>::remove(gateway_id, sfx_4b_id);
+                    let updated_abi_list = <SFXABIRegistry<T>>::iter_prefix(gateway_id).collect();
+                    Self::override_sfx_abi(gateway_id, updated_abi_list)?;
+                },
+                GatewayChange::LinkToken {
+                    token_id,
+                    token_props,
+                } => {
+                    Self::link_token_to_gateway(token_id, gateway_id, token_props)?;
+                },
+                GatewayChange::UnlinkToken { token_id } => {
+                    <Tokens<T>>::remove(token_id, gateway_id);
+                    <GatewayTokens<T>>::mutate(gateway_id, |token_ids| {
+                        token_ids.retain(|&x_token_id| x_token_id != token_id);
+                    });
+                },
+                GatewayChange::SetEscrowAccount(escrow_account) => {
+                    Gateways::<T>::mutate(gateway_id, |gateway| match gateway {
+                        None => Err(Error::<T>::GatewayRecordNotFound),
+                        Some(record) => {
+                            record.escrow_account = escrow_account;
+                            Ok(())
+                        },
+                    })?;
+                },
+                GatewayChange::SetAllowedSideEffects(allowed_side_effects) => {
+                    let record =
+                        <Gateways<T>>::get(gateway_id).ok_or(Error::<T>::GatewayRecordNotFound)?;
+                    // Drop ABIs of side effects no longer allowed
+                    let disallowed = <SFXABIRegistry<T>>::iter_key_prefix(gateway_id)
+                        .filter(|sfx_4b_id| {
+                            !allowed_side_effects
+                                .iter()
+                                .any(|(allowed, _)| allowed == sfx_4b_id)
+                        })
+                        .collect::<Vec<_>>();
+                    for sfx_4b_id in disallowed {
+                        <SFXABIRegistry<T>>::remove(gateway_id, sfx_4b_id);
+                    }
+                    Self::override_gateway(
+                        gateway_id,
+                        record.verification_vendor,
+                        record.execution_vendor,
+                        record.codec,
+                        record.registrant,
+                        record.escrow_account,
+                        allowed_side_effects,
+                    )?;
+                },
+            }
+
+            Self::deposit_event(Event::<T>::GatewayChangeApplied(gateway_id));
+            Ok(())
+        }
+
+        pub fn process_scheduled_gateway_changes(
+            n: frame_system::pallet_prelude::BlockNumberFor<T>,
+        ) -> Weight {
+            let scheduled_changes = <ScheduledGatewayChanges<T>>::take(n);
+            let mut total_weight = T::DbWeight::get().reads_writes(1, 1);
+
+            for (gateway_id, registrant, change) in scheduled_changes {
+                <PendingGatewayChanges<T>>::mutate_exists(&registrant, |pending| {
+                    *pending = pending.map(|p| p.saturating_sub(1)).filter(|p| *p > 0);
+                });
+                // The gateway may have been purged or re-registered since the change was scheduled
+                let still_registrant = <Gateways<T>>::get(gateway_id).map_or(false, |record| {
+                    record.registrant.as_ref() == Some(&registrant)
+                });
+                total_weight = total_weight.saturating_add(T::DbWeight::get().reads_writes(2, 1));
+                if !still_registrant {
+                    log::error!(
+                        "XDNS -- dropped scheduled change of gateway {:?}: no longer its registrant",
+                        gateway_id,
+                    );
+                    continue
+                }
+                if let Err(err) = frame_support::storage::with_storage_layer(|| {
+                    Self::apply_gateway_change(gateway_id, change)
+                }) {
+                    log::error!(
+                        "XDNS -- failed to apply scheduled change of gateway {:?}: {:?}",
+                        gateway_id,
+                        err
+                    );
+                }
+                total_weight = total_weight.saturating_add(T::DbWeight::get().reads_writes(3, 3));
+            }
+
+            total_weight
+        }
+
         pub fn do_reboot_self_gateway(
             origin: OriginFor<T>,
             vendor: GatewayVendor,
@@ -1168,10 +1917,23 @@ pub mod pallet {
                 verifier,
                 new_epoch,
                 current_hearbeat,
+                Self::overview_queues_weight_limit(),
             );
         }
     }
 
+    impl<T: Config> PeriodicTasks<T> for Pallet<T> {
+        fn periodic_tasks() -> Vec<PeriodicTask<T>> {
+            vec![PeriodicTask {
+                name: b"xdns::verifier_overview",
+                cadence: Cadence::Hours(1),
+                share: sp_runtime::Percent::from_percent(5),
+                priority: 10,
+                run: |n, limit| Self::check_for_manual_verifier_overview_process(n, limit),
+            }]
+        }
+    }
+
     impl<T: Config> Xdns<T, BalanceOf<T>> for Pallet<T> {
         /// Fetches all known Gateway records
         fn fetch_gateways() -> Vec<GatewayRecord<T::AccountId>> {
@@ -1293,13 +2055,14 @@ pub mod pallet {
             for token in <GatewayTokens<T>>::get(gateway_id) {
                 let token_record = <Tokens<T>>::get(token, gateway_id)
                     .ok_or(Error::<T>::TokenRecordNotFoundInGateway)?;
-                match token_record.token_props {
-                    TokenInfo::Ethereum(ref eth_token) =>
-                        if eth_token.address == Some(eth_address.into()) {
-                            return Ok(token_record.clone())
-                        },
+                let token_address = match token_record.token_props {
+                    TokenInfo::Ethereum(ref eth_token) => eth_token.address.map(H160),
+                    // pallet-assets tokens are addressed by their ERC-20 precompile
                     TokenInfo::Substrate(_) =>
-                        return Err(Error::<T>::TokenRecordNotFoundInGateway.into()),
+                        Some(get_tokens_precompile_address(token_record.token_id)),
+                };
+                if token_address == Some(eth_address) {
+                    return Ok(token_record)
                 }
             }
             Err(Error::<T>::TokenRecordNotFoundInGateway.into())
@@ -1484,6 +2247,13 @@ pub mod pallet {
             }
         }
 
+        fn get_execution_vendor(chain_id: &ChainId) -> Result<ExecutionVendor, DispatchError> {
+            match <Gateways<T>>::get(chain_id) {
+                Some(rec) => Ok(rec.execution_vendor),
+                None => Err(Error::<T>::XdnsRecordNotFound.into()),
+            }
+        }
+
         fn get_target_codec(chain_id: &ChainId) -> Result<Codec, DispatchError> {
             match <Gateways<T>>::get(chain_id) {
                 Some(rec) => Ok(rec.codec),
@@ -1561,6 +2331,49 @@ pub mod pallet {
             T::SelfTokenId::get()
         }
 
+        fn is_token_registered(token_id: AssetId) -> bool {
+            <AllTokenIds<T>>::get().contains(&token_id)
+        }
+
+        fn convert_native_fee_to_asset(
+            asset_id: AssetId,
+            native_fee: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            ensure!(
+                Self::is_token_registered(asset_id),
+                Error::<T>::AssetNotAcceptedForFees
+            );
+
+            // Only the governance-set prices are quoted in ASSET_FEE_PRICE_UNIT, unlike the estimates of the gateways
+            let price =
+                <AssetFeePrices<T>>::get(asset_id).ok_or(Error::<T>::AssetFeePriceNotFound)?;
+
+            // Round up, so that fees paid in assets never fall short of the native fee
+            let asset_fee = native_fee
+                .saturated_into::<u128>()
+                .checked_mul(price.saturated_into::<u128>())
+                .ok_or(sp_runtime::ArithmeticError::Overflow)?
+                .saturating_add(ASSET_FEE_PRICE_UNIT - 1)
+                / ASSET_FEE_PRICE_UNIT;
+
+            Ok(asset_fee.saturated_into())
+        }
+
+        fn reserve_registration_deposit(
+            registrant: &T::AccountId,
+            gateway_id: TargetId,
+        ) -> DispatchResult {
+            let deposit = T::GatewayRegistrationDeposit::get();
+            T::Currency::reserve(registrant, deposit)?;
+            <GatewayDeposits<T>>::insert(gateway_id, (registrant.clone(), deposit));
+            Self::deposit_event(Event::<T>::RegistrationDepositReserved(
+                gateway_id,
+                registrant.clone(),
+                deposit,
+            ));
+            Ok(())
+        }
+
         fn mint(asset_id: AssetId, user: T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
             assert!(
                 Self::check_asset_is_mintable(T::SelfGatewayId::get(), asset_id),
@@ -23,19 +23,25 @@ use codec::Decode;
 
 use frame_support::pallet_prelude::Weight;
 
-use frame_support::{assert_err, assert_noop, assert_ok, traits::OnInitialize};
+use frame_support::{
+    assert_err, assert_noop, assert_ok,
+    traits::{tokens::ConversionToAssetBalance, Currency, Get, OnInitialize, ReservableCurrency},
+};
 use sp_core::{crypto::AccountId32, H256};
 use sp_runtime::DispatchError;
 use t3rn_primitives::{
     circuit::SecurityLvl::{Escrow, Optimistic},
-    clock::OnHookQueues,
+    clock::Cadence,
     portal::Portal as PortalT,
-    xdns::{FullGatewayRecord, GatewayRecord, PalletAssetsOverlay, Xdns},
+    xdns::{
+        FullGatewayRecord, GatewayChange, GatewayRecord, PalletAssetsOverlay, TopologySections,
+        Xdns, ASSET_FEE_PRICE_UNIT, XDNS_TOPOLOGY_VERSION,
+    },
     EthereumToken, ExecutionVendor,
     ExecutionVendor::{Substrate, EVM},
     FinalityVerifierActivity, GatewayActivity, GatewayVendor,
     GatewayVendor::{Ethereum, Kusama, Polkadot, Rococo},
-    SpeedMode, SubstrateToken, TokenInfo, XDNSTopology,
+    SpeedMode, SubstrateToken, TokenInfo, TreasuryAccount, TreasuryAccountProvider, XDNSTopology,
 };
 
 use t3rn_abi::Codec::{Rlp, Scale};
@@ -192,7 +198,10 @@ fn should_zip_xdns_topology_for_default_records_via_event() {
                 ),
             };
 
-            assert_eq!(xdns_topology_zip.encode(), hex!("20000000000200000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00010101010000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00030303030000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00050505050000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00657468320301010000047472616e010200676174650200000000047472616e0102006b736d610100000000087472616e01027461737301040070646f740000000000087472616e01027461737301040000").to_vec())
+            assert_eq!(xdns_topology_zip.version, XDNS_TOPOLOGY_VERSION);
+            assert!(xdns_topology_zip.is_content_hash_valid());
+            // Gateways and assets sections remain compatible with the legacy (v1) topology encoding
+            assert_eq!((xdns_topology_zip.gateways, xdns_topology_zip.assets).encode(), hex!("20000000000200000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00010101010000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00030303030000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00050505050000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00657468320301010000047472616e010200676174650200000000047472616e0102006b736d610100000000087472616e01027461737301040070646f740000000000087472616e01027461737301040000").to_vec())
         });
 }
 
@@ -209,9 +218,10 @@ fn should_unzip_xdns_topology_from_decoded_form_with_default_records() {
             let encoded_xdns_topology = hex!("20000000000200000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00010101010000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00030303030000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00050505050000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00657468320301010000047472616e010200676174650200000000047472616e0102006b736d610100000000087472616e01027461737301040070646f740000000000087472616e01027461737301040000").to_vec();
             // Decode the topology
             let decoded_xdns_topology = XDNSTopology::<AccountId32>::decode(&mut &encoded_xdns_topology[..]).unwrap();
+            let decoded_xdns_topology = XDNSTopologySnapshotOf::<Runtime>::from_legacy(decoded_xdns_topology);
 
             // Unzip the topology
-            assert_ok!(XDNS::unzip_topology(Origin::root(), Some(decoded_xdns_topology), None));
+            assert_ok!(XDNS::unzip_topology(Origin::root(), Some(decoded_xdns_topology), None, None));
 
             // check that the topology is not empty via all assets and all gateways ids
             assert_eq!(pallet_xdns::Gateways::<Runtime>::iter().count(), DEFAULT_GATEWAYS_IN_STORAGE_COUNT);
@@ -230,13 +240,231 @@ fn should_unzip_xdns_topology_from_encoded_form_with_default_records() {
 
             let encoded_xdns_topology = hex!("20000000000200000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00010101010000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00030303030000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00050505050000000000187472616e0102746173730104737761700103616c697101036365766d010a7761736d010a00657468320301010000047472616e010200676174650200000000047472616e0102006b736d610100000000087472616e01027461737301040070646f740000000000087472616e01027461737301040000").to_vec();
             // Unzip the topology
-            assert_ok!(XDNS::unzip_topology(Origin::root(), None, Some(encoded_xdns_topology)));
+            assert_ok!(XDNS::unzip_topology(Origin::root(), None, Some(encoded_xdns_topology), None));
 
             // check that the topology is not empty via all assets and all gateways ids
             assert_eq!(pallet_xdns::Gateways::<Runtime>::iter().count(), DEFAULT_GATEWAYS_IN_STORAGE_COUNT);
         });
 }
 
+fn zip_default_topology_with_remote_addresses() -> XDNSTopologySnapshotOf<Runtime> {
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            assert_ok!(XDNS::add_remote_order_address(
+                Origin::root(),
+                *b"eth2",
+                H256::repeat_byte(1)
+            ));
+            assert_ok!(XDNS::add_remote_bidding_address(
+                Origin::root(),
+                *b"eth2",
+                H256::repeat_byte(2)
+            ));
+            XDNS::do_zip_topology()
+        })
+}
+
+#[test]
+fn should_zip_complete_xdns_topology_with_content_hash() {
+    let topology = zip_default_topology_with_remote_addresses();
+
+    assert_eq!(topology.version, XDNS_TOPOLOGY_VERSION);
+    assert!(topology.is_content_hash_valid());
+    assert_eq!(topology.gateways.len(), DEFAULT_GATEWAYS_IN_STORAGE_COUNT);
+    assert_eq!(topology.standard_sfx_abis.len(), STANDARD_SFX_ABI_COUNT);
+    assert!(!topology.gateway_sfx_abis.is_empty());
+    assert_eq!(
+        topology.remote_order_addresses,
+        vec![(*b"eth2", H256::repeat_byte(1))]
+    );
+    assert_eq!(
+        topology.remote_bidding_addresses,
+        vec![(*b"eth2", H256::repeat_byte(2))]
+    );
+
+    // Re-encoded snapshot decodes back to the same content
+    let decoded =
+        XDNSTopologySnapshotOf::<Runtime>::decode_any_version(&topology.encode()).unwrap();
+    assert_eq!(decoded, topology);
+}
+
+#[test]
+fn should_diff_xdns_topology_without_writing_to_storage() {
+    let topology = zip_default_topology_with_remote_addresses();
+
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            let diff = XDNS::do_diff_topology(&topology);
+
+            assert_eq!(diff.content_hash, topology.content_hash);
+            assert_eq!(diff.gateways.added.len(), DEFAULT_GATEWAYS_IN_STORAGE_COUNT);
+            assert_eq!(diff.remote_order_addresses.added, vec![*b"eth2"]);
+            assert_eq!(
+                diff.standard_sfx_abis.unchanged as usize,
+                STANDARD_SFX_ABI_COUNT
+            );
+            assert!(!diff.is_noop());
+
+            // Nothing has been written
+            assert_eq!(pallet_xdns::Gateways::<Runtime>::iter().count(), 0);
+            assert_eq!(
+                pallet_xdns::RemoteOrderAddresses::<Runtime>::iter().count(),
+                0
+            );
+        });
+}
+
+#[test]
+fn should_unzip_complete_xdns_topology_idempotently() {
+    let topology = zip_default_topology_with_remote_addresses();
+
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            assert_ok!(XDNS::unzip_topology(
+                Origin::root(),
+                Some(topology.clone()),
+                None,
+                None
+            ));
+
+            assert_eq!(
+                pallet_xdns::Gateways::<Runtime>::iter().count(),
+                DEFAULT_GATEWAYS_IN_STORAGE_COUNT
+            );
+            assert_eq!(
+                XDNS::remote_order_addresses(*b"eth2"),
+                Some(H256::repeat_byte(1))
+            );
+            assert!(XDNS::do_diff_topology(&topology).is_noop());
+
+            // Unzipping the same topology again writes nothing
+            assert_ok!(XDNS::unzip_topology(
+                Origin::root(),
+                None,
+                Some(topology.encode()),
+                None
+            ));
+            System::assert_last_event(RuntimeEvent::XDNS(pallet_xdns::Event::XDNSTopologyUnzip(
+                topology.content_hash,
+                0,
+            )));
+        });
+}
+
+#[test]
+fn should_unzip_only_selected_xdns_topology_sections() {
+    let topology = zip_default_topology_with_remote_addresses();
+
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            let sections = TopologySections {
+                gateways: true,
+                ..TopologySections::none()
+            };
+            assert_ok!(XDNS::unzip_topology(
+                Origin::root(),
+                Some(topology.clone()),
+                None,
+                Some(sections)
+            ));
+
+            assert_eq!(
+                pallet_xdns::Gateways::<Runtime>::iter().count(),
+                DEFAULT_GATEWAYS_IN_STORAGE_COUNT
+            );
+            assert_eq!(XDNS::remote_order_addresses(*b"eth2"), None);
+            assert_eq!(XDNS::remote_bidding_addresses(*b"eth2"), None);
+        });
+}
+
+#[test]
+fn unzip_topology_weight_grows_with_the_selected_sections() {
+    let topology = zip_default_topology_with_remote_addresses();
+
+    ExtBuilder::default().build().execute_with(|| {
+        let all = XDNS::unzip_topology_weight(&Some(topology.clone()), &None, &None);
+        let gateways_only = XDNS::unzip_topology_weight(
+            &Some(topology.clone()),
+            &None,
+            &Some(TopologySections {
+                gateways: true,
+                ..TopologySections::none()
+            }),
+        );
+        let nothing_selected = XDNS::unzip_topology_weight(
+            &Some(topology.clone()),
+            &None,
+            &Some(TopologySections::none()),
+        );
+
+        assert!(nothing_selected.ref_time() < gateways_only.ref_time());
+        assert!(gateways_only.ref_time() < all.ref_time());
+        // Encoded topologies are charged by their decoded content
+        assert_eq!(
+            XDNS::unzip_topology_weight(&None, &Some(topology.encode()), &None),
+            all
+        );
+        // Undecodable input is charged the base weight only
+        assert_eq!(
+            XDNS::unzip_topology_weight(&None, &Some(vec![1, 2, 3]), &None),
+            XDNS::unzip_topology_weight(&None, &None, &None)
+        );
+        assert!(
+            XDNS::unzip_topology_weight(&None, &None, &None).ref_time() < gateways_only.ref_time()
+        );
+    });
+}
+
+#[test]
+fn unzip_topology_weight_grows_with_the_local_entries_compared_against() {
+    let topology = zip_default_topology_with_remote_addresses();
+    let gateways_only = Some(TopologySections {
+        gateways: true,
+        ..TopologySections::none()
+    });
+
+    ExtBuilder::default().build().execute_with(|| {
+        let against_empty_state =
+            XDNS::unzip_topology_weight(&Some(topology.clone()), &None, &gateways_only);
+
+        assert_ok!(XDNS::unzip_topology(
+            Origin::root(),
+            Some(topology.clone()),
+            None,
+            gateways_only.clone()
+        ));
+
+        let against_unzipped_state =
+            XDNS::unzip_topology_weight(&Some(topology.clone()), &None, &gateways_only);
+        assert!(against_empty_state.ref_time() < against_unzipped_state.ref_time());
+    });
+}
+
+#[test]
+fn should_reject_unzip_of_xdns_topology_with_invalid_content_hash() {
+    let mut topology = zip_default_topology_with_remote_addresses();
+    topology.remote_order_addresses = vec![(*b"eth2", H256::repeat_byte(9))];
+
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XDNS::unzip_topology(Origin::root(), Some(topology), None, None),
+                pallet_xdns::Error::<Runtime>::TopologyContentHashMismatch
+            );
+        });
+}
+
 #[test]
 fn should_add_a_new_xdns_record_if_it_doesnt_exist() {
     ExtBuilder::default().build().execute_with(|| {
@@ -340,6 +568,56 @@ fn should_allow_to_link_token_via_extrinsic_on_sudo_permission() {
     });
 }
 
+#[test]
+fn get_token_by_eth_address_finds_pallet_assets_tokens_by_their_precompile_address() {
+    ExtBuilder::default().build().execute_with(|| {
+        add_self_as_base_gateway();
+
+        assert_ok!(XDNS::add_new_gateway(
+            *b"test",
+            GatewayVendor::Rococo,
+            ExecutionVendor::Substrate,
+            t3rn_abi::Codec::Scale,
+            None,   // registrant
+            None,   // escrow_account
+            vec![], // allowed_side_effects
+        ));
+
+        let token_id = u32::from_le_bytes(*b"test");
+        let token_info = TokenInfo::Substrate(SubstrateToken {
+            id: 1,
+            symbol: b"test".to_vec(),
+            decimals: 1,
+        });
+        assert_ok!(XDNS::register_new_token(
+            &Origin::root(),
+            token_id,
+            token_info.clone()
+        ));
+        assert_ok!(XDNS::link_token(
+            Origin::root(),
+            *b"test",
+            token_id,
+            token_info
+        ));
+
+        let token = <XDNS as Xdns<Runtime, Balance>>::get_token_by_eth_address(
+            *b"test",
+            t3rn_primitives::threevm::get_tokens_precompile_address(token_id),
+        )
+        .unwrap();
+        assert_eq!(token.token_id, token_id);
+
+        assert_err!(
+            <XDNS as Xdns<Runtime, Balance>>::get_token_by_eth_address(
+                *b"test",
+                sp_core::H160::repeat_byte(1),
+            ),
+            pallet_xdns::pallet::Error::<Runtime>::TokenRecordNotFoundInGateway
+        );
+    });
+}
+
 #[test]
 fn should_add_a_new_xdns_and_record_and_token_if_it_doesnt_exist() {
     ExtBuilder::default().build().execute_with(|| {
@@ -1354,22 +1632,25 @@ fn on_initialize_should_update_update_verifiers_overview_no_more_often_than_each
             let last_reported_block = expected_verifier_overview_all_on[0].reported_at;
 
             System::set_block_number(last_reported_block + 1);
-            <GlobalOnInitQueues as OnHookQueues<Runtime>>::process_hourly(
+            Clock::force_run_tasks_of_cadence(
                 System::block_number(),
+                Cadence::Hours(1),
                 Weight::from_parts(u64::MAX, 0),
             );
             assert_eq!(XDNS::verifier_overview(), expected_verifier_overview_all_on);
 
             System::set_block_number(last_reported_block + 5);
-            <GlobalOnInitQueues as OnHookQueues<Runtime>>::process_hourly(
+            Clock::force_run_tasks_of_cadence(
                 System::block_number(),
+                Cadence::Hours(1),
                 Weight::from_parts(u64::MAX, 0),
             );
             assert_eq!(XDNS::verifier_overview(), expected_verifier_overview_all_on);
 
             System::set_block_number(System::block_number() + 52);
-            <GlobalOnInitQueues as OnHookQueues<Runtime>>::process_hourly(
+            Clock::force_run_tasks_of_cadence(
                 System::block_number(),
+                Cadence::Hours(1),
                 Weight::from_parts(u64::MAX, 0),
             );
 
@@ -2076,3 +2357,310 @@ fn test_storage_migration_v144_to_v145_that_kills_old_xdns_records_entry() {
             );
         });
 }
+
+fn add_gateway_with_registrant(registrant: AccountId32) {
+    assert_ok!(XDNS::add_new_gateway(
+        *b"test",
+        GatewayVendor::Rococo,
+        ExecutionVendor::Substrate,
+        t3rn_abi::Codec::Scale,
+        Some(registrant),
+        None,
+        vec![(*b"tran", Some(2))],
+    ));
+}
+
+#[test]
+fn registrant_can_update_own_gateway_without_active_xtx() {
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            add_gateway_with_registrant(ALICE);
+
+            assert_ok!(XDNS::set_gateway_escrow_account(
+                Origin::signed(ALICE),
+                *b"test",
+                Some(BOB)
+            ));
+            assert_ok!(XDNS::set_gateway_allowed_side_effects(
+                Origin::signed(ALICE),
+                *b"test",
+                vec![(*b"tass", Some(4))]
+            ));
+            assert_ok!(XDNS::enroll_new_abi_to_selected_gateway(
+                Origin::signed(ALICE),
+                *b"test",
+                *b"swap",
+                None,
+                Some(4)
+            ));
+
+            let record = XDNS::gateways(*b"test").unwrap();
+            assert_eq!(record.escrow_account, Some(BOB));
+            assert_eq!(record.allowed_side_effects.len(), 2);
+            assert!(record.allowed_side_effects.contains(&(*b"swap", Some(4))));
+            assert!(record.allowed_side_effects.contains(&(*b"tass", Some(4))));
+            assert!(XDNS::get_sfx_abi(b"test", *b"tran").is_none());
+            assert!(XDNS::scheduled_gateway_changes(
+                System::block_number()
+                    + <Runtime as pallet_xdns::Config>::GatewayChangeTimelock::get()
+            )
+            .is_empty());
+        });
+}
+
+#[test]
+fn registrant_changes_are_timelocked_while_xtx_are_active_on_the_gateway() {
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            add_gateway_with_registrant(ALICE);
+            circuit_runtime_pallets::pallet_circuit::ActiveXtxCountPerTarget::<Runtime>::insert(
+                *b"test", 1,
+            );
+            let scheduled_at = System::block_number()
+                + <Runtime as pallet_xdns::Config>::GatewayChangeTimelock::get();
+
+            assert_ok!(XDNS::set_gateway_escrow_account(
+                Origin::signed(ALICE),
+                *b"test",
+                Some(BOB)
+            ));
+            assert_eq!(XDNS::gateways(*b"test").unwrap().escrow_account, None);
+            assert_eq!(XDNS::scheduled_gateway_changes(scheduled_at).len(), 1);
+
+            assert_eq!(XDNS::pending_gateway_changes(ALICE), 1);
+
+            // The changes pending per registrant are bounded
+            let max_pending =
+                <Runtime as pallet_xdns::Config>::MaxPendingGatewayChangesPerRegistrant::get();
+            for _ in 1..max_pending {
+                assert_ok!(XDNS::set_gateway_escrow_account(
+                    Origin::signed(ALICE),
+                    *b"test",
+                    Some(BOB)
+                ));
+            }
+            assert_noop!(
+                XDNS::set_gateway_escrow_account(Origin::signed(ALICE), *b"test", Some(BOB)),
+                pallet_xdns::pallet::Error::<Runtime>::TooManyPendingGatewayChanges
+            );
+        });
+}
+
+#[test]
+fn registrant_changes_scheduled_at_a_single_block_are_bounded() {
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            add_gateway_with_registrant(ALICE);
+            circuit_runtime_pallets::pallet_circuit::ActiveXtxCountPerTarget::<Runtime>::insert(
+                *b"test", 1,
+            );
+            let scheduled_at = System::block_number()
+                + <Runtime as pallet_xdns::Config>::GatewayChangeTimelock::get();
+
+            // Other registrants fill up the block
+            let max_scheduled = <Runtime as pallet_xdns::Config>::MaxScheduledGatewayChanges::get();
+            for _ in 0..max_scheduled {
+                assert_ok!(pallet_xdns::ScheduledGatewayChanges::<Runtime>::try_append(
+                    scheduled_at,
+                    (*b"test", BOB, GatewayChange::SetEscrowAccount(None)),
+                ));
+            }
+            assert_noop!(
+                XDNS::set_gateway_escrow_account(Origin::signed(ALICE), *b"test", Some(BOB)),
+                pallet_xdns::pallet::Error::<Runtime>::TooManyScheduledGatewayChanges
+            );
+        });
+}
+
+#[test]
+fn non_registrant_cannot_update_gateway() {
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            add_gateway_with_registrant(ALICE);
+
+            assert_noop!(
+                XDNS::set_gateway_escrow_account(Origin::signed(BOB), *b"test", Some(BOB)),
+                pallet_xdns::pallet::Error::<Runtime>::NotGatewayRegistrant
+            );
+            assert_noop!(
+                XDNS::unroll_abi_of_selected_gateway(Origin::signed(BOB), *b"test", *b"tran"),
+                pallet_xdns::pallet::Error::<Runtime>::NotGatewayRegistrant
+            );
+            // Root keeps the override powers
+            assert_ok!(XDNS::set_gateway_escrow_account(
+                Origin::root(),
+                *b"test",
+                Some(BOB)
+            ));
+        });
+}
+
+#[test]
+fn scheduled_gateway_changes_are_applied_once_timelock_expires() {
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            add_gateway_with_registrant(ALICE);
+
+            assert_ok!(pallet_xdns::ScheduledGatewayChanges::<Runtime>::try_append(
+                5,
+                (*b"test", ALICE, GatewayChange::SetEscrowAccount(Some(BOB))),
+            ));
+
+            XDNS::on_initialize(4);
+            assert_eq!(XDNS::gateways(*b"test").unwrap().escrow_account, None);
+
+            XDNS::on_initialize(5);
+            assert_eq!(XDNS::gateways(*b"test").unwrap().escrow_account, Some(BOB));
+            assert!(XDNS::scheduled_gateway_changes(5).is_empty());
+        });
+}
+
+#[test]
+fn scheduled_gateway_changes_are_dropped_once_the_registrant_no_longer_owns_the_gateway() {
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            add_gateway_with_registrant(ALICE);
+            circuit_runtime_pallets::pallet_circuit::ActiveXtxCountPerTarget::<Runtime>::insert(
+                *b"test", 1,
+            );
+            let scheduled_at = System::block_number()
+                + <Runtime as pallet_xdns::Config>::GatewayChangeTimelock::get();
+
+            assert_ok!(XDNS::set_gateway_escrow_account(
+                Origin::signed(ALICE),
+                *b"test",
+                Some(ALICE)
+            ));
+            pallet_xdns::Gateways::<Runtime>::mutate(*b"test", |record| {
+                record.as_mut().unwrap().registrant = Some(BOB);
+            });
+
+            XDNS::on_initialize(scheduled_at);
+            assert_eq!(XDNS::gateways(*b"test").unwrap().escrow_account, None);
+            assert!(XDNS::scheduled_gateway_changes(scheduled_at).is_empty());
+            assert_eq!(XDNS::pending_gateway_changes(ALICE), 0);
+        });
+}
+
+#[test]
+fn registration_deposit_is_slashable_by_root_and_released_at_purge() {
+    ExtBuilder::default()
+        .with_standard_sfx_abi()
+        .build()
+        .execute_with(|| {
+            let deposit = <Runtime as pallet_xdns::Config>::GatewayRegistrationDeposit::get();
+            let _ = Balances::deposit_creating(&ALICE, deposit * 2);
+            add_gateway_with_registrant(ALICE);
+
+            assert_ok!(XDNS::reserve_registration_deposit(&ALICE, *b"test"));
+            assert_eq!(Balances::reserved_balance(&ALICE), deposit);
+            assert_eq!(XDNS::gateway_deposits(*b"test"), Some((ALICE, deposit)));
+
+            assert_noop!(
+                XDNS::slash_registration_deposit(Origin::signed(ALICE), *b"test", None),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(XDNS::slash_registration_deposit(
+                Origin::root(),
+                *b"test",
+                Some(deposit / 2)
+            ));
+            assert_eq!(Balances::reserved_balance(&ALICE), deposit - deposit / 2);
+            assert_eq!(
+                Balances::free_balance(&Runtime::get_treasury_account(TreasuryAccount::Slash)),
+                deposit / 2
+            );
+
+            assert_ok!(XDNS::purge_gateway_record(Origin::root(), ALICE, *b"test"));
+            assert_eq!(Balances::reserved_balance(&ALICE), 0);
+            assert_eq!(XDNS::gateway_deposits(*b"test"), None);
+        });
+}
+
+#[test]
+fn native_fees_are_converted_into_registered_assets_by_governance_set_fee_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        add_self_as_base_gateway();
+        let asset_id = u32::from_le_bytes(*b"fees");
+        let self_gateway_id: [u8; 4] = <Runtime as pallet_xdns::Config>::SelfGatewayId::get();
+        let self_token_id: u32 = <Runtime as pallet_xdns::Config>::SelfTokenId::get();
+
+        assert_noop!(
+            XDNS::convert_native_fee_to_asset(asset_id, 1_000),
+            pallet_xdns::Error::<Runtime>::AssetNotAcceptedForFees
+        );
+        assert_noop!(
+            XDNS::set_asset_fee_price(Origin::root(), asset_id, Some(2)),
+            pallet_xdns::Error::<Runtime>::AssetNotAcceptedForFees
+        );
+
+        assert_ok!(XDNS::register_new_token(
+            &Origin::root(),
+            asset_id,
+            TokenInfo::Substrate(SubstrateToken {
+                id: 1,
+                symbol: b"fees".to_vec(),
+                decimals: 12,
+            })
+        ));
+        assert_noop!(
+            XDNS::convert_native_fee_to_asset(asset_id, 1_000),
+            pallet_xdns::Error::<Runtime>::AssetFeePriceNotFound
+        );
+
+        // Estimates of the self gateway aren't quoted in the fee price unit, so they don't price the fees
+        pallet_xdns::PerTargetAssetEstimates::<Runtime>::insert(
+            self_gateway_id,
+            (self_token_id, asset_id),
+            3 * ASSET_FEE_PRICE_UNIT,
+        );
+        assert_noop!(
+            XDNS::convert_native_fee_to_asset(asset_id, 1_000),
+            pallet_xdns::Error::<Runtime>::AssetFeePriceNotFound
+        );
+
+        assert_noop!(
+            XDNS::set_asset_fee_price(Origin::signed(ALICE), asset_id, Some(2)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(XDNS::set_asset_fee_price(
+            Origin::root(),
+            asset_id,
+            Some(3 * ASSET_FEE_PRICE_UNIT)
+        ));
+        assert_eq!(
+            XDNS::convert_native_fee_to_asset(asset_id, 1_000),
+            Ok(3_000)
+        );
+        assert_eq!(
+            pallet_xdns::fees::XdnsAssetFeeConversion::<Runtime>::to_asset_balance(1, asset_id),
+            Ok(3)
+        );
+        // Rounded up
+        assert_ok!(XDNS::set_asset_fee_price(
+            Origin::root(),
+            asset_id,
+            Some(ASSET_FEE_PRICE_UNIT / 2)
+        ));
+        assert_eq!(XDNS::convert_native_fee_to_asset(asset_id, 1_001), Ok(501));
+
+        assert_ok!(XDNS::set_asset_fee_price(Origin::root(), asset_id, None));
+        assert_eq!(XDNS::asset_fee_prices(asset_id), None);
+        assert_noop!(
+            XDNS::convert_native_fee_to_asset(asset_id, 1_000),
+            pallet_xdns::Error::<Runtime>::AssetFeePriceNotFound
+        );
+    });
+}
@@ -41,7 +41,13 @@ pub trait WeightInfo {
     fn update_ttl() -> Weight;
     fn purge_gateway() -> Weight;
     fn reboot_self_gateway() -> Weight;
+    fn set_asset_fee_price() -> Weight;
     fn best_available() -> Weight;
+    fn unzip_topology_gateways(n: u32, l: u32) -> Weight;
+    fn unzip_topology_assets(n: u32, l: u32) -> Weight;
+    fn unzip_topology_sfx_abis(n: u32, l: u32) -> Weight;
+    fn unzip_topology_addresses(n: u32, l: u32) -> Weight;
+    fn unzip_topology_epoch_history(n: u32, l: u32) -> Weight;
 }
 
 /// Weights for pallet_xdns using the Substrate node and recommended hardware.
@@ -74,6 +80,67 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(2_u64))
             .saturating_add(T::DbWeight::get().writes(1_u64))
     }
+
+    // Storage access follows the set_asset_fee_price benchmark, checking the asset against AllTokenIds.
+    // Execution time is an estimate until it's re-run on reference hardware.
+    fn set_asset_fee_price() -> Weight {
+        Weight::from_parts(18_000_000_u64, 0u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // The unzip_topology_* weights follow the storage access of the matching benchmarks in
+    // benchmarking.rs, with n snapshot entries compared against l local ones. Execution times
+    // are estimates until they're re-run on reference hardware.
+    fn unzip_topology_gateways(n: u32, l: u32) -> Weight {
+        Weight::from_parts(20_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(95_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(5_000_000_u64, 0u64).saturating_mul(l.into()))
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().reads(1_u64).saturating_mul(l.into()))
+            .saturating_add(T::DbWeight::get().reads(12_u64).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().writes(12_u64).saturating_mul(n.into()))
+    }
+
+    fn unzip_topology_assets(n: u32, l: u32) -> Weight {
+        Weight::from_parts(20_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(60_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(3_000_000_u64, 0u64).saturating_mul(l.into()))
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().reads(1_u64).saturating_mul(l.into()))
+            .saturating_add(T::DbWeight::get().reads(6_u64).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().writes(5_u64).saturating_mul(n.into()))
+    }
+
+    fn unzip_topology_sfx_abis(n: u32, l: u32) -> Weight {
+        Weight::from_parts(15_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(12_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(4_000_000_u64, 0u64).saturating_mul(l.into()))
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().reads(1_u64).saturating_mul(l.into()))
+            .saturating_add(T::DbWeight::get().reads(2_u64).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().writes(1_u64).saturating_mul(n.into()))
+    }
+
+    fn unzip_topology_addresses(n: u32, l: u32) -> Weight {
+        Weight::from_parts(15_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(8_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(1_000_000_u64, 0u64).saturating_mul(l.into()))
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().reads(1_u64).saturating_mul(l.into()))
+            .saturating_add(T::DbWeight::get().reads(1_u64).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().writes(1_u64).saturating_mul(n.into()))
+    }
+
+    fn unzip_topology_epoch_history(n: u32, l: u32) -> Weight {
+        Weight::from_parts(15_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(3_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(1_000_000_u64, 0u64).saturating_mul(l.into()))
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().reads(1_u64).saturating_mul(l.into()))
+            .saturating_add(T::DbWeight::get().reads(1_u64).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().writes(1_u64).saturating_mul(n.into()))
+    }
 }
 
 // For backwards compatibility and tests
@@ -105,4 +172,63 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(2_u64))
             .saturating_add(RocksDbWeight::get().writes(1_u64))
     }
+
+    fn set_asset_fee_price() -> Weight {
+        Weight::from_parts(18_000_000_u64, 0u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    // The unzip_topology_* weights follow the storage access of the matching benchmarks in
+    // benchmarking.rs, with n snapshot entries compared against l local ones. Execution times
+    // are estimates until they're re-run on reference hardware.
+    fn unzip_topology_gateways(n: u32, l: u32) -> Weight {
+        Weight::from_parts(20_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(95_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(5_000_000_u64, 0u64).saturating_mul(l.into()))
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().reads(1_u64).saturating_mul(l.into()))
+            .saturating_add(RocksDbWeight::get().reads(12_u64).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().writes(12_u64).saturating_mul(n.into()))
+    }
+
+    fn unzip_topology_assets(n: u32, l: u32) -> Weight {
+        Weight::from_parts(20_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(60_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(3_000_000_u64, 0u64).saturating_mul(l.into()))
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().reads(1_u64).saturating_mul(l.into()))
+            .saturating_add(RocksDbWeight::get().reads(6_u64).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().writes(5_u64).saturating_mul(n.into()))
+    }
+
+    fn unzip_topology_sfx_abis(n: u32, l: u32) -> Weight {
+        Weight::from_parts(15_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(12_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(4_000_000_u64, 0u64).saturating_mul(l.into()))
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().reads(1_u64).saturating_mul(l.into()))
+            .saturating_add(RocksDbWeight::get().reads(2_u64).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().writes(1_u64).saturating_mul(n.into()))
+    }
+
+    fn unzip_topology_addresses(n: u32, l: u32) -> Weight {
+        Weight::from_parts(15_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(8_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(1_000_000_u64, 0u64).saturating_mul(l.into()))
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().reads(1_u64).saturating_mul(l.into()))
+            .saturating_add(RocksDbWeight::get().reads(1_u64).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().writes(1_u64).saturating_mul(n.into()))
+    }
+
+    fn unzip_topology_epoch_history(n: u32, l: u32) -> Weight {
+        Weight::from_parts(15_000_000_u64, 0u64)
+            .saturating_add(Weight::from_parts(3_000_000_u64, 0u64).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(1_000_000_u64, 0u64).saturating_mul(l.into()))
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().reads(1_u64).saturating_mul(l.into()))
+            .saturating_add(RocksDbWeight::get().reads(1_u64).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().writes(1_u64).saturating_mul(n.into()))
+    }
 }
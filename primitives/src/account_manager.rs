@@ -4,6 +4,7 @@ use frame_support::dispatch::DispatchResult;
 use scale_info::TypeInfo;
 use sp_runtime::DispatchError;
 use sp_std::{fmt::Debug, prelude::*};
+use t3rn_types::sfx::TargetId;
 
 pub type ExecutionId = u64;
 
@@ -31,6 +32,22 @@ pub struct RequestCharge<Account, Balance, AssetId> {
     pub role: CircuitRole,
 }
 
+/// Liquidity of the insurance pool of an asset, owned by its providers pro rata to their shares
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Default, TypeInfo)]
+pub struct InsurancePool<Balance> {
+    pub liquidity: Balance,
+    pub total_shares: Balance,
+}
+
+/// Shares of an insurance provider on their withdrawal notice, still exposed to the top-ups paid out of the pool.
+/// The amount is the value of the shares when the withdrawal was requested, so the yield earned on notice stays in the pool.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct PendingInsuranceWithdrawal<Balance, BlockNumber> {
+    pub shares: Balance,
+    pub amount: Balance,
+    pub unlock_at: BlockNumber,
+}
+
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
 pub enum Outcome {
     UnexpectedFailure,
@@ -39,6 +56,40 @@ pub enum Outcome {
     Slash,
 }
 
+/// Sponsor of the transaction fees of executors' calls, e.g. the requester of the Xtx the executor serves.
+pub trait ExecutorFeeSponsor<Account, Balance, Hash, Call> {
+    /// Account willing to pay the fee of the executor's call instead, with the sponsorship whose budget covers it.
+    fn sponsor_of(executor: &Account, call: &Call, fee: Balance) -> Option<(Account, Hash)>;
+    /// Take the fee withdrawn from the sponsor ahead of the executor's call off the sponsorship's budget.
+    fn charge_sponsor(executor: &Account, call: &Call, sponsorship: Hash, fee: Balance);
+    /// Return the part of the fee refunded to the sponsor after the executor's call to the sponsorship's budget,
+    ///     settling the sponsorship at the actual fee.
+    fn refund_sponsor(
+        executor: &Account,
+        sponsor: &Account,
+        sponsorship: Hash,
+        refund: Balance,
+        actual_fee: Balance,
+    );
+}
+
+impl<Account, Balance, Hash, Call> ExecutorFeeSponsor<Account, Balance, Hash, Call> for () {
+    fn sponsor_of(_executor: &Account, _call: &Call, _fee: Balance) -> Option<(Account, Hash)> {
+        None
+    }
+
+    fn charge_sponsor(_executor: &Account, _call: &Call, _sponsorship: Hash, _fee: Balance) {}
+
+    fn refund_sponsor(
+        _executor: &Account,
+        _sponsor: &Account,
+        _sponsorship: Hash,
+        _refund: Balance,
+        _actual_fee: Balance,
+    ) {
+    }
+}
+
 pub trait AccountManager<Account, Balance, Hash, BlockNumber, AssetId> {
     /// Lookup charge by Id and fail if not found
     fn get_charge_or_fail(
@@ -75,10 +126,20 @@ pub trait AccountManager<Account, Balance, Hash, BlockNumber, AssetId> {
     ) -> DispatchResult;
     /// Assert infallible finalize of a transaction if exists
     fn finalize_infallible(charge_id: Hash, outcome: Outcome) -> bool;
+    /// Pay the claimed amount out of the deposit to a beneficiary and slash the rest of it to the
+    /// Escrow account, returning the amount paid
+    fn slash_deposit_to(
+        charge_id: Hash,
+        beneficiary: &Account,
+        claimed: Balance,
+    ) -> Result<Balance, DispatchError>;
 
     fn cancel_deposit(charge_id: Hash) -> bool;
 
     fn assign_deposit(charge_id: Hash, recipient: &Account) -> bool;
+    /// Withhold part of the offered reward of a pending charge from its recipient, leaving the
+    /// withheld amount out of circulation
+    fn withhold_from_deposit(charge_id: Hash, amount: Balance) -> DispatchResult;
 
     fn transfer_deposit(
         charge_id: Hash,
@@ -97,4 +158,12 @@ pub trait AccountManager<Account, Balance, Hash, BlockNumber, AssetId> {
         amount: Balance,
         asset_id: Option<AssetId>,
     ) -> DispatchResult;
+    /// Top up the compensation of a requester out of the insurance pool, within the caps of the target and the asset.
+    /// Returns the amount paid out, which is zero when no caps are set.
+    fn compensate_from_insurance_pool(
+        beneficiary: &Account,
+        target: TargetId,
+        shortfall: Balance,
+        asset_id: Option<AssetId>,
+    ) -> Balance;
 }
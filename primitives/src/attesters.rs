@@ -1,11 +1,11 @@
-use crate::GatewayVendor;
+use crate::{ExecutionVendor, GatewayVendor};
 
 use frame_support::pallet_prelude::*;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_application_crypto::{ed25519, sr25519, KeyTypeId, RuntimePublic};
 use sp_core::{H160, H256, H512};
-use sp_runtime::{traits::Zero, Percent};
+use sp_runtime::{traits::Zero, Perbill, Percent};
 use sp_std::prelude::*;
 use t3rn_types::sfx::TargetId;
 
@@ -319,7 +319,6 @@ pub fn verify_secp256k1_ecdsa_signature(
 
 pub type Signature65b = [u8; 65];
 pub type PublicKeyEcdsa33b = [u8; 33];
-pub const COMMITTEE_SIZE: usize = 32;
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, Default)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -330,9 +329,69 @@ pub enum LatencyStatus {
     Late(u32, u32),
 }
 
-pub type CommitteeTransitionIndices = [u32; COMMITTEE_SIZE];
+/// How the signatures collected for a batch count towards its quorum
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, Default)]
+pub enum QuorumKind {
+    /// Every member of the committee counts as a single approval
+    #[default]
+    HeadCount,
+    /// Every member of the committee counts with its self-bond and the nominations it received
+    StakeWeighted,
+}
+
+/// Per-target approval threshold of attestation batches
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct QuorumConfig {
+    pub kind: QuorumKind,
+    /// Share of the committee (by head count or by stake) that must sign a batch
+    pub threshold: Perbill,
+}
+
+impl Default for QuorumConfig {
+    /// Two thirds of the committee members, as expected by the EVM verifiers.
+    /// The share is rounded down, so that rounding up the quorum asks e.g. 2 out of 3 and 4 out of 6 members.
+    fn default() -> Self {
+        QuorumConfig {
+            kind: QuorumKind::HeadCount,
+            threshold: Perbill::from_parts(666_666_666),
+        }
+    }
+}
+
+/// Layout and hashing of the batch messages signed by attesters for a target
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, Default)]
+pub enum BatchMessageEncoding {
+    /// Packed 32b words hashed with keccak256 and signed with ECDSA keys, as verified by the EVM contracts
+    #[default]
+    EthAbi,
+    /// SCALE-encoded batch hashed with blake2_256 and signed with sr25519 or ed25519 keys,
+    ///     as verified by Substrate runtimes and ink! contracts
+    Scale,
+    /// EthAbi layout with each committee of a transition prefixed by the amount of its members,
+    ///     as verified by the EVM contracts accepting committees of any size
+    EthAbiV2,
+}
+
+impl BatchMessageEncoding {
+    /// Select the encoding native to the execution layer of the target, as registered in XDNS
+    pub fn select(execution_vendor: &ExecutionVendor, codec: &Codec) -> Self {
+        match (execution_vendor, codec) {
+            (ExecutionVendor::Substrate, Codec::Scale) => BatchMessageEncoding::Scale,
+            _ => BatchMessageEncoding::EthAbi,
+        }
+    }
+
+    pub fn execution_vendor(&self) -> ExecutionVendor {
+        match self {
+            BatchMessageEncoding::EthAbi | BatchMessageEncoding::EthAbiV2 => ExecutionVendor::EVM,
+            BatchMessageEncoding::Scale => ExecutionVendor::Substrate,
+        }
+    }
+}
+
+pub type CommitteeTransitionIndices = Vec<u32>;
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, Default)]
-pub struct GenericCommitteeTransition([(u32, Vec<u8>); COMMITTEE_SIZE]);
+pub struct GenericCommitteeTransition(Vec<(u32, Vec<u8>)>);
 
 pub enum GMPMessage {
     /// GMP message to be sent to the attester
@@ -342,7 +401,7 @@ pub enum GMPMessage {
 }
 pub type GMPPayload = Vec<u8>;
 
-pub type EvmCommitteeTransition = [(u32, H160); COMMITTEE_SIZE];
+pub type EvmCommitteeTransition = Vec<(u32, H160)>;
 pub type CommitteeTransition = Vec<(u32, Vec<u8>)>;
 pub type CommitteeRecoverable = Vec<Vec<u8>>;
 pub type CommitteeTransitionEncoded = Vec<u8>;
@@ -350,6 +409,7 @@ pub type CommitteeTransitionEncoded = Vec<u8>;
 pub type AttestersChange = Vec<([u8; 33], u32)>;
 pub type BatchConfirmedSfxWithGMPPayload = Vec<H512>;
 pub type BatchRevertedSfxId = Vec<H256>;
+pub type BatchGmpMessageId = Vec<H256>;
 
 pub trait AttestersWriteApi<Account, Error> {
     fn request_sfx_attestation_commit(
@@ -358,10 +418,22 @@ pub trait AttestersWriteApi<Account, Error> {
         maybe_gmp_payload: Option<H256>,
     ) -> Result<(), Error>;
     fn request_sfx_attestation_revert(target: TargetId, sfx_id: H256) -> Result<(), Error>;
+    fn request_gmp_message_attestation(target: TargetId, message_id: H256) -> Result<(), Error>;
     fn request_ban_attesters_attestation(ban_attesters: &Account) -> Result<(), Error>;
     fn request_next_committee_attestation() -> Vec<(TargetId, u32)>;
 }
 
+pub trait AttestersStakingApi<Account, Balance, Error> {
+    // Nominate the attester with nominator's free balance, e.g. when restaking claimed rewards
+    fn nominate_on_behalf(
+        nominator: &Account,
+        attester: &Account,
+        amount: Balance,
+    ) -> Result<(), Error>;
+    // Start summing the nominations snapshotted at each round over a new reward distribution period
+    fn start_nominations_snapshot_period() -> Weight;
+}
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, Default)]
 pub struct BatchingFactor {
     pub latest_confirmed: u16,
@@ -377,6 +449,8 @@ pub trait AttestersReadApi<Account, Balance, BlockNumber> {
     fn honest_active_set() -> Vec<Account>;
     fn read_attester_info(attester: &Account) -> Option<AttesterInfo>;
     fn read_nominations(for_attester: &Account) -> Vec<(Account, Balance)>;
+    // Nominations of the attester summed over the rounds of the current reward distribution period, used to split rewards
+    fn read_nominations_snapshot(for_attester: &Account) -> Vec<(Account, Balance)>;
     fn get_activated_targets() -> Vec<TargetId>;
     fn read_attestation_latency(target: &TargetId) -> Option<LatencyStatus>;
     // Estimate finality fee for user including set overcharge factor (32%)
@@ -418,6 +492,10 @@ impl<Account, Balance: Zero, Error, BlockNumber> AttestersReadApi<Account, Balan
         vec![]
     }
 
+    fn read_nominations_snapshot(_for_attester: &Account) -> Vec<(Account, Balance)> {
+        vec![]
+    }
+
     fn get_activated_targets() -> Vec<TargetId> {
         vec![]
     }
@@ -466,6 +544,10 @@ impl<Account, Balance, Error> AttestersWriteApi<Account, Error>
         Ok(())
     }
 
+    fn request_gmp_message_attestation(_target: TargetId, _message_id: H256) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn request_ban_attesters_attestation(_ban_attesters: &Account) -> Result<(), Error> {
         Ok(())
     }
@@ -475,6 +557,22 @@ impl<Account, Balance, Error> AttestersWriteApi<Account, Error>
     }
 }
 
+impl<Account, Balance, Error> AttestersStakingApi<Account, Balance, Error>
+    for AttestersReadApiEmptyMock<Account, Balance, Error>
+{
+    fn nominate_on_behalf(
+        _nominator: &Account,
+        _attester: &Account,
+        _amount: Balance,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn start_nominations_snapshot_period() -> Weight {
+        Weight::zero()
+    }
+}
+
 #[cfg(test)]
 pub mod test {
     use super::{AttestersReadApi, AttestersReadApiEmptyMock, AttestersWriteApi};
@@ -573,6 +671,15 @@ pub mod test {
             vec![]
         );
 
+        assert_eq!(
+            <AttestersReadApiEmptyMock<AccountId32, u128, DispatchError> as AttestersReadApi<
+                AccountId32,
+                u128,
+                u32,
+            >>::read_nominations_snapshot(&AccountId32::new([0; 32])),
+            vec![]
+        );
+
         assert_eq!(
             <AttestersReadApiEmptyMock<AccountId32, u128, DispatchError> as AttestersReadApi<
                 AccountId32,
@@ -17,7 +17,7 @@ use crate::{circuit::AdaptiveTimeout, light_client::InclusionReceipt};
 use t3rn_sdk_primitives::signal::ExecutionSignal;
 use t3rn_types::{
     fsx::FullSideEffect,
-    sfx::{HardenedSideEffect, SecurityLvl, SideEffect, SideEffectId},
+    sfx::{ConfirmedSideEffect, HardenedSideEffect, SecurityLvl, SideEffect, SideEffectId},
 };
 
 #[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
@@ -107,6 +107,15 @@ pub trait CircuitSubmitAPI<T: ConfigSystem, Balance> {
         amount: Balance,
     ) -> DispatchResultWithPostInfo;
 
+    fn confirm_side_effect(
+        origin: OriginFor<T>,
+        sfx_id: SideEffectId<T>,
+        confirmation: ConfirmedSideEffect<T::AccountId, BlockNumberFor<T>, Balance>,
+    ) -> DispatchResultWithPostInfo;
+
+    /// Releases the bond of the executor of a confirmed side effect ahead of its Xtx finalization.
+    fn release_executor_bond(sfx_id: SideEffectId<T>, executor: &T::AccountId) -> DispatchResult;
+
     fn store_gmp_payload(id: H256, payload: H256) -> bool;
 
     fn get_gmp_payload(id: H256) -> Option<H256>;
@@ -120,11 +129,17 @@ pub trait CircuitSubmitAPI<T: ConfigSystem, Balance> {
 }
 
 pub trait CircuitDLQ<T: ConfigSystem> {
-    fn process_dlq(n: frame_system::pallet_prelude::BlockNumberFor<T>) -> Weight;
+    fn process_dlq(
+        n: frame_system::pallet_prelude::BlockNumberFor<T>,
+        max_allowed_weight: Weight,
+    ) -> Weight;
     fn process_adaptive_xtx_timeout_queue(
         n: frame_system::pallet_prelude::BlockNumberFor<T>,
         verifier: &GatewayVendor,
+        max_allowed_weight: Weight,
     ) -> Weight;
+    /// True if any Xtx pending execution contains a side effect towards the target.
+    fn has_active_xtx_on_target(target: &TargetId) -> bool;
 }
 
 pub trait OnLocalTrigger<T: ConfigSystem, Balance> {
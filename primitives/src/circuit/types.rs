@@ -9,7 +9,7 @@ use frame_system::{pallet_prelude::BlockNumberFor, Config};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_core::{crypto::AccountId32, hexdisplay::AsBytesRef, Hasher, H160, U256};
+use sp_core::{crypto::AccountId32, hexdisplay::AsBytesRef, Hasher, H160, H256, U256};
 #[cfg(feature = "no_std")]
 use sp_runtime::RuntimeDebug as Debug;
 use sp_runtime::{traits::Zero, RuntimeDebug};
@@ -151,6 +151,34 @@ impl VacuumEVMTeleportOrder {
     }
 }
 
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct VacuumEVMConfirmation {
+    pub sfx_id: H256,
+
+    pub confirmation_proof: Vec<u8>,
+}
+
+impl VacuumEVMConfirmation {
+    pub fn new(sfx_id: H256, confirmation_proof: Vec<u8>) -> Self {
+        VacuumEVMConfirmation {
+            sfx_id,
+            confirmation_proof,
+        }
+    }
+
+    /// Decodes the packed 32 bytes of the SFX id followed by the inclusion proof of its execution.
+    pub fn from_rlp(encoded_slice: &[u8]) -> Result<Self, DispatchError> {
+        if encoded_slice.len() < 32 {
+            return Err(DispatchError::Other("Invalid encoded slice"))
+        }
+
+        Ok(VacuumEVMConfirmation {
+            sfx_id: H256::from_slice(&encoded_slice[0..32]),
+            confirmation_proof: encoded_slice[32..].to_vec(),
+        })
+    }
+}
+
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
 pub struct VacuumEVM3DOrder {
     pub destination: TargetId,
@@ -953,7 +981,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{OrderSFX, SFXAction};
+    use super::{OrderSFX, SFXAction, VacuumEVMConfirmation};
     use crate::{
         circuit::{AdaptiveTimeout, XExecSignal},
         SpeedMode,
@@ -1187,4 +1215,16 @@ mod tests {
         assert_eq!(side_effect.action, *b"cevm");
         assert_eq!(side_effect.reward_asset_id, Some(1u32));
     }
+
+    #[test]
+    fn vacuum_evm_confirmation_decodes_sfx_id_followed_by_proof() {
+        let sfx_id = sp_core::H256::repeat_byte(7);
+        let encoded = [sfx_id.as_bytes(), &[1u8, 2, 3][..]].concat();
+
+        assert_eq!(
+            VacuumEVMConfirmation::from_rlp(&encoded),
+            Ok(VacuumEVMConfirmation::new(sfx_id, vec![1u8, 2, 3]))
+        );
+        assert!(VacuumEVMConfirmation::from_rlp(&encoded[..31]).is_err());
+    }
 }
@@ -1,7 +1,12 @@
 use crate::common::RoundInfo;
+use codec::{Decode, Encode};
 use frame_support::{pallet_prelude::Weight, sp_runtime::traits::Zero};
 use frame_system::pallet_prelude::BlockNumberFor;
-use sp_std::marker::PhantomData;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{Percent, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
 
 pub trait Clock<T: frame_system::Config> {
     fn current_round() -> RoundInfo<BlockNumberFor<T>>;
@@ -23,16 +28,8 @@ impl<T: frame_system::Config> Clock<T> for ClockMock<T> {
 }
 
 pub trait OnHookQueues<T: frame_system::Config> {
-    // Process the queues for the given block number, handle the intervals internally.
+    // Process the queues for the given block number, within the given weight limit.
     fn process(n: BlockNumberFor<T>, hook_weight_limit: Weight) -> Weight;
-    // Process the queues once per week.
-    fn process_weekly(n: BlockNumberFor<T>, hook_weight_limit: Weight) -> Weight;
-    // Process the queues once per 2 weeks.
-    fn process_bi_weekly(n: BlockNumberFor<T>, hook_weight_limit: Weight) -> Weight;
-    // Process the queues once per day.
-    fn process_daily(n: BlockNumberFor<T>, hook_weight_limit: Weight) -> Weight;
-    // Process the queues once per hour.
-    fn process_hourly(n: BlockNumberFor<T>, hook_weight_limit: Weight) -> Weight;
 }
 
 pub struct EmptyOnHookQueues<T> {
@@ -43,20 +40,63 @@ impl<T: frame_system::Config> OnHookQueues<T> for EmptyOnHookQueues<T> {
     fn process(_n: BlockNumberFor<T>, _hook_weight_limit: Weight) -> Weight {
         Zero::zero()
     }
+}
 
-    fn process_weekly(_n: BlockNumberFor<T>, _hook_weight_limit: Weight) -> Weight {
-        Zero::zero()
-    }
+/// How often a periodic task is due. Hours and days are resolved to blocks by the clock.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Cadence {
+    EveryBlock,
+    Blocks(u32),
+    Hours(u32),
+    Days(u32),
+}
 
-    fn process_bi_weekly(_n: BlockNumberFor<T>, _hook_weight_limit: Weight) -> Weight {
-        Zero::zero()
-    }
+/// A named piece of periodic work registered by a pallet with the clock.
+pub struct PeriodicTask<T: frame_system::Config> {
+    /// Unique name of the task, e.g. `b"circuit::xtx_tick"`, also keying its stats
+    pub name: &'static [u8],
+    pub cadence: Cadence,
+    /// Share of the on_initialize weight budget granted to each run
+    pub share: Percent,
+    /// Due tasks run in ascending priority, the ones registered first go first on ties
+    pub priority: u8,
+    /// Runs the task at the given block within the weight limit, returning the weight used
+    pub run: fn(BlockNumberFor<T>, Weight) -> Weight,
+}
 
-    fn process_daily(_n: BlockNumberFor<T>, _hook_weight_limit: Weight) -> Weight {
-        Zero::zero()
-    }
+pub trait PeriodicTasks<T: frame_system::Config> {
+    fn periodic_tasks() -> Vec<PeriodicTask<T>>;
+}
 
-    fn process_hourly(_n: BlockNumberFor<T>, _hook_weight_limit: Weight) -> Weight {
-        Zero::zero()
+#[impl_trait_for_tuples::impl_for_tuples(16)]
+impl<T: frame_system::Config> PeriodicTasks<T> for Tuple {
+    fn periodic_tasks() -> Vec<PeriodicTask<T>> {
+        let mut tasks = Vec::new();
+        for_tuples!( #( tasks.extend(Tuple::periodic_tasks()); )* );
+        tasks
     }
 }
+
+/// Runtime statistics of a periodic task.
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TaskStats<BlockNumber> {
+    pub last_run: Option<BlockNumber>,
+    /// Weight used by the last run
+    pub weight_used: Weight,
+    pub total_runs: u32,
+    /// Due runs deferred for lack of weight since the last run
+    pub backlog: u32,
+}
+
+/// Registration and statistics of a periodic task as exposed over RPC.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TaskInfo<BlockNumber> {
+    pub name: Vec<u8>,
+    pub cadence: Cadence,
+    pub share: Percent,
+    pub priority: u8,
+    pub stats: TaskStats<BlockNumber>,
+}
@@ -176,6 +176,8 @@ pub struct ContractMetadata {
     repository: Option<Vec<u8>>,
     homepage: Option<Vec<u8>>,
     license: Option<Vec<u8>>,
+    /// Hash of the contract source, as declared by the `Source` section of the metadata
+    source_hash: Option<[u8; 32]>,
 }
 
 impl Default for ContractMetadata {
@@ -191,6 +193,7 @@ impl Default for ContractMetadata {
             repository: None,
             homepage: None,
             license: None,
+            source_hash: None,
         }
     }
 }
@@ -219,6 +222,7 @@ impl ContractMetadata {
             repository,
             homepage,
             license,
+            source_hash: None,
         }
     }
 
@@ -234,6 +238,7 @@ impl ContractMetadata {
             repository: None,
             homepage: None,
             license: None,
+            source_hash: None,
         }
     }
 
@@ -245,4 +250,46 @@ impl ContractMetadata {
         self.contract_type = kind;
         self
     }
+
+    pub fn get_source_hash(&self) -> Option<&[u8; 32]> {
+        self.source_hash.as_ref()
+    }
+
+    pub fn with_source(mut self, source: &Source) -> Self {
+        self.source_hash = Some(source.hash);
+        self
+    }
+}
+
+/// Contract metadata prior to declaring the source hash.
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+pub struct ContractMetadataV1 {
+    metadata_version: Vec<u8>,
+    name: Vec<u8>,
+    contract_type: ContractType,
+    version: Vec<u8>,
+    authors: Vec<Vec<u8>>,
+    description: Option<Vec<u8>>,
+    documentation: Option<Vec<u8>>,
+    repository: Option<Vec<u8>>,
+    homepage: Option<Vec<u8>>,
+    license: Option<Vec<u8>>,
+}
+
+impl ContractMetadataV1 {
+    pub fn into_metadata(self) -> ContractMetadata {
+        ContractMetadata {
+            metadata_version: self.metadata_version,
+            name: self.name,
+            contract_type: self.contract_type,
+            version: self.version,
+            authors: self.authors,
+            description: self.description,
+            documentation: self.documentation,
+            repository: self.repository,
+            homepage: self.homepage,
+            license: self.license,
+            source_hash: None,
+        }
+    }
 }
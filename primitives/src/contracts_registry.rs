@@ -1,5 +1,5 @@
 use crate::{
-    contract_metadata::{ContractMetadata, ContractType},
+    contract_metadata::{ContractMetadata, ContractMetadataV1, ContractType},
     gateway::ContractActionDesc,
     storage::RawAliveContractInfo,
     transfers::CurrencyBalanceOf,
@@ -9,7 +9,10 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::traits::Currency;
 use frame_system::pallet_prelude::BlockNumberFor;
 use scale_info::TypeInfo;
-use sp_runtime::{traits::Hash, RuntimeDebug};
+use sp_runtime::{
+    traits::{Hash, Saturating, Zero},
+    Percent, RuntimeDebug,
+};
 
 use crate::Vec;
 
@@ -31,10 +34,151 @@ where
     fn fetch_contracts(
         author: Option<T::AccountId>,
         metadata: Option<Vec<u8>>,
+        verified_only: bool,
     ) -> Result<
         Vec<RegistryContract<T::Hash, T::AccountId, CurrencyBalanceOf<T, C>, BlockNumberFor<T>>>,
         Self::Error,
     >;
+
+    /// Resolves the latest non-deprecated release of the package compatible with the requirement
+    fn fetch_latest_compatible(
+        package: Vec<u8>,
+        requirement: SemanticVersion,
+    ) -> Result<
+        (
+            T::Hash,
+            RegistryContract<T::Hash, T::AccountId, CurrencyBalanceOf<T, C>, BlockNumberFor<T>>,
+        ),
+        Self::Error,
+    >;
+
+    fn is_deprecated(contract_id: T::Hash) -> bool;
+
+    /// Royalty configured by the author of the registry contract, if any
+    fn royalty_of(contract_id: T::Hash) -> Option<RoyaltyConfig<T::AccountId>>;
+
+    /// Counts a use of the registry contract and what it earned its payees, in the given asset
+    fn record_usage(contract_id: T::Hash, earned: CurrencyBalanceOf<T, C>, asset_id: Option<u32>);
+}
+
+/// Pays royalties to the authors of registry contracts out of the rewards of the side effects
+/// requested by their instances.
+pub trait Royalties<AccountId, Balance, Hash> {
+    /// Takes the royalty out of the SFX reward held under `charge_id`, if the `requester` is an
+    /// instance of a registry contract with a royalty, and accrues it to the payees. Returns the
+    /// royalty taken.
+    fn accrue_royalty(
+        requester: &AccountId,
+        charge_id: Hash,
+        sfx_reward: Balance,
+        asset_id: Option<u32>,
+    ) -> Balance;
+}
+
+impl<AccountId, Balance: Zero, Hash> Royalties<AccountId, Balance, Hash> for () {
+    fn accrue_royalty(
+        _requester: &AccountId,
+        _charge_id: Hash,
+        _sfx_reward: Balance,
+        _asset_id: Option<u32>,
+    ) -> Balance {
+        Zero::zero()
+    }
+}
+
+/// Semantic version of a contract released under a registry package
+#[derive(
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Default,
+    Encode,
+    Decode,
+    MaxEncodedLen,
+    RuntimeDebug,
+    TypeInfo,
+)]
+pub struct SemanticVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl SemanticVersion {
+    pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Caret compatibility: same major and not older than the requirement.
+    /// Until 1.0.0 is released every minor bump is considered breaking.
+    pub fn is_compatible_with(&self, requirement: &SemanticVersion) -> bool {
+        if self.major != requirement.major {
+            return false
+        }
+        if self.major == 0 && self.minor != requirement.minor {
+            return false
+        }
+        self >= requirement
+    }
+}
+
+/// Release of a registry contract - links the contract into its package version lineage
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ContractRelease<Hash, AccountId, BalanceOf> {
+    /// Stable package name the contract is released under
+    pub package: Vec<u8>,
+    pub version: SemanticVersion,
+    /// Account that published the release and holds its storage deposit
+    pub publisher: AccountId,
+    pub deposit: BalanceOf,
+    /// Closest older release of the same package at the time of publishing
+    pub previous: Option<Hash>,
+    /// Deprecated releases can no longer be instantiated by 3VM
+    pub deprecated: bool,
+}
+
+/// Attestation posted by an off-chain verifier after reproducing the contract build from its source
+/// with a pinned compiler.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SourceAttestation {
+    /// Hash of the source code the build was reproduced from - must match the registry code_txt
+    pub source_hash: [u8; 32],
+    /// Hash of the reproduced bytecode - must match the registry bytes
+    pub bytecode_hash: [u8; 32],
+    /// Compiler with its pinned version used to reproduce the build, e.g. "solc 0.8.19"
+    pub compiler: Vec<u8>,
+    /// Whether the reproduced build matched the registered bytecode byte for byte
+    pub reproduced: bool,
+}
+
+impl SourceAttestation {
+    pub fn hash_code(code: &[u8]) -> [u8; 32] {
+        sp_io::hashing::blake2_256(code)
+    }
+}
+
+/// Outcome of the source verification of a registry contract
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SourceVerification<AccountId, BlockNumber> {
+    pub verifier: AccountId,
+    pub compiler: Vec<u8>,
+    pub verified_at: BlockNumber,
+    pub reproduced: bool,
+}
+
+/// Package grouping all released versions of a contract under a stable name
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ContractPackage<Hash, AccountId> {
+    pub owner: AccountId,
+    /// Releases sorted by ascending version
+    pub releases: Vec<(SemanticVersion, Hash)>,
 }
 
 #[derive(Clone, Eq, PartialEq, Default, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
@@ -53,6 +197,77 @@ impl<AccountId, BalanceOf> AuthorInfo<AccountId, BalanceOf> {
         }
     }
 }
+
+/// Maximum number of payees splitting the royalty of a registry contract
+pub const MAX_ROYALTY_PAYEES: usize = 16;
+
+/// Royalty of a registry contract: a share of the reward of every side effect requested by its
+/// instances, split between the payees.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct RoyaltyConfig<AccountId> {
+    /// Share of the SFX reward taken as royalty
+    pub rate: Percent,
+    /// Payees with their share of the royalty, adding up to 100%
+    pub payees: Vec<(AccountId, Percent)>,
+}
+
+impl<AccountId: Clone> RoyaltyConfig<AccountId> {
+    pub fn is_valid(&self) -> bool {
+        !self.payees.is_empty()
+            && self.payees.len() <= MAX_ROYALTY_PAYEES
+            && self
+                .payees
+                .iter()
+                .map(|(_, share)| share.deconstruct() as u32)
+                .sum::<u32>()
+                == 100
+    }
+
+    /// Splits the royalty on `sfx_reward` between the payees.
+    /// Rounding leftovers go to the first payee, so the parts add up to the royalty.
+    pub fn split<Balance>(&self, sfx_reward: Balance) -> Vec<(AccountId, Balance)>
+    where
+        Balance: sp_runtime::traits::AtLeast32BitUnsigned + Copy,
+    {
+        let royalty = self.rate.mul_floor(sfx_reward);
+        let mut parts: Vec<(AccountId, Balance)> = self
+            .payees
+            .iter()
+            .map(|(payee, share)| (payee.clone(), share.mul_floor(royalty)))
+            .collect();
+        let distributed = parts
+            .iter()
+            .fold(Balance::zero(), |acc, (_, part)| acc.saturating_add(*part));
+        if let Some((_, first)) = parts.first_mut() {
+            *first = first.saturating_add(royalty.saturating_sub(distributed));
+        }
+        parts
+    }
+}
+
+/// Usage of a registry contract and the royalties it earned, per reward asset
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ContractUsage<Balance> {
+    /// Side effects settled for the instances of the contract
+    pub uses: u64,
+    /// Royalties earned per asset, `None` being the native asset
+    pub earnings: Vec<(Option<u32>, Balance)>,
+}
+
+impl<Balance: Saturating + Copy> ContractUsage<Balance> {
+    pub fn record(&mut self, earned: Balance, asset_id: Option<u32>) {
+        self.uses = self.uses.saturating_add(1);
+        match self
+            .earnings
+            .iter_mut()
+            .find(|(asset, _)| *asset == asset_id)
+        {
+            Some((_, total)) => *total = total.saturating_add(earned),
+            None => self.earnings.push((asset_id, earned)),
+        }
+    }
+}
+
 /// A preliminary representation of a contract in the onchain registry.
 #[derive(Clone, Eq, PartialEq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct RegistryContract<Hash, AccountId, BalanceOf, BlockNumber> {
@@ -121,6 +336,34 @@ impl<Hash: Encode, AccountId: Encode, BalanceOf: Encode, BlockNumber: Encode>
     }
 }
 
+/// Registry contract prior to declaring the source hash in its metadata.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct RegistryContractV1<Hash, AccountId, BalanceOf, BlockNumber> {
+    pub code_txt: Vec<u8>,
+    pub bytes: Vec<u8>,
+    pub author: AuthorInfo<AccountId, BalanceOf>,
+    pub abi: Option<Vec<u8>>,
+    pub action_descriptions: Vec<ContractActionDesc<Hash, ChainId, AccountId>>,
+    pub info: Option<RawAliveContractInfo<Hash, BalanceOf, BlockNumber>>,
+    pub meta: ContractMetadataV1,
+}
+
+impl<Hash, AccountId, BalanceOf, BlockNumber>
+    RegistryContractV1<Hash, AccountId, BalanceOf, BlockNumber>
+{
+    pub fn into_contract(self) -> RegistryContract<Hash, AccountId, BalanceOf, BlockNumber> {
+        RegistryContract {
+            code_txt: self.code_txt,
+            bytes: self.bytes,
+            author: self.author,
+            abi: self.abi,
+            action_descriptions: self.action_descriptions,
+            info: self.info,
+            meta: self.meta.into_metadata(),
+        }
+    }
+}
+
 pub trait KindValidator {
     fn can_instantiate(&self) -> bool;
     fn can_generate_side_effects(&self) -> bool;
@@ -145,3 +388,47 @@ impl KindValidator for ContractType {
         matches!(self, ContractType::VanillaWasm | ContractType::VanillaEvm)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn royalty_is_split_between_payees_without_dust() {
+        let royalty = RoyaltyConfig {
+            rate: Percent::from_percent(10),
+            payees: vec![
+                (1u8, Percent::from_percent(33)),
+                (2u8, Percent::from_percent(67)),
+            ],
+        };
+        assert!(royalty.is_valid());
+        assert_eq!(royalty.split(1_000u64), vec![(1, 33), (2, 67)]);
+        // 10% of 1_001 is 100, split into 33 + 67
+        assert_eq!(royalty.split(1_001u64), vec![(1, 33), (2, 67)]);
+        assert_eq!(royalty.split(105u64), vec![(1, 4), (2, 6)]);
+    }
+
+    #[test]
+    fn royalty_shares_must_add_up_to_all_of_it() {
+        let mut royalty = RoyaltyConfig {
+            rate: Percent::from_percent(10),
+            payees: vec![(1u8, Percent::from_percent(50))],
+        };
+        assert!(!royalty.is_valid());
+        royalty.payees.push((2u8, Percent::from_percent(50)));
+        assert!(royalty.is_valid());
+        royalty.payees.clear();
+        assert!(!royalty.is_valid());
+    }
+
+    #[test]
+    fn usage_accumulates_earnings_per_asset() {
+        let mut usage = ContractUsage::<u64>::default();
+        usage.record(10, None);
+        usage.record(5, Some(7));
+        usage.record(1, None);
+        assert_eq!(usage.uses, 3);
+        assert_eq!(usage.earnings, vec![(None, 11), (Some(7), 5)]);
+    }
+}
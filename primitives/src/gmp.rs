@@ -0,0 +1,157 @@
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+use t3rn_types::sfx::TargetId;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Longest payload accepted for a single GMP message
+pub const MAX_GMP_PAYLOAD_LEN: u32 = 64 * 1024;
+
+/// Longest recipient address accepted for a single GMP message
+pub const MAX_GMP_RECIPIENT_LEN: u32 = 32;
+
+/// Longest return data accepted with the acknowledgement of a GMP message
+pub const MAX_GMP_RETURN_DATA_LEN: u32 = 16 * 1024;
+
+/// Longest event inclusion proof accepted with the acknowledgement of a GMP message
+pub const MAX_GMP_INCLUSION_PROOF_LEN: u32 = 128 * 1024;
+
+/// Position of a message within its (source, destination) channel
+pub type GmpNonce = u64;
+
+/// Local recipient of the acknowledgement and return data of a GMP message
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum GmpCallback<AccountId> {
+    /// 3VM contract receiving the acknowledgement into its inbox
+    Contract(AccountId),
+    /// Pallet hook registered under the given id. Reserved: no hooks can be registered yet, so messages
+    ///     with a pallet callback are rejected.
+    Pallet([u8; 4]),
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct GmpMessage<AccountId, BlockNumber> {
+    pub source: TargetId,
+    pub destination: TargetId,
+    pub nonce: GmpNonce,
+    pub sender: AccountId,
+    /// Address of the contract receiving the payload on the destination, in the destination's format
+    pub recipient: Vec<u8>,
+    pub payload: Vec<u8>,
+    pub callback: Option<GmpCallback<AccountId>>,
+    pub submitted_at: BlockNumber,
+}
+
+impl<AccountId: Encode, BlockNumber> GmpMessage<AccountId, BlockNumber> {
+    pub fn payload_hash(&self) -> H256 {
+        keccak_256(&self.payload)
+    }
+
+    /// Id of the message as included into the attested batches and verified by the destination contract:
+    ///     keccak256(source ++ destination ++ nonce_be ++ sender ++ recipient ++ keccak256(payload))
+    pub fn message_id(&self) -> H256 {
+        let mut message = Vec::new();
+        message.extend_from_slice(&self.source);
+        message.extend_from_slice(&self.destination);
+        message.extend_from_slice(&self.nonce.to_be_bytes());
+        message.extend_from_slice(&self.sender.encode());
+        message.extend_from_slice(&self.recipient);
+        message.extend_from_slice(self.payload_hash().as_bytes());
+        keccak_256(&message)
+    }
+}
+
+/// Outcome of a GMP message on the destination, as delivered to its callback
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct GmpAcknowledgement {
+    pub message_id: H256,
+    pub nonce: GmpNonce,
+    pub success: bool,
+    pub return_data: Vec<u8>,
+}
+
+pub trait OnGmpAcknowledged<AccountId> {
+    /// Deliver the acknowledgement of a message sent by `sender` to its callback.
+    ///     Implementations ignore the callbacks they don't own.
+    fn on_gmp_acknowledged(
+        callback: &GmpCallback<AccountId>,
+        sender: &AccountId,
+        acknowledgement: &GmpAcknowledgement,
+    ) -> Weight;
+
+    /// Upper bound of the weight of delivering an acknowledgement carrying `return_data_len` bytes of return data.
+    fn on_gmp_acknowledged_max_weight(return_data_len: u32) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(16)]
+impl<AccountId> OnGmpAcknowledged<AccountId> for Tuple {
+    fn on_gmp_acknowledged(
+        callback: &GmpCallback<AccountId>,
+        sender: &AccountId,
+        acknowledgement: &GmpAcknowledgement,
+    ) -> Weight {
+        let mut weight = Weight::zero();
+        for_tuples!( #( weight = weight.saturating_add(Tuple::on_gmp_acknowledged(callback, sender, acknowledgement)); )* );
+        weight
+    }
+
+    fn on_gmp_acknowledged_max_weight(return_data_len: u32) -> Weight {
+        let mut weight = Weight::zero();
+        for_tuples!( #( weight = weight.saturating_add(Tuple::on_gmp_acknowledged_max_weight(return_data_len)); )* );
+        weight
+    }
+}
+
+fn keccak_256(data: &[u8]) -> H256 {
+    let mut keccak = Keccak::v256();
+    keccak.update(data);
+    let mut res: [u8; 32] = [0; 32];
+    keccak.finalize(&mut res);
+    H256::from(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(nonce: GmpNonce, payload: Vec<u8>) -> GmpMessage<[u8; 32], u32> {
+        GmpMessage {
+            source: *b"3333",
+            destination: *b"sepl",
+            nonce,
+            sender: [1u8; 32],
+            recipient: vec![2u8; 20],
+            payload,
+            callback: None,
+            submitted_at: 1,
+        }
+    }
+
+    #[test]
+    fn message_id_commits_to_the_channel_nonce_and_payload() {
+        let id = message(0, b"hello".to_vec()).message_id();
+
+        assert_eq!(id, message(0, b"hello".to_vec()).message_id());
+        assert_ne!(id, message(1, b"hello".to_vec()).message_id());
+        assert_ne!(id, message(0, b"hello!".to_vec()).message_id());
+
+        let mut other_destination = message(0, b"hello".to_vec());
+        other_destination.destination = *b"eth2";
+        assert_ne!(id, other_destination.message_id());
+    }
+
+    #[test]
+    fn message_id_ignores_the_local_delivery_state() {
+        let mut delivered = message(0, b"hello".to_vec());
+        delivered.callback = Some(GmpCallback::Pallet(*b"test"));
+        delivered.submitted_at = 100;
+
+        assert_eq!(
+            delivered.message_id(),
+            message(0, b"hello".to_vec()).message_id()
+        );
+    }
+}
@@ -52,7 +52,9 @@ pub mod contract_metadata;
 pub mod contracts_registry;
 pub mod executors;
 pub mod gateway_inbound_protocol;
+pub mod gmp;
 pub mod light_client;
+pub mod maintenance;
 pub mod match_format;
 pub mod monetary;
 pub mod portal;
@@ -0,0 +1,55 @@
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+use t3rn_types::sfx::TargetId;
+
+/// Pause narrower than a whole call, enforced by the pallet owning the paused feature.
+/// Scopes can be toggled by the maintenance origins at runtime, without an upgrade.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum PauseScope {
+    /// Circuit: new bids on side effects executed on the target
+    Bidding(TargetId),
+    /// Circuit: new side effects requested on the target
+    Submission(TargetId),
+}
+
+pub trait PauseScopes {
+    fn is_scope_paused(scope: &PauseScope) -> bool;
+}
+
+impl PauseScopes for () {
+    fn is_scope_paused(_scope: &PauseScope) -> bool {
+        false
+    }
+}
+
+frame_support::parameter_types! {
+    /// Scopes paused in the mock runtimes, kept in the storage of the test externalities
+    pub storage PausedScopesMock: Vec<PauseScope> = Vec::new();
+}
+
+/// Pause scopes of the mock runtimes, toggled by tests with `pause` and `resume`
+pub struct PauseScopesMock;
+
+impl PauseScopesMock {
+    pub fn pause(scope: PauseScope) {
+        let mut paused = PausedScopesMock::get();
+        if !paused.contains(&scope) {
+            paused.push(scope);
+            PausedScopesMock::set(&paused);
+        }
+    }
+
+    pub fn resume(scope: &PauseScope) {
+        let mut paused = PausedScopesMock::get();
+        paused.retain(|paused_scope| paused_scope != scope);
+        PausedScopesMock::set(&paused);
+    }
+}
+
+impl PauseScopes for PauseScopesMock {
+    fn is_scope_paused(scope: &PauseScope) -> bool {
+        PausedScopesMock::get().contains(scope)
+    }
+}
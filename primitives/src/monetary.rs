@@ -3,7 +3,10 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::{traits::CheckedAdd, Perbill, RuntimeDebug};
+use sp_runtime::{
+    traits::{CheckedAdd, Saturating},
+    PerThing, Perbill, RuntimeDebug,
+};
 
 pub const DECIMALS: u8 = 12;
 
@@ -20,18 +23,128 @@ pub enum BeneficiaryRole {
     Executor,
 }
 
+/// Yearly inflation and its split between the rewarded roles, each expressed as a portion of the total issuance.
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Eq, PartialEq, Clone, Encode, Decode, MaxEncodedLen, Default, RuntimeDebug, TypeInfo)]
 pub struct InflationAllocation {
-    pub developer: Perbill,
+    pub total: Perbill,
+    pub attester: Perbill,
     pub executor: Perbill,
+    pub collator: Perbill,
+    pub treasury: Perbill,
 }
 
 impl InflationAllocation {
+    /// Portions of all roles must add up to exactly the total inflation.
     pub fn is_valid(&self) -> bool {
-        match self.developer.checked_add(&self.executor) {
-            Some(perbill) => perbill == Perbill::one(),
-            None => false,
+        [self.executor, self.collator, self.treasury]
+            .iter()
+            .try_fold(self.attester, |acc, portion| acc.checked_add(portion))
+            == Some(self.total)
+    }
+
+    /// The role's portion relative to the total inflation, i.e. its share of every distribution.
+    /// Unlike the portions themselves, the shares don't change as the total inflation decays.
+    pub fn share_of_total(&self, portion: Perbill) -> Perbill {
+        Perbill::from_rational(portion.deconstruct(), self.total.deconstruct())
+    }
+
+    /// Applies the decay curve after the given number of full years, scaling all portions
+    /// proportionally to the decayed total inflation.
+    pub fn decayed<BlockNumber>(
+        &self,
+        curve: &InflationCurve<BlockNumber>,
+        years_elapsed: u32,
+    ) -> InflationAllocation {
+        let decay_factor = Perbill::one()
+            .saturating_sub(curve.annual_decay)
+            .saturating_pow(years_elapsed as usize);
+        let decayed_total = (decay_factor * self.total).max(curve.floor.min(self.total));
+        if decayed_total == self.total {
+            return self.clone()
         }
+        let ratio = Perbill::from_rational(decayed_total.deconstruct(), self.total.deconstruct());
+        InflationAllocation {
+            total: decayed_total,
+            attester: ratio * self.attester,
+            executor: ratio * self.executor,
+            collator: ratio * self.collator,
+            treasury: ratio * self.treasury,
+        }
+    }
+}
+
+/// Year-over-year decay of the total inflation, counted in full years since the starting block.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, MaxEncodedLen, Default, RuntimeDebug, TypeInfo)]
+pub struct InflationCurve<BlockNumber> {
+    pub starting_block: BlockNumber,
+    /// E.g. 10% decays 4.4% of total inflation to 3.96% in the following year.
+    pub annual_decay: Perbill,
+    /// The total inflation never decays below the floor.
+    pub floor: Perbill,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allocation() -> InflationAllocation {
+        InflationAllocation {
+            total: Perbill::from_parts(44_000_000),
+            attester: Perbill::from_parts(11_000_000),
+            executor: Perbill::from_parts(8_000_000),
+            collator: Perbill::from_parts(5_000_000),
+            treasury: Perbill::from_parts(20_000_000),
+        }
+    }
+
+    #[test]
+    fn inflation_allocation_is_valid_only_if_portions_add_up_to_total() {
+        assert!(allocation().is_valid());
+
+        let mut invalid = allocation();
+        invalid.treasury = Perbill::from_parts(21_000_000);
+        assert!(!invalid.is_valid());
+    }
+
+    #[test]
+    fn inflation_allocation_decays_proportionally_down_to_floor() {
+        let curve = InflationCurve {
+            starting_block: 0u32,
+            annual_decay: Perbill::from_percent(50),
+            floor: Perbill::from_parts(10_000_000),
+        };
+
+        assert_eq!(allocation().decayed(&curve, 0), allocation());
+
+        let after_one_year = allocation().decayed(&curve, 1);
+        assert_eq!(after_one_year.total, Perbill::from_parts(22_000_000));
+        assert_eq!(after_one_year.attester, Perbill::from_parts(5_500_000));
+        assert_eq!(after_one_year.treasury, Perbill::from_parts(10_000_000));
+
+        assert_eq!(
+            allocation().decayed(&curve, 10).total,
+            Perbill::from_parts(10_000_000)
+        );
+    }
+
+    #[test]
+    fn inflation_allocation_shares_of_total_are_invariant_to_decay() {
+        let curve = InflationCurve {
+            starting_block: 0u32,
+            annual_decay: Perbill::from_percent(50),
+            floor: Perbill::zero(),
+        };
+        let decayed = allocation().decayed(&curve, 1);
+
+        assert_eq!(
+            allocation().share_of_total(allocation().attester),
+            Perbill::from_percent(25)
+        );
+        assert_eq!(
+            decayed.share_of_total(decayed.attester),
+            Perbill::from_percent(25)
+        );
     }
 }
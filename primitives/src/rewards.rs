@@ -10,4 +10,7 @@ pub trait RewardsWriteApi<Account, Balance, BlockNumber> {
         status: &CircuitStatus,
         requester: Option<Account>,
     ) -> bool;
+
+    /// Adds the royalty of a registry contract to the payee's pending claims as its author
+    fn accrue_royalty(payee: &Account, amount: Balance, asset_id: Option<u32>);
 }
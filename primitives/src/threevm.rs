@@ -21,7 +21,9 @@ use t3rn_sdk_primitives::{
     state::SideEffects,
 };
 
-use crate::circuit::{VacuumEVM3DOrder, VacuumEVMOrder, VacuumEVMProof, VacuumEVMTeleportOrder};
+use crate::circuit::{
+    VacuumEVM3DOrder, VacuumEVMConfirmation, VacuumEVMOrder, VacuumEVMProof, VacuumEVMTeleportOrder,
+};
 use circuit_runtime_types::{EvmAddress, TokenId};
 
 // Precompile pointers baked into the binary.
@@ -57,7 +59,7 @@ where
     ),
     VacuumOrder(T::RuntimeOrigin, VacuumEVMOrder),
     Vacuum3DOrder(T::RuntimeOrigin, VacuumEVM3DOrder),
-    VacuumConfirm(T::RuntimeOrigin, VacuumEVMOrder),
+    VacuumConfirm(T::RuntimeOrigin, VacuumEVMConfirmation),
     VacuumSubmitCorrectnessProof(T::RuntimeOrigin, VacuumEVMProof),
     VacuumSubmitFaultProof(T::RuntimeOrigin, VacuumEVMProof),
     VacuumTeleportOrder(T::RuntimeOrigin, VacuumEVMTeleportOrder),
@@ -165,9 +167,16 @@ where
         vacuum_evm_order: VacuumEVMTeleportOrder,
     ) -> Result<bool, DispatchError>;
 
+    /// Confirms the execution of a side effect with an inclusion proof of it on its target.
     fn evm_confirm(
         origin: &T::RuntimeOrigin,
-        vacuum_evm_order: VacuumEVMOrder,
+        vacuum_evm_confirmation: VacuumEVMConfirmation,
+    ) -> Result<bool, DispatchError>;
+
+    /// Proves the proper delivery of an order by its executor, releasing its bond early.
+    fn evm_submit_correctness_proof(
+        origin: &T::RuntimeOrigin,
+        vacuum_evm_proof: VacuumEVMProof,
     ) -> Result<bool, DispatchError>;
 
     fn evm_submit_fault_proof(
@@ -271,6 +280,12 @@ where
     ) -> Result<(), DispatchError>;
 
     fn try_remove_author(contract: &T::AccountId) -> Result<(), DispatchError>;
+
+    /// Links the contract instance to the registry contract it was instantiated from
+    fn try_persist_registry_contract(
+        contract: &T::AccountId,
+        registry_id: &T::Hash,
+    ) -> Result<(), DispatchError>;
 }
 
 pub struct NoopThreeVm;
@@ -391,6 +406,13 @@ impl<T: ConfigSystem, Balance: Encode + Decode> ThreeVm<T, Balance> for NoopThre
     fn try_remove_author(_conztract: &<T as ConfigSystem>::AccountId) -> Result<(), DispatchError> {
         Ok(())
     }
+
+    fn try_persist_registry_contract(
+        _contract: &<T as ConfigSystem>::AccountId,
+        _registry_id: &<T as ConfigSystem>::Hash,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
 }
 
 pub trait ModuleOperations<T: ConfigSystem, Balance> {
@@ -1,14 +1,18 @@
 use crate::{
     gateway::GatewayABIConfig, light_client::LightClientHeartbeat, ChainId, ExecutionVendor,
     GatewayActivity, GatewayGenesisConfig, GatewayType, GatewayVendor, SpeedMode, TokenInfo,
+    XDNSTopology,
 };
-use codec::{Decode, Encode};
+use codec::{Decode, DecodeAll, Encode};
 use frame_support::dispatch::{DispatchResult, DispatchResultWithPostInfo};
 use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
 use scale_info::TypeInfo;
-use sp_core::{H160, H256};
+use sp_core::{hashing::blake2_256, H160, H256};
 use sp_runtime::DispatchError;
-use sp_std::vec::Vec;
+use sp_std::{
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    vec::Vec,
+};
 use t3rn_abi::sfx_abi::SFXAbi;
 use t3rn_types::sfx::{SecurityLvl, Sfx4bId};
 
@@ -111,6 +115,38 @@ pub struct GatewayRecord<AccountId> {
     pub allowed_side_effects: Vec<(Sfx4bId, Option<u8>)>,
 }
 
+/// Change to a gateway record requested by its registrant or root.
+/// Registrant changes which may affect active Xtx are timelocked.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum GatewayChange<AccountId> {
+    /// Enroll the given ABI, or the standard one with the pallet index memo if None
+    EnrollSFXAbi {
+        sfx_4b_id: Sfx4bId,
+        sfx_abi: Option<SFXAbi>,
+        maybe_pallet_id: Option<u8>,
+    },
+    UnrollSFXAbi {
+        sfx_4b_id: Sfx4bId,
+    },
+    LinkToken {
+        token_id: AssetId,
+        token_props: TokenInfo,
+    },
+    UnlinkToken {
+        token_id: AssetId,
+    },
+    SetEscrowAccount(Option<AccountId>),
+    SetAllowedSideEffects(Vec<(Sfx4bId, Option<u8>)>),
+}
+
+impl<AccountId> GatewayChange<AccountId> {
+    /// Linking a new token can't alter side effects already requested towards the gateway
+    pub fn is_additive(&self) -> bool {
+        matches!(self, GatewayChange::LinkToken { .. })
+    }
+}
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct FullGatewayRecord<AccountId> {
@@ -118,6 +154,272 @@ pub struct FullGatewayRecord<AccountId> {
     pub tokens: Vec<TokenRecord>,
 }
 
+/// Current version of the XDNS topology snapshot format.
+/// Version 1 is the legacy `XDNSTopology` (gateways + self-gateway tokens only).
+pub const XDNS_TOPOLOGY_VERSION: u32 = 2;
+
+/// Native balance the asset fee prices are quoted for: prices are the amount of the asset worth 1 TRN.
+pub const ASSET_FEE_PRICE_UNIT: u128 = 1_000_000_000_000;
+
+/// Complete, versioned snapshot of the XDNS state used to migrate topology between networks.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct XDNSTopologySnapshot<AccountId, BlockNumber, Balance> {
+    /// Format version, see XDNS_TOPOLOGY_VERSION
+    pub version: u32,
+
+    /// blake2_256 hash over the SCALE-encoded content (all fields below)
+    pub content_hash: H256,
+
+    pub gateways: Vec<FullGatewayRecord<AccountId>>,
+
+    pub assets: Vec<TokenRecord>,
+
+    pub standard_sfx_abis: Vec<(Sfx4bId, SFXAbi)>,
+
+    /// Per-gateway SFX ABIs: (gateway_id, [(sfx_4b_id, abi)])
+    pub gateway_sfx_abis: Vec<(TargetId, Vec<(Sfx4bId, SFXAbi)>)>,
+
+    pub remote_order_addresses: Vec<(TargetId, H256)>,
+
+    pub remote_bidding_addresses: Vec<(TargetId, H256)>,
+
+    /// (target_id, (asset_from, asset_to), estimate)
+    pub per_target_asset_estimates: Vec<(TargetId, (AssetId, AssetId), Balance)>,
+
+    pub epoch_history: Vec<(GatewayVendor, Vec<EpochEstimate<BlockNumber>>)>,
+}
+
+impl<AccountId: Encode, BlockNumber: Encode, Balance: Encode>
+    XDNSTopologySnapshot<AccountId, BlockNumber, Balance>
+{
+    /// Assemble the snapshot and seal it with the current version and content hash.
+    pub fn new(
+        gateways: Vec<FullGatewayRecord<AccountId>>,
+        assets: Vec<TokenRecord>,
+        standard_sfx_abis: Vec<(Sfx4bId, SFXAbi)>,
+        gateway_sfx_abis: Vec<(TargetId, Vec<(Sfx4bId, SFXAbi)>)>,
+        remote_order_addresses: Vec<(TargetId, H256)>,
+        remote_bidding_addresses: Vec<(TargetId, H256)>,
+        per_target_asset_estimates: Vec<(TargetId, (AssetId, AssetId), Balance)>,
+        epoch_history: Vec<(GatewayVendor, Vec<EpochEstimate<BlockNumber>>)>,
+    ) -> Self {
+        let mut snapshot = XDNSTopologySnapshot {
+            version: XDNS_TOPOLOGY_VERSION,
+            content_hash: H256::zero(),
+            gateways,
+            assets,
+            standard_sfx_abis,
+            gateway_sfx_abis,
+            remote_order_addresses,
+            remote_bidding_addresses,
+            per_target_asset_estimates,
+            epoch_history,
+        };
+        snapshot.content_hash = snapshot.compute_content_hash();
+        snapshot
+    }
+
+    /// Upgrade the legacy (v1) topology. Sections unknown to v1 are left empty.
+    pub fn from_legacy(legacy: XDNSTopology<AccountId>) -> Self {
+        Self::new(
+            legacy.gateways,
+            legacy.assets,
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    pub fn compute_content_hash(&self) -> H256 {
+        let content = (
+            &self.gateways,
+            &self.assets,
+            &self.standard_sfx_abis,
+            &self.gateway_sfx_abis,
+            &self.remote_order_addresses,
+            &self.remote_bidding_addresses,
+            &self.per_target_asset_estimates,
+            &self.epoch_history,
+        );
+        H256(blake2_256(&content.encode()))
+    }
+
+    pub fn is_content_hash_valid(&self) -> bool {
+        self.content_hash == self.compute_content_hash()
+    }
+}
+
+impl<AccountId: Decode + Encode, BlockNumber: Decode + Encode, Balance: Decode + Encode>
+    XDNSTopologySnapshot<AccountId, BlockNumber, Balance>
+{
+    /// Decode either the current snapshot format or the legacy (v1) XDNSTopology.
+    pub fn decode_any_version(encoded: &[u8]) -> Result<Self, codec::Error> {
+        if let Ok(snapshot) = Self::decode_all(&mut &encoded[..]) {
+            if snapshot.version == XDNS_TOPOLOGY_VERSION {
+                return Ok(snapshot)
+            }
+        }
+        XDNSTopology::<AccountId>::decode_all(&mut &encoded[..]).map(Self::from_legacy)
+    }
+}
+
+/// Selects which sections of the XDNS topology are imported at unzip.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct TopologySections {
+    pub gateways: bool,
+    pub assets: bool,
+    pub standard_sfx_abis: bool,
+    pub gateway_sfx_abis: bool,
+    pub remote_order_addresses: bool,
+    pub remote_bidding_addresses: bool,
+    pub per_target_asset_estimates: bool,
+    pub epoch_history: bool,
+}
+
+impl TopologySections {
+    pub fn all() -> Self {
+        TopologySections {
+            gateways: true,
+            assets: true,
+            standard_sfx_abis: true,
+            gateway_sfx_abis: true,
+            remote_order_addresses: true,
+            remote_bidding_addresses: true,
+            per_target_asset_estimates: true,
+            epoch_history: true,
+        }
+    }
+
+    pub fn none() -> Self {
+        TopologySections {
+            gateways: false,
+            assets: false,
+            standard_sfx_abis: false,
+            gateway_sfx_abis: false,
+            remote_order_addresses: false,
+            remote_bidding_addresses: false,
+            per_target_asset_estimates: false,
+            epoch_history: false,
+        }
+    }
+}
+
+impl Default for TopologySections {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// Keys of a single topology section, grouped by how they compare against the local state.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct SectionDiff<Key> {
+    /// Present in the snapshot, missing locally
+    pub added: Vec<Key>,
+    /// Present on both sides with a different value
+    pub changed: Vec<Key>,
+    /// Present locally, missing in the snapshot. Unzip never removes these.
+    pub missing_in_snapshot: Vec<Key>,
+    pub unchanged: u32,
+}
+
+impl<Key: Ord + Clone> SectionDiff<Key> {
+    /// Compare snapshot entries against the local ones, both given as (key, value) pairs.
+    /// Local entries are indexed by key, so the comparison is O((local + incoming) log local).
+    pub fn compare<Value: PartialEq>(local: &[(Key, Value)], incoming: &[(Key, Value)]) -> Self {
+        let local_by_key = local
+            .iter()
+            .map(|(key, value)| (key, value))
+            .collect::<BTreeMap<_, _>>();
+        let incoming_keys = incoming.iter().map(|(key, _)| key).collect::<BTreeSet<_>>();
+
+        let mut diff = SectionDiff::default();
+        for (key, value) in incoming {
+            match local_by_key.get(key) {
+                None => diff.added.push(key.clone()),
+                Some(local_value) if *local_value != value => diff.changed.push(key.clone()),
+                Some(_) => diff.unchanged = diff.unchanged.saturating_add(1),
+            }
+        }
+        for (key, _) in local {
+            if !incoming_keys.contains(key) {
+                diff.missing_in_snapshot.push(key.clone());
+            }
+        }
+        diff
+    }
+
+    /// True if applying the section would not write anything
+    pub fn is_noop(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn writes_count(&self) -> u32 {
+        self.added.len().saturating_add(self.changed.len()) as u32
+    }
+}
+
+impl<Key> Default for SectionDiff<Key> {
+    fn default() -> Self {
+        SectionDiff {
+            added: Vec::new(),
+            changed: Vec::new(),
+            missing_in_snapshot: Vec::new(),
+            unchanged: 0,
+        }
+    }
+}
+
+/// Preview of changes an XDNS topology unzip would apply, per section.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, Default)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct XDNSTopologyDiff {
+    pub version: u32,
+    pub content_hash: H256,
+    pub gateways: SectionDiff<TargetId>,
+    /// (asset_id, gateway_id)
+    pub assets: SectionDiff<(AssetId, TargetId)>,
+    pub standard_sfx_abis: SectionDiff<Sfx4bId>,
+    /// (gateway_id, sfx_4b_id)
+    pub gateway_sfx_abis: SectionDiff<(TargetId, Sfx4bId)>,
+    pub remote_order_addresses: SectionDiff<TargetId>,
+    pub remote_bidding_addresses: SectionDiff<TargetId>,
+    /// (target_id, asset_from, asset_to)
+    pub per_target_asset_estimates: SectionDiff<(TargetId, AssetId, AssetId)>,
+    pub epoch_history: SectionDiff<GatewayVendor>,
+}
+
+impl XDNSTopologyDiff {
+    /// True if unzipping the compared snapshot would not write anything
+    pub fn is_noop(&self) -> bool {
+        self.gateways.is_noop()
+            && self.assets.is_noop()
+            && self.standard_sfx_abis.is_noop()
+            && self.gateway_sfx_abis.is_noop()
+            && self.remote_order_addresses.is_noop()
+            && self.remote_bidding_addresses.is_noop()
+            && self.per_target_asset_estimates.is_noop()
+            && self.epoch_history.is_noop()
+    }
+
+    pub fn writes_count(&self) -> u32 {
+        self.gateways
+            .writes_count()
+            .saturating_add(self.assets.writes_count())
+            .saturating_add(self.standard_sfx_abis.writes_count())
+            .saturating_add(self.gateway_sfx_abis.writes_count())
+            .saturating_add(self.remote_order_addresses.writes_count())
+            .saturating_add(self.remote_bidding_addresses.writes_count())
+            .saturating_add(self.per_target_asset_estimates.writes_count())
+            .saturating_add(self.epoch_history.writes_count())
+    }
+}
+
 /// A preliminary representation of a xdns_record in the onchain registry.
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
@@ -287,6 +589,21 @@ pub trait Xdns<T: frame_system::Config, Balance> {
 
     fn get_self_token_id() -> AssetId;
 
+    fn is_token_registered(token_id: AssetId) -> bool;
+
+    /// Converts the native transaction fee into the registered asset, priced by the governance-set
+    ///     fee price of the asset.
+    fn convert_native_fee_to_asset(
+        asset_id: AssetId,
+        native_fee: Balance,
+    ) -> Result<Balance, DispatchError>;
+
+    /// Reserves the gateway registration deposit of a permissionless registrant.
+    fn reserve_registration_deposit(
+        registrant: &T::AccountId,
+        gateway_id: TargetId,
+    ) -> DispatchResult;
+
     fn add_new_gateway(
         gateway_id: [u8; 4],
         verification_vendor: GatewayVendor,
@@ -333,6 +650,8 @@ pub trait Xdns<T: frame_system::Config, Balance> {
 
     fn get_verification_vendor(chain_id: &ChainId) -> Result<GatewayVendor, DispatchError>;
 
+    fn get_execution_vendor(chain_id: &ChainId) -> Result<ExecutionVendor, DispatchError>;
+
     fn get_target_codec(chain_id: &ChainId) -> Result<t3rn_abi::Codec, DispatchError>;
 
     fn get_escrow_account(chain_id: &ChainId) -> Result<Vec<u8>, DispatchError>;
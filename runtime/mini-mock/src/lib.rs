@@ -27,9 +27,7 @@ pub use pallet_circuit::{
 };
 pub use pallet_circuit_vacuum::{Config as ConfigVacuum, Event as VacuumEvent, OrderStatusRead};
 use pallet_eth2_finality_verifier::types::Root;
-mod hooks;
 mod treasuries_config;
-pub use hooks::GlobalOnInitQueues;
 use sp_runtime::DispatchResult;
 
 use hex_literal::hex;
@@ -50,8 +48,9 @@ use pallet_grandpa_finality_verifier::{
 };
 use pallet_portal::Error as PortalError;
 pub use pallet_rewards::{
-    Authors, AuthorsThisPeriod, Config as ConfigRewards, DistributionBlock, DistributionHistory,
-    Error as RewardsError, PendingClaims,
+    AccruedNominatorRewards, Authors, AuthorsThisPeriod, ClaimMode, Config as ConfigRewards,
+    DefaultClaimModes, DistributionBlock, DistributionHistory, Error as RewardsError,
+    Event as RewardsEvent, PendingClaims, PendingVestingBonuses, VestingSchedules,
 };
 
 use frame_support::parameter_types;
@@ -221,15 +220,20 @@ impl pallet_account_manager::Config for MiniRuntime {
     type Clock = Clock;
     type Currency = Balances;
     type EscrowAccount = EscrowAccount;
+    type ExecutorFeeSponsor = Circuit;
     type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
+    type InsurancePalletId = InsurancePalletId;
+    type InsurancePoolRewardShare = InsurancePoolRewardShare;
+    type InsuranceWithdrawalNoticePeriod = ConstU32<10>;
     type RuntimeEvent = RuntimeEvent;
     type Time = Timestamp;
     type WeightInfo = ();
 }
 
 impl pallet_clock::Config for MiniRuntime {
+    type BlocksPerHour = ConstU32<300>;
     type OnFinalizeQueues = t3rn_primitives::clock::EmptyOnHookQueues<Self>;
-    type OnInitializeQueues = GlobalOnInitQueues;
+    type PeriodicTasks = (XDNS, Rewards, Clock, Circuit);
     type RoundDuration = ConstU32<300>;
     type RuntimeEvent = RuntimeEvent;
 }
@@ -246,6 +250,8 @@ parameter_types! {
     pub const ExecutorBootstrapRewards: Percent = Percent::from_parts(40); // 40%
     pub const StartingRepatriationPercentage: Percent = Percent::from_parts(10); // 10%
     pub const OneYear: BlockNumber = 2_628_000; // (365.25 * 24 * 60 * 60) / 12; assuming 12s block time
+    pub const ClaimVestingPeriod: BlockNumber = 1_296_000; // (6 * 30 * 24 * 60 * 60) / 12; six months assuming 12s block time
+    pub const ClaimVestingBonus: Percent = Percent::from_percent(5);
     pub const InflationDistributionPeriod: BlockNumber = 100_800; // (14 * 24 * 60 * 60) / 12; assuming one distribution per two weeks
     pub const AvailableBootstrapSpenditure: Balance = 1_000_000 * (TRN as Balance); // 1 MLN UNIT
 }
@@ -274,24 +280,33 @@ impl pallet_rewards::Config for MiniRuntime {
     type AttesterInflation = AttesterInflation;
     type Attesters = Attesters;
     type AvailableBootstrapSpenditure = AvailableBootstrapSpenditure;
+    type ClaimVestingBonus = ClaimVestingBonus;
+    type ClaimVestingPeriod = ClaimVestingPeriod;
     type Clock = Clock;
     type CollatorBootstrapRewards = CollatorBootstrapRewards;
     type CollatorInflation = CollatorInflation;
     type Currency = Balances;
     type ExecutorBootstrapRewards = ExecutorBootstrapRewards;
     type ExecutorInflation = ExecutorInflation;
+    type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
     type FindAuthor = FindAuthorMockRoundRobinRotate32;
     type InflationDistributionPeriod = InflationDistributionPeriod;
+    type MaxClaimsPerCall = ConstU32<64>;
+    type MaxVestingSchedules = ConstU32<32>;
     type OneYear = OneYear;
     type RuntimeEvent = RuntimeEvent;
     type StartingRepatriationPercentage = StartingRepatriationPercentage;
     type TotalInflation = TotalInflation;
     type TreasuryAccounts = MiniRuntime;
     type TreasuryInflation = TreasuryInflation;
+    type WeightInfo = pallet_rewards::weights::SubstrateWeight<Self>;
 }
 
 parameter_types! {
     pub const DefaultCommission: Percent = Percent::from_percent(10);
+    pub const MaxCommissionIncrease: Percent = Percent::from_percent(5);
+    pub const InsurancePalletId: frame_support::PalletId = frame_support::PalletId(*b"pal/insr");
+    pub const InsurancePoolRewardShare: Percent = Percent::from_percent(10);
     pub const CommitmentRewardSource: AccountId = AccountId::new([51u8; 32]);
     pub const SlashAccount: AccountId = AccountId::new([51u8; 32]);
     pub const RewardMultiplier: Balance = 1;
@@ -304,11 +319,13 @@ impl pallet_attesters::Config for MiniRuntime {
     type ActiveSetSize = ConstU32<32>;
     type BatchingWindow = ConstU32<6>;
     // type CommitmentRewardSource = CommitmentRewardSource;
+    type CommissionChangeNoticePeriod = ConstU32<800>;
     type CommitteeSize = ConstU32<32>;
     type Currency = Balances;
     type DefaultCommission = DefaultCommission;
     type LightClientAsyncAPI = XDNS;
     type MaxBatchSize = ConstU32<128>;
+    type MaxCommissionIncrease = MaxCommissionIncrease;
     type MinAttesterBond = MinAttesterBond;
     type MinNominatorBond = MinNominatorBond;
     type Portal = Portal;
@@ -320,6 +337,7 @@ impl pallet_attesters::Config for MiniRuntime {
     type RuntimeEvent = RuntimeEvent;
     type ShufflingFrequency = ConstU32<400>;
     type TreasuryAccounts = MiniRuntime;
+    type WeightInfo = pallet_attesters::weights::SubstrateWeight<MiniRuntime>;
     type Xdns = XDNS;
 }
 
@@ -445,12 +463,23 @@ impl pallet_timestamp::Config for MiniRuntime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const GatewayRegistrationDeposit: Balance = 100 * (TRN as Balance);
+    pub const GatewayChangeTimelock: BlockNumber = 600;
+    pub const MaxScheduledGatewayChanges: u32 = 100;
+    pub const MaxPendingGatewayChangesPerRegistrant: u32 = 10;
+}
+
 impl pallet_xdns::Config for MiniRuntime {
     type AssetsOverlay = MiniRuntime;
     type AttestersRead = Attesters;
     type Balances = Balances;
     type CircuitDLQ = Circuit;
     type Currency = Balances;
+    type GatewayChangeTimelock = GatewayChangeTimelock;
+    type GatewayRegistrationDeposit = GatewayRegistrationDeposit;
+    type MaxPendingGatewayChangesPerRegistrant = MaxPendingGatewayChangesPerRegistrant;
+    type MaxScheduledGatewayChanges = MaxScheduledGatewayChanges;
     type Portal = Portal;
     type RuntimeEvent = RuntimeEvent;
     type SelfGatewayId = SelfGatewayId;
@@ -462,6 +491,8 @@ impl pallet_xdns::Config for MiniRuntime {
 
 parameter_types! {
     pub const CircuitAccountId: AccountId = AccountId::new([51u8; 32]); // 0x333...3
+    pub const GmpDepositPerByte: Balance = (TRN as Balance) / 1_000;
+    pub const GmpMessageTimeout: BlockNumber = 600;
     pub const SelfGatewayId: [u8; 4] = [3, 3, 3, 3];
     pub const SelfGatewayIdOptimistic: [u8; 4] = [0, 3, 3, 3];
 }
@@ -473,7 +504,12 @@ impl pallet_circuit::Config for MiniRuntime {
     type Currency = Balances;
     type DeletionQueueLimit = ConstU32<100u32>;
     type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
+    type GmpCallbacks = ();
+    type GmpDepositPerByte = GmpDepositPerByte;
+    type GmpMessageTimeout = GmpMessageTimeout;
+    type PauseScopes = t3rn_primitives::maintenance::PauseScopesMock;
     type Portal = Portal;
+    type Royalties = ();
     type RuntimeEvent = RuntimeEvent;
     type SFXBiddingPeriod = ConstU32<3u32>;
     type SelfAccountId = CircuitAccountId;
@@ -488,6 +524,7 @@ impl pallet_circuit::Config for MiniRuntime {
 }
 
 impl pallet_circuit_vacuum::Config for MiniRuntime {
+    type AccountManager = AccountManager;
     type AddressMapping = AddressMappingMock;
     type CircuitSubmitAPI = Circuit;
     type Currency = Balances;
@@ -500,6 +537,7 @@ impl pallet_circuit_vacuum::Config for MiniRuntime {
 
 impl pallet_portal::Config for MiniRuntime {
     type Currency = Balances;
+    type GatewayRegistrationOrigin = frame_system::EnsureRoot<AccountId>;
     type RuntimeEvent = RuntimeEvent;
     type SelectLightClient = SelectLightClientRegistry;
     type WeightInfo = pallet_portal::weights::SubstrateWeight<MiniRuntime>;
@@ -567,7 +605,7 @@ impl AddressMapping<AccountId> for AddressMappingMock {
     /// Returns the AccountId used go generate the given EvmAddress.
     fn into_account_id(evm: &EvmAddress) -> AccountId {
         let mut account_id = [0u8; 32];
-        account_id.copy_from_slice(&evm.0);
+        account_id[12..].copy_from_slice(&evm.0);
         AccountId::new(account_id)
     }
 
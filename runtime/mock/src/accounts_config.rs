@@ -8,6 +8,8 @@ use sp_runtime::traits::{ConstU32, ConvertInto};
 parameter_types! {
     // TODO: update me to be better
     pub EscrowAccount: AccountId32 = AccountId32::new([51_u8; 32]);
+    pub const InsurancePalletId: frame_support::PalletId = frame_support::PalletId(*b"pal/insr");
+    pub const InsurancePoolRewardShare: sp_runtime::Percent = sp_runtime::Percent::from_percent(10);
 }
 
 impl pallet_account_manager::Config for Runtime {
@@ -17,7 +19,11 @@ impl pallet_account_manager::Config for Runtime {
     type Clock = Clock;
     type Currency = Balances;
     type EscrowAccount = EscrowAccount;
+    type ExecutorFeeSponsor = Circuit;
     type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
+    type InsurancePalletId = InsurancePalletId;
+    type InsurancePoolRewardShare = InsurancePoolRewardShare;
+    type InsuranceWithdrawalNoticePeriod = ConstU32<10>;
     type RuntimeEvent = RuntimeEvent;
     type Time = Timestamp;
     type WeightInfo = ();
@@ -63,8 +63,10 @@ impl pallet_3vm::Config for Runtime {
     type ContractsRegistry = ContractsRegistry;
     type Currency = Balances;
     type EscrowAccount = EscrowAccount;
+    type MaxGmpAcknowledgements = ConstU32<64>;
     type OnLocalTrigger = Circuit;
     type Portal = Portal;
+    type Rewards = Rewards;
     type RuntimeEvent = RuntimeEvent;
     type SignalBounceThreshold = ConstU32<2>;
     type VacuumEVMApi = Vacuum;
@@ -216,12 +218,16 @@ parameter_types! {
     pub const T3rnPalletId: PalletId = PalletId(*b"trn/trsy");
     pub TreasuryModuleAccount: AccountId = T3rnPalletId::get().into_account_truncating();
     pub const StorageDepositFee: Balance = 1;
+    pub const AutoMapOnFirstInteraction: bool = true;
+    pub const MaxMigratedAccountsPerBlock: u32 = 2;
 }
 
 impl pallet_3vm_account_mapping::Config for Runtime {
     type AddressMapping = EvmAddressMapping<Runtime>;
+    type AutoMapOnFirstInteraction = AutoMapOnFirstInteraction;
     type ChainId = ChainId;
     type Currency = Balances;
+    type MaxMigratedAccountsPerBlock = MaxMigratedAccountsPerBlock;
     type NetworkTreasuryAccount = TreasuryModuleAccount;
     type RuntimeEvent = RuntimeEvent;
     type StorageDepositFee = StorageDepositFee;
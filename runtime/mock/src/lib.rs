@@ -25,7 +25,6 @@ mod accounts_config;
 mod circuit_config;
 mod consensus_aura_config;
 pub mod contracts_config;
-mod hooks;
 mod system_no_version_config;
 pub mod test_utils;
 mod treasuries_config;
@@ -33,7 +32,6 @@ mod xbi_config;
 pub type RococoLightClient = ();
 pub type PolkadotLightClient = pallet_grandpa_finality_verifier::Instance1;
 pub type KusamaLightClient = pallet_grandpa_finality_verifier::Instance2;
-pub use crate::circuit_config::GlobalOnInitQueues;
 use frame_support::traits::GenesisBuild;
 pub use pallet_3vm_account_mapping::{
     ethereum_signable_message, to_ascii_hex, EcdsaSignature, EvmAddressMapping,
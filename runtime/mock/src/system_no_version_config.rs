@@ -9,7 +9,7 @@ use frame_support::{
     weights::{constants::RocksDbWeight, ConstantMultiplier, IdentityFee},
 };
 
-use sp_runtime::traits::{AccountIdLookup, BlakeTwo256, ConvertInto};
+use sp_runtime::traits::{AccountIdLookup, BlakeTwo256};
 
 // Configure FRAME pallets to include in runtime.
 impl frame_system::Config for Runtime {
@@ -138,7 +138,7 @@ impl HandleCredit<AccountId, Assets> for CreditToBlockAuthor {
 impl pallet_asset_tx_payment::Config for Runtime {
     type Fungibles = Assets;
     type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
-        pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
+        pallet_xdns::fees::XdnsAssetFeeConversion<Runtime>,
         CreditToBlockAuthor,
     >;
     type RuntimeEvent = RuntimeEvent;
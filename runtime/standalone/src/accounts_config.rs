@@ -9,6 +9,8 @@ use sp_runtime::traits::ConvertInto;
 parameter_types! {
     // TODO: update me to be better
     pub EscrowAccount: AccountId32 = AccountId32::new([51_u8; 32]);
+    pub const InsurancePalletId: frame_support::PalletId = frame_support::PalletId(*b"pal/insr");
+    pub const InsurancePoolRewardShare: sp_runtime::Percent = sp_runtime::Percent::from_percent(5);
 }
 
 impl pallet_account_manager::Config for Runtime {
@@ -18,7 +20,11 @@ impl pallet_account_manager::Config for Runtime {
     type Clock = Clock;
     type Currency = Balances;
     type EscrowAccount = EscrowAccount;
+    type ExecutorFeeSponsor = Circuit;
     type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
+    type InsurancePalletId = InsurancePalletId;
+    type InsurancePoolRewardShare = InsurancePoolRewardShare;
+    type InsuranceWithdrawalNoticePeriod = ConstU32<{ 7 * circuit_runtime_types::DAYS }>;
     type RuntimeEvent = RuntimeEvent;
     type Time = Timestamp;
     type WeightInfo = ();
@@ -256,6 +256,39 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_maintenance_mode_rpc_runtime_api::MaintenanceModeRuntimeApi<Block, BlockNumber> for Runtime {
+        fn is_in_maintenance_mode() -> bool {
+            MaintenanceMode::maintenance_mode()
+        }
+
+        fn is_call_paused(pallet_name: Vec<u8>, call_name: Vec<u8>) -> bool {
+            MaintenanceMode::is_call_paused(&pallet_name, &call_name)
+        }
+
+        fn paused_calls() -> Vec<(Vec<u8>, Option<Vec<u8>>, Option<BlockNumber>)> {
+            MaintenanceMode::active_paused_calls()
+                .into_iter()
+                .map(|((pallet_name, call_name), expires_at)| (pallet_name, call_name, expires_at))
+                .collect()
+        }
+
+        fn paused_scopes() -> Vec<(pallet_maintenance_mode_rpc_runtime_api::PauseScope, Option<BlockNumber>)> {
+            MaintenanceMode::active_paused_scopes()
+        }
+    }
+
+    impl pallet_clock_rpc_runtime_api::ClockRuntimeApi<Block, BlockNumber> for Runtime {
+        fn fetch_tasks() -> Vec<pallet_clock_rpc_runtime_api::TaskInfo<BlockNumber>> {
+            Clock::tasks_info()
+        }
+    }
+
+    impl pallet_rewards_rpc_runtime_api::RewardsRuntimeApi<Block, AccountId, Balance> for Runtime {
+        fn accrued_nominator_rewards(nominator: AccountId) -> Vec<(AccountId, Balance)> {
+            Rewards::nominator_accrued_rewards(&nominator)
+        }
+    }
+
      impl pallet_xdns_rpc_runtime_api::XdnsRuntimeApi<Block, AccountId> for Runtime {
         fn fetch_records() -> Vec<GatewayRecord<AccountId>> {
              <XDNS as t3rn_primitives::xdns::Xdns<Runtime, Balance>>::fetch_gateways()
@@ -273,6 +306,16 @@ impl_runtime_apis! {
         fn retreive_treasury_address(treasury_account: t3rn_primitives::TreasuryAccount) -> AccountId {
             Runtime::get_treasury_account(treasury_account)
         }
+
+        fn zip_topology() -> Vec<u8> {
+            codec::Encode::encode(&XDNS::do_zip_topology())
+        }
+
+        fn diff_topology(topology_encoded: Vec<u8>) -> Option<t3rn_primitives::xdns::XDNSTopologyDiff> {
+            XDNS::decode_topology(None, Some(topology_encoded))
+                .ok()
+                .map(|topology| XDNS::do_diff_topology(&topology))
+        }
     }
 
      impl pallet_portal_rpc_runtime_api::PortalRuntimeApi<Block, AccountId, Balance, Hash> for Runtime {
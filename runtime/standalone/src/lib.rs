@@ -49,7 +49,6 @@ pub mod accounts_config;
 pub mod circuit_config;
 pub mod consensus_aura_config;
 pub mod contracts_config;
-pub mod hooks;
 pub mod impl_versioned_runtime_with_api;
 pub mod signed_extrinsics_config;
 pub mod system_config;
@@ -1,7 +1,7 @@
 use crate::{
-    hooks::GlobalOnInitQueues, treasuries_config::EscrowTreasuryId, AssetId, Assets, Balance,
-    Balances, Clock, Imbalance, OnUnbalanced, Runtime, RuntimeCall, RuntimeEvent, ThreeVm,
-    Timestamp,
+    hooks::RuntimeTasks, treasuries_config::EscrowTreasuryId, AssetId, Assets, Balance, Balances,
+    Circuit, Clock, Imbalance, OnUnbalanced, Rewards, Runtime, RuntimeCall, RuntimeEvent, ThreeVm,
+    Timestamp, XDNS,
 };
 use frame_support::parameter_types;
 use sp_core::{crypto::AccountId32, ConstU32};
@@ -9,11 +9,14 @@ use sp_runtime::traits::{AccountIdConversion, ConvertInto};
 
 parameter_types! {
     pub EscrowAccount: AccountId32 = EscrowTreasuryId::get().into_account_truncating();
+    pub const InsurancePalletId: frame_support::PalletId = frame_support::PalletId(*b"pal/insr");
+    pub const InsurancePoolRewardShare: sp_runtime::Percent = sp_runtime::Percent::from_percent(5);
 }
 
 impl pallet_clock::Config for Runtime {
+    type BlocksPerHour = ConstU32<{ circuit_runtime_types::HOURS }>;
     type OnFinalizeQueues = t3rn_primitives::clock::EmptyOnHookQueues<Self>;
-    type OnInitializeQueues = GlobalOnInitQueues;
+    type PeriodicTasks = (XDNS, Rewards, Clock, RuntimeTasks, Circuit);
     type RoundDuration = ConstU32<300u32>;
     type RuntimeEvent = RuntimeEvent;
 }
@@ -25,7 +28,11 @@ impl pallet_account_manager::Config for Runtime {
     type Clock = Clock;
     type Currency = Balances;
     type EscrowAccount = EscrowAccount;
+    type ExecutorFeeSponsor = Circuit;
     type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
+    type InsurancePalletId = InsurancePalletId;
+    type InsurancePoolRewardShare = InsurancePoolRewardShare;
+    type InsuranceWithdrawalNoticePeriod = ConstU32<{ 7 * circuit_runtime_types::DAYS }>;
     type RuntimeEvent = RuntimeEvent;
     type Time = Timestamp;
     type WeightInfo = ();
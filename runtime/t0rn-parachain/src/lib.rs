@@ -80,8 +80,13 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
+/// Migrations to apply on runtime upgrade, on top of the pallet hooks.
+/// The EVM call and withdraw origins moved from `EnsureAddressTruncated` to `EnsureAddressMapped`.
+pub type Migrations = (pallet_3vm_account_mapping::migrations::MapTruncatedEvmAddresses<Runtime>,);
+
 #[sp_version::runtime_version]
 pub const VERSION: RuntimeVersion = RuntimeVersion {
     // https://docs.rs/sp-version/latest/sp_version/struct.RuntimeVersion.html
@@ -144,7 +149,7 @@ impl pallet_identity::Config for Runtime {
 impl pallet_asset_tx_payment::Config for Runtime {
     type Fungibles = Assets;
     type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
-        pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
+        pallet_xdns::fees::XdnsAssetFeeConversion<Runtime>,
         CreditToBlockAuthor,
     >;
     type RuntimeEvent = RuntimeEvent;
@@ -162,7 +167,7 @@ parameter_types! {
 }
 use crate::system_config::CreditToBlockAuthor;
 use frame_support::traits::AsEnsureOriginWithArg;
-use sp_runtime::traits::{ConstU32, ConvertInto};
+use sp_runtime::traits::ConstU32;
 
 impl pallet_assets::Config for Runtime {
     type ApprovalDeposit = ApprovalDeposit;
@@ -290,8 +295,14 @@ mod benches {
         // [pallet_timestamp, Timestamp]
         // [pallet_collator_selection, CollatorSelection]
         // [pallet_account_manager, AccountManager]
+        [pallet_attesters, Attesters]
+        [pallet_circuit, Circuit]
         [pallet_eth2_finality_verifier, EthereumBridge]
+        [pallet_evm_precompile_3vm_dispatch, ThreeVmDispatchBench::<Runtime>]
+        [pallet_portal, Portal]
+        [pallet_rewards, Rewards]
         [pallet_vacuum, Vacuum]
+        [pallet_xdns, XDNS]
     );
 }
 
@@ -491,6 +502,39 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_maintenance_mode_rpc_runtime_api::MaintenanceModeRuntimeApi<Block, BlockNumber> for Runtime {
+        fn is_in_maintenance_mode() -> bool {
+            Maintenance::maintenance_mode()
+        }
+
+        fn is_call_paused(pallet_name: Vec<u8>, call_name: Vec<u8>) -> bool {
+            Maintenance::is_call_paused(&pallet_name, &call_name)
+        }
+
+        fn paused_calls() -> Vec<(Vec<u8>, Option<Vec<u8>>, Option<BlockNumber>)> {
+            Maintenance::active_paused_calls()
+                .into_iter()
+                .map(|((pallet_name, call_name), expires_at)| (pallet_name, call_name, expires_at))
+                .collect()
+        }
+
+        fn paused_scopes() -> Vec<(pallet_maintenance_mode_rpc_runtime_api::PauseScope, Option<BlockNumber>)> {
+            Maintenance::active_paused_scopes()
+        }
+    }
+
+    impl pallet_clock_rpc_runtime_api::ClockRuntimeApi<Block, BlockNumber> for Runtime {
+        fn fetch_tasks() -> Vec<pallet_clock_rpc_runtime_api::TaskInfo<BlockNumber>> {
+            Clock::tasks_info()
+        }
+    }
+
+    impl pallet_rewards_rpc_runtime_api::RewardsRuntimeApi<Block, AccountId, Balance> for Runtime {
+        fn accrued_nominator_rewards(nominator: AccountId) -> Vec<(AccountId, Balance)> {
+            Rewards::nominator_accrued_rewards(&nominator)
+        }
+    }
+
     impl pallet_xdns_rpc_runtime_api::XdnsRuntimeApi<Block, AccountId> for Runtime {
         fn fetch_records() -> Vec<GatewayRecord<AccountId>> {
              <XDNS as t3rn_primitives::xdns::Xdns<Runtime, Balance>>::fetch_gateways()
@@ -508,6 +552,16 @@ impl_runtime_apis! {
         fn retreive_treasury_address(treasury_account: t3rn_primitives::TreasuryAccount) -> AccountId {
             Runtime::get_treasury_account(treasury_account)
         }
+
+        fn zip_topology() -> Vec<u8> {
+            XDNS::do_zip_topology().encode()
+        }
+
+        fn diff_topology(topology_encoded: Vec<u8>) -> Option<t3rn_primitives::xdns::XDNSTopologyDiff> {
+            XDNS::decode_topology(None, Some(topology_encoded))
+                .ok()
+                .map(|topology| XDNS::do_diff_topology(&topology))
+        }
     }
 
      impl pallet_portal_rpc_runtime_api::PortalRuntimeApi<Block, AccountId, Balance, Hash> for Runtime {
@@ -779,6 +833,154 @@ impl_runtime_apis! {
         }
     }
 
+    impl fp_rpc::EvmProofRuntimeApi<Block> for Runtime {
+        fn proof_pallet_names() -> (Vec<u8>, Vec<u8>) {
+            use frame_support::traits::PalletInfoAccess;
+
+            (Evm::name().as_bytes().to_vec(), System::name().as_bytes().to_vec())
+        }
+
+        fn mapped_account_id(address: H160) -> Vec<u8> {
+            <<Runtime as pallet_3vm_evm::Config>::AddressMapping as pallet_3vm_evm::AddressMapping<AccountId>>::into_account_id(address).encode()
+        }
+    }
+
+    impl fp_rpc::EvmTracingRuntimeApi<Block> for Runtime {
+        fn trace_transaction(
+            header: &<Block as BlockT>::Header,
+            extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+            transaction_hash: H256,
+            tracer: pallet_3vm_evm_primitives::tracing::TracerKind,
+        ) -> Result<pallet_3vm_evm_primitives::tracing::TraceResult, sp_runtime::DispatchError> {
+            #[cfg(feature = "evm-tracing")]
+            {
+                Executive::initialize_block(header);
+
+                for ext in extrinsics.into_iter() {
+                    let is_traced = matches!(
+                        &ext.0.function,
+                        RuntimeCall::Ethereum(pallet_3vm_ethereum::Call::transact { transaction })
+                            if transaction.hash() == transaction_hash
+                    );
+                    if is_traced {
+                        let (_, mut trace) = pallet_3vm_evm::runner::tracing::trace(tracer, || Executive::apply_extrinsic(ext));
+                        trace.label_precompiles(evm_precompile_util::precompile_label);
+                        return Ok(trace)
+                    }
+                    let _ = Executive::apply_extrinsic(ext);
+                }
+
+                Err(sp_runtime::DispatchError::Other("transaction not found in block"))
+            }
+            #[cfg(not(feature = "evm-tracing"))]
+            {
+                let _ = (header, extrinsics, transaction_hash, tracer);
+                Err(sp_runtime::DispatchError::Other("EVM tracing is not enabled in this runtime"))
+            }
+        }
+
+        fn trace_block(
+            header: &<Block as BlockT>::Header,
+            extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+            tracer: pallet_3vm_evm_primitives::tracing::TracerKind,
+        ) -> Result<Vec<(H256, pallet_3vm_evm_primitives::tracing::TraceResult)>, sp_runtime::DispatchError> {
+            #[cfg(feature = "evm-tracing")]
+            {
+                Executive::initialize_block(header);
+
+                let mut traces = Vec::new();
+                for ext in extrinsics.into_iter() {
+                    let transaction_hash = match &ext.0.function {
+                        RuntimeCall::Ethereum(pallet_3vm_ethereum::Call::transact { transaction }) => Some(transaction.hash()),
+                        _ => None,
+                    };
+                    match transaction_hash {
+                        Some(transaction_hash) => {
+                            let (_, mut trace) = pallet_3vm_evm::runner::tracing::trace(tracer, || Executive::apply_extrinsic(ext));
+                            trace.label_precompiles(evm_precompile_util::precompile_label);
+                            traces.push((transaction_hash, trace));
+                        },
+                        None => {
+                            let _ = Executive::apply_extrinsic(ext);
+                        },
+                    }
+                }
+
+                Ok(traces)
+            }
+            #[cfg(not(feature = "evm-tracing"))]
+            {
+                let _ = (header, extrinsics, tracer);
+                Err(sp_runtime::DispatchError::Other("EVM tracing is not enabled in this runtime"))
+            }
+        }
+
+        fn trace_call(
+            from: H160,
+            to: Option<H160>,
+            data: Vec<u8>,
+            value: U256,
+            gas_limit: U256,
+            max_fee_per_gas: Option<U256>,
+            max_priority_fee_per_gas: Option<U256>,
+            nonce: Option<U256>,
+            tracer: pallet_3vm_evm_primitives::tracing::TracerKind,
+        ) -> Result<pallet_3vm_evm_primitives::tracing::TraceResult, sp_runtime::DispatchError> {
+            #[cfg(feature = "evm-tracing")]
+            {
+                let gas_limit = gas_limit.min(u64::MAX.into()).low_u64();
+                let is_transactional = false;
+                let validate = true;
+                let config = <Runtime as pallet_3vm_evm::Config>::config();
+
+                let (result, mut trace) = pallet_3vm_evm::runner::tracing::trace(tracer, || -> Result<(), sp_runtime::DispatchError> {
+                    match to {
+                        Some(to) => <Runtime as pallet_3vm_evm::Config>::Runner::call(
+                            from,
+                            to,
+                            data,
+                            value,
+                            gas_limit.unique_saturated_into(),
+                            max_fee_per_gas,
+                            max_priority_fee_per_gas,
+                            nonce,
+                            Vec::new(),
+                            is_transactional,
+                            validate,
+                            None,
+                            None,
+                            config,
+                        ).map(|_| ()).map_err(|err| err.error.into()),
+                        None => <Runtime as pallet_3vm_evm::Config>::Runner::create(
+                            from,
+                            data,
+                            value,
+                            gas_limit.unique_saturated_into(),
+                            max_fee_per_gas,
+                            max_priority_fee_per_gas,
+                            nonce,
+                            Vec::new(),
+                            is_transactional,
+                            validate,
+                            None,
+                            None,
+                            config,
+                        ).map(|_| ()).map_err(|err| err.error.into()),
+                    }
+                });
+                result?;
+
+                trace.label_precompiles(evm_precompile_util::precompile_label);
+                Ok(trace)
+            }
+            #[cfg(not(feature = "evm-tracing"))]
+            {
+                let _ = (from, to, data, value, gas_limit, max_fee_per_gas, max_priority_fee_per_gas, nonce, tracer);
+                Err(sp_runtime::DispatchError::Other("EVM tracing is not enabled in this runtime"))
+            }
+        }
+    }
+
     #[cfg(feature = "try-runtime")]
     impl frame_try_runtime::TryRuntime<Block> for Runtime {
         fn on_runtime_upgrade() -> (Weight, Weight) {
@@ -802,6 +1004,7 @@ impl_runtime_apis! {
             use frame_support::traits::StorageInfoTrait;
             use frame_system_benchmarking::Pallet as SystemBench;
             use cumulus_pallet_session_benchmarking::Pallet as SessionBench;
+            use pallet_evm_precompile_3vm_dispatch::benchmarking::Pallet as ThreeVmDispatchBench;
 
             let mut list = Vec::<BenchmarkList>::new();
             list_benchmarks!(list, extra);
@@ -821,6 +1024,9 @@ impl_runtime_apis! {
             use cumulus_pallet_session_benchmarking::Pallet as SessionBench;
             impl cumulus_pallet_session_benchmarking::Config for Runtime {}
 
+            use pallet_evm_precompile_3vm_dispatch::benchmarking::Pallet as ThreeVmDispatchBench;
+            impl pallet_evm_precompile_3vm_dispatch::benchmarking::Config for Runtime {}
+
             let whitelist: Vec<TrackedStorageKey> = vec![
                 // Block Number
                 hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
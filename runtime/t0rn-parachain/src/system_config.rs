@@ -4,7 +4,7 @@ use frame_support::{
     traits::{
         fungibles::{Balanced, Credit},
         ConstU32, ConstU8, Contains, OffchainWorker, OnFinalize, OnIdle, OnInitialize,
-        OnRuntimeUpgrade,
+        OnRuntimeUpgrade, PalletInfoAccess,
     },
     weights::IdentityFee,
 };
@@ -191,6 +191,11 @@ impl Contains<RuntimeCall> for BaseCallFilter {
                 method,
                 pallet_contracts_registry::Call::add_new_contract { .. }
                     | pallet_contracts_registry::Call::purge { .. }
+                    | pallet_contracts_registry::Call::publish_contract { .. }
+                    | pallet_contracts_registry::Call::set_deprecated { .. }
+                    | pallet_contracts_registry::Call::transfer_package_ownership { .. }
+                    | pallet_contracts_registry::Call::set_verifier { .. }
+                    | pallet_contracts_registry::Call::attest_source { .. }
             ),
             RuntimeCall::Circuit(method) => matches!(
                 method,
@@ -304,13 +309,35 @@ impl OffchainWorker<BlockNumber> for MaintenanceHooks {
     }
 }
 
+/// Pallets that must stay callable for the chain to produce blocks and to be recovered,
+/// so that neither maintenance nor pause origins can pause them.
+pub struct UnpausablePallets;
+impl Contains<&'static str> for UnpausablePallets {
+    fn contains(pallet_name: &&'static str) -> bool {
+        [
+            <System as PalletInfoAccess>::name(),
+            <ParachainSystem as PalletInfoAccess>::name(),
+            <Timestamp as PalletInfoAccess>::name(),
+            <Sudo as PalletInfoAccess>::name(),
+            <XcmpQueue as PalletInfoAccess>::name(),
+            <PolkadotXcm as PalletInfoAccess>::name(),
+            <CumulusXcm as PalletInfoAccess>::name(),
+            <DmpQueue as PalletInfoAccess>::name(),
+        ]
+        .contains(pallet_name)
+    }
+}
+
 impl pallet_maintenance_mode::Config for Runtime {
     type MaintenanceCallFilter = MaintenanceFilter;
     type MaintenanceExecutiveHooks = AllPalletsWithSystem;
     type MaintenanceOrigin = EnsureRoot<AccountId>;
     type NormalCallFilter = BaseCallFilter;
     type NormalExecutiveHooks = AllPalletsWithSystem;
+    type PauseOrigin = pallet_maintenance_mode::EnsurePauseGuardian<Runtime>;
+    type RuntimeCall = RuntimeCall;
     type RuntimeEvent = RuntimeEvent;
+    type UnpausablePallets = UnpausablePallets;
 }
 
 #[cfg(test)]
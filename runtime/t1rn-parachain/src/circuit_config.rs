@@ -43,17 +43,21 @@ parameter_types! {
     pub const MinNominatorBond: Balance = 1;
     pub const MinAttesterBond: Balance = 1;
     pub const DefaultCommission: Percent = Percent::from_percent(10);
+    pub const MaxCommissionIncrease: Percent = Percent::from_percent(5);
+    pub const CommissionChangeNoticePeriod: BlockNumber = 7 * 24 * 60 * 60 / 12; // one week assuming 12s block time
     pub const HourlyShufflingFrequency: BlockNumber = 60 * 60 / 12; // (60 * 60) / 12; assuming one distribution per two weeks
 }
 
 impl pallet_attesters::Config for Runtime {
     type ActiveSetSize = ConstU32<32>;
     type BatchingWindow = ConstU32<6>;
+    type CommissionChangeNoticePeriod = CommissionChangeNoticePeriod;
     type CommitteeSize = ConstU32<16>;
     type Currency = Balances;
     type DefaultCommission = DefaultCommission;
     type LightClientAsyncAPI = XDNS;
     type MaxBatchSize = ConstU32<128>;
+    type MaxCommissionIncrease = MaxCommissionIncrease;
     type MinAttesterBond = MinAttesterBond;
     type MinNominatorBond = MinNominatorBond;
     type Portal = Portal;
@@ -65,6 +69,7 @@ impl pallet_attesters::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type ShufflingFrequency = HourlyShufflingFrequency;
     type TreasuryAccounts = Runtime;
+    type WeightInfo = pallet_attesters::weights::SubstrateWeight<Runtime>;
     type Xdns = XDNS;
 }
 
@@ -82,6 +87,8 @@ parameter_types! {
     pub const ExecutorBootstrapRewards: Percent = Percent::from_parts(40); // 40%
     pub const StartingRepatriationPercentage: Percent = Percent::from_parts(10); // 10%
     pub const OneYear: BlockNumber = 2_628_000; // (365.25 * 24 * 60 * 60) / 12; assuming 12s block time
+    pub const ClaimVestingPeriod: BlockNumber = 1_296_000; // (6 * 30 * 24 * 60 * 60) / 12; six months assuming 12s block time
+    pub const ClaimVestingBonus: Percent = Percent::from_percent(5);
     pub const InflationDistributionPeriod: BlockNumber = 100_800; // (14 * 24 * 60 * 60) / 12; assuming one distribution per two weeks
     pub const HourlyInflationDistributionPeriod: BlockNumber = 60 * 60 / 12; // (60 * 60) / 12; assuming 12s block time
     pub const AvailableBootstrapSpenditure: Balance = 1_000_000 * (TRN as Balance); // 1 MLN UNIT
@@ -93,23 +100,30 @@ impl pallet_rewards::Config for Runtime {
     type AttesterInflation = AttesterInflation;
     type Attesters = Attesters;
     type AvailableBootstrapSpenditure = AvailableBootstrapSpenditure;
+    type ClaimVestingBonus = ClaimVestingBonus;
+    type ClaimVestingPeriod = ClaimVestingPeriod;
     type Clock = Clock;
     type CollatorBootstrapRewards = CollatorBootstrapRewards;
     type CollatorInflation = CollatorInflation;
     type Currency = Balances;
     type ExecutorBootstrapRewards = ExecutorBootstrapRewards;
     type ExecutorInflation = ExecutorInflation;
+    type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
     type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
     type InflationDistributionPeriod = HourlyInflationDistributionPeriod;
+    type MaxClaimsPerCall = ConstU32<64>;
+    type MaxVestingSchedules = ConstU32<32>;
     type OneYear = OneYear;
     type RuntimeEvent = RuntimeEvent;
     type StartingRepatriationPercentage = StartingRepatriationPercentage;
     type TotalInflation = TotalInflation;
     type TreasuryAccounts = Runtime;
     type TreasuryInflation = TreasuryInflation;
+    type WeightInfo = pallet_rewards::weights::SubstrateWeight<Self>;
 }
 
 impl pallet_vacuum::Config for Runtime {
+    type AccountManager = AccountManager;
     type AddressMapping = crate::contracts_config::EvmAddressMapping<Runtime>;
     type CircuitSubmitAPI = Circuit;
     type Currency = Balances;
@@ -120,12 +134,23 @@ impl pallet_vacuum::Config for Runtime {
     type Xdns = XDNS;
 }
 
+parameter_types! {
+    pub const GatewayRegistrationDeposit: Balance = 100 * (TRN as Balance);
+    pub const GatewayChangeTimelock: BlockNumber = 600;
+    pub const MaxScheduledGatewayChanges: u32 = 100;
+    pub const MaxPendingGatewayChangesPerRegistrant: u32 = 10;
+}
+
 impl pallet_xdns::Config for Runtime {
     type AssetsOverlay = Runtime;
     type AttestersRead = Attesters;
     type Balances = Balances;
     type CircuitDLQ = Circuit;
     type Currency = Balances;
+    type GatewayChangeTimelock = GatewayChangeTimelock;
+    type GatewayRegistrationDeposit = GatewayRegistrationDeposit;
+    type MaxPendingGatewayChangesPerRegistrant = MaxPendingGatewayChangesPerRegistrant;
+    type MaxScheduledGatewayChanges = MaxScheduledGatewayChanges;
     type Portal = Portal;
     type RuntimeEvent = RuntimeEvent;
     type SelfGatewayId = SelfGatewayId;
@@ -208,9 +233,24 @@ impl PalletAssetsOverlay<Runtime, Balance> for Runtime {
     }
 }
 
+parameter_types! {
+    pub const ContractDepositBase: Balance = 10 * (TRN as Balance);
+    pub const ContractDepositPerByte: Balance = (TRN as Balance) / 1_000;
+    pub const MaxCodeLength: u32 = 512 * 1024;
+    pub const MaxSourceLength: u32 = 512 * 1024;
+    pub const MaxPackageNameLength: u32 = 128;
+    pub const MaxReleasesPerPackage: u32 = 256;
+}
+
 impl pallet_contracts_registry::Config for Runtime {
     type Balances = Balances;
+    type ContractDepositBase = ContractDepositBase;
+    type ContractDepositPerByte = ContractDepositPerByte;
     type Currency = Balances;
+    type MaxCodeLength = MaxCodeLength;
+    type MaxPackageNameLength = MaxPackageNameLength;
+    type MaxReleasesPerPackage = MaxReleasesPerPackage;
+    type MaxSourceLength = MaxSourceLength;
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = pallet_contracts_registry::weights::SubstrateWeight<Runtime>;
 }
@@ -247,6 +287,7 @@ impl pallet_portal::SelectLightClient<Runtime> for SelectLightClientRegistry {
 
 impl pallet_portal::Config for Runtime {
     type Currency = Balances;
+    type GatewayRegistrationOrigin = frame_system::EnsureRoot<AccountId>;
     type RuntimeEvent = RuntimeEvent;
     type SelectLightClient = SelectLightClientRegistry;
     type WeightInfo = pallet_portal::weights::SubstrateWeight<Runtime>;
@@ -266,6 +307,8 @@ impl Convert<AccountId, [u8; 32]> for AccountId32Converter {
 parameter_types! {
     pub const SelfGatewayId: [u8; 4] = [3, 3, 3, 3];
     pub const SelfGatewayIdOptimistic: [u8; 4] = [0, 3, 3, 3];
+    pub const GmpDepositPerByte: Balance = (TRN as Balance) / 1_000;
+    pub const GmpMessageTimeout: BlockNumber = 7 * 24 * 60 * 60 / 12; // one week assuming 12s block time
 }
 
 impl pallet_circuit::Config for Runtime {
@@ -275,7 +318,12 @@ impl pallet_circuit::Config for Runtime {
     type Currency = Balances;
     type DeletionQueueLimit = ConstU32<100u32>;
     type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
+    type GmpCallbacks = ThreeVm;
+    type GmpDepositPerByte = GmpDepositPerByte;
+    type GmpMessageTimeout = GmpMessageTimeout;
+    type PauseScopes = Maintenance;
     type Portal = Portal;
+    type Royalties = ThreeVm;
     type RuntimeEvent = RuntimeEvent;
     type SFXBiddingPeriod = ConstU32<3u32>;
     type SelfAccountId = crate::accounts_config::EscrowAccount;
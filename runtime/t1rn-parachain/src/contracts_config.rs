@@ -57,8 +57,10 @@ impl pallet_3vm::Config for Runtime {
     type ContractsRegistry = ContractsRegistry;
     type Currency = Balances;
     type EscrowAccount = EscrowAccount;
+    type MaxGmpAcknowledgements = ConstU32<64>;
     type OnLocalTrigger = Circuit;
     type Portal = Portal;
+    type Rewards = Rewards;
     type RuntimeEvent = RuntimeEvent;
     type SignalBounceThreshold = ConstU32<2>;
     type VacuumEVMApi = Vacuum;
@@ -68,12 +70,16 @@ parameter_types! {
     pub const T3rnPalletId: PalletId = PalletId(*b"trn/trsy");
     pub TreasuryModuleAccount: AccountId = T3rnPalletId::get().into_account_truncating();
     pub const StorageDepositFee: Balance = MILLIUNIT / 100;
+    pub const AutoMapOnFirstInteraction: bool = false;
+    pub const MaxMigratedAccountsPerBlock: u32 = 256;
 }
 
 impl pallet_3vm_account_mapping::Config for Runtime {
     type AddressMapping = EvmAddressMapping<Runtime>;
+    type AutoMapOnFirstInteraction = AutoMapOnFirstInteraction;
     type ChainId = ChainId;
     type Currency = Balances;
+    type MaxMigratedAccountsPerBlock = MaxMigratedAccountsPerBlock;
     type NetworkTreasuryAccount = TreasuryModuleAccount;
     type RuntimeEvent = RuntimeEvent;
     type StorageDepositFee = StorageDepositFee;
@@ -15,10 +15,10 @@ use circuit_runtime_types::{
     AssetId, EvmAddress, BLOCK_GAS_LIMIT, GAS_LIMIT_POV_SIZE_RATIO, GAS_PRICE, GAS_WEIGHT,
     MILLIUNIT, UNIT, WEIGHT_PER_GAS,
 };
-pub use pallet_3vm_account_mapping::EvmAddressMapping;
+pub use pallet_3vm_account_mapping::{EnsureAddressMapped, EvmAddressMapping};
 use pallet_3vm_contracts::NoopMigration;
 use pallet_3vm_ethereum::PostLogContent;
-use pallet_3vm_evm::{EnsureAddressTruncated, HashedAddressMapping, SubstrateBlockHashMapping};
+use pallet_3vm_evm::{HashedAddressMapping, SubstrateBlockHashMapping};
 use pallet_3vm_evm_primitives::FeeCalculator;
 #[cfg(feature = "std")]
 pub use pallet_3vm_evm_primitives::GenesisAccount as EvmGenesisAccount;
@@ -59,8 +59,10 @@ impl pallet_3vm::Config for Runtime {
     type ContractsRegistry = ContractsRegistry;
     type Currency = Balances;
     type EscrowAccount = EscrowAccount;
+    type MaxGmpAcknowledgements = ConstU32<64>;
     type OnLocalTrigger = Circuit;
     type Portal = Portal;
+    type Rewards = Rewards;
     type RuntimeEvent = RuntimeEvent;
     type SignalBounceThreshold = ConstU32<2>;
     type VacuumEVMApi = Vacuum;
@@ -142,7 +144,7 @@ impl pallet_3vm_evm::Config for Runtime {
     type AddressMapping = EvmAddressMapping<Runtime>;
     type BlockGasLimit = BlockGasLimit;
     type BlockHashMapping = SubstrateBlockHashMapping<Self>;
-    type CallOrigin = EnsureAddressTruncated;
+    type CallOrigin = EnsureAddressMapped<Runtime>;
     type ChainId = ChainId;
     type Currency = Balances;
     // BaseFee pallet may be better from frontier TODO
@@ -160,7 +162,7 @@ impl pallet_3vm_evm::Config for Runtime {
     type Timestamp = Timestamp;
     type WeightInfo = ();
     type WeightPerGas = WeightPerGas;
-    type WithdrawOrigin = EnsureAddressTruncated;
+    type WithdrawOrigin = EnsureAddressMapped<Runtime>;
 }
 
 parameter_types! {
@@ -178,12 +180,16 @@ parameter_types! {
     pub const T3rnPalletId: PalletId = PalletId(*b"trn/trsy");
     pub TreasuryModuleAccount: AccountId = T3rnPalletId::get().into_account_truncating();
     pub const StorageDepositFee: Balance = MILLIUNIT / 100;
+    pub const AutoMapOnFirstInteraction: bool = true;
+    pub const MaxMigratedAccountsPerBlock: u32 = 256;
 }
 
 impl pallet_3vm_account_mapping::Config for Runtime {
     type AddressMapping = EvmAddressMapping<Runtime>;
+    type AutoMapOnFirstInteraction = AutoMapOnFirstInteraction;
     type ChainId = ChainId;
     type Currency = Balances;
+    type MaxMigratedAccountsPerBlock = MaxMigratedAccountsPerBlock;
     type NetworkTreasuryAccount = TreasuryModuleAccount;
     type RuntimeEvent = RuntimeEvent;
     type StorageDepositFee = StorageDepositFee;
@@ -7,13 +7,13 @@ use frame_support::{
     traits::{
         fungibles::{Balanced, Credit},
         ConstU32, ConstU64, ConstU8, Contains, OffchainWorker, OnFinalize, OnIdle, OnInitialize,
-        OnRuntimeUpgrade,
+        OnRuntimeUpgrade, PalletInfoAccess,
     },
     weights::IdentityFee,
 };
 use pallet_asset_tx_payment::HandleCredit;
 use polkadot_runtime_common::SlowAdjustingFeeUpdate;
-use sp_runtime::traits::{BlakeTwo256, ConvertInto, Zero};
+use sp_runtime::traits::{BlakeTwo256, Zero};
 
 // Configure FRAME pallets to include in runtime.
 impl frame_system::Config for Runtime {
@@ -138,7 +138,7 @@ impl HandleCredit<AccountId, Assets> for CreditToBlockAuthor {
 impl pallet_asset_tx_payment::Config for Runtime {
     type Fungibles = Assets;
     type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
-        pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
+        pallet_xdns::fees::XdnsAssetFeeConversion<Runtime>,
         CreditToBlockAuthor,
     >;
     type RuntimeEvent = RuntimeEvent;
@@ -187,6 +187,11 @@ impl Contains<RuntimeCall> for BaseCallFilter {
                 method,
                 pallet_contracts_registry::Call::add_new_contract { .. }
                     | pallet_contracts_registry::Call::purge { .. }
+                    | pallet_contracts_registry::Call::publish_contract { .. }
+                    | pallet_contracts_registry::Call::set_deprecated { .. }
+                    | pallet_contracts_registry::Call::transfer_package_ownership { .. }
+                    | pallet_contracts_registry::Call::set_verifier { .. }
+                    | pallet_contracts_registry::Call::attest_source { .. }
             ),
             RuntimeCall::Circuit(method) => matches!(
                 method,
@@ -308,13 +313,30 @@ impl OffchainWorker<BlockNumber> for MaintenanceHooks {
     }
 }
 
+/// Pallets that must stay callable for the chain to produce blocks and to be recovered,
+/// so that neither maintenance nor pause origins can pause them.
+pub struct UnpausablePallets;
+impl Contains<&'static str> for UnpausablePallets {
+    fn contains(pallet_name: &&'static str) -> bool {
+        [
+            <System as PalletInfoAccess>::name(),
+            <Timestamp as PalletInfoAccess>::name(),
+            <Sudo as PalletInfoAccess>::name(),
+        ]
+        .contains(pallet_name)
+    }
+}
+
 impl pallet_maintenance_mode::Config for Runtime {
     type MaintenanceCallFilter = MaintenanceFilter;
     type MaintenanceExecutiveHooks = AllPalletsWithSystem;
     type MaintenanceOrigin = EnsureRoot<AccountId>;
     type NormalCallFilter = BaseCallFilter;
     type NormalExecutiveHooks = AllPalletsWithSystem;
+    type PauseOrigin = pallet_maintenance_mode::EnsurePauseGuardian<Runtime>;
+    type RuntimeCall = RuntimeCall;
     type RuntimeEvent = RuntimeEvent;
+    type UnpausablePallets = UnpausablePallets;
 }
 
 #[cfg(test)]
@@ -0,0 +1,31 @@
+#!/usr/bin/env bash
+set -e
+
+# Regenerates the weights of the pallets still carrying estimated execution times
+# (xdns, portal, rewards, attesters, the 3VM dispatch precompile) from their benchmarking.rs, on the t0rn runtime.
+# Run on reference hardware; the generated files land in ./target/weights/<pallet>.rs and are
+# to be merged into pallets/<pallet>/src/weights.rs, keeping the entries without a benchmark.
+#
+# Usage: ./scripts/benchmark_weights.sh [pallet_xdns pallet_portal ...]
+
+DIR=$(git rev-parse --show-toplevel)
+PALLETS=${*:-pallet_xdns pallet_portal pallet_rewards pallet_attesters pallet_evm_precompile_3vm_dispatch}
+OUTPUT_DIR=$DIR/target/weights
+
+cd "$DIR"
+cargo build --release -p t0rn-parachain-collator --features runtime-benchmarks
+mkdir -p "$OUTPUT_DIR"
+
+for pallet in $PALLETS; do
+    echo "Benchmark: ${pallet}"
+    ./target/release/t0rn-collator benchmark pallet \
+        --chain dev \
+        --execution wasm \
+        --wasm-execution compiled \
+        --pallet "${pallet}" \
+        --extrinsic '*' \
+        --steps 50 \
+        --repeat 20 \
+        --template=./benchmarking/frame-weight-template.hbs \
+        --output "${OUTPUT_DIR}/${pallet#pallet_}.rs"
+done
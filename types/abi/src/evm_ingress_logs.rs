@@ -91,6 +91,35 @@ impl Decode for RemoteEVMCommitLog {
     }
 }
 
+// event GMPAcknowledged(bytes32 indexed messageId, bool indexed success, bytes32 returnDataHash);
+pub fn get_remote_gmp_acknowledged_abi_descriptor() -> Vec<u8> {
+    b"GMPAcknowledged:Log(messageId+:H256,success+:Bool,returnDataHash:H256)".to_vec()
+}
+
+pub struct RemoteGmpAcknowledgedLog {
+    pub message_id: H256,
+    pub success: bool,
+    pub return_data_hash: H256,
+}
+
+// Implement custom Scale decoding for RemoteGmpAcknowledgedLog
+impl Decode for RemoteGmpAcknowledgedLog {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        // Skip first byte
+        input.read_byte()?;
+
+        let message_id = H256::decode(input)?;
+        let success = bool::decode(input)?;
+        let return_data_hash = H256::decode(input)?;
+
+        Ok(RemoteGmpAcknowledgedLog {
+            message_id,
+            success,
+            return_data_hash,
+        })
+    }
+}
+
 // emit OrderCreated(id, destination, asset, targetAccount, amount, rewardAsset, insurance, maxReward, nonce);
 // event RemoteOrderIndexedCreated(bytes32 indexed id, uint32 indexed nonce, address indexed sender, bytes input);
 // where input = abi.encode(destination, asset, targetAccount, amount, rewardAsset, insurance, maxReward)